@@ -24,6 +24,8 @@ use jni::objects::JObject;
 use jni::objects::JString;
 use jni::objects::JValue;
 use jni::objects::JValueOwned;
+use jni::sys::jboolean;
+use jni::sys::jint;
 use jni::sys::jlong;
 use jni::sys::jobject;
 use jni::sys::jsize;
@@ -34,11 +36,13 @@ use opendal::Operator;
 use opendal::Scheme;
 
 use crate::convert::jmap_to_hashmap;
+use crate::convert::jstring_to_optional_string;
 use crate::convert::jstring_to_string;
 use crate::executor::executor_or_default;
 use crate::executor::get_current_env;
 use crate::executor::Executor;
 use crate::make_entry;
+use crate::make_list_page;
 use crate::make_metadata;
 use crate::make_operator_info;
 use crate::make_presigned_request;
@@ -564,6 +568,65 @@ async fn do_list<'local>(op: &mut Operator, path: String) -> Result<JObject<'loc
     Ok(jarray.into())
 }
 
+/// # Safety
+///
+/// This function should not be called before the Operator is ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_AsyncOperator_listPage(
+    mut env: JNIEnv,
+    _: JClass,
+    op: *mut Operator,
+    executor: *const Executor,
+    path: JString,
+    limit: jint,
+    token: JString,
+    recursive: jboolean,
+) -> jlong {
+    intern_list_page(&mut env, op, executor, path, limit, token, recursive).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_list_page(
+    env: &mut JNIEnv,
+    op: *mut Operator,
+    executor: *const Executor,
+    path: JString,
+    limit: jint,
+    token: JString,
+    recursive: jboolean,
+) -> Result<jlong> {
+    let op = unsafe { &mut *op };
+    let id = request_id(env)?;
+
+    let path = jstring_to_string(env, &path)?;
+    let token = jstring_to_optional_string(env, &token)?;
+    let limit = limit.max(0) as usize;
+
+    executor_or_default(env, executor)?.spawn(async move {
+        let result = do_list_page(op, path, limit, token, recursive != 0).await;
+        complete_future(id, result.map(JValueOwned::Object))
+    });
+
+    Ok(id)
+}
+
+async fn do_list_page<'local>(
+    op: &mut Operator,
+    path: String,
+    limit: usize,
+    token: Option<String>,
+    recursive: bool,
+) -> Result<JObject<'local>> {
+    let page = op
+        .list_page(&path, limit, token.as_deref(), recursive)
+        .await?;
+
+    let mut env = unsafe { get_current_env() };
+    make_list_page(&mut env, page)
+}
+
 /// # Safety
 ///
 /// This function should not be called before the Operator is ready.
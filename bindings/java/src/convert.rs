@@ -77,3 +77,14 @@ pub(crate) fn jstring_to_string(env: &mut JNIEnv, s: &JString) -> crate::Result<
     let res = unsafe { env.get_string_unchecked(s)? };
     Ok(res.into())
 }
+
+/// Same as [`jstring_to_string`], but returns `None` if the given string is `null`.
+pub(crate) fn jstring_to_optional_string(
+    env: &mut JNIEnv,
+    s: &JString,
+) -> crate::Result<Option<String>> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    jstring_to_string(env, s).map(Some)
+}
@@ -58,6 +58,10 @@ impl Error {
             ErrorKind::IsSameFile => "IsSameFile",
             ErrorKind::ConditionNotMatch => "ConditionNotMatch",
             ErrorKind::RangeNotSatisfied => "RangeNotSatisfied",
+            ErrorKind::QuotaExceeded => "QuotaExceeded",
+            ErrorKind::InvalidInput => "InvalidInput",
+            ErrorKind::CircuitOpen => "CircuitOpen",
+            ErrorKind::DataCorrupted => "DataCorrupted",
             _ => "Unexpected",
         })?;
         let message = env.new_string(format!("{:?}", self.inner))?;
@@ -22,11 +22,13 @@ use jni::objects::JValue;
 use jni::sys::jboolean;
 use jni::sys::jint;
 use jni::sys::jlong;
+use jni::sys::jsize;
 use jni::JNIEnv;
 use opendal::raw::PresignedRequest;
 use opendal::Capability;
 use opendal::Entry;
 use opendal::EntryMode;
+use opendal::ListPage;
 use opendal::Metadata;
 use opendal::OperatorInfo;
 
@@ -191,6 +193,27 @@ fn make_metadata<'a>(env: &mut JNIEnv<'a>, metadata: Metadata) -> Result<JObject
     Ok(result)
 }
 
+fn make_list_page<'a>(env: &mut JNIEnv<'a>, page: ListPage) -> Result<JObject<'a>> {
+    let (entries, next_token) = page.into_parts();
+    let next_token = convert::string_to_jstring(env, next_token.as_deref())?;
+
+    let jarray = env.new_object_array(
+        entries.len() as jsize,
+        "org/apache/opendal/Entry",
+        JObject::null(),
+    )?;
+    for (idx, entry) in entries.into_iter().enumerate() {
+        let entry = make_entry(env, entry)?;
+        env.set_object_array_element(&jarray, idx as jsize, entry)?;
+    }
+
+    Ok(env.new_object(
+        "org/apache/opendal/ListPage",
+        "([Lorg/apache/opendal/Entry;Ljava/lang/String;)V",
+        &[JValue::Object(&jarray), JValue::Object(&next_token)],
+    )?)
+}
+
 fn make_entry<'a>(env: &mut JNIEnv<'a>, entry: Entry) -> Result<JObject<'a>> {
     let path = env.new_string(entry.path())?;
     let metadata = make_metadata(env, entry.metadata().to_owned())?;
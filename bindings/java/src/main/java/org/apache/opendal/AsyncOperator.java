@@ -243,6 +243,26 @@ public class AsyncOperator extends NativeObject {
         return Objects.requireNonNull(result).thenApplyAsync(Arrays::asList);
     }
 
+    /**
+     * List a page of at most {@code limit} entries under the given path.
+     *
+     * <p>Pass {@link ListPage#getNextToken()} of the returned page as {@code token} to fetch the next page.
+     *
+     * <p>The token is the path of the last entry in the page instead of the native continuation token of
+     * services, so this requires {@code listWithStartAfter} capability, otherwise an
+     * {@link OpenDALException} with {@code Unsupported} code will be thrown.
+     *
+     * @param path the path to list.
+     * @param limit the max number of entries in the page.
+     * @param token the continuation token, or {@code null} to start from the beginning.
+     * @param recursive whether to list recursively.
+     * @return the future of the listed page.
+     */
+    public CompletableFuture<ListPage> listPage(String path, int limit, String token, boolean recursive) {
+        final long requestId = listPage(nativeHandle, executorHandle, path, limit, token, recursive);
+        return AsyncRegistry.take(requestId);
+    }
+
     @Override
     protected native void disposeInternal(long handle);
 
@@ -279,4 +299,7 @@ public class AsyncOperator extends NativeObject {
     private static native long removeAll(long nativeHandle, long executorHandle, String path);
 
     private static native long list(long nativeHandle, long executorHandle, String path);
+
+    private static native long listPage(
+            long nativeHandle, long executorHandle, String path, int limit, String token, boolean recursive);
 }
@@ -0,0 +1,44 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import java.util.Arrays;
+import java.util.List;
+import lombok.Data;
+
+/**
+ * A page of entries returned by {@code listPage}.
+ */
+@Data
+public class ListPage {
+    /**
+     * Entries in this page.
+     */
+    public final List<Entry> entries;
+    /**
+     * Token to fetch the next page, {@code null} if this is the last page.
+     */
+    public final String nextToken;
+
+    public ListPage(Entry[] entries, String nextToken) {
+        this.entries = Arrays.asList(entries);
+        this.nextToken = nextToken;
+    }
+}
@@ -72,5 +72,9 @@ public class OpenDALException extends RuntimeException {
         IsSameFile,
         ConditionNotMatch,
         RangeNotSatisfied,
+        QuotaExceeded,
+        InvalidInput,
+        CircuitOpen,
+        DataCorrupted,
     }
 }
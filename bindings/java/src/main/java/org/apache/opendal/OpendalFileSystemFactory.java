@@ -0,0 +1,130 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import java.net.URI;
+import java.net.URISyntaxException;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+
+/**
+ * OpendalFileSystemFactory constructs operators from Hadoop-style URIs like {@code opendal+s3://bucket/path}.
+ *
+ * <p>The URI is mapped to the operator config as follows:
+ *
+ * <ul>
+ *     <li>The part after {@code opendal+} in URI scheme is the service scheme, like {@code s3}.</li>
+ *     <li>The authority is set to the config key registered for the service, like {@code bucket} for {@code s3}.</li>
+ *     <li>The path is set to {@code root}.</li>
+ * </ul>
+ *
+ * <p>Values parsed from the URI take precedence over the given properties.
+ */
+public final class OpendalFileSystemFactory {
+    /**
+     * The prefix of URI schemes handled by this factory.
+     */
+    public static final String URI_SCHEME_PREFIX = "opendal+";
+
+    private static final Map<String, String> AUTHORITY_KEYS = new ConcurrentHashMap<>();
+
+    static {
+        for (String scheme : new String[] {"s3", "gcs", "oss", "obs", "cos", "b2", "upyun"}) {
+            AUTHORITY_KEYS.put(scheme, "bucket");
+        }
+        AUTHORITY_KEYS.put("azblob", "container");
+        AUTHORITY_KEYS.put("azdls", "filesystem");
+        AUTHORITY_KEYS.put("azfile", "share_name");
+    }
+
+    private OpendalFileSystemFactory() {}
+
+    /**
+     * Register the config key that the URI authority maps to for the given service.
+     *
+     * @param scheme the service scheme, like {@code s3}.
+     * @param key    the config key, like {@code bucket}.
+     */
+    public static void registerAuthorityKey(String scheme, String key) {
+        AUTHORITY_KEYS.put(scheme, key);
+    }
+
+    /**
+     * Construct a blocking operator from the given URI and properties.
+     *
+     * @param uri   the URI like {@code opendal+s3://bucket/path}.
+     * @param props the properties to construct the underneath operator.
+     * @return the constructed operator.
+     */
+    public static Operator fromUri(String uri, Map<String, String> props) {
+        final Map<String, String> config = new HashMap<>(props);
+        final String scheme = parseUri(uri, config);
+        return Operator.of(scheme, config);
+    }
+
+    /**
+     * Construct an async operator from the given URI and properties.
+     *
+     * @param uri   the URI like {@code opendal+s3://bucket/path}.
+     * @param props the properties to construct the underneath operator.
+     * @return the constructed operator.
+     * @see #fromUri(String, Map)
+     */
+    public static AsyncOperator asyncFromUri(String uri, Map<String, String> props) {
+        final Map<String, String> config = new HashMap<>(props);
+        final String scheme = parseUri(uri, config);
+        return AsyncOperator.of(scheme, config);
+    }
+
+    /**
+     * Parse the URI into the config and return the service scheme.
+     */
+    private static String parseUri(String uri, Map<String, String> config) {
+        final URI parsed;
+        try {
+            parsed = new URI(uri);
+        } catch (URISyntaxException e) {
+            throw new IllegalArgumentException("invalid uri: " + uri, e);
+        }
+
+        final String uriScheme = parsed.getScheme();
+        if (uriScheme == null || !uriScheme.startsWith(URI_SCHEME_PREFIX)) {
+            throw new IllegalArgumentException("uri scheme must start with " + URI_SCHEME_PREFIX + ": " + uri);
+        }
+        final String scheme = uriScheme.substring(URI_SCHEME_PREFIX.length());
+
+        final String authority = parsed.getAuthority();
+        if (authority != null && !authority.isEmpty()) {
+            final String key = AUTHORITY_KEYS.get(scheme);
+            if (key == null) {
+                throw new IllegalArgumentException("service " + scheme + " doesn't accept uri authority: " + uri);
+            }
+            config.put(key, authority);
+        }
+
+        final String path = parsed.getPath();
+        if (path != null && !path.isEmpty()) {
+            config.put("root", path);
+        }
+
+        return scheme;
+    }
+}
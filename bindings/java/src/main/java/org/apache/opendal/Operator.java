@@ -108,6 +108,25 @@ public class Operator extends NativeObject {
         return Arrays.asList(list(nativeHandle, path));
     }
 
+    /**
+     * List a page of at most {@code limit} entries under the given path.
+     *
+     * <p>Pass {@link ListPage#getNextToken()} of the returned page as {@code token} to fetch the next page.
+     *
+     * <p>The token is the path of the last entry in the page instead of the native continuation token of
+     * services, so this requires {@code listWithStartAfter} capability, otherwise an
+     * {@link OpenDALException} with {@code Unsupported} code will be thrown.
+     *
+     * @param path the path to list.
+     * @param limit the max number of entries in the page.
+     * @param token the continuation token, or {@code null} to start from the beginning.
+     * @param recursive whether to list recursively.
+     * @return the listed page.
+     */
+    public ListPage listPage(String path, int limit, String token, boolean recursive) {
+        return listPage(nativeHandle, path, limit, token, recursive);
+    }
+
     @Override
     protected native void disposeInternal(long handle);
 
@@ -130,4 +149,6 @@ public class Operator extends NativeObject {
     private static native void removeAll(long op, String path);
 
     private static native Entry[] list(long op, String path);
+
+    private static native ListPage listPage(long op, String path, int limit, String token, boolean recursive);
 }
@@ -0,0 +1,358 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal.hadoop;
+
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.net.URI;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+import org.apache.hadoop.conf.Configuration;
+import org.apache.hadoop.fs.FSDataInputStream;
+import org.apache.hadoop.fs.FSDataOutputStream;
+import org.apache.hadoop.fs.FileAlreadyExistsException;
+import org.apache.hadoop.fs.FileStatus;
+import org.apache.hadoop.fs.FileSystem;
+import org.apache.hadoop.fs.Path;
+import org.apache.hadoop.fs.PathIsNotEmptyDirectoryException;
+import org.apache.hadoop.fs.permission.FsPermission;
+import org.apache.hadoop.io.IOUtils;
+import org.apache.hadoop.util.Progressable;
+import org.apache.opendal.Entry;
+import org.apache.opendal.Metadata;
+import org.apache.opendal.OpenDALException;
+import org.apache.opendal.OpendalFileSystemFactory;
+import org.apache.opendal.Operator;
+
+/**
+ * OpendalFileSystem is a Hadoop {@link FileSystem} backed by an OpenDAL operator, so that jobs can
+ * switch to OpenDAL-backed storage via configuration only.
+ *
+ * <p>The file system handles URIs like {@code opendal+s3://bucket/path}, register it for every scheme in use:
+ *
+ * <pre>{@code
+ * fs.opendal+s3.impl=org.apache.opendal.hadoop.OpendalFileSystem
+ * fs.opendal.region=us-east-1
+ * }</pre>
+ *
+ * <p>The URI authority is mapped as {@link OpendalFileSystemFactory} does. All configurations with the
+ * {@code fs.opendal.} prefix are passed to the operator with the prefix stripped, like {@code region} above.
+ *
+ * <p>Limitations:
+ *
+ * <ul>
+ *     <li>{@code append} is not supported.</li>
+ *     <li>Only files can be renamed, and the rename is not atomic on services without native rename.</li>
+ *     <li>Permissions, owners and replications are ignored.</li>
+ * </ul>
+ */
+public class OpendalFileSystem extends FileSystem {
+    /**
+     * The prefix of Hadoop configurations passed to the operator.
+     */
+    public static final String CONFIG_PREFIX = "fs.opendal.";
+
+    private URI uri;
+    private Path workingDir;
+    private Operator operator;
+
+    @Override
+    public void initialize(URI name, Configuration conf) throws IOException {
+        super.initialize(name, conf);
+        setConf(conf);
+
+        final String scheme = name.getScheme();
+        if (scheme == null || !scheme.startsWith(OpendalFileSystemFactory.URI_SCHEME_PREFIX)) {
+            throw new IOException("uri scheme must start with " + OpendalFileSystemFactory.URI_SCHEME_PREFIX + ": "
+                    + name);
+        }
+        final String authority = name.getAuthority();
+        final Map<String, String> props = conf.getPropsWithPrefix(CONFIG_PREFIX);
+
+        try {
+            if (authority == null || authority.isEmpty()) {
+                this.uri = URI.create(scheme + ":///");
+                this.operator = Operator.of(scheme.substring(OpendalFileSystemFactory.URI_SCHEME_PREFIX.length()), props);
+            } else {
+                this.uri = URI.create(scheme + "://" + authority);
+                this.operator = OpendalFileSystemFactory.fromUri(uri.toString(), props);
+            }
+        } catch (OpenDALException | IllegalArgumentException e) {
+            throw new IOException("failed to initialize opendal file system for " + name, e);
+        }
+        this.workingDir = new Path("/").makeQualified(uri, null);
+    }
+
+    @Override
+    public String getScheme() {
+        return uri.getScheme();
+    }
+
+    @Override
+    public URI getUri() {
+        return uri;
+    }
+
+    @Override
+    public FSDataInputStream open(Path path, int bufferSize) throws IOException {
+        final FileStatus status = getFileStatus(path);
+        if (status.isDirectory()) {
+            throw new FileNotFoundException("cannot open directory " + path);
+        }
+        final String key = toKey(path);
+        return new FSDataInputStream(new OpendalInputStream(operator, key, status.getLen()));
+    }
+
+    @Override
+    public FSDataOutputStream create(
+            Path path,
+            FsPermission permission,
+            boolean overwrite,
+            int bufferSize,
+            short replication,
+            long blockSize,
+            Progressable progress)
+            throws IOException {
+        final FileStatus status = stat(path);
+        if (status != null) {
+            if (status.isDirectory()) {
+                throw new FileAlreadyExistsException(path + " is a directory");
+            }
+            if (!overwrite) {
+                throw new FileAlreadyExistsException(path + " already exists");
+            }
+        }
+        try {
+            return new FSDataOutputStream(operator.createOutputStream(toKey(path)), statistics);
+        } catch (OpenDALException e) {
+            throw toIOException(path, e);
+        }
+    }
+
+    @Override
+    public FSDataOutputStream append(Path path, int bufferSize, Progressable progress) throws IOException {
+        throw new UnsupportedOperationException("append is not supported by " + getClass().getSimpleName());
+    }
+
+    @Override
+    public boolean rename(Path src, Path dst) throws IOException {
+        final FileStatus srcStatus = stat(src);
+        if (srcStatus == null) {
+            return false;
+        }
+        if (srcStatus.isDirectory()) {
+            throw new IOException("rename directory " + src + " is not supported");
+        }
+
+        final FileStatus dstStatus = stat(dst);
+        if (dstStatus != null && dstStatus.isDirectory()) {
+            dst = new Path(dst, src.getName());
+        } else if (dstStatus != null) {
+            return false;
+        }
+
+        try {
+            if (operator.info.fullCapability.rename) {
+                operator.rename(toKey(src), toKey(dst));
+                return true;
+            }
+            if (operator.info.fullCapability.copy) {
+                operator.copy(toKey(src), toKey(dst));
+            } else {
+                try (InputStream in = operator.createInputStream(toKey(src));
+                        OutputStream out = operator.createOutputStream(toKey(dst))) {
+                    IOUtils.copyBytes(in, out, getConf(), false);
+                }
+            }
+            operator.delete(toKey(src));
+        } catch (OpenDALException e) {
+            throw toIOException(src, e);
+        }
+        return true;
+    }
+
+    @Override
+    public boolean delete(Path path, boolean recursive) throws IOException {
+        final FileStatus status = stat(path);
+        if (status == null) {
+            return false;
+        }
+
+        try {
+            if (status.isFile()) {
+                operator.delete(toKey(path));
+                return true;
+            }
+
+            final String dir = toDirKey(path);
+            if (recursive) {
+                operator.removeAll(dir);
+            } else if (listStatus(path).length > 0) {
+                throw new PathIsNotEmptyDirectoryException(path.toString());
+            } else if (!dir.equals("/")) {
+                operator.delete(dir);
+            }
+        } catch (OpenDALException e) {
+            throw toIOException(path, e);
+        }
+        return true;
+    }
+
+    @Override
+    public FileStatus[] listStatus(Path path) throws IOException {
+        final FileStatus status = getFileStatus(path);
+        if (status.isFile()) {
+            return new FileStatus[] {status};
+        }
+
+        final String dir = toDirKey(path);
+        final List<FileStatus> result = new ArrayList<>();
+        try {
+            for (Entry entry : operator.list(dir)) {
+                if (entry.path.equals(dir)) {
+                    continue;
+                }
+                final Path child = makeQualified(new Path("/" + entry.path));
+                if (entry.metadata.isDir()) {
+                    result.add(toFileStatus(child, entry.metadata));
+                } else {
+                    // Metadata of listed entries may be incomplete, stat for the length.
+                    result.add(getFileStatus(child));
+                }
+            }
+        } catch (OpenDALException e) {
+            throw toIOException(path, e);
+        }
+        return result.toArray(new FileStatus[0]);
+    }
+
+    @Override
+    public void setWorkingDirectory(Path path) {
+        this.workingDir = makeQualified(path);
+    }
+
+    @Override
+    public Path getWorkingDirectory() {
+        return workingDir;
+    }
+
+    @Override
+    public boolean mkdirs(Path path, FsPermission permission) throws IOException {
+        final FileStatus status = stat(path);
+        if (status != null) {
+            if (status.isFile()) {
+                throw new FileAlreadyExistsException(path + " is a file");
+            }
+            return true;
+        }
+
+        try {
+            operator.createDir(toDirKey(path));
+        } catch (OpenDALException e) {
+            throw toIOException(path, e);
+        }
+        return true;
+    }
+
+    @Override
+    public FileStatus getFileStatus(Path path) throws IOException {
+        final FileStatus status = stat(path);
+        if (status == null) {
+            throw new FileNotFoundException("no such file or directory: " + path);
+        }
+        return status;
+    }
+
+    @Override
+    public void close() throws IOException {
+        try {
+            super.close();
+        } finally {
+            if (operator != null) {
+                operator.close();
+            }
+        }
+    }
+
+    /**
+     * Stat the file or directory at the given path, returns {@code null} if it doesn't exist.
+     */
+    private FileStatus stat(Path path) throws IOException {
+        final Path qualified = makeQualified(path);
+        final String key = toKey(qualified);
+        if (key.isEmpty()) {
+            return new FileStatus(0, true, 1, 0, 0, qualified);
+        }
+
+        try {
+            return toFileStatus(qualified, operator.stat(key));
+        } catch (OpenDALException e) {
+            if (e.getCode() != OpenDALException.Code.NotFound) {
+                throw toIOException(path, e);
+            }
+        }
+        try {
+            return toFileStatus(qualified, operator.stat(key + "/"));
+        } catch (OpenDALException e) {
+            if (e.getCode() != OpenDALException.Code.NotFound) {
+                throw toIOException(path, e);
+            }
+        }
+        return null;
+    }
+
+    private FileStatus toFileStatus(Path path, Metadata metadata) {
+        final long modified = metadata.lastModified == null ? 0 : metadata.lastModified.toEpochMilli();
+        final long length = metadata.isDir() ? 0 : metadata.contentLength;
+        return new FileStatus(length, metadata.isDir(), 1, getDefaultBlockSize(path), modified, path);
+    }
+
+    /**
+     * Convert the path into the key of the operator, which is relative to the root without leading slash.
+     */
+    private String toKey(Path path) {
+        final String p = makeQualified(path).toUri().getPath();
+        return p.startsWith("/") ? p.substring(1) : p;
+    }
+
+    /**
+     * Same as {@link #toKey(Path)}, but ends with slash to represent a directory.
+     */
+    private String toDirKey(Path path) {
+        final String key = toKey(path);
+        return key.isEmpty() ? "/" : key + "/";
+    }
+
+    private static IOException toIOException(Path path, OpenDALException e) {
+        switch (e.getCode()) {
+            case NotFound:
+                final FileNotFoundException fnf = new FileNotFoundException(path + ": " + e.getMessage());
+                fnf.initCause(e);
+                return fnf;
+            case AlreadyExists:
+                return new FileAlreadyExistsException(path + ": " + e.getMessage());
+            default:
+                return new IOException(path + ": " + e.getMessage(), e);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal.hadoop;
+
+import java.io.EOFException;
+import java.io.IOException;
+import java.io.InputStream;
+import org.apache.hadoop.fs.FSInputStream;
+import org.apache.opendal.Operator;
+
+/**
+ * OpendalInputStream is a seekable input stream of the object at the given path.
+ *
+ * <p>Seeking forward skips the bytes in between, seeking backward reopens the object.
+ */
+class OpendalInputStream extends FSInputStream {
+    private final Operator operator;
+    private final String path;
+    private final long length;
+
+    private InputStream in;
+    private long pos = 0;
+
+    OpendalInputStream(Operator operator, String path, long length) {
+        this.operator = operator;
+        this.path = path;
+        this.length = length;
+        this.in = operator.createInputStream(path);
+    }
+
+    @Override
+    public synchronized void seek(long target) throws IOException {
+        if (target < 0) {
+            throw new EOFException("cannot seek to negative offset " + target);
+        }
+        if (target > length) {
+            throw new EOFException("cannot seek to " + target + " after the end of " + path);
+        }
+        if (target < pos) {
+            in.close();
+            in = operator.createInputStream(path);
+            pos = 0;
+        }
+        while (pos < target) {
+            final long skipped = in.skip(target - pos);
+            if (skipped <= 0) {
+                throw new EOFException("cannot seek to " + target + " of " + path);
+            }
+            pos += skipped;
+        }
+    }
+
+    @Override
+    public synchronized long getPos() {
+        return pos;
+    }
+
+    @Override
+    public boolean seekToNewSource(long targetPos) {
+        return false;
+    }
+
+    @Override
+    public synchronized int read() throws IOException {
+        final int b = in.read();
+        if (b >= 0) {
+            pos++;
+        }
+        return b;
+    }
+
+    @Override
+    public synchronized int available() {
+        return (int) Math.min(Integer.MAX_VALUE, length - pos);
+    }
+
+    @Override
+    public synchronized void close() throws IOException {
+        in.close();
+    }
+}
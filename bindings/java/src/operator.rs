@@ -19,7 +19,9 @@ use jni::objects::JByteArray;
 use jni::objects::JClass;
 use jni::objects::JObject;
 use jni::objects::JString;
+use jni::sys::jboolean;
 use jni::sys::jbyteArray;
+use jni::sys::jint;
 use jni::sys::jlong;
 use jni::sys::jobject;
 use jni::sys::jobjectArray;
@@ -27,8 +29,10 @@ use jni::sys::jsize;
 use jni::JNIEnv;
 use opendal::BlockingOperator;
 
+use crate::convert::jstring_to_optional_string;
 use crate::convert::jstring_to_string;
 use crate::make_entry;
+use crate::make_list_page;
 use crate::make_metadata;
 use crate::Result;
 
@@ -279,3 +283,39 @@ fn intern_list(env: &mut JNIEnv, op: &mut BlockingOperator, path: JString) -> Re
 
     Ok(jarray.into_raw())
 }
+
+/// # Safety
+///
+/// This function should not be called before the Operator is ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_listPage(
+    mut env: JNIEnv,
+    _: JClass,
+    op: *mut BlockingOperator,
+    path: JString,
+    limit: jint,
+    token: JString,
+    recursive: jboolean,
+) -> jobject {
+    intern_list_page(&mut env, &mut *op, path, limit, token, recursive).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        JObject::default().into_raw()
+    })
+}
+
+fn intern_list_page(
+    env: &mut JNIEnv,
+    op: &mut BlockingOperator,
+    path: JString,
+    limit: jint,
+    token: JString,
+    recursive: jboolean,
+) -> Result<jobject> {
+    let path = jstring_to_string(env, &path)?;
+    let token = jstring_to_optional_string(env, &token)?;
+    let limit = limit.max(0) as usize;
+
+    let page = op.list_page(&path, limit, token.as_deref(), recursive != 0)?;
+
+    Ok(make_list_page(env, page)?.into_raw())
+}
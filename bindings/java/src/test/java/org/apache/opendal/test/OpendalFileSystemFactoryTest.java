@@ -0,0 +1,58 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal.test;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.Collections;
+import org.apache.opendal.AsyncOperator;
+import org.apache.opendal.OpendalFileSystemFactory;
+import org.apache.opendal.Operator;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.io.TempDir;
+
+public class OpendalFileSystemFactoryTest {
+    @TempDir
+    private static Path tempDir;
+
+    @Test
+    public void testFromUri() throws Exception {
+        final String uri = "opendal+fs://" + tempDir.toUri().getPath();
+        try (final Operator op = OpendalFileSystemFactory.fromUri(uri, Collections.emptyMap())) {
+            assertThat(op.info.scheme).isEqualTo("fs");
+            op.write("hello.txt", "hello");
+        }
+        assertThat(Files.readAllLines(tempDir.resolve("hello.txt"))).containsExactly("hello");
+
+        try (final AsyncOperator op = OpendalFileSystemFactory.asyncFromUri(uri, Collections.emptyMap())) {
+            assertThat(op.read("hello.txt").join()).isEqualTo("hello".getBytes());
+        }
+    }
+
+    @Test
+    public void testFromInvalidUri() {
+        assertThatThrownBy(() -> OpendalFileSystemFactory.fromUri("s3://bucket", Collections.emptyMap()))
+                .isInstanceOf(IllegalArgumentException.class);
+        assertThatThrownBy(() -> OpendalFileSystemFactory.fromUri("opendal+memory://host/", Collections.emptyMap()))
+                .isInstanceOf(IllegalArgumentException.class);
+    }
+}
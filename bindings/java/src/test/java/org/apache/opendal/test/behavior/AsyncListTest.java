@@ -30,6 +30,7 @@ import java.util.UUID;
 import java.util.stream.Collectors;
 import org.apache.opendal.Capability;
 import org.apache.opendal.Entry;
+import org.apache.opendal.ListPage;
 import org.apache.opendal.Metadata;
 import org.apache.opendal.OpenDALException;
 import org.apache.opendal.test.condition.OpenDALExceptionCondition;
@@ -99,6 +100,39 @@ class AsyncListTest extends BehaviorTestBase {
         asyncOp().removeAll(parentPath).join();
     }
 
+    /**
+     * List page by page should return all entries exactly once.
+     */
+    @Test
+    public void testListPage() {
+        assumeTrue(asyncOp().info.fullCapability.listWithStartAfter);
+
+        final String parentPath = String.format("%s/", UUID.randomUUID());
+        asyncOp().createDir(parentPath).join();
+        final List<String> expected = new ArrayList<>();
+        for (int i = 0; i < 5; i++) {
+            expected.add(String.format("%sfile-%d", parentPath, i));
+        }
+        for (String path : expected) {
+            asyncOp().write(path, parentPath).join();
+        }
+
+        final List<String> actual = new ArrayList<>();
+        String token = null;
+        do {
+            final ListPage page = asyncOp().listPage(parentPath, 2, token, false).join();
+            assertThat(page.getEntries().size()).isLessThanOrEqualTo(2);
+            page.getEntries().forEach(entry -> actual.add(entry.getPath()));
+            token = page.getNextToken();
+        } while (token != null);
+
+        expected.add(parentPath);
+        Collections.sort(expected);
+        Collections.sort(actual);
+        assertThat(actual).isEqualTo(expected);
+        asyncOp().removeAll(parentPath).join();
+    }
+
     /**
      * List empty dir should return nothing.
      */
@@ -23,10 +23,13 @@ import static org.assertj.core.api.Assertions.assertThat;
 import static org.assertj.core.api.Assertions.assertThatThrownBy;
 import static org.junit.jupiter.api.Assertions.assertTrue;
 import static org.junit.jupiter.api.Assumptions.assumeTrue;
+import java.util.ArrayList;
+import java.util.Collections;
 import java.util.List;
 import java.util.UUID;
 import org.apache.opendal.Capability;
 import org.apache.opendal.Entry;
+import org.apache.opendal.ListPage;
 import org.apache.opendal.Metadata;
 import org.apache.opendal.OpenDALException;
 import org.apache.opendal.test.condition.OpenDALExceptionCondition;
@@ -79,6 +82,39 @@ public class BlockingListTest extends BehaviorTestBase {
         assertTrue(list.isEmpty());
     }
 
+    /**
+     * List page by page should return all entries exactly once.
+     */
+    @Test
+    public void testBlockingListPage() {
+        assumeTrue(op().info.fullCapability.listWithStartAfter);
+
+        final String parentPath = String.format("%s/", UUID.randomUUID());
+        op().createDir(parentPath);
+        final List<String> expected = new ArrayList<>();
+        for (int i = 0; i < 5; i++) {
+            expected.add(String.format("%sfile-%d", parentPath, i));
+        }
+        for (String path : expected) {
+            op().write(path, parentPath);
+        }
+
+        final List<String> actual = new ArrayList<>();
+        String token = null;
+        do {
+            final ListPage page = op().listPage(parentPath, 2, token, false);
+            assertThat(page.getEntries().size()).isLessThanOrEqualTo(2);
+            page.getEntries().forEach(entry -> actual.add(entry.getPath()));
+            token = page.getNextToken();
+        } while (token != null);
+
+        expected.add(parentPath);
+        Collections.sort(expected);
+        Collections.sort(actual);
+        assertThat(actual).isEqualTo(expected);
+        op().removeAll(parentPath);
+    }
+
     /**
      * Remove all should remove all in this path.
      */
@@ -0,0 +1,142 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal.test.hadoop;
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.assertj.core.api.Assertions.assertThatThrownBy;
+import java.io.FileNotFoundException;
+import java.net.URI;
+import java.nio.charset.StandardCharsets;
+import java.util.Arrays;
+import java.util.stream.Collectors;
+import org.apache.commons.io.IOUtils;
+import org.apache.hadoop.conf.Configuration;
+import org.apache.hadoop.fs.FSDataInputStream;
+import org.apache.hadoop.fs.FSDataOutputStream;
+import org.apache.hadoop.fs.FileAlreadyExistsException;
+import org.apache.hadoop.fs.FileStatus;
+import org.apache.hadoop.fs.FileSystem;
+import org.apache.hadoop.fs.Path;
+import org.apache.hadoop.fs.PathIsNotEmptyDirectoryException;
+import org.apache.opendal.hadoop.OpendalFileSystem;
+import org.junit.jupiter.api.AfterEach;
+import org.junit.jupiter.api.BeforeEach;
+import org.junit.jupiter.api.Test;
+
+public class OpendalFileSystemTest {
+    private FileSystem fs;
+
+    @BeforeEach
+    public void setUp() throws Exception {
+        final Configuration conf = new Configuration(false);
+        conf.set("fs.opendal+memory.impl", OpendalFileSystem.class.getName());
+        conf.setBoolean("fs.opendal+memory.impl.disable.cache", true);
+        fs = FileSystem.get(URI.create("opendal+memory:///"), conf);
+    }
+
+    @AfterEach
+    public void tearDown() throws Exception {
+        fs.close();
+    }
+
+    private void write(Path path, String content) throws Exception {
+        try (FSDataOutputStream out = fs.create(path)) {
+            out.write(content.getBytes(StandardCharsets.UTF_8));
+        }
+    }
+
+    private String read(Path path) throws Exception {
+        try (FSDataInputStream in = fs.open(path)) {
+            return IOUtils.toString(in, StandardCharsets.UTF_8);
+        }
+    }
+
+    @Test
+    public void testWriteAndRead() throws Exception {
+        assertThat(fs).isInstanceOf(OpendalFileSystem.class);
+
+        final Path path = new Path("/dir/hello.txt");
+        write(path, "hello, world");
+        assertThat(read(path)).isEqualTo("hello, world");
+
+        final FileStatus status = fs.getFileStatus(path);
+        assertThat(status.isFile()).isTrue();
+        assertThat(status.getLen()).isEqualTo(12);
+        assertThat(fs.getFileStatus(new Path("/dir")).isDirectory()).isTrue();
+
+        assertThatThrownBy(() -> fs.create(path, false)).isInstanceOf(FileAlreadyExistsException.class);
+        assertThatThrownBy(() -> fs.getFileStatus(new Path("/not-exist"))).isInstanceOf(FileNotFoundException.class);
+    }
+
+    @Test
+    public void testSeek() throws Exception {
+        final Path path = new Path("/seek.txt");
+        write(path, "0123456789");
+
+        try (FSDataInputStream in = fs.open(path)) {
+            in.seek(5);
+            assertThat(in.read()).isEqualTo('5');
+            assertThat(in.getPos()).isEqualTo(6);
+            in.seek(1);
+            assertThat(in.read()).isEqualTo('1');
+
+            final byte[] buf = new byte[3];
+            in.readFully(7, buf);
+            assertThat(new String(buf, StandardCharsets.UTF_8)).isEqualTo("789");
+        }
+    }
+
+    @Test
+    public void testListAndDelete() throws Exception {
+        assertThat(fs.mkdirs(new Path("/list/sub"))).isTrue();
+        write(new Path("/list/a.txt"), "a");
+        write(new Path("/list/b.txt"), "bb");
+
+        final FileStatus[] statuses = fs.listStatus(new Path("/list"));
+        assertThat(Arrays.stream(statuses).map(s -> s.getPath().getName()).collect(Collectors.toList()))
+                .containsExactlyInAnyOrder("a.txt", "b.txt", "sub");
+        assertThat(Arrays.stream(statuses)
+                        .filter(s -> s.getPath().getName().equals("b.txt"))
+                        .findFirst()
+                        .get()
+                        .getLen())
+                .isEqualTo(2);
+
+        assertThatThrownBy(() -> fs.delete(new Path("/list"), false))
+                .isInstanceOf(PathIsNotEmptyDirectoryException.class);
+        assertThat(fs.delete(new Path("/list/a.txt"), false)).isTrue();
+        assertThat(fs.exists(new Path("/list/a.txt"))).isFalse();
+        assertThat(fs.delete(new Path("/list"), true)).isTrue();
+        assertThat(fs.exists(new Path("/list/b.txt"))).isFalse();
+        assertThat(fs.delete(new Path("/list"), true)).isFalse();
+    }
+
+    @Test
+    public void testRename() throws Exception {
+        final Path src = new Path("/rename/src.txt");
+        write(src, "content");
+        fs.mkdirs(new Path("/rename/target"));
+
+        assertThat(fs.rename(src, new Path("/rename/target"))).isTrue();
+        assertThat(fs.exists(src)).isFalse();
+        assertThat(read(new Path("/rename/target/src.txt"))).isEqualTo("content");
+        assertThat(fs.rename(new Path("/rename/not-exist"), new Path("/rename/dst"))).isFalse();
+    }
+}
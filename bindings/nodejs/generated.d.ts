@@ -32,6 +32,11 @@ export interface ListOptions {
   limit?: number
   recursive?: boolean
 }
+export interface ListPageOptions {
+  /** The continuation token returned by the previous page. */
+  token?: string
+  recursive?: boolean
+}
 export interface WriteOptions {
   /**
    * Append bytes into a path.
@@ -505,6 +510,54 @@ export class Operator {
    * ```
    */
   listSync(path: string, options?: ListOptions | undefined | null): Array<Entry>
+  /**
+   * List a page of entries under the given path.
+   *
+   * Pass the `nextToken` of the returned page as `token` to fetch the next
+   * page. `nextToken` will be `null` on the last page.
+   *
+   * The token is the path of the last entry in the page instead of the
+   * native continuation token of services, so this requires the
+   * `listWithStartAfter` capability.
+   *
+   * ### Example
+   *
+   * ```javascript
+   * let token = null;
+   * do {
+   *   const page = await op.listPage("path/to/dir/", 100, { token });
+   *   for (let entry of page.entries) {
+   *     console.log(entry.path());
+   *   }
+   *   token = page.nextToken;
+   * } while (token);
+   * ```
+   */
+  listPage(path: string, limit: number, options?: ListPageOptions | undefined | null): Promise<ListPage>
+  /**
+   * List a page of entries under the given path synchronously.
+   *
+   * Pass the `nextToken` of the returned page as `token` to fetch the next
+   * page. `nextToken` will be `null` on the last page.
+   *
+   * The token is the path of the last entry in the page instead of the
+   * native continuation token of services, so this requires the
+   * `listWithStartAfter` capability.
+   *
+   * ### Example
+   *
+   * ```javascript
+   * let token = null;
+   * do {
+   *   const page = op.listPageSync("path/to/dir/", 100, { token });
+   *   for (let entry of page.entries) {
+   *     console.log(entry.path());
+   *   }
+   *   token = page.nextToken;
+   * } while (token);
+   * ```
+   */
+  listPageSync(path: string, limit: number, options?: ListPageOptions | undefined | null): ListPage
   /**
    * Get a presigned request for read.
    *
@@ -561,6 +614,13 @@ export class Entry {
   /** Return the path of this entry. */
   path(): string
 }
+/** ListPage is a page of entries returned by `listPage`. */
+export class ListPage {
+  /** Entries in this page. */
+  get entries(): Array<Entry>
+  /** Token to fetch the next page, `null` if this is the last page. */
+  get nextToken(): string | null
+}
 /** Metadata carries all metadata associated with a path. */
 export class Metadata {
   /** Returns true if the <op.stat> object describes a file system directory. */
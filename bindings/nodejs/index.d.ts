@@ -40,4 +40,39 @@ declare module './generated' {
   }
 }
 
+/**
+ * The kind of errors returned by opendal.
+ */
+export type ErrorKind =
+  | 'Unexpected'
+  | 'Unsupported'
+  | 'ConfigInvalid'
+  | 'NotFound'
+  | 'PermissionDenied'
+  | 'IsADirectory'
+  | 'NotADirectory'
+  | 'AlreadyExists'
+  | 'RateLimited'
+  | 'IsSameFile'
+  | 'ConditionNotMatch'
+  | 'RangeNotSatisfied'
+  | 'QuotaExceeded'
+  | 'InvalidInput'
+  | 'CircuitOpen'
+  | 'DataCorrupted'
+
+/**
+ * OpendalError is thrown by all operations of opendal.
+ */
+export class OpendalError extends Error {
+  /** The kind of this error. */
+  readonly kind: ErrorKind
+  /** Whether the operation could succeed by retrying. */
+  readonly isRetryable: boolean
+  /** The http status code returned by services, if any. */
+  readonly statusCode?: number
+  /** The request id returned by services, if any. */
+  readonly requestId?: string
+}
+
 export * from './generated'
@@ -119,7 +119,94 @@ class BlockingWriteStream extends Writable {
   }
 }
 
-const { Operator, RetryLayer, BlockingReader, Reader, BlockingWriter, Writer } = require('./generated.js')
+/**
+ * OpendalError is thrown by all operations of opendal.
+ *
+ * `kind` is the error kind of opendal like `NotFound`, and `isRetryable`
+ * tells whether the operation could succeed by retrying. `statusCode` and
+ * `requestId` are only available for errors returned by http based services.
+ */
+class OpendalError extends Error {
+  constructor(message, { kind, isRetryable, statusCode, requestId } = {}) {
+    super(message)
+    this.name = 'OpendalError'
+    this.kind = kind ?? 'Unexpected'
+    this.isRetryable = isRetryable ?? false
+    this.statusCode = statusCode
+    this.requestId = requestId
+  }
+}
+
+const ERROR_HEADER = /^\[opendal:([^\]]*)\] /
+
+// Parse the header encoded by `format_napi_error` back into an OpendalError.
+function toOpendalError(e) {
+  if (!(e instanceof Error) || e instanceof OpendalError) {
+    return e
+  }
+  const m = ERROR_HEADER.exec(e.message)
+  if (!m) {
+    return e
+  }
+
+  const fields = Object.fromEntries(
+    m[1].split(';').map((kv) => {
+      const i = kv.indexOf('=')
+      return [kv.slice(0, i), kv.slice(i + 1)]
+    }),
+  )
+  const err = new OpendalError(e.message.slice(m[0].length), {
+    kind: fields.kind,
+    isRetryable: fields.retryable === 'true',
+    statusCode: fields.status_code === undefined ? undefined : Number(fields.status_code),
+    requestId: fields.request_id,
+  })
+  err.stack = e.stack
+  return err
+}
+
+// Wrap all methods of given class to throw OpendalError.
+function wrapErrors(cls) {
+  for (const name of Object.getOwnPropertyNames(cls.prototype)) {
+    const desc = Object.getOwnPropertyDescriptor(cls.prototype, name)
+    if (name === 'constructor' || typeof desc.value !== 'function') {
+      continue
+    }
+
+    const f = desc.value
+    cls.prototype[name] = function (...args) {
+      let res
+      try {
+        res = f.apply(this, args)
+      } catch (e) {
+        throw toOpendalError(e)
+      }
+      if (res instanceof Promise) {
+        return res.catch((e) => {
+          throw toOpendalError(e)
+        })
+      }
+      return res
+    }
+  }
+}
+
+const generated = require('./generated.js')
+const { RetryLayer, BlockingReader, Reader, BlockingWriter, Writer, Lister, BlockingLister } = generated
+
+for (const cls of [generated.Operator, BlockingReader, Reader, BlockingWriter, Writer, Lister, BlockingLister]) {
+  wrapErrors(cls)
+}
+
+const Operator = new Proxy(generated.Operator, {
+  construct(target, args) {
+    try {
+      return Reflect.construct(target, args)
+    } catch (e) {
+      throw toOpendalError(e)
+    }
+  },
+})
 
 BlockingReader.prototype.createReadStream = function (options) {
   return new BlockingReadStream(this, options)
@@ -138,6 +225,7 @@ Writer.prototype.createWriteStream = function (options) {
 }
 
 module.exports.Operator = Operator
+module.exports.OpendalError = OpendalError
 module.exports.layers = {
   RetryLayer,
 }
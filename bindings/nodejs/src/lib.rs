@@ -19,7 +19,6 @@
 extern crate napi_derive;
 
 use std::collections::HashMap;
-use std::fmt::Display;
 use std::io::Read;
 use std::str::FromStr;
 use std::time::Duration;
@@ -581,6 +580,88 @@ impl Operator {
             .collect())
     }
 
+    /// List a page of entries under the given path.
+    ///
+    /// Pass the `nextToken` of the returned page as `token` to fetch the next
+    /// page. `nextToken` will be `null` on the last page.
+    ///
+    /// The token is the path of the last entry in the page instead of the
+    /// native continuation token of services, so this requires the
+    /// `listWithStartAfter` capability.
+    ///
+    /// ### Example
+    ///
+    /// ```javascript
+    /// let token = null;
+    /// do {
+    ///   const page = await op.listPage("path/to/dir/", 100, { token });
+    ///   for (let entry of page.entries) {
+    ///     console.log(entry.path());
+    ///   }
+    ///   token = page.nextToken;
+    /// } while (token);
+    /// ```
+    #[napi]
+    pub async fn list_page(
+        &self,
+        path: String,
+        limit: u32,
+        options: Option<ListPageOptions>,
+    ) -> Result<ListPage> {
+        let options = options.unwrap_or_default();
+        self.0
+            .list_page(
+                &path,
+                limit as usize,
+                options.token.as_deref(),
+                options.recursive.unwrap_or_default(),
+            )
+            .await
+            .map(ListPage)
+            .map_err(format_napi_error)
+    }
+
+    /// List a page of entries under the given path synchronously.
+    ///
+    /// Pass the `nextToken` of the returned page as `token` to fetch the next
+    /// page. `nextToken` will be `null` on the last page.
+    ///
+    /// The token is the path of the last entry in the page instead of the
+    /// native continuation token of services, so this requires the
+    /// `listWithStartAfter` capability.
+    ///
+    /// ### Example
+    ///
+    /// ```javascript
+    /// let token = null;
+    /// do {
+    ///   const page = op.listPageSync("path/to/dir/", 100, { token });
+    ///   for (let entry of page.entries) {
+    ///     console.log(entry.path());
+    ///   }
+    ///   token = page.nextToken;
+    /// } while (token);
+    /// ```
+    #[napi]
+    pub fn list_page_sync(
+        &self,
+        path: String,
+        limit: u32,
+        options: Option<ListPageOptions>,
+    ) -> Result<ListPage> {
+        let options = options.unwrap_or_default();
+        self.0
+            .blocking()
+            .list_page(
+                &path,
+                limit as usize,
+                options.token.as_deref(),
+                options.recursive.unwrap_or_default(),
+            )
+            .map(ListPage)
+            .map_err(format_napi_error)
+    }
+
     /// Get a presigned request for read.
     ///
     /// Unit of `expires` is seconds.
@@ -727,6 +808,33 @@ pub struct ListOptions {
     pub recursive: Option<bool>,
 }
 
+#[napi(object)]
+#[derive(Default)]
+pub struct ListPageOptions {
+    /// The continuation token returned by the previous page.
+    pub token: Option<String>,
+    pub recursive: Option<bool>,
+}
+
+/// ListPage is a page of entries returned by `listPage`.
+#[napi]
+pub struct ListPage(opendal::ListPage);
+
+#[napi]
+impl ListPage {
+    /// Entries in this page.
+    #[napi(getter)]
+    pub fn entries(&self) -> Vec<Entry> {
+        self.0.entries().iter().cloned().map(Entry).collect()
+    }
+
+    /// Token to fetch the next page, `null` if this is the last page.
+    #[napi(getter)]
+    pub fn next_token(&self) -> Option<String> {
+        self.0.next_token().map(|v| v.to_string())
+    }
+}
+
 /// BlockingReader is designed to read data from a given path in a blocking
 /// manner.
 #[napi]
@@ -739,7 +847,7 @@ impl BlockingReader {
     #[napi]
     pub fn read(&mut self, mut buf: Buffer) -> Result<usize> {
         let buf = buf.as_mut();
-        let n = self.inner.read(buf).map_err(format_napi_error)?;
+        let n = self.inner.read(buf).map_err(format_napi_io_error)?;
         Ok(n)
     }
 }
@@ -761,7 +869,7 @@ impl Reader {
     #[napi]
     pub async unsafe fn read(&mut self, mut buf: Buffer) -> Result<usize> {
         let buf = buf.as_mut();
-        let n = self.inner.read(buf).await.map_err(format_napi_error)?;
+        let n = self.inner.read(buf).await.map_err(format_napi_io_error)?;
         Ok(n)
     }
 }
@@ -1146,7 +1254,37 @@ impl RetryLayer {
 
 /// Format opendal error to napi error.
 ///
-/// FIXME: handle error correctly.
-fn format_napi_error(err: impl Display) -> Error {
-    Error::from_reason(format!("{}", err))
+/// napi errors can only carry a reason, so the kind, the retryable status,
+/// the status code and the request id are encoded into a header like
+/// `[opendal:kind=NotFound;retryable=false]` before the message. `index.js`
+/// parses the header back into `OpendalError`.
+fn format_napi_error(err: opendal::Error) -> Error {
+    let mut header = format!(
+        "kind={};retryable={}",
+        err.kind().into_static(),
+        err.is_temporary()
+    );
+    if let Some(status_code) = err.context("status_code") {
+        header.push_str(&format!(";status_code={status_code}"));
+    }
+    if let Some(request_id) = err.context("request_id") {
+        header.push_str(&format!(";request_id={request_id}"));
+    }
+    Error::from_reason(format!("[opendal:{header}] {err}"))
+}
+
+/// Format io error returned by opendal readers to napi error.
+fn format_napi_io_error(err: std::io::Error) -> Error {
+    let kind = err.kind();
+    match err.into_inner().map(|e| e.downcast::<opendal::Error>()) {
+        Some(Ok(err)) => format_napi_error(*err),
+        Some(Err(err)) => format_napi_error(
+            opendal::Error::new(opendal::ErrorKind::Unexpected, "read failed")
+                .set_source(std::io::Error::new(kind, err)),
+        ),
+        None => format_napi_error(
+            opendal::Error::new(opendal::ErrorKind::Unexpected, "read failed")
+                .set_source(std::io::Error::from(kind)),
+        ),
+    }
 }
@@ -19,6 +19,7 @@
 
 import { randomUUID } from 'node:crypto'
 import { test } from 'vitest'
+import { OpendalError } from '../../index.js'
 import { generateBytes, generateFixedBytes } from '../utils.mjs'
 import { Readable, Writable } from 'node:stream'
 import { finished, pipeline } from 'node:stream/promises'
@@ -35,6 +36,16 @@ export function run(op) {
       }
     })
 
+    test('async stat not exist files throws OpendalError', async () => {
+      const filename = `random_file_${randomUUID()}`
+
+      const error = await op.stat(filename).catch((e) => e)
+      assert.instanceOf(error, OpendalError)
+      assert.equal(error.kind, 'NotFound')
+      assert.isFalse(error.isRetryable)
+      assert.notInclude(error.message, '[opendal:')
+    })
+
     test.runIf(op.capability().read && op.capability().write && op.capability().writeCanMulti)(
       'reader/writer stream pipeline',
       async () => {
@@ -114,5 +125,28 @@ export function run(op) {
         op.deleteSync(filename)
       }
     })
+
+    test.runIf(op.capability().write && op.capability().list && op.capability().listWithStartAfter)(
+      'list page',
+      async () => {
+        const dir = `random_dir_${randomUUID()}/`
+        const expected = [0, 1, 2, 3, 4].map((i) => `${dir}file_${i}`)
+        for (const path of expected) {
+          await op.write(path, Buffer.from('hello'))
+        }
+
+        const paths = []
+        let token = null
+        do {
+          const page = await op.listPage(dir, 2, { token })
+          assert.isAtMost(page.entries.length, 2)
+          paths.push(...page.entries.map((e) => e.path()))
+          token = page.nextToken
+        } while (token)
+
+        assert.deepEqual(paths.filter((p) => p !== dir).sort(), expected)
+        await op.removeAll(dir)
+      },
+    )
   })
 }
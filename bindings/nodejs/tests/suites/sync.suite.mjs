@@ -19,7 +19,7 @@
 
 import { randomUUID } from 'node:crypto'
 import { test } from 'vitest'
-import { WriteStream, ReadStream } from '../../index.js'
+import { OpendalError, WriteStream, ReadStream } from '../../index.js'
 import { generateFixedBytes } from '../utils.mjs'
 import { Readable } from 'node:stream'
 
@@ -35,6 +35,18 @@ export function run(op) {
       }
     })
 
+    test('sync stat not exist files throws OpendalError', () => {
+      const filename = `random_file_${randomUUID()}`
+
+      assert.throws(() => op.statSync(filename), OpendalError)
+      try {
+        op.statSync(filename)
+      } catch (error) {
+        assert.equal(error.kind, 'NotFound')
+        assert.isFalse(error.isRetryable)
+      }
+    })
+
     test.runIf(op.capability().read && op.capability().write && op.capability().writeCanMulti)(
       'blocking reader/writer stream pipeline',
       async () => {
@@ -111,5 +123,30 @@ export function run(op) {
         op.deleteSync(filename)
       }
     })
+
+    test.runIf(op.capability().write && op.capability().list && op.capability().listWithStartAfter)(
+      'list page sync',
+      () => {
+        const dir = `random_dir_${randomUUID()}/`
+        const expected = [0, 1, 2, 3, 4].map((i) => `${dir}file_${i}`)
+        for (const path of expected) {
+          op.writeSync(path, Buffer.from('hello'))
+        }
+
+        const paths = []
+        let token = null
+        do {
+          const page = op.listPageSync(dir, 2, { token })
+          assert.isAtMost(page.entries.length, 2)
+          paths.push(...page.entries.map((e) => e.path()))
+          token = page.nextToken
+        } while (token)
+
+        assert.deepEqual(paths.filter((p) => p !== dir).sort(), expected)
+        for (const path of expected) {
+          op.deleteSync(path)
+        }
+      },
+    )
   })
 }
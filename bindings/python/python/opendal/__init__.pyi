@@ -15,7 +15,17 @@
 # specific language governing permissions and limitations
 # under the License.
 
-from typing import Any, AsyncIterable, Iterable, Optional, final, Union, Type
+from typing import (
+    Any,
+    AsyncIterable,
+    Iterable,
+    List,
+    Optional,
+    Tuple,
+    final,
+    Union,
+    Type,
+)
 from types import TracebackType
 
 from opendal import exceptions as exceptions
@@ -44,6 +54,14 @@ class Operator:
     def delete(self, path: str) -> None: ...
     def list(self, path: str) -> Iterable[Entry]: ...
     def scan(self, path: str) -> Iterable[Entry]: ...
+    def list_page(
+        self,
+        path: str,
+        limit: int,
+        *,
+        token: Optional[str] = None,
+        recursive: bool = False,
+    ) -> ListPage: ...
     def capability(self) -> Capability: ...
     def copy(self, source: str, target: str) -> None: ...
     def rename(self, source: str, target: str) -> None: ...
@@ -70,8 +88,25 @@ class AsyncOperator:
     async def stat(self, path: str) -> Metadata: ...
     async def create_dir(self, path: str) -> None: ...
     async def delete(self, path: str) -> None: ...
+    async def read_many(
+        self, paths: List[str], *, concurrent: int = 16
+    ) -> List[Union[bytes, Exception]]: ...
+    async def write_many(
+        self, items: List[Tuple[str, bytes]], *, concurrent: int = 16
+    ) -> List[Optional[Exception]]: ...
+    async def delete_many(
+        self, paths: List[str], *, concurrent: int = 16
+    ) -> List[Optional[Exception]]: ...
     async def list(self, path: str) -> AsyncIterable[Entry]: ...
     async def scan(self, path: str) -> AsyncIterable[Entry]: ...
+    async def list_page(
+        self,
+        path: str,
+        limit: int,
+        *,
+        token: Optional[str] = None,
+        recursive: bool = False,
+    ) -> ListPage: ...
     async def presign_stat(self, path: str, expire_second: int) -> PresignedRequest: ...
     async def presign_read(self, path: str, expire_second: int) -> PresignedRequest: ...
     async def presign_write(
@@ -131,6 +166,13 @@ class Entry:
     @property
     def path(self) -> str: ...
 
+@final
+class ListPage:
+    @property
+    def entries(self) -> List[Entry]: ...
+    @property
+    def next_token(self) -> Optional[str]: ...
+
 @final
 class Metadata:
     @property
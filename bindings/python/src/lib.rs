@@ -79,6 +79,7 @@ fn _opendal(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<AsyncFile>()?;
 
     m.add_class::<Entry>()?;
+    m.add_class::<ListPage>()?;
     m.add_class::<EntryMode>()?;
     m.add_class::<Metadata>()?;
     m.add_class::<PresignedRequest>()?;
@@ -79,3 +79,29 @@ impl AsyncLister {
         Ok(Some(fut.into()))
     }
 }
+
+/// A page of entries returned by `list_page`.
+#[pyclass(module = "opendal")]
+pub struct ListPage(ocore::ListPage);
+
+impl ListPage {
+    /// Create a new list page.
+    pub fn new(inner: ocore::ListPage) -> Self {
+        Self(inner)
+    }
+}
+
+#[pymethods]
+impl ListPage {
+    /// Entries in this page.
+    #[getter]
+    pub fn entries(&self) -> Vec<Entry> {
+        self.0.entries().iter().cloned().map(Entry::new).collect()
+    }
+
+    /// Token to fetch the next page, `None` if this is the last page.
+    #[getter]
+    pub fn next_token(&self) -> Option<&str> {
+        self.0.next_token()
+    }
+}
@@ -22,6 +22,7 @@ use std::time::Duration;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
+use pyo3::types::PyList;
 use pyo3_async_runtimes::tokio::future_into_py;
 
 use crate::*;
@@ -41,6 +42,15 @@ fn build_operator(
     Ok(op)
 }
 
+/// Convert results of bulk operations into a list of `None` or exceptions.
+fn errors_into_list(py: Python, results: Vec<ocore::Result<()>>) -> Py<PyList> {
+    let items = results.into_iter().map(|res| match res {
+        Ok(()) => py.None(),
+        Err(err) => format_pyerr(err).into_value(py).into_any(),
+    });
+    PyList::new_bound(py, items).unbind()
+}
+
 /// `Operator` is the entry for all public blocking APIs
 ///
 /// Create a new blocking `Operator` with the given `scheme` and options(`**kwargs`).
@@ -183,6 +193,28 @@ impl Operator {
         Ok(BlockingLister::new(l))
     }
 
+    /// List a page of entries under given path.
+    ///
+    /// Pass the `next_token` of the returned page as `token` to fetch the
+    /// next page.
+    ///
+    /// The token is the path of the last entry in the page instead of the
+    /// native continuation token of services, so this requires the
+    /// `list_with_start_after` capability.
+    #[pyo3(signature = (path, limit, *, token=None, recursive=false))]
+    pub fn list_page(
+        &self,
+        path: &str,
+        limit: usize,
+        token: Option<&str>,
+        recursive: bool,
+    ) -> PyResult<ListPage> {
+        self.0
+            .list_page(path, limit, token, recursive)
+            .map(ListPage::new)
+            .map_err(format_pyerr)
+    }
+
     pub fn capability(&self) -> PyResult<capability::Capability> {
         Ok(capability::Capability::new(self.0.info().full_capability()))
     }
@@ -388,6 +420,93 @@ impl AsyncOperator {
         )
     }
 
+    /// Read given paths concurrently.
+    ///
+    /// Returns a list in the same order of `paths`, every item is either the
+    /// content in bytes or the exception raised while reading it.
+    #[pyo3(signature = (paths, *, concurrent=16))]
+    pub fn read_many<'p>(
+        &'p self,
+        py: Python<'p>,
+        paths: Vec<String>,
+        concurrent: usize,
+    ) -> PyResult<Bound<PyAny>> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let results: Vec<_> = futures::stream::iter(paths)
+                .map(|path| {
+                    let this = this.clone();
+                    async move { this.read(&path).await }
+                })
+                .buffered(concurrent.max(1))
+                .collect()
+                .await;
+
+            Python::with_gil(|py| {
+                let items = results
+                    .into_iter()
+                    .map(|res| match res {
+                        Ok(bs) => Buffer::new(bs.to_vec()).into_bytes(py),
+                        Err(err) => Ok(format_pyerr(err).into_value(py).into_any()),
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+                Ok(PyList::new_bound(py, items).unbind())
+            })
+        })
+    }
+
+    /// Write given `(path, bytes)` items concurrently.
+    ///
+    /// Returns a list in the same order of `items`, every item is either
+    /// `None` or the exception raised while writing it.
+    #[pyo3(signature = (items, *, concurrent=16))]
+    pub fn write_many<'p>(
+        &'p self,
+        py: Python<'p>,
+        items: Vec<(String, Vec<u8>)>,
+        concurrent: usize,
+    ) -> PyResult<Bound<PyAny>> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let results: Vec<_> = futures::stream::iter(items)
+                .map(|(path, bs)| {
+                    let this = this.clone();
+                    async move { this.write(&path, bs).await }
+                })
+                .buffered(concurrent.max(1))
+                .collect()
+                .await;
+
+            Python::with_gil(|py| Ok(errors_into_list(py, results)))
+        })
+    }
+
+    /// Delete given paths concurrently.
+    ///
+    /// Returns a list in the same order of `paths`, every item is either
+    /// `None` or the exception raised while deleting it.
+    #[pyo3(signature = (paths, *, concurrent=16))]
+    pub fn delete_many<'p>(
+        &'p self,
+        py: Python<'p>,
+        paths: Vec<String>,
+        concurrent: usize,
+    ) -> PyResult<Bound<PyAny>> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let results: Vec<_> = futures::stream::iter(paths)
+                .map(|path| {
+                    let this = this.clone();
+                    async move { this.delete(&path).await }
+                })
+                .buffered(concurrent.max(1))
+                .collect()
+                .await;
+
+            Python::with_gil(|py| Ok(errors_into_list(py, results)))
+        })
+    }
+
     /// List current dir path.
     pub fn list<'p>(&'p self, py: Python<'p>, path: String) -> PyResult<Bound<PyAny>> {
         let this = self.0.clone();
@@ -412,6 +531,34 @@ impl AsyncOperator {
         })
     }
 
+    /// List a page of entries under given path.
+    ///
+    /// Pass the `next_token` of the returned page as `token` to fetch the
+    /// next page.
+    ///
+    /// The token is the path of the last entry in the page instead of the
+    /// native continuation token of services, so this requires the
+    /// `list_with_start_after` capability.
+    #[pyo3(signature = (path, limit, *, token=None, recursive=false))]
+    pub fn list_page<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        limit: usize,
+        token: Option<String>,
+        recursive: bool,
+    ) -> PyResult<Bound<PyAny>> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let page = this
+                .list_page(&path, limit, token.as_deref(), recursive)
+                .await
+                .map_err(format_pyerr)?;
+            let page: PyObject = Python::with_gil(|py| ListPage::new(page).into_py(py));
+            Ok(page)
+        })
+    }
+
     /// Presign an operation for stat(head) which expires after `expire_second` seconds.
     pub fn presign_stat<'p>(
         &'p self,
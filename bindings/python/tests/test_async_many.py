@@ -0,0 +1,43 @@
+# Licensed to the Apache Software Foundation (ASF) under one
+# or more contributor license agreements.  See the NOTICE file
+# distributed with this work for additional information
+# regarding copyright ownership.  The ASF licenses this file
+# to you under the Apache License, Version 2.0 (the
+# "License"); you may not use this file except in compliance
+# with the License.  You may obtain a copy of the License at
+#
+#   http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing,
+# software distributed under the License is distributed on an
+# "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+# KIND, either express or implied.  See the License for the
+# specific language governing permissions and limitations
+# under the License.
+
+import os
+from uuid import uuid4
+
+import pytest
+from opendal.exceptions import NotFound
+
+
+@pytest.mark.asyncio
+@pytest.mark.need_capability("read", "write", "delete")
+async def test_async_many(service_name, operator, async_operator):
+    parent = f"random_dir_{str(uuid4())}"
+    items = [(f"{parent}/{i}", os.urandom(1024)) for i in range(8)]
+
+    results = await async_operator.write_many(items, concurrent=4)
+    assert results == [None] * len(items)
+
+    paths = [path for path, _ in items] + [f"{parent}/not_exist"]
+    results = await async_operator.read_many(paths, concurrent=4)
+    assert results[:-1] == [content for _, content in items]
+    assert isinstance(results[-1], NotFound)
+
+    results = await async_operator.delete_many(paths, concurrent=4)
+    assert results == [None] * len(paths)
+    for path, _ in items:
+        with pytest.raises(NotFound):
+            await async_operator.stat(path)
@@ -0,0 +1,66 @@
+# Licensed to the Apache Software Foundation (ASF) under one
+# or more contributor license agreements.  See the NOTICE file
+# distributed with this work for additional information
+# regarding copyright ownership.  The ASF licenses this file
+# to you under the Apache License, Version 2.0 (the
+# "License"); you may not use this file except in compliance
+# with the License.  You may obtain a copy of the License at
+#
+#   http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing,
+# software distributed under the License is distributed on an
+# "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+# KIND, either express or implied.  See the License for the
+# specific language governing permissions and limitations
+# under the License.
+
+import os
+from uuid import uuid4
+
+import pytest
+
+
+@pytest.mark.need_capability(
+    "write", "list", "list_with_start_after", "blocking", "create_dir"
+)
+def test_sync_list_page(service_name, operator, async_operator):
+    parent = f"random_dir_{str(uuid4())}/"
+    expected = [f"{parent}file_{i}" for i in range(5)]
+    for path in expected:
+        operator.write(path, os.urandom(16))
+
+    paths = []
+    token = None
+    while True:
+        page = operator.list_page(parent, 2, token=token)
+        assert len(page.entries) <= 2
+        paths.extend(entry.path for entry in page.entries)
+        token = page.next_token
+        if token is None:
+            break
+
+    assert sorted(p for p in paths if p != parent) == expected
+    operator.remove_all(parent)
+
+
+@pytest.mark.asyncio
+@pytest.mark.need_capability("write", "list", "list_with_start_after", "create_dir")
+async def test_async_list_page(service_name, operator, async_operator):
+    parent = f"random_dir_{str(uuid4())}/"
+    expected = [f"{parent}file_{i}" for i in range(5)]
+    for path in expected:
+        await async_operator.write(path, os.urandom(16))
+
+    paths = []
+    token = None
+    while True:
+        page = await async_operator.list_page(parent, 2, token=token)
+        assert len(page.entries) <= 2
+        paths.extend(entry.path for entry in page.entries)
+        token = page.next_token
+        if token is None:
+            break
+
+    assert sorted(p for p in paths if p != parent) == expected
+    await async_operator.remove_all(parent)
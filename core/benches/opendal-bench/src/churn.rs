@@ -0,0 +1,48 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::Operator;
+
+use super::utils::*;
+
+/// Small-object churn: write, stat and delete small objects in turn, which
+/// is dominated by the per-request latency.
+pub fn bench(c: &mut Criterion, op: &Operator) {
+    let name = op.info().scheme().into_static();
+    let mut group = c.benchmark_group(format!("{name}/small_object_churn"));
+
+    let mut rng = rng();
+    let dir = TempDir::new(op);
+
+    for size in [1024, 4 * 1024, 64 * 1024] {
+        let content = gen_bytes(&mut rng, size);
+        let path = dir.join(&format!("churn-{size}"));
+
+        group.throughput(criterion::Throughput::Elements(1));
+        group.bench_function(format!("{}KiB", size / 1024), |b| {
+            b.to_async(&*TEST_RUNTIME).iter(|| async {
+                op.write(&path, content.clone()).await.unwrap();
+                op.stat(&path).await.unwrap();
+                op.delete(&path).await.unwrap();
+            })
+        });
+    }
+
+    group.finish()
+}
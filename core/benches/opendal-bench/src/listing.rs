@@ -0,0 +1,75 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+use futures::TryStreamExt;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::Operator;
+
+use super::utils::*;
+
+/// The depth of the generated tree.
+const DEPTH: usize = 4;
+/// The number of dirs and files under every dir.
+const FANOUT: usize = 4;
+
+/// Deep listing: list a deep tree recursively, which is dominated by the
+/// pagination of services.
+pub fn bench(c: &mut Criterion, op: &Operator) {
+    let name = op.info().scheme().into_static();
+    let mut group = c.benchmark_group(format!("{name}/deep_listing"));
+    group.sample_size(10);
+
+    let dir = TempDir::new(op);
+    let mut files = vec![];
+    prepare(dir.path(), DEPTH, &mut files);
+    TEST_RUNTIME.block_on(async {
+        for path in &files {
+            op.write(path, vec![0; 16]).await.expect("create test data");
+        }
+    });
+
+    group.throughput(criterion::Throughput::Elements(files.len() as u64));
+    group.bench_function(format!("depth{DEPTH}x{FANOUT}"), |b| {
+        b.to_async(&*TEST_RUNTIME).iter(|| async {
+            let entries: Vec<_> = op
+                .lister_with(dir.path())
+                .recursive(true)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert!(entries.len() >= files.len());
+        })
+    });
+
+    group.finish()
+}
+
+/// Generate file paths of a tree with given depth under `dir`.
+fn prepare(dir: &str, depth: usize, files: &mut Vec<String>) {
+    for i in 0..FANOUT {
+        files.push(format!("{dir}file-{i}"));
+    }
+    if depth == 0 {
+        return;
+    }
+    for i in 0..FANOUT {
+        prepare(&format!("{dir}dir-{i}/"), depth - 1, files);
+    }
+}
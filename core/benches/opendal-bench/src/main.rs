@@ -0,0 +1,36 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+mod churn;
+mod listing;
+mod ranged;
+mod sequential;
+mod utils;
+
+use criterion::Criterion;
+
+fn main() {
+    let op = utils::init_operator();
+
+    let mut c = Criterion::default().configure_from_args();
+    churn::bench(&mut c, &op);
+    sequential::bench(&mut c, &op);
+    ranged::bench(&mut c, &op);
+    listing::bench(&mut c, &op);
+
+    c.final_summary();
+}
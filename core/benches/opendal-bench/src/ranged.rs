@@ -0,0 +1,64 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::Operator;
+use rand::prelude::*;
+
+use super::utils::*;
+
+/// The size of the object that ranged reads will be issued on.
+const OBJECT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Random ranged reads: read small ranges at random offsets of a large object,
+/// which is common for columnar formats like parquet.
+pub fn bench(c: &mut Criterion, op: &Operator) {
+    let name = op.info().scheme().into_static();
+    let mut group = c.benchmark_group(format!("{name}/random_ranged_read"));
+
+    let mut rng = rng();
+    let dir = TempDir::new(op);
+    let path = dir.join("ranged");
+    let content = gen_bytes(&mut rng, OBJECT_SIZE as usize);
+    TEST_RUNTIME.block_on(async { op.write(&path, content).await.expect("create test data") });
+
+    for size in [4 * 1024, 64 * 1024, 1024 * 1024] {
+        // Offsets are generated ahead so that every run reads the same ranges.
+        let offsets: Vec<u64> = (0..1024)
+            .map(|_| rng.gen_range(0..OBJECT_SIZE - size))
+            .collect();
+
+        group.throughput(criterion::Throughput::Bytes(size));
+        group.bench_function(format!("{}KiB", size / 1024), |b| {
+            let mut idx = 0;
+            b.to_async(&*TEST_RUNTIME).iter(|| {
+                let offset = offsets[idx % offsets.len()];
+                idx += 1;
+                let path = &path;
+                async move {
+                    op.read_with(path)
+                        .range(offset..offset + size)
+                        .await
+                        .unwrap();
+                }
+            })
+        });
+    }
+
+    group.finish()
+}
@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+use futures::io;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::Operator;
+
+use super::utils::*;
+
+/// Large sequential: write and read large objects from start to end, which
+/// is dominated by the throughput.
+pub fn bench(c: &mut Criterion, op: &Operator) {
+    let name = op.info().scheme().into_static();
+    let mut group = c.benchmark_group(format!("{name}/large_sequential"));
+    group.sample_size(10);
+
+    let mut rng = rng();
+    let dir = TempDir::new(op);
+
+    for size in [16 * 1024 * 1024, 64 * 1024 * 1024] {
+        let content = gen_bytes(&mut rng, size);
+        let path = dir.join(&format!("sequential-{size}"));
+
+        group.throughput(criterion::Throughput::Bytes(size as u64));
+        group.bench_function(format!("write/{}MiB", size / 1024 / 1024), |b| {
+            b.to_async(&*TEST_RUNTIME).iter(|| async {
+                let mut w = op.writer_with(&path).chunk(8 * 1024 * 1024).await.unwrap();
+                w.write(content.clone()).await.unwrap();
+                w.close().await.unwrap();
+            })
+        });
+        group.bench_function(format!("read/{}MiB", size / 1024 / 1024), |b| {
+            b.to_async(&*TEST_RUNTIME).iter(|| async {
+                let r = op.reader(&path).await.unwrap();
+                let r = r.into_futures_async_read(0..size as u64).await.unwrap();
+                io::copy(r, &mut io::sink()).await.unwrap();
+            })
+        });
+    }
+
+    group.finish()
+}
@@ -0,0 +1,81 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use opendal::raw::tests::init_test_service;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::services;
+use opendal::Operator;
+use rand::prelude::*;
+
+/// The seed used by all workloads, so that every run generates the same
+/// content, offsets and tree layout.
+pub const SEED: u64 = 0x0da1;
+
+/// Init the operator to bench from `OPENDAL_TEST`, fallback to memory.
+pub fn init_operator() -> Operator {
+    match init_test_service().expect("init test service must succeed") {
+        Some(op) => op,
+        None => Operator::new(services::Memory::default())
+            .expect("init memory service must succeed")
+            .finish(),
+    }
+}
+
+/// Build the rng used by workloads.
+pub fn rng() -> StdRng {
+    StdRng::seed_from_u64(SEED)
+}
+
+pub fn gen_bytes(rng: &mut StdRng, size: usize) -> Vec<u8> {
+    let mut content = vec![0; size];
+    rng.fill_bytes(&mut content);
+    content
+}
+
+/// TempDir is a unique dir for one workload, which will be removed on drop.
+pub struct TempDir {
+    op: Operator,
+    path: String,
+}
+
+impl TempDir {
+    pub fn new(op: &Operator) -> Self {
+        Self {
+            op: op.clone(),
+            path: format!("{}/", uuid::Uuid::new_v4()),
+        }
+    }
+
+    pub fn join(&self, name: &str) -> String {
+        format!("{}{}", self.path, name)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        TEST_RUNTIME.block_on(async {
+            self.op
+                .remove_all(&self.path)
+                .await
+                .expect("cleanup test data");
+        })
+    }
+}
@@ -0,0 +1,180 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Utc;
+
+use crate::raw::*;
+use crate::*;
+
+/// The time to hold the limit after decreasing if services don't return
+/// `Retry-After`.
+const DEFAULT_HOLD: Duration = Duration::from_secs(1);
+
+/// Aimd adjusts a limit by additive-increase/multiplicative-decrease
+/// according to the feedback of services.
+///
+/// - The limit is halved once services throttle requests, throttled
+///   requests within the hold time (`Retry-After` or 1s) are ignored.
+/// - After the hold time, the limit is increased by `step` every
+///   `limit / step` successful requests until reaching `max`.
+#[derive(Debug)]
+pub(crate) struct Aimd {
+    min: usize,
+    max: usize,
+    step: usize,
+    state: Mutex<AimdState>,
+}
+
+#[derive(Debug)]
+struct AimdState {
+    limit: usize,
+    successes: usize,
+    hold_until: Option<Instant>,
+}
+
+impl Aimd {
+    pub(crate) fn new(min: usize, max: usize, step: usize) -> Self {
+        let min = min.clamp(1, max.max(1));
+        Self {
+            min,
+            max,
+            step: step.max(1),
+            state: Mutex::new(AimdState {
+                limit: max,
+                successes: 0,
+                hold_until: None,
+            }),
+        }
+    }
+
+    /// Feed the result of a request, returns the old and new limit if the
+    /// limit has been changed.
+    pub(crate) fn observe<T>(&self, res: &Result<T>) -> Option<(usize, usize)> {
+        match res {
+            Ok(_) => self.on_success(),
+            Err(err) => self.observe_error(err),
+        }
+    }
+
+    /// Feed an error returned in the middle of a request, like reading or
+    /// writing data.
+    pub(crate) fn observe_error(&self, err: &Error) -> Option<(usize, usize)> {
+        if is_throttled(err) {
+            self.on_throttled(retry_after(err))
+        } else {
+            None
+        }
+    }
+
+    fn on_throttled(&self, retry_after: Option<Duration>) -> Option<(usize, usize)> {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("lock must succeed");
+        if state.hold_until.is_some_and(|t| now < t) {
+            return None;
+        }
+
+        let old = state.limit;
+        state.limit = (old / 2).max(self.min);
+        state.successes = 0;
+        state.hold_until = Some(now + retry_after.unwrap_or(DEFAULT_HOLD));
+        (old != state.limit).then_some((old, state.limit))
+    }
+
+    fn on_success(&self) -> Option<(usize, usize)> {
+        let mut state = self.state.lock().expect("lock must succeed");
+        if state.limit >= self.max || state.hold_until.is_some_and(|t| Instant::now() < t) {
+            return None;
+        }
+
+        state.successes += 1;
+        if state.successes < state.limit.div_ceil(self.step) {
+            return None;
+        }
+
+        let old = state.limit;
+        state.limit = (old + self.step).min(self.max);
+        state.successes = 0;
+        Some((old, state.limit))
+    }
+}
+
+/// Check whether services are throttling requests.
+fn is_throttled(err: &Error) -> bool {
+    err.kind() == ErrorKind::RateLimited
+        || matches!(err.context("status_code"), Some("429") | Some("503"))
+}
+
+/// Parse `Retry-After` recorded in the error, which could be seconds or a
+/// http date.
+fn retry_after(err: &Error) -> Option<Duration> {
+    let v = err.context("retry_after")?;
+    if let Ok(secs) = v.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = parse_datetime_from_rfc2822(v).ok()?;
+    (at - Utc::now()).to_std().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttled(retry_after: &str) -> Result<()> {
+        Err(Error::new(ErrorKind::Unexpected, "slow down")
+            .with_context("status_code", "503")
+            .with_context("retry_after", retry_after))
+    }
+
+    #[test]
+    fn test_aimd() {
+        let aimd = Aimd::new(2, 8, 1);
+
+        assert_eq!(aimd.observe(&throttled("0")), Some((8, 4)));
+        assert_eq!(aimd.observe(&throttled("0")), Some((4, 2)));
+        // Never lower than min.
+        assert_eq!(aimd.observe(&throttled("0")), None);
+        // Other errors are ignored.
+        assert_eq!(
+            aimd.observe::<()>(&Err(Error::new(ErrorKind::NotFound, "not found"))),
+            None
+        );
+
+        assert_eq!(aimd.observe(&Ok(())), None);
+        assert_eq!(aimd.observe(&Ok(())), Some((2, 3)));
+        for _ in 0..2 {
+            assert_eq!(aimd.observe(&Ok(())), None);
+        }
+        assert_eq!(aimd.observe(&Ok(())), Some((3, 4)));
+    }
+
+    #[test]
+    fn test_aimd_hold() {
+        let aimd = Aimd::new(1, 8, 1);
+
+        assert_eq!(aimd.observe(&throttled("60")), Some((8, 4)));
+        // Throttled requests sent before decreasing are ignored.
+        assert_eq!(aimd.observe(&throttled("60")), None);
+        // Don't increase until the hold time passed.
+        for _ in 0..8 {
+            assert_eq!(aimd.observe(&Ok(())), None);
+        }
+    }
+}
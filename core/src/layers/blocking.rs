@@ -15,9 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::future::Future;
 use std::sync::Arc;
 
+use once_cell::sync::OnceCell;
 use tokio::runtime::Handle;
+use tokio::runtime::Runtime;
 
 use crate::raw::*;
 use crate::*;
@@ -127,28 +130,136 @@ use crate::*;
 ///     Ok(())
 /// }
 /// ```
+///
+/// ## With an explicit handle
+///
+/// If the runtime is managed elsewhere, pass its handle by [`BlockingLayer::from_handle`]
+/// instead of entering it.
+///
+/// ```rust,no_run
+/// # use opendal::layers::BlockingLayer;
+/// # use opendal::services;
+/// # use opendal::BlockingOperator;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// fn blocking_fn(handle: tokio::runtime::Handle) -> Result<BlockingOperator> {
+///     let builder = services::S3::default().bucket("test").region("us-east-1");
+///
+///     let op: BlockingOperator = Operator::new(builder)?
+///         .layer(BlockingLayer::from_handle(handle))
+///         .finish()
+///         .blocking();
+///     Ok(op)
+/// }
+/// ```
+///
+/// ## With an owned runtime
+///
+/// For plain threads and FFI callers that don't have any runtime at all,
+/// [`BlockingLayer::new_owned_runtime`] creates a current-thread runtime on first use and
+/// keeps it alive together with the operator.
+///
+/// ```rust,no_run
+/// # use opendal::layers::BlockingLayer;
+/// # use opendal::services;
+/// # use opendal::BlockingOperator;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// fn main() -> Result<()> {
+///     let builder = services::S3::default().bucket("test").region("us-east-1");
+///
+///     let _: BlockingOperator = Operator::new(builder)?
+///         .layer(BlockingLayer::new_owned_runtime())
+///         .finish()
+///         .blocking();
+///
+///     Ok(())
+/// }
+/// ```
 #[derive(Debug, Clone)]
 pub struct BlockingLayer {
-    handle: Handle,
+    runtime: BlockingRuntime,
 }
 
 impl BlockingLayer {
     /// Create a new `BlockingLayer` with the current runtime's handle
     pub fn create() -> Result<Self> {
-        Ok(Self {
-            handle: Handle::try_current()
-                .map_err(|_| Error::new(ErrorKind::Unexpected, "failed to get current handle"))?,
+        let handle = Handle::try_current()
+            .map_err(|_| Error::new(ErrorKind::Unexpected, "failed to get current handle"))?;
+        Ok(Self::from_handle(handle))
+    }
+
+    /// Create a new `BlockingLayer` with given runtime's handle.
+    ///
+    /// The runtime must be a multi-thread runtime and must outlive the operator.
+    pub fn from_handle(handle: Handle) -> Self {
+        Self {
+            runtime: BlockingRuntime::Handle(handle),
+        }
+    }
+
+    /// Create a new `BlockingLayer` that owns a current-thread runtime.
+    ///
+    /// The runtime will be created lazily on the first blocking call and shared by all
+    /// accessors built from this layer.
+    pub fn new_owned_runtime() -> Self {
+        Self {
+            runtime: BlockingRuntime::Owned(Arc::default()),
+        }
+    }
+}
+
+/// The runtime used by `BlockingLayer` to drive async calls.
+#[derive(Debug, Clone)]
+enum BlockingRuntime {
+    Handle(Handle),
+    Owned(Arc<OwnedRuntime>),
+}
+
+impl BlockingRuntime {
+    fn block_on<T>(&self, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        match self {
+            BlockingRuntime::Handle(handle) => handle.block_on(fut),
+            BlockingRuntime::Owned(rt) => rt.get()?.block_on(fut),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct OwnedRuntime(OnceCell<Runtime>);
+
+impl OwnedRuntime {
+    fn get(&self) -> Result<&Runtime> {
+        self.0.get_or_try_init(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "failed to build tokio runtime")
+                        .set_source(err)
+                })
         })
     }
 }
 
+impl Drop for OwnedRuntime {
+    fn drop(&mut self) {
+        // Dropping a runtime inside an async context panics, shutdown it in background instead.
+        if let Some(rt) = self.0.take() {
+            rt.shutdown_background();
+        }
+    }
+}
+
 impl<A: Access> Layer<A> for BlockingLayer {
     type LayeredAccess = BlockingAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         BlockingAccessor {
             inner,
-            handle: self.handle.clone(),
+            runtime: self.runtime.clone(),
         }
     }
 }
@@ -157,7 +268,7 @@ impl<A: Access> Layer<A> for BlockingLayer {
 pub struct BlockingAccessor<A: Access> {
     inner: A,
 
-    handle: Handle,
+    runtime: BlockingRuntime,
 }
 
 impl<A: Access> LayeredAccess for BlockingAccessor<A> {
@@ -220,81 +331,81 @@ impl<A: Access> LayeredAccess for BlockingAccessor<A> {
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        self.handle.block_on(self.inner.create_dir(path, args))
+        self.runtime.block_on(self.inner.create_dir(path, args))
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        self.handle.block_on(async {
+        self.runtime.block_on(async {
             let (rp, reader) = self.inner.read(path, args).await?;
-            let blocking_reader = Self::BlockingReader::new(self.handle.clone(), reader);
+            let blocking_reader = Self::BlockingReader::new(self.runtime.clone(), reader);
 
             Ok((rp, blocking_reader))
         })
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        self.handle.block_on(async {
+        self.runtime.block_on(async {
             let (rp, writer) = self.inner.write(path, args).await?;
-            let blocking_writer = Self::BlockingWriter::new(self.handle.clone(), writer);
+            let blocking_writer = Self::BlockingWriter::new(self.runtime.clone(), writer);
             Ok((rp, blocking_writer))
         })
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        self.handle.block_on(self.inner.copy(from, to, args))
+        self.runtime.block_on(self.inner.copy(from, to, args))
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        self.handle.block_on(self.inner.rename(from, to, args))
+        self.runtime.block_on(self.inner.rename(from, to, args))
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        self.handle.block_on(self.inner.stat(path, args))
+        self.runtime.block_on(self.inner.stat(path, args))
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        self.handle.block_on(self.inner.delete(path, args))
+        self.runtime.block_on(self.inner.delete(path, args))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        self.handle.block_on(async {
+        self.runtime.block_on(async {
             let (rp, lister) = self.inner.list(path, args).await?;
-            let blocking_lister = Self::BlockingLister::new(self.handle.clone(), lister);
+            let blocking_lister = Self::BlockingLister::new(self.runtime.clone(), lister);
             Ok((rp, blocking_lister))
         })
     }
 }
 
 pub struct BlockingWrapper<I> {
-    handle: Handle,
+    runtime: BlockingRuntime,
     inner: I,
 }
 
 impl<I> BlockingWrapper<I> {
-    fn new(handle: Handle, inner: I) -> Self {
-        Self { handle, inner }
+    fn new(runtime: BlockingRuntime, inner: I) -> Self {
+        Self { runtime, inner }
     }
 }
 
 impl<I: oio::Read + 'static> oio::BlockingRead for BlockingWrapper<I> {
     fn read(&mut self) -> Result<Buffer> {
-        self.handle.block_on(self.inner.read())
+        self.runtime.block_on(self.inner.read())
     }
 }
 
 impl<I: oio::Write + 'static> oio::BlockingWrite for BlockingWrapper<I> {
     fn write(&mut self, bs: Buffer) -> Result<()> {
-        self.handle.block_on(self.inner.write(bs))
+        self.runtime.block_on(self.inner.write(bs))
     }
 
-    fn close(&mut self) -> Result<()> {
-        self.handle.block_on(self.inner.close())
+    fn close(&mut self) -> Result<Metadata> {
+        self.runtime.block_on(self.inner.close())
     }
 }
 
 impl<I: oio::List> oio::BlockingList for BlockingWrapper<I> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.handle.block_on(self.inner.next())
+        self.runtime.block_on(self.inner.next())
     }
 }
 
@@ -328,6 +439,33 @@ mod tests {
         assert!(layer.is_ok())
     }
 
+    #[test]
+    fn test_blocking_layer_from_handle() {
+        let op = Operator::new(crate::services::Memory::default())
+            .unwrap()
+            .layer(BlockingLayer::from_handle(RUNTIME.handle().clone()))
+            .finish()
+            .blocking();
+
+        op.write("test", "abcd").expect("write must succeed");
+        assert_eq!(op.read("test").unwrap().to_vec(), b"abcd");
+    }
+
+    #[test]
+    fn test_blocking_layer_with_owned_runtime() {
+        let op = Operator::new(crate::services::Memory::default())
+            .unwrap()
+            .layer(BlockingLayer::new_owned_runtime())
+            .finish()
+            .blocking();
+
+        let handle = std::thread::spawn(move || {
+            op.write("test", "abcd").expect("write must succeed");
+            op.read("test").expect("read must succeed").to_vec()
+        });
+        assert_eq!(handle.join().unwrap(), b"abcd");
+    }
+
     #[test]
     fn test_blocking_layer_in_async_context() {
         // create and drop in an async context
@@ -0,0 +1,312 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Override capabilities of the underlying services, mostly for testing.
+///
+/// # Notes
+///
+/// CapabilityOverrideLayer changes both native and full capabilities with
+/// the given function. Operations whose capability has been turned off will
+/// return [`ErrorKind::Unsupported`] without reaching the underlying services.
+///
+/// Capabilities can be masked to exercise fallback paths, for example,
+/// pretend that the service doesn't support `copy` or `list_with_recursive`.
+/// Turning on capabilities that the underlying services don't support is
+/// not checked and will likely lead to errors.
+///
+/// OpenDAL emulates some capabilities (like `list_with_recursive`) based on
+/// native capabilities while building operators. To exercise these emulated
+/// code paths, apply this layer on the service before building operators
+/// via [`OperatorBuilder::new`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::CapabilityOverrideLayer;
+/// # use opendal::raw::Layer;
+/// # use opendal::services;
+/// # use opendal::Builder;
+/// # use opendal::Operator;
+/// # use opendal::OperatorBuilder;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let layer = CapabilityOverrideLayer::new(|cap| {
+///     cap.copy = false;
+///     cap.list_with_recursive = false;
+/// });
+///
+/// // Mask capabilities on operator.
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(layer.clone())
+///     .finish();
+///
+/// // Mask capabilities on service, so that recursive listing will be emulated.
+/// let acc = services::Memory::default().build()?;
+/// let _ = OperatorBuilder::new(layer.layer(acc)).finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CapabilityOverrideLayer {
+    f: Arc<dyn Fn(&mut Capability) + Send + Sync>,
+}
+
+impl CapabilityOverrideLayer {
+    /// Create a new `CapabilityOverrideLayer` that overrides capabilities
+    /// with given function.
+    pub fn new(f: impl Fn(&mut Capability) + Send + Sync + 'static) -> Self {
+        Self { f: Arc::new(f) }
+    }
+}
+
+impl<A: Access> Layer<A> for CapabilityOverrideLayer {
+    type LayeredAccess = CapabilityOverrideAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let mut info = (*inner.info()).clone();
+
+        let mut native = info.native_capability();
+        (self.f)(&mut native);
+        let mut full = info.full_capability();
+        (self.f)(&mut full);
+        info.set_native_capability(native);
+        *info.full_capability_mut() = full;
+
+        CapabilityOverrideAccessor {
+            inner,
+            info: Arc::new(info),
+        }
+    }
+}
+
+pub struct CapabilityOverrideAccessor<A: Access> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+}
+
+impl<A: Access> Debug for CapabilityOverrideAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<A: Access> CapabilityOverrideAccessor<A> {
+    fn check(&self, op: Operation, supported: bool) -> Result<()> {
+        if supported {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation has been disabled by capability override",
+        )
+        .with_operation(op))
+    }
+}
+
+impl<A: Access> LayeredAccess for CapabilityOverrideAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let cap = self.info.full_capability();
+        self.check(Operation::CreateDir, cap.create_dir)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Read, cap.read)?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Write, cap.write)?;
+        self.check(Operation::Write, !args.append() || cap.write_can_append)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Copy, cap.copy)?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Rename, cap.rename)?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Stat, cap.stat)?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Delete, cap.delete)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::List, cap.list)?;
+        self.check(
+            Operation::List,
+            !args.recursive() || cap.list_with_recursive,
+        )?;
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Batch, cap.batch)?;
+        self.inner.batch(args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let cap = self.info.full_capability();
+        self.check(Operation::Presign, cap.presign)?;
+        self.inner.presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingCreateDir, cap.create_dir && cap.blocking)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingRead, cap.read && cap.blocking)?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingWrite, cap.write && cap.blocking)?;
+        self.check(
+            Operation::BlockingWrite,
+            !args.append() || cap.write_can_append,
+        )?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingCopy, cap.copy && cap.blocking)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingRename, cap.rename && cap.blocking)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingStat, cap.stat && cap.blocking)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingDelete, cap.delete && cap.blocking)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let cap = self.info.full_capability();
+        self.check(Operation::BlockingList, cap.list && cap.blocking)?;
+        self.check(
+            Operation::BlockingList,
+            !args.recursive() || cap.list_with_recursive,
+        )?;
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_capability_override() {
+        let layer = CapabilityOverrideLayer::new(|cap| {
+            cap.copy = false;
+            cap.list_with_recursive = false;
+        });
+
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(layer.clone())
+            .finish();
+        assert!(!op.info().full_capability().copy);
+        op.write("dir/file", "abcd")
+            .await
+            .expect("write must succeed");
+        let err = op
+            .copy("dir/file", "dir/copied")
+            .await
+            .expect_err("copy must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        // Recursive listing is emulated while masked on service.
+        let acc = services::Memory::default().build().unwrap();
+        let op = OperatorBuilder::new(layer.layer(acc)).finish();
+        assert!(!op.info().native_capability().list_with_recursive);
+        op.write("dir/sub/file", "abcd")
+            .await
+            .expect("write must succeed");
+        let entries: Vec<_> = op
+            .lister_with("dir/")
+            .recursive(true)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(entries.iter().any(|e| e.path() == "dir/sub/file"));
+    }
+}
@@ -17,6 +17,7 @@
 
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use rand::prelude::*;
 use rand::rngs::StdRng;
@@ -34,25 +35,41 @@ use crate::*;
 /// Running tests under ChaosLayer will make your application more robust.
 ///
 /// For example: If we specify an error rate of 0.5, there is a 50% chance
-/// of an EOF error for every read operation.
+/// of an error for every read operation.
 ///
-/// # Note
+/// ChaosLayer can inject the following failures:
 ///
-/// For now, ChaosLayer only injects read operations. More operations may
-/// be added in the future.
+/// - Errors: every `Reader::read` will fail with given error ratio. Errors can be
+///   injected into all operations (including `stat`, `Writer::write` and so on)
+///   by [`ChaosLayer::with_all_operations`]. The error kind will be picked from
+///   [`ChaosLayer::with_error_kinds`] randomly, default to [`ErrorKind::Unexpected`].
+///   All injected errors are temporary so that they can be retried.
+/// - Latency: every operation will be delayed by a random duration up to
+///   [`ChaosLayer::with_latency`].
+/// - Truncated reads: readers will stop early without any error with given
+///   [`ChaosLayer::with_truncate_ratio`].
+/// - Slow writes: every `Writer::write` will be delayed by
+///   [`ChaosLayer::with_write_latency`].
 ///
 /// # Examples
 ///
 /// ```no_run
+/// # use std::time::Duration;
 /// # use opendal::layers::ChaosLayer;
 /// # use opendal::services;
+/// # use opendal::ErrorKind;
 /// # use opendal::Operator;
 /// # use opendal::Result;
 /// # use opendal::Scheme;
 ///
 /// # fn main() -> Result<()> {
 /// let _ = Operator::new(services::Memory::default())?
-///     .layer(ChaosLayer::new(0.1))
+///     .layer(
+///         ChaosLayer::new(0.1)
+///             .with_error_kinds(vec![ErrorKind::Unexpected, ErrorKind::RateLimited])
+///             .with_latency(Duration::from_millis(100))
+///             .with_truncate_ratio(0.05),
+///     )
 ///     .finish();
 /// Ok(())
 /// # }
@@ -60,6 +77,11 @@ use crate::*;
 #[derive(Debug, Clone)]
 pub struct ChaosLayer {
     error_ratio: f64,
+    error_kinds: Vec<ErrorKind>,
+    all_operations: bool,
+    latency: Option<Duration>,
+    truncate_ratio: f64,
+    write_latency: Option<Duration>,
 }
 
 impl ChaosLayer {
@@ -73,7 +95,65 @@ impl ChaosLayer {
             (0.0..=1.0).contains(&error_ratio),
             "error_ratio must between 0.0 and 1.0"
         );
-        Self { error_ratio }
+        Self {
+            error_ratio,
+            error_kinds: vec![ErrorKind::Unexpected],
+            all_operations: false,
+            latency: None,
+            truncate_ratio: 0.0,
+            write_latency: None,
+        }
+    }
+
+    /// Set the error kinds that could be injected.
+    ///
+    /// The kind of every injected error will be picked from given kinds randomly.
+    ///
+    /// # Panics
+    ///
+    /// Input kinds must not be empty.
+    pub fn with_error_kinds(mut self, kinds: Vec<ErrorKind>) -> Self {
+        assert!(!kinds.is_empty(), "error_kinds must not be empty");
+        self.error_kinds = kinds;
+        self
+    }
+
+    /// Set whether to inject errors into all operations.
+    ///
+    /// By default, errors are only injected into `Reader::read`.
+    pub fn with_all_operations(mut self, enabled: bool) -> Self {
+        self.all_operations = enabled;
+        self
+    }
+
+    /// Set the max latency that will be added to every operation.
+    ///
+    /// The added latency is picked from `[0, latency]` randomly.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Set the ratio of readers that will be truncated.
+    ///
+    /// A truncated reader will return part of the data and then EOF without any error.
+    ///
+    /// # Panics
+    ///
+    /// Input truncate_ratio must in [0.0..=1.0]
+    pub fn with_truncate_ratio(mut self, truncate_ratio: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&truncate_ratio),
+            "truncate_ratio must between 0.0 and 1.0"
+        );
+        self.truncate_ratio = truncate_ratio;
+        self
+    }
+
+    /// Set the latency that will be added to every `Writer::write`.
+    pub fn with_write_latency(mut self, latency: Duration) -> Self {
+        self.write_latency = Some(latency);
+        self
     }
 }
 
@@ -83,26 +163,104 @@ impl<A: Access> Layer<A> for ChaosLayer {
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         ChaosAccessor {
             inner,
-            rng: StdRng::from_entropy(),
-            error_ratio: self.error_ratio,
+            chaos: Arc::new(Chaos {
+                rng: Mutex::new(StdRng::from_entropy()),
+                error_ratio: self.error_ratio,
+                error_kinds: self.error_kinds.clone(),
+                all_operations: self.all_operations,
+                latency: self.latency,
+                truncate_ratio: self.truncate_ratio,
+                write_latency: self.write_latency,
+            }),
         }
     }
 }
 
+/// Chaos holds the shared state to decide which failure to inject.
 #[derive(Debug)]
-pub struct ChaosAccessor<A> {
-    inner: A,
-    rng: StdRng,
+struct Chaos {
+    rng: Mutex<StdRng>,
 
     error_ratio: f64,
+    error_kinds: Vec<ErrorKind>,
+    all_operations: bool,
+    latency: Option<Duration>,
+    truncate_ratio: f64,
+    write_latency: Option<Duration>,
+}
+
+impl Chaos {
+    /// If I feel lucky, we can return the correct response. Otherwise,
+    /// we need to generate a failure.
+    fn i_feel_lucky(&self, ratio: f64) -> bool {
+        self.rng.lock().unwrap().gen::<f64>() >= ratio
+    }
+
+    /// Return an error with given ratio.
+    fn check(&self, op: Operation) -> Result<()> {
+        let enabled = self.all_operations
+            || matches!(op, Operation::ReaderRead | Operation::BlockingReaderRead);
+        if !enabled || self.i_feel_lucky(self.error_ratio) {
+            return Ok(());
+        }
+
+        let kind = *self
+            .error_kinds
+            .choose(&mut *self.rng.lock().unwrap())
+            .expect("error kinds must not be empty");
+        Err(Error::new(kind, "I am your chaos!")
+            .with_operation(op)
+            .with_context("chaos", "injected")
+            .set_temporary())
+    }
+
+    /// Pick a random latency in `[0, latency]`.
+    fn latency(&self) -> Option<Duration> {
+        let latency = self.latency?;
+        let nanos = self
+            .rng
+            .lock()
+            .unwrap()
+            .gen_range(0..=latency.as_nanos() as u64);
+        Some(Duration::from_nanos(nanos))
+    }
+
+    async fn delay(&self) {
+        if let Some(d) = self.latency() {
+            tokio::time::sleep(d).await;
+        }
+    }
+
+    fn blocking_delay(&self) {
+        if let Some(d) = self.latency() {
+            std::thread::sleep(d);
+        }
+    }
+
+    /// Apply latency and then inject error for given operation.
+    async fn inject(&self, op: Operation) -> Result<()> {
+        self.delay().await;
+        self.check(op)
+    }
+
+    fn blocking_inject(&self, op: Operation) -> Result<()> {
+        self.blocking_delay();
+        self.check(op)
+    }
+}
+
+#[derive(Debug)]
+pub struct ChaosAccessor<A> {
+    inner: A,
+    chaos: Arc<Chaos>,
 }
 
 impl<A: Access> LayeredAccess for ChaosAccessor<A> {
     type Inner = A;
     type Reader = ChaosReader<A::Reader>;
     type BlockingReader = ChaosReader<A::BlockingReader>;
-    type Writer = A::Writer;
-    type BlockingWriter = A::BlockingWriter;
+    type Writer = ChaosWriter<A::Writer>;
+    type BlockingWriter = ChaosWriter<A::BlockingWriter>;
     type Lister = A::Lister;
     type BlockingLister = A::BlockingLister;
 
@@ -110,32 +268,98 @@ impl<A: Access> LayeredAccess for ChaosAccessor<A> {
         &self.inner
     }
 
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.chaos.inject(Operation::CreateDir).await?;
+        self.inner.create_dir(path, args).await
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.chaos.inject(Operation::Read).await?;
         self.inner
             .read(path, args)
             .await
-            .map(|(rp, r)| (rp, ChaosReader::new(r, self.rng.clone(), self.error_ratio)))
+            .map(|(rp, r)| (rp, ChaosReader::new(r, self.chaos.clone())))
     }
 
-    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.chaos.inject(Operation::Write).await?;
         self.inner
-            .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ChaosReader::new(r, self.rng.clone(), self.error_ratio)))
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, ChaosWriter::new(w, self.chaos.clone())))
     }
 
-    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        self.inner.write(path, args).await
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.chaos.inject(Operation::Copy).await?;
+        self.inner.copy(from, to, args).await
     }
 
-    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        self.inner.blocking_write(path, args)
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.chaos.inject(Operation::Rename).await?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.chaos.inject(Operation::Stat).await?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.chaos.inject(Operation::Delete).await?;
+        self.inner.delete(path, args).await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.chaos.inject(Operation::List).await?;
         self.inner.list(path, args).await
     }
 
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.chaos.inject(Operation::Batch).await?;
+        self.inner.batch(args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.chaos.blocking_inject(Operation::BlockingCreateDir)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.chaos.blocking_inject(Operation::BlockingRead)?;
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, ChaosReader::new(r, self.chaos.clone())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.chaos.blocking_inject(Operation::BlockingWrite)?;
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, ChaosWriter::new(w, self.chaos.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.chaos.blocking_inject(Operation::BlockingCopy)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.chaos.blocking_inject(Operation::BlockingRename)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.chaos.blocking_inject(Operation::BlockingStat)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.chaos.blocking_inject(Operation::BlockingDelete)?;
+        self.inner.blocking_delete(path, args)
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.chaos.blocking_inject(Operation::BlockingList)?;
         self.inner.blocking_list(path, args)
     }
 }
@@ -143,50 +367,129 @@ impl<A: Access> LayeredAccess for ChaosAccessor<A> {
 /// ChaosReader will inject error into read operations.
 pub struct ChaosReader<R> {
     inner: R,
-    rng: Arc<Mutex<StdRng>>,
+    chaos: Arc<Chaos>,
 
-    error_ratio: f64,
+    /// Whether this reader should be truncated.
+    truncate: bool,
+    /// Whether this reader has been truncated, all following reads will return EOF.
+    truncated: bool,
 }
 
 impl<R> ChaosReader<R> {
-    fn new(inner: R, rng: StdRng, error_ratio: f64) -> Self {
+    fn new(inner: R, chaos: Arc<Chaos>) -> Self {
+        let truncate = !chaos.i_feel_lucky(chaos.truncate_ratio);
         Self {
             inner,
-            rng: Arc::new(Mutex::new(rng)),
-            error_ratio,
+            chaos,
+            truncate,
+            truncated: false,
         }
     }
 
-    /// If I feel lucky, we can return the correct response. Otherwise,
-    /// we need to generate an error.
-    fn i_feel_lucky(&self) -> bool {
-        let point = self.rng.lock().unwrap().gen_range(0..=100);
-        point >= (self.error_ratio * 100.0) as i32
-    }
-
-    fn unexpected_eof() -> Error {
-        Error::new(ErrorKind::Unexpected, "I am your chaos!")
-            .with_operation("chaos")
-            .set_temporary()
+    /// Truncate the given buffer to half if this reader should be truncated.
+    fn maybe_truncate(&mut self, mut buf: Buffer) -> Buffer {
+        if self.truncate && !buf.is_empty() {
+            buf.truncate(buf.len() / 2);
+            self.truncated = true;
+        }
+        buf
     }
 }
 
 impl<R: oio::Read> oio::Read for ChaosReader<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        if self.i_feel_lucky() {
-            self.inner.read().await
-        } else {
-            Err(Self::unexpected_eof())
+        if self.truncated {
+            return Ok(Buffer::new());
         }
+
+        self.chaos.inject(Operation::ReaderRead).await?;
+        let buf = self.inner.read().await?;
+        Ok(self.maybe_truncate(buf))
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ChaosReader<R> {
     fn read(&mut self) -> Result<Buffer> {
-        if self.i_feel_lucky() {
-            self.inner.read()
-        } else {
-            Err(Self::unexpected_eof())
+        if self.truncated {
+            return Ok(Buffer::new());
         }
+
+        self.chaos.blocking_inject(Operation::BlockingReaderRead)?;
+        let buf = self.inner.read()?;
+        Ok(self.maybe_truncate(buf))
+    }
+}
+
+/// ChaosWriter will inject error and latency into write operations.
+pub struct ChaosWriter<W> {
+    inner: W,
+    chaos: Arc<Chaos>,
+}
+
+impl<W> ChaosWriter<W> {
+    fn new(inner: W, chaos: Arc<Chaos>) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+impl<W: oio::Write> oio::Write for ChaosWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        if let Some(d) = self.chaos.write_latency {
+            tokio::time::sleep(d).await;
+        }
+        self.chaos.inject(Operation::WriterWrite).await?;
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.chaos.inject(Operation::WriterClose).await?;
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for ChaosWriter<W> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        if let Some(d) = self.chaos.write_latency {
+            std::thread::sleep(d);
+        }
+        self.chaos.blocking_inject(Operation::BlockingWriterWrite)?;
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        self.chaos.blocking_inject(Operation::BlockingWriterClose)?;
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_chaos_scope() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+
+        // Only reads are injected by default.
+        let chaos = op.clone().layer(ChaosLayer::new(1.0));
+        chaos.stat("test").await?;
+        let err = chaos.read("test").await.unwrap_err();
+        assert!(err.is_temporary());
+
+        let chaos = op.layer(ChaosLayer::new(1.0).with_all_operations(true));
+        let err = chaos.stat("test").await.unwrap_err();
+        assert!(err.is_temporary());
+
+        let chaos = Operator::new(services::Memory::default())?
+            .layer(ChaosLayer::new(0.0).with_all_operations(true))
+            .finish();
+        chaos.write("test", "Hello, World!").await?;
+        assert_eq!(chaos.read("test").await?.to_vec(), b"Hello, World!");
+        Ok(())
     }
 }
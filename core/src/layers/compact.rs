@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::maintenance::Manifest;
+use crate::maintenance::PackedObject;
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// Serve objects packed by [`maintenance::compact`](crate::maintenance::compact)
+/// by their original paths.
+///
+/// # Compact
+///
+/// Reads and stats under the compacted prefixes go to the underlying
+/// services first. Objects that are not found will be looked up in the
+/// manifest of the prefix and served from their packfiles instead.
+///
+/// Deleting a packed object or writing to its path removes it from the
+/// manifest, so that the packed copy won't be served again.
+///
+/// # Notes
+///
+/// - The manifest is cached in memory and reloaded when an object is not
+///   found in the cached one.
+/// - Packed objects will not show up in list.
+/// - Only async operations are supported, blocking operations are forwarded
+///   to the underlying services directly.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::CompactLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(CompactLayer::new().with_prefix("logs/2024-01-01/"))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CompactLayer {
+    prefixes: Vec<String>,
+}
+
+impl CompactLayer {
+    /// Create a new `CompactLayer` without any prefix.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve packed objects under given prefix.
+    ///
+    /// Call this function multiple times to add more prefixes.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefixes.push(prefix.to_string());
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for CompactLayer {
+    type LayeredAccess = CompactAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        CompactAccessor {
+            inner,
+            prefixes: Arc::new(
+                self.prefixes
+                    .iter()
+                    .map(|prefix| (prefix.clone(), Mutex::new(None)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The compacted prefixes and their cached manifests.
+type CachedManifests = Vec<(String, Mutex<Option<Arc<Manifest>>>)>;
+
+#[derive(Debug)]
+pub struct CompactAccessor<A: Access> {
+    inner: A,
+    prefixes: Arc<CachedManifests>,
+}
+
+impl<A: Access> CompactAccessor<A> {
+    fn find_prefix(&self, path: &str) -> Option<&(String, Mutex<Option<Arc<Manifest>>>)> {
+        self.prefixes
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+    }
+
+    /// Find the packed object of the path.
+    async fn lookup(&self, path: &str) -> Result<Option<PackedObject>> {
+        let Some((prefix, cache)) = self.find_prefix(path) else {
+            return Ok(None);
+        };
+
+        let cached = cache.lock().expect("lock must succeed").clone();
+        if let Some(obj) = cached.and_then(|v| v.objects.get(path).cloned()) {
+            return Ok(Some(obj));
+        }
+
+        let manifest = Arc::new(self.load_manifest(prefix).await?);
+        let obj = manifest.objects.get(path).cloned();
+        *cache.lock().expect("lock must succeed") = Some(manifest);
+        Ok(obj)
+    }
+
+    async fn load_manifest(&self, prefix: &str) -> Result<Manifest> {
+        let path = Manifest::path(prefix);
+        match self.inner.read(&path, OpRead::new()).await {
+            Ok((_, mut r)) => Manifest::from_slice(&r.read_all().await?.to_bytes()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Remove the path from the manifest and write it back.
+    async fn forget(&self, path: &str) -> Result<()> {
+        let Some((prefix, cache)) = self.find_prefix(path) else {
+            return Ok(());
+        };
+
+        let mut manifest = self.load_manifest(prefix).await?;
+        if manifest.objects.remove(path).is_some() {
+            let bs = Buffer::from(manifest.to_vec()?);
+            let (_, mut w) = self
+                .inner
+                .write(&Manifest::path(prefix), OpWrite::new())
+                .await?;
+            oio::Write::write(&mut w, bs).await?;
+            oio::Write::close(&mut w).await?;
+        }
+        *cache.lock().expect("lock must succeed") = Some(Arc::new(manifest));
+        Ok(())
+    }
+}
+
+impl<A: Access> LayeredAccess for CompactAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let err = match self.inner.read(path, args.clone()).await {
+            Err(err) if err.kind() == ErrorKind::NotFound => err,
+            res => return res,
+        };
+        let Some(obj) = self.lookup(path).await? else {
+            return Err(err);
+        };
+
+        let range = args.range();
+        let offset = range.offset().min(obj.size);
+        let size = match range.size() {
+            Some(size) => size.min(obj.size - offset),
+            None => obj.size - offset,
+        };
+        let args = args.with_range(BytesRange::new(obj.offset + offset, Some(size)));
+        self.inner.read(&obj.pack, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // The new content shadows the packed one, make sure the packed one
+        // won't be served again after the new content is deleted.
+        if self.lookup(path).await?.is_some() {
+            self.forget(path).await?;
+        }
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let err = match self.inner.stat(path, args).await {
+            Err(err) if err.kind() == ErrorKind::NotFound => err,
+            res => return res,
+        };
+        match self.lookup(path).await? {
+            Some(obj) => Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(obj.size),
+            )),
+            None => Err(err),
+        }
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if self.find_prefix(path).is_none() {
+            return self.inner.delete(path, args).await;
+        }
+
+        // Deleting the original objects that have been packed, which
+        // happens while compacting, should keep the packed ones.
+        let exists = match self.inner.stat(path, OpStat::new()).await {
+            Ok(_) => true,
+            Err(err) if err.kind() == ErrorKind::NotFound => false,
+            Err(err) => return Err(err),
+        };
+        let rp = self.inner.delete(path, args).await?;
+        if !exists {
+            self.forget(path).await?;
+        }
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maintenance;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_compact() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(CompactLayer::new().with_prefix("logs/"))
+            .finish();
+
+        for i in 0..5 {
+            op.write(&format!("logs/{i}"), format!("event-{i}")).await?;
+        }
+        op.write("logs/large", vec![0; 32]).await?;
+
+        let summary = maintenance::compact(&op, "logs/", 16).await?;
+        assert_eq!(summary.objects, 5);
+        assert_eq!(summary.packs, 2);
+        assert_eq!(summary.bytes, 35);
+
+        // Packed objects are removed but still readable.
+        assert!(!op.list("logs/").await?.iter().any(|e| e.path() == "logs/1"));
+        assert_eq!(op.read("logs/1").await?.to_vec(), b"event-1");
+        assert_eq!(op.read_with("logs/3").range(2..).await?.to_vec(), b"ent-3");
+        assert_eq!(op.stat("logs/4").await?.content_length(), 7);
+        assert_eq!(op.read("logs/large").await?.len(), 32);
+
+        op.delete("logs/1").await?;
+        assert_eq!(
+            op.read("logs/1").await.unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(op.read("logs/2").await?.to_vec(), b"event-2");
+
+        // New content shadows the packed one.
+        op.write("logs/2", "new").await?;
+        assert_eq!(op.read("logs/2").await?.to_vec(), b"new");
+        op.delete("logs/2").await?;
+        assert!(!op.exists("logs/2").await?);
+
+        Ok(())
+    }
+}
@@ -139,6 +139,27 @@ impl<A: Access> CompleteAccessor<A> {
         .with_operation(op)
     }
 
+    /// Customer-provided keys must not be ignored, otherwise objects will be
+    /// written without encryption.
+    fn check_customer_key(
+        &self,
+        op: Operation,
+        key: Option<&CustomerEncryptionKey>,
+        supported: bool,
+    ) -> Result<()> {
+        if key.is_none() || supported {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!(
+                "service {} doesn't support operation {op} with customer key",
+                self.info.scheme()
+            ),
+        )
+        .with_operation(op))
+    }
+
     async fn complete_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         let capability = self.info.full_capability();
         if capability.create_dir {
@@ -172,6 +193,11 @@ impl<A: Access> CompleteAccessor<A> {
         if !capability.stat {
             return Err(self.new_unsupported_error(Operation::Stat));
         }
+        self.check_customer_key(
+            Operation::Stat,
+            args.customer_key(),
+            capability.stat_with_customer_key,
+        )?;
 
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -217,6 +243,11 @@ impl<A: Access> CompleteAccessor<A> {
         if !capability.stat {
             return Err(self.new_unsupported_error(Operation::Stat));
         }
+        self.check_customer_key(
+            Operation::BlockingStat,
+            args.customer_key(),
+            capability.stat_with_customer_key,
+        )?;
 
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -278,11 +309,13 @@ impl<A: Access> CompleteAccessor<A> {
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let p = FlatLister::new(self.inner.clone(), path)
+                        .with_cycle_policy(args.cycle_policy());
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let p = FlatLister::new(self.inner.clone(), parent)
+                        .with_cycle_policy(args.cycle_policy());
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -326,11 +359,13 @@ impl<A: Access> CompleteAccessor<A> {
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let p = FlatLister::new(self.inner.clone(), path)
+                        .with_cycle_policy(args.cycle_policy());
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let p = FlatLister::new(self.inner.clone(), parent)
+                        .with_cycle_policy(args.cycle_policy());
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -385,6 +420,21 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.read {
             return Err(self.new_unsupported_error(Operation::Read));
         }
+        if args.select().is_some() && !capability.read_with_select {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation read with select",
+                    self.info.scheme()
+                ),
+            )
+            .with_operation(Operation::Read));
+        }
+        self.check_customer_key(
+            Operation::Read,
+            args.customer_key(),
+            capability.read_with_customer_key,
+        )?;
 
         let size = args.range().size();
         self.inner
@@ -407,6 +457,11 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
                 ),
             ));
         }
+        self.check_customer_key(
+            Operation::Write,
+            args.customer_key(),
+            capability.write_with_customer_key,
+        )?;
 
         let (rp, w) = self.inner.write(path, args.clone()).await?;
         let w = CompleteWriter::new(w);
@@ -449,6 +504,16 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.list {
             return Err(self.new_unsupported_error(Operation::List));
         }
+        if args.query().is_some() && !capability.list_with_query {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation list with query",
+                    self.info.scheme()
+                ),
+            )
+            .with_operation(Operation::List));
+        }
 
         self.complete_list(path, args).await
     }
@@ -458,6 +523,14 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.batch {
             return Err(self.new_unsupported_error(Operation::Batch));
         }
+        if !capability.batch_copy
+            && args
+                .operation()
+                .iter()
+                .any(|(_, op)| matches!(op, BatchOperation::Copy(_, _)))
+        {
+            return Err(self.new_unsupported_error(Operation::Batch));
+        }
 
         self.inner().batch(args).await
     }
@@ -471,6 +544,41 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         self.inner.presign(path, args).await
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        let capability = self.info.full_capability();
+        if !capability.sign {
+            return Err(self.new_unsupported_error(Operation::Sign));
+        }
+
+        self.inner.sign(args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let capability = self.info.full_capability();
+        if !capability.list_multipart_uploads {
+            return Err(self.new_unsupported_error(Operation::ListMultipartUploads));
+        }
+
+        self.inner.list_multipart_uploads(path, args).await
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let capability = self.info.full_capability();
+        if !capability.abort_multipart_upload {
+            return Err(self.new_unsupported_error(Operation::AbortMultipartUpload));
+        }
+
+        self.inner.abort_multipart_upload(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.complete_blocking_create_dir(path, args)
     }
@@ -480,6 +588,11 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.read || !capability.blocking {
             return Err(self.new_unsupported_error(Operation::Read));
         }
+        self.check_customer_key(
+            Operation::BlockingRead,
+            args.customer_key(),
+            capability.read_with_customer_key,
+        )?;
 
         let size = args.range().size();
         self.inner
@@ -502,6 +615,11 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
                 ),
             ));
         }
+        self.check_customer_key(
+            Operation::BlockingWrite,
+            args.customer_key(),
+            capability.write_with_customer_key,
+        )?;
 
         self.inner
             .blocking_write(path, args)
@@ -544,6 +662,16 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.list || !capability.blocking {
             return Err(self.new_unsupported_error(Operation::BlockingList));
         }
+        if args.query().is_some() && !capability.list_with_query {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation list with query",
+                    self.info.scheme()
+                ),
+            )
+            .with_operation(Operation::BlockingList));
+        }
 
         self.complete_blocking_list(path, args)
     }
@@ -649,15 +777,15 @@ where
         w.write(bs).await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.close().await?;
+        let meta = w.close().await?;
         self.inner = None;
 
-        Ok(())
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -684,14 +812,14 @@ where
         w.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.close()?;
+        let meta = w.close()?;
         self.inner = None;
-        Ok(())
+        Ok(meta)
     }
 }
 
@@ -745,7 +873,7 @@ mod tests {
         }
 
         async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
-            Ok((RpList {}, Box::new(())))
+            Ok((RpList::default(), Box::new(())))
         }
 
         async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
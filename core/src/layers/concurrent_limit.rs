@@ -16,11 +16,14 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::Semaphore;
 
+use super::adaptive::Aimd;
 use crate::raw::*;
 use crate::*;
 
@@ -31,6 +34,39 @@ use crate::*;
 /// Users can control how many concurrent connections could be established
 /// between OpenDAL and underlying storage services.
 ///
+/// ## Per-operation limits
+///
+/// Besides the global permits, users can set separate limits for different
+/// kinds of operations:
+///
+/// - [`ConcurrentLimitLayer::with_read_permits`] limits `read` and `stat`.
+/// - [`ConcurrentLimitLayer::with_write_permits`] limits `write`,
+///   `create_dir`, `delete` and `batch`.
+/// - [`ConcurrentLimitLayer::with_list_permits`] limits `list`.
+///
+/// An operation must acquire both its own permit and a global permit before
+/// it can be sent to the underlying services.
+///
+/// ## Waiting queue
+///
+/// By default, requests wait for permits without bound. Users can set
+/// [`ConcurrentLimitLayer::with_max_waiting`] to bound the number of waiting
+/// requests, requests beyond the bound fail immediately with
+/// [`ErrorKind::RateLimited`].
+///
+/// Blocking operations never wait: they fail with [`ErrorKind::RateLimited`]
+/// if no permit is available.
+///
+/// ## Adaptive limit
+///
+/// Users can set [`ConcurrentLimitLayer::with_adaptive`] to follow the
+/// feedback of services. Once services throttle requests (returning
+/// [`ErrorKind::RateLimited`], `429 Too Many Requests` or `503 Service
+/// Unavailable`), the global permits will be halved and held until
+/// `Retry-After` (1s if not returned). After that, the permits will be
+/// increased by one every `permits` successful requests until reaching the
+/// configured permits.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -47,15 +83,83 @@ use crate::*;
 /// Ok(())
 /// # }
 /// ```
+///
+/// Limit reads and writes separately:
+///
+/// ```no_run
+/// # use opendal::layers::ConcurrentLimitLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         ConcurrentLimitLayer::new(128)
+///             .with_read_permits(64)
+///             .with_write_permits(8)
+///             .with_max_waiting(1024),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
 #[derive(Clone)]
 pub struct ConcurrentLimitLayer {
     permits: usize,
+    read_permits: Option<usize>,
+    write_permits: Option<usize>,
+    list_permits: Option<usize>,
+    max_waiting: Option<usize>,
+    min_permits: Option<usize>,
 }
 
 impl ConcurrentLimitLayer {
     /// Create a new ConcurrentLimitLayer will specify permits
     pub fn new(permits: usize) -> Self {
-        Self { permits }
+        Self {
+            permits,
+            read_permits: None,
+            write_permits: None,
+            list_permits: None,
+            max_waiting: None,
+            min_permits: None,
+        }
+    }
+
+    /// Set the permits for `read` and `stat`.
+    pub fn with_read_permits(mut self, permits: usize) -> Self {
+        self.read_permits = Some(permits);
+        self
+    }
+
+    /// Set the permits for `write`, `create_dir`, `delete` and `batch`.
+    pub fn with_write_permits(mut self, permits: usize) -> Self {
+        self.write_permits = Some(permits);
+        self
+    }
+
+    /// Set the permits for `list`.
+    pub fn with_list_permits(mut self, permits: usize) -> Self {
+        self.list_permits = Some(permits);
+        self
+    }
+
+    /// Set the max number of requests waiting for permits.
+    ///
+    /// Requests beyond this bound fail with [`ErrorKind::RateLimited`].
+    pub fn with_max_waiting(mut self, max_waiting: usize) -> Self {
+        self.max_waiting = Some(max_waiting);
+        self
+    }
+
+    /// Adjust the global permits according to the throttling of services,
+    /// the permits will never be lower than `min_permits`.
+    ///
+    /// Read [adaptive limit](#adaptive-limit) for more details.
+    pub fn with_adaptive(mut self, min_permits: usize) -> Self {
+        self.min_permits = Some(min_permits);
+        self
     }
 }
 
@@ -63,9 +167,185 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
     type LayeredAccess = ConcurrentLimitAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let new_semaphore = |permits: Option<usize>| permits.map(|v| Arc::new(Semaphore::new(v)));
+
         ConcurrentLimitAccessor {
             inner,
-            semaphore: Arc::new(Semaphore::new(self.permits)),
+            limiter: Arc::new(ConcurrentLimiter {
+                global: Arc::new(Semaphore::new(self.permits)),
+                read: new_semaphore(self.read_permits),
+                write: new_semaphore(self.write_permits),
+                list: new_semaphore(self.list_permits),
+                max_waiting: self.max_waiting,
+                waiting: AtomicUsize::new(0),
+                adaptive: self.min_permits.map(|min| Aimd::new(min, self.permits, 1)),
+                debt: AtomicUsize::new(0),
+            }),
+        }
+    }
+}
+
+/// The kind of operation that permits are acquired for.
+#[derive(Debug, Clone, Copy)]
+enum LimitKind {
+    Read,
+    Write,
+    List,
+}
+
+#[derive(Debug)]
+struct ConcurrentLimiter {
+    global: Arc<Semaphore>,
+    read: Option<Arc<Semaphore>>,
+    write: Option<Arc<Semaphore>>,
+    list: Option<Arc<Semaphore>>,
+    max_waiting: Option<usize>,
+    waiting: AtomicUsize,
+    adaptive: Option<Aimd>,
+    /// The number of global permits to forget once they are released.
+    debt: AtomicUsize,
+}
+
+impl ConcurrentLimiter {
+    fn semaphore(&self, kind: LimitKind) -> Option<&Arc<Semaphore>> {
+        match kind {
+            LimitKind::Read => self.read.as_ref(),
+            LimitKind::Write => self.write.as_ref(),
+            LimitKind::List => self.list.as_ref(),
+        }
+    }
+
+    async fn acquire(self: &Arc<Self>, kind: LimitKind) -> Result<ConcurrentLimitPermit> {
+        // Acquire the operation permit first so that waiting requests don't
+        // hold global permits.
+        let op = match self.semaphore(kind) {
+            Some(semaphore) => Some(self.acquire_one(semaphore).await?),
+            None => None,
+        };
+        let global = self.acquire_one(&self.global).await?;
+
+        Ok(ConcurrentLimitPermit {
+            limiter: self.clone(),
+            _op: op,
+            global: Some(global),
+        })
+    }
+
+    async fn acquire_one(&self, semaphore: &Arc<Semaphore>) -> Result<OwnedSemaphorePermit> {
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        let waiting = self.waiting.fetch_add(1, Ordering::Relaxed);
+        // Decrease the counter even if the future has been dropped.
+        let _guard = WaitingGuard(&self.waiting);
+        if self.max_waiting.is_some_and(|max| waiting >= max) {
+            return Err(new_rate_limited_error(
+                "too many requests waiting for permits",
+            ));
+        }
+
+        Ok(semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore must be valid"))
+    }
+
+    fn try_acquire(self: &Arc<Self>, kind: LimitKind) -> Result<ConcurrentLimitPermit> {
+        let op = match self.semaphore(kind) {
+            Some(semaphore) => Some(Self::try_acquire_one(semaphore)?),
+            None => None,
+        };
+        let global = Self::try_acquire_one(&self.global)?;
+
+        Ok(ConcurrentLimitPermit {
+            limiter: self.clone(),
+            _op: op,
+            global: Some(global),
+        })
+    }
+
+    fn try_acquire_one(semaphore: &Arc<Semaphore>) -> Result<OwnedSemaphorePermit> {
+        semaphore
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| new_rate_limited_error("no permits available"))
+    }
+
+    /// Feed the result of a request to the adaptive limit.
+    fn observe<T>(&self, res: Result<T>) -> Result<T> {
+        if let Some(adaptive) = &self.adaptive {
+            self.adjust(adaptive.observe(&res));
+        }
+        res
+    }
+
+    /// Feed an error returned in the middle of a request to the adaptive limit.
+    fn observe_error(&self, err: Error) -> Error {
+        if let Some(adaptive) = &self.adaptive {
+            self.adjust(adaptive.observe_error(&err));
+        }
+        err
+    }
+
+    fn adjust(&self, changed: Option<(usize, usize)>) {
+        match changed {
+            Some((old, new)) if new < old => {
+                for _ in new..old {
+                    match self.global.try_acquire() {
+                        Ok(permit) => permit.forget(),
+                        // The permit is in use, forget it once released.
+                        Err(_) => {
+                            self.debt.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+            Some((old, new)) => {
+                for _ in old..new {
+                    if !self.pay_debt() {
+                        self.global.add_permits(1);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Decrease the debt by one, returns false if there is no debt.
+    fn pay_debt(&self) -> bool {
+        self.debt
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| v.checked_sub(1))
+            .is_ok()
+    }
+}
+
+fn new_rate_limited_error(msg: &'static str) -> Error {
+    Error::new(ErrorKind::RateLimited, msg).set_temporary()
+}
+
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Permits held by a single request.
+struct ConcurrentLimitPermit {
+    limiter: Arc<ConcurrentLimiter>,
+    _op: Option<OwnedSemaphorePermit>,
+    global: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for ConcurrentLimitPermit {
+    fn drop(&mut self) {
+        if self.limiter.pay_debt() {
+            if let Some(permit) = self.global.take() {
+                permit.forget();
+            }
         }
     }
 }
@@ -73,7 +353,7 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
 #[derive(Debug, Clone)]
 pub struct ConcurrentLimitAccessor<A: Access> {
     inner: A,
-    semaphore: Arc<Semaphore>,
+    limiter: Arc<ConcurrentLimiter>,
 }
 
 impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
@@ -90,147 +370,94 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.acquire(LimitKind::Write).await?;
 
-        self.inner.create_dir(path, args).await
+        self.limiter
+            .observe(self.inner.create_dir(path, args).await)
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.limiter.acquire(LimitKind::Read).await?;
 
-        self.inner
-            .read(path, args)
-            .await
+        self.limiter
+            .observe(self.inner.read(path, args).await)
             .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.limiter.acquire(LimitKind::Write).await?;
 
-        self.inner
-            .write(path, args)
-            .await
+        self.limiter
+            .observe(self.inner.write(path, args).await)
             .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.acquire(LimitKind::Read).await?;
 
-        self.inner.stat(path, args).await
+        self.limiter.observe(self.inner.stat(path, args).await)
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.acquire(LimitKind::Write).await?;
 
-        self.inner.delete(path, args).await
+        self.limiter.observe(self.inner.delete(path, args).await)
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.limiter.acquire(LimitKind::List).await?;
 
-        self.inner
-            .list(path, args)
-            .await
+        self.limiter
+            .observe(self.inner.list(path, args).await)
             .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit)))
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.acquire(LimitKind::Write).await?;
 
-        self.inner.batch(args).await
+        self.limiter.observe(self.inner.batch(args).await)
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.try_acquire(LimitKind::Write)?;
 
-        self.inner.blocking_create_dir(path, args)
+        self.limiter
+            .observe(self.inner.blocking_create_dir(path, args))
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.limiter.try_acquire(LimitKind::Read)?;
 
-        self.inner
-            .blocking_read(path, args)
+        self.limiter
+            .observe(self.inner.blocking_read(path, args))
             .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.limiter.try_acquire(LimitKind::Write)?;
 
-        self.inner
-            .blocking_write(path, args)
+        self.limiter
+            .observe(self.inner.blocking_write(path, args))
             .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.try_acquire(LimitKind::Read)?;
 
-        self.inner.blocking_stat(path, args)
+        self.limiter.observe(self.inner.blocking_stat(path, args))
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.limiter.try_acquire(LimitKind::Write)?;
 
-        self.inner.blocking_delete(path, args)
+        self.limiter.observe(self.inner.blocking_delete(path, args))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.limiter.try_acquire(LimitKind::List)?;
 
-        self.inner
-            .blocking_list(path, args)
+        self.limiter
+            .observe(self.inner.blocking_list(path, args))
             .map(|(rp, it)| (rp, ConcurrentLimitWrapper::new(it, permit)))
     }
 }
@@ -239,62 +466,133 @@ pub struct ConcurrentLimitWrapper<R> {
     inner: R,
 
     // Hold on this permit until this reader has been dropped.
-    _permit: OwnedSemaphorePermit,
+    permit: ConcurrentLimitPermit,
 }
 
 impl<R> ConcurrentLimitWrapper<R> {
-    fn new(inner: R, permit: OwnedSemaphorePermit) -> Self {
-        Self {
-            inner,
-            _permit: permit,
-        }
+    fn new(inner: R, permit: ConcurrentLimitPermit) -> Self {
+        Self { inner, permit }
+    }
+
+    fn observe<T>(&self, res: Result<T>) -> Result<T> {
+        res.map_err(|err| self.permit.limiter.observe_error(err))
     }
 }
 
 impl<R: oio::Read> oio::Read for ConcurrentLimitWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        self.inner.read().await
+        let res = self.inner.read().await;
+        self.observe(res)
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ConcurrentLimitWrapper<R> {
     fn read(&mut self) -> Result<Buffer> {
-        self.inner.read()
+        let res = self.inner.read();
+        self.observe(res)
     }
 }
 
 impl<R: oio::Write> oio::Write for ConcurrentLimitWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
-        self.inner.write(bs).await
+        let res = self.inner.write(bs).await;
+        self.observe(res)
     }
 
-    async fn close(&mut self) -> Result<()> {
-        self.inner.close().await
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.observe(res)
     }
 
     async fn abort(&mut self) -> Result<()> {
-        self.inner.abort().await
+        let res = self.inner.abort().await;
+        self.observe(res)
     }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
     fn write(&mut self, bs: Buffer) -> Result<()> {
-        self.inner.write(bs)
+        let res = self.inner.write(bs);
+        self.observe(res)
     }
 
-    fn close(&mut self) -> Result<()> {
-        self.inner.close()
+    fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close();
+        self.observe(res)
     }
 }
 
 impl<R: oio::List> oio::List for ConcurrentLimitWrapper<R> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.inner.next().await
+        let res = self.inner.next().await;
+        self.observe(res)
     }
 }
 
 impl<R: oio::BlockingList> oio::BlockingList for ConcurrentLimitWrapper<R> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.inner.next()
+        let res = self.inner.next();
+        self.observe(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_write_permits() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(
+                ConcurrentLimitLayer::new(16)
+                    .with_write_permits(1)
+                    .with_max_waiting(0),
+            )
+            .finish();
+
+        // Hold the only write permit.
+        let mut w = op.writer("a").await.expect("writer must succeed");
+
+        let err = op.write("b", "abcd").await.expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        // Reads are not limited by write permits.
+        let err = op.stat("a").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        w.close().await.expect("close must succeed");
+        drop(w);
+        op.write("b", "abcd").await.expect("write must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_adaptive() {
+        let acc = ConcurrentLimitLayer::new(4).with_adaptive(1).layer(());
+        let limiter = acc.limiter.clone();
+        let throttled = || -> Result<()> {
+            Err(Error::new(ErrorKind::Unexpected, "slow down")
+                .with_context("status_code", "429")
+                .with_context("retry_after", "0"))
+        };
+
+        // Shrink while permits are in use.
+        let mut permits = Vec::new();
+        for _ in 0..3 {
+            permits.push(limiter.acquire(LimitKind::Read).await.unwrap());
+        }
+        let _ = limiter.observe(throttled());
+        assert_eq!(limiter.global.available_permits(), 0);
+        assert_eq!(limiter.debt.load(Ordering::Relaxed), 1);
+        drop(permits);
+        assert_eq!(limiter.global.available_permits(), 2);
+        assert_eq!(limiter.debt.load(Ordering::Relaxed), 0);
+
+        // Recover after successful requests.
+        for _ in 0..2 {
+            limiter.observe(Ok(())).unwrap();
+        }
+        assert_eq!(limiter.global.available_permits(), 3);
     }
 }
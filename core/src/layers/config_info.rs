@@ -0,0 +1,207 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Keys whose values contain these words are redacted.
+const SECRET_WORDS: [&str; 5] = [
+    "secret",
+    "password",
+    "token",
+    "credential",
+    "connection_string",
+];
+
+/// ConfigInfoLayer records the configuration of services into
+/// [`AccessorInfo`] with secrets redacted.
+///
+/// # Notes
+///
+/// ConfigInfoLayer is not a public accessible layer that can be used by
+/// external users. It's applied by operators built from configurations.
+pub struct ConfigInfoLayer {
+    config: Option<BTreeMap<String, String>>,
+}
+
+impl ConfigInfoLayer {
+    /// Create a new `ConfigInfoLayer` with the redacted `cfg`.
+    pub fn new<C: Configurator>(cfg: &C) -> Self {
+        Self {
+            config: redact_config(cfg),
+        }
+    }
+}
+
+impl<A: Access> Layer<A> for ConfigInfoLayer {
+    type LayeredAccess = ConfigInfoAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let mut info = (*inner.info()).clone();
+        if let Some(mut config) = self.config.clone() {
+            // Use the normalized root instead of the configured one.
+            config.insert("root".to_string(), info.root().to_string());
+            info.set_config(config);
+        }
+
+        ConfigInfoAccessor {
+            inner,
+            info: Arc::new(info),
+        }
+    }
+}
+
+pub struct ConfigInfoAccessor<A: Access> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+}
+
+impl<A: Access> Debug for ConfigInfoAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<A: Access> LayeredAccess for ConfigInfoAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// Flatten `cfg` into key-value pairs and redact the secrets.
+///
+/// Unset options are skipped, returns `None` if `cfg` can't be serialized.
+fn redact_config<C: Configurator>(cfg: &C) -> Option<BTreeMap<String, String>> {
+    let serde_json::Value::Object(fields) = serde_json::to_value(cfg).ok()? else {
+        return None;
+    };
+
+    let config = fields
+        .into_iter()
+        .filter_map(|(k, v)| {
+            let v = match v {
+                serde_json::Value::Null => return None,
+                _ if is_secret(&k) => "<redacted>".to_string(),
+                serde_json::Value::String(v) => v,
+                v => v.to_string(),
+            };
+            Some((k, v))
+        })
+        .collect();
+    Some(config)
+}
+
+/// Check if the value of `key` is a secret, paths to secrets are kept.
+fn is_secret(key: &str) -> bool {
+    if key.ends_with("_path") || key.ends_with("_file") {
+        return false;
+    }
+    key == "key" || key.ends_with("_key") || SECRET_WORDS.iter().any(|w| key.contains(w))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct TestConfig {
+        bucket: String,
+        access_key_id: Option<String>,
+        secret_access_key: Option<String>,
+        session_token: Option<String>,
+        credential_path: Option<String>,
+        batch_max_operations: Option<usize>,
+    }
+
+    impl Configurator for TestConfig {
+        type Builder = ();
+
+        fn into_builder(self) -> Self::Builder {}
+    }
+
+    #[test]
+    fn test_redact_config() {
+        let cfg = TestConfig {
+            bucket: "test".to_string(),
+            access_key_id: Some("access_key_id".to_string()),
+            secret_access_key: Some("secret_access_key".to_string()),
+            credential_path: Some("/etc/credential".to_string()),
+            batch_max_operations: Some(100),
+            ..Default::default()
+        };
+
+        let config = redact_config(&cfg).unwrap();
+        assert_eq!(
+            config,
+            BTreeMap::from([
+                ("access_key_id".to_string(), "access_key_id".to_string()),
+                ("batch_max_operations".to_string(), "100".to_string()),
+                ("bucket".to_string(), "test".to_string()),
+                ("credential_path".to_string(), "/etc/credential".to_string()),
+                ("secret_access_key".to_string(), "<redacted>".to_string()),
+            ])
+        );
+
+        assert!(is_secret("password"));
+        assert!(is_secret("account_key"));
+        assert!(!is_secret("oidc_token_file"));
+    }
+}
@@ -0,0 +1,682 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use crate::raw::oio::QueueBuf;
+use crate::raw::*;
+use crate::*;
+
+/// Deduplicate written content by storing it under its BLAKE3 digest.
+///
+/// # Dedup
+///
+/// DedupLayer hashes the content of every write with BLAKE3, stores the
+/// content as a blob at `{blob_prefix}{digest[..2]}/{digest}` and writes a
+/// small manifest object that points to the blob at the user visible path.
+/// Uploading the blob will be skipped if it already exists, so writing the
+/// same content to many paths only stores it once.
+///
+/// Reading a path will read its manifest first and then the blob.
+///
+/// # Notes
+///
+/// - Content is hashed while writing. If the underlying service supports
+///   `rename` or `copy`, content will be streamed into a staging object
+///   under the blob prefix and moved to the blob path while closing.
+///   Otherwise, content is buffered in memory until the writer is closed.
+/// - `delete` only removes the manifest. Blobs are never deleted by this
+///   layer, users need to collect unreferenced blobs by themselves.
+/// - `append` is not supported.
+/// - `stat` and `list` return the size of the content recorded in manifests,
+///   so listing files will read their manifests.
+/// - Entries under the blob prefix are hidden from `list`.
+/// - Objects that are not written through this layer can't be read and will
+///   return [`ErrorKind::Unexpected`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::DedupLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(DedupLayer::new().with_blob_prefix(".blobs/"))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DedupLayer {
+    blob_prefix: String,
+}
+
+impl Default for DedupLayer {
+    fn default() -> Self {
+        Self {
+            blob_prefix: ".dedup/".to_string(),
+        }
+    }
+}
+
+impl DedupLayer {
+    /// Create a new `DedupLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the prefix that blobs will be stored under.
+    ///
+    /// Default to `.dedup/`.
+    pub fn with_blob_prefix(mut self, prefix: &str) -> Self {
+        let prefix = normalize_root(prefix);
+        // Blob prefix is relative to the operator root.
+        self.blob_prefix = prefix.trim_start_matches('/').to_string();
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for DedupLayer {
+    type LayeredAccess = DedupAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        DedupAccessor {
+            inner: Arc::new(inner),
+            blob_prefix: Arc::from(self.blob_prefix.as_str()),
+        }
+    }
+}
+
+/// Manifest is the object stored at the user visible path.
+///
+/// It's encoded as `blake3:{digest}:{size}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Manifest {
+    digest: String,
+    size: u64,
+}
+
+impl Manifest {
+    fn new(hasher: &blake3::Hasher, size: u64) -> Self {
+        Self {
+            digest: hasher.finalize().to_hex().to_string(),
+            size,
+        }
+    }
+
+    fn blob_path(&self, prefix: &str) -> String {
+        format!("{prefix}{}/{}", &self.digest[..2], self.digest)
+    }
+
+    fn encode(&self) -> Buffer {
+        Buffer::from(format!("blake3:{}:{}", self.digest, self.size))
+    }
+
+    fn decode(path: &str, content: Buffer) -> Result<Self> {
+        let content = content.to_bytes();
+        let err = || {
+            Error::new(
+                ErrorKind::Unexpected,
+                "object is not a valid dedup manifest",
+            )
+            .with_context("path", path)
+        };
+
+        let content = std::str::from_utf8(&content).map_err(|_| err())?;
+        let mut parts = content.trim_end().splitn(3, ':');
+        let (Some("blake3"), Some(digest), Some(size)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(err());
+        };
+        if digest.len() != 64 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let size = size.parse::<u64>().map_err(|e| err().set_source(e))?;
+
+        Ok(Self {
+            digest: digest.to_string(),
+            size,
+        })
+    }
+}
+
+async fn read_manifest<A: Access>(acc: &A, path: &str) -> Result<Manifest> {
+    let (_, mut r) = acc.read(path, OpRead::new()).await?;
+    let content = oio::Read::read_all(&mut r).await?;
+    Manifest::decode(path, content)
+}
+
+fn blocking_read_manifest<A: Access>(acc: &A, path: &str) -> Result<Manifest> {
+    let (_, mut r) = acc.blocking_read(path, OpRead::new())?;
+    let mut bufs = vec![];
+    loop {
+        let buf = oio::BlockingRead::read(&mut r)?;
+        if buf.is_empty() {
+            break;
+        }
+        bufs.push(buf);
+    }
+    Manifest::decode(path, bufs.into_iter().flatten().collect())
+}
+
+#[derive(Debug)]
+pub struct DedupAccessor<A: Access> {
+    inner: Arc<A>,
+    blob_prefix: Arc<str>,
+}
+
+impl<A: Access> DedupAccessor<A> {
+    async fn read_manifest(&self, path: &str) -> Result<Manifest> {
+        read_manifest(&*self.inner, path).await
+    }
+
+    fn blocking_read_manifest(&self, path: &str) -> Result<Manifest> {
+        blocking_read_manifest(&*self.inner, path)
+    }
+
+    fn check_write(&self, op: Operation, path: &str, args: &OpWrite) -> Result<()> {
+        if args.append() {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "dedup layer doesn't support append")
+                    .with_operation(op)
+                    .with_context("path", path),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<A: Access> LayeredAccess for DedupAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = DedupWriter<A, A::Writer>;
+    type BlockingWriter = DedupWriter<A, A::BlockingWriter>;
+    type Lister = DedupLister<A, A::Lister>;
+    type BlockingLister = DedupLister<A, A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let manifest = self.read_manifest(path).await?;
+        self.inner
+            .read(&manifest.blob_path(&self.blob_prefix), args)
+            .await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write(Operation::Write, path, &args)?;
+        Ok((
+            RpWrite::default(),
+            DedupWriter::new(self.inner.clone(), self.blob_prefix.clone(), path, args),
+        ))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.stat(path, args).await?;
+        if rp.metadata().is_dir() {
+            return Ok(rp);
+        }
+
+        let manifest = self.read_manifest(path).await?;
+        Ok(rp.map_metadata(|m| m.with_content_length(manifest.size)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let (rp, lister) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            DedupLister::new(self.inner.clone(), self.blob_prefix.clone(), lister),
+        ))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let manifest = self.blocking_read_manifest(path)?;
+        self.inner
+            .blocking_read(&manifest.blob_path(&self.blob_prefix), args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write(Operation::BlockingWrite, path, &args)?;
+        Ok((
+            RpWrite::default(),
+            DedupWriter::new(self.inner.clone(), self.blob_prefix.clone(), path, args),
+        ))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.blocking_stat(path, args)?;
+        if rp.metadata().is_dir() {
+            return Ok(rp);
+        }
+
+        let manifest = self.blocking_read_manifest(path)?;
+        Ok(rp.map_metadata(|m| m.with_content_length(manifest.size)))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let (rp, lister) = self.inner.blocking_list(path, args)?;
+        Ok((
+            rp,
+            DedupLister::new(self.inner.clone(), self.blob_prefix.clone(), lister),
+        ))
+    }
+}
+
+/// DedupWriter hashes content while writing and uploads the blob and
+/// manifest while closing.
+///
+/// Content will be streamed into a staging object under the blob prefix
+/// and then moved to the blob path if the underlying service supports
+/// `rename` or `copy`. Otherwise, content will be buffered in memory.
+pub struct DedupWriter<A: Access, W> {
+    acc: Arc<A>,
+    blob_prefix: Arc<str>,
+    path: String,
+    args: OpWrite,
+    hasher: blake3::Hasher,
+    size: u64,
+    staging: Staging<W>,
+}
+
+enum Staging<W> {
+    Buffer(QueueBuf),
+    /// The writer will be opened at the first write.
+    Stream {
+        path: String,
+        w: Option<W>,
+    },
+}
+
+impl<A: Access, W> DedupWriter<A, W> {
+    fn new(acc: Arc<A>, blob_prefix: Arc<str>, path: &str, args: OpWrite) -> Self {
+        let cap = acc.info().native_capability();
+        let staging = if cap.write_can_multi && (cap.rename || cap.copy) {
+            Staging::Stream {
+                path: format!("{blob_prefix}staging/{}", uuid::Uuid::new_v4()),
+                w: None,
+            }
+        } else {
+            Staging::Buffer(QueueBuf::new())
+        };
+
+        Self {
+            acc,
+            blob_prefix,
+            path: path.to_string(),
+            args,
+            hasher: blake3::Hasher::new(),
+            size: 0,
+            staging,
+        }
+    }
+
+    fn update(&mut self, bs: &Buffer) {
+        for chunk in bs.clone() {
+            self.hasher.update(&chunk);
+        }
+        self.size += bs.len() as u64;
+    }
+
+    fn manifest(&self) -> Manifest {
+        Manifest::new(&self.hasher, self.size)
+    }
+}
+
+impl<A: Access> DedupWriter<A, A::Writer> {
+    /// Move the staging object to the blob path, or remove it if the blob
+    /// already exists.
+    async fn commit_staging(&self, staging: &str, blob_path: &str) -> Result<()> {
+        if !blob_exists(self.acc.stat(blob_path, OpStat::new()).await)? {
+            if self.acc.info().native_capability().rename {
+                self.acc.rename(staging, blob_path, OpRename::new()).await?;
+                return Ok(());
+            }
+            self.acc.copy(staging, blob_path, OpCopy::new()).await?;
+        }
+        self.acc.delete(staging, OpDelete::new()).await?;
+        Ok(())
+    }
+}
+
+impl<A: Access> DedupWriter<A, A::BlockingWriter> {
+    fn blocking_commit_staging(&self, staging: &str, blob_path: &str) -> Result<()> {
+        if !blob_exists(self.acc.blocking_stat(blob_path, OpStat::new()))? {
+            if self.acc.info().native_capability().rename {
+                self.acc
+                    .blocking_rename(staging, blob_path, OpRename::new())?;
+                return Ok(());
+            }
+            self.acc.blocking_copy(staging, blob_path, OpCopy::new())?;
+        }
+        self.acc.blocking_delete(staging, OpDelete::new())?;
+        Ok(())
+    }
+}
+
+fn blob_exists(res: Result<RpStat>) -> Result<bool> {
+    match res {
+        Ok(_) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+impl<A: Access> oio::Write for DedupWriter<A, A::Writer> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.update(&bs);
+        match &mut self.staging {
+            Staging::Buffer(buf) => {
+                buf.push(bs);
+                Ok(())
+            }
+            Staging::Stream { path, w } => {
+                if w.is_none() {
+                    let (_, sw) = self.acc.write(path, OpWrite::new()).await?;
+                    *w = Some(sw);
+                }
+                let w = w.as_mut().expect("writer must be initialized");
+                oio::Write::write(w, bs).await
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let manifest = self.manifest();
+        let blob_path = manifest.blob_path(&self.blob_prefix);
+
+        match &mut self.staging {
+            Staging::Buffer(buf) => {
+                let content: Buffer = buf.take().collect();
+                if !blob_exists(self.acc.stat(&blob_path, OpStat::new()).await)? {
+                    let (_, mut w) = self.acc.write(&blob_path, OpWrite::new()).await?;
+                    oio::Write::write(&mut w, content).await?;
+                    oio::Write::close(&mut w).await?;
+                }
+            }
+            Staging::Stream { path, w } => {
+                let path = path.clone();
+                match w.take() {
+                    Some(mut w) => {
+                        oio::Write::close(&mut w).await?;
+                        if let Err(err) = self.commit_staging(&path, &blob_path).await {
+                            let _ = self.acc.delete(&path, OpDelete::new()).await;
+                            return Err(err);
+                        }
+                    }
+                    // Nothing has been written, upload the empty blob directly.
+                    None => {
+                        if !blob_exists(self.acc.stat(&blob_path, OpStat::new()).await)? {
+                            let (_, mut w) = self.acc.write(&blob_path, OpWrite::new()).await?;
+                            oio::Write::close(&mut w).await?;
+                        }
+                    }
+                }
+            }
+        }
+
+        let (_, mut w) = self.acc.write(&self.path, self.args.clone()).await?;
+        oio::Write::write(&mut w, manifest.encode()).await?;
+        oio::Write::close(&mut w).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        match &mut self.staging {
+            Staging::Buffer(buf) => buf.clear(),
+            Staging::Stream { w, .. } => {
+                if let Some(mut w) = w.take() {
+                    oio::Write::abort(&mut w).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<A: Access> oio::BlockingWrite for DedupWriter<A, A::BlockingWriter> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.update(&bs);
+        match &mut self.staging {
+            Staging::Buffer(buf) => {
+                buf.push(bs);
+                Ok(())
+            }
+            Staging::Stream { path, w } => {
+                if w.is_none() {
+                    let (_, sw) = self.acc.blocking_write(path, OpWrite::new())?;
+                    *w = Some(sw);
+                }
+                let w = w.as_mut().expect("writer must be initialized");
+                oio::BlockingWrite::write(w, bs)
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        let manifest = self.manifest();
+        let blob_path = manifest.blob_path(&self.blob_prefix);
+
+        match &mut self.staging {
+            Staging::Buffer(buf) => {
+                let content: Buffer = buf.take().collect();
+                if !blob_exists(self.acc.blocking_stat(&blob_path, OpStat::new()))? {
+                    let (_, mut w) = self.acc.blocking_write(&blob_path, OpWrite::new())?;
+                    oio::BlockingWrite::write(&mut w, content)?;
+                    oio::BlockingWrite::close(&mut w)?;
+                }
+            }
+            Staging::Stream { path, w } => {
+                let path = path.clone();
+                match w.take() {
+                    Some(mut w) => {
+                        oio::BlockingWrite::close(&mut w)?;
+                        if let Err(err) = self.blocking_commit_staging(&path, &blob_path) {
+                            let _ = self.acc.blocking_delete(&path, OpDelete::new());
+                            return Err(err);
+                        }
+                    }
+                    // Nothing has been written, upload the empty blob directly.
+                    None => {
+                        if !blob_exists(self.acc.blocking_stat(&blob_path, OpStat::new()))? {
+                            let (_, mut w) = self.acc.blocking_write(&blob_path, OpWrite::new())?;
+                            oio::BlockingWrite::close(&mut w)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        let (_, mut w) = self.acc.blocking_write(&self.path, self.args.clone())?;
+        oio::BlockingWrite::write(&mut w, manifest.encode())?;
+        oio::BlockingWrite::close(&mut w)
+    }
+}
+
+/// DedupLister hides entries under the blob prefix and reports the size
+/// recorded in manifests for files.
+pub struct DedupLister<A: Access, L> {
+    acc: Arc<A>,
+    blob_prefix: Arc<str>,
+    inner: L,
+}
+
+impl<A: Access, L> DedupLister<A, L> {
+    fn new(acc: Arc<A>, blob_prefix: Arc<str>, inner: L) -> Self {
+        Self {
+            acc,
+            blob_prefix,
+            inner,
+        }
+    }
+
+    fn is_blob(&self, entry: &oio::Entry) -> bool {
+        entry.path().starts_with(&*self.blob_prefix)
+    }
+}
+
+impl<A: Access, L: oio::List> oio::List for DedupLister<A, L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next().await? {
+            if self.is_blob(&entry) {
+                continue;
+            }
+            if !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            let manifest = read_manifest(&*self.acc, entry.path()).await?;
+            let meta = entry.metadata().clone().with_content_length(manifest.size);
+            return Ok(Some(oio::Entry::new(entry.path(), meta)));
+        }
+        Ok(None)
+    }
+}
+
+impl<A: Access, L: oio::BlockingList> oio::BlockingList for DedupLister<A, L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next()? {
+            if self.is_blob(&entry) {
+                continue;
+            }
+            if !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            let manifest = blocking_read_manifest(&*self.acc, entry.path())?;
+            let meta = entry.metadata().clone().with_content_length(manifest.size);
+            return Ok(Some(oio::Entry::new(entry.path(), meta)));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[test]
+    fn test_manifest() {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"hello");
+        let manifest = Manifest::new(&hasher, 5);
+        assert_eq!(
+            manifest.digest,
+            "ea8f163db38682925e4491c5e58d4bb3506ef8c14eb78a86e908c5624a67200f"
+        );
+        assert_eq!(
+            manifest.blob_path(".dedup/"),
+            ".dedup/ea/ea8f163db38682925e4491c5e58d4bb3506ef8c14eb78a86e908c5624a67200f"
+        );
+        assert_eq!(
+            Manifest::decode("path", manifest.encode()).unwrap(),
+            manifest
+        );
+
+        let err = Manifest::decode("path", Buffer::from("hello")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_dedup() {
+        let mem = Arc::new(services::Memory::default().build().unwrap());
+        let op = OperatorBuilder::new(mem.clone())
+            .layer(DedupLayer::new())
+            .finish();
+
+        op.write("a", "hello").await.expect("write must succeed");
+        op.write("dir/b", "hello")
+            .await
+            .expect("write must succeed");
+
+        assert_eq!(op.read("dir/b").await.unwrap().to_vec(), b"hello");
+        assert_eq!(op.read_with("a").range(1..3).await.unwrap().to_vec(), b"el");
+        assert_eq!(op.stat("a").await.unwrap().content_length(), 5);
+
+        let entries: Vec<_> = op
+            .lister_with("")
+            .recursive(true)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        let mut files: Vec<_> = entries
+            .iter()
+            .filter(|e| e.metadata().is_file())
+            .map(|e| (e.path(), e.metadata().content_length()))
+            .collect();
+        files.sort_unstable();
+        assert_eq!(files, vec![("a", 5), ("dir/b", 5)]);
+
+        // Both paths share the same blob.
+        let (_, mut lister) = mem
+            .blocking_list(".dedup/", OpList::new().with_recursive(true))
+            .unwrap();
+        let mut blobs = 0;
+        while let Some(entry) = oio::BlockingList::next(&mut lister).unwrap() {
+            if entry.mode().is_file() {
+                blobs += 1;
+            }
+        }
+        assert_eq!(blobs, 1);
+    }
+
+    #[cfg(feature = "services-fs")]
+    #[tokio::test]
+    async fn test_dedup_streaming() {
+        let root = std::env::temp_dir().join(format!("opendal-dedup-{}", uuid::Uuid::new_v4()));
+        let op = Operator::new(services::Fs::default().root(root.to_str().unwrap()))
+            .unwrap()
+            .layer(DedupLayer::new())
+            .finish();
+
+        let mut w = op.writer("a").await.unwrap();
+        w.write("hello, ").await.unwrap();
+        w.write("world").await.unwrap();
+        w.close().await.unwrap();
+        op.write("b", "hello, world").await.unwrap();
+
+        assert_eq!(op.read("a").await.unwrap().to_vec(), b"hello, world");
+        assert_eq!(op.read("b").await.unwrap().to_vec(), b"hello, world");
+
+        // Staging objects are moved or removed after close.
+        let raw = Operator::new(services::Fs::default().root(root.to_str().unwrap()))
+            .unwrap()
+            .finish();
+        let blobs: Vec<_> = raw
+            .list_with(".dedup/")
+            .recursive(true)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.metadata().is_file())
+            .map(|e| e.path().to_string())
+            .collect();
+        assert_eq!(blobs.len(), 1);
+        assert!(!blobs[0].starts_with(".dedup/staging/"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
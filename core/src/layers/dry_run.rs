@@ -0,0 +1,302 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use log::info;
+
+use crate::raw::*;
+use crate::*;
+
+/// Log mutations instead of executing them on the underlying services.
+///
+/// # Dry Run
+///
+/// DryRunLayer turns `write`, `create_dir`, `delete`, `copy`, `rename`,
+/// `batch` and `abort_multipart_upload` into no-ops that only emit a log
+/// record with the target paths and the written size. All of them return
+/// success as if the mutation has been applied.
+///
+/// `read`, `stat`, `list` and `presign` are passed to the underlying services
+/// as is, so that tools can still inspect the existing data.
+///
+/// # Notes
+///
+/// Mutations are not applied, so reading back a path written under dry run
+/// will still return the content stored in the underlying services (or
+/// `NotFound`).
+///
+/// # Logging
+///
+/// Records are emitted under target `opendal::layers::dry_run` in `info`
+/// level.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::DryRunLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let dry_run = true;
+/// let mut op = Operator::new(services::Memory::default())?.finish();
+/// if dry_run {
+///     op = op.layer(DryRunLayer);
+/// }
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DryRunLayer;
+
+impl<A: Access> Layer<A> for DryRunLayer {
+    type LayeredAccess = DryRunAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let info = inner.info();
+        DryRunAccessor { inner, info }
+    }
+}
+
+static DRY_RUN_TARGET: &str = "opendal::layers::dry_run";
+
+#[derive(Debug)]
+pub struct DryRunAccessor<A: Access> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+}
+
+impl<A: Access> DryRunAccessor<A> {
+    fn log(&self, op: Operation, message: std::fmt::Arguments) {
+        info!(
+            target: DRY_RUN_TARGET,
+            "service={} name={} {op} {message}",
+            self.info.scheme(),
+            self.info.name(),
+        );
+    }
+
+    fn log_batch(&self, args: OpBatch) -> RpBatch {
+        let results = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| {
+                let rp = match op {
+                    BatchOperation::Delete(_) => {
+                        self.log(Operation::Delete, format_args!("path={path}"));
+                        BatchedReply::Delete(RpDelete::default())
+                    }
+                    BatchOperation::Copy(to, _) => {
+                        self.log(Operation::Copy, format_args!("from={path} to={to}"));
+                        BatchedReply::Copy(RpCopy::default())
+                    }
+                };
+                (path, Ok(rp))
+            })
+            .collect();
+        RpBatch::new(results)
+    }
+}
+
+impl<A: Access> LayeredAccess for DryRunAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = DryRunWriter;
+    type BlockingWriter = DryRunWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.log(Operation::CreateDir, format_args!("path={path}"));
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            DryRunWriter::new(self.info.clone(), path),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.log(Operation::Copy, format_args!("from={from} to={to}"));
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.log(Operation::Rename, format_args!("from={from} to={to}"));
+        Ok(RpRename::default())
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.log(Operation::Delete, format_args!("path={path}"));
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        Ok(self.log_batch(args))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        self.log(
+            Operation::AbortMultipartUpload,
+            format_args!("path={path} upload_id={}", args.upload_id()),
+        );
+        Ok(RpAbortMultipartUpload::default())
+    }
+
+    fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.log(Operation::BlockingCreateDir, format_args!("path={path}"));
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Ok((
+            RpWrite::default(),
+            DryRunWriter::new(self.info.clone(), path),
+        ))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.log(Operation::BlockingCopy, format_args!("from={from} to={to}"));
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.log(
+            Operation::BlockingRename,
+            format_args!("from={from} to={to}"),
+        );
+        Ok(RpRename::default())
+    }
+
+    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.log(Operation::BlockingDelete, format_args!("path={path}"));
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// DryRunWriter discards all written data and logs the total size on close.
+pub struct DryRunWriter {
+    info: Arc<AccessorInfo>,
+    path: String,
+    size: u64,
+}
+
+impl DryRunWriter {
+    fn new(info: Arc<AccessorInfo>, path: &str) -> Self {
+        Self {
+            info,
+            path: path.to_string(),
+            size: 0,
+        }
+    }
+
+    fn log(&self, op: Operation) {
+        info!(
+            target: DRY_RUN_TARGET,
+            "service={} name={} {op} path={} size={}",
+            self.info.scheme(),
+            self.info.name(),
+            self.path,
+            self.size,
+        );
+    }
+}
+
+impl oio::Write for DryRunWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.size += bs.len() as u64;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.log(Operation::WriterClose);
+        Ok(Metadata::new(EntryMode::FILE))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl oio::BlockingWrite for DryRunWriter {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.size += bs.len() as u64;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        self.log(Operation::BlockingWriterClose);
+        Ok(Metadata::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("exist", "abcd").await.expect("write must succeed");
+
+        let op = op.layer(DryRunLayer);
+        op.write("new", "abcd").await.expect("write must succeed");
+        op.delete("exist").await.expect("delete must succeed");
+        op.copy("exist", "copied").await.expect("copy must succeed");
+
+        // Mutations are not applied.
+        let err = op.stat("new").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = op.stat("copied").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Reads are passed through.
+        let bs = op.read("exist").await.expect("read must succeed");
+        assert_eq!(bs.to_vec(), b"abcd");
+    }
+}
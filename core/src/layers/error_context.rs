@@ -176,6 +176,12 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let op = args
+            .operation()
+            .first()
+            .map(|(_, v)| v.operation())
+            .unwrap_or(Operation::Delete);
+
         self.inner
             .batch(args)
             .await
@@ -185,7 +191,7 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
                     .into_iter()
                     .map(|(path, res)| {
                         let res = res.map_err(|err| {
-                            err.with_operation(Operation::Delete)
+                            err.with_operation(op)
                                 .with_context("service", self.info.scheme())
                                 .with_context("path", &path)
                         });
@@ -209,6 +215,45 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
         })
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        self.inner.sign(args).await.map_err(|err| {
+            err.with_operation(Operation::Sign)
+                .with_context("service", self.info.scheme())
+        })
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        self.inner
+            .list_multipart_uploads(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::ListMultipartUploads)
+                    .with_context("service", self.info.scheme())
+                    .with_context("path", path)
+            })
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let upload_id = args.upload_id().to_string();
+        self.inner
+            .abort_multipart_upload(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::AbortMultipartUpload)
+                    .with_context("service", self.info.scheme())
+                    .with_context("path", path)
+                    .with_context("upload_id", upload_id)
+            })
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner.blocking_create_dir(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingCreateDir)
@@ -383,7 +428,7 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
             })
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await.map_err(|err| {
             err.with_operation(Operation::WriterClose)
                 .with_context("service", self.scheme)
@@ -419,7 +464,7 @@ impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
             })
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close().map_err(|err| {
             err.with_operation(Operation::BlockingWriterClose)
                 .with_context("service", self.scheme)
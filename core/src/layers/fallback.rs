@@ -0,0 +1,481 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use log::warn;
+
+use crate::raw::oio::Read;
+use crate::raw::oio::Write;
+use crate::raw::*;
+use crate::*;
+
+/// Serve reads from a secondary operator if the underlying service doesn't
+/// have the object.
+///
+/// # Fallback
+///
+/// `read` and `stat` failed with [`ErrorKind::NotFound`] will be sent to the
+/// secondary operator, for example, the bucket that the underlying service is
+/// mirrored from. Other operations only touch the underlying service.
+///
+/// # Read repair
+///
+/// Enable [`FallbackLayer::with_read_repair`] to write objects read from the
+/// secondary back to the underlying service in background, so mirrored
+/// deployments converge without a separate sync job.
+///
+/// - Repairs are spawned by the executor set via
+///   [`FallbackLayer::with_executor`], which is required if the
+///   `executors-tokio` feature is not enabled.
+/// - Repairs are written with `if_not_exists` so that objects written to the
+///   underlying service in the meantime are never overwritten. Services
+///   without [`Capability::write_with_if_not_exists`] are not repaired.
+/// - The whole object is read from the secondary again even if only a range
+///   of it has been read. It's streamed to the underlying service if
+///   [`Capability::write_can_multi`] is supported, otherwise buffered in
+///   memory.
+/// - Repairs of the same path are not started again until the running one
+///   finishes.
+///
+/// Use [`FallbackLayer::snapshot`] to fetch the counters of fallbacks and
+/// repairs.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::FallbackLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let secondary = Operator::new(services::Memory::default())?.finish();
+/// let fallback = FallbackLayer::new(secondary).with_read_repair(true);
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(fallback.clone())
+///     .finish();
+///
+/// let snapshot = fallback.snapshot();
+/// println!("repairs: {}, failures: {}", snapshot.repairs, snapshot.repair_failures);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackLayer {
+    secondary: Accessor,
+    read_repair: bool,
+    executor: Executor,
+    state: Arc<FallbackState>,
+}
+
+impl FallbackLayer {
+    /// Create a new `FallbackLayer` serving missing objects from `secondary`.
+    pub fn new(secondary: Operator) -> Self {
+        Self {
+            secondary: secondary.into_inner(),
+            read_repair: false,
+            executor: Executor::new(),
+            state: Arc::default(),
+        }
+    }
+
+    /// Write objects read from the secondary back to the underlying service.
+    ///
+    /// Default to `false`.
+    pub fn with_read_repair(mut self, enabled: bool) -> Self {
+        self.read_repair = enabled;
+        self
+    }
+
+    /// Set the executor to run repairs in background.
+    pub fn with_executor(mut self, executor: Executor) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    /// Take a snapshot of current counters.
+    pub fn snapshot(&self) -> FallbackSnapshot {
+        self.state.snapshot()
+    }
+}
+
+/// A point-in-time copy of the counters accumulated by [`FallbackLayer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FallbackSnapshot {
+    /// Reads and stats served by the secondary.
+    pub fallbacks: u64,
+    /// Objects written back to the underlying service.
+    pub repairs: u64,
+    /// Repairs that have failed.
+    pub repair_failures: u64,
+}
+
+impl<A: Access> Layer<A> for FallbackLayer {
+    type LayeredAccess = FallbackAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        FallbackAccessor {
+            inner: Arc::new(inner),
+            secondary: self.secondary.clone(),
+            read_repair: self.read_repair,
+            executor: self.executor.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FallbackState {
+    fallbacks: AtomicU64,
+    repairs: AtomicU64,
+    repair_failures: AtomicU64,
+    /// Paths that are being repaired.
+    repairing: Mutex<HashSet<String>>,
+}
+
+impl FallbackState {
+    fn snapshot(&self) -> FallbackSnapshot {
+        FallbackSnapshot {
+            fallbacks: self.fallbacks.load(Ordering::Relaxed),
+            repairs: self.repairs.load(Ordering::Relaxed),
+            repair_failures: self.repair_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FallbackAccessor<A: Access> {
+    inner: Arc<A>,
+    secondary: Accessor,
+    read_repair: bool,
+    executor: Executor,
+    state: Arc<FallbackState>,
+}
+
+impl<A: Access> FallbackAccessor<A> {
+    /// Return the result of the secondary, the error of the underlying
+    /// service is kept if the secondary doesn't have the object either.
+    fn fallback<T>(&self, err: Error, res: Result<T>) -> Result<T> {
+        match res {
+            Ok(v) => {
+                self.state.fallbacks.fetch_add(1, Ordering::Relaxed);
+                Ok(v)
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(err),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Start repairing `path` in background if read repair is enabled.
+    fn repair(&self, path: &str) {
+        if !self.read_repair || !self.inner.info().full_capability().write_with_if_not_exists {
+            return;
+        }
+        let started = self
+            .state
+            .repairing
+            .lock()
+            .expect("lock must succeed")
+            .insert(path.to_string());
+        if !started {
+            return;
+        }
+
+        let inner = self.inner.clone();
+        let secondary = self.secondary.clone();
+        let state = self.state.clone();
+        let path = path.to_string();
+        self.executor
+            .clone()
+            .into_inner()
+            .execute(Box::pin(async move {
+                match repair(inner.as_ref(), &secondary, &path).await {
+                    Ok(()) => {
+                        state.repairs.fetch_add(1, Ordering::Relaxed);
+                    }
+                    // The object has been written to the underlying service by others.
+                    Err(err) if err.kind() == ErrorKind::ConditionNotMatch => {}
+                    Err(err) => {
+                        state.repair_failures.fetch_add(1, Ordering::Relaxed);
+                        warn!(target: "opendal::layers::fallback", "repair {path} failed: {err}");
+                    }
+                }
+                state
+                    .repairing
+                    .lock()
+                    .expect("lock must succeed")
+                    .remove(&path);
+            }));
+    }
+}
+
+/// Copy the object at `path` from the secondary to the underlying service
+/// if it doesn't exist there.
+async fn repair<A: Access>(inner: &A, secondary: &Accessor, path: &str) -> Result<()> {
+    let (_, mut r) = secondary.read(path, OpRead::new()).await?;
+    let (_, mut w) = inner
+        .write(path, OpWrite::new().with_if_not_exists(true))
+        .await?;
+
+    let res: Result<()> = async {
+        if !inner.info().full_capability().write_can_multi {
+            return w.write(r.read_all().await?).await;
+        }
+        loop {
+            let buf = r.read().await?;
+            if buf.is_empty() {
+                return Ok(());
+            }
+            w.write(buf).await?;
+        }
+    }
+    .await;
+
+    match res {
+        Ok(()) => w.close().await.map(|_| ()),
+        Err(err) => {
+            let _ = w.abort().await;
+            Err(err)
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for FallbackAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, oio::Reader>;
+    type BlockingReader = TwoWays<A::BlockingReader, oio::BlockingReader>;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        match self.inner.read(path, args.clone()).await {
+            Ok((rp, r)) => Ok((rp, TwoWays::One(r))),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let (rp, r) = self.fallback(err, self.secondary.read(path, args).await)?;
+                self.repair(path);
+                Ok((rp, TwoWays::Two(r)))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.stat(path, args.clone()).await {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                self.fallback(err, self.secondary.stat(path, args).await)
+            }
+            res => res,
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        match self.inner.blocking_read(path, args.clone()) {
+            Ok((rp, r)) => Ok((rp, TwoWays::One(r))),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let (rp, r) = self.fallback(err, self.secondary.blocking_read(path, args))?;
+                self.repair(path);
+                Ok((rp, TwoWays::Two(r)))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.blocking_stat(path, args.clone()) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                self.fallback(err, self.secondary.blocking_stat(path, args))
+            }
+            res => res,
+        }
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+
+    /// The objects written to [`MockService`] and whether they are written
+    /// with `if_not_exists`.
+    type Written = Arc<Mutex<HashMap<String, (bool, Vec<u8>)>>>;
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        written: Written,
+    }
+
+    impl Access for MockService {
+        type Reader = ();
+        type Writer = MockWriter;
+        type Lister = ();
+        type BlockingReader = ();
+        type BlockingWriter = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                read: true,
+                stat: true,
+                write: true,
+                write_can_multi: true,
+                write_with_if_not_exists: true,
+                ..Default::default()
+            });
+
+            am.into()
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            Err(Error::new(ErrorKind::NotFound, "not found"))
+        }
+
+        async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            Err(Error::new(ErrorKind::NotFound, "not found"))
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((
+                RpWrite::new(),
+                MockWriter {
+                    path: path.to_string(),
+                    if_not_exists: args.if_not_exists(),
+                    buf: Vec::new(),
+                    written: self.written.clone(),
+                },
+            ))
+        }
+    }
+
+    struct MockWriter {
+        path: String,
+        if_not_exists: bool,
+        buf: Vec<u8>,
+        written: Written,
+    }
+
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, bs: Buffer) -> Result<()> {
+            self.buf.extend(bs.to_vec());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<Metadata> {
+            self.written.lock().unwrap().insert(
+                self.path.clone(),
+                (self.if_not_exists, std::mem::take(&mut self.buf)),
+            );
+            Ok(Metadata::new(EntryMode::FILE))
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn wait_for_repairs(layer: &FallbackLayer) {
+        for _ in 0..100 {
+            let snapshot = layer.snapshot();
+            if snapshot.repairs + snapshot.repair_failures > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_repair() {
+        let secondary = Operator::new(services::Memory::default()).unwrap().finish();
+        secondary.write("a.txt", "hello").await.unwrap();
+
+        let srv = MockService::default();
+        let layer = FallbackLayer::new(secondary).with_read_repair(true);
+        let op = OperatorBuilder::new(srv.clone())
+            .finish()
+            .layer(layer.clone());
+
+        assert_eq!(op.stat("a.txt").await.unwrap().content_length(), 5);
+        assert_eq!(op.read("a.txt").await.unwrap().to_vec(), b"hello");
+        assert_eq!(
+            op.read("b.txt").await.unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+
+        wait_for_repairs(&layer).await;
+        assert_eq!(
+            layer.snapshot(),
+            FallbackSnapshot {
+                fallbacks: 2,
+                repairs: 1,
+                repair_failures: 0,
+            }
+        );
+        assert_eq!(
+            srv.written.lock().unwrap().get("a.txt"),
+            Some(&(true, b"hello".to_vec()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_repair_without_if_not_exists() {
+        let secondary = Operator::new(services::Memory::default()).unwrap().finish();
+        secondary.write("a.txt", "hello").await.unwrap();
+
+        let primary = Operator::new(services::Memory::default()).unwrap().finish();
+        let layer = FallbackLayer::new(secondary).with_read_repair(true);
+        let op = primary.clone().layer(layer.clone());
+
+        assert_eq!(op.read("a.txt").await.unwrap().to_vec(), b"hello");
+
+        // Repairs are not started at all.
+        assert_eq!(layer.snapshot().repairs, 0);
+        assert_eq!(
+            primary.stat("a.txt").await.unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,510 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use md5::Digest;
+use md5::Md5;
+
+use crate::raw::*;
+use crate::*;
+
+/// Verify the integrity of full-object reads by comparing checksums.
+///
+/// # Integrity
+///
+/// Some services return checksums of the content, like `Content-MD5` or the CRC32C
+/// returned by gcs. `IntegrityLayer` will compute the checksums of the content while
+/// reading and compare them with the checksums returned by `stat` once the reader
+/// reaches EOF. If they don't match, an error with kind [`ErrorKind::DataCorrupted`]
+/// will be returned instead of the EOF.
+///
+/// This makes it possible to detect bit-rot without reading the content a second time.
+///
+/// # Notes
+///
+/// - Only full-object reads will be verified. Ranged reads (including chunked reads
+///   with `chunk` set) will be passed through as-is.
+/// - `IntegrityLayer` will send an extra `stat` request for every full-object read.
+/// - Objects without a usable checksum will not be verified.
+/// - MD5 and CRC32C are supported, both will be verified if the service returns both.
+/// - Services like s3 return the MD5 of the content as `ETag` for objects that are not
+///   uploaded by multipart. Users can enable [`IntegrityLayer::with_etag`] to use it as
+///   MD5 checksum.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::IntegrityLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(IntegrityLayer::new())
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityLayer {
+    use_etag: bool,
+}
+
+impl IntegrityLayer {
+    /// Create a new `IntegrityLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether to use `ETag` as MD5 checksum if `Content-MD5` is missing.
+    ///
+    /// `ETag` will only be used if it looks like a MD5 digest. Please make sure
+    /// the service returns the MD5 of the content as `ETag` before enabling
+    /// this, some s3 compatible services return `ETag` that looks like MD5 but
+    /// is not, which will make all reads fail.
+    ///
+    /// Default to `false`.
+    pub fn with_etag(mut self, enabled: bool) -> Self {
+        self.use_etag = enabled;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for IntegrityLayer {
+    type LayeredAccess = IntegrityAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        IntegrityAccessor {
+            inner,
+            use_etag: self.use_etag,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IntegrityAccessor<A: Access> {
+    inner: A,
+    use_etag: bool,
+}
+
+impl<A: Access> IntegrityAccessor<A> {
+    /// Decide whether the read should be verified by given args.
+    ///
+    /// Returns `None` if this read is not a full-object read.
+    fn need_verify(args: &OpRead) -> Option<OpStat> {
+        let range = args.range();
+        if range.offset() != 0 {
+            return None;
+        }
+
+        let mut op = OpStat::new();
+        if let Some(v) = args.version() {
+            op = op.with_version(v);
+        }
+        if let Some(key) = args.customer_key() {
+            op = op.with_customer_key(key.clone());
+        }
+        Some(op)
+    }
+
+    /// Build the checksum expectation by given metadata.
+    fn build_expect(&self, args: &OpRead, meta: &Metadata) -> Option<Checksum> {
+        // Reading part of the object can't be verified.
+        if let Some(size) = args.range().size() {
+            if size != meta.content_length() {
+                return None;
+            }
+        }
+
+        let md5 = meta.content_md5().and_then(parse_content_md5).or_else(|| {
+            if self.use_etag {
+                meta.etag().and_then(parse_etag_md5)
+            } else {
+                None
+            }
+        });
+        let crc32c = meta.content_crc32c().and_then(parse_content_crc32c);
+        if md5.is_none() && crc32c.is_none() {
+            return None;
+        }
+
+        Some(Checksum {
+            md5,
+            crc32c,
+            size: meta.content_length(),
+        })
+    }
+}
+
+impl<A: Access> LayeredAccess for IntegrityAccessor<A> {
+    type Inner = A;
+    type Reader = IntegrityReader<A::Reader>;
+    type BlockingReader = IntegrityReader<A::BlockingReader>;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let expect = match Self::need_verify(&args) {
+            Some(op) => {
+                let meta = self.inner.stat(path, op).await?.into_metadata();
+                self.build_expect(&args, &meta)
+            }
+            None => None,
+        };
+
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, IntegrityReader::new(r, path, expect)))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let expect = match Self::need_verify(&args) {
+            Some(op) => {
+                let meta = self.inner.blocking_stat(path, op)?.into_metadata();
+                self.build_expect(&args, &meta)
+            }
+            None => None,
+        };
+
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, IntegrityReader::new(r, path, expect)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// Parse base64 encoded `Content-MD5` into lower case hex string.
+fn parse_content_md5(v: &str) -> Option<String> {
+    let bs = BASE64_STANDARD.decode(v.trim()).ok()?;
+    if bs.len() != 16 {
+        return None;
+    }
+    Some(bs.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Parse base64 encoded big-endian CRC32C.
+fn parse_content_crc32c(v: &str) -> Option<u32> {
+    let bs = BASE64_STANDARD.decode(v.trim()).ok()?;
+    let bs: [u8; 4] = bs.try_into().ok()?;
+    Some(u32::from_be_bytes(bs))
+}
+
+/// Parse strong `ETag` into lower case hex string if it looks like a MD5 digest.
+///
+/// Weak etags (`W/"xxx"`) and multipart etags (`"xxx-2"`) will be ignored.
+fn parse_etag_md5(v: &str) -> Option<String> {
+    let etag = Etag::parse(v);
+    if etag.is_weak() {
+        return None;
+    }
+    let v = etag.value();
+    if v.len() != 32 || !v.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(v.to_ascii_lowercase())
+}
+
+#[derive(Debug, Clone)]
+struct Checksum {
+    md5: Option<String>,
+    crc32c: Option<u32>,
+    size: u64,
+}
+
+/// IntegrityReader will compute the checksum of the content while reading.
+pub struct IntegrityReader<R> {
+    inner: R,
+    path: String,
+
+    expect: Option<Checksum>,
+    md5: Md5,
+    crc32c: u32,
+    read: u64,
+}
+
+impl<R> IntegrityReader<R> {
+    fn new(inner: R, path: &str, expect: Option<Checksum>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            expect,
+            md5: Md5::new(),
+            crc32c: 0,
+            read: 0,
+        }
+    }
+
+    fn update(&mut self, buf: &Buffer) {
+        let Some(expect) = &self.expect else {
+            return;
+        };
+        for bs in buf.clone() {
+            if expect.md5.is_some() {
+                self.md5.update(&bs);
+            }
+            if expect.crc32c.is_some() {
+                self.crc32c = crc32c::crc32c_append(self.crc32c, &bs);
+            }
+        }
+        self.read += buf.len() as u64;
+    }
+
+    /// Verify the checksum while reaching EOF.
+    ///
+    /// The expectation will be taken so that we only verify once.
+    fn verify(&mut self) -> Result<()> {
+        let Some(expect) = self.expect.take() else {
+            return Ok(());
+        };
+
+        if self.read != expect.size {
+            return Err(
+                Error::new(ErrorKind::DataCorrupted, "data corrupted: size mismatch")
+                    .with_operation(Operation::ReaderRead)
+                    .with_context("path", &self.path)
+                    .with_context("expect", expect.size.to_string())
+                    .with_context("actual", self.read.to_string()),
+            );
+        }
+
+        if let Some(md5) = expect.md5 {
+            let actual = format!("{:x}", std::mem::take(&mut self.md5).finalize());
+            if actual != md5 {
+                return Err(
+                    Error::new(ErrorKind::DataCorrupted, "data corrupted: md5 mismatch")
+                        .with_operation(Operation::ReaderRead)
+                        .with_context("path", &self.path)
+                        .with_context("expect", md5)
+                        .with_context("actual", actual),
+                );
+            }
+        }
+
+        if let Some(crc32c) = expect.crc32c {
+            if self.crc32c != crc32c {
+                return Err(Error::new(
+                    ErrorKind::DataCorrupted,
+                    "data corrupted: crc32c mismatch",
+                )
+                .with_operation(Operation::ReaderRead)
+                .with_context("path", &self.path)
+                .with_context("expect", format!("{crc32c:08x}"))
+                .with_context("actual", format!("{:08x}", self.crc32c)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: oio::Read> oio::Read for IntegrityReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        let buf = self.inner.read().await?;
+        if buf.is_empty() {
+            self.verify()?;
+        } else {
+            self.update(&buf);
+        }
+        Ok(buf)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for IntegrityReader<R> {
+    fn read(&mut self) -> Result<Buffer> {
+        let buf = self.inner.read()?;
+        if buf.is_empty() {
+            self.verify()?;
+        } else {
+            self.update(&buf);
+        }
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::layers::TypeEraseLayer;
+
+    const DATA: &str = "Hello, World!";
+
+    #[derive(Debug, Clone)]
+    struct MockService {
+        content: Bytes,
+        etag: String,
+        crc32c: Option<String>,
+    }
+
+    impl MockService {
+        fn new(content: &'static str, checksum_of: &str) -> Self {
+            Self {
+                content: Bytes::from(content),
+                etag: format!("\"{:x}\"", Md5::digest(checksum_of.as_bytes())),
+                crc32c: None,
+            }
+        }
+
+        fn with_crc32c(mut self, checksum_of: &str) -> Self {
+            let crc = crc32c::crc32c(checksum_of.as_bytes());
+            self.crc32c = Some(BASE64_STANDARD.encode(crc.to_be_bytes()));
+            self
+        }
+    }
+
+    impl Access for MockService {
+        type Reader = Buffer;
+        type Writer = ();
+        type Lister = ();
+        type BlockingReader = ();
+        type BlockingWriter = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                read: true,
+                stat: true,
+                ..Default::default()
+            });
+
+            am.into()
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            let mut meta = Metadata::new(EntryMode::FILE)
+                .with_content_length(self.content.len() as u64)
+                .with_etag(self.etag.clone());
+            if let Some(v) = &self.crc32c {
+                meta.set_content_crc32c(v);
+            }
+            Ok(RpStat::new(meta))
+        }
+
+        async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            let bs = Buffer::from(self.content.clone()).slice(args.range().to_range_as_usize());
+            Ok((RpRead::new(), bs))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_matched() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService::new(DATA, DATA))) as Accessor;
+        let op = Operator::from_inner(acc).layer(IntegrityLayer::new().with_etag(true));
+
+        let bs = op.read("test").await.unwrap();
+        assert_eq!(bs.to_bytes(), Bytes::from(DATA));
+    }
+
+    #[tokio::test]
+    async fn test_read_corrupted() {
+        let acc =
+            Arc::new(TypeEraseLayer.layer(MockService::new(DATA, "Hello, World?"))) as Accessor;
+        let op = Operator::from_inner(acc).layer(IntegrityLayer::new().with_etag(true));
+
+        let err = op.read("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DataCorrupted);
+        assert!(!err.is_temporary());
+        assert!(err.to_string().contains("data corrupted"));
+
+        // Ranged read will not be verified.
+        let bs = op.read_with("test").range(1..4).await.unwrap();
+        assert_eq!(bs.to_bytes(), Bytes::from(&DATA[1..4]));
+    }
+
+    #[tokio::test]
+    async fn test_read_without_etag() {
+        let acc =
+            Arc::new(TypeEraseLayer.layer(MockService::new(DATA, "Hello, World?"))) as Accessor;
+        let op = Operator::from_inner(acc).layer(IntegrityLayer::new());
+
+        let bs = op.read("test").await.unwrap();
+        assert_eq!(bs.to_bytes(), Bytes::from(DATA));
+    }
+
+    #[tokio::test]
+    async fn test_read_crc32c() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService::new(DATA, DATA).with_crc32c(DATA)))
+            as Accessor;
+        let op = Operator::from_inner(acc).layer(IntegrityLayer::new());
+        let bs = op.read("test").await.unwrap();
+        assert_eq!(bs.to_bytes(), Bytes::from(DATA));
+
+        let acc = Arc::new(
+            TypeEraseLayer.layer(MockService::new(DATA, DATA).with_crc32c("Hello, World?")),
+        ) as Accessor;
+        let op = Operator::from_inner(acc).layer(IntegrityLayer::new());
+        let err = op.read("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DataCorrupted);
+        assert!(err.to_string().contains("crc32c mismatch"));
+    }
+
+    #[test]
+    fn test_parse_content_crc32c() {
+        assert_eq!(parse_content_crc32c("j/un9g=="), Some(0x8ffba7f6));
+        assert_eq!(parse_content_crc32c("fHcEH1vPwA6eTPqxuasXcg=="), None);
+        assert_eq!(parse_content_crc32c("not base64"), None);
+    }
+
+    #[test]
+    fn test_parse_etag_md5() {
+        let cases = vec![
+            (
+                "\"9a0364b9e99bb480dd25e1f0284c8555\"",
+                Some("9a0364b9e99bb480dd25e1f0284c8555"),
+            ),
+            (
+                "9A0364B9E99BB480DD25E1F0284C8555",
+                Some("9a0364b9e99bb480dd25e1f0284c8555"),
+            ),
+            ("W/\"9a0364b9e99bb480dd25e1f0284c8555\"", None),
+            ("\"9a0364b9e99bb480dd25e1f0284c8555-2\"", None),
+            ("\"0815\"", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(parse_etag_md5(input).as_deref(), expected, "{input}");
+        }
+    }
+}
@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use log::log;
 use log::Level;
+use log::LevelFilter;
 
 use crate::raw::*;
 use crate::*;
@@ -71,6 +75,42 @@ use crate::*;
 /// RUST_LOG="info,opendal::services=debug" ./app
 /// ```
 ///
+/// # Log Levels
+///
+/// The level of logs for every operation can be changed by
+/// [`LoggingLayer::with_level`]. Use [`LevelFilter::Off`] to disable logs of
+/// an operation. Errors are always logged in `Warn` or `Error` level.
+///
+/// Operations that take longer than the threshold set by
+/// [`LoggingLayer::with_slow_threshold`] will be logged again in `Warn` level
+/// after they finished.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// #
+/// # use log::LevelFilter;
+/// # use opendal::layers::LoggingLayer;
+/// # use opendal::raw::Operation;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         LoggingLayer::default()
+///             // Keep routine reads and stats quiet.
+///             .with_level(Operation::Read, LevelFilter::Trace)
+///             .with_level(Operation::Stat, LevelFilter::Trace)
+///             // Only log reader reads while they are slow.
+///             .with_level(Operation::ReaderRead, LevelFilter::Off)
+///             .with_slow_threshold(Duration::from_secs(1)),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+///
 /// # Logging Interceptor
 ///
 /// You can implement your own logging interceptor to customize the logging behavior.
@@ -111,12 +151,14 @@ use crate::*;
 #[derive(Debug)]
 pub struct LoggingLayer<I = DefaultLoggingInterceptor> {
     logger: I,
+    slow_threshold: Option<Duration>,
 }
 
 impl Default for LoggingLayer {
     fn default() -> Self {
         Self {
-            logger: DefaultLoggingInterceptor,
+            logger: DefaultLoggingInterceptor::default(),
+            slow_threshold: None,
         }
     }
 }
@@ -124,7 +166,33 @@ impl Default for LoggingLayer {
 impl LoggingLayer {
     /// Create the layer with specific logging interceptor.
     pub fn new<I: LoggingInterceptor>(logger: I) -> LoggingLayer<I> {
-        LoggingLayer { logger }
+        LoggingLayer {
+            logger,
+            slow_threshold: None,
+        }
+    }
+
+    /// Set the level of non-error logs for given operation.
+    ///
+    /// By default, oneshot operations are logged in `Debug` level and others
+    /// (like `Reader::read`) are logged in `Trace` level. Set to
+    /// [`LevelFilter::Off`] to disable non-error logs of this operation.
+    pub fn with_level(mut self, operation: Operation, level: LevelFilter) -> Self {
+        self.logger = self.logger.with_level(operation, level);
+        self
+    }
+}
+
+impl<I: LoggingInterceptor> LoggingLayer<I> {
+    /// Log operations that take longer than given threshold via
+    /// [`LoggingInterceptor::log_slow`].
+    ///
+    /// Only the operations on the accessor (like `stat`, `read` and `write`)
+    /// are measured. For `read`, `write` and `list`, the time to create the
+    /// reader, writer or lister is measured.
+    pub fn with_slow_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_threshold = Some(threshold);
+        self
     }
 }
 
@@ -138,6 +206,7 @@ impl<A: Access, I: LoggingInterceptor> Layer<A> for LoggingLayer<I> {
 
             info,
             logger: self.logger.clone(),
+            slow_threshold: self.slow_threshold,
         }
     }
 }
@@ -167,11 +236,53 @@ pub trait LoggingInterceptor: Debug + Clone + Send + Sync + Unpin + 'static {
         message: &str,
         err: Option<&Error>,
     );
+
+    /// Everytime an operation took longer than the slow threshold, this
+    /// function will be called after it finished.
+    ///
+    /// Logs in `Warn` level by default.
+    ///
+    /// # Inputs
+    ///
+    /// - info: The service's access info.
+    /// - operation: The slow operation.
+    /// - context: Additional context of the log like path, etc.
+    /// - elapsed: The time the operation took.
+    fn log_slow(
+        &self,
+        info: &AccessorInfo,
+        operation: Operation,
+        context: &[(&str, &str)],
+        elapsed: Duration,
+    ) {
+        log!(
+            target: LOGGING_TARGET,
+            Level::Warn,
+            "service={} name={} {}: {operation} slow: took {elapsed:?}",
+            info.scheme(),
+            info.name(),
+            LoggingContext(context),
+        );
+    }
 }
 
 /// The DefaultLoggingInterceptor will log the message by the standard logging macro.
-#[derive(Debug, Copy, Clone, Default)]
-pub struct DefaultLoggingInterceptor;
+#[derive(Debug, Clone, Default)]
+pub struct DefaultLoggingInterceptor {
+    levels: Arc<HashMap<Operation, LevelFilter>>,
+}
+
+impl DefaultLoggingInterceptor {
+    /// Set the level of non-error logs for given operation.
+    ///
+    /// By default, oneshot operations are logged in `Debug` level and others
+    /// (like `Reader::read`) are logged in `Trace` level. Set to
+    /// [`LevelFilter::Off`] to disable non-error logs of this operation.
+    pub fn with_level(mut self, operation: Operation, level: LevelFilter) -> Self {
+        Arc::make_mut(&mut self.levels).insert(operation, level);
+        self
+    }
+}
 
 impl LoggingInterceptor for DefaultLoggingInterceptor {
     #[inline]
@@ -211,10 +322,13 @@ impl LoggingInterceptor for DefaultLoggingInterceptor {
         }
 
         // Print debug message if operation is oneshot, otherwise in trace.
-        let lvl = if operation.is_oneshot() {
-            Level::Debug
-        } else {
-            Level::Trace
+        let lvl = match self.levels.get(&operation) {
+            Some(level) => match level.to_level() {
+                Some(lvl) => lvl,
+                None => return,
+            },
+            None if operation.is_oneshot() => Level::Debug,
+            None => Level::Trace,
         };
 
         log!(
@@ -249,6 +363,20 @@ pub struct LoggingAccessor<A: Access, I: LoggingInterceptor> {
 
     info: Arc<AccessorInfo>,
     logger: I,
+    slow_threshold: Option<Duration>,
+}
+
+impl<A: Access, I: LoggingInterceptor> LoggingAccessor<A, I> {
+    fn check_slow(&self, operation: Operation, context: &[(&str, &str)], start: Instant) {
+        let Some(threshold) = self.slow_threshold else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        if elapsed >= threshold {
+            self.logger
+                .log_slow(&self.info, operation, context, elapsed);
+        }
+    }
 }
 
 static LOGGING_TARGET: &str = "opendal::services";
@@ -287,7 +415,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .create_dir(path, args)
             .await
             .map(|v| {
@@ -309,7 +439,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::CreateDir, &[("path", path)], start);
+        res
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
@@ -321,7 +453,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .read(path, args)
             .await
             .map(|(rp, r)| {
@@ -346,7 +480,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Read, &[("path", path)], start);
+        res
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -358,7 +494,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .write(path, args)
             .await
             .map(|(rp, w)| {
@@ -381,7 +519,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Write, &[("path", path)], start);
+        res
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -393,7 +533,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .copy(from, to, args)
             .await
             .map(|v| {
@@ -415,7 +557,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Copy, &[("from", from), ("to", to)], start);
+        res
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
@@ -427,7 +571,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .rename(from, to, args)
             .await
             .map(|v| {
@@ -449,7 +595,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Rename, &[("from", from), ("to", to)], start);
+        res
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
@@ -461,7 +609,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .stat(path, args)
             .await
             .map(|v| {
@@ -483,7 +633,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Stat, &[("path", path)], start);
+        res
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
@@ -495,7 +647,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .delete(path, args.clone())
             .await
             .map(|v| {
@@ -517,7 +671,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Delete, &[("path", path)], start);
+        res
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
@@ -529,7 +685,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .list(path, args)
             .await
             .map(|(rp, v)| {
@@ -552,7 +710,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::List, &[("path", path)], start);
+        res
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
@@ -564,7 +724,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .presign(path, args)
             .await
             .map(|v| {
@@ -586,7 +748,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::Presign, &[("path", path)], start);
+        res
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
@@ -600,7 +764,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .batch(args)
             .await
             .map(|v| {
@@ -627,7 +793,13 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(
+            Operation::Batch,
+            &[("op", op.into_static()), ("count", &count.to_string())],
+            start,
+        );
+        res
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
@@ -639,7 +811,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_create_dir(path, args)
             .map(|v| {
                 self.logger.log(
@@ -660,7 +834,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingCreateDir, &[("path", path)], start);
+        res
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
@@ -672,7 +848,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_read(path, args.clone())
             .map(|(rp, r)| {
                 self.logger.log(
@@ -694,7 +872,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingRead, &[("path", path)], start);
+        res
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
@@ -706,7 +886,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_write(path, args)
             .map(|(rp, w)| {
                 self.logger.log(
@@ -728,7 +910,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingWrite, &[("path", path)], start);
+        res
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -740,7 +924,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_copy(from, to, args)
             .map(|v| {
                 self.logger.log(
@@ -761,7 +947,13 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(
+            Operation::BlockingCopy,
+            &[("from", from), ("to", to)],
+            start,
+        );
+        res
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
@@ -773,7 +965,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_rename(from, to, args)
             .map(|v| {
                 self.logger.log(
@@ -794,7 +988,13 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(
+            Operation::BlockingRename,
+            &[("from", from), ("to", to)],
+            start,
+        );
+        res
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
@@ -806,7 +1006,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_stat(path, args)
             .map(|v| {
                 self.logger.log(
@@ -827,7 +1029,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingStat, &[("path", path)], start);
+        res
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
@@ -839,7 +1043,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_delete(path, args)
             .map(|v| {
                 self.logger.log(
@@ -860,7 +1066,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingDelete, &[("path", path)], start);
+        res
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
@@ -872,7 +1080,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             None,
         );
 
-        self.inner
+        let start = Instant::now();
+        let res = self
+            .inner
             .blocking_list(path, args)
             .map(|(rp, v)| {
                 self.logger.log(
@@ -894,7 +1104,9 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
                     Some(&err),
                 );
                 err
-            })
+            });
+        self.check_slow(Operation::BlockingList, &[("path", path)], start);
+        res
     }
 }
 
@@ -1113,7 +1325,7 @@ impl<W: oio::Write, I: LoggingInterceptor> oio::Write for LoggingWriter<W, I> {
         }
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.logger.log(
             &self.info,
             Operation::WriterClose,
@@ -1123,7 +1335,7 @@ impl<W: oio::Write, I: LoggingInterceptor> oio::Write for LoggingWriter<W, I> {
         );
 
         match self.inner.close().await {
-            Ok(_) => {
+            Ok(meta) => {
                 self.logger.log(
                     &self.info,
                     Operation::WriterClose,
@@ -1131,7 +1343,7 @@ impl<W: oio::Write, I: LoggingInterceptor> oio::Write for LoggingWriter<W, I> {
                     "succeeded",
                     None,
                 );
-                Ok(())
+                Ok(meta)
             }
             Err(err) => {
                 self.logger.log(
@@ -1195,7 +1407,7 @@ impl<W: oio::BlockingWrite, I: LoggingInterceptor> oio::BlockingWrite for Loggin
         }
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.logger.log(
             &self.info,
             Operation::BlockingWriterClose,
@@ -1205,7 +1417,7 @@ impl<W: oio::BlockingWrite, I: LoggingInterceptor> oio::BlockingWrite for Loggin
         );
 
         match self.inner.close() {
-            Ok(_) => {
+            Ok(meta) => {
                 self.logger.log(
                     &self.info,
                     Operation::BlockingWriterWrite,
@@ -1213,7 +1425,7 @@ impl<W: oio::BlockingWrite, I: LoggingInterceptor> oio::BlockingWrite for Loggin
                     "succeeded",
                     None,
                 );
-                Ok(())
+                Ok(meta)
             }
             Err(err) => {
                 self.logger.log(
@@ -1351,3 +1563,57 @@ impl<P: oio::BlockingList, I: LoggingInterceptor> oio::BlockingList for LoggingL
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::services;
+
+    #[derive(Debug, Clone, Default)]
+    struct SlowInterceptor {
+        slow: Arc<Mutex<Vec<Operation>>>,
+    }
+
+    impl LoggingInterceptor for SlowInterceptor {
+        fn log(
+            &self,
+            _: &AccessorInfo,
+            _: Operation,
+            _: &[(&str, &str)],
+            _: &str,
+            _: Option<&Error>,
+        ) {
+        }
+
+        fn log_slow(
+            &self,
+            _: &AccessorInfo,
+            operation: Operation,
+            _: &[(&str, &str)],
+            _: Duration,
+        ) {
+            self.slow.lock().unwrap().push(operation);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_threshold() {
+        let interceptor = SlowInterceptor::default();
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(LoggingLayer::new(interceptor.clone()).with_slow_threshold(Duration::ZERO))
+            .finish();
+
+        op.stat("not_exist").await.expect_err("stat must fail");
+        assert_eq!(*interceptor.slow.lock().unwrap(), vec![Operation::Stat]);
+
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(LoggingLayer::new(interceptor.clone()))
+            .finish();
+        op.stat("not_exist").await.expect_err("stat must fail");
+        assert_eq!(interceptor.slow.lock().unwrap().len(), 1);
+    }
+}
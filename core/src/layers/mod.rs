@@ -26,12 +26,35 @@ pub(crate) use error_context::ErrorContextLayer;
 mod complete;
 pub(crate) use complete::CompleteLayer;
 
+mod config_info;
+pub(crate) use config_info::ConfigInfoLayer;
+
+mod adaptive;
+
+mod capability_override;
+pub use capability_override::CapabilityOverrideLayer;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
+#[cfg(feature = "layers-dedup")]
+mod dedup;
+#[cfg(feature = "layers-dedup")]
+pub use self::dedup::DedupLayer;
+
+mod dry_run;
+pub use dry_run::DryRunLayer;
+
+mod fallback;
+pub use fallback::FallbackLayer;
+pub use fallback::FallbackSnapshot;
+
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
 
+mod integrity;
+pub use integrity::IntegrityLayer;
+
 mod logging;
 pub use logging::LoggingInterceptor;
 pub use logging::LoggingLayer;
@@ -92,11 +115,53 @@ mod oteltrace;
 #[cfg(feature = "layers-otel-trace")]
 pub use self::oteltrace::OtelTraceLayer;
 
+#[cfg(feature = "layers-path-rewrite")]
+mod path_rewrite;
+#[cfg(feature = "layers-path-rewrite")]
+pub use self::path_rewrite::PathRewriteLayer;
+
+mod compact;
+pub use compact::CompactLayer;
+
+mod policy;
+pub use policy::PolicyLayer;
+
+mod qos;
+pub use qos::QosLayer;
+
+mod quota;
+pub use quota::QuotaLayer;
+
+mod retention;
+pub use retention::RetentionLayer;
+
+mod shadow;
+pub use shadow::ShadowLayer;
+pub use shadow::ShadowSnapshot;
+
+mod shard;
+pub use shard::HashShardMapper;
+pub use shard::PrefixShardMapper;
+pub use shard::ShardLayer;
+pub use shard::ShardMapper;
+
+mod stats;
+pub use stats::StatsLayer;
+pub use stats::StatsSnapshot;
+
+#[cfg(feature = "layers-stat-cache")]
+mod stat_cache;
+#[cfg(feature = "layers-stat-cache")]
+pub use self::stat_cache::StatCacheLayer;
+
 #[cfg(feature = "layers-throttle")]
 mod throttle;
 #[cfg(feature = "layers-throttle")]
 pub use self::throttle::ThrottleLayer;
 
+mod ttl;
+pub use ttl::TtlLayer;
+
 #[cfg(feature = "layers-await-tree")]
 mod await_tree;
 #[cfg(feature = "layers-await-tree")]
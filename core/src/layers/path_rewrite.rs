@@ -0,0 +1,522 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::raw::*;
+use crate::*;
+
+/// Rewrite paths between the application and underlying services.
+///
+/// # Rewrite
+///
+/// PathRewriteLayer maps the logical paths used by the application into the
+/// physical paths stored in services, and maps them back in list results,
+/// multipart uploads and error messages.
+///
+/// - Prefix rules added by [`PathRewriteLayer::with_prefix`] are reversible
+///   by nature. The first matched rule will be applied.
+/// - Regex rules added by [`PathRewriteLayer::with_regex`] are applied one by
+///   one after prefix rules while sending paths to services. Add the reverse
+///   rules by [`PathRewriteLayer::with_reverse_regex`] to map paths returned by
+///   services back, which are applied one by one before prefix rules.
+///
+/// # Notes
+///
+/// Paths that don't match any rule will be passed as is.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::PathRewriteLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// // Expose `tenant-a/...` while storing at `prod/v2/tenant-a/...`.
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         PathRewriteLayer::new()
+///             .with_prefix("tenant-a/", "prod/v2/tenant-a/")
+///             .with_regex(r"\.jpeg$", ".jpg")?
+///             .with_reverse_regex(r"\.jpg$", ".jpeg")?,
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PathRewriteLayer {
+    rules: Rules,
+}
+
+impl PathRewriteLayer {
+    /// Create a new `PathRewriteLayer` without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map paths starting with `logical` to paths starting with `physical`.
+    pub fn with_prefix(mut self, logical: &str, physical: &str) -> Self {
+        self.rules
+            .prefixes
+            .push((logical.to_string(), physical.to_string()));
+        self
+    }
+
+    /// Rewrite paths sent to services by given regex pattern.
+    ///
+    /// The `replacement` follows the syntax of [`Regex::replace_all`], for
+    /// example, `$1` refers to the first capture group.
+    pub fn with_regex(mut self, pattern: &str, replacement: &str) -> Result<Self> {
+        self.rules
+            .regexes
+            .push((new_regex(pattern)?, replacement.to_string()));
+        Ok(self)
+    }
+
+    /// Rewrite paths returned by services by given regex pattern.
+    ///
+    /// The `replacement` follows the syntax of [`Regex::replace_all`], for
+    /// example, `$1` refers to the first capture group.
+    pub fn with_reverse_regex(mut self, pattern: &str, replacement: &str) -> Result<Self> {
+        self.rules
+            .reverse_regexes
+            .push((new_regex(pattern)?, replacement.to_string()));
+        Ok(self)
+    }
+}
+
+fn new_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "regex pattern is invalid")
+            .with_context("pattern", pattern)
+            .set_source(err)
+    })
+}
+
+impl<A: Access> Layer<A> for PathRewriteLayer {
+    type LayeredAccess = PathRewriteAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        PathRewriteAccessor {
+            inner,
+            rules: Arc::new(self.rules.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Rules {
+    prefixes: Vec<(String, String)>,
+    regexes: Vec<(Regex, String)>,
+    reverse_regexes: Vec<(Regex, String)>,
+}
+
+impl Rules {
+    /// Rewrite logical path into physical path.
+    fn forward(&self, path: &str) -> String {
+        let mut path = match self
+            .prefixes
+            .iter()
+            .find(|(logical, _)| path.starts_with(logical.as_str()))
+        {
+            Some((logical, physical)) => format!("{physical}{}", &path[logical.len()..]),
+            None => path.to_string(),
+        };
+        for (re, replacement) in &self.regexes {
+            path = re.replace_all(&path, replacement.as_str()).into_owned();
+        }
+        path
+    }
+
+    /// Rewrite physical path back into logical path.
+    fn backward(&self, path: &str) -> String {
+        let mut path = path.to_string();
+        for (re, replacement) in &self.reverse_regexes {
+            path = re.replace_all(&path, replacement.as_str()).into_owned();
+        }
+        match self
+            .prefixes
+            .iter()
+            .find(|(_, physical)| path.starts_with(physical.as_str()))
+        {
+            Some((logical, physical)) => format!("{logical}{}", &path[physical.len()..]),
+            None => path,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PathRewriteAccessor<A: Access> {
+    inner: A,
+    rules: Arc<Rules>,
+}
+
+impl<A: Access> PathRewriteAccessor<A> {
+    fn wrap<T>(&self, inner: T, physical: String, logical: &str) -> PathRewriteWrapper<T> {
+        PathRewriteWrapper {
+            inner,
+            rules: self.rules.clone(),
+            physical,
+            logical: logical.to_string(),
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for PathRewriteAccessor<A> {
+    type Inner = A;
+    type Reader = PathRewriteWrapper<A::Reader>;
+    type BlockingReader = PathRewriteWrapper<A::BlockingReader>;
+    type Writer = PathRewriteWrapper<A::Writer>;
+    type BlockingWriter = PathRewriteWrapper<A::BlockingWriter>;
+    type Lister = PathRewriteWrapper<A::Lister>;
+    type BlockingLister = PathRewriteWrapper<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let p = self.rules.forward(path);
+        self.inner
+            .create_dir(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let p = self.rules.forward(path);
+        match self.inner.read(&p, args).await {
+            Ok((rp, r)) => Ok((rp, self.wrap(r, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let p = self.rules.forward(path);
+        match self.inner.write(&p, args).await {
+            Ok((rp, w)) => Ok((rp, self.wrap(w, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (f, t) = (self.rules.forward(from), self.rules.forward(to));
+        self.inner
+            .copy(&f, &t, args)
+            .await
+            .map_err(|err| err.replace_path(&f, from).replace_path(&t, to))
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (f, t) = (self.rules.forward(from), self.rules.forward(to));
+        self.inner
+            .rename(&f, &t, args)
+            .await
+            .map_err(|err| err.replace_path(&f, from).replace_path(&t, to))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let p = self.rules.forward(path);
+        self.inner
+            .stat(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let p = self.rules.forward(path);
+        self.inner
+            .delete(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let p = self.rules.forward(path);
+        match self.inner.list(&p, args).await {
+            Ok((rp, l)) => Ok((rp, self.wrap(l, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| {
+                let op = match op {
+                    BatchOperation::Copy(to, args) => {
+                        BatchOperation::Copy(self.rules.forward(&to), args)
+                    }
+                    op => op,
+                };
+                (self.rules.forward(&path), op)
+            })
+            .collect();
+
+        let rp = self.inner.batch(OpBatch::new(ops)).await?;
+        let results = rp
+            .into_results()
+            .into_iter()
+            .map(|(p, res)| {
+                let path = self.rules.backward(&p);
+                let res = res.map_err(|err| err.replace_path(&p, &path));
+                (path, res)
+            })
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let p = self.rules.forward(path);
+        self.inner
+            .presign(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let p = self.rules.forward(path);
+        let rp = self
+            .inner
+            .list_multipart_uploads(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))?;
+
+        let uploads = rp
+            .into_uploads()
+            .into_iter()
+            .map(|v| {
+                let upload = MultipartUpload::new(&self.rules.backward(v.path()), v.upload_id());
+                match v.initiated() {
+                    Some(initiated) => upload.with_initiated(initiated),
+                    None => upload,
+                }
+            })
+            .collect();
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let p = self.rules.forward(path);
+        self.inner
+            .abort_multipart_upload(&p, args)
+            .await
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let p = self.rules.forward(path);
+        self.inner
+            .blocking_create_dir(&p, args)
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let p = self.rules.forward(path);
+        match self.inner.blocking_read(&p, args) {
+            Ok((rp, r)) => Ok((rp, self.wrap(r, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let p = self.rules.forward(path);
+        match self.inner.blocking_write(&p, args) {
+            Ok((rp, w)) => Ok((rp, self.wrap(w, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (f, t) = (self.rules.forward(from), self.rules.forward(to));
+        self.inner
+            .blocking_copy(&f, &t, args)
+            .map_err(|err| err.replace_path(&f, from).replace_path(&t, to))
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (f, t) = (self.rules.forward(from), self.rules.forward(to));
+        self.inner
+            .blocking_rename(&f, &t, args)
+            .map_err(|err| err.replace_path(&f, from).replace_path(&t, to))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let p = self.rules.forward(path);
+        self.inner
+            .blocking_stat(&p, args)
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let p = self.rules.forward(path);
+        self.inner
+            .blocking_delete(&p, args)
+            .map_err(|err| err.replace_path(&p, path))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let p = self.rules.forward(path);
+        match self.inner.blocking_list(&p, args) {
+            Ok((rp, l)) => Ok((rp, self.wrap(l, p, path))),
+            Err(err) => Err(err.replace_path(&p, path)),
+        }
+    }
+}
+
+pub struct PathRewriteWrapper<T> {
+    inner: T,
+    rules: Arc<Rules>,
+    physical: String,
+    logical: String,
+}
+
+impl<T> PathRewriteWrapper<T> {
+    fn map_err(&self, err: Error) -> Error {
+        err.replace_path(&self.physical, &self.logical)
+    }
+
+    fn map_entry(&self, entry: Option<oio::Entry>) -> Option<oio::Entry> {
+        entry.map(|mut entry| {
+            let path = self.rules.backward(entry.path());
+            entry.set_path(&path);
+            entry
+        })
+    }
+}
+
+impl<T: oio::Read> oio::Read for PathRewriteWrapper<T> {
+    async fn read(&mut self) -> Result<Buffer> {
+        self.inner.read().await.map_err(|err| self.map_err(err))
+    }
+}
+
+impl<T: oio::BlockingRead> oio::BlockingRead for PathRewriteWrapper<T> {
+    fn read(&mut self) -> Result<Buffer> {
+        self.inner.read().map_err(|err| self.map_err(err))
+    }
+}
+
+impl<T: oio::Write> oio::Write for PathRewriteWrapper<T> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).await.map_err(|err| self.map_err(err))
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.inner.close().await.map_err(|err| self.map_err(err))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await.map_err(|err| self.map_err(err))
+    }
+}
+
+impl<T: oio::BlockingWrite> oio::BlockingWrite for PathRewriteWrapper<T> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).map_err(|err| self.map_err(err))
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        self.inner.close().map_err(|err| self.map_err(err))
+    }
+}
+
+impl<T: oio::List> oio::List for PathRewriteWrapper<T> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        match self.inner.next().await {
+            Ok(entry) => Ok(self.map_entry(entry)),
+            Err(err) => Err(self.map_err(err)),
+        }
+    }
+}
+
+impl<T: oio::BlockingList> oio::BlockingList for PathRewriteWrapper<T> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        match self.inner.next() {
+            Ok(entry) => Ok(self.map_entry(entry)),
+            Err(err) => Err(self.map_err(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[test]
+    fn test_rules() {
+        let layer = PathRewriteLayer::new()
+            .with_prefix("tenant-a/", "prod/v2/tenant-a/")
+            .with_regex(r"^(.*)\.jpeg$", "$1.jpg")
+            .unwrap()
+            .with_reverse_regex(r"^(.*)\.jpg$", "$1.jpeg")
+            .unwrap();
+        let rules = layer.rules;
+
+        assert_eq!(rules.forward("tenant-a/x"), "prod/v2/tenant-a/x");
+        assert_eq!(rules.forward("tenant-b/x"), "tenant-b/x");
+        assert_eq!(rules.forward("tenant-a/x.jpeg"), "prod/v2/tenant-a/x.jpg");
+        assert_eq!(rules.backward("prod/v2/tenant-a/x.jpg"), "tenant-a/x.jpeg");
+        assert_eq!(rules.backward("other/x"), "other/x");
+
+        assert!(PathRewriteLayer::new().with_regex("(", "").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_path_rewrite() {
+        let inner = Operator::new(services::Memory::default()).unwrap().finish();
+        let op = inner
+            .clone()
+            .layer(PathRewriteLayer::new().with_prefix("tenant-a/", "prod/v2/tenant-a/"));
+
+        op.write("tenant-a/x", "abcd").await.expect("must succeed");
+        assert!(inner.exists("prod/v2/tenant-a/x").await.unwrap());
+
+        let entries: Vec<_> = op
+            .lister_with("tenant-a/")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert!(paths.contains(&"tenant-a/x"), "{paths:?}");
+
+        let err = op.stat("tenant-a/not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let msg = err.to_string();
+        assert!(msg.contains("tenant-a/not_exist"), "{msg}");
+        assert!(!msg.contains("prod/v2/"), "{msg}");
+    }
+}
@@ -0,0 +1,504 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Enforce upload and encryption policies for underlying services.
+///
+/// # Policy
+///
+/// PolicyLayer rejects writes before their bytes are sent to the services:
+///
+/// - Max object size: writes that make an object larger than
+///   [`PolicyLayer::with_max_object_size`] will be rejected with
+///   [`ErrorKind::QuotaExceeded`].
+/// - Allowed content types: writes whose content type doesn't match any of
+///   [`PolicyLayer::with_allowed_content_type`] will be rejected with
+///   [`ErrorKind::PermissionDenied`] before the writer is created.
+///
+/// PolicyLayer also checks the server-side encryption status of existing
+/// objects if [`PolicyLayer::with_encryption_required`] or
+/// [`PolicyLayer::with_allowed_encryption_key_id`] is set:
+///
+/// - Reads of objects that are not encrypted as required will be rejected
+///   with [`ErrorKind::PermissionDenied`].
+/// - Objects that are not encrypted as required will be skipped in list.
+///
+/// # Notes
+///
+/// - Content types are matched case-insensitively without parameters, and
+///   `type/*` matches all subtypes of `type`.
+/// - Writes without content type will be rejected if any content type is
+///   allowed. Apply [`MimeGuessLayer`](crate::layers::MimeGuessLayer) after
+///   this layer to fill the content type from the path extension.
+/// - Use [`QuotaLayer`](crate::layers::QuotaLayer) for per-prefix quotas.
+/// - Checking encryption sends an extra stat before every read, and for
+///   every listed file if the service doesn't return the encryption status
+///   in list, see [`Capability::list_has_server_side_encryption`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::PolicyLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         PolicyLayer::new()
+///             .with_max_object_size(16 * 1024 * 1024)
+///             .with_allowed_content_type("image/*")
+///             .with_allowed_content_type("application/pdf"),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PolicyLayer {
+    max_object_size: Option<u64>,
+    allowed_content_types: Vec<String>,
+    encryption_required: bool,
+    allowed_encryption_key_ids: Vec<String>,
+}
+
+impl PolicyLayer {
+    /// Create a new `PolicyLayer` without any policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max size in bytes of a single object.
+    pub fn with_max_object_size(mut self, size: u64) -> Self {
+        self.max_object_size = Some(size);
+        self
+    }
+
+    /// Allow writing objects of given content type.
+    ///
+    /// Call this function multiple times to allow more content types, for
+    /// example `image/png` or `image/*`. All content types are allowed if
+    /// this is never called.
+    pub fn with_allowed_content_type(mut self, content_type: &str) -> Self {
+        self.allowed_content_types
+            .push(content_type.trim().to_ascii_lowercase());
+        self
+    }
+
+    /// Require objects to be encrypted by the services.
+    pub fn with_encryption_required(mut self) -> Self {
+        self.encryption_required = true;
+        self
+    }
+
+    /// Require objects to be encrypted by given key, like a KMS key id.
+    ///
+    /// Call this function multiple times to allow more keys. Objects must be
+    /// encrypted by the services if this is called.
+    pub fn with_allowed_encryption_key_id(mut self, key_id: &str) -> Self {
+        self.encryption_required = true;
+        self.allowed_encryption_key_ids.push(key_id.to_string());
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for PolicyLayer {
+    type LayeredAccess = PolicyAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        PolicyAccessor {
+            inner: Arc::new(inner),
+            policy: Arc::new(self.clone()),
+        }
+    }
+}
+
+impl PolicyLayer {
+    fn check_content_type(&self, op: Operation, path: &str, args: &OpWrite) -> Result<()> {
+        if self.allowed_content_types.is_empty() {
+            return Ok(());
+        }
+
+        let Some(content_type) = args.content_type() else {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "content type is required by the write policy",
+            )
+            .with_operation(op)
+            .with_context("path", path));
+        };
+
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let allowed = self
+            .allowed_content_types
+            .iter()
+            .any(|v| match v.strip_suffix("/*") {
+                Some(ty) => essence
+                    .split_once('/')
+                    .is_some_and(|(prefix, _)| prefix == ty),
+                None => *v == essence,
+            });
+        if allowed {
+            return Ok(());
+        }
+
+        Err(
+            Error::new(ErrorKind::PermissionDenied, "content type is not allowed")
+                .with_operation(op)
+                .with_context("path", path)
+                .with_context("content_type", content_type)
+                .with_context("allowed", self.allowed_content_types.join(",")),
+        )
+    }
+
+    /// Check whether the object is encrypted as required.
+    fn check_encryption(&self, op: Operation, path: &str, meta: &Metadata) -> Result<()> {
+        if !self.encryption_required {
+            return Ok(());
+        }
+
+        let Some(algorithm) = meta.server_side_encryption() else {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "object is not encrypted as required by the policy",
+            )
+            .with_operation(op)
+            .with_context("path", path));
+        };
+        if self.allowed_encryption_key_ids.is_empty() {
+            return Ok(());
+        }
+
+        let key_id = meta.server_side_encryption_key_id().unwrap_or_default();
+        if self.allowed_encryption_key_ids.iter().any(|v| v == key_id) {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "object is not encrypted by the allowed keys",
+        )
+        .with_operation(op)
+        .with_context("path", path)
+        .with_context("algorithm", algorithm)
+        .with_context("key_id", key_id)
+        .with_context("allowed", self.allowed_encryption_key_ids.join(",")))
+    }
+
+    fn check_object_size(&self, op: Operation, path: &str, size: u64) -> Result<()> {
+        match self.max_object_size {
+            Some(limit) if size > limit => Err(Error::new(
+                ErrorKind::QuotaExceeded,
+                "object size exceeds the max object size",
+            )
+            .with_operation(op)
+            .with_context("path", path)
+            .with_context("size", size.to_string())
+            .with_context("limit", limit.to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PolicyAccessor<A: Access> {
+    inner: Arc<A>,
+    policy: Arc<PolicyLayer>,
+}
+
+impl<A: Access> PolicyAccessor<A> {
+    fn stat_args(args: &OpRead) -> OpStat {
+        match args.version() {
+            Some(version) => OpStat::new().with_version(version),
+            None => OpStat::new(),
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for PolicyAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = PolicyWriter<A::Writer>;
+    type BlockingWriter = PolicyWriter<A::BlockingWriter>;
+    type Lister = PolicyLister<A, A::Lister>;
+    type BlockingLister = PolicyLister<A, A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if self.policy.encryption_required {
+            let rp = self.inner.stat(path, Self::stat_args(&args)).await?;
+            self.policy
+                .check_encryption(Operation::Read, path, rp.metadata())?;
+        }
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.policy
+            .check_content_type(Operation::Write, path, &args)?;
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, PolicyWriter::new(w, path, self.policy.clone())))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let (rp, lister) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            PolicyLister::new(self.inner.clone(), self.policy.clone(), lister),
+        ))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        if self.policy.encryption_required {
+            let rp = self.inner.blocking_stat(path, Self::stat_args(&args))?;
+            self.policy
+                .check_encryption(Operation::BlockingRead, path, rp.metadata())?;
+        }
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.policy
+            .check_content_type(Operation::BlockingWrite, path, &args)?;
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, PolicyWriter::new(w, path, self.policy.clone())))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let (rp, lister) = self.inner.blocking_list(path, args)?;
+        Ok((
+            rp,
+            PolicyLister::new(self.inner.clone(), self.policy.clone(), lister),
+        ))
+    }
+}
+
+pub struct PolicyLister<A: Access, L> {
+    acc: Arc<A>,
+    policy: Arc<PolicyLayer>,
+    inner: L,
+}
+
+impl<A: Access, L> PolicyLister<A, L> {
+    fn new(acc: Arc<A>, policy: Arc<PolicyLayer>, inner: L) -> Self {
+        Self { acc, policy, inner }
+    }
+}
+
+impl<A: Access, L: oio::List> oio::List for PolicyLister<A, L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next().await? {
+            if !self.policy.encryption_required || !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            // Listed entries may not carry the encryption status, stat them instead.
+            let allowed = if entry.metadata().server_side_encryption().is_some() {
+                self.policy
+                    .check_encryption(Operation::List, entry.path(), entry.metadata())
+                    .is_ok()
+            } else {
+                match self.acc.stat(entry.path(), OpStat::new()).await {
+                    Ok(rp) => self
+                        .policy
+                        .check_encryption(Operation::List, entry.path(), rp.metadata())
+                        .is_ok(),
+                    // The entry has been removed during listing.
+                    Err(err) if err.kind() == ErrorKind::NotFound => false,
+                    Err(err) => return Err(err),
+                }
+            };
+            if allowed {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<A: Access, L: oio::BlockingList> oio::BlockingList for PolicyLister<A, L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next()? {
+            if !self.policy.encryption_required || !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            let allowed = if entry.metadata().server_side_encryption().is_some() {
+                self.policy
+                    .check_encryption(Operation::BlockingList, entry.path(), entry.metadata())
+                    .is_ok()
+            } else {
+                match self.acc.blocking_stat(entry.path(), OpStat::new()) {
+                    Ok(rp) => self
+                        .policy
+                        .check_encryption(Operation::BlockingList, entry.path(), rp.metadata())
+                        .is_ok(),
+                    Err(err) if err.kind() == ErrorKind::NotFound => false,
+                    Err(err) => return Err(err),
+                }
+            };
+            if allowed {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+pub struct PolicyWriter<W> {
+    inner: W,
+    path: String,
+    policy: Arc<PolicyLayer>,
+    written: u64,
+}
+
+impl<W> PolicyWriter<W> {
+    fn new(inner: W, path: &str, policy: Arc<PolicyLayer>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            policy,
+            written: 0,
+        }
+    }
+
+    /// Check the size before sending the bytes to the inner writer.
+    fn check(&self, op: Operation, size: u64) -> Result<()> {
+        self.policy
+            .check_object_size(op, &self.path, self.written + size)
+    }
+}
+
+impl<W: oio::Write> oio::Write for PolicyWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        self.check(Operation::WriterWrite, size)?;
+        self.inner.write(bs).await?;
+        self.written += size;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for PolicyWriter<W> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        self.check(Operation::BlockingWriterWrite, size)?;
+        self.inner.write(bs)?;
+        self.written += size;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_max_object_size() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(PolicyLayer::new().with_max_object_size(4))
+            .finish();
+
+        op.write("small", "abcd").await.expect("write must succeed");
+
+        let err = op
+            .write("large", "abcde")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+        assert!(!op.exists("large").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_allowed_content_types() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(
+                PolicyLayer::new()
+                    .with_allowed_content_type("image/*")
+                    .with_allowed_content_type("application/pdf"),
+            )
+            .finish();
+
+        op.write_with("a.png", "abc")
+            .content_type("image/png")
+            .await
+            .expect("write must succeed");
+        op.write_with("a.pdf", "abc")
+            .content_type("Application/PDF; charset=binary")
+            .await
+            .expect("write must succeed");
+
+        let err = op
+            .write_with("a.txt", "abc")
+            .content_type("text/plain")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.write("a", "abc").await.expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_encryption_required() {
+        // Memory doesn't encrypt objects.
+        let inner = Operator::new(services::Memory::default()).unwrap().finish();
+        inner.write("dir/a", "abc").await.unwrap();
+
+        let op = inner
+            .clone()
+            .layer(PolicyLayer::new().with_encryption_required());
+
+        let err = op.read("dir/a").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(op.list("dir/").await.unwrap().is_empty());
+        assert!(op.stat("dir/a").await.is_ok());
+    }
+}
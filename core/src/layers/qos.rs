@@ -0,0 +1,528 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::sync::oneshot;
+
+use crate::raw::*;
+use crate::*;
+
+/// Share concurrency between workloads by weighted fair scheduling.
+///
+/// # QoS
+///
+/// QosLayer bounds the total concurrent requests sent to the underlying
+/// services like [`ConcurrentLimitLayer`](super::ConcurrentLimitLayer), but
+/// requests are classified into priority classes. When all permits are in
+/// use, released permits are handed to the waiting classes in proportion to
+/// their weights, so background workloads can't starve latency-sensitive
+/// ones while still making progress.
+///
+/// Classes are registered by [`QosLayer::with_class`], and operators pick
+/// their class by applying the layer returned by [`QosLayer::class`]. All
+/// layers derived from the same `QosLayer` share the same permits.
+/// Operators that apply the `QosLayer` itself use the `default` class with
+/// weight `1`.
+///
+/// # Notes
+///
+/// - Readers, writers and listers hold their permits until they are dropped.
+/// - Blocking operations never wait: they fail with
+///   [`ErrorKind::RateLimited`] if no permit is available.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::QosLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?.finish();
+///
+/// let qos = QosLayer::new(64)
+///     .with_class("serving", 4)
+///     .with_class("batch", 1);
+/// let serving = op.clone().layer(qos.class("serving")?);
+/// let batch = op.layer(qos.class("batch")?);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct QosLayer {
+    scheduler: Arc<Scheduler>,
+    class: usize,
+}
+
+impl QosLayer {
+    /// The name of the class used by default.
+    pub const DEFAULT_CLASS: &'static str = "default";
+
+    /// Create a new `QosLayer` with given total permits.
+    pub fn new(permits: usize) -> Self {
+        let scheduler = Scheduler {
+            permits,
+            state: Mutex::new(SchedulerState {
+                in_use: 0,
+                vtime: 0.0,
+                classes: vec![ClassState::new(Self::DEFAULT_CLASS, 1)],
+            }),
+        };
+        Self {
+            scheduler: Arc::new(scheduler),
+            class: 0,
+        }
+    }
+
+    /// Register a class with given weight, or update the weight if the
+    /// class already exists.
+    ///
+    /// A class with weight `4` gets four times the permits of a class with
+    /// weight `1` while both are waiting. Weight `0` is treated as `1`.
+    pub fn with_class(self, name: &str, weight: u32) -> Self {
+        {
+            let mut state = self.scheduler.state.lock().expect("lock must succeed");
+            match state.classes.iter_mut().find(|c| c.name == name) {
+                Some(class) => class.weight = weight.max(1),
+                None => state.classes.push(ClassState::new(name, weight)),
+            }
+        }
+        self
+    }
+
+    /// Get the layer that schedules operations in given class.
+    ///
+    /// Returns [`ErrorKind::ConfigInvalid`] if the class has not been
+    /// registered.
+    pub fn class(&self, name: &str) -> Result<Self> {
+        let state = self.scheduler.state.lock().expect("lock must succeed");
+        let class = state
+            .classes
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "qos class is not registered")
+                    .with_context("class", name)
+            })?;
+        Ok(Self {
+            scheduler: self.scheduler.clone(),
+            class,
+        })
+    }
+}
+
+impl<A: Access> Layer<A> for QosLayer {
+    type LayeredAccess = QosAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        QosAccessor {
+            inner,
+            scheduler: self.scheduler.clone(),
+            class: self.class,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClassState {
+    name: String,
+    weight: u32,
+    /// The virtual finish time of the last permit granted to this class.
+    finish: f64,
+    waiters: VecDeque<oneshot::Sender<QosPermit>>,
+}
+
+impl ClassState {
+    fn new(name: &str, weight: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            weight: weight.max(1),
+            finish: 0.0,
+            waiters: VecDeque::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SchedulerState {
+    in_use: usize,
+    /// The virtual start time of the last granted permit.
+    vtime: f64,
+    classes: Vec<ClassState>,
+}
+
+impl SchedulerState {
+    /// Charge a permit to the class, following start-time fair queuing.
+    fn charge(&mut self, class: usize) {
+        let c = &mut self.classes[class];
+        let start = c.finish.max(self.vtime);
+        c.finish = start + 1.0 / c.weight as f64;
+        self.vtime = start;
+    }
+
+    /// Pick the waiting class with the smallest virtual start time.
+    fn next_class(&self) -> Option<usize> {
+        self.classes
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.waiters.is_empty())
+            .map(|(idx, c)| (idx, c.finish.max(self.vtime)))
+            .fold(None, |min: Option<(usize, f64)>, (idx, start)| match min {
+                Some((_, v)) if v <= start => min,
+                _ => Some((idx, start)),
+            })
+            .map(|(idx, _)| idx)
+    }
+}
+
+#[derive(Debug)]
+struct Scheduler {
+    permits: usize,
+    state: Mutex<SchedulerState>,
+}
+
+impl Scheduler {
+    async fn acquire(self: &Arc<Self>, class: usize) -> Result<QosPermit> {
+        let rx = {
+            let mut state = self.state.lock().expect("lock must succeed");
+            // Released permits are handed to waiters directly, so there are
+            // no waiters while permits are available.
+            if state.in_use < self.permits {
+                state.in_use += 1;
+                state.charge(class);
+                return Ok(QosPermit(self.clone()));
+            }
+
+            let (tx, rx) = oneshot::channel();
+            state.classes[class].waiters.push_back(tx);
+            rx
+        };
+
+        rx.await.map_err(|_| {
+            Error::new(ErrorKind::Unexpected, "qos scheduler has been dropped").set_temporary()
+        })
+    }
+
+    fn try_acquire(self: &Arc<Self>, class: usize) -> Result<QosPermit> {
+        let mut state = self.state.lock().expect("lock must succeed");
+        if state.in_use >= self.permits {
+            return Err(Error::new(ErrorKind::RateLimited, "no permits available").set_temporary());
+        }
+        state.in_use += 1;
+        state.charge(class);
+        Ok(QosPermit(self.clone()))
+    }
+
+    fn release(self: &Arc<Self>) {
+        let tx = {
+            let mut state = self.state.lock().expect("lock must succeed");
+            loop {
+                let Some(class) = state.next_class() else {
+                    state.in_use -= 1;
+                    return;
+                };
+                let tx = state.classes[class]
+                    .waiters
+                    .pop_front()
+                    .expect("waiters must not be empty");
+                // Skip waiters that have been cancelled.
+                if tx.is_closed() {
+                    continue;
+                }
+                state.charge(class);
+                break tx;
+            }
+        };
+
+        // Hand the permit over, it will be released again if the waiter
+        // has gone in the meantime.
+        let _ = tx.send(QosPermit(self.clone()));
+    }
+}
+
+/// The permit held by a single request.
+#[derive(Debug)]
+struct QosPermit(Arc<Scheduler>);
+
+impl Drop for QosPermit {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+#[derive(Debug)]
+pub struct QosAccessor<A: Access> {
+    inner: A,
+    scheduler: Arc<Scheduler>,
+    class: usize,
+}
+
+impl<A: Access> LayeredAccess for QosAccessor<A> {
+    type Inner = A;
+    type Reader = QosWrapper<A::Reader>;
+    type BlockingReader = QosWrapper<A::BlockingReader>;
+    type Writer = QosWrapper<A::Writer>;
+    type BlockingWriter = QosWrapper<A::BlockingWriter>;
+    type Lister = QosWrapper<A::Lister>;
+    type BlockingLister = QosWrapper<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, QosWrapper::new(r, permit)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, QosWrapper::new(w, permit)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, l)| (rp, QosWrapper::new(l, permit)))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let _permit = self.scheduler.acquire(self.class).await?;
+
+        self.inner.batch(args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, QosWrapper::new(r, permit)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, QosWrapper::new(w, permit)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let permit = self.scheduler.try_acquire(self.class)?;
+
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, l)| (rp, QosWrapper::new(l, permit)))
+    }
+}
+
+pub struct QosWrapper<R> {
+    inner: R,
+
+    // Hold on this permit until this wrapper has been dropped.
+    _permit: QosPermit,
+}
+
+impl<R> QosWrapper<R> {
+    fn new(inner: R, permit: QosPermit) -> Self {
+        Self {
+            inner,
+            _permit: permit,
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for QosWrapper<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        self.inner.read().await
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for QosWrapper<R> {
+    fn read(&mut self) -> Result<Buffer> {
+        self.inner.read()
+    }
+}
+
+impl<R: oio::Write> oio::Write for QosWrapper<R> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for QosWrapper<R> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        self.inner.close()
+    }
+}
+
+impl<R: oio::List> oio::List for QosWrapper<R> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        self.inner.next().await
+    }
+}
+
+impl<R: oio::BlockingList> oio::BlockingList for QosWrapper<R> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_weighted_scheduling() {
+        let qos = QosLayer::new(1)
+            .with_class("serving", 3)
+            .with_class("batch", 1);
+        let serving = qos.class("serving").unwrap();
+        let batch = qos.class("batch").unwrap();
+        assert!(qos.class("unknown").is_err());
+
+        let scheduler = qos.scheduler.clone();
+        let permit = scheduler.acquire(batch.class).await.unwrap();
+        assert!(scheduler.try_acquire(batch.class).is_err());
+
+        let granted = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for layer in [&batch, &batch, &batch, &serving, &serving, &serving] {
+            let scheduler = scheduler.clone();
+            let granted = granted.clone();
+            let class = layer.class;
+            tasks.push(tokio::spawn(async move {
+                let _permit = scheduler.acquire(class).await.unwrap();
+                granted.lock().unwrap().push(class);
+            }));
+        }
+        // Wait for all tasks to be queued.
+        while scheduler
+            .state
+            .lock()
+            .unwrap()
+            .classes
+            .iter()
+            .map(|c| c.waiters.len())
+            .sum::<usize>()
+            < 6
+        {
+            tokio::task::yield_now().await;
+        }
+
+        drop(permit);
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let granted = granted.lock().unwrap().clone();
+        assert_eq!(granted.len(), 6);
+        assert_eq!(
+            granted[..3],
+            [serving.class, serving.class, serving.class],
+            "{granted:?}"
+        );
+    }
+}
@@ -0,0 +1,523 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add write quotas for underlying services.
+///
+/// # Quota
+///
+/// QuotaLayer guards the storage path of multi-tenant services:
+///
+/// - Per-object size: writes that make an object larger than
+///   [`QuotaLayer::with_max_object_size`] will be rejected.
+/// - Per-prefix bytes: the cumulative bytes written under a prefix (for
+///   example, a tenant's dir) will be tracked and writes that exceed
+///   [`QuotaLayer::with_prefix_quota`] will be rejected.
+///
+/// Rejected operations return [`ErrorKind::QuotaExceeded`].
+///
+/// # Notes
+///
+/// - Usage is tracked in memory and shared by all operators built with the
+///   same layer. Bytes that existed before the layer is applied are not
+///   counted.
+/// - Deleting files will not release usage. Aborted or failed writes,
+///   copies and renames will release the bytes they reserved.
+/// - `copy` and `rename` into a quota-guarded prefix will `stat` the source
+///   first to check and reserve its size.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::QuotaLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         QuotaLayer::new()
+///             .with_max_object_size(64 * 1024 * 1024)
+///             .with_prefix_quota("tenant-a/", 10 * 1024 * 1024 * 1024),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QuotaLayer {
+    max_object_size: Option<u64>,
+    prefix_quotas: Vec<(String, u64)>,
+    usage: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl QuotaLayer {
+    /// Create a new `QuotaLayer` without any quota.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max size in bytes of a single object.
+    pub fn with_max_object_size(mut self, size: u64) -> Self {
+        self.max_object_size = Some(size);
+        self
+    }
+
+    /// Set the max cumulative bytes that can be written under given prefix.
+    ///
+    /// The prefix is relative to the operator's root. Call this function
+    /// multiple times to add quotas for different prefixes (tenants), a
+    /// write must fit in all quotas it matches.
+    pub fn with_prefix_quota(mut self, prefix: &str, limit: u64) -> Self {
+        self.prefix_quotas.push((prefix.to_string(), limit));
+        self
+    }
+
+    /// Get the cumulative bytes written under given prefix.
+    ///
+    /// Returns `None` if there is no quota for this prefix.
+    pub fn usage(&self, prefix: &str) -> Option<u64> {
+        if !self.prefix_quotas.iter().any(|(p, _)| p == prefix) {
+            return None;
+        }
+        let usage = self.usage.lock().expect("lock must succeed");
+        Some(usage.get(prefix).copied().unwrap_or_default())
+    }
+}
+
+impl<A: Access> Layer<A> for QuotaLayer {
+    type LayeredAccess = QuotaAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        QuotaAccessor {
+            inner,
+            quota: Arc::new(Quota {
+                max_object_size: self.max_object_size,
+                prefix_quotas: self.prefix_quotas.clone(),
+                usage: self.usage.clone(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Quota {
+    max_object_size: Option<u64>,
+    prefix_quotas: Vec<(String, u64)>,
+    usage: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl Quota {
+    /// Check if the path is guarded by any quota.
+    fn is_guarded(&self, path: &str) -> bool {
+        self.max_object_size.is_some()
+            || self
+                .prefix_quotas
+                .iter()
+                .any(|(prefix, _)| path.starts_with(prefix.as_str()))
+    }
+
+    fn check_object_size(&self, op: Operation, path: &str, size: u64) -> Result<()> {
+        match self.max_object_size {
+            Some(limit) if size > limit => Err(Error::new(
+                ErrorKind::QuotaExceeded,
+                "object size exceeds the max object size",
+            )
+            .with_operation(op)
+            .with_context("path", path)
+            .with_context("size", size.to_string())
+            .with_context("limit", limit.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reserve `size` bytes for path in all matched prefixes.
+    ///
+    /// Nothing will be reserved if any of the prefixes exceeds.
+    fn reserve(&self, op: Operation, path: &str, size: u64) -> Result<()> {
+        let mut usage = self.usage.lock().expect("lock must succeed");
+
+        let matched = self
+            .prefix_quotas
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()));
+        for (prefix, limit) in matched.clone() {
+            let used = usage.get(prefix).copied().unwrap_or_default();
+            if used + size > *limit {
+                return Err(Error::new(
+                    ErrorKind::QuotaExceeded,
+                    "bytes written exceed the prefix quota",
+                )
+                .with_operation(op)
+                .with_context("path", path)
+                .with_context("prefix", prefix)
+                .with_context("used", used.to_string())
+                .with_context("size", size.to_string())
+                .with_context("limit", limit.to_string()));
+            }
+        }
+        for (prefix, _) in matched {
+            *usage.entry(prefix.clone()).or_default() += size;
+        }
+        Ok(())
+    }
+
+    /// Release `size` bytes reserved by path.
+    fn release(&self, path: &str, size: u64) {
+        let mut usage = self.usage.lock().expect("lock must succeed");
+
+        for (prefix, _) in self
+            .prefix_quotas
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+        {
+            if let Some(v) = usage.get_mut(prefix) {
+                *v = v.saturating_sub(size);
+            }
+        }
+    }
+}
+
+/// Bytes reserved for an in-flight write, copy or rename.
+///
+/// The bytes will be released on drop unless [`Reservation::commit`] is
+/// called, so that failed or cancelled transfers don't eat the quota.
+struct Reservation {
+    quota: Arc<Quota>,
+    path: String,
+    size: u64,
+}
+
+impl Reservation {
+    fn empty(quota: Arc<Quota>, path: &str) -> Self {
+        Self {
+            quota,
+            path: path.to_string(),
+            size: 0,
+        }
+    }
+
+    fn reserve(quota: Arc<Quota>, op: Operation, path: &str, size: u64) -> Result<Self> {
+        quota.check_object_size(op, path, size)?;
+        quota.reserve(op, path, size)?;
+        Ok(Self {
+            quota,
+            path: path.to_string(),
+            size,
+        })
+    }
+
+    /// Reserve `size` more bytes.
+    fn grow(&mut self, op: Operation, size: u64) -> Result<()> {
+        self.quota
+            .check_object_size(op, &self.path, self.size + size)?;
+        self.quota.reserve(op, &self.path, size)?;
+        self.size += size;
+        Ok(())
+    }
+
+    /// Release `size` bytes that are not going to be written.
+    fn shrink(&mut self, size: u64) {
+        self.quota.release(&self.path, size);
+        self.size -= size;
+    }
+
+    /// Keep the reserved bytes since the transfer succeeded.
+    fn commit(&mut self) {
+        self.size = 0;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if self.size > 0 {
+            self.quota.release(&self.path, self.size);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaAccessor<A: Access> {
+    inner: A,
+    quota: Arc<Quota>,
+}
+
+impl<A: Access> QuotaAccessor<A> {
+    /// Check and reserve the size of `from` for `to`.
+    ///
+    /// The returned reservation must be committed after the transfer
+    /// succeeds, otherwise it will be released on drop.
+    async fn check_transfer(&self, op: Operation, from: &str, to: &str) -> Result<Reservation> {
+        if !self.quota.is_guarded(to) {
+            return Ok(Reservation::empty(self.quota.clone(), to));
+        }
+
+        let size = self
+            .inner
+            .stat(from, OpStat::new())
+            .await?
+            .into_metadata()
+            .content_length();
+        Reservation::reserve(self.quota.clone(), op, to, size)
+    }
+
+    fn blocking_check_transfer(&self, op: Operation, from: &str, to: &str) -> Result<Reservation> {
+        if !self.quota.is_guarded(to) {
+            return Ok(Reservation::empty(self.quota.clone(), to));
+        }
+
+        let size = self
+            .inner
+            .blocking_stat(from, OpStat::new())?
+            .into_metadata()
+            .content_length();
+        Reservation::reserve(self.quota.clone(), op, to, size)
+    }
+}
+
+impl<A: Access> LayeredAccess for QuotaAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = QuotaWriter<A::Writer>;
+    type BlockingWriter = QuotaWriter<A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await.map(|(rp, w)| {
+            (
+                rp,
+                QuotaWriter::new(w, Reservation::empty(self.quota.clone(), path)),
+            )
+        })
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let mut reservation = self.check_transfer(Operation::Copy, from, to).await?;
+        let rp = self.inner.copy(from, to, args).await?;
+        reservation.commit();
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let mut reservation = self.check_transfer(Operation::Rename, from, to).await?;
+        let rp = self.inner.rename(from, to, args).await?;
+        reservation.commit();
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args).map(|(rp, w)| {
+            (
+                rp,
+                QuotaWriter::new(w, Reservation::empty(self.quota.clone(), path)),
+            )
+        })
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let mut reservation = self.blocking_check_transfer(Operation::BlockingCopy, from, to)?;
+        let rp = self.inner.blocking_copy(from, to, args)?;
+        reservation.commit();
+        Ok(rp)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let mut reservation = self.blocking_check_transfer(Operation::BlockingRename, from, to)?;
+        let rp = self.inner.blocking_rename(from, to, args)?;
+        reservation.commit();
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// QuotaWriter reserves quota for every write.
+///
+/// The reserved bytes will be released if the writer is dropped without
+/// a successful close, including failed writes and aborts.
+pub struct QuotaWriter<W> {
+    inner: W,
+    reservation: Reservation,
+}
+
+impl<W> QuotaWriter<W> {
+    fn new(inner: W, reservation: Reservation) -> Self {
+        Self { inner, reservation }
+    }
+}
+
+impl<W: oio::Write> oio::Write for QuotaWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        self.reservation.grow(Operation::WriterWrite, size)?;
+        if let Err(err) = self.inner.write(bs).await {
+            self.reservation.shrink(size);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let meta = self.inner.close().await?;
+        self.reservation.commit();
+        Ok(meta)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for QuotaWriter<W> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        self.reservation
+            .grow(Operation::BlockingWriterWrite, size)?;
+        if let Err(err) = self.inner.write(bs) {
+            self.reservation.shrink(size);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        let meta = self.inner.close()?;
+        self.reservation.commit();
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_max_object_size() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(QuotaLayer::new().with_max_object_size(4))
+            .finish();
+
+        op.write("small", "abcd").await.expect("write must succeed");
+
+        let err = op
+            .write("large", "abcde")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_quota() {
+        let layer = QuotaLayer::new().with_prefix_quota("tenant-a/", 8);
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(layer.clone())
+            .finish();
+
+        op.write("tenant-a/x", "abcd").await.expect("must succeed");
+        op.write("tenant-a/y", "abcd").await.expect("must succeed");
+        assert_eq!(layer.usage("tenant-a/"), Some(8));
+
+        let err = op
+            .write("tenant-a/z", "a")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+        assert_eq!(layer.usage("tenant-a/"), Some(8));
+
+        // Other tenants are not affected.
+        op.write("tenant-b/z", "abcdefghi")
+            .await
+            .expect("must succeed");
+        assert_eq!(layer.usage("tenant-b/"), None);
+
+        // Copy into the guarded prefix is counted too.
+        let err = op
+            .copy("tenant-b/z", "tenant-a/z")
+            .await
+            .expect_err("copy must fail");
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_failed_transfer_releases_quota() {
+        let layer = QuotaLayer::new().with_prefix_quota("tenant-a/", 8);
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(layer.clone())
+            .finish();
+
+        op.write("tenant-b/x", "abcd").await.expect("must succeed");
+
+        // Memory doesn't support copy, the reserved bytes must be released.
+        let err = op
+            .copy("tenant-b/x", "tenant-a/x")
+            .await
+            .expect_err("copy must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(layer.usage("tenant-a/"), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_unclosed_writer_releases_quota() {
+        let layer = QuotaLayer::new().with_prefix_quota("tenant-a/", 8);
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(layer.clone())
+            .finish();
+
+        let mut w = op.writer("tenant-a/x").await.expect("must succeed");
+        w.write("abcd").await.expect("must succeed");
+        assert_eq!(layer.usage("tenant-a/"), Some(4));
+        drop(w);
+        assert_eq!(layer.usage("tenant-a/"), Some(0));
+
+        let mut w = op.writer("tenant-a/x").await.expect("must succeed");
+        w.write("abcd").await.expect("must succeed");
+        w.close().await.expect("must succeed");
+        drop(w);
+        assert_eq!(layer.usage("tenant-a/"), Some(4));
+    }
+}
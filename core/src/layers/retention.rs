@@ -0,0 +1,432 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add write-once-read-many (WORM) retention for underlying services.
+///
+/// # Retention
+///
+/// RetentionLayer rejects `delete` and overwriting `write` on objects that
+/// are younger than the configured retention period, which gives
+/// application-level write-once semantics on services without native object
+/// lock. Writing to new paths is always allowed.
+///
+/// The age of an object is computed from:
+///
+/// - the RFC 3339 timestamp stored in user metadata under the key set by
+///   [`RetentionLayer::with_metadata_key`], if any.
+/// - otherwise, the last modified time of the object.
+///
+/// Rejected operations return [`ErrorKind::PermissionDenied`].
+///
+/// # Notes
+///
+/// - Objects whose age can't be determined are treated as retained, since we
+///   can't prove that the retention period has passed.
+/// - `rename` checks both the source and the target, `copy` checks the target.
+/// - Every guarded operation will `stat` the object first, and the check is
+///   not atomic with the operation itself.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// #
+/// # use opendal::layers::RetentionLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(RetentionLayer::new(Duration::from_secs(30 * 24 * 60 * 60)))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetentionLayer {
+    period: Duration,
+    metadata_key: Option<String>,
+    clock: Clock,
+}
+
+impl RetentionLayer {
+    /// Create a new `RetentionLayer` with given retention period.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            metadata_key: None,
+            clock: Clock::system(),
+        }
+    }
+
+    /// Read the creation time of objects from given user metadata key.
+    ///
+    /// The value must be a RFC 3339 timestamp like `2024-01-01T00:00:00Z`.
+    pub fn with_metadata_key(mut self, key: &str) -> Self {
+        self.metadata_key = Some(key.to_string());
+        self
+    }
+
+    /// Set the clock to check the retention period.
+    ///
+    /// Default to [`Clock::system`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for RetentionLayer {
+    type LayeredAccess = RetentionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        RetentionAccessor {
+            inner,
+            retention: Arc::new(Retention {
+                period: self.period,
+                metadata_key: self.metadata_key.clone(),
+                clock: self.clock.clone(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Retention {
+    period: Duration,
+    metadata_key: Option<String>,
+    clock: Clock,
+}
+
+impl Retention {
+    /// Get the time since which the object is retained.
+    fn retained_since(&self, meta: &Metadata) -> Option<DateTime<Utc>> {
+        if let Some(key) = &self.metadata_key {
+            return meta
+                .user_metadata()
+                .and_then(|m| m.get(key))
+                .and_then(|v| parse_datetime_from_rfc3339(v).ok());
+        }
+        meta.last_modified()
+    }
+
+    /// Check the stat result of path against the retention period.
+    ///
+    /// Not existing objects are never retained.
+    fn check(&self, op: Operation, path: &str, stat: Result<RpStat>) -> Result<()> {
+        let meta = match stat {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if meta.is_dir() {
+            return Ok(());
+        }
+
+        let since = self.retained_since(&meta);
+        let until = since.and_then(|v| {
+            chrono::Duration::from_std(self.period)
+                .ok()
+                .and_then(|d| v.checked_add_signed(d))
+        });
+        match until {
+            Some(until) if until <= self.clock.now() => Ok(()),
+            _ => {
+                let mut err = Error::new(
+                    ErrorKind::PermissionDenied,
+                    "object is still under retention",
+                )
+                .with_operation(op)
+                .with_context("path", path);
+                if let Some(until) = until {
+                    err = err.with_context("retained_until", until.to_rfc3339());
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RetentionAccessor<A: Access> {
+    inner: A,
+    retention: Arc<Retention>,
+}
+
+impl<A: Access> RetentionAccessor<A> {
+    async fn check(&self, op: Operation, path: &str) -> Result<()> {
+        let stat = self.inner.stat(path, OpStat::new()).await;
+        self.retention.check(op, path, stat)
+    }
+
+    fn blocking_check(&self, op: Operation, path: &str) -> Result<()> {
+        let stat = self.inner.blocking_stat(path, OpStat::new());
+        self.retention.check(op, path, stat)
+    }
+}
+
+impl<A: Access> LayeredAccess for RetentionAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check(Operation::Write, path).await?;
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check(Operation::Copy, to).await?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.check(Operation::Rename, from).await?;
+        self.check(Operation::Rename, to).await?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check(Operation::Delete, path).await?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        // Keep the results in the same order as ops, rejected ops are filled
+        // in place and the others are filled by the inner batch.
+        let mut results = Vec::new();
+        let mut ops = Vec::new();
+        for (path, op) in args.into_operation() {
+            let checked = match &op {
+                BatchOperation::Delete(_) => self.check(Operation::Delete, &path).await,
+                BatchOperation::Copy(to, _) => self.check(Operation::Copy, to).await,
+            };
+            match checked {
+                Ok(()) => {
+                    results.push(None);
+                    ops.push((path, op));
+                }
+                Err(err) => results.push(Some((path, Err(err)))),
+            }
+        }
+
+        if !ops.is_empty() {
+            let expected = ops.len();
+            let accepted = self.inner.batch(OpBatch::new(ops)).await?.into_results();
+            if accepted.len() != expected {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch returns mismatched number of results",
+                )
+                .with_operation(Operation::Batch)
+                .with_context("expected", expected.to_string())
+                .with_context("actual", accepted.len().to_string()));
+            }
+
+            let mut accepted = accepted.into_iter();
+            for slot in results.iter_mut().filter(|v| v.is_none()) {
+                *slot = accepted.next();
+            }
+        }
+
+        Ok(RpBatch::new(results.into_iter().flatten().collect()))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.blocking_check(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_check(Operation::BlockingCopy, to)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.blocking_check(Operation::BlockingRename, from)?;
+        self.blocking_check(Operation::BlockingRename, to)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.blocking_check(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_retention() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(RetentionLayer::new(Duration::from_secs(3600)))
+            .finish();
+
+        op.write("retained", "abcd")
+            .await
+            .expect("write must succeed");
+
+        let err = op.delete("retained").await.expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op
+            .write("retained", "efgh")
+            .await
+            .expect_err("overwrite must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // Not existing objects are not retained.
+        op.delete("not_exist").await.expect("delete must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_retention_with_metadata_key() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(RetentionLayer::new(Duration::from_secs(3600)).with_metadata_key("created"))
+            .finish();
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        meta.with_user_metadata(
+            [("created".to_string(), "2000-01-01T00:00:00Z".to_string())].into(),
+        );
+        let retention = Retention {
+            period: Duration::from_secs(3600),
+            metadata_key: Some("created".to_string()),
+            clock: Clock::system(),
+        };
+        retention
+            .check(Operation::Delete, "expired", Ok(RpStat::new(meta)))
+            .expect("expired object must not be retained");
+
+        // Objects without the metadata key are retained.
+        op.write("unknown", "abcd")
+            .await
+            .expect("write must succeed");
+        let err = op.delete("unknown").await.expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[derive(Debug)]
+    struct MockService;
+
+    impl Access for MockService {
+        type Reader = ();
+        type Writer = ();
+        type Lister = ();
+        type BlockingReader = ();
+        type BlockingWriter = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                stat: true,
+                delete: true,
+                batch: true,
+                batch_delete: true,
+                ..Default::default()
+            });
+            am.into()
+        }
+
+        async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+            if path.starts_with("retained") {
+                Ok(RpStat::new(
+                    Metadata::new(EntryMode::FILE).with_last_modified(Utc::now()),
+                ))
+            } else {
+                Err(Error::new(ErrorKind::NotFound, "not found"))
+            }
+        }
+
+        async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+            Ok(RpBatch::new(
+                args.into_operation()
+                    .into_iter()
+                    .map(|(path, _)| (path, Ok(BatchedReply::Delete(RpDelete::default()))))
+                    .collect(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retention_batch_keeps_order() {
+        let acc = RetentionLayer::new(Duration::from_secs(3600)).layer(MockService);
+
+        let rp = Access::batch(
+            &acc,
+            OpBatch::new(vec![
+                ("a".to_string(), BatchOperation::Delete(OpDelete::new())),
+                (
+                    "retained".to_string(),
+                    BatchOperation::Delete(OpDelete::new()),
+                ),
+                ("b".to_string(), BatchOperation::Delete(OpDelete::new())),
+            ]),
+        )
+        .await
+        .expect("batch must succeed");
+
+        let results = rp.into_results();
+        let paths: Vec<_> = results.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["a", "retained", "b"]);
+        assert!(results[0].1.is_ok());
+        assert_eq!(
+            results[1].1.as_ref().err().map(|err| err.kind()),
+            Some(ErrorKind::PermissionDenied)
+        );
+        assert!(results[2].1.is_ok());
+    }
+}
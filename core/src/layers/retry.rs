@@ -18,7 +18,9 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
+use std::time::Instant;
 
 use backon::BlockingRetryable;
 use backon::ExponentialBuilder;
@@ -119,6 +121,8 @@ use crate::*;
 pub struct RetryLayer<I = DefaultRetryInterceptor> {
     builder: ExponentialBuilder,
     notify: Arc<I>,
+    budget: Option<(usize, f32)>,
+    circuit_breaker: Option<(usize, Duration)>,
 }
 
 impl<I> Clone for RetryLayer<I> {
@@ -126,6 +130,8 @@ impl<I> Clone for RetryLayer<I> {
         Self {
             builder: self.builder,
             notify: self.notify.clone(),
+            budget: self.budget,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 }
@@ -135,6 +141,8 @@ impl Default for RetryLayer {
         Self {
             builder: ExponentialBuilder::default(),
             notify: Arc::new(DefaultRetryInterceptor),
+            budget: None,
+            circuit_breaker: None,
         }
     }
 }
@@ -188,6 +196,8 @@ impl RetryLayer {
         RetryLayer {
             builder: self.builder,
             notify: Arc::new(notify),
+            budget: self.budget,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 
@@ -231,6 +241,40 @@ impl RetryLayer {
         self.builder = self.builder.with_max_times(max_times);
         self
     }
+
+    /// Set a token bucket retry budget to cap the fraction of retried requests.
+    ///
+    /// The bucket starts with `max_tokens` tokens. Every request deposits
+    /// `ratio` tokens into the bucket (up to `max_tokens`), and every retry
+    /// withdraws one token. Errors will not be retried if the bucket is
+    /// empty, so retries are bounded to about `ratio` of all requests in
+    /// the long run.
+    ///
+    /// For example, `with_retry_budget(10, 0.1)` allows bursts of 10 retries
+    /// and about 1 retry per 10 requests after that.
+    pub fn with_retry_budget(mut self, max_tokens: usize, ratio: f32) -> Self {
+        self.budget = Some((max_tokens, ratio));
+        self
+    }
+
+    /// Set a circuit breaker that fails fast after consecutive failures.
+    ///
+    /// The circuit breaker opens after `threshold` consecutive requests
+    /// failed with temporary errors (after retries). While opened, all
+    /// requests will fail immediately without reaching the underlying
+    /// services until `cool_down` has passed and return
+    /// [`ErrorKind::CircuitOpen`]. After that, exactly one probe request is
+    /// allowed: a success closes the circuit breaker, while a failure opens
+    /// it for another `cool_down`. Other requests are still rejected while
+    /// the probe is in flight.
+    ///
+    /// Only operations on the accessor (like `stat`, `read`, `write` and
+    /// `list`) are guarded by the circuit breaker. Calls on opened readers,
+    /// writers and listers are not.
+    pub fn with_circuit_breaker(mut self, threshold: usize, cool_down: Duration) -> Self {
+        self.circuit_breaker = Some((threshold, cool_down));
+        self
+    }
 }
 
 impl<A: Access, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
@@ -241,6 +285,20 @@ impl<A: Access, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
             inner: Arc::new(inner),
             builder: self.builder,
             notify: self.notify.clone(),
+            state: Arc::new(RetryState {
+                budget: self.budget.map(|(max_tokens, ratio)| RetryBudget {
+                    max_tokens: max_tokens as f32,
+                    ratio,
+                    tokens: Mutex::new(max_tokens as f32),
+                }),
+                circuit_breaker: self.circuit_breaker.map(|(threshold, cool_down)| {
+                    CircuitBreaker {
+                        threshold,
+                        cool_down,
+                        state: Mutex::new(CircuitBreakerState::default()),
+                    }
+                }),
+            }),
         }
     }
 }
@@ -277,10 +335,115 @@ impl RetryInterceptor for DefaultRetryInterceptor {
     }
 }
 
+/// RetryState is shared by the accessor and all readers, writers and listers
+/// created from it.
+struct RetryState {
+    budget: Option<RetryBudget>,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+struct RetryBudget {
+    max_tokens: f32,
+    ratio: f32,
+    tokens: Mutex<f32>,
+}
+
+struct CircuitBreaker {
+    threshold: usize,
+    cool_down: Duration,
+    state: Mutex<CircuitBreakerState>,
+}
+
+#[derive(Default)]
+struct CircuitBreakerState {
+    /// Consecutive failures.
+    failures: usize,
+    /// The time until which the circuit breaker is opened.
+    open_until: Option<Instant>,
+    /// Whether a probe request is in flight after cool down.
+    probing: bool,
+}
+
+impl RetryState {
+    /// Check whether the request can be sent, and deposit into retry budget.
+    fn check(&self, op: Operation) -> Result<()> {
+        if let Some(cb) = &self.circuit_breaker {
+            let mut state = cb.state.lock().expect("lock must succeed");
+            if let Some(open_until) = state.open_until {
+                let now = Instant::now();
+                if let Some(remaining) = open_until.checked_duration_since(now) {
+                    return Err(Error::new(
+                        ErrorKind::CircuitOpen,
+                        "circuit breaker is open, request is rejected",
+                    )
+                    .with_operation(op)
+                    .with_context("failures", state.failures.to_string())
+                    .with_context("remaining", format!("{remaining:?}")));
+                }
+
+                // Cool down has passed, let this request through as the only
+                // probe. Others will be rejected until the probe finishes or
+                // another cool down passes in case the probe is dropped.
+                state.open_until = Some(now + cb.cool_down);
+                state.probing = true;
+            }
+        }
+
+        if let Some(budget) = &self.budget {
+            let mut tokens = budget.tokens.lock().expect("lock must succeed");
+            *tokens = (*tokens + budget.ratio).min(budget.max_tokens);
+        }
+
+        Ok(())
+    }
+
+    /// Check whether this error should be retried, and withdraw from retry budget.
+    fn should_retry(&self, err: &Error) -> bool {
+        if !err.is_temporary() {
+            return false;
+        }
+
+        match &self.budget {
+            None => true,
+            Some(budget) => {
+                let mut tokens = budget.tokens.lock().expect("lock must succeed");
+                if *tokens < 1.0 {
+                    return false;
+                }
+                *tokens -= 1.0;
+                true
+            }
+        }
+    }
+
+    /// Record the final result of the request into circuit breaker.
+    fn finish<T>(&self, res: Result<T>) -> Result<T> {
+        if let Some(cb) = &self.circuit_breaker {
+            let mut state = cb.state.lock().expect("lock must succeed");
+            match &res {
+                Err(err) if err.is_temporary() => {
+                    state.failures += 1;
+                    if state.probing || state.failures >= cb.threshold {
+                        state.open_until = Some(Instant::now() + cb.cool_down);
+                        state.probing = false;
+                    }
+                }
+                // Permanent errors are returned by the service itself, so
+                // they close the circuit breaker only after a probe.
+                Err(_) if !state.probing => {}
+                _ => *state = CircuitBreakerState::default(),
+            }
+        }
+
+        res.map_err(|e| e.set_persistent())
+    }
+}
+
 pub struct RetryAccessor<A: Access, I: RetryInterceptor> {
     inner: Arc<A>,
     builder: ExponentialBuilder,
     notify: Arc<I>,
+    state: Arc<RetryState>,
 }
 
 impl<A: Access, I: RetryInterceptor> Debug for RetryAccessor<A, I> {
@@ -305,89 +468,118 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        { || self.inner.create_dir(path, args.clone()) }
+        self.state.check(Operation::CreateDir)?;
+
+        let res = { || self.inner.create_dir(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur: Duration| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())
+            .await;
+        self.state.finish(res)
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let (rp, reader) = { || self.inner.read(path, args.clone()) }
+        self.state.check(Operation::Read)?;
+
+        let res = { || self.inner.read(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())?;
+            .await;
+        let (rp, reader) = self.state.finish(res)?;
 
         let retry_reader = RetryReader::new(self.inner.clone(), path.to_string(), args, reader);
-        let retry_wrapper = RetryWrapper::new(retry_reader, self.notify.clone(), self.builder);
+        let retry_wrapper = RetryWrapper::new(
+            retry_reader,
+            self.notify.clone(),
+            self.builder,
+            self.state.clone(),
+        );
 
         Ok((rp, retry_wrapper))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        { || self.inner.write(path, args.clone()) }
+        self.state.check(Operation::Write)?;
+
+        let res = { || self.inner.write(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, self.notify.clone(), self.builder)))
-            .map_err(|e| e.set_persistent())
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    RetryWrapper::new(r, self.notify.clone(), self.builder, self.state.clone()),
+                )
+            });
+        self.state.finish(res)
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.stat(path, args.clone()) }
+        self.state.check(Operation::Stat)?;
+
+        let res = { || self.inner.stat(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())
+            .await;
+        self.state.finish(res)
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.delete(path, args.clone()) }
+        self.state.check(Operation::Delete)?;
+
+        let res = { || self.inner.delete(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())
+            .await;
+        self.state.finish(res)
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        { || self.inner.copy(from, to, args.clone()) }
+        self.state.check(Operation::Copy)?;
+
+        let res = { || self.inner.copy(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())
+            .await;
+        self.state.finish(res)
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        { || self.inner.rename(from, to, args.clone()) }
+        self.state.check(Operation::Rename)?;
+
+        let res = { || self.inner.rename(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .await
-            .map_err(|e| e.set_persistent())
+            .await;
+        self.state.finish(res)
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        { || self.inner.list(path, args.clone()) }
+        self.state.check(Operation::List)?;
+
+        let res = { || self.inner.list(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
             .map(|(l, p)| {
-                let lister = RetryWrapper::new(p, self.notify.clone(), self.builder);
+                let lister =
+                    RetryWrapper::new(p, self.notify.clone(), self.builder, self.state.clone());
                 (l, lister)
-            })
-            .map_err(|e| e.set_persistent())
+            });
+        self.state.finish(res)
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        {
+        self.state.check(Operation::Batch)?;
+
+        let res = {
             || async {
                 let rp = self.inner.batch(args.clone()).await?;
                 let mut nrp = Vec::with_capacity(rp.results().len());
@@ -399,92 +591,118 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
             }
         }
         .retry(self.builder)
-        .when(|e: &Error| e.is_temporary())
+        .when(|e: &Error| self.state.should_retry(e))
         .notify(|err, dur| self.notify.intercept(err, dur))
-        .await
-        .map_err(|e| e.set_persistent())
+        .await;
+        self.state.finish(res)
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        { || self.inner.blocking_create_dir(path, args.clone()) }
+        self.state.check(Operation::BlockingCreateDir)?;
+
+        let res = { || self.inner.blocking_create_dir(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())
+            .call();
+        self.state.finish(res)
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let (rp, reader) = { || self.inner.blocking_read(path, args.clone()) }
+        self.state.check(Operation::BlockingRead)?;
+
+        let res = { || self.inner.blocking_read(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())?;
+            .call();
+        let (rp, reader) = self.state.finish(res)?;
 
         let retry_reader = RetryReader::new(self.inner.clone(), path.to_string(), args, reader);
-        let retry_wrapper = RetryWrapper::new(retry_reader, self.notify.clone(), self.builder);
+        let retry_wrapper = RetryWrapper::new(
+            retry_reader,
+            self.notify.clone(),
+            self.builder,
+            self.state.clone(),
+        );
 
         Ok((rp, retry_wrapper))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        { || self.inner.blocking_write(path, args.clone()) }
+        self.state.check(Operation::BlockingWrite)?;
+
+        let res = { || self.inner.blocking_write(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, self.notify.clone(), self.builder)))
-            .map_err(|e| e.set_persistent())
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    RetryWrapper::new(r, self.notify.clone(), self.builder, self.state.clone()),
+                )
+            });
+        self.state.finish(res)
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.blocking_stat(path, args.clone()) }
+        self.state.check(Operation::BlockingStat)?;
+
+        let res = { || self.inner.blocking_stat(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())
+            .call();
+        self.state.finish(res)
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.blocking_delete(path, args.clone()) }
+        self.state.check(Operation::BlockingDelete)?;
+
+        let res = { || self.inner.blocking_delete(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())
+            .call();
+        self.state.finish(res)
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        { || self.inner.blocking_copy(from, to, args.clone()) }
+        self.state.check(Operation::BlockingCopy)?;
+
+        let res = { || self.inner.blocking_copy(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())
+            .call();
+        self.state.finish(res)
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        { || self.inner.blocking_rename(from, to, args.clone()) }
+        self.state.check(Operation::BlockingRename)?;
+
+        let res = { || self.inner.blocking_rename(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
-            .call()
-            .map_err(|e| e.set_persistent())
+            .call();
+        self.state.finish(res)
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        { || self.inner.blocking_list(path, args.clone()) }
+        self.state.check(Operation::BlockingList)?;
+
+        let res = { || self.inner.blocking_list(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map(|(rp, p)| {
-                let p = RetryWrapper::new(p, self.notify.clone(), self.builder);
+                let p = RetryWrapper::new(p, self.notify.clone(), self.builder, self.state.clone());
                 (rp, p)
-            })
-            .map_err(|e| e.set_persistent())
+            });
+        self.state.finish(res)
     }
 }
 
@@ -553,14 +771,16 @@ pub struct RetryWrapper<R, I> {
     notify: Arc<I>,
 
     builder: ExponentialBuilder,
+    state: Arc<RetryState>,
 }
 
 impl<R, I> RetryWrapper<R, I> {
-    fn new(inner: R, notify: Arc<I>, backoff: ExponentialBuilder) -> Self {
+    fn new(inner: R, notify: Arc<I>, backoff: ExponentialBuilder, state: Arc<RetryState>) -> Self {
         Self {
             inner: Some(inner),
             notify,
             builder: backoff,
+            state,
         }
     }
 
@@ -588,7 +808,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -612,7 +832,7 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .call();
@@ -636,7 +856,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context((inner, bs))
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -658,7 +878,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -667,7 +887,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         res.map_err(|err| err.set_persistent())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
@@ -680,7 +900,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -694,7 +914,7 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
     fn write(&mut self, bs: Buffer) -> Result<()> {
         { || self.inner.as_mut().unwrap().write(bs.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -702,10 +922,10 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
             .map_err(|e| e.set_persistent())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         { || self.inner.as_mut().unwrap().close() }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -728,7 +948,7 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .when(|e| self.state.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -742,7 +962,7 @@ impl<P: oio::BlockingList, I: RetryInterceptor> oio::BlockingList for RetryWrapp
     fn next(&mut self) -> Result<Option<oio::Entry>> {
         { || self.inner.as_mut().unwrap().next() }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.state.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -801,6 +1021,7 @@ mod tests {
                 stat: true,
                 list: true,
                 list_with_recursive: true,
+                delete: true,
                 batch: true,
                 ..Default::default()
             });
@@ -829,6 +1050,11 @@ mod tests {
             Ok((RpWrite::new(), MockWriter {}))
         }
 
+        async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+            *self.attempt.lock().unwrap() += 1;
+            Err(Error::new(ErrorKind::Unexpected, "always delete failed").set_temporary())
+        }
+
         async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
             let lister = MockLister::default();
             Ok((RpList::default(), lister))
@@ -934,7 +1160,7 @@ mod tests {
             Ok(())
         }
 
-        async fn close(&mut self) -> Result<()> {
+        async fn close(&mut self) -> Result<Metadata> {
             Err(Error::new(ErrorKind::Unexpected, "always close failed").set_temporary())
         }
 
@@ -1092,4 +1318,83 @@ mod tests {
         op.remove(paths).await.expect("batch must succeed");
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+
+    #[tokio::test]
+    async fn test_retry_budget() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(1))
+                    .with_max_times(3)
+                    .with_retry_budget(1, 0.0),
+            )
+            .finish();
+
+        // The only token in budget is used by the first request.
+        op.delete("a").await.expect_err("delete must fail");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+        op.delete("a").await.expect_err("delete must fail");
+        assert_eq!(*builder.attempt.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_circuit_breaker() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_max_times(0)
+                    .with_circuit_breaker(2, Duration::from_secs(60)),
+            )
+            .finish();
+
+        op.delete("a").await.expect_err("delete must fail");
+        op.delete("a").await.expect_err("delete must fail");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+
+        // The circuit breaker is open, requests fail without reaching service.
+        let err = op.delete("a").await.expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::CircuitOpen);
+        assert!(!err.is_temporary());
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_retry_circuit_breaker_half_open() {
+        let state = RetryState {
+            budget: None,
+            circuit_breaker: Some(CircuitBreaker {
+                threshold: 1,
+                cool_down: Duration::from_millis(10),
+                state: Mutex::new(CircuitBreakerState::default()),
+            }),
+        };
+        let temporary = || Err::<(), _>(Error::new(ErrorKind::Unexpected, "").set_temporary());
+
+        state.check(Operation::Stat).unwrap();
+        let _ = state.finish(temporary());
+        let err = state.check(Operation::Stat).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::CircuitOpen);
+
+        // Only one probe is allowed after cool down.
+        std::thread::sleep(Duration::from_millis(20));
+        state.check(Operation::Stat).expect("probe must be allowed");
+        state
+            .check(Operation::Stat)
+            .expect_err("others must be rejected");
+
+        // A failed probe opens the circuit breaker again.
+        let _ = state.finish(temporary());
+        state.check(Operation::Stat).unwrap_err();
+
+        // A successful probe closes it.
+        std::thread::sleep(Duration::from_millis(20));
+        state.check(Operation::Stat).unwrap();
+        state.finish(Ok(())).unwrap();
+        state.check(Operation::Stat).unwrap();
+        state.check(Operation::Stat).unwrap();
+    }
 }
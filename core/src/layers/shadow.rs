@@ -0,0 +1,487 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use log::warn;
+use md5::Digest;
+use md5::Md5;
+
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// Mirror reads and stats to a shadow operator and compare the results.
+///
+/// # Shadow
+///
+/// A sampled part of `read` and `stat` sent to the underlying service will be
+/// sent to the shadow operator again in background, for example, the service
+/// that data is migrating to. Results are compared by:
+///
+/// - the error kind if the request failed
+/// - the content length
+/// - the MD5 of the content for `read`, or `Content-MD5` for `stat` if both
+///   services return it
+///
+/// Divergences are logged with `warn` level and counted, use
+/// [`ShadowLayer::snapshot`] to fetch the counters. Results returned to users
+/// always come from the underlying service.
+///
+/// # Notes
+///
+/// - Comparisons are spawned by the executor set via
+///   [`ShadowLayer::with_executor`], which is required if the
+///   `executors-tokio` feature is not enabled.
+/// - Reads are compared once the reader reaches EOF or fails, readers dropped
+///   before that are not compared.
+/// - The shadow operator reads the whole range again, which doubles the
+///   traffic of sampled reads.
+/// - Blocking operations are not mirrored.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::ShadowLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let shadow = Operator::new(services::Memory::default())?.finish();
+/// // Mirror 10% of reads and stats.
+/// let layer = ShadowLayer::new(shadow).with_ratio(0.1);
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(layer.clone())
+///     .finish();
+///
+/// let snapshot = layer.snapshot();
+/// println!("mirrored: {}, divergences: {}", snapshot.mirrored, snapshot.divergences);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShadowLayer {
+    shadow: Accessor,
+    ratio: f64,
+    executor: Executor,
+    state: Arc<ShadowState>,
+}
+
+impl ShadowLayer {
+    /// Create a new `ShadowLayer` mirroring requests to `shadow`.
+    pub fn new(shadow: Operator) -> Self {
+        Self {
+            shadow: shadow.into_inner(),
+            ratio: 1.0,
+            executor: Executor::new(),
+            state: Arc::default(),
+        }
+    }
+
+    /// Set the ratio of requests to mirror, which is clamped into `[0, 1]`.
+    ///
+    /// Requests are sampled evenly, for example, every 10th request is mirrored
+    /// with ratio `0.1`.
+    ///
+    /// Default to `1.0`.
+    pub fn with_ratio(mut self, ratio: f64) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the executor to run comparisons in background.
+    pub fn with_executor(mut self, executor: Executor) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    /// Take a snapshot of current counters.
+    pub fn snapshot(&self) -> ShadowSnapshot {
+        self.state.snapshot()
+    }
+}
+
+/// A point-in-time copy of the counters accumulated by [`ShadowLayer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShadowSnapshot {
+    /// Requests that have been mirrored and compared.
+    pub mirrored: u64,
+    /// Requests whose results differ between services.
+    pub divergences: u64,
+}
+
+impl<A: Access> Layer<A> for ShadowLayer {
+    type LayeredAccess = ShadowAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ShadowAccessor {
+            inner,
+            core: Arc::new(ShadowCore {
+                shadow: self.shadow.clone(),
+                ratio: self.ratio,
+                executor: self.executor.clone(),
+                state: self.state.clone(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ShadowState {
+    /// Requests that have been sampled.
+    requests: AtomicU64,
+    mirrored: AtomicU64,
+    divergences: AtomicU64,
+}
+
+impl ShadowState {
+    fn snapshot(&self) -> ShadowSnapshot {
+        ShadowSnapshot {
+            mirrored: self.mirrored.load(Ordering::Relaxed),
+            divergences: self.divergences.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The result of a request to be compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Failed(ErrorKind),
+    Succeeded {
+        content_length: u64,
+        md5: Option<String>,
+    },
+}
+
+impl Outcome {
+    fn from_stat(res: &Result<RpStat>) -> Self {
+        match res {
+            Ok(rp) => Outcome::Succeeded {
+                content_length: rp.metadata().content_length(),
+                md5: rp.metadata().content_md5().map(|v| v.to_string()),
+            },
+            Err(err) => Outcome::Failed(err.kind()),
+        }
+    }
+
+    /// Check whether two outcomes are the same, md5 is only compared if both
+    /// are known.
+    fn matches(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (
+                Outcome::Succeeded {
+                    content_length: l1,
+                    md5: m1,
+                },
+                Outcome::Succeeded {
+                    content_length: l2,
+                    md5: m2,
+                },
+            ) => l1 == l2 && (m1.is_none() || m2.is_none() || m1 == m2),
+            (a, b) => a == b,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ShadowCore {
+    shadow: Accessor,
+    ratio: f64,
+    executor: Executor,
+    state: Arc<ShadowState>,
+}
+
+impl ShadowCore {
+    /// Decide whether the current request should be mirrored.
+    fn sample(&self) -> bool {
+        let n = self.state.requests.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.ratio).floor() > (n * self.ratio).floor()
+    }
+
+    /// Run `op` against the shadow operator in background and compare its
+    /// outcome with `expect`.
+    fn compare(self: &Arc<Self>, op: Operation, path: &str, args: OpRead, expect: Outcome) {
+        let core = self.clone();
+        let path = path.to_string();
+        self.executor
+            .clone()
+            .into_inner()
+            .execute(Box::pin(async move {
+                let actual = match op {
+                    Operation::Stat => {
+                        let mut stat = OpStat::new();
+                        if let Some(v) = args.version() {
+                            stat = stat.with_version(v);
+                        }
+                        if let Some(key) = args.customer_key() {
+                            stat = stat.with_customer_key(key.clone());
+                        }
+                        Outcome::from_stat(&core.shadow.stat(&path, stat).await)
+                    }
+                    _ => read_outcome(&core.shadow, &path, args).await,
+                };
+
+                core.state.mirrored.fetch_add(1, Ordering::Relaxed);
+                if !expect.matches(&actual) {
+                    core.state.divergences.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        target: "opendal::layers::shadow",
+                        "{op} {path} diverged: expect {expect:?}, actual {actual:?}"
+                    );
+                }
+            }));
+    }
+}
+
+/// Read the content of `path` from the shadow operator.
+async fn read_outcome(shadow: &Accessor, path: &str, args: OpRead) -> Outcome {
+    let res = async {
+        let (_, mut r) = shadow.read(path, args).await?;
+        r.read_all().await
+    }
+    .await;
+
+    match res {
+        Ok(bs) => {
+            let mut hasher = Md5::new();
+            let content_length = bs.len() as u64;
+            for b in bs {
+                hasher.update(&b);
+            }
+            Outcome::Succeeded {
+                content_length,
+                md5: Some(format!("{:x}", hasher.finalize())),
+            }
+        }
+        Err(err) => Outcome::Failed(err.kind()),
+    }
+}
+
+#[derive(Debug)]
+pub struct ShadowAccessor<A: Access> {
+    inner: A,
+    core: Arc<ShadowCore>,
+}
+
+impl<A: Access> LayeredAccess for ShadowAccessor<A> {
+    type Inner = A;
+    type Reader = ShadowReader<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if !self.core.sample() {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, ShadowReader::new(r, None)));
+        }
+
+        match self.inner.read(path, args.clone()).await {
+            Ok((rp, r)) => {
+                let ctx = ShadowReadContext {
+                    core: self.core.clone(),
+                    path: path.to_string(),
+                    args,
+                };
+                Ok((rp, ShadowReader::new(r, Some(ctx))))
+            }
+            Err(err) => {
+                self.core
+                    .compare(Operation::Read, path, args, Outcome::Failed(err.kind()));
+                Err(err)
+            }
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !self.core.sample() {
+            return self.inner.stat(path, args).await;
+        }
+
+        let mut read = OpRead::new();
+        if let Some(v) = args.version() {
+            read = read.with_version(v);
+        }
+        if let Some(key) = args.customer_key() {
+            read = read.with_customer_key(key.clone());
+        }
+        let res = self.inner.stat(path, args).await;
+        self.core
+            .compare(Operation::Stat, path, read, Outcome::from_stat(&res));
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+struct ShadowReadContext {
+    core: Arc<ShadowCore>,
+    path: String,
+    args: OpRead,
+}
+
+/// ShadowReader hashes the content while reading, and starts the comparison
+/// once reaching EOF or failing.
+pub struct ShadowReader<R> {
+    inner: R,
+    ctx: Option<ShadowReadContext>,
+    hasher: Md5,
+    size: u64,
+}
+
+impl<R> ShadowReader<R> {
+    fn new(inner: R, ctx: Option<ShadowReadContext>) -> Self {
+        Self {
+            inner,
+            ctx,
+            hasher: Md5::new(),
+            size: 0,
+        }
+    }
+
+    fn finish(&mut self, res: &Result<Buffer>) {
+        let Some(ctx) = self.ctx.take() else {
+            return;
+        };
+        let expect = match res {
+            Ok(_) => Outcome::Succeeded {
+                content_length: self.size,
+                md5: Some(format!("{:x}", std::mem::take(&mut self.hasher).finalize())),
+            },
+            Err(err) => Outcome::Failed(err.kind()),
+        };
+        ctx.core
+            .compare(Operation::Read, &ctx.path, ctx.args, expect);
+    }
+}
+
+impl<R: oio::Read> oio::Read for ShadowReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        let res = self.inner.read().await;
+        if self.ctx.is_none() {
+            return res;
+        }
+
+        match &res {
+            Ok(bs) if !bs.is_empty() => {
+                self.size += bs.len() as u64;
+                for b in bs.clone() {
+                    self.hasher.update(&b);
+                }
+            }
+            _ => self.finish(&res),
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+
+    async fn wait_mirrored(layer: &ShadowLayer, n: u64) {
+        for _ in 0..100 {
+            if layer.snapshot().mirrored >= n {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shadow_layer() {
+        let shadow = Operator::new(services::Memory::default()).unwrap().finish();
+        shadow.write("same.txt", "hello").await.unwrap();
+        shadow.write("diff.txt", "world").await.unwrap();
+
+        let primary = Operator::new(services::Memory::default()).unwrap().finish();
+        primary.write("same.txt", "hello").await.unwrap();
+        primary.write("diff.txt", "hello").await.unwrap();
+        let layer = ShadowLayer::new(shadow);
+        let op = primary.layer(layer.clone());
+
+        assert_eq!(op.read("same.txt").await.unwrap().to_vec(), b"hello");
+        assert_eq!(op.stat("same.txt").await.unwrap().content_length(), 5);
+        wait_mirrored(&layer, 2).await;
+        assert_eq!(
+            layer.snapshot(),
+            ShadowSnapshot {
+                mirrored: 2,
+                divergences: 0,
+            }
+        );
+
+        // Same length but different content.
+        assert_eq!(op.read("diff.txt").await.unwrap().to_vec(), b"hello");
+        // Missing in the primary only.
+        op.write("new.txt", "new").await.unwrap();
+        op.stat("new.txt").await.unwrap();
+        wait_mirrored(&layer, 4).await;
+        assert_eq!(
+            layer.snapshot(),
+            ShadowSnapshot {
+                mirrored: 4,
+                divergences: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let shadow = Operator::new(services::Memory::default()).unwrap().finish();
+        let core = ShadowCore {
+            shadow: shadow.into_inner(),
+            ratio: 0.25,
+            executor: Executor::new(),
+            state: Arc::default(),
+        };
+        let sampled = (0..100).filter(|_| core.sample()).count();
+        assert_eq!(sampled, 25);
+    }
+}
@@ -0,0 +1,689 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::layers::TypeEraseLayer;
+use crate::raw::oio::BlockingRead;
+use crate::raw::oio::BlockingWrite;
+use crate::raw::oio::Read;
+use crate::raw::oio::Write;
+use crate::raw::*;
+use crate::*;
+
+/// ShardMapper decides which shard a file belongs to.
+///
+/// The mapping must be stable, files can't be found anymore if they are
+/// mapped to another shard.
+pub trait ShardMapper: Debug + Send + Sync + 'static {
+    /// Return the index of the shard for file `path`, which must be less
+    /// than `shards`.
+    fn shard(&self, path: &str, shards: usize) -> usize;
+}
+
+/// Map files to shards by the hash of their paths.
+///
+/// [Jump consistent hash](https://arxiv.org/abs/1406.2294) is used so that
+/// only `1 / shards` of files are mapped to another shard after adding a
+/// new shard at the end.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashShardMapper;
+
+impl ShardMapper for HashShardMapper {
+    fn shard(&self, path: &str, shards: usize) -> usize {
+        jump_consistent_hash(fnv1a(path.as_bytes()), shards)
+    }
+}
+
+/// Map files to shards by the ranges of their paths.
+///
+/// Given boundaries `["g", "p"]`, paths less than `g` belong to shard 0,
+/// paths in `[g, p)` belong to shard 1 and others belong to shard 2. Paths
+/// beyond the last shard belong to the last shard.
+#[derive(Debug, Clone, Default)]
+pub struct PrefixShardMapper {
+    boundaries: Vec<String>,
+}
+
+impl PrefixShardMapper {
+    /// Create a new `PrefixShardMapper` with sorted boundaries.
+    pub fn new(boundaries: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut boundaries: Vec<String> = boundaries.into_iter().map(Into::into).collect();
+        boundaries.sort();
+        Self { boundaries }
+    }
+}
+
+impl ShardMapper for PrefixShardMapper {
+    fn shard(&self, path: &str, shards: usize) -> usize {
+        let idx = self.boundaries.partition_point(|b| b.as_str() <= path);
+        idx.min(shards - 1)
+    }
+}
+
+/// Spread files across multiple operators while presenting a single
+/// namespace.
+///
+/// The underlying service is the first shard, others are added via
+/// [`ShardLayer::with_shard`]. Use it to spread rate limits and namespace
+/// size across buckets or accounts.
+///
+/// # Routing
+///
+/// - Files are routed by [`ShardMapper`], [`HashShardMapper`] is used by
+///   default.
+/// - Dirs exist on all shards: `create_dir` and `delete` of dirs are sent to
+///   every shard, `stat` of dirs succeeds if any shard has the dir.
+/// - `list` merges the entries of all shards, shards are listed one after
+///   another and dirs are only returned once.
+/// - `copy` and `rename` across shards read the whole file into memory and
+///   write it into the target shard.
+///
+/// # Notes
+///
+/// Changing shards or the mapper makes existing files unreachable, move
+/// them to their new shards first. All shards should be the same kind of
+/// service, capabilities of the underlying service are used.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::PrefixShardMapper;
+/// # use opendal::layers::ShardLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let second = Operator::new(services::Memory::default())?.finish();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         ShardLayer::new()
+///             .with_shard(second)
+///             .with_mapper(PrefixShardMapper::new(["m"])),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShardLayer {
+    shards: Vec<Accessor>,
+    mapper: Arc<dyn ShardMapper>,
+}
+
+impl Default for ShardLayer {
+    fn default() -> Self {
+        Self {
+            shards: Vec::new(),
+            mapper: Arc::new(HashShardMapper),
+        }
+    }
+}
+
+impl ShardLayer {
+    /// Create a new `ShardLayer` with the underlying service as the only shard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a shard after existing shards.
+    pub fn with_shard(mut self, shard: Operator) -> Self {
+        self.shards.push(shard.into_inner());
+        self
+    }
+
+    /// Set the mapper to decide which shard a file belongs to.
+    pub fn with_mapper(mut self, mapper: impl ShardMapper) -> Self {
+        self.mapper = Arc::new(mapper);
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for ShardLayer {
+    type LayeredAccess = ShardAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let inner = Arc::new(inner);
+        // Erase the types of the underlying service so that it can be used
+        // along with other shards.
+        let mut shards = vec![Arc::new(TypeEraseLayer.layer(inner.clone())) as Accessor];
+        shards.extend(self.shards.iter().cloned());
+
+        ShardAccessor {
+            inner,
+            shards,
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ShardAccessor<A: Access> {
+    inner: Arc<A>,
+    /// All shards, starting with the underlying service.
+    shards: Vec<Accessor>,
+    mapper: Arc<dyn ShardMapper>,
+}
+
+impl<A: Access> ShardAccessor<A> {
+    fn index(&self, path: &str) -> usize {
+        self.mapper.shard(path, self.shards.len())
+    }
+
+    fn shard(&self, path: &str) -> &Accessor {
+        &self.shards[self.index(path)]
+    }
+
+    /// Copy file `from` to `to` in another shard by streaming the content
+    /// through a reader and a writer.
+    async fn copy_across(&self, from: &str, to: &str) -> Result<()> {
+        let (_, mut r) = self.shard(from).read(from, OpRead::new()).await?;
+        let (_, mut w) = self.shard(to).write(to, OpWrite::new()).await?;
+
+        let res: Result<()> = async {
+            loop {
+                let buf = r.read().await?;
+                if buf.is_empty() {
+                    return Ok(());
+                }
+                w.write(buf).await?;
+            }
+        }
+        .await;
+        match res {
+            Ok(()) => w.close().await.map(|_| ()),
+            Err(err) => {
+                let _ = w.abort().await;
+                Err(err)
+            }
+        }
+    }
+
+    fn blocking_copy_across(&self, from: &str, to: &str) -> Result<()> {
+        let (_, mut r) = self.shard(from).blocking_read(from, OpRead::new())?;
+        let (_, mut w) = self.shard(to).blocking_write(to, OpWrite::new())?;
+
+        loop {
+            let buf = r.read()?;
+            if buf.is_empty() {
+                break;
+            }
+            w.write(buf)?;
+        }
+        w.close()
+    }
+}
+
+impl<A: Access> LayeredAccess for ShardAccessor<A> {
+    type Inner = A;
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Lister = ShardLister;
+    type BlockingLister = BlockingShardLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        for shard in &self.shards {
+            shard.create_dir(path, args.clone()).await?;
+        }
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.shard(path).read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.shard(path).write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if self.index(from) == self.index(to) {
+            return self.shard(from).copy(from, to, args).await;
+        }
+        self.copy_across(from, to).await?;
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        if self.index(from) == self.index(to) {
+            return self.shard(from).rename(from, to, args).await;
+        }
+        self.copy_across(from, to).await?;
+        self.shard(from).delete(from, OpDelete::new()).await?;
+        Ok(RpRename::default())
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') {
+            return self.shard(path).stat(path, args).await;
+        }
+
+        let mut first_err = None;
+        for shard in &self.shards {
+            match shard.stat(path, args.clone()).await {
+                Ok(rp) => return Ok(rp),
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        Err(first_err.expect("shards must not be empty"))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.shard(path).delete(path, args).await;
+        }
+        for shard in &self.shards {
+            shard.delete(path, args.clone()).await?;
+        }
+        Ok(RpDelete::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        Ok((
+            RpList::default(),
+            ShardLister::new(self.shards.clone(), path, args),
+        ))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        // Ops are grouped by shard with their index, so that the results
+        // could be put back in the same order as ops.
+        let mut batches: Vec<Vec<(usize, String, BatchOperation)>> =
+            (0..self.shards.len()).map(|_| Vec::new()).collect();
+        let mut results: Vec<Option<(String, Result<BatchedReply>)>> = Vec::new();
+        for (idx, (path, op)) in args.into_operation().into_iter().enumerate() {
+            results.push(None);
+            if let BatchOperation::Copy(to, _) = &op {
+                if self.index(&path) != self.index(to) {
+                    let err = Error::new(
+                        ErrorKind::Unsupported,
+                        "batch copy across shards is not supported",
+                    )
+                    .with_operation(Operation::Batch)
+                    .with_context("from", &path)
+                    .with_context("to", to);
+                    results[idx] = Some((path, Err(err)));
+                    continue;
+                }
+            }
+            batches[self.index(&path)].push((idx, path, op));
+        }
+
+        for (shard, ops) in self.shards.iter().zip(batches) {
+            if ops.is_empty() {
+                continue;
+            }
+            let (indexes, ops): (Vec<_>, Vec<_>) = ops
+                .into_iter()
+                .map(|(idx, path, op)| (idx, (path, op)))
+                .unzip();
+            let rp = shard.batch(OpBatch::new(ops)).await?.into_results();
+            if rp.len() != indexes.len() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch returns mismatched number of results",
+                )
+                .with_operation(Operation::Batch)
+                .with_context("expected", indexes.len().to_string())
+                .with_context("actual", rp.len().to_string()));
+            }
+            for (idx, res) in indexes.into_iter().zip(rp) {
+                results[idx] = Some(res);
+            }
+        }
+        Ok(RpBatch::new(results.into_iter().flatten().collect()))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.shard(path).presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        for shard in &self.shards {
+            shard.blocking_create_dir(path, args.clone())?;
+        }
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.shard(path).blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.shard(path).blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if self.index(from) == self.index(to) {
+            return self.shard(from).blocking_copy(from, to, args);
+        }
+        self.blocking_copy_across(from, to)?;
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        if self.index(from) == self.index(to) {
+            return self.shard(from).blocking_rename(from, to, args);
+        }
+        self.blocking_copy_across(from, to)?;
+        self.shard(from).blocking_delete(from, OpDelete::new())?;
+        Ok(RpRename::default())
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !path.ends_with('/') {
+            return self.shard(path).blocking_stat(path, args);
+        }
+
+        let mut first_err = None;
+        for shard in &self.shards {
+            match shard.blocking_stat(path, args.clone()) {
+                Ok(rp) => return Ok(rp),
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        Err(first_err.expect("shards must not be empty"))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if !path.ends_with('/') {
+            return self.shard(path).blocking_delete(path, args);
+        }
+        for shard in &self.shards {
+            shard.blocking_delete(path, args.clone())?;
+        }
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        Ok((
+            RpList::default(),
+            BlockingShardLister::new(self.shards.clone(), path, args),
+        ))
+    }
+}
+
+/// ShardLister lists shards one after another, dirs returned by more than
+/// one shard are skipped.
+pub struct ShardLister {
+    shards: Vec<Accessor>,
+    path: String,
+    args: OpList,
+
+    next: usize,
+    lister: Option<oio::Lister>,
+    dirs: HashSet<String>,
+}
+
+impl ShardLister {
+    fn new(shards: Vec<Accessor>, path: &str, args: OpList) -> Self {
+        Self {
+            shards,
+            path: path.to_string(),
+            args,
+            next: 0,
+            lister: None,
+            dirs: HashSet::new(),
+        }
+    }
+}
+
+impl oio::List for ShardLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            let Some(lister) = self.lister.as_mut() else {
+                let Some(shard) = self.shards.get(self.next) else {
+                    return Ok(None);
+                };
+                self.next += 1;
+                let (_, lister) = shard.list(&self.path, self.args.clone()).await?;
+                self.lister = Some(lister);
+                continue;
+            };
+
+            match lister.next().await? {
+                Some(entry)
+                    if entry.mode().is_dir() && !self.dirs.insert(entry.path().to_string()) => {}
+                Some(entry) => return Ok(Some(entry)),
+                None => self.lister = None,
+            }
+        }
+    }
+}
+
+/// BlockingShardLister is the blocking version of [`ShardLister`].
+pub struct BlockingShardLister {
+    shards: Vec<Accessor>,
+    path: String,
+    args: OpList,
+
+    next: usize,
+    lister: Option<oio::BlockingLister>,
+    dirs: HashSet<String>,
+}
+
+impl BlockingShardLister {
+    fn new(shards: Vec<Accessor>, path: &str, args: OpList) -> Self {
+        Self {
+            shards,
+            path: path.to_string(),
+            args,
+            next: 0,
+            lister: None,
+            dirs: HashSet::new(),
+        }
+    }
+}
+
+impl oio::BlockingList for BlockingShardLister {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            let Some(lister) = self.lister.as_mut() else {
+                let Some(shard) = self.shards.get(self.next) else {
+                    return Ok(None);
+                };
+                self.next += 1;
+                let (_, lister) = shard.blocking_list(&self.path, self.args.clone())?;
+                self.lister = Some(lister);
+                continue;
+            };
+
+            match lister.next()? {
+                Some(entry)
+                    if entry.mode().is_dir() && !self.dirs.insert(entry.path().to_string()) => {}
+                Some(entry) => return Ok(Some(entry)),
+                None => self.lister = None,
+            }
+        }
+    }
+}
+
+/// 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html)
+/// hash, which is stable across platforms and releases.
+fn fnv1a(bs: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bs {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn jump_consistent_hash(mut key: u64, buckets: usize) -> usize {
+    let (mut b, mut j) = (-1i64, 0i64);
+    while j < buckets as i64 {
+        b = j;
+        key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+        j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    b as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[test]
+    fn test_prefix_shard_mapper() {
+        let mapper = PrefixShardMapper::new(["p", "g"]);
+        assert_eq!(mapper.shard("a.txt", 3), 0);
+        assert_eq!(mapper.shard("g", 3), 1);
+        assert_eq!(mapper.shard("o/a.txt", 3), 1);
+        assert_eq!(mapper.shard("z.txt", 3), 2);
+        assert_eq!(mapper.shard("z.txt", 2), 1);
+    }
+
+    #[test]
+    fn test_hash_shard_mapper() {
+        let mapper = HashShardMapper;
+        for i in 0..100 {
+            let path = format!("dir/{i}.txt");
+            let before = mapper.shard(&path, 4);
+            assert!(before < 4);
+            // Files are either kept or moved to the new shard.
+            let after = mapper.shard(&path, 5);
+            assert!(after == before || after == 4);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shard_layer() {
+        let first = Operator::new(services::Memory::default()).unwrap().finish();
+        let second = Operator::new(services::Memory::default()).unwrap().finish();
+        let op = first.clone().layer(
+            ShardLayer::new()
+                .with_shard(second.clone())
+                .with_mapper(PrefixShardMapper::new(["dir/m"])),
+        );
+
+        op.write("dir/a.txt", "a").await.unwrap();
+        op.write("dir/z.txt", "z").await.unwrap();
+        op.write("dir/sub/x.txt", "x").await.unwrap();
+        assert!(first.exists("dir/a.txt").await.unwrap());
+        assert!(second.exists("dir/z.txt").await.unwrap());
+        assert_eq!(op.read("dir/z.txt").await.unwrap().to_vec(), b"z");
+
+        // Rename across shards.
+        op.rename("dir/z.txt", "dir/b.txt").await.unwrap();
+        assert!(!second.exists("dir/z.txt").await.unwrap());
+        assert_eq!(first.read("dir/b.txt").await.unwrap().to_vec(), b"z");
+
+        op.write("dir/y.txt", "y").await.unwrap();
+        let mut paths: Vec<String> = op
+            .lister("dir/")
+            .await
+            .unwrap()
+            .map_ok(|e| e.path().to_string())
+            .try_filter(|p| futures::future::ready(p != "dir/"))
+            .try_collect()
+            .await
+            .unwrap();
+        paths.sort();
+        assert_eq!(paths, ["dir/a.txt", "dir/b.txt", "dir/sub/", "dir/y.txt"]);
+    }
+
+    #[derive(Debug)]
+    struct MockService;
+
+    impl Access for MockService {
+        type Reader = ();
+        type Writer = ();
+        type Lister = ();
+        type BlockingReader = ();
+        type BlockingWriter = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                delete: true,
+                copy: true,
+                batch: true,
+                batch_delete: true,
+                batch_copy: true,
+                ..Default::default()
+            });
+            am.into()
+        }
+
+        async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+            Ok(RpBatch::new(
+                args.into_operation()
+                    .into_iter()
+                    .map(|(path, op)| {
+                        let reply = match op {
+                            BatchOperation::Delete(_) => BatchedReply::Delete(RpDelete::default()),
+                            BatchOperation::Copy(_, _) => BatchedReply::Copy(RpCopy::default()),
+                        };
+                        (path, Ok(reply))
+                    })
+                    .collect(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shard_batch_keeps_order() {
+        let second = OperatorBuilder::new(MockService).finish();
+        let acc = ShardLayer::new()
+            .with_shard(second)
+            .with_mapper(PrefixShardMapper::new(["m"]))
+            .layer(MockService);
+
+        let rp = Access::batch(
+            &acc,
+            OpBatch::new(vec![
+                ("z.txt".to_string(), BatchOperation::Delete(OpDelete::new())),
+                (
+                    "a.txt".to_string(),
+                    BatchOperation::Copy("z.txt".to_string(), OpCopy::new()),
+                ),
+                ("b.txt".to_string(), BatchOperation::Delete(OpDelete::new())),
+                ("y.txt".to_string(), BatchOperation::Delete(OpDelete::new())),
+            ]),
+        )
+        .await
+        .unwrap();
+
+        let results = rp.into_results();
+        let paths: Vec<_> = results.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["z.txt", "a.txt", "b.txt", "y.txt"]);
+        assert!(results[0].1.is_ok());
+        assert_eq!(
+            results[1].1.as_ref().err().map(|err| err.kind()),
+            Some(ErrorKind::Unsupported)
+        );
+        assert!(results[2].1.is_ok());
+        assert!(results[3].1.is_ok());
+    }
+}
@@ -0,0 +1,500 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use moka::sync::Cache;
+
+use crate::raw::*;
+use crate::*;
+
+/// Cache `stat` results in memory for underlying services.
+///
+/// # Stat Cache
+///
+/// StatCacheLayer keeps the metadata returned by `stat` in an in-memory cache
+/// with a time-to-live, so that the common "stat before read" pattern doesn't
+/// need to send a request every time.
+///
+/// Cached entries are invalidated on `write`, `create_dir`, `delete`,
+/// `copy`, `rename` and `batch` through the same operator. If
+/// [`StatCacheLayer::with_cache_list`] is enabled, metadata of files returned
+/// by `list` will be cached as well.
+///
+/// # Notes
+///
+/// - Changes made outside of this operator are only observed after the
+///   cached entry expires.
+/// - Only successful `stat` without extra options (like `if_match` and
+///   `version`) is cached, `NotFound` is never cached.
+/// - Metadata returned by `list` could be incomplete on some services, only
+///   enable caching list results when the listed metadata is enough.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// #
+/// # use opendal::layers::StatCacheLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         StatCacheLayer::new()
+///             .with_ttl(Duration::from_secs(30))
+///             .with_capacity(100_000),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatCacheLayer {
+    ttl: Duration,
+    capacity: u64,
+    cache_list: bool,
+}
+
+impl Default for StatCacheLayer {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(60),
+            capacity: 10_000,
+            cache_list: false,
+        }
+    }
+}
+
+impl StatCacheLayer {
+    /// Create a new `StatCacheLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time-to-live of cached entries.
+    ///
+    /// Default to 60 seconds.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the max number of cached entries.
+    ///
+    /// Default to 10000.
+    pub fn with_capacity(mut self, capacity: u64) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Cache the metadata of files returned by `list`.
+    pub fn with_cache_list(mut self, enabled: bool) -> Self {
+        self.cache_list = enabled;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for StatCacheLayer {
+    type LayeredAccess = StatCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        StatCacheAccessor {
+            inner,
+            cache: Cache::builder()
+                .max_capacity(self.capacity)
+                .time_to_live(self.ttl)
+                .build(),
+            cache_list: self.cache_list,
+        }
+    }
+}
+
+pub struct StatCacheAccessor<A: Access> {
+    inner: A,
+    cache: Cache<String, Metadata>,
+    cache_list: bool,
+}
+
+impl<A: Access> Debug for StatCacheAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatCacheAccessor")
+            .field("inner", &self.inner)
+            .field("cache_list", &self.cache_list)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Only `stat` without extra options can be served from cache.
+fn is_cacheable(args: &OpStat) -> bool {
+    args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+}
+
+impl<A: Access> StatCacheAccessor<A> {
+    /// Collect the paths that will be changed by the batch.
+    fn batch_paths(args: &OpBatch) -> Vec<String> {
+        args.operation()
+            .iter()
+            .map(|(path, op)| match op {
+                BatchOperation::Delete(_) => path.clone(),
+                BatchOperation::Copy(to, _) => to.clone(),
+            })
+            .collect()
+    }
+
+    fn lister<L>(&self, inner: L) -> StatCacheLister<L> {
+        StatCacheLister {
+            inner,
+            cache: self.cache_list.then(|| self.cache.clone()),
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for StatCacheAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = StatCacheWrapper<A::Writer>;
+    type BlockingWriter = StatCacheWrapper<A::BlockingWriter>;
+    type Lister = StatCacheLister<A::Lister>;
+    type BlockingLister = StatCacheLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.cache.invalidate(path);
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.cache.invalidate(path);
+        self.inner.write(path, args).await.map(|(rp, w)| {
+            (
+                rp,
+                StatCacheWrapper::new(w, self.cache.clone(), path.to_string()),
+            )
+        })
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.copy(from, to, args).await;
+        self.cache.invalidate(to);
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.rename(from, to, args).await;
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_cacheable(&args) {
+            return self.inner.stat(path, args).await;
+        }
+        if let Some(meta) = self.cache.get(path) {
+            return Ok(RpStat::new(meta));
+        }
+
+        let rp = self.inner.stat(path, args).await?;
+        self.cache.insert(path.to_string(), rp.metadata().clone());
+        Ok(rp)
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let res = self.inner.delete(path, args).await;
+        self.cache.invalidate(path);
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, l)| (rp, self.lister(l)))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let paths = Self::batch_paths(&args);
+        let res = self.inner.batch(args).await;
+        for path in paths {
+            self.cache.invalidate(&path);
+        }
+        res
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.cache.invalidate(path);
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.cache.invalidate(path);
+        self.inner.blocking_write(path, args).map(|(rp, w)| {
+            (
+                rp,
+                StatCacheWrapper::new(w, self.cache.clone(), path.to_string()),
+            )
+        })
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.blocking_copy(from, to, args);
+        self.cache.invalidate(to);
+        res
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.blocking_rename(from, to, args);
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        res
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_cacheable(&args) {
+            return self.inner.blocking_stat(path, args);
+        }
+        if let Some(meta) = self.cache.get(path) {
+            return Ok(RpStat::new(meta));
+        }
+
+        let rp = self.inner.blocking_stat(path, args)?;
+        self.cache.insert(path.to_string(), rp.metadata().clone());
+        Ok(rp)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let res = self.inner.blocking_delete(path, args);
+        self.cache.invalidate(path);
+        res
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, l)| (rp, self.lister(l)))
+    }
+}
+
+/// StatCacheWrapper invalidates the cached entry after the writer is closed.
+pub struct StatCacheWrapper<W> {
+    inner: W,
+    cache: Cache<String, Metadata>,
+    path: String,
+}
+
+impl<W> StatCacheWrapper<W> {
+    fn new(inner: W, cache: Cache<String, Metadata>, path: String) -> Self {
+        Self { inner, cache, path }
+    }
+}
+
+impl<W: oio::Write> oio::Write for StatCacheWrapper<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for StatCacheWrapper<W> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close();
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+/// StatCacheLister fills the cache with listed files if enabled.
+pub struct StatCacheLister<L> {
+    inner: L,
+    cache: Option<Cache<String, Metadata>>,
+}
+
+impl<L> StatCacheLister<L> {
+    fn fill(&self, entry: &oio::Entry) {
+        if let Some(cache) = &self.cache {
+            if entry.mode().is_file() {
+                cache.insert(entry.path().to_string(), entry.metadata().clone());
+            }
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for StatCacheLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let entry = self.inner.next().await?;
+        if let Some(entry) = &entry {
+            self.fill(entry);
+        }
+        Ok(entry)
+    }
+}
+
+impl<L: oio::BlockingList> oio::BlockingList for StatCacheLister<L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let entry = self.inner.next()?;
+        if let Some(entry) = &entry {
+            self.fill(entry);
+        }
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::services;
+
+    /// Count the `stat` requests sent to underlying services.
+    #[derive(Debug, Clone)]
+    struct StatCounter(Arc<AtomicUsize>);
+
+    impl<A: Access> Layer<A> for StatCounter {
+        type LayeredAccess = StatCounterAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccess {
+            StatCounterAccessor {
+                inner,
+                count: self.0.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct StatCounterAccessor<A: Access> {
+        inner: A,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<A: Access> LayeredAccess for StatCounterAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Lister = A::Lister;
+        type BlockingLister = A::BlockingLister;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.inner.stat(path, args).await
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.inner.write(path, args).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(
+            &self,
+            path: &str,
+            args: OpList,
+        ) -> Result<(RpList, Self::BlockingLister)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stat_cache() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(StatCounter(count.clone()))
+            .layer(StatCacheLayer::new())
+            .finish();
+
+        op.write("test", "abcd").await.unwrap();
+        assert_eq!(op.stat("test").await.unwrap().content_length(), 4);
+        assert_eq!(op.stat("test").await.unwrap().content_length(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        // Write invalidates the cached entry.
+        op.write("test", "abcdefgh").await.unwrap();
+        assert_eq!(op.stat("test").await.unwrap().content_length(), 8);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+
+        // Delete invalidates the cached entry.
+        op.delete("test").await.unwrap();
+        let err = op.stat("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,395 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Accumulate operation statistics in process.
+///
+/// # Stats
+///
+/// StatsLayer keeps a few counters and gauges in memory without depending on
+/// any metrics registry:
+///
+/// - `ops`: operations that have been started.
+/// - `errors`: operations (including reads, writes and listing on the
+///   returned readers, writers and listers) that have failed.
+/// - `bytes_read` and `bytes_written`: bytes transferred by readers and
+///   writers.
+/// - `in_flight`: operations that are still running. Readers, writers and
+///   listers are counted until they are dropped.
+///
+/// Use [`StatsLayer::snapshot`] to fetch the current values, for example, in
+/// a health endpoint.
+///
+/// # Notes
+///
+/// Stats are shared by all operators built with the same layer (and its
+/// clones).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::StatsLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let stats = StatsLayer::new();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(stats.clone())
+///     .finish();
+///
+/// let snapshot = stats.snapshot();
+/// println!("ops: {}, errors: {}", snapshot.ops, snapshot.errors);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatsLayer {
+    stats: Arc<Stats>,
+}
+
+impl StatsLayer {
+    /// Create a new `StatsLayer` with all stats set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a snapshot of current stats.
+    pub fn snapshot(&self) -> StatsSnapshot {
+        self.stats.snapshot()
+    }
+}
+
+/// A point-in-time copy of the stats accumulated by [`StatsLayer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// Operations that have been started.
+    pub ops: u64,
+    /// Operations that have failed.
+    pub errors: u64,
+    /// Bytes read from the underlying services.
+    pub bytes_read: u64,
+    /// Bytes written into the underlying services.
+    pub bytes_written: u64,
+    /// Operations that are still running.
+    pub in_flight: u64,
+}
+
+impl<A: Access> Layer<A> for StatsLayer {
+    type LayeredAccess = StatsAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        StatsAccessor {
+            inner,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    ops: AtomicU64,
+    errors: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    in_flight: AtomicU64,
+}
+
+impl Stats {
+    fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            ops: self.ops.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Start an operation, the returned guard keeps it in flight.
+    fn start(self: &Arc<Self>) -> InFlightGuard {
+        self.ops.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard(self.clone())
+    }
+
+    fn record<T>(&self, res: Result<T>) -> Result<T> {
+        if res.is_err() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        res
+    }
+}
+
+struct InFlightGuard(Arc<Stats>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+pub struct StatsAccessor<A: Access> {
+    inner: A,
+    stats: Arc<Stats>,
+}
+
+impl<A: Access> StatsAccessor<A> {
+    /// Wrap the returned reader, writer or lister to keep it in flight.
+    fn wrap<R, T>(
+        &self,
+        guard: InFlightGuard,
+        res: Result<(R, T)>,
+    ) -> Result<(R, StatsWrapper<T>)> {
+        let (rp, inner) = self.stats.record(res)?;
+        Ok((
+            rp,
+            StatsWrapper {
+                inner,
+                stats: self.stats.clone(),
+                _guard: guard,
+            },
+        ))
+    }
+}
+
+impl<A: Access> LayeredAccess for StatsAccessor<A> {
+    type Inner = A;
+    type Reader = StatsWrapper<A::Reader>;
+    type BlockingReader = StatsWrapper<A::BlockingReader>;
+    type Writer = StatsWrapper<A::Writer>;
+    type BlockingWriter = StatsWrapper<A::BlockingWriter>;
+    type Lister = StatsWrapper<A::Lister>;
+    type BlockingLister = StatsWrapper<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.create_dir(path, args).await)
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.read(path, args).await)
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.write(path, args).await)
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.copy(from, to, args).await)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.rename(from, to, args).await)
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.stat(path, args).await)
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.delete(path, args).await)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.list(path, args).await)
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.batch(args).await)
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.presign(path, args).await)
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _guard = self.stats.start();
+        self.stats
+            .record(self.inner.blocking_create_dir(path, args))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.blocking_read(path, args))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.blocking_write(path, args))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.blocking_copy(from, to, args))
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _guard = self.stats.start();
+        self.stats
+            .record(self.inner.blocking_rename(from, to, args))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.blocking_stat(path, args))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let _guard = self.stats.start();
+        self.stats.record(self.inner.blocking_delete(path, args))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let guard = self.stats.start();
+        self.wrap(guard, self.inner.blocking_list(path, args))
+    }
+}
+
+pub struct StatsWrapper<T> {
+    inner: T,
+    stats: Arc<Stats>,
+    _guard: InFlightGuard,
+}
+
+impl<T> StatsWrapper<T> {
+    fn record_read(&self, res: Result<Buffer>) -> Result<Buffer> {
+        if let Ok(bs) = &res {
+            self.stats
+                .bytes_read
+                .fetch_add(bs.len() as u64, Ordering::Relaxed);
+        }
+        self.stats.record(res)
+    }
+
+    fn record_write(&self, size: u64, res: Result<()>) -> Result<()> {
+        if res.is_ok() {
+            self.stats.bytes_written.fetch_add(size, Ordering::Relaxed);
+        }
+        self.stats.record(res)
+    }
+}
+
+impl<T: oio::Read> oio::Read for StatsWrapper<T> {
+    async fn read(&mut self) -> Result<Buffer> {
+        let res = self.inner.read().await;
+        self.record_read(res)
+    }
+}
+
+impl<T: oio::BlockingRead> oio::BlockingRead for StatsWrapper<T> {
+    fn read(&mut self) -> Result<Buffer> {
+        let res = self.inner.read();
+        self.record_read(res)
+    }
+}
+
+impl<T: oio::Write> oio::Write for StatsWrapper<T> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.write(bs).await;
+        self.record_write(size, res)
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.stats.record(res)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        self.stats.record(res)
+    }
+}
+
+impl<T: oio::BlockingWrite> oio::BlockingWrite for StatsWrapper<T> {
+    fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.write(bs);
+        self.record_write(size, res)
+    }
+
+    fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close();
+        self.stats.record(res)
+    }
+}
+
+impl<T: oio::List> oio::List for StatsWrapper<T> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let res = self.inner.next().await;
+        self.stats.record(res)
+    }
+}
+
+impl<T: oio::BlockingList> oio::BlockingList for StatsWrapper<T> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let res = self.inner.next();
+        self.stats.record(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_stats() {
+        let stats = StatsLayer::new();
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(stats.clone())
+            .finish();
+
+        op.write("test", "abcd").await.expect("write must succeed");
+        let bs = op.read("test").await.expect("read must succeed");
+        assert_eq!(bs.len(), 4);
+        op.stat("not_exist").await.expect_err("stat must fail");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.bytes_written, 4);
+        assert_eq!(snapshot.bytes_read, 4);
+        assert_eq!(snapshot.errors, 1);
+        assert_eq!(snapshot.in_flight, 0);
+        assert!(snapshot.ops >= 3, "{snapshot:?}");
+
+        let _lister = op.lister("/").await.expect("list must succeed");
+        assert_eq!(stats.snapshot().in_flight, 1);
+    }
+}
@@ -17,6 +17,7 @@
 
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::sync::RwLock;
 use std::thread;
 
 use governor::clock::Clock;
@@ -27,6 +28,7 @@ use governor::state::NotKeyed;
 use governor::Quota;
 use governor::RateLimiter;
 
+use super::adaptive::Aimd;
 use crate::raw::*;
 use crate::*;
 
@@ -46,6 +48,15 @@ use crate::*;
 ///
 /// Read more about [Quota](https://docs.rs/governor/latest/governor/struct.Quota.html#examples)
 ///
+/// # Adaptive bandwidth
+///
+/// Users can set [`ThrottleLayer::with_adaptive`] to follow the feedback of
+/// services. Once services throttle requests (returning
+/// [`ErrorKind::RateLimited`], `429 Too Many Requests` or `503 Service
+/// Unavailable`), the bandwidth will be halved and held until `Retry-After`
+/// (1s if not returned). After that, the bandwidth will be increased by 1/16
+/// of the configured bandwidth gradually until reaching it.
+///
 /// # Examples
 ///
 /// This example limits bandwidth to 10 KiB/s and burst size to 10 MiB.
@@ -69,6 +80,7 @@ use crate::*;
 pub struct ThrottleLayer {
     bandwidth: NonZeroU32,
     burst: NonZeroU32,
+    min_bandwidth: Option<u32>,
 }
 
 impl ThrottleLayer {
@@ -82,20 +94,35 @@ impl ThrottleLayer {
         Self {
             bandwidth: NonZeroU32::new(bandwidth).unwrap(),
             burst: NonZeroU32::new(burst).unwrap(),
+            min_bandwidth: None,
         }
     }
+
+    /// Adjust the bandwidth according to the throttling of services, the
+    /// bandwidth will never be lower than `min_bandwidth`.
+    ///
+    /// Read [adaptive bandwidth](#adaptive-bandwidth) for more details.
+    pub fn with_adaptive(mut self, min_bandwidth: u32) -> Self {
+        self.min_bandwidth = Some(min_bandwidth);
+        self
+    }
 }
 
 impl<A: Access> Layer<A> for ThrottleLayer {
     type LayeredAccess = ThrottleAccessor<A>;
 
     fn layer(&self, accessor: A) -> Self::LayeredAccess {
-        let rate_limiter = Arc::new(RateLimiter::direct(
-            Quota::per_second(self.bandwidth).allow_burst(self.burst),
-        ));
+        let bandwidth = self.bandwidth.get() as usize;
+        let state = ThrottleState {
+            rate_limiter: RwLock::new(new_rate_limiter(self.bandwidth, self.burst)),
+            burst: self.burst,
+            adaptive: self
+                .min_bandwidth
+                .map(|min| Aimd::new(min as usize, bandwidth, bandwidth.div_ceil(16))),
+        };
         ThrottleAccessor {
             inner: accessor,
-            rate_limiter,
+            state: Arc::new(state),
         }
     }
 }
@@ -105,10 +132,56 @@ impl<A: Access> Layer<A> for ThrottleLayer {
 /// Read more about [Middleware](https://docs.rs/governor/latest/governor/middleware/index.html)
 type SharedRateLimiter = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>;
 
+fn new_rate_limiter(bandwidth: NonZeroU32, burst: NonZeroU32) -> SharedRateLimiter {
+    Arc::new(RateLimiter::direct(
+        Quota::per_second(bandwidth).allow_burst(burst),
+    ))
+}
+
+#[derive(Debug)]
+pub struct ThrottleState {
+    rate_limiter: RwLock<SharedRateLimiter>,
+    burst: NonZeroU32,
+    adaptive: Option<Aimd>,
+}
+
+impl ThrottleState {
+    fn rate_limiter(&self) -> SharedRateLimiter {
+        self.rate_limiter.read().expect("lock must succeed").clone()
+    }
+
+    /// Feed the result of a request to the adaptive bandwidth.
+    fn observe<T>(&self, res: Result<T>) -> Result<T> {
+        if let Some(adaptive) = &self.adaptive {
+            self.adjust(adaptive.observe(&res));
+        }
+        res
+    }
+
+    /// Feed an error returned in the middle of a request to the adaptive bandwidth.
+    fn observe_error(&self, err: Error) -> Error {
+        if let Some(adaptive) = &self.adaptive {
+            self.adjust(adaptive.observe_error(&err));
+        }
+        err
+    }
+
+    /// Replace the rate limiter with the new bandwidth, the cells consumed by
+    /// the old one are not carried over.
+    fn adjust(&self, changed: Option<(usize, usize)>) {
+        let Some((_, bandwidth)) = changed else {
+            return;
+        };
+        let bandwidth = NonZeroU32::new(bandwidth as u32).expect("bandwidth must be positive");
+        *self.rate_limiter.write().expect("lock must succeed") =
+            new_rate_limiter(bandwidth, self.burst);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ThrottleAccessor<A: Access> {
     inner: A,
-    rate_limiter: SharedRateLimiter,
+    state: Arc<ThrottleState>,
 }
 
 impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
@@ -125,21 +198,19 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let limiter = self.rate_limiter.clone();
+        let state = self.state.clone();
 
-        self.inner
-            .read(path, args)
-            .await
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+        self.state
+            .observe(self.inner.read(path, args).await)
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, state)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let limiter = self.rate_limiter.clone();
+        let state = self.state.clone();
 
-        self.inner
-            .write(path, args)
-            .await
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+        self.state
+            .observe(self.inner.write(path, args).await)
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, state)))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
@@ -147,19 +218,19 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let limiter = self.rate_limiter.clone();
+        let state = self.state.clone();
 
-        self.inner
-            .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+        self.state
+            .observe(self.inner.blocking_read(path, args))
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, state)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        let limiter = self.rate_limiter.clone();
+        let state = self.state.clone();
 
-        self.inner
-            .blocking_write(path, args)
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+        self.state
+            .observe(self.inner.blocking_write(path, args))
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, state)))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
@@ -169,27 +240,30 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
 
 pub struct ThrottleWrapper<R> {
     inner: R,
-    limiter: SharedRateLimiter,
+    state: Arc<ThrottleState>,
 }
 
 impl<R> ThrottleWrapper<R> {
-    pub fn new(inner: R, rate_limiter: SharedRateLimiter) -> Self {
-        Self {
-            inner,
-            limiter: rate_limiter,
-        }
+    pub fn new(inner: R, state: Arc<ThrottleState>) -> Self {
+        Self { inner, state }
+    }
+
+    fn observe<T>(&self, res: Result<T>) -> Result<T> {
+        res.map_err(|err| self.state.observe_error(err))
     }
 }
 
 impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        self.inner.read().await
+        let res = self.inner.read().await;
+        self.observe(res)
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ThrottleWrapper<R> {
     fn read(&mut self) -> Result<Buffer> {
-        self.inner.read()
+        let res = self.inner.read();
+        self.observe(res)
     }
 }
 
@@ -198,9 +272,12 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
         let buf_length = NonZeroU32::new(bs.len() as u32).unwrap();
 
         loop {
-            match self.limiter.check_n(buf_length) {
+            match self.state.rate_limiter().check_n(buf_length) {
                 Ok(res) => match res {
-                    Ok(_) => return self.inner.write(bs).await,
+                    Ok(_) => {
+                        let res = self.inner.write(bs).await;
+                        return self.observe(res);
+                    }
                     // the query is valid but the Decider can not accommodate them.
                     Err(not_until) => {
                         let _ = not_until.wait_time_from(DefaultClock::default().now());
@@ -223,8 +300,9 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
         self.inner.abort().await
     }
 
-    async fn close(&mut self) -> Result<()> {
-        self.inner.close().await
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.observe(res)
     }
 }
 
@@ -233,9 +311,12 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
         let buf_length = NonZeroU32::new(bs.len() as u32).unwrap();
 
         loop {
-            match self.limiter.check_n(buf_length) {
+            match self.state.rate_limiter().check_n(buf_length) {
                 Ok(res) => match res {
-                    Ok(_) => return self.inner.write(bs),
+                    Ok(_) => {
+                        let res = self.inner.write(bs);
+                        return self.observe(res);
+                    }
                     // the query is valid but the Decider can not accommodate them.
                     Err(not_until) => {
                         let wait_time = not_until.wait_time_from(DefaultClock::default().now());
@@ -251,7 +332,8 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
         }
     }
 
-    fn close(&mut self) -> Result<()> {
-        self.inner.close()
+    fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close();
+        self.observe(res)
     }
 }
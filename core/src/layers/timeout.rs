@@ -37,10 +37,16 @@ use crate::*;
 /// - IO Operation like `read`, `Reader::read` and `Writer::write`, they operate on data directly, we
 ///   control them by setting `io_timeout`.
 ///
+/// Besides, users can set `stall_timeout` to detect stalled data transfers. If set, it replaces
+/// `io_timeout` for `Reader::read` and `Writer::write` which move data in chunks, so a hung
+/// connection can be detected quickly while slow but legit operations like `Writer::close`
+/// (which may complete a large multipart upload) are still controlled by `io_timeout`.
+///
 /// # Default
 ///
 /// - timeout: 60 seconds
 /// - io_timeout: 10 seconds
+/// - stall_timeout: not set
 ///
 /// # Panics
 ///
@@ -116,6 +122,7 @@ use crate::*;
 pub struct TimeoutLayer {
     timeout: Duration,
     io_timeout: Duration,
+    stall_timeout: Option<Duration>,
 }
 
 impl Default for TimeoutLayer {
@@ -123,6 +130,7 @@ impl Default for TimeoutLayer {
         Self {
             timeout: Duration::from_secs(60),
             io_timeout: Duration::from_secs(10),
+            stall_timeout: None,
         }
     }
 }
@@ -149,6 +157,16 @@ impl TimeoutLayer {
         self
     }
 
+    /// Set stall timeout for TimeoutLayer with given value.
+    ///
+    /// A reader or writer is treated as stalled if no bytes move within this timeout, that is
+    /// `Reader::read` or `Writer::write` doesn't finish in time. Other operations are still
+    /// controlled by `io_timeout`.
+    pub fn with_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+
     /// Set speed for TimeoutLayer with given value.
     ///
     /// # Notes
@@ -174,6 +192,7 @@ impl<A: Access> Layer<A> for TimeoutLayer {
 
             timeout: self.timeout,
             io_timeout: self.io_timeout,
+            stall_timeout: self.stall_timeout,
         }
     }
 }
@@ -184,6 +203,7 @@ pub struct TimeoutAccessor<A: Access> {
 
     timeout: Duration,
     io_timeout: Duration,
+    stall_timeout: Option<Duration>,
 }
 
 impl<A: Access> TimeoutAccessor<A> {
@@ -240,7 +260,11 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
 
         self.io_timeout(Operation::Read, self.inner.read(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| {
+                let r =
+                    TimeoutWrapper::new(r, self.io_timeout).with_stall_timeout(self.stall_timeout);
+                (rp, r)
+            })
     }
 
     async fn write(&self, path: &str, mut args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -253,7 +277,11 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
 
         self.io_timeout(Operation::Write, self.inner.write(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, w)| {
+                let w =
+                    TimeoutWrapper::new(w, self.io_timeout).with_stall_timeout(self.stall_timeout);
+                (rp, w)
+            })
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -329,11 +357,46 @@ pub struct TimeoutWrapper<R> {
     inner: R,
 
     timeout: Duration,
+    stall_timeout: Option<Duration>,
 }
 
 impl<R> TimeoutWrapper<R> {
     fn new(inner: R, timeout: Duration) -> Self {
-        Self { inner, timeout }
+        Self {
+            inner,
+            timeout,
+            stall_timeout: None,
+        }
+    }
+
+    fn with_stall_timeout(mut self, stall_timeout: Option<Duration>) -> Self {
+        self.stall_timeout = stall_timeout;
+        self
+    }
+
+    /// Apply stall timeout if set, otherwise fallback to io timeout.
+    #[inline]
+    async fn stall_timeout<F: Future<Output = Result<T>>, T>(
+        timeout: Duration,
+        stall_timeout: Option<Duration>,
+        op: &'static str,
+        fut: F,
+    ) -> Result<T> {
+        let Some(stall_timeout) = stall_timeout else {
+            return Self::io_timeout(timeout, op, fut).await;
+        };
+
+        tokio::time::timeout(stall_timeout, fut)
+            .await
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "io stalled, no bytes moved before timeout",
+                )
+                .with_operation(op)
+                .with_context("stall_timeout", stall_timeout.as_secs_f64().to_string())
+                .set_temporary()
+            })?
     }
 
     #[inline]
@@ -354,17 +417,29 @@ impl<R> TimeoutWrapper<R> {
 impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
         let fut = self.inner.read();
-        Self::io_timeout(self.timeout, Operation::ReaderRead.into_static(), fut).await
+        Self::stall_timeout(
+            self.timeout,
+            self.stall_timeout,
+            Operation::ReaderRead.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
 impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
         let fut = self.inner.write(bs);
-        Self::io_timeout(self.timeout, Operation::WriterWrite.into_static(), fut).await
+        Self::stall_timeout(
+            self.timeout,
+            self.stall_timeout,
+            Operation::WriterWrite.into_static(),
+            fut,
+        )
+        .await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let fut = self.inner.close();
         Self::io_timeout(self.timeout, Operation::WriterClose.into_static(), fut).await
     }
@@ -489,6 +564,30 @@ mod tests {
         assert!(err.to_string().contains("timeout"))
     }
 
+    #[tokio::test]
+    async fn test_stall_timeout() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_io_timeout(Duration::from_secs(60))
+                .with_stall_timeout(Duration::from_secs(1)),
+        );
+
+        let reader = op.reader("test").await.unwrap();
+
+        let fut = async {
+            let res = reader.read(0..4).await;
+            assert!(res.is_err());
+            let err = res.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Unexpected);
+            assert!(err.to_string().contains("stalled"))
+        };
+
+        timeout(Duration::from_secs(2), fut)
+            .await
+            .expect("this test should not exceed 2 seconds")
+    }
+
     #[tokio::test]
     async fn test_list_timeout() {
         let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
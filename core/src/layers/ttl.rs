@@ -0,0 +1,439 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Emulate object expiry for services without native lifecycle rules.
+///
+/// # TTL
+///
+/// TtlLayer stamps an expiry timestamp into the user metadata of every
+/// written object under the key [`TtlLayer::METADATA_KEY`]. Objects that have
+/// expired will be treated as not existing:
+///
+/// - `stat` and `read` return [`ErrorKind::NotFound`].
+/// - `list` skips them.
+///
+/// If gc is enabled by [`TtlLayer::with_gc`], expired objects will be deleted
+/// lazily while being accessed.
+///
+/// # Notes
+///
+/// - The underlying service must support `write_with_user_metadata`,
+///   otherwise writes will return [`ErrorKind::Unsupported`] instead of
+///   silently losing the expiry timestamp.
+/// - Users can override the expiry of a single object by setting
+///   [`TtlLayer::METADATA_KEY`] in user metadata while writing.
+/// - Objects without the metadata key never expire.
+/// - `list` will `stat` every file entry whose metadata doesn't carry user
+///   metadata, which could be slow on large directories.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// #
+/// # use opendal::layers::TtlLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(TtlLayer::new(Duration::from_secs(24 * 60 * 60)).with_gc(true))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TtlLayer {
+    ttl: Duration,
+    gc: bool,
+    clock: Clock,
+}
+
+impl TtlLayer {
+    /// The user metadata key that stores the RFC 3339 expiry timestamp.
+    pub const METADATA_KEY: &'static str = "opendal-expires-at";
+
+    /// Create a new `TtlLayer` with given time-to-live.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            gc: false,
+            clock: Clock::system(),
+        }
+    }
+
+    /// Delete expired objects while accessing them.
+    ///
+    /// Errors returned by the deletion are ignored.
+    pub fn with_gc(mut self, enabled: bool) -> Self {
+        self.gc = enabled;
+        self
+    }
+
+    /// Set the clock to stamp and check the expiry timestamp.
+    ///
+    /// Default to [`Clock::system`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for TtlLayer {
+    type LayeredAccess = TtlAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let user_metadata = inner.info().full_capability().write_with_user_metadata;
+        TtlAccessor {
+            inner: Arc::new(inner),
+            ttl: Arc::new(Ttl {
+                ttl: self.ttl,
+                gc: self.gc,
+                clock: self.clock.clone(),
+                user_metadata,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Ttl {
+    ttl: Duration,
+    gc: bool,
+    clock: Clock,
+    /// Whether the underlying service supports `write_with_user_metadata`.
+    user_metadata: bool,
+}
+
+impl Ttl {
+    /// Stamp the expiry timestamp into the write args.
+    ///
+    /// Returns [`ErrorKind::Unsupported`] if the underlying service can't
+    /// persist user metadata, since the object would never expire.
+    fn stamp(&self, op: Operation, path: &str, args: OpWrite) -> Result<OpWrite> {
+        if !self.user_metadata {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "TtlLayer requires write_with_user_metadata to store the expiry timestamp",
+            )
+            .with_operation(op)
+            .with_context("path", path));
+        }
+
+        let mut user_metadata = args.user_metadata().cloned().unwrap_or_default();
+        if !user_metadata.contains_key(TtlLayer::METADATA_KEY) {
+            let expires_at = chrono::Duration::from_std(self.ttl)
+                .ok()
+                .and_then(|d| self.clock.now().checked_add_signed(d))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+            user_metadata.insert(TtlLayer::METADATA_KEY.to_string(), expires_at.to_rfc3339());
+        }
+        Ok(args.with_user_metadata(user_metadata))
+    }
+
+    /// Get the expiry time of the object if it has been expired.
+    fn expired_at(&self, meta: &Metadata) -> Option<DateTime<Utc>> {
+        if meta.is_dir() {
+            return None;
+        }
+        meta.user_metadata()
+            .and_then(|m| m.get(TtlLayer::METADATA_KEY))
+            .and_then(|v| parse_datetime_from_rfc3339(v).ok())
+            .filter(|v| *v <= self.clock.now())
+    }
+}
+
+fn new_expired_error(op: Operation, path: &str, expired_at: DateTime<Utc>) -> Error {
+    Error::new(ErrorKind::NotFound, "object has expired")
+        .with_operation(op)
+        .with_context("path", path)
+        .with_context("expired_at", expired_at.to_rfc3339())
+}
+
+#[derive(Debug)]
+pub struct TtlAccessor<A: Access> {
+    inner: Arc<A>,
+    ttl: Arc<Ttl>,
+}
+
+impl<A: Access> TtlAccessor<A> {
+    /// Check whether the object has expired, return `NotFound` if so.
+    async fn check(&self, op: Operation, path: &str, meta: &Metadata) -> Result<()> {
+        let Some(expired_at) = self.ttl.expired_at(meta) else {
+            return Ok(());
+        };
+        if self.ttl.gc {
+            let _ = self.inner.delete(path, OpDelete::new()).await;
+        }
+        Err(new_expired_error(op, path, expired_at))
+    }
+
+    fn blocking_check(&self, op: Operation, path: &str, meta: &Metadata) -> Result<()> {
+        let Some(expired_at) = self.ttl.expired_at(meta) else {
+            return Ok(());
+        };
+        if self.ttl.gc {
+            let _ = self.inner.blocking_delete(path, OpDelete::new());
+        }
+        Err(new_expired_error(op, path, expired_at))
+    }
+}
+
+impl<A: Access> LayeredAccess for TtlAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = TtlLister<A, A::Lister>;
+    type BlockingLister = TtlLister<A, A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let rp = self.inner.stat(path, OpStat::new()).await?;
+        self.check(Operation::Read, path, rp.metadata()).await?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let args = self.ttl.stamp(Operation::Write, path, args)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.stat(path, args).await?;
+        self.check(Operation::Stat, path, rp.metadata()).await?;
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let (rp, lister) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            TtlLister::new(self.inner.clone(), self.ttl.clone(), lister),
+        ))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let rp = self.inner.blocking_stat(path, OpStat::new())?;
+        self.blocking_check(Operation::BlockingRead, path, rp.metadata())?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let args = self.ttl.stamp(Operation::BlockingWrite, path, args)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.blocking_stat(path, args)?;
+        self.blocking_check(Operation::BlockingStat, path, rp.metadata())?;
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let (rp, lister) = self.inner.blocking_list(path, args)?;
+        Ok((
+            rp,
+            TtlLister::new(self.inner.clone(), self.ttl.clone(), lister),
+        ))
+    }
+}
+
+pub struct TtlLister<A: Access, L> {
+    acc: Arc<A>,
+    ttl: Arc<Ttl>,
+    inner: L,
+}
+
+impl<A: Access, L> TtlLister<A, L> {
+    fn new(acc: Arc<A>, ttl: Arc<Ttl>, inner: L) -> Self {
+        Self { acc, ttl, inner }
+    }
+}
+
+impl<A: Access, L: oio::List> oio::List for TtlLister<A, L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next().await? {
+            if !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            // Listed entries may not carry user metadata, stat them instead.
+            let expired = if entry.metadata().user_metadata().is_some() {
+                self.ttl.expired_at(entry.metadata()).is_some()
+            } else {
+                match self.acc.stat(entry.path(), OpStat::new()).await {
+                    Ok(rp) => self.ttl.expired_at(rp.metadata()).is_some(),
+                    // The entry has been removed during listing.
+                    Err(err) if err.kind() == ErrorKind::NotFound => true,
+                    Err(err) => return Err(err),
+                }
+            };
+            if !expired {
+                return Ok(Some(entry));
+            }
+            if self.ttl.gc {
+                let _ = self.acc.delete(entry.path(), OpDelete::new()).await;
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<A: Access, L: oio::BlockingList> oio::BlockingList for TtlLister<A, L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next()? {
+            if !entry.mode().is_file() {
+                return Ok(Some(entry));
+            }
+
+            let expired = if entry.metadata().user_metadata().is_some() {
+                self.ttl.expired_at(entry.metadata()).is_some()
+            } else {
+                match self.acc.blocking_stat(entry.path(), OpStat::new()) {
+                    Ok(rp) => self.ttl.expired_at(rp.metadata()).is_some(),
+                    Err(err) if err.kind() == ErrorKind::NotFound => true,
+                    Err(err) => return Err(err),
+                }
+            };
+            if !expired {
+                return Ok(Some(entry));
+            }
+            if self.ttl.gc {
+                let _ = self.acc.blocking_delete(entry.path(), OpDelete::new());
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService;
+
+    impl Access for MockService {
+        type Reader = ();
+        type Writer = ();
+        type Lister = ();
+        type BlockingReader = ();
+        type BlockingWriter = ();
+        type BlockingLister = ();
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let mut am = AccessorInfo::default();
+            am.set_native_capability(Capability {
+                write: true,
+                ..Default::default()
+            });
+
+            am.into()
+        }
+
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((RpWrite::new(), ()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ttl() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(TtlLayer::new(Duration::from_secs(3600)).with_gc(true))
+            .finish();
+
+        op.write("dir/alive", "abcd")
+            .await
+            .expect("write must succeed");
+        op.write_with("dir/expired", "abcd")
+            .user_metadata([(
+                TtlLayer::METADATA_KEY.to_string(),
+                "2000-01-01T00:00:00Z".to_string(),
+            )])
+            .await
+            .expect("write must succeed");
+
+        let meta = op.stat("dir/alive").await.expect("stat must succeed");
+        assert!(meta
+            .user_metadata()
+            .is_some_and(|m| m.contains_key(TtlLayer::METADATA_KEY)));
+
+        let entries: Vec<_> = op
+            .lister("dir/")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        let paths: Vec<_> = entries
+            .iter()
+            .filter(|e| e.metadata().is_file())
+            .map(|e| e.path())
+            .collect();
+        assert_eq!(paths, vec!["dir/alive"]);
+
+        let err = op.read("dir/expired").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = op.stat("dir/expired").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_with_clock() {
+        let clock = Clock::fixed(Utc::now());
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(TtlLayer::new(Duration::from_secs(3600)).with_clock(clock.clone()))
+            .finish();
+
+        op.write("file", "abcd").await.expect("write must succeed");
+        op.stat("file").await.expect("stat must succeed");
+
+        clock.advance(Duration::from_secs(3600));
+        let err = op.stat("file").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_without_user_metadata() {
+        let op = OperatorBuilder::new(MockService)
+            .layer(TtlLayer::new(Duration::from_secs(3600)))
+            .finish();
+
+        let err = op.write("file", "abcd").await.expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+}
@@ -136,6 +136,7 @@ pub use types::*;
 #[cfg(docsrs)]
 pub mod docs;
 pub mod layers;
+pub mod maintenance;
 pub mod raw;
 pub mod services;
 
@@ -0,0 +1,233 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::raw::*;
+use crate::*;
+
+/// The dir under the compacted prefix to store packfiles and the manifest.
+const COMPACT_DIR: &str = ".compact/";
+
+/// The index of objects that have been packed under a prefix.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    /// Packed objects keyed by their original paths.
+    pub(crate) objects: BTreeMap<String, PackedObject>,
+}
+
+/// The location of an object inside a packfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PackedObject {
+    pub(crate) pack: String,
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+}
+
+impl Manifest {
+    /// The path of the manifest of given prefix.
+    pub(crate) fn path(prefix: &str) -> String {
+        format!("{prefix}{COMPACT_DIR}manifest.json")
+    }
+
+    /// Check if the path is a packfile or manifest.
+    pub(crate) fn is_internal(prefix: &str, path: &str) -> bool {
+        path.strip_prefix(prefix)
+            .is_some_and(|p| p.starts_with(COMPACT_DIR))
+    }
+
+    pub(crate) fn from_slice(bs: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bs).map_err(new_json_deserialize_error)
+    }
+
+    pub(crate) fn to_vec(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(new_json_serialize_error)
+    }
+}
+
+/// The summary of a [`compact`] job.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CompactSummary {
+    /// The number of objects that have been packed.
+    pub objects: usize,
+    /// The number of packfiles that have been written.
+    pub packs: usize,
+    /// The bytes that have been packed.
+    pub bytes: u64,
+}
+
+/// Compact small objects under `prefix` into packfiles of `target_size`.
+///
+/// Objects smaller than `target_size` are concatenated into packfiles under
+/// `{prefix}.compact/`, and their locations are recorded in the manifest
+/// `{prefix}.compact/manifest.json`. The original objects are deleted after
+/// the manifest has been written.
+///
+/// Use [`CompactLayer`](crate::layers::CompactLayer) to read the packed
+/// objects back by their original paths.
+///
+/// # Notes
+///
+/// - Only one compact job should run on the same prefix at the same time.
+/// - Objects written while compacting may be deleted, pause writers or
+///   compact prefixes that are no longer written, like the dirs of past days.
+/// - Packfiles are never rewritten, the bytes of packed objects that are
+///   overwritten or deleted later are not reclaimed.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::maintenance;
+/// use opendal::Operator;
+///
+/// # async fn test(op: Operator) -> Result<()> {
+/// let summary = maintenance::compact(&op, "logs/2024-01-01/", 64 * 1024 * 1024).await?;
+/// println!("packed {} objects", summary.objects);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn compact(op: &Operator, prefix: &str, target_size: u64) -> Result<CompactSummary> {
+    if !prefix.ends_with('/') {
+        return Err(
+            Error::new(ErrorKind::NotADirectory, "prefix to compact must be a dir")
+                .with_operation("maintenance::compact")
+                .with_context("prefix", prefix),
+        );
+    }
+    if target_size == 0 {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "target size of packfiles must be larger than 0",
+        )
+        .with_operation("maintenance::compact")
+        .with_context("prefix", prefix));
+    }
+
+    let manifest_path = Manifest::path(prefix);
+    let mut manifest = match op.read(&manifest_path).await {
+        Ok(bs) => Manifest::from_slice(&bs.to_bytes())?,
+        Err(err) if err.kind() == ErrorKind::NotFound => Manifest::default(),
+        Err(err) => return Err(err),
+    };
+
+    let mut paths: Vec<String> = op
+        .lister_with(prefix)
+        .recursive(true)
+        .await?
+        .try_filter(|entry| {
+            let is_candidate = entry.metadata().is_file()
+                && !Manifest::is_internal(prefix, entry.path())
+                && entry.metadata().content_length() < target_size;
+            futures::future::ready(is_candidate)
+        })
+        .map_ok(|entry| entry.path().to_string())
+        .try_collect()
+        .await?;
+    paths.sort_unstable();
+
+    let mut summary = CompactSummary::default();
+    let mut packed = Vec::new();
+    let mut pack = Pack::default();
+    for path in paths {
+        let bs = op.read(&path).await?;
+        // Content length is not always returned by list.
+        if bs.len() as u64 >= target_size {
+            continue;
+        }
+        pack.push(path, bs);
+
+        if pack.size >= target_size {
+            summary.packs += 1;
+            packed.extend(pack.flush(op, prefix, &mut manifest).await?);
+        }
+    }
+    // Packing a single object doesn't reduce the number of objects.
+    if pack.objects.len() > 1 {
+        summary.packs += 1;
+        packed.extend(pack.flush(op, prefix, &mut manifest).await?);
+    }
+    if packed.is_empty() {
+        return Ok(summary);
+    }
+
+    op.write(&manifest_path, manifest.to_vec()?).await?;
+
+    summary.objects = packed.len();
+    summary.bytes = packed
+        .iter()
+        .filter_map(|path| manifest.objects.get(path))
+        .map(|v| v.size)
+        .sum();
+    op.remove(packed).await?;
+    Ok(summary)
+}
+
+/// The objects to be written into the next packfile.
+#[derive(Default)]
+struct Pack {
+    objects: Vec<(String, u64)>,
+    parts: Vec<Bytes>,
+    size: u64,
+}
+
+impl Pack {
+    fn push(&mut self, path: String, bs: Buffer) {
+        let size = bs.len() as u64;
+        self.objects.push((path, size));
+        self.parts.extend(bs);
+        self.size += size;
+    }
+
+    /// Write the packfile and record its objects in manifest.
+    ///
+    /// Returns the paths of the packed objects.
+    async fn flush(
+        &mut self,
+        op: &Operator,
+        prefix: &str,
+        manifest: &mut Manifest,
+    ) -> Result<Vec<String>> {
+        let pack = format!("{prefix}{COMPACT_DIR}pack-{}", Uuid::new_v4());
+        op.write(&pack, Buffer::from(std::mem::take(&mut self.parts)))
+            .await?;
+
+        let mut offset = 0;
+        let mut paths = Vec::with_capacity(self.objects.len());
+        for (path, size) in self.objects.drain(..) {
+            manifest.objects.insert(
+                path.clone(),
+                PackedObject {
+                    pack: pack.clone(),
+                    offset,
+                    size,
+                },
+            );
+            offset += size;
+            paths.push(path);
+        }
+        self.size = 0;
+        Ok(paths)
+    }
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::future::ready;
 use std::sync::Arc;
@@ -278,6 +279,60 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         )))
     }
 
+    /// Invoke the `sign` operation on a user-built request.
+    ///
+    /// Require [`Capability::sign`]
+    ///
+    /// # Behavior
+    ///
+    /// - The request MUST be signed with the same credential used by the service.
+    /// - The request MUST NOT be sent.
+    fn sign(&self, args: OpSign) -> impl Future<Output = Result<RpSign>> + MaybeSend {
+        let _ = args;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `list_multipart_uploads` operation on the specified prefix.
+    ///
+    /// Require [`Capability::list_multipart_uploads`]
+    ///
+    /// # Behavior
+    ///
+    /// - All in-progress uploads under the prefix MUST be returned.
+    /// - Paths of returned uploads MUST be relative to the root.
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `abort_multipart_upload` operation on the specified path.
+    ///
+    /// Require [`Capability::abort_multipart_upload`]
+    fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> impl Future<Output = Result<RpAbortMultipartUpload>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
     /// Invoke the `blocking_create` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::create_dir`]
@@ -451,6 +506,20 @@ pub trait AccessDyn: Send + Sync + Debug + Unpin {
     ) -> BoxedFuture<'a, Result<RpPresign>>;
     /// Dyn version of [`Accessor::batch`]
     fn batch_dyn(&self, args: OpBatch) -> BoxedFuture<'_, Result<RpBatch>>;
+    /// Dyn version of [`Accessor::sign`]
+    fn sign_dyn(&self, args: OpSign) -> BoxedFuture<'_, Result<RpSign>>;
+    /// Dyn version of [`Accessor::list_multipart_uploads`]
+    fn list_multipart_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListMultipartUploads,
+    ) -> BoxedFuture<'a, Result<RpListMultipartUploads>>;
+    /// Dyn version of [`Accessor::abort_multipart_upload`]
+    fn abort_multipart_upload_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipartUpload,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipartUpload>>;
     /// Dyn version of [`Accessor::blocking_create_dir`]
     fn blocking_create_dir_dyn(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir>;
     /// Dyn version of [`Accessor::blocking_stat`]
@@ -562,6 +631,26 @@ where
         Box::pin(self.batch(args))
     }
 
+    fn sign_dyn(&self, args: OpSign) -> BoxedFuture<'_, Result<RpSign>> {
+        Box::pin(self.sign(args))
+    }
+
+    fn list_multipart_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListMultipartUploads,
+    ) -> BoxedFuture<'a, Result<RpListMultipartUploads>> {
+        Box::pin(self.list_multipart_uploads(path, args))
+    }
+
+    fn abort_multipart_upload_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipartUpload,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipartUpload>> {
+        Box::pin(self.abort_multipart_upload(path, args))
+    }
+
     fn blocking_create_dir_dyn(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.blocking_create_dir(path, args)
     }
@@ -651,6 +740,26 @@ impl Access for dyn AccessDyn {
         self.batch_dyn(args)
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        self.sign_dyn(args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        self.list_multipart_uploads_dyn(path, args).await
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        self.abort_multipart_upload_dyn(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.blocking_create_dir_dyn(path, args)
     }
@@ -700,6 +809,9 @@ impl Access for () {
             name: "dummy".to_string(),
             native_capability: Capability::default(),
             full_capability: Capability::default(),
+            config: None,
+            #[cfg(feature = "extensions")]
+            extension: None,
         }
         .into()
     }
@@ -796,6 +908,26 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         async move { self.as_ref().batch(args).await }
     }
 
+    fn sign(&self, args: OpSign) -> impl Future<Output = Result<RpSign>> + MaybeSend {
+        async move { self.as_ref().sign(args).await }
+    }
+
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        async move { self.as_ref().list_multipart_uploads(path, args).await }
+    }
+
+    fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> impl Future<Output = Result<RpAbortMultipartUpload>> + MaybeSend {
+        async move { self.as_ref().abort_multipart_upload(path, args).await }
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().blocking_create_dir(path, args)
     }
@@ -841,6 +973,11 @@ pub struct AccessorInfo {
 
     native_capability: Capability,
     full_capability: Capability,
+
+    config: Option<Arc<BTreeMap<String, String>>>,
+
+    #[cfg(feature = "extensions")]
+    extension: Option<Arc<dyn std::any::Any + Send + Sync>>,
 }
 
 impl AccessorInfo {
@@ -910,4 +1047,32 @@ impl AccessorInfo {
     pub fn full_capability_mut(&mut self) -> &mut Capability {
         &mut self.full_capability
     }
+
+    /// Get the configuration of service with secrets redacted.
+    ///
+    /// Returns `None` if the service is not built from a configuration.
+    pub fn config(&self) -> Option<&BTreeMap<String, String>> {
+        self.config.as_deref()
+    }
+
+    /// Set the configuration of service, secrets must have been redacted.
+    pub fn set_config(&mut self, config: BTreeMap<String, String>) -> &mut Self {
+        self.config = Some(Arc::new(config));
+        self
+    }
+
+    /// Get the provider-specific extension of service.
+    ///
+    /// Returns `None` if service doesn't provide an extension of type `T`.
+    #[cfg(feature = "extensions")]
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extension.as_ref().and_then(|v| v.downcast_ref::<T>())
+    }
+
+    /// Set the provider-specific extension of service.
+    #[cfg(feature = "extensions")]
+    pub fn set_extension<T: Send + Sync + 'static>(&mut self, extension: T) -> &mut Self {
+        self.extension = Some(Arc::new(extension));
+        self
+    }
 }
@@ -18,6 +18,8 @@
 use std::sync::Arc;
 use std::vec::IntoIter;
 
+use chrono::Utc;
+
 use super::Adapter;
 use super::Value;
 use crate::raw::oio::HierarchyLister;
@@ -76,6 +78,7 @@ impl<S: Adapter> Access for Backend<S> {
         if kv_cap.set {
             cap.write = true;
             cap.write_can_empty = true;
+            cap.write_with_user_metadata = true;
         }
 
         if kv_cap.delete {
@@ -262,6 +265,7 @@ impl<S> KvWriter<S> {
 
         let mut metadata = Metadata::new(EntryMode::FILE);
         metadata.set_content_length(value.len() as u64);
+        metadata.set_last_modified(Utc::now());
 
         if let Some(v) = self.op.cache_control() {
             metadata.set_cache_control(v);
@@ -272,6 +276,9 @@ impl<S> KvWriter<S> {
         if let Some(v) = self.op.content_type() {
             metadata.set_content_type(v);
         }
+        if let Some(v) = self.op.user_metadata() {
+            metadata.with_user_metadata(v.clone());
+        }
 
         Value { metadata, value }
     }
@@ -285,7 +292,7 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let value = match &self.value {
             Some(value) => value.clone(),
             None => {
@@ -294,8 +301,9 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
                 value
             }
         };
+        let meta = value.metadata.clone();
         self.kv.set(&self.path, value).await?;
-        Ok(())
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -312,7 +320,7 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let kv = self.kv.clone();
         let value = match &self.value {
             Some(value) => value.clone(),
@@ -323,7 +331,8 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
             }
         };
 
+        let meta = value.metadata.clone();
         kv.blocking_set(&self.path, value)?;
-        Ok(())
+        Ok(meta)
     }
 }
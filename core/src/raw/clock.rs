@@ -0,0 +1,201 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use http::header::DATE;
+use http::HeaderMap;
+
+use super::parse_datetime_from_rfc2822;
+
+/// The skew less than this is ignored, the `Date` header is only precise to
+/// seconds.
+const MIN_SKEW_MILLIS: i64 = 2000;
+
+/// Clock provides the current time for signing requests and checking
+/// expirations.
+///
+/// - [`Clock::system`] returns the system time, which is the default.
+/// - [`Clock::fixed`] returns a fixed time that only moves by
+///   [`Clock::advance`], which is useful for deterministic tests.
+/// - [`Clock::skew_corrected`] returns the system time corrected by the
+///   `Date` headers of responses, which fixes signature failures like
+///   `RequestTimeTooSkewed` on machines with clock skew.
+///
+/// Clones of a clock share the same state.
+#[derive(Clone, Default)]
+pub struct Clock {
+    inner: Arc<ClockInner>,
+}
+
+#[derive(Default)]
+struct ClockInner {
+    fixed: Option<DateTime<Utc>>,
+    correct_skew: bool,
+    /// The offset added to the base time in milliseconds.
+    offset: AtomicI64,
+}
+
+impl Debug for Clock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clock")
+            .field("fixed", &self.inner.fixed)
+            .field("correct_skew", &self.inner.correct_skew)
+            .field("offset", &self.offset())
+            .finish()
+    }
+}
+
+impl Clock {
+    /// Create a clock that returns the system time.
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// Create a clock that always returns `now` until being advanced.
+    pub fn fixed(now: DateTime<Utc>) -> Self {
+        Self {
+            inner: Arc::new(ClockInner {
+                fixed: Some(now),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Create a clock that returns the system time corrected by the `Date`
+    /// headers observed via [`Clock::observe_date`].
+    pub fn skew_corrected() -> Self {
+        Self {
+            inner: Arc::new(ClockInner {
+                correct_skew: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Get the current time of this clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        let base = self.inner.fixed.unwrap_or_else(Utc::now);
+        base + self.offset()
+    }
+
+    /// Check if this clock differs from the system time, either fixed or
+    /// corrected by an offset.
+    pub fn is_adjusted(&self) -> bool {
+        self.inner.fixed.is_some() || self.offset() != chrono::Duration::zero()
+    }
+
+    /// Get the offset that has been applied to the base time.
+    pub fn offset(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.inner.offset.load(Ordering::Relaxed))
+    }
+
+    /// Move this clock forward by `d`.
+    pub fn advance(&self, d: Duration) {
+        let millis = i64::try_from(d.as_millis()).unwrap_or(i64::MAX);
+        self.inner.offset.fetch_add(millis, Ordering::Relaxed);
+    }
+
+    /// Correct the skew against the `Date` header of a response.
+    ///
+    /// Only skew-corrected clocks are affected, it's a no-op for others.
+    pub fn observe_date(&self, headers: &HeaderMap) {
+        if !self.inner.correct_skew {
+            return;
+        }
+        let Some(date) = headers
+            .get(DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_datetime_from_rfc2822(v).ok())
+        else {
+            return;
+        };
+
+        let skew = (date - Utc::now()).num_milliseconds();
+        let offset = if skew.abs() < MIN_SKEW_MILLIS {
+            0
+        } else {
+            skew
+        };
+        let current = self.inner.offset.load(Ordering::Relaxed);
+        if (offset - current).abs() >= MIN_SKEW_MILLIS || (offset == 0 && current != 0) {
+            self.inner.offset.store(offset, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock() {
+        let now = parse_datetime_from_rfc2822("Sat, 01 Jan 2000 00:00:00 GMT").unwrap();
+        let clock = Clock::fixed(now);
+        assert_eq!(clock.now(), now);
+
+        clock.clone().advance(Duration::from_secs(60));
+        assert_eq!(clock.now(), now + chrono::Duration::seconds(60));
+
+        // Fixed clocks are not corrected.
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Mon, 01 Jan 2024 00:00:00 GMT"),
+        );
+        clock.observe_date(&headers);
+        assert_eq!(clock.now(), now + chrono::Duration::seconds(60));
+    }
+
+    #[test]
+    fn test_skew_corrected_clock() {
+        let clock = Clock::skew_corrected();
+        assert!(!clock.is_adjusted());
+
+        let server = Utc::now() + chrono::Duration::hours(1);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            DATE,
+            HeaderValue::from_str(&format_http_date(server)).unwrap(),
+        );
+        clock.observe_date(&headers);
+        assert!(clock.is_adjusted());
+        let skew = clock.offset() - chrono::Duration::hours(1);
+        assert!(skew.num_seconds().abs() <= 1, "offset: {}", clock.offset());
+
+        // Small skew resets the offset.
+        headers.insert(
+            DATE,
+            HeaderValue::from_str(&format_http_date(Utc::now())).unwrap(),
+        );
+        clock.observe_date(&headers);
+        assert_eq!(clock.offset(), chrono::Duration::zero());
+    }
+
+    fn format_http_date(t: DateTime<Utc>) -> String {
+        t.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    }
+}
@@ -163,6 +163,13 @@ impl FromStr for BytesContentRange {
         }
         let start: u64 = v[0].parse().map_err(parse_int_error)?;
         let end: u64 = v[1].parse().map_err(parse_int_error)?;
+        if end < start {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "header content range is invalid")
+                    .with_operation("BytesContentRange::from_str")
+                    .with_context("value", value),
+            );
+        }
         let mut bcr = BytesContentRange::default().with_range(start, end);
 
         // Handle size part first.
@@ -206,6 +213,8 @@ mod tests {
             assert_eq!(expected, actual, "{name}")
         }
 
+        assert!("bytes 124-123/1024".parse::<BytesContentRange>().is_err());
+
         Ok(())
     }
 
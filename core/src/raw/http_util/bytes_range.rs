@@ -71,6 +71,38 @@ impl BytesRange {
         self.1
     }
 
+    /// Create a new `BytesRange` from given range bounds.
+    ///
+    /// Returns [`ErrorKind::InvalidInput`] if the end of the range is before the start.
+    pub fn try_from_bounds(range: impl RangeBounds<u64>) -> Result<Self> {
+        let offset = match range.start_bound().cloned() {
+            Bound::Included(n) => n,
+            Bound::Excluded(n) => n.checked_add(1).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidInput, "range start overflows")
+                    .with_operation("BytesRange::try_from_bounds")
+                    .with_context("start", n)
+            })?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound().cloned() {
+            // An inclusive end of `u64::MAX` covers all the remaining content.
+            Bound::Included(n) => n.checked_add(1),
+            Bound::Excluded(n) => Some(n),
+            Bound::Unbounded => None,
+        };
+        let size = match end {
+            Some(end) => Some(end.checked_sub(offset).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidInput, "range end is before start")
+                    .with_operation("BytesRange::try_from_bounds")
+                    .with_context("start", offset)
+                    .with_context("end", end)
+            })?),
+            None => None,
+        };
+
+        Ok(BytesRange(offset, size))
+    }
+
     /// Advance the range by `n` bytes.
     ///
     /// # Panics
@@ -173,6 +205,11 @@ impl FromStr for BytesRange {
             // <range-start>-<range-end>
             let start: u64 = v[0].parse().map_err(parse_int_error)?;
             let end: u64 = v[1].parse().map_err(parse_int_error)?;
+            if end < start {
+                return Err(Error::new(ErrorKind::Unexpected, "header range is invalid")
+                    .with_operation("BytesRange::from_str")
+                    .with_context("value", value));
+            }
             Ok(BytesRange::new(start, Some(end - start + 1)))
         }
     }
@@ -182,15 +219,19 @@ impl<T> From<T> for BytesRange
 where
     T: RangeBounds<u64>,
 {
+    /// Convert range bounds into `BytesRange`.
+    ///
+    /// A range whose end is before the start will be clamped into an empty
+    /// range, use [`BytesRange::try_from_bounds`] to reject it instead.
     fn from(range: T) -> Self {
         let offset = match range.start_bound().cloned() {
             Bound::Included(n) => n,
-            Bound::Excluded(n) => n + 1,
+            Bound::Excluded(n) => n.saturating_add(1),
             Bound::Unbounded => 0,
         };
         let size = match range.end_bound().cloned() {
-            Bound::Included(n) => Some(n + 1 - offset),
-            Bound::Excluded(n) => Some(n - offset),
+            Bound::Included(n) => n.checked_add(1).map(|end| end.saturating_sub(offset)),
+            Bound::Excluded(n) => Some(n.saturating_sub(offset)),
             Bound::Unbounded => None,
         };
 
@@ -257,4 +298,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_bytes_range_invalid() {
+        let err = BytesRange::try_from_bounds(20..10).expect_err("range must be invalid");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(BytesRange::from(20..10), BytesRange::new(20, Some(0)));
+        assert!("bytes=20-10".parse::<BytesRange>().is_err());
+
+        let err = BytesRange::try_from_bounds((Bound::Excluded(u64::MAX), Bound::Unbounded))
+            .expect_err("range must be invalid");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_bytes_range_overflow() {
+        assert_eq!(
+            BytesRange::try_from_bounds(10..=u64::MAX).unwrap(),
+            BytesRange::new(10, None)
+        );
+        assert_eq!(BytesRange::from(10..=u64::MAX), BytesRange::new(10, None));
+        assert_eq!(
+            BytesRange::from((Bound::Excluded(u64::MAX), Bound::Unbounded)),
+            BytesRange::new(u64::MAX, None)
+        );
+    }
 }
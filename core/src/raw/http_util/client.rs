@@ -25,6 +25,7 @@ use std::sync::Arc;
 
 use futures::Future;
 use futures::TryStreamExt;
+use http::HeaderMap;
 use http::Request;
 use http::Response;
 use once_cell::sync::Lazy;
@@ -49,6 +50,7 @@ pub type HttpFetcher = Arc<dyn HttpFetchDyn>;
 #[derive(Clone)]
 pub struct HttpClient {
     fetcher: HttpFetcher,
+    default_headers: Option<Arc<HeaderMap>>,
 }
 
 /// We don't want users to know details about our clients.
@@ -62,13 +64,19 @@ impl HttpClient {
     /// Create a new http client in async context.
     pub fn new() -> Result<Self> {
         let fetcher = Arc::new(reqwest::Client::new());
-        Ok(Self { fetcher })
+        Ok(Self {
+            fetcher,
+            default_headers: None,
+        })
     }
 
     /// Construct `Self` with given [`reqwest::Client`]
     pub fn with(client: impl HttpFetch) -> Self {
         let fetcher = Arc::new(client);
-        Self { fetcher }
+        Self {
+            fetcher,
+            default_headers: None,
+        }
     }
 
     /// Build a new http client in async context.
@@ -78,7 +86,26 @@ impl HttpClient {
             Error::new(ErrorKind::Unexpected, "http client build failed").set_source(err)
         })?;
         let fetcher = Arc::new(client);
-        Ok(Self { fetcher })
+        Ok(Self {
+            fetcher,
+            default_headers: None,
+        })
+    }
+
+    /// Attach given headers to every request sent by this client.
+    ///
+    /// This is useful for headers required by gateways or for cost
+    /// allocation, without changing the services themselves. Headers that
+    /// have already been set by services will not be overwritten.
+    ///
+    /// # Notes
+    ///
+    /// Headers are attached after the request has been signed, so they are
+    /// not part of the signature. Services like s3 require all `x-amz-*`
+    /// headers to be signed, please don't inject them here.
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = Some(Arc::new(headers));
+        self
     }
 
     /// Send a request in async way.
@@ -89,7 +116,14 @@ impl HttpClient {
     }
 
     /// Fetch a request in async way.
-    pub async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+    pub async fn fetch(&self, mut req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        if let Some(headers) = &self.default_headers {
+            for (k, v) in headers.iter() {
+                if !req.headers().contains_key(k) {
+                    req.headers_mut().insert(k.clone(), v.clone());
+                }
+            }
+        }
         self.fetcher.fetch(req).await
     }
 }
@@ -221,3 +255,46 @@ fn is_temporary_error(err: &reqwest::Error) -> bool {
     // error decoding response body, for example, connection reset.
     err.is_decode()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct MockFetcher {
+        headers: Arc<Mutex<Option<HeaderMap>>>,
+    }
+
+    impl HttpFetch for MockFetcher {
+        async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+            *self.headers.lock().unwrap() = Some(req.headers().clone());
+            Ok(Response::new(HttpBody::new(
+                futures::stream::empty::<Result<Buffer>>(),
+                Some(0),
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_headers() {
+        let fetcher = MockFetcher::default();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-gateway-auth", HeaderValue::from_static("token"));
+        headers.insert("x-team", HeaderValue::from_static("default"));
+        let client = HttpClient::with(fetcher.clone()).with_default_headers(headers);
+
+        let req = Request::get("http://127.0.0.1/test")
+            .header("x-team", "storage")
+            .body(Buffer::new())
+            .unwrap();
+        client.send(req).await.expect("send must succeed");
+
+        let got = fetcher.headers.lock().unwrap().take().unwrap();
+        assert_eq!(got.get("x-gateway-auth").unwrap(), "token");
+        assert_eq!(got.get("x-team").unwrap(), "storage");
+    }
+}
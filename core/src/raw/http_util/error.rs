@@ -46,17 +46,45 @@ pub fn new_request_sign_error(err: anyhow::Error) -> Error {
         .set_source(err)
 }
 
+/// Headers used by services to carry the id of a request.
+const REQUEST_ID_HEADERS: &[&str] = &[
+    "x-amz-request-id",
+    "x-ms-request-id",
+    "x-oss-request-id",
+    "x-cos-request-id",
+    "x-obs-request-id",
+    "x-guploader-uploadid",
+    "x-request-id",
+];
+
 /// Add response context to error.
 ///
 /// This helper function will:
 ///
 /// - remove sensitive or useless headers from parts.
 /// - fetch uri if parts extensions contains `Uri`.
+/// - record the status code and the request id returned by services.
+/// - record `Retry-After` returned by services as `retry_after`.
 pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
     if let Some(uri) = parts.extensions.get::<Uri>() {
         err = err.with_context("uri", uri.to_string());
     }
 
+    err = err.with_context("status_code", parts.status.as_u16());
+    if let Some(request_id) = REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|k| parts.headers.get(*k).and_then(|v| v.to_str().ok()))
+    {
+        err = err.with_context("request_id", request_id);
+    }
+    if let Some(retry_after) = parts
+        .headers
+        .get(http::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+    {
+        err = err.with_context("retry_after", retry_after);
+    }
+
     // The following headers may contains sensitive information.
     parts.headers.remove("Set-Cookie");
     parts.headers.remove("WWW-Authenticate");
@@ -188,6 +188,9 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
         m.set_content_disposition(v);
     }
 
+    #[cfg(feature = "raw-headers")]
+    m.set_raw_headers(headers.clone());
+
     Ok(m)
 }
 
@@ -324,4 +327,15 @@ mod tests {
             assert_eq!(actual, expected)
         }
     }
+
+    #[cfg(feature = "raw-headers")]
+    #[test]
+    fn test_parse_into_metadata_raw_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amz-replication-status", "COMPLETED".parse().unwrap());
+
+        let meta = parse_into_metadata("a.txt", &headers).expect("parse must succeed");
+        let raw = meta.raw_headers().expect("raw headers must be kept");
+        assert_eq!(raw.get("x-amz-replication-status").unwrap(), "COMPLETED");
+    }
 }
@@ -48,6 +48,22 @@ pub fn percent_encode_path(path: &str) -> String {
     utf8_percent_encode(path, &PATH_ENCODE_SET).to_string()
 }
 
+/// QUERY_ENCODE_SET is the encode set for http url query component.
+///
+/// This set encodes all characters except unreserved characters `A-Z a-z 0-9 - _ . ~`
+/// defined in [RFC 3986](https://datatracker.ietf.org/doc/html/rfc3986#section-2.3),
+/// so that `&`, `=`, `+` and `/` in values won't be mixed up with the query.
+static QUERY_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// percent_encode_query will do percent encoding for a http query key or value.
+pub fn percent_encode_query(v: &str) -> String {
+    utf8_percent_encode(v, &QUERY_ENCODE_SET).to_string()
+}
+
 /// percent_decode_path will do percent decoding for http decode path.
 ///
 /// If the input is not percent encoded or not valid utf8, return the input.
@@ -91,6 +107,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_percent_encode_query() {
+        let cases = vec![
+            (
+                "Reserved Characters",
+                ";,/?:@&=+$",
+                "%3B%2C%2F%3F%3A%40%26%3D%2B%24",
+            ),
+            ("Unescaped Characters", "-_.~", "-_.~"),
+            ("Quoted Value", "\"a=b&c\"", "%22a%3Db%26c%22"),
+            (
+                "Alphanumeric Characters + Space",
+                "ABC abc 123",
+                "ABC%20abc%20123",
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            let actual = percent_encode_query(input);
+
+            assert_eq!(actual, expected, "{name}");
+        }
+    }
+
     #[test]
     fn test_percent_decode_path() {
         let cases = vec![
@@ -203,6 +203,26 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         self.inner().batch(args)
     }
 
+    fn sign(&self, args: OpSign) -> impl Future<Output = Result<RpSign>> + MaybeSend {
+        self.inner().sign(args)
+    }
+
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        self.inner().list_multipart_uploads(path, args)
+    }
+
+    fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> impl Future<Output = Result<RpAbortMultipartUpload>> + MaybeSend {
+        self.inner().abort_multipart_upload(path, args)
+    }
+
     fn presign(
         &self,
         path: &str,
@@ -286,6 +306,26 @@ impl<L: LayeredAccess> Access for L {
         LayeredAccess::batch(self, args).await
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        LayeredAccess::sign(self, args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        LayeredAccess::list_multipart_uploads(self, path, args).await
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        LayeredAccess::abort_multipart_upload(self, path, args).await
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         LayeredAccess::presign(self, path, args).await
     }
@@ -61,6 +61,9 @@ pub use serde_util::*;
 mod chrono_util;
 pub use chrono_util::*;
 
+mod clock;
+pub use clock::Clock;
+
 #[cfg(feature = "internal-tokio-rt")]
 mod tokio_util;
 #[cfg(feature = "internal-tokio-rt")]
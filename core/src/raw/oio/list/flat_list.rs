@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
+
 use crate::raw::*;
 use crate::*;
 
@@ -54,11 +56,28 @@ use crate::*;
 /// Especially, for storage services that can't return dirs first, ToFlatLister
 /// may output parent dirs' files before nested dirs, this is expected because files
 /// always output directly while listing.
+///
+/// Dirs that have been walked will be handled by the [`ListCyclePolicy`].
 pub struct FlatLister<A: Access, L> {
     acc: A,
 
     next_dir: Option<oio::Entry>,
     active_lister: Vec<(Option<oio::Entry>, L)>,
+
+    policy: ListCyclePolicy,
+    /// The file ids of dirs that have been walked.
+    ///
+    /// Only dirs with file id are tracked, so the memory is bounded by the
+    /// number of dirs instead of entries.
+    walked: HashSet<String>,
+}
+
+/// The action to take on an entry returned by the active lister.
+enum Visit {
+    /// Walk into this dir.
+    Walk,
+    /// Return this entry directly.
+    Return,
 }
 
 /// # Safety
@@ -80,6 +99,40 @@ where
             acc,
             next_dir: Some(oio::Entry::new(path, Metadata::new(EntryMode::DIR))),
             active_lister: vec![],
+
+            policy: ListCyclePolicy::default(),
+            walked: HashSet::new(),
+        }
+    }
+
+    /// Set the policy to handle dirs that have been walked.
+    pub fn with_cycle_policy(mut self, policy: ListCyclePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn visit(&mut self, entry: &oio::Entry) -> Result<Visit> {
+        if !entry.mode().is_dir() {
+            return Ok(Visit::Return);
+        }
+        if self.policy == ListCyclePolicy::Follow {
+            return Ok(Visit::Walk);
+        }
+
+        let Some(file_id) = entry.metadata().file_id() else {
+            return Ok(Visit::Walk);
+        };
+        if self.walked.insert(file_id.to_string()) {
+            return Ok(Visit::Walk);
+        }
+        match self.policy {
+            ListCyclePolicy::Error => Err(Error::new(
+                ErrorKind::Unexpected,
+                "dir has been walked, cycle detected during recursive list",
+            )
+            .with_context("path", entry.path())
+            .with_context("file_id", file_id)),
+            _ => Ok(Visit::Return),
         }
     }
 }
@@ -102,14 +155,14 @@ where
             };
 
             match lister.next().await? {
-                Some(v) if v.mode().is_dir() => {
-                    // should not loop itself again
-                    if v.path() != de.as_ref().expect("de should not be none here").path() {
-                        self.next_dir = Some(v);
-                        continue;
-                    }
-                }
-                Some(v) => return Ok(Some(v)),
+                // should not loop itself again
+                Some(v)
+                    if v.mode().is_dir()
+                        && v.path() == de.as_ref().expect("de should not be none here").path() => {}
+                Some(v) => match self.visit(&v)? {
+                    Visit::Walk => self.next_dir = Some(v),
+                    Visit::Return => return Ok(Some(v)),
+                },
                 None => match de.take() {
                     Some(de) => {
                         return Ok(Some(de));
@@ -143,13 +196,13 @@ where
             };
 
             match lister.next()? {
-                Some(v) if v.mode().is_dir() => {
-                    if v.path() != de.as_ref().expect("de should not be none here").path() {
-                        self.next_dir = Some(v);
-                        continue;
-                    }
-                }
-                Some(v) => return Ok(Some(v)),
+                Some(v)
+                    if v.mode().is_dir()
+                        && v.path() == de.as_ref().expect("de should not be none here").path() => {}
+                Some(v) => match self.visit(&v)? {
+                    Visit::Walk => self.next_dir = Some(v),
+                    Visit::Return => return Ok(Some(v)),
+                },
                 None => match de.take() {
                     Some(de) => {
                         return Ok(Some(de));
@@ -179,6 +232,7 @@ mod tests {
     #[derive(Debug)]
     struct MockService {
         map: HashMap<&'static str, Vec<&'static str>>,
+        file_ids: HashMap<&'static str, &'static str>,
     }
 
     impl MockService {
@@ -188,7 +242,20 @@ mod tests {
             map.insert("x/x/", vec!["x/x/x/"]);
             map.insert("x/x/x/", vec!["x/x/x/x"]);
 
-            Self { map }
+            Self {
+                map,
+                file_ids: HashMap::default(),
+            }
+        }
+
+        /// `x/y/link/` is a link to `x/y/`.
+        fn new_with_cycle() -> Self {
+            let mut map = HashMap::default();
+            map.insert("x/", vec!["x/y/"]);
+            map.insert("x/y/", vec!["x/y/a", "x/y/link/"]);
+
+            let file_ids = HashMap::from([("x/y/", "1"), ("x/y/link/", "1")]);
+            Self { map, file_ids }
         }
 
         fn get(&self, path: &str) -> MockLister {
@@ -196,6 +263,7 @@ mod tests {
 
             MockLister {
                 inner: inner.into_iter(),
+                file_ids: self.file_ids.clone(),
             }
         }
     }
@@ -223,16 +291,21 @@ mod tests {
 
     struct MockLister {
         inner: IntoIter<&'static str>,
+        file_ids: HashMap<&'static str, &'static str>,
     }
 
     impl BlockingList for MockLister {
         fn next(&mut self) -> Result<Option<oio::Entry>> {
             Ok(self.inner.next().map(|path| {
-                if path.ends_with('/') {
-                    oio::Entry::new(path, Metadata::new(EntryMode::DIR))
+                let mut meta = if path.ends_with('/') {
+                    Metadata::new(EntryMode::DIR)
                 } else {
-                    oio::Entry::new(path, Metadata::new(EntryMode::FILE))
+                    Metadata::new(EntryMode::FILE)
+                };
+                if let Some(id) = self.file_ids.get(path) {
+                    meta.set_file_id(id);
                 }
+                oio::Entry::new(path, meta)
             }))
         }
     }
@@ -257,4 +330,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blocking_list_with_cycle() -> Result<()> {
+        let acc = MockService::new_with_cycle();
+        let mut lister = FlatLister::new(acc, "x/");
+
+        let mut paths = Vec::default();
+        while let Some(e) = lister.next()? {
+            paths.push(e.path().to_string())
+        }
+        assert_eq!(paths, vec!["x/y/a", "x/y/link/", "x/y/", "x/"]);
+
+        let acc = MockService::new_with_cycle();
+        let mut lister = FlatLister::new(acc, "x/").with_cycle_policy(ListCyclePolicy::Error);
+
+        let mut result = Ok(None);
+        for _ in 0..4 {
+            result = lister.next();
+            if result.is_err() {
+                break;
+            }
+        }
+        assert!(result.is_err());
+
+        Ok(())
+    }
 }
@@ -22,6 +22,7 @@ pub use api::List;
 pub use api::Lister;
 
 mod page_list;
+pub use page_list::ListStatsRecorder;
 pub use page_list::PageContext;
 pub use page_list::PageList;
 pub use page_list::PageLister;
@@ -19,6 +19,8 @@ use crate::raw::*;
 use crate::*;
 use std::collections::VecDeque;
 use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 /// PageList is used to implement [`oio::List`] based on API supporting pagination. By implementing
 /// PageList, services don't need to care about the details of page list.
@@ -59,6 +61,43 @@ pub struct PageContext {
     /// PageLister makes sure that entries is reset before calling `next_page`. Implementer
     /// can call `push_back` on `entries` directly.
     pub entries: VecDeque<oio::Entry>,
+    /// stats are used to record the counts reported by underlying storage.
+    ///
+    /// `pages` is maintained by PageLister, implementer only needs to update
+    /// the fields returned by underlying storage.
+    pub stats: ListStatsRecorder,
+    /// error_policy is used to handle entries that can't be parsed, see
+    /// [`PageContext::skip_entry`].
+    pub error_policy: ListErrorPolicy,
+}
+
+impl PageContext {
+    /// Skip the entry that can't be parsed according to the error policy.
+    ///
+    /// Returns the error if the listing should stop, implementer should
+    /// propagate it with `?` and continue with the next entry otherwise.
+    pub fn skip_entry(&self, err: Error) -> Result<()> {
+        self.error_policy.handle(err)
+    }
+}
+
+/// ListStatsRecorder records [`ListStats`] while listing.
+///
+/// Services share the recorder of [`PageContext`] to users via
+/// [`RpList::with_stats`].
+#[derive(Debug, Clone, Default)]
+pub struct ListStatsRecorder(Arc<Mutex<ListStats>>);
+
+impl ListStatsRecorder {
+    /// Update the recorded stats.
+    pub fn update(&self, f: impl FnOnce(&mut ListStats)) {
+        f(&mut self.0.lock().expect("lock must succeed"))
+    }
+
+    /// Get a copy of the recorded stats.
+    pub fn get(&self) -> ListStats {
+        *self.0.lock().expect("lock must succeed")
+    }
 }
 
 /// PageLister implements [`oio::List`] based on [`PageList`].
@@ -79,9 +118,23 @@ where
                 done: false,
                 token: "".to_string(),
                 entries: VecDeque::new(),
+                stats: ListStatsRecorder::default(),
+                error_policy: ListErrorPolicy::Fail,
             },
         }
     }
+
+    /// Set the policy to handle entries that can't be parsed.
+    pub fn with_error_policy(mut self, policy: ListErrorPolicy) -> Self {
+        self.ctx.error_policy = policy;
+        self
+    }
+
+    /// Get the recorder of stats, pass it to [`RpList::with_stats`] to
+    /// expose stats to users.
+    pub fn stats(&self) -> ListStatsRecorder {
+        self.ctx.stats.clone()
+    }
 }
 
 impl<L> oio::List for PageLister<L>
@@ -98,6 +151,81 @@ where
             }
 
             self.inner.next_page(&mut self.ctx).await?;
+            self.ctx.stats.update(|stats| stats.pages += 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::oio::List;
+
+    struct MockList;
+
+    impl PageList for MockList {
+        async fn next_page(&self, ctx: &mut PageContext) -> Result<()> {
+            ctx.entries
+                .push_back(oio::Entry::new("a", Metadata::new(EntryMode::FILE)));
+            ctx.stats.update(|stats| {
+                *stats.entries.get_or_insert(0) += 1;
+                stats.total_entries = Some(2);
+            });
+            ctx.done = ctx.stats.get().pages == 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_lister_stats() {
+        let mut l = PageLister::new(MockList);
+        let stats = l.stats();
+        assert_eq!(stats.get(), ListStats::default());
+
+        while l.next().await.unwrap().is_some() {}
+        let stats = stats.get();
+        assert_eq!(stats.pages, 2);
+        assert_eq!(stats.entries, Some(2));
+        assert_eq!(stats.total_entries, Some(2));
+        assert_eq!(stats.bytes, None);
+    }
+
+    struct BrokenList;
+
+    impl PageList for BrokenList {
+        async fn next_page(&self, ctx: &mut PageContext) -> Result<()> {
+            for path in ["a", "b", "c"] {
+                if path == "b" {
+                    ctx.skip_entry(
+                        Error::new(ErrorKind::Unexpected, "broken entry")
+                            .with_context("path", path),
+                    )?;
+                    continue;
+                }
+                ctx.entries
+                    .push_back(oio::Entry::new(path, Metadata::new(EntryMode::FILE)));
+            }
+            ctx.done = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_lister_error_policy() {
+        let mut l = PageLister::new(BrokenList);
+        assert!(l.next().await.is_err());
+
+        let report = ListErrorReport::new();
+        let mut l = PageLister::new(BrokenList)
+            .with_error_policy(ListErrorPolicy::SkipAndReport(report.clone()));
+        let mut paths = Vec::new();
+        while let Some(e) = l.next().await.unwrap() {
+            paths.push(e.path().to_string());
         }
+        assert_eq!(paths, vec!["a", "c"]);
+        let errors = report.take();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), ErrorKind::Unexpected);
+        assert!(report.is_empty());
     }
 }
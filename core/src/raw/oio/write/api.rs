@@ -35,7 +35,10 @@ pub trait Write: Unpin + Send + Sync {
     fn write(&mut self, bs: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
 
     /// Close the writer and make sure all data has been flushed.
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend;
+    ///
+    /// Returns the metadata of the written object returned by services like
+    /// `etag`, which will be empty if services don't return any.
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// Abort the pending writer.
     fn abort(&mut self) -> impl Future<Output = Result<()>> + MaybeSend;
@@ -46,7 +49,7 @@ impl Write for () {
         unimplemented!("write is required to be implemented for oio::Write")
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         Err(Error::new(
             ErrorKind::Unsupported,
             "output writer doesn't support close",
@@ -64,7 +67,7 @@ impl Write for () {
 pub trait WriteDyn: Unpin + Send + Sync {
     fn write_dyn(&mut self, bs: Buffer) -> BoxedFuture<Result<()>>;
 
-    fn close_dyn(&mut self) -> BoxedFuture<Result<()>>;
+    fn close_dyn(&mut self) -> BoxedFuture<Result<Metadata>>;
 
     fn abort_dyn(&mut self) -> BoxedFuture<Result<()>>;
 }
@@ -74,7 +77,7 @@ impl<T: Write + ?Sized> WriteDyn for T {
         Box::pin(self.write(bs))
     }
 
-    fn close_dyn(&mut self) -> BoxedFuture<Result<()>> {
+    fn close_dyn(&mut self) -> BoxedFuture<Result<Metadata>> {
         Box::pin(self.close())
     }
 
@@ -88,7 +91,7 @@ impl<T: WriteDyn + ?Sized> Write for Box<T> {
         self.deref_mut().write_dyn(bs).await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.deref_mut().close_dyn().await
     }
 
@@ -114,7 +117,10 @@ pub trait BlockingWrite: Send + Sync + 'static {
     fn write(&mut self, bs: Buffer) -> Result<()>;
 
     /// Close the writer and make sure all data has been flushed.
-    fn close(&mut self) -> Result<()>;
+    ///
+    /// Returns the metadata of the written object returned by services like
+    /// `etag`, which will be empty if services don't return any.
+    fn close(&mut self) -> Result<Metadata>;
 }
 
 impl BlockingWrite for () {
@@ -124,7 +130,7 @@ impl BlockingWrite for () {
         unimplemented!("write is required to be implemented for oio::BlockingWrite")
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         Err(Error::new(
             ErrorKind::Unsupported,
             "output writer doesn't support close",
@@ -140,7 +146,7 @@ impl<T: BlockingWrite + ?Sized> BlockingWrite for Box<T> {
         (**self).write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         (**self).close()
     }
 }
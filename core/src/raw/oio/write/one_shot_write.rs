@@ -63,11 +63,12 @@ impl<W: OneShotWrite> oio::Write for OneShotWriter<W> {
         }
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         match self.buffer.clone() {
-            Some(bs) => self.inner.write_once(bs).await,
-            None => self.inner.write_once(Buffer::new()).await,
+            Some(bs) => self.inner.write_once(bs).await?,
+            None => self.inner.write_once(Buffer::new()).await?,
         }
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
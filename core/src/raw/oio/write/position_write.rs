@@ -149,7 +149,7 @@ impl<W: PositionWrite> oio::Write for PositionWriter<W> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         // Make sure all tasks are finished.
         while self.tasks.next().await.transpose()?.is_some() {}
 
@@ -159,7 +159,7 @@ impl<W: PositionWrite> oio::Write for PositionWriter<W> {
             self.cache = None;
         }
         self.w.close().await?;
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -55,6 +55,12 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Access::presign`]
     Presign,
+    /// Operation for [`crate::raw::Access::sign`]
+    Sign,
+    /// Operation for [`crate::raw::Access::list_multipart_uploads`]
+    ListMultipartUploads,
+    /// Operation for [`crate::raw::Access::abort_multipart_upload`]
+    AbortMultipartUpload,
     /// Operation for [`crate::raw::Access::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Access::blocking_read`]
@@ -130,6 +136,9 @@ impl From<Operation> for &'static str {
             Operation::ListerNext => "List::next",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::Sign => "sign",
+            Operation::ListMultipartUploads => "list_multipart_uploads",
+            Operation::AbortMultipartUpload => "abort_multipart_upload",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingReaderRead => "BlockingReader::read",
@@ -22,6 +22,10 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
+use http::Request;
+
 use crate::raw::*;
 use crate::*;
 
@@ -99,6 +103,21 @@ pub struct OpList {
     ///
     /// Default to `false`
     version: bool,
+    /// The cycle policy is used to control how recursive list handles dirs
+    /// that have been walked.
+    ///
+    /// Default to [`ListCyclePolicy::Skip`].
+    cycle_policy: ListCyclePolicy,
+    /// The query passed to underlying service to filter entries on server side.
+    ///
+    /// The syntax is service specific, for example, the `q` parameter of
+    /// gdrive's `files.list`.
+    query: Option<String>,
+    /// The error policy is used to control how list handles entries that
+    /// can't be parsed.
+    ///
+    /// Default to [`ListErrorPolicy::Fail`].
+    error_policy: ListErrorPolicy,
 }
 
 impl Default for OpList {
@@ -109,6 +128,9 @@ impl Default for OpList {
             recursive: false,
             concurrent: 1,
             version: false,
+            cycle_policy: ListCyclePolicy::Skip,
+            query: None,
+            error_policy: ListErrorPolicy::Fail,
         }
     }
 }
@@ -180,6 +202,39 @@ impl OpList {
     pub fn version(&self) -> bool {
         self.version
     }
+
+    /// Change the cycle policy of this list operation.
+    pub fn with_cycle_policy(mut self, policy: ListCyclePolicy) -> Self {
+        self.cycle_policy = policy;
+        self
+    }
+
+    /// Get the cycle policy of this list operation.
+    pub fn cycle_policy(&self) -> ListCyclePolicy {
+        self.cycle_policy
+    }
+
+    /// Change the query of this list operation.
+    pub fn with_query(mut self, query: &str) -> Self {
+        self.query = Some(query.to_string());
+        self
+    }
+
+    /// Get the query of this list operation.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Change the error policy of this list operation.
+    pub fn with_error_policy(mut self, policy: ListErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Get the error policy of this list operation.
+    pub fn error_policy(&self) -> &ListErrorPolicy {
+        &self.error_policy
+    }
 }
 
 /// Args for `presign` operation.
@@ -276,6 +331,10 @@ impl OpBatch {
 pub enum BatchOperation {
     /// Batch delete operation.
     Delete(OpDelete),
+    /// Batch copy operation.
+    ///
+    /// The path of batch is the source, and the string here is the target.
+    Copy(String, OpCopy),
 }
 
 impl From<OpDelete> for BatchOperation {
@@ -290,10 +349,60 @@ impl BatchOperation {
         use BatchOperation::*;
         match self {
             Delete(_) => Operation::Delete,
+            Copy(_, _) => Operation::Copy,
         }
     }
 }
 
+/// Args for `sign` operation.
+#[derive(Debug)]
+pub struct OpSign {
+    req: Request<Buffer>,
+}
+
+impl OpSign {
+    /// Create a new `OpSign` for given request.
+    pub fn new(req: Request<Buffer>) -> Self {
+        Self { req }
+    }
+
+    /// Consume args to get the request to sign.
+    pub fn into_request(self) -> Request<Buffer> {
+        self.req
+    }
+}
+
+/// Args for `list_multipart_uploads` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListMultipartUploads {}
+
+impl OpListMultipartUploads {
+    /// Create a new `OpListMultipartUploads`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `abort_multipart_upload` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpAbortMultipartUpload {
+    upload_id: String,
+}
+
+impl OpAbortMultipartUpload {
+    /// Create a new `OpAbortMultipartUpload` for given upload id.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get upload id from option.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
 /// Args for `read` operation.
 #[derive(Debug, Clone, Default)]
 pub struct OpRead {
@@ -305,6 +414,8 @@ pub struct OpRead {
     override_content_disposition: Option<String>,
     version: Option<String>,
     executor: Option<Executor>,
+    select: Option<(String, SelectInputFormat)>,
+    customer_key: Option<CustomerEncryptionKey>,
 }
 
 impl OpRead {
@@ -396,6 +507,28 @@ impl OpRead {
         self.version.as_deref()
     }
 
+    /// Set the query expression to select the records of the object.
+    pub fn with_select(mut self, expression: &str, format: SelectInputFormat) -> Self {
+        self.select = Some((expression.to_string(), format));
+        self
+    }
+
+    /// Get the query expression and input format from option
+    pub fn select(&self) -> Option<(&str, SelectInputFormat)> {
+        self.select.as_ref().map(|(e, f)| (e.as_str(), *f))
+    }
+
+    /// Set the customer-provided key to decrypt the object.
+    pub fn with_customer_key(mut self, key: CustomerEncryptionKey) -> Self {
+        self.customer_key = Some(key);
+        self
+    }
+
+    /// Get the customer-provided key from option
+    pub fn customer_key(&self) -> Option<&CustomerEncryptionKey> {
+        self.customer_key.as_ref()
+    }
+
     /// Set the executor of the option
     pub fn with_executor(mut self, executor: Executor) -> Self {
         self.executor = Some(executor);
@@ -492,6 +625,7 @@ pub struct OpStat {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    customer_key: Option<CustomerEncryptionKey>,
 }
 
 impl OpStat {
@@ -566,6 +700,17 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the customer-provided key to access the object.
+    pub fn with_customer_key(mut self, key: CustomerEncryptionKey) -> Self {
+        self.customer_key = Some(key);
+        self
+    }
+
+    /// Get the customer-provided key from option
+    pub fn customer_key(&self) -> Option<&CustomerEncryptionKey> {
+        self.customer_key.as_ref()
+    }
 }
 
 /// Args for `write` operation.
@@ -577,9 +722,15 @@ pub struct OpWrite {
     content_disposition: Option<String>,
     cache_control: Option<String>,
     executor: Option<Executor>,
+    if_match: Option<String>,
     if_none_match: Option<String>,
     if_not_exists: bool,
+    idempotency_key: Option<String>,
     user_metadata: Option<HashMap<String, String>>,
+    custom_time: Option<DateTime<Utc>>,
+    object_lock: Option<(ObjectLockMode, DateTime<Utc>)>,
+    legal_hold: Option<bool>,
+    customer_key: Option<CustomerEncryptionKey>,
 }
 
 impl OpWrite {
@@ -664,6 +815,17 @@ impl OpWrite {
         self
     }
 
+    /// Set the If-Match of the option
+    pub fn with_if_match(mut self, s: &str) -> Self {
+        self.if_match = Some(s.to_string());
+        self
+    }
+
+    /// Get If-Match from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
     /// Set the If-None-Match of the option
     pub fn with_if_none_match(mut self, s: &str) -> Self {
         self.if_none_match = Some(s.to_string());
@@ -686,6 +848,17 @@ impl OpWrite {
         self.if_not_exists
     }
 
+    /// Set the idempotency key of the option
+    pub fn with_idempotency_key(mut self, key: &str) -> Self {
+        self.idempotency_key = Some(key.to_string());
+        self
+    }
+
+    /// Get the idempotency key from option
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
     /// Merge given executor into option.
     ///
     /// If executor has already been set, this will do nothing.
@@ -710,6 +883,51 @@ impl OpWrite {
     pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
         self.user_metadata.as_ref()
     }
+
+    /// Set the custom time of the op
+    pub fn with_custom_time(mut self, custom_time: DateTime<Utc>) -> Self {
+        self.custom_time = Some(custom_time);
+        self
+    }
+
+    /// Get the custom time from the op
+    pub fn custom_time(&self) -> Option<DateTime<Utc>> {
+        self.custom_time
+    }
+
+    /// Set the object lock retention mode and the time until which the
+    /// object is retained.
+    pub fn with_object_lock(mut self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.object_lock = Some((mode, retain_until));
+        self
+    }
+
+    /// Get the object lock retention from the op
+    pub fn object_lock(&self) -> Option<(ObjectLockMode, DateTime<Utc>)> {
+        self.object_lock
+    }
+
+    /// Set whether the object is under legal hold
+    pub fn with_legal_hold(mut self, legal_hold: bool) -> Self {
+        self.legal_hold = Some(legal_hold);
+        self
+    }
+
+    /// Get the legal hold from the op
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
+
+    /// Set the customer-provided key to encrypt the object.
+    pub fn with_customer_key(mut self, key: CustomerEncryptionKey) -> Self {
+        self.customer_key = Some(key);
+        self
+    }
+
+    /// Get the customer-provided key from option
+    pub fn customer_key(&self) -> Option<&CustomerEncryptionKey> {
+        self.customer_key.as_ref()
+    }
 }
 
 /// Args for `writer` operation.
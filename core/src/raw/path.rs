@@ -95,8 +95,15 @@ pub fn build_rel_path(root: &str, path: &str) -> String {
 /// - Empty path will be `/`: `` => `/`
 pub fn normalize_path(path: &str) -> String {
     // - all whitespace has been trimmed.
+    normalize_path_keep_whitespace(path.trim())
+}
+
+/// Same as [`normalize_path`] but keep the leading and trailing whitespace.
+///
+/// This is used by operators that want to round-trip keys like `abc ` as is.
+pub fn normalize_path_keep_whitespace(path: &str) -> String {
     // - all leading `/` has been trimmed.
-    let path = path.trim().trim_start_matches('/');
+    let path = path.trim_start_matches('/');
 
     // Fast line for empty path.
     if path.is_empty() {
@@ -16,9 +16,15 @@
 // under the License.
 
 use std::collections::VecDeque;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures::Future;
+use log::warn;
 use moka::sync::Cache;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use tokio::sync::Mutex;
 use tokio::sync::MutexGuard;
 
@@ -43,6 +49,92 @@ pub trait PathQuery {
     ) -> impl Future<Output = Result<String>> + MaybeSend;
 }
 
+/// PathCacheStore is the persistent storage of [`PathCacher`].
+///
+/// The in-memory cache of `PathCacher` is lost after the process restarts.
+/// Implement this trait to keep the path to id mapping in a local file or a
+/// remote storage so that it can be reused by the next process.
+///
+/// The values are opaque strings encoded by `PathCacher`, which carry
+/// their own expiry. Stores only need to keep them as is.
+pub trait PathCacheStore: Send + Sync + Unpin + 'static {
+    /// Get the value of the path.
+    fn get(&self, path: &str) -> impl Future<Output = Result<Option<String>>> + MaybeSend;
+    /// Set the value of the path.
+    fn set(&self, path: &str, value: &str) -> impl Future<Output = Result<()>> + MaybeSend;
+    /// Delete the value of the path.
+    fn delete(&self, path: &str) -> impl Future<Output = Result<()>> + MaybeSend;
+}
+
+/// PathCacheStoreDyn is the dyn version of [`PathCacheStore`]
+/// which make it possible to use as `Arc<dyn PathCacheStoreDyn>`.
+/// User should never implement this trait, but use `PathCacheStore` instead.
+pub trait PathCacheStoreDyn: Send + Sync + Unpin + 'static {
+    /// The dyn version of [`PathCacheStore::get`].
+    fn get_dyn<'a>(&'a self, path: &'a str) -> BoxedFuture<'a, Result<Option<String>>>;
+    /// The dyn version of [`PathCacheStore::set`].
+    fn set_dyn<'a>(&'a self, path: &'a str, value: &'a str) -> BoxedFuture<'a, Result<()>>;
+    /// The dyn version of [`PathCacheStore::delete`].
+    fn delete_dyn<'a>(&'a self, path: &'a str) -> BoxedFuture<'a, Result<()>>;
+}
+
+impl<T: PathCacheStore + ?Sized> PathCacheStoreDyn for T {
+    fn get_dyn<'a>(&'a self, path: &'a str) -> BoxedFuture<'a, Result<Option<String>>> {
+        Box::pin(self.get(path))
+    }
+
+    fn set_dyn<'a>(&'a self, path: &'a str, value: &'a str) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(self.set(path, value))
+    }
+
+    fn delete_dyn<'a>(&'a self, path: &'a str) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(self.delete(path))
+    }
+}
+
+impl<T: PathCacheStoreDyn + ?Sized> PathCacheStore for Arc<T> {
+    async fn get(&self, path: &str) -> Result<Option<String>> {
+        self.deref().get_dyn(path).await
+    }
+
+    async fn set(&self, path: &str, value: &str) -> Result<()> {
+        self.deref().set_dyn(path, value).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.deref().delete_dyn(path).await
+    }
+}
+
+/// Store the path cache in another operator, for example a local fs or a
+/// redis service.
+///
+/// Every path is stored as a single object, whose name is the percent-encoded
+/// path.
+impl PathCacheStore for Operator {
+    async fn get(&self, path: &str) -> Result<Option<String>> {
+        let key = utf8_percent_encode(path, NON_ALPHANUMERIC).to_string();
+        match self.read(&key).await {
+            Ok(bs) => Ok(Some(String::from_utf8_lossy(&bs.to_vec()).to_string())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn set(&self, path: &str, value: &str) -> Result<()> {
+        let key = utf8_percent_encode(path, NON_ALPHANUMERIC).to_string();
+        self.write(&key, value.to_string()).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let key = utf8_percent_encode(path, NON_ALPHANUMERIC).to_string();
+        Operator::delete(self, &key).await
+    }
+}
+
+/// The default capacity of the in-memory cache of [`PathCacher`].
+const DEFAULT_CAPACITY: u64 = 64 * 1024;
+
 /// PathCacher is a cache for path query.
 ///
 /// OpenDAL is designed for path based storage systems, such as S3, HDFS, etc. But there are many
@@ -54,9 +146,20 @@ pub trait PathQuery {
 ///
 /// The `path` in the cache is always an absolute one. For example, if the service root is `/root/`,
 /// then the path of file `a/b` in cache will be `/root/a/b`.
+///
+/// Entries are kept in memory, and also in the [`PathCacheStore`] if set by
+/// [`PathCacher::with_store`]. Failures of the store are logged and ignored
+/// since the ids can always be queried from the service again.
+///
+/// Ids may become stale after the files are changed by others, use
+/// [`PathCacher::with_ttl`] to expire them.
 pub struct PathCacher<Q: PathQuery> {
     query: Q,
     cache: Cache<String, String>,
+    capacity: u64,
+    ttl: Option<Duration>,
+    store: Option<Arc<dyn PathCacheStoreDyn>>,
+    clock: Clock,
 
     /// This optional lock here is used to prevent concurrent insertions of the same path.
     ///
@@ -70,11 +173,55 @@ impl<Q: PathQuery> PathCacher<Q> {
     pub fn new(query: Q) -> Self {
         Self {
             query,
-            cache: Cache::new(64 * 1024),
+            cache: Cache::new(DEFAULT_CAPACITY),
+            capacity: DEFAULT_CAPACITY,
+            ttl: None,
+            store: None,
+            clock: Clock::system(),
             lock: None,
         }
     }
 
+    /// Set the max number of entries in the in-memory cache.
+    ///
+    /// Default to 65536.
+    pub fn with_capacity(mut self, capacity: u64) -> Self {
+        self.capacity = capacity;
+        self.cache = self.build_cache();
+        self
+    }
+
+    /// Expire the entries after given duration since they were inserted.
+    ///
+    /// Entries never expire by default.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self.cache = self.build_cache();
+        self
+    }
+
+    /// Persist the entries in given store besides the in-memory cache.
+    pub fn with_store(mut self, store: Arc<dyn PathCacheStoreDyn>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Set the clock to check the expiry of entries in the store.
+    ///
+    /// Default to [`Clock::system`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn build_cache(&self) -> Cache<String, String> {
+        let mut builder = Cache::builder().max_capacity(self.capacity);
+        if let Some(ttl) = self.ttl {
+            builder = builder.time_to_live(ttl);
+        }
+        builder.build()
+    }
+
     /// Enable the lock for the path cacher.
     pub fn with_lock(mut self) -> Self {
         self.lock = Some(Mutex::default());
@@ -89,6 +236,51 @@ impl<Q: PathQuery> PathCacher<Q> {
         }
     }
 
+    /// Get the id from the in-memory cache or the store.
+    async fn get_cached(&self, path: &str) -> Option<String> {
+        if let Some(id) = self.cache.get(path) {
+            return Some(id);
+        }
+
+        let store = self.store.as_ref()?;
+        let value = match store.get(path).await {
+            Ok(value) => value?,
+            Err(err) => {
+                warn!("path cache store get {path} failed: {err}");
+                return None;
+            }
+        };
+        // Values are encoded as `{expires_at} {id}` in unix seconds.
+        let (expires_at, id) = value.split_once(' ')?;
+        let ttl = expires_at.parse::<i64>().ok()? - self.clock.now().timestamp();
+        if ttl <= 0 {
+            return None;
+        }
+
+        self.cache.insert(path.to_string(), id.to_string());
+        Some(id.to_string())
+    }
+
+    /// Insert the id into the in-memory cache and the store.
+    async fn put(&self, path: &str, id: &str) {
+        self.cache.insert(path.to_string(), id.to_string());
+
+        let Some(store) = &self.store else {
+            return;
+        };
+        let expires_at = match self.ttl {
+            Some(ttl) => self
+                .clock
+                .now()
+                .timestamp()
+                .saturating_add(ttl.as_secs() as i64),
+            None => i64::MAX,
+        };
+        if let Err(err) = store.set(path, &format!("{expires_at} {id}")).await {
+            warn!("path cache store set {path} failed: {err}");
+        }
+    }
+
     /// Insert a new cache entry.
     pub async fn insert(&self, path: &str, id: &str) {
         let _guard = self.lock().await;
@@ -102,21 +294,31 @@ impl<Q: PathQuery> PathCacher<Q> {
             return;
         }
 
-        self.cache.insert(path.to_string(), id.to_string());
+        self.put(path, id).await;
     }
 
     /// Remove a cache entry.
     pub async fn remove(&self, path: &str) {
         let _guard = self.lock().await;
 
-        self.cache.invalidate(path)
+        self.cache.invalidate(path);
+        if let Some(store) = &self.store {
+            if let Err(err) = store.delete(path).await {
+                warn!("path cache store delete {path} failed: {err}");
+            }
+        }
+    }
+
+    /// Get the cached id for the given path without querying the service.
+    pub fn peek(&self, path: &str) -> Option<String> {
+        self.cache.get(path)
     }
 
     /// Get the id for the given path.
     pub async fn get(&self, path: &str) -> Result<Option<String>> {
         let _guard = self.lock().await;
 
-        if let Some(id) = self.cache.get(path) {
+        if let Some(id) = self.get_cached(path).await {
             return Ok(Some(id));
         }
 
@@ -126,13 +328,13 @@ impl<Q: PathQuery> PathCacher<Q> {
         while current_path != "/" && !current_path.is_empty() {
             paths.push_front(current_path.to_string());
             current_path = get_parent(current_path);
-            if let Some(id) = self.cache.get(current_path) {
+            if let Some(id) = self.get_cached(current_path).await {
                 return self.query_down(&id, paths).await;
             }
         }
 
         let root_id = self.query.root().await?;
-        self.cache.insert("/".to_string(), root_id.clone());
+        self.put("/", &root_id).await;
         self.query_down(&root_id, paths).await
     }
 
@@ -146,7 +348,7 @@ impl<Q: PathQuery> PathCacher<Q> {
             let name = get_basename(&path);
             current_id = match self.query.query(&current_id, name).await? {
                 Some(id) => {
-                    self.cache.insert(path, id.clone());
+                    self.put(&path, &id).await;
                     id
                 }
                 None => return Ok(None),
@@ -156,6 +358,10 @@ impl<Q: PathQuery> PathCacher<Q> {
     }
 
     /// Ensure input dir exists.
+    ///
+    /// Only dirs below the deepest cached parent are checked, and dirs below
+    /// the first missing one are created without querying since they can't
+    /// exist.
     pub async fn ensure_dir(&self, path: &str) -> Result<String> {
         let _guard = self.lock().await;
 
@@ -172,26 +378,42 @@ impl<Q: PathQuery> PathCacher<Q> {
             parents.push(tmp.to_string());
         }
 
-        let mut parent_id = match self.cache.get("/") {
+        // Find the deepest parent that has been cached.
+        let mut cached = None;
+        for (idx, parent) in parents.iter().enumerate().rev() {
+            if let Some(id) = self.get_cached(parent).await {
+                cached = Some((idx + 1, id));
+                break;
+            }
+        }
+        let (start, mut parent_id) = match cached {
             Some(v) => v,
-            None => self.query.root().await?,
+            None => match self.get_cached("/").await {
+                Some(v) => (0, v),
+                None => {
+                    let root_id = self.query.root().await?;
+                    self.put("/", &root_id).await;
+                    (0, root_id)
+                }
+            },
         };
-        for parent in parents {
-            parent_id = match self.cache.get(&parent) {
+
+        let mut missing = false;
+        for parent in &parents[start..] {
+            let name = get_basename(parent);
+            let existing = if missing {
+                None
+            } else {
+                self.query.query(&parent_id, name).await?
+            };
+            parent_id = match existing {
                 Some(value) => value,
                 None => {
-                    let value = match self.query.query(&parent_id, get_basename(&parent)).await? {
-                        Some(value) => value,
-                        None => {
-                            self.query
-                                .create_dir(&parent_id, get_basename(&parent))
-                                .await?
-                        }
-                    };
-                    self.cache.insert(parent, value.clone());
-                    value
+                    missing = true;
+                    self.query.create_dir(&parent_id, name).await?
                 }
-            }
+            };
+            self.put(parent, &parent_id).await;
         }
 
         Ok(parent_id)
@@ -200,7 +422,12 @@ impl<Q: PathQuery> PathCacher<Q> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
 
+    use crate::raw::PathCacheStore;
     use crate::raw::PathCacher;
     use crate::raw::PathQuery;
     use crate::*;
@@ -241,4 +468,117 @@ mod tests {
             assert_eq!(actual.as_deref(), expect, "{}", name)
         }
     }
+
+    #[derive(Default)]
+    struct TestStore {
+        values: Mutex<HashMap<String, String>>,
+    }
+
+    impl PathCacheStore for TestStore {
+        async fn get(&self, path: &str) -> Result<Option<String>> {
+            Ok(self.values.lock().unwrap().get(path).cloned())
+        }
+
+        async fn set(&self, path: &str, value: &str) -> Result<()> {
+            self.values
+                .lock()
+                .unwrap()
+                .insert(path.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn delete(&self, path: &str) -> Result<()> {
+            self.values.lock().unwrap().remove(path);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_path_cacher_store() {
+        let store = Arc::new(TestStore::default());
+
+        let cache = PathCacher::new(TestQuery {}).with_store(store.clone());
+        cache.get("/a/b").await.unwrap();
+        cache.insert("/a/c", "c").await;
+
+        // A new cacher loads the ids from the store.
+        let cache = PathCacher::new(TestQuery {}).with_store(store.clone());
+        assert_eq!(cache.peek("/a/c"), None);
+        assert_eq!(cache.get("/a/c").await.unwrap().as_deref(), Some("c"));
+        assert_eq!(cache.peek("/a/c").as_deref(), Some("c"));
+
+        cache.remove("/a/c").await;
+        assert!(!store.values.lock().unwrap().contains_key("/a/c"));
+        assert!(store.values.lock().unwrap().contains_key("/a/b"));
+    }
+
+    #[tokio::test]
+    async fn test_path_cacher_store_expired() {
+        let store = Arc::new(TestStore::default());
+        store.set("/a", "0 stale").await.unwrap();
+
+        let cache = PathCacher::new(TestQuery {})
+            .with_ttl(Duration::from_secs(60))
+            .with_store(store);
+        assert_eq!(cache.get("/a").await.unwrap().as_deref(), Some("root/a"));
+    }
+
+    /// Record the dirs queried and created.
+    #[derive(Default)]
+    struct RecordQuery {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl PathQuery for &RecordQuery {
+        async fn root(&self) -> Result<String> {
+            Ok("root/".to_string())
+        }
+
+        async fn query(&self, parent_id: &str, name: &str) -> Result<Option<String>> {
+            self.calls.lock().unwrap().push(format!("query {name}"));
+            if name.starts_with("not_exist") {
+                return Ok(None);
+            }
+            Ok(Some(format!("{parent_id}{name}")))
+        }
+
+        async fn create_dir(&self, parent_id: &str, name: &str) -> Result<String> {
+            self.calls.lock().unwrap().push(format!("create {name}"));
+            Ok(format!("{parent_id}{name}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_path_cacher_ensure_dir() {
+        let query = RecordQuery::default();
+        let cache = PathCacher::new(&query);
+
+        let id = cache.ensure_dir("a/not_exist/b/").await.unwrap();
+        assert_eq!(id, "root/a/not_exist/b/");
+        assert_eq!(
+            *query.calls.lock().unwrap(),
+            [
+                "query a/",
+                "query not_exist/",
+                "create not_exist/",
+                "create b/"
+            ]
+        );
+
+        // Only dirs below the deepest cached parent are checked.
+        query.calls.lock().unwrap().clear();
+        cache.ensure_dir("a/not_exist/b/c/").await.unwrap();
+        assert_eq!(*query.calls.lock().unwrap(), ["query c/"]);
+    }
+
+    #[tokio::test]
+    async fn test_path_cacher_peek() {
+        let cache = PathCacher::new(TestQuery {});
+        assert_eq!(cache.peek("/a/b"), None);
+
+        cache.get("/a/b").await.unwrap();
+        assert_eq!(cache.peek("/").as_deref(), Some("root/"));
+        assert_eq!(cache.peek("/a/").as_deref(), Some("root/a/"));
+        assert_eq!(cache.peek("/a/b").as_deref(), Some("root/a/b"));
+    }
 }
@@ -30,7 +30,22 @@ pub struct RpDelete {}
 
 /// Reply for `list` operation.
 #[derive(Debug, Clone, Default)]
-pub struct RpList {}
+pub struct RpList {
+    stats: Option<oio::ListStatsRecorder>,
+}
+
+impl RpList {
+    /// Set the recorder of stats reported by services while listing.
+    pub fn with_stats(mut self, stats: oio::ListStatsRecorder) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Consume RpList to get the recorder of stats.
+    pub fn into_stats(self) -> Option<oio::ListStatsRecorder> {
+        self.stats
+    }
+}
 
 /// Reply for `presign` operation.
 #[derive(Debug, Clone)]
@@ -97,6 +112,68 @@ impl<T: Default> From<PresignedRequest> for Request<T> {
     }
 }
 
+/// Reply for `sign` operation.
+#[derive(Debug)]
+pub struct RpSign {
+    req: SignedRequest,
+}
+
+impl RpSign {
+    /// Create a new reply for `sign`.
+    pub fn new(req: SignedRequest) -> Self {
+        RpSign { req }
+    }
+
+    /// Consume reply to build a signed request.
+    pub fn into_signed_request(self) -> SignedRequest {
+        self.req
+    }
+}
+
+/// SignedRequest is a user-built request signed by `sign`.
+#[derive(Debug)]
+pub struct SignedRequest {
+    req: Request<Buffer>,
+}
+
+impl SignedRequest {
+    /// Create a new SignedRequest
+    pub fn new(req: Request<Buffer>) -> Self {
+        Self { req }
+    }
+
+    /// Return request's method.
+    pub fn method(&self) -> &http::Method {
+        self.req.method()
+    }
+
+    /// Return request's uri.
+    pub fn uri(&self) -> &http::Uri {
+        self.req.uri()
+    }
+
+    /// Return request's header.
+    pub fn header(&self) -> &http::HeaderMap {
+        self.req.headers()
+    }
+
+    /// Return request's body.
+    pub fn body(&self) -> &Buffer {
+        self.req.body()
+    }
+
+    /// Consume to get the signed request.
+    pub fn into_request(self) -> Request<Buffer> {
+        self.req
+    }
+}
+
+impl From<SignedRequest> for Request<Buffer> {
+    fn from(v: SignedRequest) -> Self {
+        v.req
+    }
+}
+
 /// Reply for `read` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpRead {
@@ -116,6 +193,14 @@ pub struct RpRead {
     /// It's ok to leave range as empty, but it's recommended to set range if possible. We will use
     /// this range as hint to do some optimization like avoid an extra stat or read.
     range: Option<BytesContentRange>,
+    /// Metadata is the metadata of the file parsed from the read response.
+    ///
+    /// - `Some(meta)` means the metadata is the same as returned by `stat`.
+    /// - `None` means the metadata is not available.
+    ///
+    /// Services should only set it if the response carries the full metadata,
+    /// for example, while reading the whole file.
+    metadata: Option<Metadata>,
 }
 
 impl RpRead {
@@ -151,6 +236,22 @@ impl RpRead {
         self.range = range;
         self
     }
+
+    /// Got the metadata of the file returned by this read operation.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Set the metadata of the file returned by this read operation.
+    pub fn with_metadata(mut self, metadata: Option<Metadata>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Consume reply to get the metadata of the file.
+    pub fn into_metadata(self) -> Option<Metadata> {
+        self.metadata
+    }
 }
 
 /// Reply for `batch` operation.
@@ -179,6 +280,8 @@ impl RpBatch {
 pub enum BatchedReply {
     /// results of `delete batch` operation
     Delete(RpDelete),
+    /// results of `copy batch` operation
+    Copy(RpCopy),
 }
 
 impl From<RpDelete> for BatchedReply {
@@ -187,6 +290,34 @@ impl From<RpDelete> for BatchedReply {
     }
 }
 
+impl From<RpCopy> for BatchedReply {
+    fn from(rp: RpCopy) -> Self {
+        Self::Copy(rp)
+    }
+}
+
+/// Reply for `list_multipart_uploads` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListMultipartUploads {
+    uploads: Vec<MultipartUpload>,
+}
+
+impl RpListMultipartUploads {
+    /// Create a new reply for `list_multipart_uploads`.
+    pub fn new(uploads: Vec<MultipartUpload>) -> Self {
+        Self { uploads }
+    }
+
+    /// Consume reply to get the listed uploads.
+    pub fn into_uploads(self) -> Vec<MultipartUpload> {
+        self.uploads
+    }
+}
+
+/// Reply for `abort_multipart_upload` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpAbortMultipartUpload {}
+
 /// Reply for `stat` operation.
 #[derive(Debug, Clone)]
 pub struct RpStat {
@@ -205,6 +336,11 @@ impl RpStat {
         self
     }
 
+    /// Get a reference of the inner metadata.
+    pub fn metadata(&self) -> &Metadata {
+        &self.meta
+    }
+
     /// Consume RpStat to get the inner metadata.
     pub fn into_metadata(self) -> Metadata {
         self.meta
@@ -280,4 +416,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_signed_request_convert() -> Result<()> {
+        let req = Request::put("https://opendal.apache.org/path/to/file?tagging")
+            .header(CONTENT_TYPE, "application/xml")
+            .body(Buffer::from("<Tagging/>"))?;
+
+        let sr = SignedRequest::new(req);
+        assert_eq!(Method::PUT, sr.method());
+        assert_eq!(b"<Tagging/>", sr.body().to_vec().as_slice());
+
+        let req: Request<Buffer> = sr.into();
+        assert_eq!(
+            "https://opendal.apache.org/path/to/file?tagging",
+            req.uri().to_string()
+        );
+        assert_eq!("application/xml", req.headers().get(CONTENT_TYPE).unwrap());
+
+        Ok(())
+    }
 }
@@ -24,6 +24,7 @@ use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Buf;
 use http::header::CONTENT_TYPE;
+use http::HeaderMap;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -33,7 +34,10 @@ use reqsign::AzureStorageSigner;
 use sha2::Digest;
 use sha2::Sha256;
 
+use super::core::constants;
 use super::core::constants::X_MS_META_PREFIX;
+use super::core::AZBLOB_SERVER_SIDE_ENCRYPTION;
+use super::error::is_blob_archived;
 use super::error::parse_error;
 use super::lister::AzblobLister;
 use super::writer::AzblobWriter;
@@ -257,6 +261,33 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set the access tier of blobs written by this backend.
+    ///
+    /// Available values are `Hot`, `Cool`, `Cold` and `Archive`, the default
+    /// access tier of the account is used if not set. Appendable blobs
+    /// don't support access tiers.
+    ///
+    /// See [Access tiers for blob data](https://learn.microsoft.com/en-us/azure/storage/blobs/access-tiers-overview)
+    /// for more info.
+    pub fn default_access_tier(mut self, tier: &str) -> Self {
+        if !tier.is_empty() {
+            self.config.default_access_tier = Some(tier.to_string());
+        }
+
+        self
+    }
+
+    /// Start rehydrating archived blobs to the `Hot` tier with standard
+    /// priority while reading them.
+    ///
+    /// Reading archived blobs still fails until the rehydration is done,
+    /// which could take hours.
+    pub fn enable_rehydrate_on_read(mut self) -> Self {
+        self.config.rehydrate_on_read = true;
+
+        self
+    }
+
     /// from_connection_string will make a builder from connection string
     ///
     /// connection string looks like:
@@ -451,6 +482,8 @@ impl Builder for AzblobBuilder {
                 loader: cred_loader,
                 signer,
                 batch_max_operations,
+                default_access_tier: self.config.default_access_tier.clone(),
+                rehydrate_on_read: self.config.rehydrate_on_read,
             }),
             has_sas_token: self.config.sas_token.is_some(),
         })
@@ -503,10 +536,13 @@ impl Access for AzblobBackend {
             .set_name(&self.core.container)
             .set_native_capability(Capability {
                 stat: true,
+                stat_has_server_side_encryption: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_version: true,
 
                 read: true,
+                read_with_version: true,
 
                 read_with_if_match: true,
                 read_with_if_none_match: true,
@@ -520,6 +556,7 @@ impl Access for AzblobBackend {
                 write_with_content_type: true,
                 write_with_if_not_exists: true,
                 write_with_if_none_match: true,
+                write_with_if_match: true,
                 write_with_user_metadata: true,
 
                 delete: true,
@@ -527,18 +564,25 @@ impl Access for AzblobBackend {
 
                 list: true,
                 list_with_recursive: true,
+                list_has_server_side_encryption: true,
+                list_with_version: true,
+                list_has_version: true,
 
                 presign: self.has_sas_token,
                 presign_stat: self.has_sas_token,
                 presign_read: self.has_sas_token,
                 presign_write: self.has_sas_token,
 
+                sign: true,
+
                 batch: true,
                 batch_delete: true,
                 batch_max_operations: Some(self.core.batch_max_operations),
 
                 ..Default::default()
             });
+        #[cfg(feature = "extensions")]
+        am.set_extension(super::ext::AzblobExtension::new(self.core.clone()));
 
         am.into()
     }
@@ -550,14 +594,10 @@ impl Access for AzblobBackend {
 
         match status {
             StatusCode::OK => {
-                let headers = resp.headers();
-                let mut meta = parse_into_metadata(path, headers)?;
-
-                let user_meta = parse_prefixed_headers(headers, X_MS_META_PREFIX);
-                if !user_meta.is_empty() {
-                    meta.with_user_metadata(user_meta);
+                let mut meta = parse_azblob_metadata(path, resp.headers())?;
+                if let Some(v) = args.version() {
+                    meta.set_version(v);
                 }
-
                 Ok(RpStat::new(meta))
             }
             _ => Err(parse_error(resp)),
@@ -569,11 +609,34 @@ impl Access for AzblobBackend {
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
+            // The headers of a full read carry the same metadata as `stat`.
+            StatusCode::OK => {
+                let meta = parse_azblob_metadata(path, resp.headers())?;
+                Ok((RpRead::new().with_metadata(Some(meta)), resp.into_body()))
+            }
+            StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
-                Err(parse_error(Response::from_parts(part, buf)))
+                let resp = Response::from_parts(part, buf);
+                if !(self.core.rehydrate_on_read && is_blob_archived(&resp)) {
+                    return Err(parse_error(resp));
+                }
+
+                let rehydrate = self
+                    .core
+                    .azblob_set_blob_tier(path, "Hot", Some("Standard"))
+                    .await?;
+                match rehydrate.status() {
+                    StatusCode::OK | StatusCode::ACCEPTED => {
+                        Err(parse_error(resp).with_context("rehydrate", "started"))
+                    }
+                    // The blob is being rehydrated already.
+                    StatusCode::CONFLICT => {
+                        Err(parse_error(resp).with_context("rehydrate", "pending"))
+                    }
+                    _ => Err(parse_error(rehydrate).with_operation("rehydrate")),
+                }
             }
         }
     }
@@ -610,6 +673,7 @@ impl Access for AzblobBackend {
             path.to_string(),
             args.recursive(),
             args.limit(),
+            args.version(),
         );
 
         Ok((RpList::default(), oio::PageLister::new(l)))
@@ -650,6 +714,13 @@ impl Access for AzblobBackend {
         )))
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        let mut req = args.into_request();
+        self.core.sign(&mut req).await?;
+
+        Ok(RpSign::new(SignedRequest::new(req)))
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let ops = args.into_operation();
         let paths = ops.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
@@ -722,6 +793,30 @@ impl Access for AzblobBackend {
     }
 }
 
+/// Parse the metadata of a blob from the response headers of
+/// `Get Blob Properties` or `Get Blob`.
+fn parse_azblob_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_metadata(path, headers)?;
+
+    let user_meta = parse_prefixed_headers(headers, X_MS_META_PREFIX);
+    if !user_meta.is_empty() {
+        meta.with_user_metadata(user_meta);
+    }
+
+    if parse_header_to_str(headers, constants::X_MS_SERVER_ENCRYPTED)? == Some("true") {
+        meta.set_server_side_encryption(AZBLOB_SERVER_SIDE_ENCRYPTION);
+    }
+    let key_id = match parse_header_to_str(headers, constants::X_MS_ENCRYPTION_SCOPE)? {
+        Some(v) => Some(v),
+        None => parse_header_to_str(headers, constants::X_MS_ENCRYPTION_KEY_SHA256)?,
+    };
+    if let Some(v) = key_id {
+        meta.set_server_side_encryption_key_id(v);
+    }
+
+    Ok(meta)
+}
+
 #[cfg(test)]
 mod tests {
     use super::infer_storage_name_from_endpoint;
@@ -60,6 +60,15 @@ pub struct AzblobConfig {
 
     /// The maximum batch operations of Azblob service backend.
     pub batch_max_operations: Option<usize>,
+
+    /// The access tier of blobs written by Azblob service backend, like
+    /// `Hot`, `Cool`, `Cold` or `Archive`.
+    ///
+    /// The default access tier of the account is used if not set.
+    pub default_access_tier: Option<String>,
+
+    /// Start rehydrating archived blobs to the `Hot` tier while reading them.
+    pub rehydrate_on_read: bool,
 }
 
 impl Debug for AzblobConfig {
@@ -79,6 +88,8 @@ impl Debug for AzblobConfig {
         if self.sas_token.is_some() {
             ds.field("sas_token", &"<redacted>");
         }
+        ds.field("default_access_tier", &self.default_access_tier);
+        ds.field("rehydrate_on_read", &self.rehydrate_on_read);
 
         ds.finish()
     }
@@ -51,11 +51,21 @@ pub mod constants {
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
     pub const X_MS_META_PREFIX: &str = "x-ms-meta-";
+    pub const X_MS_SNAPSHOT: &str = "x-ms-snapshot";
+
+    // Access tiers
+    pub const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
+    pub const X_MS_ACCESS_TIER_INFERRED: &str = "x-ms-access-tier-inferred";
+    pub const X_MS_ACCESS_TIER_CHANGE_TIME: &str = "x-ms-access-tier-change-time";
+    pub const X_MS_ARCHIVE_STATUS: &str = "x-ms-archive-status";
+    pub const X_MS_REHYDRATE_PRIORITY: &str = "x-ms-rehydrate-priority";
 
     // Server-side encryption with customer-provided headers
     pub const X_MS_ENCRYPTION_KEY: &str = "x-ms-encryption-key";
     pub const X_MS_ENCRYPTION_KEY_SHA256: &str = "x-ms-encryption-key-sha256";
     pub const X_MS_ENCRYPTION_ALGORITHM: &str = "x-ms-encryption-algorithm";
+    pub const X_MS_SERVER_ENCRYPTED: &str = "x-ms-server-encrypted";
+    pub const X_MS_ENCRYPTION_SCOPE: &str = "x-ms-encryption-scope";
 }
 
 pub struct AzblobCore {
@@ -69,6 +79,8 @@ pub struct AzblobCore {
     pub loader: AzureStorageLoader,
     pub signer: AzureStorageSigner,
     pub batch_max_operations: usize,
+    pub default_access_tier: Option<String>,
+    pub rehydrate_on_read: bool,
 }
 
 impl Debug for AzblobCore {
@@ -181,6 +193,9 @@ impl AzblobCore {
         );
 
         let mut query_args = Vec::new();
+        if let Some(snapshot) = args.version() {
+            query_args.push(format!("snapshot={}", percent_encode_path(snapshot)))
+        }
         if let Some(override_content_disposition) = args.override_content_disposition() {
             query_args.push(format!(
                 "rscd={}",
@@ -268,10 +283,18 @@ impl AzblobCore {
             req = req.header(IF_NONE_MATCH, v);
         }
 
+        if let Some(v) = args.if_match() {
+            req = req.header(IF_MATCH, v);
+        }
+
         if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
 
+        if let Some(tier) = &self.default_access_tier {
+            req = req.header(constants::X_MS_ACCESS_TIER, tier);
+        }
+
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
@@ -300,6 +323,10 @@ impl AzblobCore {
     /// The following custom header could be set:
     /// - `content-type`
     /// - `x-ms-blob-cache-control`
+    /// - `x-ms-meta-*`
+    ///
+    /// The blob is only created if it doesn't exist, so that concurrent
+    /// writers won't truncate the blob created by others.
     ///
     /// # Reference
     ///
@@ -339,6 +366,14 @@ impl AzblobCore {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
 
+        if let Some(user_metadata) = args.user_metadata() {
+            for (key, value) in user_metadata {
+                req = req.header(format!("{X_MS_META_PREFIX}{key}"), value)
+            }
+        }
+
+        req = req.header(IF_NONE_MATCH, "*");
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -351,6 +386,9 @@ impl AzblobCore {
     ///
     /// - The maximum size of the content could be appended is 4MB.
     /// - `Append Block` succeeds only if the blob already exists.
+    /// - `x-ms-blob-condition-appendpos` is set to `position`, the append
+    ///   fails with `412 Precondition Failed` if other writers have appended
+    ///   to the blob, so that the content of writers never interleave.
     ///
     /// # Reference
     ///
@@ -461,6 +499,12 @@ impl AzblobCore {
         if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
+        if let Some(tier) = &self.default_access_tier {
+            req = req.header(constants::X_MS_ACCESS_TIER, tier);
+        }
+        if let Some(v) = args.if_match() {
+            req = req.header(IF_MATCH, v);
+        }
 
         let content = quick_xml::se::to_string(&PutBlockListRequest {
             latest: block_ids
@@ -498,13 +542,18 @@ impl AzblobCore {
     pub fn azblob_head_blob_request(&self, path: &str, args: &OpStat) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/{}/{}",
             self.endpoint,
             self.container,
             percent_encode_path(&p)
         );
 
+        if let Some(snapshot) = args.version() {
+            write!(url, "?snapshot={}", percent_encode_path(snapshot))
+                .expect("write into string must succeed");
+        }
+
         let mut req = Request::head(&url);
 
         // Set SSE headers.
@@ -585,12 +634,78 @@ impl AzblobCore {
         self.send(req).await
     }
 
+    /// Create a read-only snapshot of the blob, the snapshot id is returned
+    /// in `x-ms-snapshot` header.
+    ///
+    /// # Reference
+    ///
+    /// https://learn.microsoft.com/en-us/rest/api/storageservices/snapshot-blob
+    pub async fn azblob_snapshot_blob(&self, path: &str) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=snapshot",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req);
+
+        let mut req = req
+            .header(CONTENT_LENGTH, 0)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Set the access tier of the blob, archived blobs will be rehydrated
+    /// with `rehydrate_priority` if moved to an online tier.
+    ///
+    /// # Reference
+    ///
+    /// https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-tier
+    pub async fn azblob_set_blob_tier(
+        &self,
+        path: &str,
+        tier: &str,
+        rehydrate_priority: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=tier",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url)
+            .header(constants::X_MS_ACCESS_TIER, tier)
+            .header(CONTENT_LENGTH, 0);
+
+        if let Some(v) = rehydrate_priority {
+            req = req.header(constants::X_MS_REHYDRATE_PRIORITY, v);
+        }
+
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn azblob_list_blobs(
         &self,
         path: &str,
         next_marker: &str,
         delimiter: &str,
         limit: Option<usize>,
+        snapshots: bool,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -611,6 +726,9 @@ impl AzblobCore {
         if !next_marker.is_empty() {
             write!(url, "&marker={next_marker}").expect("write into string must succeed");
         }
+        if snapshots {
+            url.push_str("&include=snapshots");
+        }
 
         let mut req = Request::get(&url)
             .body(Buffer::new())
@@ -678,6 +796,9 @@ pub struct BlobPrefix {
 pub struct Blob {
     pub properties: Properties,
     pub name: String,
+    /// The id of the snapshot, only returned for snapshots while listing
+    /// with `include=snapshots`.
+    pub snapshot: Option<String>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -692,8 +813,14 @@ pub struct Properties {
     #[serde(rename = "Content-Type")]
     pub content_type: String,
     pub etag: String,
+    pub server_encrypted: bool,
+    pub customer_provided_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
+/// Azure blob storage always encrypts with 256-bit AES.
+pub const AZBLOB_SERVER_SIDE_ENCRYPTION: &str = "AES256";
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -918,4 +1045,41 @@ mod tests {
             vec!["1".to_string(), "2".to_string(), "3".to_string()]
         );
     }
+
+    #[test]
+    fn test_parse_list_blobs_with_snapshots() {
+        let bs = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://test.blob.core.windows.net/" ContainerName="test">
+  <Blobs>
+    <Blob>
+      <Name>a.txt</Name>
+      <Snapshot>2024-05-01T08:00:00.1234567Z</Snapshot>
+      <Properties>
+        <Last-Modified>Wed, 01 May 2024 07:00:00 GMT</Last-Modified>
+        <Etag>0x8DA8BEB55D0EA35</Etag>
+        <Content-Length>3</Content-Length>
+      </Properties>
+    </Blob>
+    <Blob>
+      <Name>a.txt</Name>
+      <Properties>
+        <Last-Modified>Wed, 01 May 2024 09:00:00 GMT</Last-Modified>
+        <Etag>0x8DA8BEB55D99C08</Etag>
+        <Content-Length>5</Content-Length>
+      </Properties>
+    </Blob>
+  </Blobs>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let out: ListBlobsOutput = de::from_reader(Bytes::from(bs).reader()).expect("must success");
+        assert_eq!(out.blobs.blob.len(), 2);
+        assert_eq!(
+            out.blobs.blob[0].snapshot.as_deref(),
+            Some("2024-05-01T08:00:00.1234567Z")
+        );
+        assert_eq!(out.blobs.blob[0].properties.content_length, 3);
+        assert_eq!(out.blobs.blob[1].snapshot, None);
+        assert_eq!(out.blobs.blob[1].properties.content_length, 5);
+    }
 }
@@ -107,6 +107,16 @@ pub(super) fn parse_error(resp: Response<Buffer>) -> Error {
     err
 }
 
+/// Check if the error response is returned for reading archived blobs,
+/// which must be rehydrated first.
+pub(super) fn is_blob_archived(resp: &Response<Buffer>) -> bool {
+    resp.status() == StatusCode::CONFLICT
+        && resp
+            .headers()
+            .get("x-ms-error-code")
+            .is_some_and(|v| v.as_bytes() == b"BlobArchived")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
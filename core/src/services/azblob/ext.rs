@@ -0,0 +1,289 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use http::HeaderMap;
+use http::StatusCode;
+
+use super::backend::AzblobBuilder;
+use super::core::constants;
+use super::core::AzblobCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The [access tier](https://learn.microsoft.com/en-us/azure/storage/blobs/access-tiers-overview)
+/// of a blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AzblobAccessTier {
+    /// Online tier for frequently accessed data.
+    Hot,
+    /// Online tier for data stored for at least 30 days.
+    Cool,
+    /// Online tier for data stored for at least 90 days.
+    Cold,
+    /// Offline tier, blobs must be rehydrated to an online tier before reading.
+    Archive,
+}
+
+impl AzblobAccessTier {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AzblobAccessTier::Hot => "Hot",
+            AzblobAccessTier::Cool => "Cool",
+            AzblobAccessTier::Cold => "Cold",
+            AzblobAccessTier::Archive => "Archive",
+        }
+    }
+}
+
+/// The priority to rehydrate an archived blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AzblobRehydratePriority {
+    /// Rehydrate in up to 15 hours.
+    Standard,
+    /// Rehydrate in under an hour for blobs smaller than 10 GiB.
+    High,
+}
+
+impl AzblobRehydratePriority {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AzblobRehydratePriority::Standard => "Standard",
+            AzblobRehydratePriority::High => "High",
+        }
+    }
+}
+
+/// The access tier status of a blob returned by [`AzblobExtension::tier_status`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AzblobTierStatus {
+    /// The access tier of the blob, like `Hot` or `Archive`.
+    pub tier: Option<String>,
+    /// Whether the tier is inferred from the default tier of the account.
+    pub tier_inferred: bool,
+    /// The time the tier was changed last time.
+    pub tier_change_time: Option<DateTime<Utc>>,
+    /// The rehydration status of archived blobs, like
+    /// `rehydrate-pending-to-hot`.
+    pub archive_status: Option<String>,
+    /// The priority of the pending rehydration.
+    pub rehydrate_priority: Option<String>,
+}
+
+impl AzblobTierStatus {
+    fn parse(headers: &HeaderMap) -> Result<Self> {
+        let tier_change_time =
+            match parse_header_to_str(headers, constants::X_MS_ACCESS_TIER_CHANGE_TIME)? {
+                Some(v) => Some(parse_datetime_from_rfc2822(v)?),
+                None => None,
+            };
+
+        Ok(Self {
+            tier: parse_header_to_str(headers, constants::X_MS_ACCESS_TIER)?.map(String::from),
+            tier_inferred: parse_header_to_str(headers, constants::X_MS_ACCESS_TIER_INFERRED)?
+                == Some("true"),
+            tier_change_time,
+            archive_status: parse_header_to_str(headers, constants::X_MS_ARCHIVE_STATUS)?
+                .map(String::from),
+            rehydrate_priority: parse_header_to_str(headers, constants::X_MS_REHYDRATE_PRIORITY)?
+                .map(String::from),
+        })
+    }
+
+    /// Check whether the blob is being rehydrated from the archive tier.
+    pub fn is_rehydrating(&self) -> bool {
+        self.archive_status
+            .as_deref()
+            .is_some_and(|v| v.starts_with("rehydrate-pending-to-"))
+    }
+}
+
+/// AzblobExtension provides azblob-specific operations that are not covered
+/// by [`Operator`].
+///
+/// Get it via `op.as_service::<services::Azblob>()`.
+#[derive(Clone)]
+pub struct AzblobExtension {
+    core: Arc<AzblobCore>,
+}
+
+impl Debug for AzblobExtension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AzblobExtension")
+            .field("core", &self.core)
+            .finish()
+    }
+}
+
+impl AzblobExtension {
+    pub(super) fn new(core: Arc<AzblobCore>) -> Self {
+        Self { core }
+    }
+
+    /// Create a read-only [snapshot](https://learn.microsoft.com/en-us/rest/api/storageservices/snapshot-blob)
+    /// of the blob at `path`, returns the id of the snapshot.
+    ///
+    /// Snapshots are exposed as versions of the blob:
+    ///
+    /// - Read or stat a snapshot with the id via `read_with(path).version(id)`.
+    /// - List snapshots via `list_with(path).version(true)`, the blob itself
+    ///   is listed without version.
+    pub async fn create_snapshot(&self, path: &str) -> Result<String> {
+        let resp = self.core.azblob_snapshot_blob(path).await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                let snapshot = parse_header_to_str(resp.headers(), constants::X_MS_SNAPSHOT)?
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "x-ms-snapshot is missing in the response",
+                        )
+                        .with_operation("AzblobExtension::create_snapshot")
+                        .with_context("path", path)
+                    })?;
+                Ok(snapshot.to_string())
+            }
+            _ => Err(parse_error(resp)
+                .with_operation("AzblobExtension::create_snapshot")
+                .with_context("path", path)),
+        }
+    }
+
+    /// Fetch the access tier and rehydration status of the blob at `path`.
+    pub async fn tier_status(&self, path: &str) -> Result<AzblobTierStatus> {
+        let resp = self
+            .core
+            .azblob_get_blob_properties(path, &OpStat::new())
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => AzblobTierStatus::parse(resp.headers()),
+            _ => Err(parse_error(resp)
+                .with_operation("AzblobExtension::tier_status")
+                .with_context("path", path)),
+        }
+    }
+
+    /// Move the blob at `path` to `tier`.
+    ///
+    /// Use [`AzblobExtension::rehydrate`] to move archived blobs back to an
+    /// online tier with a specific priority.
+    pub async fn set_tier(&self, path: &str, tier: AzblobAccessTier) -> Result<()> {
+        self.set_blob_tier(path, tier, None, "AzblobExtension::set_tier")
+            .await
+    }
+
+    /// Start rehydrating the archived blob at `path` to the online `tier`.
+    ///
+    /// Rehydration takes hours, check the progress via
+    /// [`AzblobExtension::tier_status`].
+    pub async fn rehydrate(
+        &self,
+        path: &str,
+        tier: AzblobAccessTier,
+        priority: AzblobRehydratePriority,
+    ) -> Result<()> {
+        self.set_blob_tier(path, tier, Some(priority), "AzblobExtension::rehydrate")
+            .await
+    }
+
+    async fn set_blob_tier(
+        &self,
+        path: &str,
+        tier: AzblobAccessTier,
+        priority: Option<AzblobRehydratePriority>,
+        operation: &'static str,
+    ) -> Result<()> {
+        let resp = self
+            .core
+            .azblob_set_blob_tier(path, tier.as_str(), priority.map(|v| v.as_str()))
+            .await?;
+
+        match resp.status() {
+            // Accepted is returned if the blob is being rehydrated.
+            StatusCode::OK | StatusCode::ACCEPTED => Ok(()),
+            _ => Err(parse_error(resp)
+                .with_operation(operation)
+                .with_context("path", path)
+                .with_context("tier", tier.as_str())),
+        }
+    }
+}
+
+impl ServiceExtension for AzblobBuilder {
+    type Extension = AzblobExtension;
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_tier_status() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            constants::X_MS_ACCESS_TIER,
+            HeaderValue::from_static("Archive"),
+        );
+        headers.insert(
+            constants::X_MS_ACCESS_TIER_CHANGE_TIME,
+            HeaderValue::from_static("Wed, 01 May 2024 07:00:00 GMT"),
+        );
+        headers.insert(
+            constants::X_MS_ARCHIVE_STATUS,
+            HeaderValue::from_static("rehydrate-pending-to-hot"),
+        );
+        headers.insert(
+            constants::X_MS_REHYDRATE_PRIORITY,
+            HeaderValue::from_static("High"),
+        );
+
+        let status = AzblobTierStatus::parse(&headers).expect("must succeed");
+        assert_eq!(status.tier.as_deref(), Some("Archive"));
+        assert!(!status.tier_inferred);
+        assert_eq!(
+            status.tier_change_time,
+            Some(parse_datetime_from_rfc2822("Wed, 01 May 2024 07:00:00 GMT").unwrap())
+        );
+        assert_eq!(status.rehydrate_priority.as_deref(), Some("High"));
+        assert!(status.is_rehydrating());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(constants::X_MS_ACCESS_TIER, HeaderValue::from_static("Hot"));
+        headers.insert(
+            constants::X_MS_ACCESS_TIER_INFERRED,
+            HeaderValue::from_static("true"),
+        );
+
+        let status = AzblobTierStatus::parse(&headers).expect("must succeed");
+        assert_eq!(status.tier.as_deref(), Some("Hot"));
+        assert!(status.tier_inferred);
+        assert!(!status.is_rehydrating());
+    }
+}
@@ -22,6 +22,7 @@ use quick_xml::de;
 
 use super::core::AzblobCore;
 use super::core::ListBlobsOutput;
+use super::core::AZBLOB_SERVER_SIDE_ENCRYPTION;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
@@ -32,10 +33,17 @@ pub struct AzblobLister {
     path: String,
     delimiter: &'static str,
     limit: Option<usize>,
+    snapshots: bool,
 }
 
 impl AzblobLister {
-    pub fn new(core: Arc<AzblobCore>, path: String, recursive: bool, limit: Option<usize>) -> Self {
+    pub fn new(
+        core: Arc<AzblobCore>,
+        path: String,
+        recursive: bool,
+        limit: Option<usize>,
+        snapshots: bool,
+    ) -> Self {
         let delimiter = if recursive { "" } else { "/" };
 
         Self {
@@ -43,6 +51,7 @@ impl AzblobLister {
             path,
             delimiter,
             limit,
+            snapshots,
         }
     }
 }
@@ -51,7 +60,13 @@ impl oio::PageList for AzblobLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
         let resp = self
             .core
-            .azblob_list_blobs(&self.path, &ctx.token, self.delimiter, self.limit)
+            .azblob_list_blobs(
+                &self.path,
+                &ctx.token,
+                self.delimiter,
+                self.limit,
+                self.snapshots,
+            )
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -88,7 +103,7 @@ impl oio::PageList for AzblobLister {
                 path = "/".to_string();
             }
 
-            let meta = Metadata::new(EntryMode::from_path(&path))
+            let mut meta = Metadata::new(EntryMode::from_path(&path))
                 // Keep fit with ETag header.
                 .with_etag(format!("\"{}\"", object.properties.etag.as_str()))
                 .with_content_length(object.properties.content_length)
@@ -97,6 +112,21 @@ impl oio::PageList for AzblobLister {
                 .with_last_modified(parse_datetime_from_rfc2822(
                     object.properties.last_modified.as_str(),
                 )?);
+            if object.properties.server_encrypted {
+                meta.set_server_side_encryption(AZBLOB_SERVER_SIDE_ENCRYPTION);
+            }
+            if let Some(v) = object
+                .properties
+                .encryption_scope
+                .or(object.properties.customer_provided_key_sha256)
+            {
+                meta.set_server_side_encryption_key_id(&v);
+            }
+            // Snapshots are listed with the id as version, the blob itself
+            // is listed without version.
+            if let Some(v) = object.snapshot {
+                meta.set_version(&v);
+            }
 
             let de = oio::Entry::with(path, meta);
             ctx.entries.push_back(de);
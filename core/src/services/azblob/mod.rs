@@ -29,5 +29,16 @@ mod backend;
 #[cfg(feature = "services-azblob")]
 pub use backend::AzblobBuilder as Azblob;
 
+#[cfg(all(feature = "services-azblob", feature = "extensions"))]
+mod ext;
+#[cfg(all(feature = "services-azblob", feature = "extensions"))]
+pub use ext::AzblobAccessTier;
+#[cfg(all(feature = "services-azblob", feature = "extensions"))]
+pub use ext::AzblobExtension;
+#[cfg(all(feature = "services-azblob", feature = "extensions"))]
+pub use ext::AzblobRehydratePriority;
+#[cfg(all(feature = "services-azblob", feature = "extensions"))]
+pub use ext::AzblobTierStatus;
+
 mod config;
 pub use config::AzblobConfig;
@@ -75,14 +75,16 @@ impl oio::AppendWrite for AzblobWriter {
 
                 let status = resp.status();
                 match status {
-                    StatusCode::CREATED => {
-                        // do nothing
-                    }
-                    _ => {
-                        return Err(parse_error(resp));
-                    }
+                    StatusCode::CREATED => Ok(0),
+                    // The blob has been created by other writers at the same time.
+                    StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => Err(Error::new(
+                        ErrorKind::ConditionNotMatch,
+                        "the blob has been created by other writers",
+                    )
+                    .with_operation("AppendWrite::offset")
+                    .with_context("path", &self.path)),
+                    _ => Err(parse_error(resp)),
                 }
-                Ok(0)
             }
             _ => Err(parse_error(resp)),
         }
@@ -100,13 +102,21 @@ impl oio::AppendWrite for AzblobWriter {
         let status = resp.status();
         match status {
             StatusCode::CREATED => Ok(()),
+            // The append position doesn't match, other writers have appended to the blob.
+            StatusCode::PRECONDITION_FAILED => Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "the blob has been appended by other writers",
+            )
+            .with_operation("AppendWrite::append")
+            .with_context("path", &self.path)
+            .with_context("offset", offset.to_string())),
             _ => Err(parse_error(resp)),
         }
     }
 }
 
 impl oio::BlockWrite for AzblobWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req: http::Request<Buffer> =
             self.core
                 .azblob_put_blob_request(&self.path, Some(size), &self.op, body)?;
@@ -117,7 +127,7 @@ impl oio::BlockWrite for AzblobWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(&resp),
             _ => Err(parse_error(resp)),
         }
     }
@@ -135,7 +145,7 @@ impl oio::BlockWrite for AzblobWriter {
         }
     }
 
-    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<()> {
+    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<Metadata> {
         let resp = self
             .core
             .azblob_complete_put_block_list(&self.path, block_ids, &self.op)
@@ -143,7 +153,7 @@ impl oio::BlockWrite for AzblobWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(&resp),
             _ => Err(parse_error(resp)),
         }
     }
@@ -155,3 +165,12 @@ impl oio::BlockWrite for AzblobWriter {
         Ok(())
     }
 }
+
+/// Parse the metadata like `etag` of the written blob from the response.
+fn parse_write_metadata(resp: &http::Response<Buffer>) -> Result<Metadata> {
+    let mut meta = Metadata::new(EntryMode::FILE);
+    if let Some(etag) = parse_etag(resp.headers())? {
+        meta.set_etag(etag);
+    }
+    Ok(meta)
+}
@@ -0,0 +1,227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use bytes::Buf;
+use http::Response;
+use http::StatusCode;
+
+use super::core::BoxCore;
+use super::core::BoxItem;
+use super::error::parse_error;
+use super::lister::BoxLister;
+use super::writer::BoxWriter;
+use crate::raw::*;
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct BoxBackend {
+    pub core: Arc<BoxCore>,
+}
+
+impl Access for BoxBackend {
+    type Reader = HttpBody;
+    type Writer = oio::OneShotWriter<BoxWriter>;
+    type Lister = oio::PageLister<BoxLister>;
+    type BlockingReader = ();
+    type BlockingWriter = ();
+    type BlockingLister = ();
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::Box)
+            .set_root(&self.core.root)
+            .set_native_capability(Capability {
+                stat: true,
+                stat_has_content_length: true,
+                stat_has_last_modified: true,
+                stat_has_etag: true,
+
+                read: true,
+
+                list: true,
+                list_with_limit: true,
+                list_has_content_length: true,
+                list_has_last_modified: true,
+                list_has_etag: true,
+
+                write: true,
+                write_can_empty: true,
+
+                create_dir: true,
+                delete: true,
+                rename: true,
+                copy: true,
+                ..Default::default()
+            });
+
+        ma.into()
+    }
+
+    async fn create_dir(&self, path: &str, _args: OpCreateDir) -> Result<RpCreateDir> {
+        let path = build_abs_path(&self.core.root, path);
+        let _ = self.core.path_cache.ensure_dir(&path).await?;
+
+        Ok(RpCreateDir::default())
+    }
+
+    async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
+        let path = build_abs_path(&self.core.root, path);
+        let id = self.core.resolve_existing_path(&path).await?;
+
+        let resp = self
+            .core
+            .box_get_item(&id, path.is_empty() || path.ends_with('/'))
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let item: BoxItem = serde_json::from_reader(resp.into_body().reader())
+            .map_err(new_json_deserialize_error)?;
+        Ok(RpStat::new(item.to_metadata()?))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let path = build_abs_path(&self.core.root, path);
+        let id = self.core.resolve_existing_path(&path).await?;
+
+        let resp = self.core.box_download(&id, args.range()).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                // `202 Accepted` means the file is not ready to be downloaded yet.
+                if status == StatusCode::ACCEPTED {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "file is not ready to be downloaded",
+                    )
+                    .with_context("path", &path)
+                    .set_temporary());
+                }
+                Err(parse_error(Response::from_parts(part, buf)))
+            }
+        }
+    }
+
+    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let path = build_abs_path(&self.core.root, path);
+        let parent_id = self.core.path_cache.ensure_dir(get_parent(&path)).await?;
+
+        // Upload a new version if the file exists, so that its id and
+        // shared links are kept.
+        let file_id = self.core.resolve_path(&path).await?;
+
+        Ok((
+            RpWrite::default(),
+            oio::OneShotWriter::new(BoxWriter::new(self.core.clone(), path, parent_id, file_id)),
+        ))
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let path = build_abs_path(&self.core.root, path);
+        let Some(id) = self.core.resolve_path(&path).await? else {
+            return Ok(RpDelete::default());
+        };
+
+        let resp = self.core.box_delete(&id, path.ends_with('/')).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                self.core.path_cache.remove(&path).await;
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let path = build_abs_path(&self.core.root, path);
+        let l = BoxLister::new(path, self.core.clone(), &args);
+        Ok((RpList::default(), oio::PageLister::new(l)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        let from = build_abs_path(&self.core.root, from);
+        let to = build_abs_path(&self.core.root, to);
+
+        let id = self.core.resolve_existing_path(&from).await?;
+        let parent_id = self.core.path_cache.ensure_dir(get_parent(&to)).await?;
+        // copy will overwrite `to`, delete it if exist
+        self.delete_file(&to).await?;
+
+        let resp = self
+            .core
+            .box_copy(&id, &parent_id, get_basename(&to))
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                let item: BoxItem = serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+                self.core.path_cache.insert(&to, &item.id).await;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        let from = build_abs_path(&self.core.root, from);
+        let to = build_abs_path(&self.core.root, to);
+
+        let id = self.core.resolve_existing_path(&from).await?;
+        let parent_id = self.core.path_cache.ensure_dir(get_parent(&to)).await?;
+        // rename will overwrite `to`, delete it if exist
+        self.delete_file(&to).await?;
+
+        let resp = self
+            .core
+            .box_move(&id, &parent_id, get_basename(&to))
+            .await?;
+        match resp.status() {
+            StatusCode::OK => {
+                self.core.path_cache.remove(&from).await;
+                self.core.path_cache.insert(&to, &id).await;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+impl BoxBackend {
+    /// Delete the file at the absolute path if it exists.
+    async fn delete_file(&self, path: &str) -> Result<()> {
+        let Some(id) = self.core.resolve_path(path).await? else {
+            return Ok(());
+        };
+
+        let resp = self.core.box_delete(&id, false).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                self.core.path_cache.remove(path).await;
+                Ok(())
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
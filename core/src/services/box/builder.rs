@@ -0,0 +1,309 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use log::debug;
+use tokio::sync::Mutex;
+
+use super::backend::BoxBackend;
+use super::core::BoxAppSettings;
+use super::core::BoxCore;
+use super::core::BoxPathQuery;
+use super::core::BoxSigner;
+use crate::raw::*;
+use crate::services::BoxConfig;
+use crate::*;
+
+/// Box requires files uploaded via chunked upload sessions to be at least 20 MiB.
+const MIN_CHUNKED_UPLOAD_THRESHOLD: usize = 20 * 1024 * 1024;
+const DEFAULT_CHUNKED_UPLOAD_THRESHOLD: usize = 50 * 1024 * 1024;
+
+impl Configurator for BoxConfig {
+    type Builder = BoxBuilder;
+    fn into_builder(self) -> Self::Builder {
+        BoxBuilder {
+            config: self,
+            http_client: None,
+        }
+    }
+}
+
+/// [Box](https://www.box.com/) backend support.
+#[derive(Default)]
+#[doc = include_str!("docs.md")]
+pub struct BoxBuilder {
+    config: BoxConfig,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for BoxBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoxBuilder")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl BoxBuilder {
+    /// Set root path of Box folder.
+    pub fn root(mut self, root: &str) -> Self {
+        self.config.root = if root.is_empty() {
+            None
+        } else {
+            Some(root.to_string())
+        };
+
+        self
+    }
+
+    /// Access token is used for temporary access to the Box API.
+    ///
+    /// You can get a developer token from the developer console of your app.
+    ///
+    /// # Note
+    ///
+    /// - An access token is valid for 1 hour.
+    /// - If you want to use the access token for a long time,
+    ///   you can use the refresh token to get a new access token.
+    pub fn access_token(mut self, access_token: &str) -> Self {
+        self.config.access_token = Some(access_token.to_string());
+        self
+    }
+
+    /// Refresh token is used for long term access to the Box API.
+    ///
+    /// You can get the refresh token via OAuth 2.0 Flow of Box API.
+    ///
+    /// OpenDAL will use this refresh token to get a new access token when the old one is expired.
+    pub fn refresh_token(mut self, refresh_token: &str) -> Self {
+        self.config.refresh_token = Some(refresh_token.to_string());
+        self
+    }
+
+    /// Set the client id for Box.
+    ///
+    /// This is required for OAuth 2.0 Flow to refresh the access token.
+    pub fn client_id(mut self, client_id: &str) -> Self {
+        self.config.client_id = Some(client_id.to_string());
+        self
+    }
+
+    /// Set the client secret for Box.
+    ///
+    /// This is required for OAuth 2.0 Flow to refresh the access token.
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.config.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    /// Set the app settings JSON of JWT apps, encoded in base64.
+    ///
+    /// JWT apps are used to access Box without the interactive OAuth 2.0
+    /// Flow, for example in headless pipelines.
+    pub fn credential(mut self, credential: &str) -> Self {
+        self.config.credential = if credential.is_empty() {
+            None
+        } else {
+            Some(credential.to_string())
+        };
+
+        self
+    }
+
+    /// Set the path to the app settings JSON of JWT apps.
+    pub fn credential_path(mut self, path: &str) -> Self {
+        self.config.credential_path = if path.is_empty() {
+            None
+        } else {
+            Some(path.to_string())
+        };
+
+        self
+    }
+
+    /// Set the id of the user to act as with JWT apps.
+    ///
+    /// The service account of the enterprise will be used if not set.
+    pub fn user_id(mut self, user_id: &str) -> Self {
+        self.config.user_id = if user_id.is_empty() {
+            None
+        } else {
+            Some(user_id.to_string())
+        };
+
+        self
+    }
+
+    /// Set the size above which files will be uploaded with chunked upload
+    /// sessions.
+    ///
+    /// Must be at least 20 MiB. Default to 50 MiB.
+    pub fn chunked_upload_threshold(mut self, size: usize) -> Self {
+        self.config.chunked_upload_threshold = Some(size);
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+}
+
+impl Builder for BoxBuilder {
+    const SCHEME: Scheme = Scheme::Box;
+    type Config = BoxConfig;
+
+    fn build(self) -> Result<impl Access> {
+        let root = normalize_root(&self.config.root.unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let client = if let Some(client) = self.http_client {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Box)
+            })?
+        };
+
+        let app_settings = match (&self.config.credential, &self.config.credential_path) {
+            (Some(credential), _) => {
+                let content = BASE64_STANDARD.decode(credential).map_err(|e| {
+                    Error::new(ErrorKind::ConfigInvalid, "credential is not valid base64")
+                        .with_context("service", Scheme::Box)
+                        .set_source(e)
+                })?;
+                Some(
+                    BoxAppSettings::from_slice(&content)
+                        .map_err(|e| e.with_context("service", Scheme::Box))?,
+                )
+            }
+            (None, Some(path)) => {
+                let content = std::fs::read(path).map_err(|e| {
+                    Error::new(ErrorKind::ConfigInvalid, "failed to read credential_path")
+                        .with_context("service", Scheme::Box)
+                        .with_context("credential_path", path)
+                        .set_source(e)
+                })?;
+                Some(
+                    BoxAppSettings::from_slice(&content)
+                        .map_err(|e| e.with_context("service", Scheme::Box))?,
+                )
+            }
+            (None, None) => None,
+        };
+        if self.config.user_id.is_some() && app_settings.is_none() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "user_id can only be used with the credential of JWT apps",
+            )
+            .with_context("service", Scheme::Box));
+        }
+
+        let mut signer = BoxSigner::new(client.clone());
+        match (
+            self.config.access_token,
+            self.config.refresh_token,
+            app_settings,
+        ) {
+            (Some(access_token), None, None) => {
+                signer.access_token = access_token;
+                // We will never expire user specified access token.
+                signer.expires_in = DateTime::<Utc>::MAX_UTC;
+            }
+            (None, Some(refresh_token), None) => {
+                let client_id = self.config.client_id.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "client_id must be set when refresh_token is set",
+                    )
+                    .with_context("service", Scheme::Box)
+                })?;
+                let client_secret = self.config.client_secret.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "client_secret must be set when refresh_token is set",
+                    )
+                    .with_context("service", Scheme::Box)
+                })?;
+
+                signer.refresh_token = refresh_token;
+                signer.client_id = client_id;
+                signer.client_secret = client_secret;
+            }
+            (None, None, Some(app_settings)) => {
+                signer.app_settings = Some(app_settings);
+                signer.user_id = self.config.user_id;
+            }
+            (None, None, None) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "access_token, refresh_token or credential must be set",
+                )
+                .with_context("service", Scheme::Box))
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "only one of access_token, refresh_token and credential can be set",
+                )
+                .with_context("service", Scheme::Box))
+            }
+        };
+
+        let chunked_upload_threshold = self
+            .config
+            .chunked_upload_threshold
+            .unwrap_or(DEFAULT_CHUNKED_UPLOAD_THRESHOLD);
+        if chunked_upload_threshold < MIN_CHUNKED_UPLOAD_THRESHOLD {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "chunked_upload_threshold must be at least 20 MiB",
+            )
+            .with_context("service", Scheme::Box)
+            .with_context(
+                "chunked_upload_threshold",
+                chunked_upload_threshold.to_string(),
+            ));
+        }
+
+        let signer = Arc::new(Mutex::new(signer));
+        Ok(BoxBackend {
+            core: Arc::new(BoxCore {
+                root,
+                chunked_upload_threshold,
+                client: client.clone(),
+                signer: signer.clone(),
+                path_cache: PathCacher::new(BoxPathQuery::new(client, signer)).with_lock(),
+            }),
+        })
+    }
+}
@@ -0,0 +1,62 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// [Box](https://www.box.com/) configuration.
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct BoxConfig {
+    /// The root for box.
+    pub root: Option<String>,
+    /// Access token for box.
+    pub access_token: Option<String>,
+    /// Refresh token for box.
+    pub refresh_token: Option<String>,
+    /// Client id for box.
+    pub client_id: Option<String>,
+    /// Client secret for box.
+    pub client_secret: Option<String>,
+    /// Credential string of the app settings JSON of JWT apps, base64 encoded.
+    pub credential: Option<String>,
+    /// Path to the app settings JSON of JWT apps.
+    pub credential_path: Option<String>,
+    /// The id of the user to act as with JWT apps.
+    ///
+    /// The service account of the enterprise will be used if not set.
+    pub user_id: Option<String>,
+    /// Files larger than this size will be uploaded with chunked upload
+    /// sessions.
+    ///
+    /// Must be at least 20 MiB. Default to 50 MiB.
+    pub chunked_upload_threshold: Option<usize>,
+}
+
+impl Debug for BoxConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoxConfig")
+            .field("root", &self.root)
+            .field("credential_path", &self.credential_path)
+            .field("user_id", &self.user_id)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,822 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use bytes::Buf;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+use http::header;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::SignatureEncoding;
+use rsa::signature::Signer;
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use sha1::Digest;
+use sha1::Sha1;
+use sha2::Sha256;
+use tokio::sync::Mutex;
+
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+const BOX_API_URL: &str = "https://api.box.com/2.0";
+const BOX_UPLOAD_URL: &str = "https://upload.box.com/api/2.0";
+const BOX_TOKEN_URL: &str = "https://api.box.com/oauth2/token";
+/// The id of the root folder of every user.
+const BOX_ROOT_FOLDER_ID: &str = "0";
+/// The fields of items to request, box only returns a few of them by default.
+const BOX_ITEM_FIELDS: &str = "id,type,name,size,modified_at,etag";
+/// The max number of items returned in one list request.
+pub(super) const BOX_MAX_LIST_LIMIT: usize = 1000;
+
+pub struct BoxCore {
+    pub root: String,
+    /// Files larger than this size are uploaded with chunked upload sessions.
+    pub chunked_upload_threshold: usize,
+
+    pub client: HttpClient,
+
+    pub signer: Arc<Mutex<BoxSigner>>,
+
+    /// Cache the mapping from path to item id
+    pub path_cache: PathCacher<BoxPathQuery>,
+}
+
+impl Debug for BoxCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoxCore")
+            .field("root", &self.root)
+            .field("chunked_upload_threshold", &self.chunked_upload_threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BoxCore {
+    /// Resolve the id of the item at the given absolute path.
+    pub async fn resolve_path(&self, path: &str) -> Result<Option<String>> {
+        self.path_cache.get(path).await
+    }
+
+    /// Resolve the id of the item at the given absolute path, returns
+    /// `NotFound` if the item doesn't exist.
+    pub async fn resolve_existing_path(&self, path: &str) -> Result<String> {
+        self.resolve_path(path).await?.ok_or_else(|| {
+            Error::new(ErrorKind::NotFound, "path not found").with_context("path", path)
+        })
+    }
+
+    /// Get the information of the file or folder.
+    pub async fn box_get_item(&self, id: &str, is_dir: bool) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{BOX_API_URL}/{}/{id}?fields={BOX_ITEM_FIELDS}",
+            item_kind(is_dir)
+        );
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Download the content of the file.
+    ///
+    /// Box redirects the request to a pre-signed download url.
+    pub async fn box_download(&self, id: &str, range: BytesRange) -> Result<Response<HttpBody>> {
+        let url = format!("{BOX_API_URL}/files/{id}/content");
+
+        let mut req = Request::get(&url)
+            .header(header::RANGE, range.to_header())
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.fetch(req).await
+    }
+
+    /// List the items in the folder.
+    ///
+    /// refer to https://developer.box.com/reference/get-folders-id-items/
+    pub async fn box_list_folder(
+        &self,
+        id: &str,
+        limit: usize,
+        marker: &str,
+    ) -> Result<Response<Buffer>> {
+        list_folder(
+            &self.client,
+            &self.signer,
+            id,
+            BOX_ITEM_FIELDS,
+            limit,
+            marker,
+        )
+        .await
+    }
+
+    /// Delete the file or folder, folders must be empty.
+    pub async fn box_delete(&self, id: &str, is_dir: bool) -> Result<Response<Buffer>> {
+        let url = format!("{BOX_API_URL}/{}/{id}", item_kind(is_dir));
+
+        let mut req = Request::delete(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Copy the file into the folder of `parent_id` with the name.
+    pub async fn box_copy(
+        &self,
+        id: &str,
+        parent_id: &str,
+        name: &str,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{BOX_API_URL}/files/{id}/copy?fields={BOX_ITEM_FIELDS}");
+        let body = serde_json::to_vec(&json!({
+            "name": name,
+            "parent": { "id": parent_id },
+        }))
+        .map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Move and rename the file to the folder of `parent_id` with the name.
+    pub async fn box_move(
+        &self,
+        id: &str,
+        parent_id: &str,
+        name: &str,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{BOX_API_URL}/files/{id}?fields={BOX_ITEM_FIELDS}");
+        let body = serde_json::to_vec(&json!({
+            "name": name,
+            "parent": { "id": parent_id },
+        }))
+        .map_err(new_json_serialize_error)?;
+
+        let mut req = Request::put(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Upload the file in a single request.
+    ///
+    /// A new file will be created in the folder of `parent_id` if `file_id`
+    /// is `None`, otherwise a new version of the file will be uploaded.
+    ///
+    /// refer to https://developer.box.com/guides/uploads/direct/
+    pub async fn box_upload(
+        &self,
+        parent_id: &str,
+        name: &str,
+        file_id: Option<&str>,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let (url, attributes) = match file_id {
+            Some(file_id) => (
+                format!("{BOX_UPLOAD_URL}/files/{file_id}/content"),
+                json!({ "name": name }),
+            ),
+            None => (
+                format!("{BOX_UPLOAD_URL}/files/content"),
+                json!({ "name": name, "parent": { "id": parent_id } }),
+            ),
+        };
+        let attributes = serde_json::to_vec(&attributes).map_err(new_json_serialize_error)?;
+
+        // The attributes must be sent before the content of the file.
+        let multipart = Multipart::new()
+            .part(FormDataPart::new("attributes").content(attributes))
+            .part(
+                FormDataPart::new("file")
+                    .header(
+                        header::CONTENT_DISPOSITION,
+                        format!("form-data; name=\"file\"; filename=\"{name}\"")
+                            .parse()
+                            .map_err(|_| {
+                                Error::new(ErrorKind::Unexpected, "file name is not valid header")
+                                    .with_context("name", name)
+                            })?,
+                    )
+                    .header(
+                        header::CONTENT_TYPE,
+                        "application/octet-stream".parse().unwrap(),
+                    )
+                    .content(body),
+            );
+
+        let mut req = multipart.apply(Request::post(url))?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Create an upload session for a file of `size` bytes.
+    ///
+    /// refer to https://developer.box.com/guides/uploads/chunked/
+    pub async fn box_create_upload_session(
+        &self,
+        parent_id: &str,
+        name: &str,
+        file_id: Option<&str>,
+        size: u64,
+    ) -> Result<BoxUploadSession> {
+        let (url, body) = match file_id {
+            Some(file_id) => (
+                format!("{BOX_UPLOAD_URL}/files/{file_id}/upload_sessions"),
+                json!({ "file_size": size, "file_name": name }),
+            ),
+            None => (
+                format!("{BOX_UPLOAD_URL}/files/upload_sessions"),
+                json!({ "folder_id": parent_id, "file_size": size, "file_name": name }),
+            ),
+        };
+        let body = serde_json::to_vec(&body).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::CREATED => serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Upload a part that starts at `offset` to the upload session.
+    pub async fn box_upload_part(
+        &self,
+        session: &BoxUploadSession,
+        offset: u64,
+        total: u64,
+        body: Buffer,
+    ) -> Result<BoxUploadPart> {
+        let digest = sha1_digest(&body);
+        let end = offset + body.len() as u64 - 1;
+
+        let mut req = Request::put(&session.session_endpoints.upload_part)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header(header::CONTENT_LENGTH, body.len())
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {offset}-{end}/{total}"),
+            )
+            .header("digest", format!("sha={digest}"))
+            .body(body)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let part: BoxUploadPartResponse =
+                    serde_json::from_reader(resp.into_body().reader())
+                        .map_err(new_json_deserialize_error)?;
+                Ok(part.part)
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Commit the upload session with all uploaded parts, `digest` is the
+    /// base64 encoded sha1 of the whole file.
+    pub async fn box_commit_upload_session(
+        &self,
+        session: &BoxUploadSession,
+        digest: &str,
+        parts: &[BoxUploadPart],
+    ) -> Result<Response<Buffer>> {
+        let body =
+            serde_json::to_vec(&json!({ "parts": parts })).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&session.session_endpoints.commit)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("digest", format!("sha={digest}"))
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// Abort the upload session, uploaded parts will be discarded.
+    pub async fn box_abort_upload_session(&self, session: &BoxUploadSession) -> Result<()> {
+        let mut req = Request::delete(&session.session_endpoints.abort)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        let mut signer = self.signer.lock().await;
+        signer.sign(req).await
+    }
+}
+
+fn item_kind(is_dir: bool) -> &'static str {
+    if is_dir {
+        "folders"
+    } else {
+        "files"
+    }
+}
+
+/// Calculate the base64 encoded sha1 of the content, which is required by
+/// chunked upload sessions.
+pub fn sha1_digest(content: &Buffer) -> String {
+    let mut hasher = Sha1::new();
+    for bs in content.clone() {
+        hasher.update(&bs);
+    }
+    BASE64_STANDARD.encode(hasher.finalize())
+}
+
+async fn list_folder(
+    client: &HttpClient,
+    signer: &Mutex<BoxSigner>,
+    id: &str,
+    fields: &str,
+    limit: usize,
+    marker: &str,
+) -> Result<Response<Buffer>> {
+    let mut url =
+        format!("{BOX_API_URL}/folders/{id}/items?fields={fields}&limit={limit}&usemarker=true");
+    if !marker.is_empty() {
+        url.push_str(&format!("&marker={}", percent_encode_path(marker)));
+    }
+
+    let mut req = Request::get(&url)
+        .body(Buffer::new())
+        .map_err(new_request_build_error)?;
+    signer.lock().await.sign(&mut req).await?;
+
+    client.send(req).await
+}
+
+pub struct BoxSigner {
+    pub client: HttpClient,
+
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+
+    /// The app settings of JWT apps, used instead of the refresh token if set.
+    pub app_settings: Option<BoxAppSettings>,
+    /// The user to act as with JWT apps.
+    pub user_id: Option<String>,
+
+    pub access_token: String,
+    pub expires_in: DateTime<Utc>,
+}
+
+impl BoxSigner {
+    /// Create a new signer.
+    pub fn new(client: HttpClient) -> Self {
+        BoxSigner {
+            client,
+
+            client_id: "".to_string(),
+            client_secret: "".to_string(),
+            refresh_token: "".to_string(),
+            app_settings: None,
+            user_id: None,
+            access_token: "".to_string(),
+            expires_in: DateTime::<Utc>::MIN_UTC,
+        }
+    }
+
+    /// Sign a request.
+    pub async fn sign<T>(&mut self, req: &mut Request<T>) -> Result<()> {
+        if self.access_token.is_empty() || self.expires_in <= Utc::now() {
+            self.refresh().await?;
+        }
+
+        let value = format!("Bearer {}", self.access_token)
+            .parse()
+            .expect("access token must be valid header value");
+        req.headers_mut().insert(header::AUTHORIZATION, value);
+        Ok(())
+    }
+
+    async fn refresh(&mut self) -> Result<()> {
+        let body = match &self.app_settings {
+            Some(settings) => {
+                let assertion = settings.build_assertion(self.user_id.as_deref())?;
+                format!(
+                    "grant_type={}&assertion={}&client_id={}&client_secret={}",
+                    percent_encode_path("urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                    assertion,
+                    percent_encode_path(&settings.box_app_settings.client_id),
+                    percent_encode_path(&settings.box_app_settings.client_secret),
+                )
+            }
+            None => format!(
+                "grant_type=refresh_token&refresh_token={}&client_id={}&client_secret={}",
+                percent_encode_path(&self.refresh_token),
+                percent_encode_path(&self.client_id),
+                percent_encode_path(&self.client_secret),
+            ),
+        };
+
+        let req = Request::post(BOX_TOKEN_URL)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let token: BoxTokenResponse = serde_json::from_reader(resp.into_body().reader())
+            .map_err(new_json_deserialize_error)?;
+        self.access_token = token.access_token;
+        // Refresh tokens of box can only be used once, keep the new one.
+        if let Some(refresh_token) = token.refresh_token {
+            self.refresh_token = refresh_token;
+        }
+        let expires_in = chrono::TimeDelta::try_seconds(token.expires_in).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "expires_in of box token is not valid seconds",
+            )
+            .with_context("expires_in", token.expires_in.to_string())
+        })?;
+        self.expires_in = Utc::now() + expires_in
+            - chrono::TimeDelta::try_seconds(120).expect("120 must be valid seconds");
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct BoxTokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: Option<String>,
+}
+
+/// The app settings JSON downloaded from the developer console of JWT apps.
+///
+/// refer to https://developer.box.com/guides/authentication/jwt/jwt-setup/
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxAppSettings {
+    pub box_app_settings: BoxAppSettingsInner,
+    #[serde(rename = "enterpriseID")]
+    pub enterprise_id: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxAppSettingsInner {
+    #[serde(rename = "clientID")]
+    pub client_id: String,
+    pub client_secret: String,
+    pub app_auth: BoxAppAuth,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxAppAuth {
+    #[serde(rename = "publicKeyID")]
+    pub public_key_id: String,
+    pub private_key: String,
+    #[serde(default)]
+    pub passphrase: String,
+}
+
+impl Debug for BoxAppSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoxAppSettings")
+            .field("client_id", &self.box_app_settings.client_id)
+            .field("enterprise_id", &self.enterprise_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BoxAppSettings {
+    /// Parse the app settings from the content of JSON.
+    pub fn from_slice(content: &[u8]) -> Result<Self> {
+        let settings: Self = serde_json::from_slice(content).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "app settings is invalid").set_source(e)
+        })?;
+        // Make sure the private key is valid while building.
+        settings.signing_key()?;
+        Ok(settings)
+    }
+
+    fn signing_key(&self) -> Result<SigningKey<Sha256>> {
+        let auth = &self.box_app_settings.app_auth;
+        let key = if auth.passphrase.is_empty() {
+            RsaPrivateKey::from_pkcs8_pem(&auth.private_key)
+        } else {
+            RsaPrivateKey::from_pkcs8_encrypted_pem(&auth.private_key, auth.passphrase.as_bytes())
+        }
+        .map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "private key of app is invalid")
+                .with_context("client_id", &self.box_app_settings.client_id)
+                .set_source(e)
+        })?;
+        Ok(SigningKey::new(key))
+    }
+
+    /// Build the JWT assertion signed with RS256, which acts as the user if
+    /// `user_id` is set, otherwise the service account of the enterprise.
+    ///
+    /// refer to https://developer.box.com/guides/authentication/jwt/without-sdk/
+    fn build_assertion(&self, user_id: Option<&str>) -> Result<String> {
+        let header = serde_json::to_vec(&json!({
+            "alg": "RS256",
+            "typ": "JWT",
+            "kid": self.box_app_settings.app_auth.public_key_id,
+        }))
+        .map_err(new_json_serialize_error)?;
+        let (sub, sub_type) = match user_id {
+            Some(user_id) => (user_id, "user"),
+            None => (self.enterprise_id.as_str(), "enterprise"),
+        };
+        let claims = serde_json::to_vec(&json!({
+            "iss": self.box_app_settings.client_id,
+            "sub": sub,
+            "box_sub_type": sub_type,
+            "aud": BOX_TOKEN_URL,
+            "jti": uuid::Uuid::new_v4().to_string(),
+            // The assertion can be valid for at most 60 seconds.
+            "exp": Utc::now().timestamp() + 45,
+        }))
+        .map_err(new_json_serialize_error)?;
+
+        let message = format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(header),
+            BASE64_URL_SAFE_NO_PAD.encode(claims)
+        );
+        let signature = self.signing_key()?.sign(message.as_bytes());
+        Ok(format!(
+            "{message}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        ))
+    }
+}
+
+pub struct BoxPathQuery {
+    pub client: HttpClient,
+    pub signer: Arc<Mutex<BoxSigner>>,
+}
+
+impl BoxPathQuery {
+    pub fn new(client: HttpClient, signer: Arc<Mutex<BoxSigner>>) -> Self {
+        BoxPathQuery { client, signer }
+    }
+}
+
+impl PathQuery for BoxPathQuery {
+    async fn root(&self) -> Result<String> {
+        Ok(BOX_ROOT_FOLDER_ID.to_string())
+    }
+
+    async fn query(&self, parent_id: &str, name: &str) -> Result<Option<String>> {
+        // Names are unique among files and folders in the same folder.
+        let (name, is_dir) = match name.strip_suffix('/') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+
+        // Box doesn't support filtering items by name, and search results
+        // are not consistent, so we have to walk through the folder.
+        let mut marker = String::new();
+        loop {
+            let resp = list_folder(
+                &self.client,
+                &self.signer,
+                parent_id,
+                "id,type,name",
+                BOX_MAX_LIST_LIMIT,
+                &marker,
+            )
+            .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let list: BoxItemList = serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error)?;
+            if let Some(item) = list
+                .entries
+                .into_iter()
+                .find(|v| v.name == name && v.is_folder() == is_dir)
+            {
+                return Ok(Some(item.id));
+            }
+
+            match list.next_marker {
+                Some(v) if !v.is_empty() => marker = v,
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    async fn create_dir(&self, parent_id: &str, name: &str) -> Result<String> {
+        let name = name.trim_end_matches('/');
+        let url = format!("{BOX_API_URL}/folders?fields=id");
+        let body = serde_json::to_vec(&json!({
+            "name": name,
+            "parent": { "id": parent_id },
+        }))
+        .map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+        self.signer.lock().await.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                let item: BoxItem = serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+                Ok(item.id)
+            }
+            // The folder has been created by others.
+            StatusCode::CONFLICT => match self.query(parent_id, &format!("{name}/")).await? {
+                Some(id) => Ok(id),
+                None => Err(parse_error(resp)),
+            },
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+/// The item returned by box, only the fields we need are included.
+///
+/// refer to https://developer.box.com/reference/resources/item/
+#[derive(Deserialize, Debug)]
+pub struct BoxItem {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub size: Option<u64>,
+    pub modified_at: Option<String>,
+    pub etag: Option<String>,
+}
+
+impl BoxItem {
+    /// Check if this item is a folder.
+    pub fn is_folder(&self) -> bool {
+        self.type_ == "folder"
+    }
+
+    /// Build metadata from the fields returned by box.
+    ///
+    /// Web links are returned as files without content.
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        if self.is_folder() {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        if let Some(v) = self.size {
+            meta.set_content_length(v);
+        }
+        if let Some(v) = &self.modified_at {
+            meta.set_last_modified(parse_datetime_from_rfc3339(v)?);
+        }
+        if let Some(v) = &self.etag {
+            meta.set_etag(v);
+        }
+        Ok(meta)
+    }
+}
+
+/// refer to https://developer.box.com/reference/resources/items--marker-paginated/
+#[derive(Deserialize)]
+pub(super) struct BoxItemList {
+    pub(super) entries: Vec<BoxItem>,
+    pub(super) next_marker: Option<String>,
+}
+
+/// refer to https://developer.box.com/reference/resources/files/
+#[derive(Deserialize)]
+pub(super) struct BoxFileList {
+    pub(super) entries: Vec<BoxItem>,
+}
+
+/// refer to https://developer.box.com/reference/resources/upload-session/
+#[derive(Deserialize, Debug, Clone)]
+pub struct BoxUploadSession {
+    pub id: String,
+    pub part_size: u64,
+    pub session_endpoints: BoxUploadSessionEndpoints,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BoxUploadSessionEndpoints {
+    pub upload_part: String,
+    pub commit: String,
+    pub abort: String,
+}
+
+#[derive(Deserialize)]
+struct BoxUploadPartResponse {
+    part: BoxUploadPart,
+}
+
+/// refer to https://developer.box.com/reference/resources/upload-part/
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoxUploadPart {
+    pub part_id: String,
+    pub offset: u64,
+    pub size: u64,
+    pub sha1: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_item_list() {
+        let bs = r#"{
+  "total_count": 2,
+  "limit": 1000,
+  "entries": [
+    {"type": "folder", "id": "11", "etag": "1", "name": "logs", "modified_at": "2024-05-01T10:00:00-07:00"},
+    {"type": "file", "id": "12", "etag": "3", "name": "a.txt", "size": 1024, "modified_at": "2024-05-01T10:00:00-07:00", "sha1": "85136c79cbf9fe36bb9d05d0639c70c265c18d37"}
+  ],
+  "next_marker": "JV9IRGZmieiBasejOG9yDCRNgd2ymoZIbjsxbJMjIs3kioVii"
+}"#;
+
+        let list: BoxItemList = serde_json::from_str(bs).expect("must succeed");
+        assert_eq!(list.entries.len(), 2);
+        assert!(list.entries[0].is_folder());
+        assert!(list.entries[0].to_metadata().unwrap().is_dir());
+
+        let meta = list.entries[1].to_metadata().unwrap();
+        assert!(meta.is_file());
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(meta.etag(), Some("3"));
+        assert!(list.next_marker.is_some());
+    }
+
+    #[test]
+    fn test_sha1_digest() {
+        let content = Buffer::from(vec![Bytes::from("hello "), Bytes::from("world")]);
+        assert_eq!(sha1_digest(&content), "Kq5sNclPz7QV2+lfQIuc6R7oRu0=");
+    }
+}
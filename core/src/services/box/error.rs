@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Buf;
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// refer to https://developer.box.com/reference/resources/client-error/
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct BoxError {
+    code: String,
+    message: String,
+    request_id: String,
+}
+
+/// Parse error response into Error.
+pub(super) fn parse_error(resp: Response<Buffer>) -> Error {
+    let (parts, mut body) = resp.into_parts();
+    let bs = body.copy_to_bytes(body.remaining());
+
+    let (mut kind, mut retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+        StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::CONFLICT => (ErrorKind::AlreadyExists, false),
+        StatusCode::PRECONDITION_FAILED => (ErrorKind::ConditionNotMatch, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let (message, box_err) = serde_json::from_slice::<BoxError>(&bs)
+        .map(|box_err| (format!("{box_err:?}"), Some(box_err)))
+        .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
+
+    if let Some(box_err) = &box_err {
+        (kind, retryable) = parse_box_error_code(&box_err.code).unwrap_or((kind, retryable));
+    }
+
+    let mut err = Error::new(kind, message);
+
+    err = with_error_response_context(err, parts);
+    if let Some(box_err) = box_err.filter(|v| !v.request_id.is_empty()) {
+        err = err.with_context("request_id", box_err.request_id);
+    }
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    err
+}
+
+/// Returns the `Error kind` of this code and whether the error is retryable.
+///
+/// refer to https://developer.box.com/guides/api-calls/permissions-and-errors/common-errors/
+fn parse_box_error_code(code: &str) -> Option<(ErrorKind, bool)> {
+    match code {
+        "item_name_in_use" | "conflict" => Some((ErrorKind::AlreadyExists, false)),
+        "item_name_invalid" | "item_name_too_long" => Some((ErrorKind::Unexpected, false)),
+        "operation_blocked_temporary" | "rate_limit_exceeded" => {
+            Some((ErrorKind::RateLimited, true))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let body = r#"{
+  "type": "error",
+  "status": 409,
+  "code": "item_name_in_use",
+  "message": "Item with the same name already exists",
+  "request_id": "abcdef123456"
+}"#;
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Buffer::from(body))
+            .unwrap();
+        let err = parse_error(resp);
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(err.context("request_id"), Some("abcdef123456"));
+
+        let resp = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .body(Buffer::from("rate limited"))
+            .unwrap();
+        let err = parse_error(resp);
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+    }
+}
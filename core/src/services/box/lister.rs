@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use bytes::Buf;
+use http::StatusCode;
+
+use super::core::BoxCore;
+use super::core::BoxItemList;
+use super::core::BOX_MAX_LIST_LIMIT;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The number of items to fetch in one request if the limit is not specified.
+const DEFAULT_LIST_LIMIT: usize = 100;
+
+pub struct BoxLister {
+    path: String,
+    core: Arc<BoxCore>,
+    limit: usize,
+    /// The id of the folder, resolved at the first page.
+    folder_id: Mutex<Option<String>>,
+}
+
+impl BoxLister {
+    pub fn new(path: String, core: Arc<BoxCore>, args: &OpList) -> Self {
+        let limit = args
+            .limit()
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .clamp(1, BOX_MAX_LIST_LIMIT);
+
+        Self {
+            path,
+            core,
+            limit,
+            folder_id: Mutex::new(None),
+        }
+    }
+}
+
+impl oio::PageList for BoxLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        let folder_id = self.folder_id.lock().unwrap().clone();
+        let folder_id = match folder_id {
+            Some(id) => id,
+            None => {
+                let Some(id) = self.core.resolve_path(&self.path).await? else {
+                    ctx.done = true;
+                    return Ok(());
+                };
+
+                // Return self at the first page.
+                let path = build_rel_path(&self.core.root, &self.path);
+                ctx.entries
+                    .push_back(oio::Entry::new(&path, Metadata::new(EntryMode::DIR)));
+
+                *self.folder_id.lock().unwrap() = Some(id.clone());
+                id
+            }
+        };
+
+        let resp = self
+            .core
+            .box_list_folder(&folder_id, self.limit, &ctx.token)
+            .await?;
+        match resp.status() {
+            StatusCode::OK => {}
+            // The folder has been deleted.
+            StatusCode::NOT_FOUND => {
+                ctx.done = true;
+                return Ok(());
+            }
+            _ => return Err(parse_error(resp)),
+        }
+
+        let list: BoxItemList = serde_json::from_reader(resp.into_body().reader())
+            .map_err(new_json_deserialize_error)?;
+
+        for item in list.entries {
+            let mut meta = item.to_metadata()?;
+            let path = if item.is_folder() {
+                format!("{}{}/", self.path, item.name)
+            } else {
+                format!("{}{}", self.path, item.name)
+            };
+
+            // Update path cache with list result.
+            self.core.path_cache.insert(&path, &item.id).await;
+
+            meta.set_file_id(&item.id);
+            ctx.entries.push_back(oio::Entry::new(
+                &build_rel_path(&self.core.root, &path),
+                meta,
+            ));
+        }
+
+        match list.next_marker {
+            Some(marker) if !marker.is_empty() => ctx.token = marker,
+            _ => ctx.done = true,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use backon::ExponentialBuilder;
+use backon::Retryable;
+use bytes::Buf;
+use http::Response;
+use http::StatusCode;
+use once_cell::sync::Lazy;
+
+use super::core::sha1_digest;
+use super::core::BoxCore;
+use super::core::BoxFileList;
+use super::core::BoxUploadPart;
+use super::core::BoxUploadSession;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The backoff to wait for box to assemble the parts of upload sessions.
+static COMMIT_BACKOFF: Lazy<ExponentialBuilder> = Lazy::new(|| {
+    ExponentialBuilder::default()
+        .with_max_delay(Duration::from_secs(10))
+        .with_max_times(10)
+        .with_jitter()
+});
+
+/// BoxWriter uploads small files in a single request and large files via
+/// chunked upload sessions.
+///
+/// Box requires the size of the whole file to create an upload session, so
+/// only one write is supported.
+pub struct BoxWriter {
+    core: Arc<BoxCore>,
+
+    path: String,
+    parent_id: String,
+    file_id: Option<String>,
+}
+
+impl BoxWriter {
+    pub fn new(
+        core: Arc<BoxCore>,
+        path: String,
+        parent_id: String,
+        file_id: Option<String>,
+    ) -> Self {
+        BoxWriter {
+            core,
+            path,
+            parent_id,
+            file_id,
+        }
+    }
+
+    async fn write_simple(&self, bs: Buffer) -> Result<()> {
+        let resp = self
+            .core
+            .box_upload(
+                &self.parent_id,
+                get_basename(&self.path),
+                self.file_id.as_deref(),
+                bs,
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::CREATED | StatusCode::OK => self.finish(resp).await,
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn write_chunked(&self, bs: Buffer) -> Result<()> {
+        let total = bs.len() as u64;
+        let session = self
+            .core
+            .box_create_upload_session(
+                &self.parent_id,
+                get_basename(&self.path),
+                self.file_id.as_deref(),
+                total,
+            )
+            .await?;
+
+        match self.upload_parts(&session, bs).await {
+            Ok(resp) => self.finish(resp).await,
+            Err(err) => {
+                // Discard the uploaded parts, box will expire the session anyway.
+                let _ = self.core.box_abort_upload_session(&session).await;
+                Err(err.with_context("upload_session_id", &session.id))
+            }
+        }
+    }
+
+    /// Upload all parts to the session and commit it.
+    async fn upload_parts(
+        &self,
+        session: &BoxUploadSession,
+        bs: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let total = bs.len() as u64;
+        let part_size = session.part_size.max(1);
+
+        let mut parts: Vec<BoxUploadPart> = Vec::new();
+        let mut offset = 0;
+        while offset < total {
+            let end = (offset + part_size).min(total);
+            let part = bs.slice(offset as usize..end as usize);
+            parts.push(
+                self.core
+                    .box_upload_part(session, offset, total, part)
+                    .await?,
+            );
+            offset = end;
+        }
+
+        let digest = sha1_digest(&bs);
+        { || self.commit(session, &digest, &parts) }
+            .retry(*COMMIT_BACKOFF)
+            .when(|e| e.is_temporary())
+            .await
+    }
+
+    async fn commit(
+        &self,
+        session: &BoxUploadSession,
+        digest: &str,
+        parts: &[BoxUploadPart],
+    ) -> Result<Response<Buffer>> {
+        let resp = self
+            .core
+            .box_commit_upload_session(session, digest, parts)
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(resp),
+            // The parts are still being processed.
+            StatusCode::ACCEPTED => Err(Error::new(
+                ErrorKind::Unexpected,
+                "upload session is being processed",
+            )
+            .set_temporary()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Finish the upload with the file returned by box.
+    async fn finish(&self, resp: Response<Buffer>) -> Result<()> {
+        // If we don't have the file id before, let's update the cache to avoid re-fetching.
+        if self.file_id.is_none() {
+            let list: BoxFileList = serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error)?;
+            if let Some(file) = list.entries.first() {
+                self.core.path_cache.insert(&self.path, &file.id).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl oio::OneShotWrite for BoxWriter {
+    async fn write_once(&self, bs: Buffer) -> Result<()> {
+        if bs.len() > self.core.chunked_upload_threshold {
+            self.write_chunked(bs).await
+        } else {
+            self.write_simple(bs).await
+        }
+    }
+}
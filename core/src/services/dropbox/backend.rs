@@ -67,6 +67,8 @@ impl Access for DropboxBackend {
 
                 batch: true,
                 batch_delete: true,
+                batch_copy: true,
+                batch_max_operations: Some(DROPBOX_BATCH_MAX_OPERATIONS),
 
                 ..Default::default()
             });
@@ -234,7 +236,23 @@ impl Access for DropboxBackend {
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let ops = args.into_operation();
-        if ops.len() > 1000 {
+        // Dropbox has different endpoints for copies and deletes, reject mixed
+        // batches before sending anything.
+        let copies = ops
+            .iter()
+            .filter(|(_, op)| matches!(op, BatchOperation::Copy(_, _)))
+            .count();
+        if copies != 0 && copies != ops.len() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "dropbox services don't support mixed operations in batch",
+            )
+            .with_operation(Operation::Batch));
+        }
+        if copies != 0 {
+            return self.batch_copy(ops).await;
+        }
+        if ops.len() > DROPBOX_BATCH_MAX_OPERATIONS {
             return Err(Error::new(
                 ErrorKind::Unsupported,
                 "dropbox services only allow delete up to 1000 keys at once",
@@ -244,7 +262,7 @@ impl Access for DropboxBackend {
 
         let paths = ops.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
 
-        let resp = self.core.dropbox_delete_batch(paths).await?;
+        let resp = self.core.dropbox_delete_batch(&paths).await?;
         if resp.status() != StatusCode::OK {
             return Err(parse_error(resp));
         }
@@ -256,14 +274,16 @@ impl Access for DropboxBackend {
         match decoded_response.tag.as_str() {
             "complete" => {
                 let entries = decoded_response.entries.unwrap_or_default();
-                let results = self.core.handle_batch_delete_complete_result(entries);
+                let results = self
+                    .core
+                    .handle_batch_delete_complete_result(&paths, entries);
                 Ok(RpBatch::new(results))
             }
             "async_job_id" => {
                 let job_id = decoded_response
                     .async_job_id
                     .expect("async_job_id should be present");
-                let res = { || self.core.dropbox_delete_batch_check(job_id.clone()) }
+                let res = { || self.core.dropbox_delete_batch_check(job_id.clone(), &paths) }
                     .retry(*BACKOFF)
                     .when(|e| e.is_temporary())
                     .await?;
@@ -280,3 +300,65 @@ impl Access for DropboxBackend {
         }
     }
 }
+
+impl DropboxBackend {
+    async fn batch_copy(&self, ops: Vec<(String, BatchOperation)>) -> Result<RpBatch> {
+        if ops.len() > DROPBOX_BATCH_MAX_OPERATIONS {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "dropbox services only allow copy up to 1000 files at once",
+            )
+            .with_context("length", ops.len().to_string()));
+        }
+
+        let pairs = ops
+            .into_iter()
+            .map(|(from, op)| match op {
+                BatchOperation::Copy(to, _) => Ok((from, to)),
+                _ => Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "dropbox services don't support mixed operations in batch",
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let paths = pairs
+            .iter()
+            .map(|(from, _)| from.clone())
+            .collect::<Vec<_>>();
+
+        let resp = self.core.dropbox_copy_batch(&pairs).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let bs = resp.into_body();
+        let decoded_response: DropboxCopyBatchResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+
+        match decoded_response.tag.as_str() {
+            "complete" => {
+                let entries = decoded_response.entries.unwrap_or_default();
+                let results = self.core.handle_batch_copy_complete_result(&paths, entries);
+                Ok(RpBatch::new(results))
+            }
+            "async_job_id" => {
+                let job_id = decoded_response
+                    .async_job_id
+                    .expect("async_job_id should be present");
+                let res = { || self.core.dropbox_copy_batch_check(job_id.clone(), &paths) }
+                    .retry(*BACKOFF)
+                    .when(|e| e.is_temporary())
+                    .await?;
+
+                Ok(res)
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!(
+                    "copy batch failed with unexpected tag {}",
+                    decoded_response.tag
+                ),
+            )),
+        }
+    }
+}
@@ -21,6 +21,8 @@ use std::sync::Arc;
 
 use chrono::DateTime;
 use chrono::Utc;
+use http::HeaderValue;
+use serde_json::json;
 use tokio::sync::Mutex;
 
 use super::backend::DropboxBackend;
@@ -108,6 +110,47 @@ impl DropboxBuilder {
         self
     }
 
+    /// Set the id of the root namespace to access.
+    ///
+    /// Paths are resolved from the root of this namespace, for example, set
+    /// it to the `root_namespace_id` of the team to access the team space of
+    /// business accounts. By default, paths are resolved from the home
+    /// folder of the member.
+    ///
+    /// Reference: [Path Root Header Modes](https://developers.dropbox.com/dbx-team-files-guide#namespaces)
+    pub fn root_namespace_id(mut self, id: &str) -> Self {
+        self.config.root_namespace_id = if id.is_empty() {
+            None
+        } else {
+            Some(id.to_string())
+        };
+        self
+    }
+
+    /// Set the id of the namespace to access, like a shared folder.
+    ///
+    /// Paths are resolved from the root of this namespace.
+    pub fn namespace_id(mut self, id: &str) -> Self {
+        self.config.namespace_id = if id.is_empty() {
+            None
+        } else {
+            Some(id.to_string())
+        };
+        self
+    }
+
+    /// Set the id of the team member to act as.
+    ///
+    /// This is required by access tokens of team scoped apps.
+    pub fn team_member_id(mut self, id: &str) -> Self {
+        self.config.team_member_id = if id.is_empty() {
+            None
+        } else {
+            Some(id.to_string())
+        };
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -181,11 +224,44 @@ impl Builder for DropboxBuilder {
             }
         };
 
+        let path_root = match (self.config.root_namespace_id, self.config.namespace_id) {
+            (Some(id), None) => Some(json!({ ".tag": "root", "root": id })),
+            (None, Some(id)) => Some(json!({ ".tag": "namespace_id", "namespace_id": id })),
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "root_namespace_id and namespace_id can not be set at the same time",
+                )
+                .with_context("service", Scheme::Dropbox))
+            }
+        };
+        let path_root = path_root
+            .map(|v| HeaderValue::from_str(&v.to_string()))
+            .transpose()
+            .map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "namespace id is invalid")
+                    .with_context("service", Scheme::Dropbox)
+                    .set_source(err)
+            })?;
+        let select_user = self
+            .config
+            .team_member_id
+            .map(|v| HeaderValue::from_str(&v))
+            .transpose()
+            .map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "team_member_id is invalid")
+                    .with_context("service", Scheme::Dropbox)
+                    .set_source(err)
+            })?;
+
         Ok(DropboxBackend {
             core: Arc::new(DropboxCore {
                 root,
                 signer: Arc::new(Mutex::new(signer)),
                 client,
+                path_root,
+                select_user,
             }),
         })
     }
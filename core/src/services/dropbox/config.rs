@@ -36,12 +36,21 @@ pub struct DropboxConfig {
     pub client_id: Option<String>,
     /// client_secret for dropbox.
     pub client_secret: Option<String>,
+    /// the id of the root namespace to access, like the team space of business accounts.
+    pub root_namespace_id: Option<String>,
+    /// the id of the namespace to access, like a shared folder.
+    pub namespace_id: Option<String>,
+    /// the id of the team member to act as, used with team scoped access tokens.
+    pub team_member_id: Option<String>,
 }
 
 impl Debug for DropboxConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DropBoxConfig")
             .field("root", &self.root)
+            .field("root_namespace_id", &self.root_namespace_id)
+            .field("namespace_id", &self.namespace_id)
+            .field("team_member_id", &self.team_member_id)
             .finish_non_exhaustive()
     }
 }
@@ -30,6 +30,8 @@ use chrono::Utc;
 use http::header;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::HeaderName;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -50,12 +52,23 @@ pub static BACKOFF: Lazy<ExponentialBuilder> = Lazy::new(|| {
         .with_jitter()
 });
 
+const DROPBOX_API_PATH_ROOT: &str = "dropbox-api-path-root";
+const DROPBOX_API_SELECT_USER: &str = "dropbox-api-select-user";
+
+/// The max number of entries in a single `delete_batch` or `copy_batch_v2` call.
+pub const DROPBOX_BATCH_MAX_OPERATIONS: usize = 1000;
+
 pub struct DropboxCore {
     pub root: String,
 
     pub client: HttpClient,
 
     pub signer: Arc<Mutex<DropboxSigner>>,
+
+    /// The value of `Dropbox-API-Path-Root` header.
+    pub path_root: Option<HeaderValue>,
+    /// The value of `Dropbox-API-Select-User` header.
+    pub select_user: Option<HeaderValue>,
 }
 
 impl Debug for DropboxCore {
@@ -75,6 +88,15 @@ impl DropboxCore {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        if let Some(v) = &self.path_root {
+            req.headers_mut()
+                .insert(HeaderName::from_static(DROPBOX_API_PATH_ROOT), v.clone());
+        }
+        if let Some(v) = &self.select_user {
+            req.headers_mut()
+                .insert(HeaderName::from_static(DROPBOX_API_SELECT_USER), v.clone());
+        }
+
         let mut signer = self.signer.lock().await;
 
         // Access token is valid, use it directly.
@@ -202,13 +224,13 @@ impl DropboxCore {
         self.client.send(request).await
     }
 
-    pub async fn dropbox_delete_batch(&self, paths: Vec<String>) -> Result<Response<Buffer>> {
+    pub async fn dropbox_delete_batch(&self, paths: &[String]) -> Result<Response<Buffer>> {
         let url = "https://api.dropboxapi.com/2/files/delete_batch".to_string();
         let args = DropboxDeleteBatchArgs {
             entries: paths
-                .into_iter()
+                .iter()
                 .map(|path| DropboxDeleteBatchEntry {
-                    path: self.build_path(&path),
+                    path: self.build_path(path),
                 })
                 .collect(),
         };
@@ -225,7 +247,11 @@ impl DropboxCore {
         self.client.send(request).await
     }
 
-    pub async fn dropbox_delete_batch_check(&self, async_job_id: String) -> Result<RpBatch> {
+    pub async fn dropbox_delete_batch_check(
+        &self,
+        async_job_id: String,
+        paths: &[String],
+    ) -> Result<RpBatch> {
         let url = "https://api.dropboxapi.com/2/files/delete_batch/check".to_string();
         let args = DropboxDeleteBatchCheckArgs { async_job_id };
 
@@ -256,7 +282,7 @@ impl DropboxCore {
             .set_temporary()),
             "complete" => {
                 let entries = decoded_response.entries.unwrap_or_default();
-                let results = self.handle_batch_delete_complete_result(entries);
+                let results = self.handle_batch_delete_complete_result(paths, entries);
                 Ok(RpBatch::new(results))
             }
             _ => Err(Error::new(
@@ -269,6 +295,79 @@ impl DropboxCore {
         }
     }
 
+    pub async fn dropbox_copy_batch(&self, pairs: &[(String, String)]) -> Result<Response<Buffer>> {
+        let url = "https://api.dropboxapi.com/2/files/copy_batch_v2".to_string();
+        let args = DropboxCopyBatchArgs {
+            entries: pairs
+                .iter()
+                .map(|(from, to)| DropboxCopyArgs {
+                    from_path: self.build_path(from),
+                    to_path: self.build_path(to),
+                })
+                .collect(),
+            autorename: false,
+        };
+
+        let bs = Bytes::from(serde_json::to_string(&args).map_err(new_json_serialize_error)?);
+
+        let mut request = Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, bs.len())
+            .body(Buffer::from(bs))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
+        self.client.send(request).await
+    }
+
+    pub async fn dropbox_copy_batch_check(
+        &self,
+        async_job_id: String,
+        paths: &[String],
+    ) -> Result<RpBatch> {
+        let url = "https://api.dropboxapi.com/2/files/copy_batch/check_v2".to_string();
+        let args = DropboxDeleteBatchCheckArgs { async_job_id };
+
+        let bs = Bytes::from(serde_json::to_vec(&args).map_err(new_json_serialize_error)?);
+
+        let mut request = Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, bs.len())
+            .body(Buffer::from(bs))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
+
+        let resp = self.client.send(request).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let bs = resp.into_body();
+
+        let decoded_response: DropboxCopyBatchResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+        match decoded_response.tag.as_str() {
+            "in_progress" => Err(Error::new(
+                ErrorKind::Unexpected,
+                "copy batch job still in progress",
+            )
+            .set_temporary()),
+            "complete" => {
+                let entries = decoded_response.entries.unwrap_or_default();
+                let results = self.handle_batch_copy_complete_result(paths, entries);
+                Ok(RpBatch::new(results))
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!(
+                    "copy batch check failed with unexpected tag {}",
+                    decoded_response.tag
+                ),
+            )),
+        }
+    }
+
     pub async fn dropbox_create_folder(&self, path: &str) -> Result<RpCreateDir> {
         let url = "https://api.dropboxapi.com/2/files/create_folder_v2".to_string();
         let args = DropboxCreateFolderArgs {
@@ -405,49 +504,72 @@ impl DropboxCore {
         self.client.send(request).await
     }
 
+    /// Entries of delete batch are returned in the same order as requested,
+    /// so we can tell which path failed.
     pub fn handle_batch_delete_complete_result(
         &self,
+        paths: &[String],
         entries: Vec<DropboxDeleteBatchResponseEntry>,
     ) -> Vec<(String, Result<BatchedReply>)> {
         let mut results = Vec::with_capacity(entries.len());
-        for entry in entries {
+        for (path, entry) in paths.iter().zip(entries) {
             let result = match entry.tag.as_str() {
-                // Only success response has metadata and then path,
-                // so we cannot tell which path failed.
-                "success" => {
-                    let path = entry
-                        .metadata
-                        .expect("metadata should be present")
-                        .path_display;
-                    (path, Ok(RpDelete::default().into()))
-                }
+                "success" => Ok(RpDelete::default().into()),
                 "failure" => {
-                    let error = entry.failure.expect("error should be present");
-                    let error_cause = &error
+                    let error = entry.failure.unwrap_or_default();
+                    let error_cause = error
                         .failure_cause_map
                         .get(&error.tag)
-                        .expect("error should be present")
-                        .tag;
+                        .map(|v| v.tag.as_str())
+                        .unwrap_or_default();
                     // Ignore errors about path lookup not found and report others.
                     if error.tag == "path_lookup" && error_cause == "not_found" {
-                        ("".to_string(), Ok(RpDelete::default().into()))
+                        Ok(RpDelete::default().into())
                     } else {
-                        let err = Error::new(
+                        Err(Error::new(
                             ErrorKind::Unexpected,
                             format!("delete failed with error {} {}", error.tag, error_cause),
-                        );
-                        ("".to_string(), Err(err))
+                        ))
                     }
                 }
-                _ => (
-                    "".to_string(),
+                _ => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    format!("delete failed with unexpected tag {}", entry.tag),
+                )),
+            };
+            results.push((path.clone(), result));
+        }
+        results
+    }
+
+    /// Entries of copy batch are returned in the same order as requested,
+    /// so we can tell which path failed.
+    pub fn handle_batch_copy_complete_result(
+        &self,
+        paths: &[String],
+        entries: Vec<DropboxCopyBatchResponseEntry>,
+    ) -> Vec<(String, Result<BatchedReply>)> {
+        let mut results = Vec::with_capacity(entries.len());
+        for (path, entry) in paths.iter().zip(entries) {
+            let result = match entry.tag.as_str() {
+                "success" => Ok(RpCopy::default().into()),
+                "failure" => {
+                    let error = entry.failure.unwrap_or_default();
+                    let kind = match error.tag.as_str() {
+                        "from_lookup" => ErrorKind::NotFound,
+                        _ => ErrorKind::Unexpected,
+                    };
                     Err(Error::new(
-                        ErrorKind::Unexpected,
-                        format!("delete failed with unexpected tag {}", entry.tag),
-                    )),
-                ),
+                        kind,
+                        format!("copy failed with error {}", error.tag),
+                    ))
+                }
+                _ => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    format!("copy failed with unexpected tag {}", entry.tag),
+                )),
             };
-            results.push(result);
+            results.push((path.clone(), result));
         }
         results
     }
@@ -545,6 +667,12 @@ struct DropboxCopyArgs {
     to_path: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxCopyBatchArgs {
+    entries: Vec<DropboxCopyArgs>,
+    autorename: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct DropboxMoveArgs {
     from_path: String,
@@ -665,3 +793,27 @@ pub struct DropboxDeleteBatchFailureResponseCause {
     #[serde(rename(deserialize = ".tag"))]
     pub tag: String,
 }
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxCopyBatchResponse {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+    pub async_job_id: Option<String>,
+    pub entries: Option<Vec<DropboxCopyBatchResponseEntry>>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxCopyBatchResponseEntry {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+    pub failure: Option<DropboxCopyBatchFailureResponse>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxCopyBatchFailureResponse {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+}
@@ -69,8 +69,17 @@ impl oio::List for FsLister<tokio::fs::ReadDir> {
 
         let ft = de.file_type().await.map_err(new_std_io_error)?;
         let entry = if ft.is_dir() {
+            #[allow(unused_mut)]
+            let mut meta = Metadata::new(EntryMode::DIR);
+            // Bind mounts could make the same dir show up under its children.
+            #[cfg(unix)]
+            {
+                let m = de.metadata().await.map_err(new_std_io_error)?;
+                meta.set_file_id(&unix_file_id(&m));
+            }
+
             // Make sure we are returning the correct path.
-            oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            oio::Entry::new(&format!("{rel_path}/"), meta)
         } else if ft.is_file() {
             oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
         } else {
@@ -104,8 +113,16 @@ impl oio::BlockingList for FsLister<std::fs::ReadDir> {
 
         let ft = de.file_type().map_err(new_std_io_error)?;
         let entry = if ft.is_dir() {
+            #[allow(unused_mut)]
+            let mut meta = Metadata::new(EntryMode::DIR);
+            #[cfg(unix)]
+            {
+                let m = de.metadata().map_err(new_std_io_error)?;
+                meta.set_file_id(&unix_file_id(&m));
+            }
+
             // Make sure we are returning the correct path.
-            oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            oio::Entry::new(&format!("{rel_path}/"), meta)
         } else if ft.is_file() {
             oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
         } else {
@@ -115,3 +132,11 @@ impl oio::BlockingList for FsLister<std::fs::ReadDir> {
         Ok(Some(entry))
     }
 }
+
+/// Build the file id from the device and inode number.
+#[cfg(unix)]
+fn unix_file_id(m: &std::fs::Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+
+    format!("{}:{}", m.dev(), m.ino())
+}
@@ -63,7 +63,7 @@ impl oio::Write for FsWriter<tokio::fs::File> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let f = self.f.as_mut().expect("FsWriter must be initialized");
         f.flush().await.map_err(new_std_io_error)?;
         f.sync_all().await.map_err(new_std_io_error)?;
@@ -73,7 +73,7 @@ impl oio::Write for FsWriter<tokio::fs::File> {
                 .await
                 .map_err(new_std_io_error)?;
         }
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -102,7 +102,7 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         if let Some(f) = self.f.take() {
             f.sync_all().map_err(new_std_io_error)?;
 
@@ -111,7 +111,7 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
             }
         }
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 }
 
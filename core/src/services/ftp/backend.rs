@@ -33,6 +33,7 @@ use suppaftp::AsyncRustlsConnector;
 use suppaftp::AsyncRustlsFtpStream;
 use suppaftp::FtpError;
 use suppaftp::ImplAsyncFtpStream;
+use suppaftp::Mode;
 use suppaftp::Status;
 use tokio::sync::OnceCell;
 use uuid::Uuid;
@@ -111,6 +112,39 @@ impl FtpBuilder {
 
         self
     }
+
+    /// Use active mode for data connections instead of passive mode.
+    ///
+    /// The server connects back to the client in active mode, which is
+    /// required by some legacy servers. The local port of data connections
+    /// is picked by the OS.
+    pub fn active_mode(mut self) -> Self {
+        self.config.active_mode = true;
+        self
+    }
+
+    /// Set the path to the PEM encoded CA certificates used to verify the
+    /// certificate of FTPS servers.
+    ///
+    /// The builtin roots are used if not set.
+    pub fn ca_path(mut self, ca_path: &str) -> Self {
+        self.config.ca_path = if ca_path.is_empty() {
+            None
+        } else {
+            Some(ca_path.to_string())
+        };
+
+        self
+    }
+
+    /// Don't verify the certificate of FTPS servers.
+    ///
+    /// This is insecure, only use it for self-signed servers in trusted
+    /// networks.
+    pub fn insecure_skip_verify(mut self) -> Self {
+        self.config.insecure_skip_verify = true;
+        self
+    }
 }
 
 impl Builder for FtpBuilder {
@@ -155,6 +189,11 @@ impl Builder for FtpBuilder {
 
         let root = normalize_root(&self.config.root.unwrap_or_default());
 
+        let tls = build_tls_connector(
+            self.config.ca_path.as_deref(),
+            self.config.insecure_skip_verify,
+        )?;
+
         let user = match &self.config.user {
             None => "".to_string(),
             Some(v) => v.clone(),
@@ -171,17 +210,82 @@ impl Builder for FtpBuilder {
             user,
             password,
             enable_secure,
+            tls,
+            active_mode: self.config.active_mode,
             pool: OnceCell::new(),
         })
     }
 }
 
+/// Build the connector to upgrade connections via `AUTH TLS`.
+fn build_tls_connector(ca_path: Option<&str>, insecure_skip_verify: bool) -> Result<TlsConnector> {
+    if ca_path.is_none() && !insecure_skip_verify {
+        return Ok(TlsConnector::default());
+    }
+
+    let builder = rustls::ClientConfig::builder().with_safe_defaults();
+    let config = if insecure_skip_verify {
+        builder
+            .with_custom_certificate_verifier(Arc::new(NoCertificateVerification))
+            .with_no_client_auth()
+    } else {
+        let ca_path = ca_path.expect("ca_path must be set");
+        let content = std::fs::read(ca_path).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "failed to read ca_path")
+                .with_context("service", Scheme::Ftp)
+                .with_context("ca_path", ca_path)
+                .set_source(e)
+        })?;
+        let certs = rustls_pemfile::certs(&mut content.as_slice()).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "ca_path is not valid pem")
+                .with_context("service", Scheme::Ftp)
+                .with_context("ca_path", ca_path)
+                .set_source(e)
+        })?;
+
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in certs {
+            roots.add(&rustls::Certificate(cert)).map_err(|e| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "ca_path contains invalid certificate",
+                )
+                .with_context("service", Scheme::Ftp)
+                .with_context("ca_path", ca_path)
+                .set_source(e)
+            })?;
+        }
+        builder.with_root_certificates(roots).with_no_client_auth()
+    };
+
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Accept any certificate for [`FtpBuilder::insecure_skip_verify`].
+struct NoCertificateVerification;
+
+impl rustls::client::ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _: &rustls::Certificate,
+        _: &[rustls::Certificate],
+        _: &rustls::ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        _: std::time::SystemTime,
+    ) -> std::result::Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
 pub struct Manager {
     endpoint: String,
     root: String,
     user: String,
     password: String,
     enable_secure: bool,
+    tls: TlsConnector,
+    active_mode: bool,
 }
 
 #[async_trait::async_trait]
@@ -194,15 +298,16 @@ impl bb8::ManageConnection for Manager {
         // switch to secure mode if ssl/tls is on.
         let mut ftp_stream = if self.enable_secure {
             stream
-                .into_secure(
-                    AsyncRustlsConnector::from(TlsConnector::default()),
-                    &self.endpoint,
-                )
+                .into_secure(AsyncRustlsConnector::from(self.tls.clone()), &self.endpoint)
                 .await?
         } else {
             stream
         };
 
+        if self.active_mode {
+            ftp_stream.set_mode(Mode::Active);
+        }
+
         // login if needed
         if !self.user.is_empty() {
             ftp_stream.login(&self.user, &self.password).await?;
@@ -246,6 +351,8 @@ pub struct FtpBackend {
     user: String,
     password: String,
     enable_secure: bool,
+    tls: TlsConnector,
+    active_mode: bool,
     pool: OnceCell<bb8::Pool<Manager>>,
 }
 
@@ -426,6 +533,8 @@ impl FtpBackend {
                         user: self.user.to_string(),
                         password: self.password.to_string(),
                         enable_secure: self.enable_secure,
+                        tls: self.tls.clone(),
+                        active_mode: self.active_mode,
                     })
                     .await
             })
@@ -499,5 +608,20 @@ mod build_test {
         assert!(b.is_err());
         let e = b.unwrap_err();
         assert_eq!(e.kind(), ErrorKind::ConfigInvalid);
+
+        // missing ca file
+        let b = FtpBuilder::default()
+            .endpoint("ftps://ftp_server.local")
+            .ca_path("/path/to/not_exist.pem")
+            .build();
+        assert_eq!(b.unwrap_err().kind(), ErrorKind::ConfigInvalid);
+
+        // skip verification
+        let b = FtpBuilder::default()
+            .endpoint("ftps://ftp_server.local")
+            .insecure_skip_verify()
+            .active_mode()
+            .build();
+        assert!(b.is_ok());
     }
 }
@@ -34,6 +34,16 @@ pub struct FtpConfig {
     pub user: Option<String>,
     /// password of this backend
     pub password: Option<String>,
+    /// Use active mode for data connections instead of passive mode.
+    pub active_mode: bool,
+    /// Path to the PEM encoded CA certificates used to verify the certificate
+    /// of FTPS servers, the builtin roots are used if not set.
+    pub ca_path: Option<String>,
+    /// Don't verify the certificate of FTPS servers.
+    ///
+    /// This is insecure, only use it for self-signed servers in trusted
+    /// networks.
+    pub insecure_skip_verify: bool,
 }
 
 impl Debug for FtpConfig {
@@ -41,6 +51,9 @@ impl Debug for FtpConfig {
         f.debug_struct("FtpConfig")
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
+            .field("active_mode", &self.active_mode)
+            .field("ca_path", &self.ca_path)
+            .field("insecure_skip_verify", &self.insecure_skip_verify)
             .finish_non_exhaustive()
     }
 }
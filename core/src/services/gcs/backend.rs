@@ -20,6 +20,8 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Buf;
 use http::Response;
 use http::StatusCode;
@@ -220,6 +222,18 @@ impl GcsBuilder {
         self
     }
 
+    /// Set the customer-supplied encryption key (CSEK) with 32 raw key bytes.
+    ///
+    /// Objects will be encrypted with this key on write, and the key is
+    /// required to read or stat them. Use `customer_key` of operations to
+    /// override it per call.
+    ///
+    /// Reference: [Customer-supplied encryption keys](https://cloud.google.com/storage/docs/encryption/customer-supplied-keys)
+    pub fn customer_encryption_key(mut self, key: &[u8]) -> Self {
+        self.config.customer_encryption_key = Some(BASE64_STANDARD.encode(key));
+        self
+    }
+
     /// Allow anonymous requests.
     ///
     /// This is typically used for buckets which are open to the public or GCS
@@ -250,7 +264,29 @@ impl Builder for GcsBuilder {
             ),
         }?;
 
-        // TODO: server side encryption
+        let customer_key = match &self.config.customer_encryption_key {
+            Some(key) => {
+                let key = BASE64_STANDARD.decode(key).map_err(|err| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "customer encryption key is invalid",
+                    )
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Gcs)
+                    .set_source(err)
+                })?;
+                let key: &[u8; 32] = key.as_slice().try_into().map_err(|_| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "customer encryption key must be 32 bytes",
+                    )
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Gcs)
+                })?;
+                Some(CustomerEncryptionKey::aes256(key))
+            }
+            None => None,
+        };
 
         let client = if let Some(client) = self.http_client {
             client
@@ -323,6 +359,7 @@ impl Builder for GcsBuilder {
                 credential_loader: cred_loader,
                 predefined_acl: self.config.predefined_acl.clone(),
                 default_storage_class: self.config.default_storage_class.clone(),
+                customer_key,
                 allow_anonymous: self.config.allow_anonymous,
             }),
         };
@@ -352,13 +389,17 @@ impl Access for GcsBackend {
             .set_name(&self.core.bucket)
             .set_native_capability(Capability {
                 stat: true,
+                stat_has_custom_time: true,
+                stat_has_server_side_encryption: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_customer_key: true,
 
                 read: true,
 
                 read_with_if_match: true,
                 read_with_if_none_match: true,
+                read_with_customer_key: true,
 
                 write: true,
                 write_can_empty: true,
@@ -366,6 +407,8 @@ impl Access for GcsBackend {
                 write_with_content_type: true,
                 write_with_user_metadata: true,
                 write_with_if_not_exists: true,
+                write_with_custom_time: true,
+                write_with_customer_key: true,
 
                 // The min multipart size of Gcs is 5 MiB.
                 //
@@ -387,6 +430,7 @@ impl Access for GcsBackend {
                 list_with_limit: true,
                 list_with_start_after: true,
                 list_with_recursive: true,
+                list_has_server_side_encryption: true,
 
                 batch: true,
                 batch_max_operations: Some(100),
@@ -395,8 +439,13 @@ impl Access for GcsBackend {
                 presign_read: true,
                 presign_write: true,
 
+                sign: true,
+
                 ..Default::default()
             });
+        #[cfg(feature = "extensions")]
+        am.set_extension(super::ext::GcsExtension::new(self.core.clone()));
+
         am.into()
     }
 
@@ -416,6 +465,9 @@ impl Access for GcsBackend {
 
         m.set_etag(&meta.etag);
         m.set_content_md5(&meta.md5_hash);
+        if !meta.crc32c.is_empty() {
+            m.set_content_crc32c(&meta.crc32c);
+        }
 
         let size = meta
             .size
@@ -427,6 +479,14 @@ impl Access for GcsBackend {
         }
 
         m.set_last_modified(parse_datetime_from_rfc3339(&meta.updated)?);
+        if let Some(custom_time) = &meta.custom_time {
+            m.set_custom_time(parse_datetime_from_rfc3339(custom_time)?);
+        }
+        set_server_side_encryption(
+            &mut m,
+            meta.kms_key_name.as_deref(),
+            meta.customer_encryption.as_ref(),
+        );
 
         if !meta.metadata.is_empty() {
             m.with_user_metadata(meta.metadata);
@@ -485,12 +545,22 @@ impl Access for GcsBackend {
     }
 
     async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.gcs_copy_object(from, to).await?;
+        let mut rewrite_token = None;
+        loop {
+            let resp = self
+                .core
+                .gcs_rewrite_object(from, to, rewrite_token.as_deref())
+                .await?;
+            if !resp.status().is_success() {
+                return Err(parse_error(resp));
+            }
 
-        if resp.status().is_success() {
-            Ok(RpCopy::default())
-        } else {
-            Err(parse_error(resp))
+            let output: RewriteResponse = serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error)?;
+            if output.done {
+                return Ok(RpCopy::default());
+            }
+            rewrite_token = output.rewrite_token;
         }
     }
 
@@ -517,6 +587,13 @@ impl Access for GcsBackend {
         )))
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        let mut req = args.into_request();
+        self.core.sign(&mut req).await?;
+
+        Ok(RpSign::new(SignedRequest::new(req)))
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let ops = args.into_operation();
         if ops.len() > 100 {
@@ -597,6 +674,10 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
     md5_hash: String,
+    /// Content crc32c checksum in base64 encoded big-endian.
+    ///
+    /// For example: `"crc32c": "j/un9g=="`
+    crc32c: String,
     /// Content type of this object.
     ///
     /// For example: `"contentType": "image/png",`
@@ -605,6 +686,18 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"metadata" : { "my-key": "my-value" }`
     metadata: HashMap<String, String>,
+    /// User-specified timestamp of this object in RFC3339 format.
+    ///
+    /// For example: `"customTime": "2022-08-15T00:00:00Z"`
+    custom_time: Option<String>,
+    /// Cloud KMS key used to encrypt this object.
+    ///
+    /// For example: `"kmsKeyName": "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1"`
+    kms_key_name: Option<String>,
+    /// Customer-supplied encryption key info of this object.
+    ///
+    /// For example: `"customerEncryption": { "encryptionAlgorithm": "AES256", "keySha256": "..." }`
+    customer_encryption: Option<CustomerEncryption>,
 }
 
 #[cfg(test)]
@@ -631,6 +724,7 @@ mod tests {
   "timeCreated": "2022-08-15T11:33:34.866Z",
   "updated": "2022-08-15T11:33:34.866Z",
   "timeStorageClassUpdated": "2022-08-15T11:33:34.866Z",
+  "customTime": "2022-08-15T00:00:00Z",
   "metadata" : {
     "location" : "everywhere"
   }
@@ -642,8 +736,10 @@ mod tests {
         assert_eq!(meta.size, "56535");
         assert_eq!(meta.updated, "2022-08-15T11:33:34.866Z");
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
+        assert_eq!(meta.crc32c, "j/un9g==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.custom_time.as_deref(), Some("2022-08-15T00:00:00Z"));
         assert_eq!(
             meta.metadata,
             HashMap::from_iter([("location".to_string(), "everywhere".to_string())])
@@ -45,6 +45,9 @@ pub struct GcsConfig {
     pub predefined_acl: Option<String>,
     /// The default storage class used by gcs.
     pub default_storage_class: Option<String>,
+    /// The base64 encoded AES-256 customer-supplied encryption key, objects
+    /// will be encrypted with it on write and it's required to read them.
+    pub customer_encryption_key: Option<String>,
     /// Allow opendal to send requests without signing when credentials are not
     /// loaded.
     pub allow_anonymous: bool,
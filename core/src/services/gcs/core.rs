@@ -23,12 +23,16 @@ use std::time::Duration;
 
 use backon::ExponentialBuilder;
 use backon::Retryable;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Bytes;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::HOST;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderName;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use once_cell::sync::Lazy;
@@ -39,6 +43,8 @@ use reqsign::GoogleToken;
 use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 
 use super::uri::percent_encode_path;
 use crate::raw::*;
@@ -49,6 +55,15 @@ pub mod constants {
     pub const X_GOOG_ACL: &str = "x-goog-acl";
     pub const X_GOOG_STORAGE_CLASS: &str = "x-goog-storage-class";
     pub const X_GOOG_META_PREFIX: &str = "x-goog-meta-";
+    pub const X_GOOG_CUSTOM_TIME: &str = "x-goog-custom-time";
+    pub const X_GOOG_ENCRYPTION_ALGORITHM: &str = "x-goog-encryption-algorithm";
+    pub const X_GOOG_ENCRYPTION_KEY: &str = "x-goog-encryption-key";
+    pub const X_GOOG_ENCRYPTION_KEY_SHA256: &str = "x-goog-encryption-key-sha256";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_ALGORITHM: &str =
+        "x-goog-copy-source-encryption-algorithm";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_KEY: &str = "x-goog-copy-source-encryption-key";
+    pub const X_GOOG_COPY_SOURCE_ENCRYPTION_KEY_SHA256: &str =
+        "x-goog-copy-source-encryption-key-sha256";
 }
 
 pub struct GcsCore {
@@ -65,6 +80,7 @@ pub struct GcsCore {
 
     pub predefined_acl: Option<String>,
     pub default_storage_class: Option<String>,
+    pub customer_key: Option<CustomerEncryptionKey>,
 
     pub allow_anonymous: bool,
 }
@@ -171,6 +187,81 @@ impl GcsCore {
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
         self.client.send(req).await
     }
+
+    /// Insert the headers of customer-supplied encryption key.
+    ///
+    /// The key of the operation takes precedence over the one of the builder.
+    pub fn insert_encryption_headers(
+        &self,
+        req: http::request::Builder,
+        customer_key: Option<&CustomerEncryptionKey>,
+    ) -> http::request::Builder {
+        match customer_key.or(self.customer_key.as_ref()) {
+            Some(key) => insert_customer_key_headers(
+                req,
+                key,
+                [
+                    X_GOOG_ENCRYPTION_ALGORITHM,
+                    X_GOOG_ENCRYPTION_KEY,
+                    X_GOOG_ENCRYPTION_KEY_SHA256,
+                ],
+            ),
+            None => req,
+        }
+    }
+
+    /// Insert the headers of customer-supplied encryption key of the copy
+    /// source, which is always the key of the builder.
+    pub fn insert_copy_source_encryption_headers(
+        &self,
+        req: http::request::Builder,
+    ) -> http::request::Builder {
+        match &self.customer_key {
+            Some(key) => insert_customer_key_headers(
+                req,
+                key,
+                [
+                    X_GOOG_COPY_SOURCE_ENCRYPTION_ALGORITHM,
+                    X_GOOG_COPY_SOURCE_ENCRYPTION_KEY,
+                    X_GOOG_COPY_SOURCE_ENCRYPTION_KEY_SHA256,
+                ],
+            ),
+            None => req,
+        }
+    }
+}
+
+/// Insert the algorithm, key and key sha256 of `key` as sensitive headers
+/// of `names`.
+fn insert_customer_key_headers(
+    mut req: http::request::Builder,
+    key: &CustomerEncryptionKey,
+    names: [&'static str; 3],
+) -> http::request::Builder {
+    let key_sha256 = format_customer_key_sha256(key);
+    for (name, v) in names
+        .into_iter()
+        .zip([key.algorithm(), key.key(), key_sha256.as_str()])
+    {
+        req = match HeaderValue::from_str(v) {
+            Ok(mut v) => {
+                v.set_sensitive(true);
+                req.header(HeaderName::from_static(name), v)
+            }
+            // Let the builder report the invalid value.
+            Err(_) => req.header(HeaderName::from_static(name), v),
+        };
+    }
+    req
+}
+
+/// GCS identifies customer-supplied encryption keys by the base64 encoded
+/// SHA256 of the raw key instead of MD5.
+fn format_customer_key_sha256(key: &CustomerEncryptionKey) -> String {
+    let raw = BASE64_STANDARD
+        .decode(key.key())
+        .expect("customer key must be base64 encoded");
+    BASE64_STANDARD.encode(Sha256::digest(raw))
 }
 
 impl GcsCore {
@@ -197,6 +288,7 @@ impl GcsCore {
         if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
+        req = self.insert_encryption_headers(req, args.customer_key());
         if !range.is_full() {
             req = req.header(http::header::RANGE, range.to_header());
         }
@@ -252,6 +344,7 @@ impl GcsCore {
             cache_control: op.cache_control(),
             content_type: op.content_type(),
             metadata: op.user_metadata(),
+            custom_time: op.custom_time().map(|v| v.to_rfc3339()),
         };
 
         let mut url = format!(
@@ -280,6 +373,7 @@ impl GcsCore {
         let mut req = Request::post(&url);
 
         req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
+        req = self.insert_encryption_headers(req, op.customer_key());
 
         if request_metadata.is_empty() {
             // If the metadata is empty, we do not set any `Content-Type` header,
@@ -303,7 +397,8 @@ impl GcsCore {
             let media_part = FormDataPart::new("media").content(body);
             multipart = multipart.part(media_part);
 
-            let req = multipart.apply(Request::post(url))?;
+            let req = self.insert_encryption_headers(Request::post(url), op.customer_key());
+            let req = multipart.apply(req)?;
             Ok(req)
         }
     }
@@ -331,6 +426,10 @@ impl GcsCore {
             req = req.header(CONTENT_TYPE, content_type);
         }
 
+        if let Some(custom_time) = args.custom_time() {
+            req = req.header(X_GOOG_CUSTOM_TIME, custom_time.to_rfc3339());
+        }
+
         if let Some(acl) = &self.predefined_acl {
             req = req.header(X_GOOG_ACL, acl);
         }
@@ -364,6 +463,8 @@ impl GcsCore {
             req = req.header(IF_MATCH, if_match);
         }
 
+        req = self.insert_encryption_headers(req, args.customer_key());
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -448,21 +549,110 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    /// Rewrite object `from` to `to`, large objects may need multiple calls
+    /// with the `rewrite_token` of the last response.
+    pub async fn gcs_rewrite_object(
+        &self,
+        from: &str,
+        to: &str,
+        rewrite_token: Option<&str>,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let dest = build_abs_path(&self.root, to);
 
-        let req_uri = format!(
-            "{}/storage/v1/b/{}/o/{}/copyTo/b/{}/o/{}",
+        let mut req_uri = format!(
+            "{}/storage/v1/b/{}/o/{}/rewriteTo/b/{}/o/{}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&source),
             self.bucket,
             percent_encode_path(&dest)
         );
+        if let Some(token) = rewrite_token {
+            write!(&mut req_uri, "?rewriteToken={}", percent_encode_path(token)).unwrap();
+        }
+
+        let mut req = Request::post(req_uri).header(CONTENT_LENGTH, 0);
+        req = self.insert_copy_source_encryption_headers(req);
+        req = self.insert_encryption_headers(req, None);
+
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn gcs_compose_object(&self, sources: &[&str], to: &str) -> Result<Response<Buffer>> {
+        let dest = build_abs_path(&self.root, to);
+
+        let req_uri = format!(
+            "{}/storage/v1/b/{}/o/{}/compose",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&dest)
+        );
+
+        let body = ComposeRequest {
+            source_objects: sources
+                .iter()
+                .map(|p| ComposeRequestSourceObject {
+                    name: build_abs_path(&self.root, p),
+                })
+                .collect(),
+        };
+        let body = serde_json::to_vec(&body).map_err(new_json_serialize_error)?;
+
+        let req = Request::post(req_uri)
+            .header(CONTENT_LENGTH, body.len())
+            .header(CONTENT_TYPE, "application/json");
+        let mut req = self
+            .insert_encryption_headers(req, None)
+            .body(Buffer::from(body))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
 
-        let mut req = Request::post(req_uri)
-            .header(CONTENT_LENGTH, 0)
+    pub async fn gcs_list_notifications(&self) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/storage/v1/b/{}/notificationConfigs",
+            self.endpoint, self.bucket
+        );
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn gcs_insert_notification(&self, body: Buffer) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/storage/v1/b/{}/notificationConfigs",
+            self.endpoint, self.bucket
+        );
+
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, body.len())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn gcs_delete_notification(&self, id: &str) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/storage/v1/b/{}/notificationConfigs/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(id)
+        );
+
+        let mut req = Request::delete(&url)
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
 
@@ -518,15 +708,23 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_initiate_multipart_upload(&self, path: &str) -> Result<Response<Buffer>> {
+    pub async fn gcs_initiate_multipart_upload(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}/{}?uploads", self.endpoint, self.bucket, p);
 
-        let mut req = Request::post(&url)
-            .header(CONTENT_LENGTH, 0)
-            .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+        let mut req = Request::post(&url).header(CONTENT_LENGTH, 0);
+
+        if let Some(custom_time) = args.custom_time() {
+            req = req.header(X_GOOG_CUSTOM_TIME, custom_time.to_rfc3339());
+        }
+        req = self.insert_encryption_headers(req, args.customer_key());
+
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -538,6 +736,7 @@ impl GcsCore {
         upload_id: &str,
         part_number: usize,
         size: u64,
+        customer_key: Option<&CustomerEncryptionKey>,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
@@ -554,6 +753,7 @@ impl GcsCore {
         let mut req = Request::put(&url);
 
         req = req.header(CONTENT_LENGTH, size);
+        req = self.insert_encryption_headers(req, customer_key);
 
         let mut req = req.body(body).map_err(new_request_build_error)?;
 
@@ -628,6 +828,8 @@ pub struct InsertRequestMetadata<'a> {
     cache_control: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<&'a HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_time: Option<String>,
 }
 
 impl InsertRequestMetadata<'_> {
@@ -636,8 +838,35 @@ impl InsertRequestMetadata<'_> {
             && self.storage_class.is_none()
             && self.cache_control.is_none()
             && self.metadata.is_none()
+            && self.custom_time.is_none()
     }
 }
+
+/// Response JSON of GCS rewrite objects API.
+///
+/// refer to https://cloud.google.com/storage/docs/json_api/v1/objects/rewrite for details
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RewriteResponse {
+    pub done: bool,
+    pub rewrite_token: Option<String>,
+}
+
+/// Request JSON of GCS compose objects API.
+///
+/// refer to https://cloud.google.com/storage/docs/json_api/v1/objects/compose for details
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ComposeRequest {
+    pub source_objects: Vec<ComposeRequestSourceObject>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ComposeRequestSourceObject {
+    pub name: String,
+}
+
 /// Response JSON from GCS list objects API.
 ///
 /// refer to https://cloud.google.com/storage/docs/json_api/v1/objects/list for details
@@ -663,8 +892,43 @@ pub struct ListResponseItem {
     // metadata
     pub etag: String,
     pub md5_hash: String,
+    pub crc32c: String,
     pub updated: String,
     pub content_type: String,
+    pub custom_time: Option<String>,
+    pub kms_key_name: Option<String>,
+    pub customer_encryption: Option<CustomerEncryption>,
+}
+
+/// The customer-supplied encryption key info of an object.
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CustomerEncryption {
+    pub encryption_algorithm: String,
+    pub key_sha256: String,
+}
+
+/// Set the server-side encryption status of an object.
+///
+/// GCS always encrypts objects with 256-bit AES, by google-managed keys if
+/// neither `kmsKeyName` nor `customerEncryption` is returned.
+pub fn set_server_side_encryption(
+    meta: &mut Metadata,
+    kms_key_name: Option<&str>,
+    customer_encryption: Option<&CustomerEncryption>,
+) {
+    match customer_encryption {
+        Some(v) => {
+            meta.set_server_side_encryption(&v.encryption_algorithm);
+            meta.set_server_side_encryption_key_id(&v.key_sha256);
+        }
+        None => {
+            meta.set_server_side_encryption("AES256");
+            if let Some(v) = kms_key_name {
+                meta.set_server_side_encryption_key_id(v);
+            }
+        }
+    }
 }
 
 /// Result of CreateMultipartUpload
@@ -694,6 +958,50 @@ pub struct CompleteMultipartUploadRequestPart {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_serialize_compose_request() {
+        let req = ComposeRequest {
+            source_objects: vec![
+                ComposeRequestSourceObject {
+                    name: "logs/1".to_string(),
+                },
+                ComposeRequestSourceObject {
+                    name: "logs/2".to_string(),
+                },
+            ],
+        };
+
+        let v = serde_json::to_string(&req).expect("must succeed");
+        assert_eq!(
+            v,
+            r#"{"sourceObjects":[{"name":"logs/1"},{"name":"logs/2"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_format_customer_key_sha256() {
+        let key = CustomerEncryptionKey::aes256(&[0; 32]);
+        assert_eq!(
+            format_customer_key_sha256(&key),
+            "Zmh6rfhivXdsj8GLjp+OIAiXFIVu4jOzkCpZHQ1fKSU="
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rewrite_response() {
+        let content = r#"{
+  "kind": "storage#rewriteResponse",
+  "totalBytesRewritten": "1048576",
+  "objectSize": "10000000000",
+  "done": false,
+  "rewriteToken": "token"
+}"#;
+
+        let output: RewriteResponse = serde_json::from_str(content).expect("must succeed");
+        assert!(!output.done);
+        assert_eq!(output.rewrite_token.as_deref(), Some("token"));
+    }
+
     #[test]
     fn test_deserialize_list_response() {
         let content = r#"
@@ -753,6 +1061,7 @@ mod tests {
         assert_eq!(output.items[0].name, "1.png");
         assert_eq!(output.items[0].size, "56535");
         assert_eq!(output.items[0].md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
+        assert_eq!(output.items[0].crc32c, "j/un9g==");
         assert_eq!(output.items[0].etag, "CKWasoTgyPkCEAE=");
         assert_eq!(output.items[0].updated, "2022-08-15T11:33:34.866Z");
         assert_eq!(output.items[1].name, "2.png");
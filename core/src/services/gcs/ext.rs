@@ -0,0 +1,227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use bytes::Buf;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::backend::GcsBuilder;
+use super::core::GcsCore;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The [notification configuration](https://cloud.google.com/storage/docs/json_api/v1/notifications)
+/// of a bucket, which publishes changes of objects to a Pub/Sub topic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GcsNotification {
+    /// The id of the notification, assigned by GCS.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// The Pub/Sub topic, like `//pubsub.googleapis.com/projects/{project}/topics/{topic}`.
+    pub topic: String,
+    /// The event types to publish, like `OBJECT_FINALIZE`. All events will be
+    /// published if empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
+    /// Only objects with this prefix will be published.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub object_name_prefix: String,
+    /// The format of the message payload, `JSON_API_V1` or `NONE`.
+    pub payload_format: String,
+    /// Attributes attached to every message.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub custom_attributes: HashMap<String, String>,
+}
+
+/// The max number of source objects in one compose request.
+const MAX_COMPOSE_SOURCES: usize = 32;
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct GcsNotificationList {
+    items: Vec<GcsNotification>,
+}
+
+/// GcsExtension provides gcs-specific operations that are not covered by
+/// [`Operator`].
+///
+/// Get it via `op.as_service::<services::Gcs>()`.
+#[derive(Clone)]
+pub struct GcsExtension {
+    core: Arc<GcsCore>,
+}
+
+impl Debug for GcsExtension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcsExtension")
+            .field("core", &self.core)
+            .finish()
+    }
+}
+
+impl GcsExtension {
+    pub(super) fn new(core: Arc<GcsCore>) -> Self {
+        Self { core }
+    }
+
+    /// Concatenate `sources` into `target` in order via
+    /// [compose](https://cloud.google.com/storage/docs/json_api/v1/objects/compose)
+    /// without downloading the data, `target` will be overwritten.
+    ///
+    /// GCS composes up to 32 objects at once, more sources are composed in
+    /// batches by appending the next 31 sources to `target` every time. A
+    /// composite object can have at most 1024 components in total.
+    ///
+    /// Sources are kept, `target` could be one of them.
+    pub async fn compose(&self, sources: &[&str], target: &str) -> Result<()> {
+        if sources.is_empty() {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "sources of compose must not be empty",
+            )
+            .with_operation("GcsExtension::compose")
+            .with_context("target", target));
+        }
+
+        let (first, rest) = sources.split_at(sources.len().min(MAX_COMPOSE_SOURCES));
+        self.compose_once(first, target).await?;
+        for batch in rest.chunks(MAX_COMPOSE_SOURCES - 1) {
+            let mut batch_sources = Vec::with_capacity(batch.len() + 1);
+            batch_sources.push(target);
+            batch_sources.extend_from_slice(batch);
+            self.compose_once(&batch_sources, target).await?;
+        }
+        Ok(())
+    }
+
+    async fn compose_once(&self, sources: &[&str], target: &str) -> Result<()> {
+        let resp = self.core.gcs_compose_object(sources, target).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)
+                .with_operation("GcsExtension::compose")
+                .with_context("target", target)),
+        }
+    }
+
+    /// List the notification configurations of the bucket.
+    pub async fn list_notifications(&self) -> Result<Vec<GcsNotification>> {
+        let resp = self.core.gcs_list_notifications().await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let list: GcsNotificationList = serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+                Ok(list.items)
+            }
+            _ => Err(parse_error(resp).with_operation("GcsExtension::list_notifications")),
+        }
+    }
+
+    /// Create a notification configuration on the bucket, returns the
+    /// created one with its id.
+    ///
+    /// `payload_format` will be `JSON_API_V1` if not set.
+    pub async fn create_notification(
+        &self,
+        mut notification: GcsNotification,
+    ) -> Result<GcsNotification> {
+        if notification.payload_format.is_empty() {
+            notification.payload_format = "JSON_API_V1".to_string();
+        }
+        let body = serde_json::to_vec(&notification).map_err(new_json_serialize_error)?;
+
+        let resp = self
+            .core
+            .gcs_insert_notification(Buffer::from(body))
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error),
+            _ => Err(parse_error(resp).with_operation("GcsExtension::create_notification")),
+        }
+    }
+
+    /// Delete the notification configuration of given id.
+    pub async fn delete_notification(&self, id: &str) -> Result<()> {
+        let resp = self.core.gcs_delete_notification(id).await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)
+                .with_operation("GcsExtension::delete_notification")
+                .with_context("id", id)),
+        }
+    }
+}
+
+impl ServiceExtension for GcsBuilder {
+    type Extension = GcsExtension;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_notification() {
+        let notification = GcsNotification {
+            topic: "//pubsub.googleapis.com/projects/p/topics/t".to_string(),
+            event_types: vec!["OBJECT_FINALIZE".to_string()],
+            payload_format: "JSON_API_V1".to_string(),
+            ..Default::default()
+        };
+
+        let v = serde_json::to_string(&notification).expect("must succeed");
+        assert_eq!(
+            v,
+            r#"{"topic":"//pubsub.googleapis.com/projects/p/topics/t","event_types":["OBJECT_FINALIZE"],"payload_format":"JSON_API_V1"}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_notification_list() {
+        let bs = r#"{
+            "kind": "storage#notifications",
+            "items": [{
+                "kind": "storage#notification",
+                "id": "7",
+                "topic": "//pubsub.googleapis.com/projects/p/topics/t",
+                "payload_format": "NONE",
+                "object_name_prefix": "logs/",
+                "etag": "7",
+                "selfLink": "https://www.googleapis.com/storage/v1/b/b/notificationConfigs/7"
+            }]
+        }"#;
+
+        let list: GcsNotificationList = serde_json::from_str(bs).expect("must succeed");
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].id, "7");
+        assert_eq!(list.items[0].object_name_prefix, "logs/");
+        assert!(list.items[0].event_types.is_empty());
+    }
+}
@@ -114,6 +114,9 @@ impl oio::PageList for GcsLister {
 
             // set metadata fields
             meta.set_content_md5(object.md5_hash.as_str());
+            if !object.crc32c.is_empty() {
+                meta.set_content_crc32c(&object.crc32c);
+            }
             meta.set_etag(object.etag.as_str());
 
             let size = object.size.parse().map_err(|e| {
@@ -125,6 +128,14 @@ impl oio::PageList for GcsLister {
             }
 
             meta.set_last_modified(parse_datetime_from_rfc3339(object.updated.as_str())?);
+            if let Some(custom_time) = &object.custom_time {
+                meta.set_custom_time(parse_datetime_from_rfc3339(custom_time)?);
+            }
+            set_server_side_encryption(
+                &mut meta,
+                object.kms_key_name.as_deref(),
+                object.customer_encryption.as_ref(),
+            );
 
             let de = oio::Entry::with(path, meta);
 
@@ -31,5 +31,12 @@ mod backend;
 #[cfg(feature = "services-gcs")]
 pub use backend::GcsBuilder as Gcs;
 
+#[cfg(all(feature = "services-gcs", feature = "extensions"))]
+mod ext;
+#[cfg(all(feature = "services-gcs", feature = "extensions"))]
+pub use ext::GcsExtension;
+#[cfg(all(feature = "services-gcs", feature = "extensions"))]
+pub use ext::GcsNotification;
+
 mod config;
 pub use config::GcsConfig;
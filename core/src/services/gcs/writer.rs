@@ -46,7 +46,7 @@ impl GcsWriter {
 }
 
 impl oio::MultipartWrite for GcsWriter {
-    async fn write_once(&self, _: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, _: u64, body: Buffer) -> Result<Metadata> {
         let size = body.len() as u64;
         let mut req = self.core.gcs_insert_object_request(
             &percent_encode_path(&self.path),
@@ -62,7 +62,7 @@ impl oio::MultipartWrite for GcsWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp)),
         }
     }
@@ -70,7 +70,7 @@ impl oio::MultipartWrite for GcsWriter {
     async fn initiate_part(&self) -> Result<String> {
         let resp = self
             .core
-            .gcs_initiate_multipart_upload(&percent_encode_path(&self.path))
+            .gcs_initiate_multipart_upload(&percent_encode_path(&self.path), &self.op)
             .await?;
 
         if !resp.status().is_success() {
@@ -95,7 +95,14 @@ impl oio::MultipartWrite for GcsWriter {
 
         let resp = self
             .core
-            .gcs_upload_part(&self.path, upload_id, part_number, size, body)
+            .gcs_upload_part(
+                &self.path,
+                upload_id,
+                part_number,
+                size,
+                self.op.customer_key(),
+                body,
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -118,7 +125,11 @@ impl oio::MultipartWrite for GcsWriter {
         })
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| CompleteMultipartUploadRequestPart {
@@ -135,7 +146,7 @@ impl oio::MultipartWrite for GcsWriter {
         if !resp.status().is_success() {
             return Err(parse_error(resp));
         }
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort_part(&self, upload_id: &str) -> Result<()> {
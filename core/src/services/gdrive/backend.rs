@@ -20,20 +20,29 @@ use std::sync::Arc;
 
 use bytes::Buf;
 use bytes::Bytes;
-use chrono::Utc;
+use futures::stream;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use serde_json::json;
 
+use super::core::append_drive_params;
 use super::core::GdriveCore;
 use super::core::GdriveFile;
+use super::core::GdriveRevision;
+use super::error::is_not_downloadable;
 use super::error::parse_error;
 use super::lister::GdriveLister;
+use super::lister::GdriveListers;
+use super::lister::GdriveRevisionLister;
 use super::writer::GdriveWriter;
 use crate::raw::*;
 use crate::*;
 
+/// Drive starts to reject ranged downloads of the same file with
+/// `403 rateLimitExceeded` if too many of them are sent at once.
+const DEFAULT_READ_MAX_CONCURRENT: usize = 4;
+
 #[derive(Clone, Debug)]
 pub struct GdriveBackend {
     pub core: Arc<GdriveCore>,
@@ -41,8 +50,8 @@ pub struct GdriveBackend {
 
 impl Access for GdriveBackend {
     type Reader = HttpBody;
-    type Writer = oio::OneShotWriter<GdriveWriter>;
-    type Lister = oio::PageLister<GdriveLister>;
+    type Writer = GdriveWriter;
+    type Lister = GdriveListers;
     type BlockingReader = ();
     type BlockingWriter = ();
     type BlockingLister = ();
@@ -53,12 +62,21 @@ impl Access for GdriveBackend {
             .set_root(&self.core.root)
             .set_native_capability(Capability {
                 stat: true,
+                stat_with_version: true,
+                stat_has_version: true,
 
                 read: true,
+                read_with_version: true,
+                read_max_concurrent: Some(DEFAULT_READ_MAX_CONCURRENT),
 
                 list: true,
+                list_with_limit: true,
+                list_with_recursive: true,
+                list_with_version: true,
+                list_with_query: true,
 
                 write: true,
+                write_can_multi: true,
 
                 create_dir: true,
                 delete: true,
@@ -77,37 +95,30 @@ impl Access for GdriveBackend {
         Ok(RpCreateDir::default())
     }
 
-    async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
-        let resp = self.core.gdrive_stat(path).await?;
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let resp = self.core.gdrive_stat(path, args.version()).await?;
 
         if resp.status() != StatusCode::OK {
             return Err(parse_error(resp));
         }
 
         let bs = resp.into_body();
+        if args.version().is_some() {
+            let revision: GdriveRevision =
+                serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+            return Ok(RpStat::new(revision.to_metadata()?));
+        }
         let gdrive_file: GdriveFile =
             serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
 
-        if gdrive_file.mime_type == "application/vnd.google-apps.folder" {
-            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
-        };
-
-        let mut meta = Metadata::new(EntryMode::FILE);
-        if let Some(v) = gdrive_file.size {
-            meta = meta.with_content_length(v.parse::<u64>().map_err(|e| {
-                Error::new(ErrorKind::Unexpected, "parse content length").set_source(e)
-            })?);
-        }
-        if let Some(v) = gdrive_file.modified_time {
-            meta = meta.with_last_modified(v.parse::<chrono::DateTime<Utc>>().map_err(|e| {
-                Error::new(ErrorKind::Unexpected, "parse last modified time").set_source(e)
-            })?);
-        }
-        Ok(RpStat::new(meta))
+        Ok(RpStat::new(gdrive_file.to_metadata()?))
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.core.gdrive_get(path, args.range()).await?;
+        let resp = self
+            .core
+            .gdrive_get(path, args.range(), args.version())
+            .await?;
 
         let status = resp.status();
         match status {
@@ -115,7 +126,19 @@ impl Access for GdriveBackend {
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
-                Err(parse_error(Response::from_parts(part, buf)))
+                let resp = Response::from_parts(part, buf);
+                // Revisions of google workspace documents can't be exported.
+                if !is_not_downloadable(&resp) || args.version().is_some() {
+                    return Err(parse_error(resp));
+                }
+
+                // Google workspace documents can't be downloaded, export them instead.
+                let buf = self.core.gdrive_export(path, args.range()).await?;
+                let size = buf.len() as u64;
+                Ok((
+                    RpRead::new().with_size(Some(size)),
+                    HttpBody::new(stream::iter(Some(Ok(buf))), Some(size)),
+                ))
             }
         }
     }
@@ -125,17 +148,17 @@ impl Access for GdriveBackend {
 
         // As Google Drive allows files have the same name, we need to check if the file exists.
         // If the file exists, we will keep its ID and update it.
-        let file_id = self.core.path_cache.get(&path).await?;
+        let file_id = self.core.resolve_path(&path).await?;
 
         Ok((
             RpWrite::default(),
-            oio::OneShotWriter::new(GdriveWriter::new(self.core.clone(), path, file_id)),
+            GdriveWriter::new(self.core.clone(), path, file_id),
         ))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let path = build_abs_path(&self.core.root, path);
-        let file_id = self.core.path_cache.get(&path).await?;
+        let file_id = self.core.resolve_path(&path).await?;
         let file_id = if let Some(id) = file_id {
             id
         } else {
@@ -153,30 +176,44 @@ impl Access for GdriveBackend {
         Ok(RpDelete::default())
     }
 
-    async fn list(&self, path: &str, _args: OpList) -> Result<(RpList, Self::Lister)> {
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         let path = build_abs_path(&self.core.root, path);
-        let l = GdriveLister::new(path, self.core.clone());
-        Ok((RpList::default(), oio::PageLister::new(l)))
+        let l = if args.version() {
+            TwoWays::Two(oio::PageLister::new(GdriveRevisionLister::new(
+                path,
+                self.core.clone(),
+                &args,
+            )))
+        } else {
+            TwoWays::One(oio::PageLister::new(GdriveLister::new(
+                path,
+                self.core.clone(),
+                &args,
+            )))
+        };
+        Ok((RpList::default(), l))
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
         let from = build_abs_path(&self.core.root, from);
 
-        let from_file_id = self.core.path_cache.get(&from).await?.ok_or(Error::new(
+        let from_file_id = self.core.resolve_target(&from).await?.ok_or(Error::new(
             ErrorKind::NotFound,
             "the file to copy does not exist",
         ))?;
 
         let to_name = get_basename(to);
         let to_path = build_abs_path(&self.core.root, to);
-        let to_parent_id = self
-            .core
-            .path_cache
-            .ensure_dir(get_parent(&to_path))
-            .await?;
+        let to_parent_id = self.core.target_id(
+            &self
+                .core
+                .path_cache
+                .ensure_dir(get_parent(&to_path))
+                .await?,
+        );
 
         // copy will overwrite `to`, delete it if exist
-        if let Some(id) = self.core.path_cache.get(&to_path).await? {
+        if let Some(id) = self.core.resolve_path(&to_path).await? {
             let resp = self.core.gdrive_trash(&id).await?;
             let status = resp.status();
             if status != StatusCode::OK {
@@ -186,10 +223,11 @@ impl Access for GdriveBackend {
             self.core.path_cache.remove(&to_path).await;
         }
 
-        let url = format!(
+        let mut url = format!(
             "https://www.googleapis.com/drive/v3/files/{}/copy",
             from_file_id
         );
+        append_drive_params(&mut url, self.core.drive_id.as_deref(), false);
 
         let request_body = &json!({
             "name": to_name,
@@ -215,7 +253,7 @@ impl Access for GdriveBackend {
         let target = build_abs_path(&self.core.root, to);
 
         // rename will overwrite `to`, delete it if exist
-        if let Some(id) = self.core.path_cache.get(&target).await? {
+        if let Some(id) = self.core.resolve_path(&target).await? {
             let resp = self.core.gdrive_trash(&id).await?;
             let status = resp.status();
             if status != StatusCode::OK {
@@ -15,33 +15,65 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use chrono::DateTime;
 use chrono::Utc;
 use log::debug;
 use tokio::sync::Mutex;
 
 use super::backend::GdriveBackend;
+use super::lister::MAX_PAGE_SIZE;
 use crate::raw::normalize_root;
 use crate::raw::Access;
 use crate::raw::HttpClient;
+use crate::raw::PathCacheStore;
+use crate::raw::PathCacheStoreDyn;
 use crate::raw::PathCacher;
 use crate::services::gdrive::core::GdriveCore;
 use crate::services::gdrive::core::GdrivePathQuery;
+use crate::services::gdrive::core::GdriveServiceAccount;
+use crate::services::gdrive::core::GdriveShortcuts;
 use crate::services::gdrive::core::GdriveSigner;
 use crate::services::GdriveConfig;
 use crate::Scheme;
 use crate::*;
 
+/// Chunks uploaded to resumable upload sessions must be a multiple of 256 KiB.
+const UPLOAD_CHUNK_SIZE_UNIT: usize = 256 * 1024;
+const DEFAULT_UPLOAD_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const DEFAULT_LIST_PAGE_SIZE: usize = 100;
+
+/// The default formats to export google workspace documents into.
+const DEFAULT_EXPORT_FORMATS: &[(&str, &str)] = &[
+    (
+        "application/vnd.google-apps.document",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    (
+        "application/vnd.google-apps.spreadsheet",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    (
+        "application/vnd.google-apps.presentation",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("application/vnd.google-apps.drawing", "image/png"),
+];
+
 impl Configurator for GdriveConfig {
     type Builder = GdriveBuilder;
     fn into_builder(self) -> Self::Builder {
         GdriveBuilder {
             config: self,
             http_client: None,
+            path_cache_store: None,
         }
     }
 }
@@ -53,6 +85,7 @@ pub struct GdriveBuilder {
     config: GdriveConfig,
 
     http_client: Option<HttpClient>,
+    path_cache_store: Option<Arc<dyn PathCacheStoreDyn>>,
 }
 
 impl Debug for GdriveBuilder {
@@ -116,6 +149,146 @@ impl GdriveBuilder {
         self
     }
 
+    /// Set the credential of the service account, which is the JSON key
+    /// encoded in base64.
+    ///
+    /// Service accounts are used to access GoogleDrive without the
+    /// interactive OAuth 2.0 Flow, for example in headless pipelines.
+    pub fn credential(mut self, credential: &str) -> Self {
+        self.config.credential = if credential.is_empty() {
+            None
+        } else {
+            Some(credential.to_string())
+        };
+
+        self
+    }
+
+    /// Set the path to the JSON key of the service account.
+    pub fn credential_path(mut self, path: &str) -> Self {
+        self.config.credential_path = if path.is_empty() {
+            None
+        } else {
+            Some(path.to_string())
+        };
+
+        self
+    }
+
+    /// Set the user to impersonate via domain-wide delegation.
+    ///
+    /// This requires the service account to be granted domain-wide
+    /// authority in the Google Workspace admin console.
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.config.subject = if subject.is_empty() {
+            None
+        } else {
+            Some(subject.to_string())
+        };
+
+        self
+    }
+
+    /// Set the id of the shared drive to access.
+    ///
+    /// Files in "My Drive" will be accessed if this is not set.
+    pub fn drive_id(mut self, drive_id: &str) -> Self {
+        self.config.drive_id = if drive_id.is_empty() {
+            None
+        } else {
+            Some(drive_id.to_string())
+        };
+
+        self
+    }
+
+    /// Set the format to export google workspace documents of given mime
+    /// type into when reading.
+    ///
+    /// By default, documents are exported to docx, spreadsheets to xlsx,
+    /// presentations to pptx and drawings to png.
+    ///
+    /// ```
+    /// use opendal::services::Gdrive;
+    ///
+    /// let builder = Gdrive::default().export_format(
+    ///     "application/vnd.google-apps.spreadsheet",
+    ///     "text/csv",
+    /// );
+    /// ```
+    pub fn export_format(mut self, mime_type: &str, export_mime_type: &str) -> Self {
+        let format = format!("{mime_type}={export_mime_type}");
+        self.config.export_formats = match self.config.export_formats.take() {
+            Some(v) if !v.is_empty() => Some(format!("{v},{format}")),
+            _ => Some(format),
+        };
+        self
+    }
+
+    /// Set the size of chunks to upload large files with resumable upload
+    /// sessions.
+    ///
+    /// Files no larger than this size are uploaded in a single request.
+    /// Must be a multiple of 256 KiB. Default to 8 MiB.
+    pub fn upload_chunk_size(mut self, size: usize) -> Self {
+        self.config.upload_chunk_size = Some(size);
+        self
+    }
+
+    /// Set the number of entries to fetch in one list request.
+    ///
+    /// This is used when the limit of list is not specified. Must be between
+    /// 1 and 1000. Default to 100.
+    pub fn list_page_size(mut self, size: usize) -> Self {
+        self.config.list_page_size = Some(size);
+        self
+    }
+
+    /// Disable resolving shortcuts to the files they point to.
+    ///
+    /// By default, shortcuts behave like their targets in stat, read and
+    /// list. Shortcuts will be surfaced as symlink-like files after this is
+    /// called, whose target ids can be found in the user metadata
+    /// `shortcut_target_id`.
+    pub fn disable_shortcut_resolution(mut self) -> Self {
+        self.config.disable_shortcut_resolution = true;
+        self
+    }
+
+    /// Set the max number of path to id entries cached in memory.
+    ///
+    /// Default to 65536.
+    pub fn path_cache_capacity(mut self, capacity: u64) -> Self {
+        self.config.path_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Set the time to live of the cached path to id entries.
+    ///
+    /// Entries never expire by default, set this if the files could be
+    /// changed by others.
+    pub fn path_cache_ttl(mut self, ttl: Duration) -> Self {
+        if !ttl.is_zero() {
+            self.config.path_cache_ttl = Some(ttl);
+        }
+        self
+    }
+
+    /// Persist the path to id entries in given store, so that they can be
+    /// reused after the process restarts.
+    ///
+    /// An [`Operator`] can be used as the store directly, for example an
+    /// operator of the local fs. Don't share the same store between drives.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `PathCacheStore` could be
+    /// changed during minor updates.
+    pub fn path_cache_store(mut self, store: impl PathCacheStore) -> Self {
+        self.path_cache_store = Some(Arc::new(store));
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -145,14 +318,52 @@ impl Builder for GdriveBuilder {
             })?
         };
 
+        let service_account = match (&self.config.credential, &self.config.credential_path) {
+            (Some(credential), _) => {
+                let content = BASE64_STANDARD.decode(credential).map_err(|e| {
+                    Error::new(ErrorKind::ConfigInvalid, "credential is not valid base64")
+                        .with_context("service", Scheme::Gdrive)
+                        .set_source(e)
+                })?;
+                Some(
+                    GdriveServiceAccount::from_slice(&content)
+                        .map_err(|e| e.with_context("service", Scheme::Gdrive))?,
+                )
+            }
+            (None, Some(path)) => {
+                let content = std::fs::read(path).map_err(|e| {
+                    Error::new(ErrorKind::ConfigInvalid, "failed to read credential_path")
+                        .with_context("service", Scheme::Gdrive)
+                        .with_context("credential_path", path)
+                        .set_source(e)
+                })?;
+                Some(
+                    GdriveServiceAccount::from_slice(&content)
+                        .map_err(|e| e.with_context("service", Scheme::Gdrive))?,
+                )
+            }
+            (None, None) => None,
+        };
+        if self.config.subject.is_some() && service_account.is_none() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "subject can only be used with the credential of service account",
+            )
+            .with_context("service", Scheme::Gdrive));
+        }
+
         let mut signer = GdriveSigner::new(client.clone());
-        match (self.config.access_token, self.config.refresh_token) {
-            (Some(access_token), None) => {
+        match (
+            self.config.access_token,
+            self.config.refresh_token,
+            service_account,
+        ) {
+            (Some(access_token), None, None) => {
                 signer.access_token = access_token;
                 // We will never expire user specified access token.
                 signer.expires_in = DateTime::<Utc>::MAX_UTC;
             }
-            (None, Some(refresh_token)) => {
+            (None, Some(refresh_token), None) => {
                 let client_id = self.config.client_id.ok_or_else(|| {
                     Error::new(
                         ErrorKind::ConfigInvalid,
@@ -173,29 +384,110 @@ impl Builder for GdriveBuilder {
                 signer.client_id = client_id;
                 signer.client_secret = client_secret;
             }
-            (Some(_), Some(_)) => {
+            (None, None, Some(service_account)) => {
+                signer.service_account = Some(service_account);
+                signer.subject = self.config.subject;
+            }
+            (None, None, None) => {
                 return Err(Error::new(
                     ErrorKind::ConfigInvalid,
-                    "access_token and refresh_token cannot be set at the same time",
+                    "access_token, refresh_token or credential must be set",
                 )
                 .with_context("service", Scheme::Gdrive))
             }
-            (None, None) => {
+            _ => {
                 return Err(Error::new(
                     ErrorKind::ConfigInvalid,
-                    "access_token or refresh_token must be set",
+                    "only one of access_token, refresh_token and credential can be set",
                 )
                 .with_context("service", Scheme::Gdrive))
             }
         };
 
+        let mut export_formats: HashMap<String, String> = DEFAULT_EXPORT_FORMATS
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        for format in self
+            .config
+            .export_formats
+            .iter()
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            let (mime_type, export_mime_type) = format.split_once('=').ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "export format must be in the form of {mime_type}={export_mime_type}",
+                )
+                .with_context("service", Scheme::Gdrive)
+                .with_context("export_format", format)
+            })?;
+            export_formats.insert(
+                mime_type.trim().to_string(),
+                export_mime_type.trim().to_string(),
+            );
+        }
+
+        let upload_chunk_size = self
+            .config
+            .upload_chunk_size
+            .unwrap_or(DEFAULT_UPLOAD_CHUNK_SIZE);
+        if upload_chunk_size == 0 || upload_chunk_size % UPLOAD_CHUNK_SIZE_UNIT != 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "upload_chunk_size must be a multiple of 256 KiB",
+            )
+            .with_context("service", Scheme::Gdrive)
+            .with_context("upload_chunk_size", upload_chunk_size.to_string()));
+        }
+
+        let list_page_size = self.config.list_page_size.unwrap_or(DEFAULT_LIST_PAGE_SIZE);
+        if list_page_size == 0 || list_page_size > MAX_PAGE_SIZE {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "list_page_size must be between 1 and 1000",
+            )
+            .with_context("service", Scheme::Gdrive)
+            .with_context("list_page_size", list_page_size.to_string()));
+        }
+
         let signer = Arc::new(Mutex::new(signer));
+        let drive_id = self.config.drive_id;
+        let shortcuts = if self.config.disable_shortcut_resolution {
+            None
+        } else {
+            Some(GdriveShortcuts::default())
+        };
+        let mut path_cache = PathCacher::new(GdrivePathQuery::new(
+            client.clone(),
+            signer.clone(),
+            drive_id.clone(),
+            shortcuts.clone(),
+        ))
+        .with_lock();
+        if let Some(capacity) = self.config.path_cache_capacity {
+            path_cache = path_cache.with_capacity(capacity);
+        }
+        if let Some(ttl) = self.config.path_cache_ttl {
+            path_cache = path_cache.with_ttl(ttl);
+        }
+        if let Some(store) = self.path_cache_store {
+            path_cache = path_cache.with_store(store);
+        }
+
         Ok(GdriveBackend {
             core: Arc::new(GdriveCore {
                 root,
-                signer: signer.clone(),
-                client: client.clone(),
-                path_cache: PathCacher::new(GdrivePathQuery::new(client, signer)).with_lock(),
+                drive_id,
+                export_formats,
+                upload_chunk_size,
+                list_page_size,
+                shortcuts,
+                signer,
+                client,
+                path_cache,
             }),
         })
     }
@@ -17,6 +17,7 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -36,12 +37,55 @@ pub struct GdriveConfig {
     pub client_id: Option<String>,
     /// Client secret for gdrive.
     pub client_secret: Option<String>,
+    /// Credential string of the service account JSON key, base64 encoded.
+    pub credential: Option<String>,
+    /// Path to the service account JSON key.
+    pub credential_path: Option<String>,
+    /// The user to impersonate via domain-wide delegation of the service
+    /// account.
+    pub subject: Option<String>,
+    /// The id of the shared drive to access.
+    ///
+    /// Files in "My Drive" will be accessed if not set.
+    pub drive_id: Option<String>,
+    /// The formats to export google workspace documents into when reading.
+    ///
+    /// The value is a comma separated list of `{mime_type}={export_mime_type}`,
+    /// for example `application/vnd.google-apps.document=application/pdf`.
+    /// Entries here override the default formats.
+    pub export_formats: Option<String>,
+    /// The size of chunks to upload large files with resumable upload sessions.
+    ///
+    /// Must be a multiple of 256 KiB. Default to 8 MiB.
+    pub upload_chunk_size: Option<usize>,
+    /// The number of entries to fetch in one list request if the limit is
+    /// not specified.
+    ///
+    /// Must be between 1 and 1000. Default to 100.
+    pub list_page_size: Option<usize>,
+    /// Disable resolving shortcuts to the files they point to.
+    ///
+    /// Shortcuts will be surfaced as symlink-like files if set, whose target
+    /// ids can be found in the user metadata `shortcut_target_id`.
+    pub disable_shortcut_resolution: bool,
+    /// The max number of path to id entries cached in memory.
+    ///
+    /// Default to 65536.
+    pub path_cache_capacity: Option<u64>,
+    /// The time to live of the cached path to id entries.
+    ///
+    /// Entries never expire by default, set this if the files could be
+    /// changed by others.
+    pub path_cache_ttl: Option<Duration>,
 }
 
 impl Debug for GdriveConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GdriveConfig")
             .field("root", &self.root)
+            .field("drive_id", &self.drive_id)
+            .field("credential_path", &self.credential_path)
+            .field("subject", &self.subject)
             .finish_non_exhaustive()
     }
 }
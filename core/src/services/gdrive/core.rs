@@ -15,10 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
 use bytes;
 use bytes::Buf;
 use bytes::Bytes;
@@ -28,16 +32,42 @@ use http::header;
 use http::Request;
 use http::Response;
 use http::StatusCode;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::SignatureEncoding;
+use rsa::signature::Signer;
+use rsa::RsaPrivateKey;
 use serde::Deserialize;
 use serde_json::json;
+use sha2::Sha256;
 use tokio::sync::Mutex;
 
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// The mime type of folders in Google Drive.
+const GDRIVE_FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+/// The mime type of shortcuts in Google Drive.
+const GDRIVE_SHORTCUT_MIME_TYPE: &str = "application/vnd.google-apps.shortcut";
+
+/// The target ids of shortcuts, keyed by the shortcut ids.
+pub type GdriveShortcuts = Arc<std::sync::Mutex<HashMap<String, String>>>;
+
 pub struct GdriveCore {
     pub root: String,
+    /// The id of the shared drive to access, `None` means "My Drive".
+    pub drive_id: Option<String>,
+    /// The export mime types of google workspace documents, keyed by their
+    /// own mime types.
+    pub export_formats: HashMap<String, String>,
+    /// The size of chunks uploaded to resumable upload sessions.
+    pub upload_chunk_size: usize,
+    /// The page size of list requests if the limit is not specified.
+    pub list_page_size: usize,
+    /// The shortcuts that have been seen, `None` means shortcuts will be
+    /// surfaced as they are instead of being resolved to their targets.
+    pub shortcuts: Option<GdriveShortcuts>,
 
     pub client: HttpClient,
 
@@ -51,24 +81,159 @@ impl Debug for GdriveCore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut de = f.debug_struct("GdriveCore");
         de.field("root", &self.root);
+        de.field("drive_id", &self.drive_id);
         de.finish()
     }
 }
 
 impl GdriveCore {
-    pub async fn gdrive_stat(&self, path: &str) -> Result<Response<Buffer>> {
+    /// Resolve the file id of the given absolute path.
+    ///
+    /// On a cold cache, all the missing components of the path are looked up
+    /// by a single `files.list` request instead of one request per component.
+    pub async fn resolve_path(&self, path: &str) -> Result<Option<String>> {
+        if self.path_cache.peek(path).is_none() {
+            self.warm_path_cache(path).await?;
+        }
+        self.path_cache.get(path).await
+    }
+
+    /// Resolve the file id of the given absolute path, following the
+    /// shortcut to its target if needed.
+    pub async fn resolve_target(&self, path: &str) -> Result<Option<String>> {
+        let id = self.resolve_path(path).await?;
+        Ok(id.map(|id| self.target_id(&id)))
+    }
+
+    /// Get the target id if the given id is a shortcut, otherwise the id itself.
+    pub fn target_id(&self, id: &str) -> String {
+        shortcut_target(self.shortcuts.as_ref(), id)
+    }
+
+    /// Remember the target of the file if it's a shortcut.
+    pub fn record_shortcut(&self, file: &GdriveFile) {
+        record_shortcut(self.shortcuts.as_ref(), file)
+    }
+
+    /// Fill the path cache with the missing components of the given path.
+    ///
+    /// Components that can't be resolved here, for example because the
+    /// matched files don't fit into one page, will be left to the path cache
+    /// which queries them one by one.
+    async fn warm_path_cache(&self, path: &str) -> Result<()> {
+        let mut missing = VecDeque::new();
+        let mut parent_id = None;
+        let mut current_path = path;
+        while current_path != "/" && !current_path.is_empty() {
+            missing.push_front(current_path);
+            current_path = get_parent(current_path);
+            if let Some(id) = self.path_cache.peek(current_path) {
+                parent_id = Some(id);
+                break;
+            }
+        }
+
+        // Path cache will resolve a single component with the same cost.
+        if missing.len() < 2 {
+            return Ok(());
+        }
+
+        let mut parent_id = match parent_id {
+            Some(id) => self.target_id(&id),
+            None => match self.path_cache.get("/").await? {
+                Some(id) => id,
+                None => return Ok(()),
+            },
+        };
+
+        let mut names: Vec<&str> = missing.iter().map(|p| get_basename(*p)).collect();
+        names.sort_unstable();
+        names.dedup();
+        let files = self.gdrive_search_names(&names).await?;
+
+        for path in missing {
+            let name = get_basename(path);
+            let file = files.iter().find(|f| {
+                f.name == name
+                    && f.parents.contains(&parent_id)
+                    && (!name.ends_with('/')
+                        || f.is_folder()
+                        || (self.shortcuts.is_some() && f.is_shortcut_to_folder()))
+            });
+            match file {
+                Some(file) => {
+                    self.record_shortcut(file);
+                    self.path_cache.insert(path, &file.id).await;
+                    parent_id = self.target_id(&file.id);
+                }
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Search files that match any of the given names in one request.
+    async fn gdrive_search_names(&self, names: &[&str]) -> Result<Vec<GdriveFile>> {
+        let names = names
+            .iter()
+            .map(|name| format!("name = '{}'", name.replace('\'', "\\'")))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        let q = format!("({names}) and trashed = false");
+
+        let mut url = format!(
+            "https://www.googleapis.com/drive/v3/files?pageSize=1000&fields={}&q={}",
+            percent_encode_path("files(id,name,mimeType,parents,shortcutDetails)"),
+            percent_encode_path(&q)
+        );
+        append_drive_params(&mut url, self.drive_id.as_deref(), true);
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let body = resp.into_body();
+                let list: GdriveFileList =
+                    serde_json::from_reader(body.reader()).map_err(new_json_deserialize_error)?;
+                Ok(list.files)
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Stat the file at given path, or its revision if `revision_id` is set.
+    pub async fn gdrive_stat(
+        &self,
+        path: &str,
+        revision_id: Option<&str>,
+    ) -> Result<Response<Buffer>> {
         let path = build_abs_path(&self.root, path);
-        let file_id = self.path_cache.get(&path).await?.ok_or(Error::new(
+        let file_id = self.resolve_target(&path).await?.ok_or(Error::new(
             ErrorKind::NotFound,
             format!("path not found: {}", path),
         ))?;
 
         // The file metadata in the Google Drive API is very complex.
         // For now, we only need the file id, name, mime type and modified time.
-        let mut req = Request::get(&format!(
-            "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType,size,modifiedTime",
-            file_id
-        ))
+        let mut url = match revision_id {
+            Some(revision_id) => format!(
+                "https://www.googleapis.com/drive/v3/files/{}/revisions/{}?fields=id,mimeType,size,modifiedTime",
+                file_id,
+                percent_encode_path(revision_id)
+            ),
+            None => format!(
+                "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType,size,modifiedTime,headRevisionId,shortcutDetails",
+                file_id
+            ),
+        };
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
+
+        let mut req = Request::get(&url)
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
         self.sign(&mut req).await?;
@@ -76,17 +241,31 @@ impl GdriveCore {
         self.client.send(req).await
     }
 
-    pub async fn gdrive_get(&self, path: &str, range: BytesRange) -> Result<Response<HttpBody>> {
+    /// Download the file at given path, or its revision if `revision_id` is set.
+    pub async fn gdrive_get(
+        &self,
+        path: &str,
+        range: BytesRange,
+        revision_id: Option<&str>,
+    ) -> Result<Response<HttpBody>> {
         let path = build_abs_path(&self.root, path);
-        let path_id = self.path_cache.get(&path).await?.ok_or(Error::new(
+        let path_id = self.resolve_target(&path).await?.ok_or(Error::new(
             ErrorKind::NotFound,
             format!("path not found: {}", path),
         ))?;
 
-        let url: String = format!(
-            "https://www.googleapis.com/drive/v3/files/{}?alt=media",
-            path_id
-        );
+        let mut url: String = match revision_id {
+            Some(revision_id) => format!(
+                "https://www.googleapis.com/drive/v3/files/{}/revisions/{}?alt=media",
+                path_id,
+                percent_encode_path(revision_id)
+            ),
+            None => format!(
+                "https://www.googleapis.com/drive/v3/files/{}?alt=media",
+                path_id
+            ),
+        };
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let mut req = Request::get(&url)
             .header(header::RANGE, range.to_header())
@@ -97,21 +276,123 @@ impl GdriveCore {
         self.client.fetch(req).await
     }
 
+    /// Export the google workspace document at given path.
+    ///
+    /// Workspace documents have no binary content, they can only be exported
+    /// into the format configured in `export_formats`. The export endpoint
+    /// doesn't support range, so the range is applied after downloading.
+    pub async fn gdrive_export(&self, path: &str, range: BytesRange) -> Result<Buffer> {
+        let path = build_abs_path(&self.root, path);
+        let file_id = self.resolve_target(&path).await?.ok_or(Error::new(
+            ErrorKind::NotFound,
+            format!("path not found: {}", path),
+        ))?;
+
+        let mut url = format!(
+            "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType",
+            file_id
+        );
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+        let file: GdriveFile = serde_json::from_reader(resp.into_body().reader())
+            .map_err(new_json_deserialize_error)?;
+
+        let export_mime_type = self.export_formats.get(&file.mime_type).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "no export format configured for this google workspace document",
+            )
+            .with_context("path", &path)
+            .with_context("mime_type", &file.mime_type)
+        })?;
+
+        let url = format!(
+            "https://www.googleapis.com/drive/v3/files/{}/export?mimeType={}",
+            file_id,
+            percent_encode_path(export_mime_type)
+        );
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let buf = resp.into_body();
+        let start = (range.offset() as usize).min(buf.len());
+        let end = match range.size() {
+            Some(size) => (start + size as usize).min(buf.len()),
+            None => buf.len(),
+        };
+        Ok(buf.slice(start..end))
+    }
+
+    /// List files whose parent is any of `parent_ids`.
+    ///
+    /// `query` is combined with the parent filter, folders are always
+    /// returned if `keep_folders` is set so that they can be walked.
     pub async fn gdrive_list(
+        &self,
+        parent_ids: &[String],
+        query: Option<&str>,
+        keep_folders: bool,
+        page_size: usize,
+        next_page_token: &str,
+    ) -> Result<Response<Buffer>> {
+        let q = build_list_query(parent_ids, query, keep_folders);
+        let mut url = format!(
+            "https://www.googleapis.com/drive/v3/files?pageSize={}&q={}&fields={}",
+            page_size,
+            percent_encode_path(&q),
+            percent_encode_path(
+                "nextPageToken,files(id,name,mimeType,size,modifiedTime,headRevisionId,parents,shortcutDetails)"
+            )
+        );
+        if !next_page_token.is_empty() {
+            url += &format!("&pageToken={next_page_token}");
+        };
+        append_drive_params(&mut url, self.drive_id.as_deref(), true);
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
+    /// List the revisions of the file.
+    ///
+    /// refer to https://developers.google.com/drive/api/reference/rest/v3/revisions/list
+    pub async fn gdrive_list_revisions(
         &self,
         file_id: &str,
-        page_size: i32,
+        page_size: usize,
         next_page_token: &str,
     ) -> Result<Response<Buffer>> {
-        let q = format!("'{}' in parents and trashed = false", file_id);
         let mut url = format!(
-            "https://www.googleapis.com/drive/v3/files?pageSize={}&q={}",
+            "https://www.googleapis.com/drive/v3/files/{}/revisions?pageSize={}&fields={}",
+            file_id,
             page_size,
-            percent_encode_path(&q)
+            percent_encode_path("nextPageToken,revisions(id,mimeType,size,modifiedTime)")
         );
         if !next_page_token.is_empty() {
             url += &format!("&pageToken={next_page_token}");
         };
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let mut req = Request::get(&url)
             .body(Buffer::new())
@@ -127,7 +408,7 @@ impl GdriveCore {
         source: &str,
         target: &str,
     ) -> Result<Response<Buffer>> {
-        let source_file_id = self.path_cache.get(source).await?.ok_or(Error::new(
+        let source_file_id = self.resolve_path(source).await?.ok_or(Error::new(
             ErrorKind::NotFound,
             format!("source path not found: {}", source),
         ))?;
@@ -136,9 +417,11 @@ impl GdriveCore {
             .path_cache
             .get(source_parent)
             .await?
+            .map(|id| self.target_id(&id))
             .expect("old parent must exist");
 
-        let target_parent_id = self.path_cache.ensure_dir(get_parent(target)).await?;
+        let target_parent_id =
+            self.target_id(&self.path_cache.ensure_dir(get_parent(target)).await?);
         let target_file_name = get_basename(target);
 
         let metadata = &json!({
@@ -147,10 +430,11 @@ impl GdriveCore {
             "addParents": [target_parent_id],
         });
 
-        let url = format!(
+        let mut url = format!(
             "https://www.googleapis.com/drive/v3/files/{}",
             source_file_id
         );
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
         let mut req = Request::patch(url)
             .body(Buffer::from(Bytes::from(metadata.to_string())))
             .map_err(new_request_build_error)?;
@@ -161,7 +445,8 @@ impl GdriveCore {
     }
 
     pub async fn gdrive_trash(&self, file_id: &str) -> Result<Response<Buffer>> {
-        let url = format!("https://www.googleapis.com/drive/v3/files/{}", file_id);
+        let mut url = format!("https://www.googleapis.com/drive/v3/files/{}", file_id);
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let body = serde_json::to_vec(&json!({
             "trashed": true
@@ -184,9 +469,11 @@ impl GdriveCore {
         size: u64,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
-        let parent = self.path_cache.ensure_dir(get_parent(path)).await?;
+        let parent = self.target_id(&self.path_cache.ensure_dir(get_parent(path)).await?);
 
-        let url = "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart";
+        let mut url =
+            "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart".to_string();
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let file_name = get_basename(path);
 
@@ -234,10 +521,11 @@ impl GdriveCore {
         size: u64,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
-        let url = format!(
+        let mut url = format!(
             "https://www.googleapis.com/upload/drive/v3/files/{}?uploadType=media",
             file_id
         );
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let mut req = Request::patch(url)
             .header(header::CONTENT_TYPE, "application/octet-stream")
@@ -251,12 +539,100 @@ impl GdriveCore {
         self.client.send(req).await
     }
 
+    /// Start a resumable upload session and return the session uri.
+    ///
+    /// The file will be created if `file_id` is `None`, otherwise overwritten.
+    ///
+    /// ref: <https://developers.google.com/drive/api/guides/manage-uploads#resumable>
+    pub async fn gdrive_create_resumable_upload(
+        &self,
+        path: &str,
+        file_id: Option<&str>,
+    ) -> Result<String> {
+        let mut req = match file_id {
+            Some(file_id) => {
+                let mut url = format!(
+                    "https://www.googleapis.com/upload/drive/v3/files/{}?uploadType=resumable",
+                    file_id
+                );
+                append_drive_params(&mut url, self.drive_id.as_deref(), false);
+
+                Request::patch(url)
+                    .header(header::CONTENT_LENGTH, 0)
+                    .body(Buffer::new())
+                    .map_err(new_request_build_error)?
+            }
+            None => {
+                let parent = self.target_id(&self.path_cache.ensure_dir(get_parent(path)).await?);
+
+                let mut url =
+                    "https://www.googleapis.com/upload/drive/v3/files?uploadType=resumable"
+                        .to_string();
+                append_drive_params(&mut url, self.drive_id.as_deref(), false);
+
+                let metadata = serde_json::to_vec(&json!({
+                    "name": get_basename(path),
+                    "parents": [parent],
+                }))
+                .map_err(new_json_serialize_error)?;
+
+                Request::post(url)
+                    .header(header::CONTENT_TYPE, "application/json; charset=UTF-8")
+                    .header(header::CONTENT_LENGTH, metadata.len())
+                    .body(Buffer::from(Bytes::from(metadata)))
+                    .map_err(new_request_build_error)?
+            }
+        };
+        self.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        parse_location(resp.headers())?
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "resumable upload session uri is missing in response",
+                )
+            })
+    }
+
+    /// Upload bytes to the resumable upload session.
+    ///
+    /// `content_range` is the value of `Content-Range` header, for example
+    /// `bytes 0-1023/*`. Sending `bytes */*` with empty body will query the
+    /// status of the session.
+    pub async fn gdrive_resumable_upload(
+        &self,
+        session_uri: &str,
+        content_range: &str,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let mut req = Request::put(session_uri)
+            .header(header::CONTENT_LENGTH, body.len())
+            .header(header::CONTENT_RANGE, content_range)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.client.send(req).await
+    }
+
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let mut signer = self.signer.lock().await;
         signer.sign(req).await
     }
 }
 
+/// The default uri to exchange tokens from google.
+const GOOGLE_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+/// The scope requested by service accounts.
+const GDRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive";
+
 #[derive(Clone)]
 pub struct GdriveSigner {
     pub client: HttpClient,
@@ -265,6 +641,12 @@ pub struct GdriveSigner {
     pub client_secret: String,
     pub refresh_token: String,
 
+    /// The service account to sign JWT assertions, used instead of the
+    /// refresh token if set.
+    pub service_account: Option<GdriveServiceAccount>,
+    /// The user to impersonate via domain-wide delegation.
+    pub subject: Option<String>,
+
     pub access_token: String,
     pub expires_in: DateTime<Utc>,
 }
@@ -278,6 +660,8 @@ impl GdriveSigner {
             client_id: "".to_string(),
             client_secret: "".to_string(),
             refresh_token: "".to_string(),
+            service_account: None,
+            subject: None,
             access_token: "".to_string(),
             expires_in: DateTime::<Utc>::MIN_UTC,
         }
@@ -294,16 +678,20 @@ impl GdriveSigner {
             return Ok(());
         }
 
-        let url = format!(
-            "https://oauth2.googleapis.com/token?refresh_token={}&client_id={}&client_secret={}&grant_type=refresh_token",
-            self.refresh_token, self.client_id, self.client_secret
-        );
-
         {
-            let req = Request::post(url)
-                .header(header::CONTENT_LENGTH, 0)
-                .body(Buffer::new())
-                .map_err(new_request_build_error)?;
+            let req = match &self.service_account {
+                Some(account) => self.build_jwt_token_request(account)?,
+                None => {
+                    let url = format!(
+                        "{GOOGLE_TOKEN_URI}?refresh_token={}&client_id={}&client_secret={}&grant_type=refresh_token",
+                        self.refresh_token, self.client_id, self.client_secret
+                    );
+                    Request::post(url)
+                        .header(header::CONTENT_LENGTH, 0)
+                        .body(Buffer::new())
+                        .map_err(new_request_build_error)?
+                }
+            };
 
             let resp = self.client.send(req).await?;
             let status = resp.status();
@@ -331,25 +719,150 @@ impl GdriveSigner {
 
         Ok(())
     }
+
+    /// Build the request to exchange a signed JWT assertion for an access
+    /// token.
+    ///
+    /// ref: <https://developers.google.com/identity/protocols/oauth2/service-account#httprest>
+    fn build_jwt_token_request(&self, account: &GdriveServiceAccount) -> Result<Request<Buffer>> {
+        let token_uri = account.token_uri.as_deref().unwrap_or(GOOGLE_TOKEN_URI);
+
+        let now = Utc::now().timestamp();
+        let mut claims = json!({
+            "iss": account.client_email,
+            "scope": GDRIVE_SCOPE,
+            "aud": token_uri,
+            "iat": now,
+            "exp": now + 3600,
+        });
+        if let Some(subject) = &self.subject {
+            claims["sub"] = json!(subject);
+        }
+        let assertion = account.sign_jwt(&claims)?;
+
+        let body = format!(
+            "grant_type={}&assertion={}",
+            percent_encode_path("urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            assertion
+        );
+        Request::post(token_uri)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Buffer::from(Bytes::from(body)))
+            .map_err(new_request_build_error)
+    }
+}
+
+/// The service account JSON key downloaded from google cloud console.
+///
+/// refer to https://cloud.google.com/iam/docs/keys-create-delete
+#[derive(Deserialize, Clone)]
+pub struct GdriveServiceAccount {
+    pub client_email: String,
+    pub private_key: String,
+    pub token_uri: Option<String>,
+}
+
+impl Debug for GdriveServiceAccount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GdriveServiceAccount")
+            .field("client_email", &self.client_email)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GdriveServiceAccount {
+    /// Parse the service account from the content of JSON key.
+    pub fn from_slice(content: &[u8]) -> Result<Self> {
+        let account: Self = serde_json::from_slice(content).map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "service account key is invalid").set_source(e)
+        })?;
+        // Make sure the private key is valid while building.
+        account.signing_key()?;
+        Ok(account)
+    }
+
+    fn signing_key(&self) -> Result<SigningKey<Sha256>> {
+        let key = RsaPrivateKey::from_pkcs8_pem(&self.private_key).map_err(|e| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "private key of service account is invalid",
+            )
+            .with_context("client_email", &self.client_email)
+            .set_source(e)
+        })?;
+        Ok(SigningKey::new(key))
+    }
+
+    /// Sign the claims into a JWT with RS256.
+    fn sign_jwt(&self, claims: &serde_json::Value) -> Result<String> {
+        let header = BASE64_URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#);
+        let claims = BASE64_URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(claims).map_err(new_json_serialize_error)?);
+        let message = format!("{header}.{claims}");
+
+        let signature = self.signing_key()?.sign(message.as_bytes());
+        Ok(format!(
+            "{message}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        ))
+    }
 }
 
 pub struct GdrivePathQuery {
     pub client: HttpClient,
     pub signer: Arc<Mutex<GdriveSigner>>,
+    pub drive_id: Option<String>,
+    pub shortcuts: Option<GdriveShortcuts>,
 }
 
 impl GdrivePathQuery {
-    pub fn new(client: HttpClient, signer: Arc<Mutex<GdriveSigner>>) -> Self {
-        GdrivePathQuery { client, signer }
+    pub fn new(
+        client: HttpClient,
+        signer: Arc<Mutex<GdriveSigner>>,
+        drive_id: Option<String>,
+        shortcuts: Option<GdriveShortcuts>,
+    ) -> Self {
+        GdrivePathQuery {
+            client,
+            signer,
+            drive_id,
+            shortcuts,
+        }
     }
 }
 
 impl PathQuery for GdrivePathQuery {
     async fn root(&self) -> Result<String> {
-        Ok("root".to_string())
+        // The root folder of a shared drive shares the same id with the drive.
+        if let Some(drive_id) = &self.drive_id {
+            return Ok(drive_id.clone());
+        }
+
+        // Fetch the real id of root instead of using the `root` alias, so
+        // that it can be matched against the `parents` of files.
+        let mut req =
+            Request::get("https://www.googleapis.com/drive/v3/files/root?fields=id,name,mimeType")
+                .body(Buffer::new())
+                .map_err(new_request_build_error)?;
+
+        self.signer.lock().await.sign(&mut req).await?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let body = resp.into_body();
+                let file: GdriveFile =
+                    serde_json::from_reader(body.reader()).map_err(new_json_deserialize_error)?;
+                Ok(file.id)
+            }
+            _ => Err(parse_error(resp)),
+        }
     }
 
     async fn query(&self, parent_id: &str, name: &str) -> Result<Option<String>> {
+        // Children of a shortcut to folder live in the target folder.
+        let parent_id = shortcut_target(self.shortcuts.as_ref(), parent_id);
         let mut queries = vec![
             // Make sure name has been replaced with escaped name.
             //
@@ -359,14 +872,22 @@ impl PathQuery for GdrivePathQuery {
             "trashed = false".to_string(),
         ];
         if name.ends_with('/') {
-            queries.push("mimeType = 'application/vnd.google-apps.folder'".to_string());
+            if self.shortcuts.is_some() {
+                queries.push(format!(
+                    "(mimeType = '{GDRIVE_FOLDER_MIME_TYPE}' or mimeType = '{GDRIVE_SHORTCUT_MIME_TYPE}')"
+                ));
+            } else {
+                queries.push(format!("mimeType = '{GDRIVE_FOLDER_MIME_TYPE}'"));
+            }
         }
         let query = queries.join(" and ");
 
-        let url = format!(
-            "https://www.googleapis.com/drive/v3/files?q={}",
-            percent_encode_path(query.as_str())
+        let mut url = format!(
+            "https://www.googleapis.com/drive/v3/files?q={}&fields={}",
+            percent_encode_path(query.as_str()),
+            percent_encode_path("files(id,name,mimeType,shortcutDetails)")
         );
+        append_drive_params(&mut url, self.drive_id.as_deref(), true);
 
         let mut req = Request::get(&url)
             .body(Buffer::new())
@@ -383,7 +904,13 @@ impl PathQuery for GdrivePathQuery {
                 let meta: GdriveFileList =
                     serde_json::from_reader(body.reader()).map_err(new_json_deserialize_error)?;
 
-                if let Some(f) = meta.files.first() {
+                // Shortcuts to files can't be used as folders.
+                let file = meta
+                    .files
+                    .iter()
+                    .find(|f| !name.ends_with('/') || f.is_folder() || f.is_shortcut_to_folder());
+                if let Some(f) = file {
+                    record_shortcut(self.shortcuts.as_ref(), f);
                     Ok(Some(f.id.clone()))
                 } else {
                     Ok(None)
@@ -394,13 +921,14 @@ impl PathQuery for GdrivePathQuery {
     }
 
     async fn create_dir(&self, parent_id: &str, name: &str) -> Result<String> {
-        let url = "https://www.googleapis.com/drive/v3/files";
+        let mut url = "https://www.googleapis.com/drive/v3/files".to_string();
+        append_drive_params(&mut url, self.drive_id.as_deref(), false);
 
         let content = serde_json::to_vec(&json!({
             "name": name,
             "mimeType": "application/vnd.google-apps.folder",
             // If the parent is not provided, the folder will be created in the root folder.
-            "parents": [parent_id],
+            "parents": [shortcut_target(self.shortcuts.as_ref(), parent_id)],
         }))
         .map_err(new_json_serialize_error)?;
 
@@ -423,6 +951,62 @@ impl PathQuery for GdrivePathQuery {
     }
 }
 
+/// Append the query parameters required to access files in a shared drive.
+///
+/// `files.list` requests must also specify the drive to search in, so
+/// `list` should be set for them. Nothing is appended if `drive_id` is `None`.
+pub fn append_drive_params(url: &mut String, drive_id: Option<&str>, list: bool) {
+    let Some(drive_id) = drive_id else {
+        return;
+    };
+
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str("supportsAllDrives=true");
+    if list {
+        url.push_str("&includeItemsFromAllDrives=true&corpora=drive&driveId=");
+        url.push_str(&percent_encode_path(drive_id));
+    }
+}
+
+/// Get the target id if the given id is a known shortcut.
+/// Build the `q` of `files.list` for the children of `parent_ids`.
+///
+/// ref: <https://developers.google.com/drive/api/guides/search-files>
+fn build_list_query(parent_ids: &[String], query: Option<&str>, keep_folders: bool) -> String {
+    let parents = parent_ids
+        .iter()
+        .map(|id| format!("'{id}' in parents"))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let mut q = format!("({parents}) and trashed = false");
+    match query {
+        Some(query) if keep_folders => {
+            q += &format!(" and (({query}) or mimeType = '{GDRIVE_FOLDER_MIME_TYPE}')")
+        }
+        Some(query) => q += &format!(" and ({query})"),
+        None => {}
+    }
+    q
+}
+
+fn shortcut_target(shortcuts: Option<&GdriveShortcuts>, id: &str) -> String {
+    shortcuts
+        .and_then(|v| v.lock().expect("lock must succeed").get(id).cloned())
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Remember the target of the file if it's a shortcut and shortcuts should
+/// be resolved.
+fn record_shortcut(shortcuts: Option<&GdriveShortcuts>, file: &GdriveFile) {
+    let (Some(shortcuts), Some(details)) = (shortcuts, &file.shortcut_details) else {
+        return;
+    };
+    shortcuts
+        .lock()
+        .expect("lock must succeed")
+        .insert(file.id.clone(), details.target_id.clone());
+}
+
 #[derive(Deserialize)]
 pub struct GdriveTokenResponse {
     access_token: String,
@@ -441,10 +1025,122 @@ pub struct GdriveFile {
     pub size: Option<String>,
     // The modified time is not returned unless the `fields`
     // query parameter contains `modifiedTime`.
-    // As we only need the modified time when we do `stat` operation,
-    // if other operations(such as search) do not specify the `fields` query parameter,
-    // try to access this field, it will be `None`.
+    // `stat` and `list` request it explicitly, if other operations (such as search)
+    // do not specify the `fields` query parameter, this field will be `None`.
     pub modified_time: Option<String>,
+    // The parents are only returned when the `fields` query parameter
+    // contains `parents`.
+    #[serde(default)]
+    pub parents: Vec<String>,
+    // The id of the latest revision, only returned for files with binary
+    // content when the `fields` query parameter contains `headRevisionId`.
+    pub head_revision_id: Option<String>,
+    // The shortcut details are only returned for shortcuts when the
+    // `fields` query parameter contains `shortcutDetails`.
+    pub shortcut_details: Option<GdriveShortcutDetails>,
+}
+
+/// refer to https://developers.google.com/drive/api/reference/rest/v3/files#File.ShortcutDetails
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GdriveShortcutDetails {
+    pub target_id: String,
+    #[serde(default)]
+    pub target_mime_type: String,
+}
+
+impl GdriveFile {
+    /// Check if this file is a folder.
+    pub fn is_folder(&self) -> bool {
+        self.mime_type == GDRIVE_FOLDER_MIME_TYPE
+    }
+
+    /// Check if this file is a shortcut.
+    pub fn is_shortcut(&self) -> bool {
+        self.mime_type == GDRIVE_SHORTCUT_MIME_TYPE
+    }
+
+    /// Check if this file is a shortcut to a folder.
+    pub fn is_shortcut_to_folder(&self) -> bool {
+        self.shortcut_details
+            .as_ref()
+            .is_some_and(|v| v.target_mime_type == GDRIVE_FOLDER_MIME_TYPE)
+    }
+
+    /// Build metadata from the fields returned by gdrive.
+    ///
+    /// Shortcuts are returned as symlink-like files, whose target id can be
+    /// found in the user metadata `shortcut_target_id`.
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        if self.is_folder() {
+            return Ok(Metadata::new(EntryMode::DIR));
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        if let Some(details) = &self.shortcut_details {
+            meta.with_user_metadata(HashMap::from([(
+                "shortcut_target_id".to_string(),
+                details.target_id.clone(),
+            )]));
+            meta.set_content_type(GDRIVE_SHORTCUT_MIME_TYPE);
+        }
+        if let Some(v) = &self.size {
+            meta = meta.with_content_length(v.parse::<u64>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "parse content length").set_source(e)
+            })?);
+        }
+        if let Some(v) = &self.modified_time {
+            meta = meta.with_last_modified(v.parse::<DateTime<Utc>>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "parse last modified time").set_source(e)
+            })?);
+        }
+        if let Some(v) = &self.head_revision_id {
+            meta.set_version(v);
+        }
+        Ok(meta)
+    }
+}
+
+/// This is the revision struct returned by the Google Drive API.
+/// refer to https://developers.google.com/drive/api/reference/rest/v3/revisions#Revision
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GdriveRevision {
+    pub id: String,
+    #[serde(default)]
+    pub mime_type: String,
+    pub size: Option<String>,
+    pub modified_time: Option<String>,
+}
+
+impl GdriveRevision {
+    /// Build metadata from the fields returned by gdrive, the revision id
+    /// is returned as the version.
+    pub fn to_metadata(&self) -> Result<Metadata> {
+        let mut meta = Metadata::new(EntryMode::FILE).with_version(self.id.clone());
+        if !self.mime_type.is_empty() {
+            meta.set_content_type(&self.mime_type);
+        }
+        if let Some(v) = &self.size {
+            meta.set_content_length(v.parse::<u64>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "parse content length").set_source(e)
+            })?);
+        }
+        if let Some(v) = &self.modified_time {
+            meta.set_last_modified(v.parse::<DateTime<Utc>>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "parse last modified time").set_source(e)
+            })?);
+        }
+        Ok(meta)
+    }
+}
+
+/// refer to https://developers.google.com/drive/api/reference/rest/v3/revisions/list
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GdriveRevisionList {
+    pub(crate) revisions: Vec<GdriveRevision>,
+    pub(crate) next_page_token: Option<String>,
 }
 
 /// refer to https://developers.google.com/drive/api/reference/rest/v3/files/list
@@ -454,3 +1150,26 @@ pub(crate) struct GdriveFileList {
     pub(crate) files: Vec<GdriveFile>,
     pub(crate) next_page_token: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_list_query() {
+        let parents = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(
+            build_list_query(&parents, None, false),
+            "('a' in parents or 'b' in parents) and trashed = false"
+        );
+        assert_eq!(
+            build_list_query(&parents[..1], Some("name contains 'x'"), false),
+            "('a' in parents) and trashed = false and (name contains 'x')"
+        );
+        assert_eq!(
+            build_list_query(&parents[..1], Some("name contains 'x'"), true),
+            "('a' in parents) and trashed = false \
+             and ((name contains 'x') or mimeType = 'application/vnd.google-apps.folder')"
+        );
+    }
+}
@@ -31,6 +31,14 @@ struct GdriveError {
 #[derive(Default, Debug, Deserialize)]
 struct GdriveInnerError {
     message: String,
+    #[serde(default)]
+    errors: Vec<GdriveErrorDetail>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct GdriveErrorDetail {
+    #[serde(default)]
+    reason: String,
 }
 
 /// Parse error response into Error.
@@ -55,8 +63,9 @@ pub(super) fn parse_error(resp: Response<Buffer>) -> Error {
         .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
 
     if let Some(gdrive_err) = gdrive_err {
-        (kind, retryable) =
-            parse_gdrive_error_code(gdrive_err.error.message.as_str()).unwrap_or((kind, retryable));
+        (kind, retryable) = parse_gdrive_error_code(gdrive_err.error.message.as_str())
+            .or_else(|| parse_gdrive_error_reason(&gdrive_err.error.errors))
+            .unwrap_or((kind, retryable));
     }
 
     let mut err = Error::new(kind, message);
@@ -70,6 +79,23 @@ pub(super) fn parse_error(resp: Response<Buffer>) -> Error {
     err
 }
 
+/// Check if the error response is returned for downloading google workspace
+/// documents, which can only be exported.
+pub(super) fn is_not_downloadable(resp: &Response<Buffer>) -> bool {
+    if resp.status() != StatusCode::FORBIDDEN {
+        return false;
+    }
+
+    serde_json::from_slice::<GdriveError>(&resp.body().to_bytes())
+        .map(|err| {
+            err.error
+                .errors
+                .iter()
+                .any(|v| v.reason == "fileNotDownloadable")
+        })
+        .unwrap_or_default()
+}
+
 pub fn parse_gdrive_error_code(message: &str) -> Option<(ErrorKind, bool)> {
     match message {
         // > Please reduce your request rate.
@@ -79,3 +105,81 @@ pub fn parse_gdrive_error_code(message: &str) -> Option<(ErrorKind, bool)> {
         _ => None,
     }
 }
+
+/// Drive returns `403 Forbidden` instead of `429 Too Many Requests` while
+/// throttling, which can only be told from permission errors by the reason.
+///
+/// Reference: <https://developers.google.com/drive/api/guides/handle-errors#resolve_a_403_error_rate_limit_exceeded>
+fn parse_gdrive_error_reason(errors: &[GdriveErrorDetail]) -> Option<(ErrorKind, bool)> {
+    errors.iter().find_map(|v| match v.reason.as_str() {
+        "rateLimitExceeded" | "userRateLimitExceeded" => Some((ErrorKind::RateLimited, true)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_not_downloadable() {
+        let body = r#"{
+  "error": {
+    "code": 403,
+    "message": "Only files with binary content can be downloaded. Use Export with Docs Editors files.",
+    "errors": [
+      {
+        "message": "Only files with binary content can be downloaded. Use Export with Docs Editors files.",
+        "domain": "global",
+        "reason": "fileNotDownloadable",
+        "location": "alt",
+        "locationType": "parameter"
+      }
+    ]
+  }
+}"#;
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Buffer::from(body))
+            .unwrap();
+        assert!(is_not_downloadable(&resp));
+
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Buffer::from(r#"{"error": {"message": "Forbidden"}}"#))
+            .unwrap();
+        assert!(!is_not_downloadable(&resp));
+    }
+
+    #[test]
+    fn test_parse_rate_limit_error() {
+        let body = r#"{
+  "error": {
+    "code": 403,
+    "message": "Rate Limit Exceeded",
+    "errors": [
+      {
+        "message": "Rate Limit Exceeded",
+        "domain": "usageLimits",
+        "reason": "rateLimitExceeded"
+      }
+    ]
+  }
+}"#;
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Buffer::from(body))
+            .unwrap();
+        let err = parse_error(resp);
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Buffer::from(r#"{"error": {"message": "Forbidden"}}"#))
+            .unwrap();
+        let err = parse_error(resp);
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(!err.is_temporary());
+    }
+}
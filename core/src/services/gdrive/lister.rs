@@ -15,42 +15,114 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use http::StatusCode;
 
 use super::core::GdriveCore;
 use super::core::GdriveFileList;
+use super::core::GdriveRevisionList;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+pub type GdriveListers =
+    TwoWays<oio::PageLister<GdriveLister>, oio::PageLister<GdriveRevisionLister>>;
+
+/// The max page size allowed by gdrive.
+pub(super) const MAX_PAGE_SIZE: usize = 1000;
+/// The max folders to be queried in one request while listing recursively,
+/// which keeps the query string in a reasonable size.
+const MAX_PARENTS_PER_QUERY: usize = 50;
+
 pub struct GdriveLister {
     path: String,
     core: Arc<GdriveCore>,
+    page_size: usize,
+    recursive: bool,
+    cycle_policy: ListCyclePolicy,
+    query: Option<String>,
+    state: Mutex<ListState>,
+}
+
+#[derive(Default)]
+struct ListState {
+    started: bool,
+    /// Folders to be listed, in `(file_id, abs_path)`.
+    pending: VecDeque<(String, String)>,
+    /// Folders that are being listed by current query, keyed by file id.
+    parents: HashMap<String, String>,
+    /// Folders that have been listed, shortcuts may lead to them again.
+    visited: HashSet<String>,
 }
 
 impl GdriveLister {
-    pub fn new(path: String, core: Arc<GdriveCore>) -> Self {
-        Self { path, core }
+    pub fn new(path: String, core: Arc<GdriveCore>, args: &OpList) -> Self {
+        // Only fetch the entries that callers need.
+        let page_size = args
+            .limit()
+            .unwrap_or(core.list_page_size)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        Self {
+            path,
+            core,
+            page_size,
+            recursive: args.recursive(),
+            cycle_policy: args.cycle_policy(),
+            query: args.query().map(|v| v.to_string()),
+            state: Mutex::default(),
+        }
     }
 }
 
 impl oio::PageList for GdriveLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
-        let file_id = self.core.path_cache.get(&self.path).await?;
-
-        let file_id = match file_id {
-            Some(file_id) => file_id,
-            None => {
+        let started = self.state.lock().unwrap().started;
+        if !started {
+            let Some(file_id) = self.core.resolve_target(&self.path).await? else {
                 ctx.done = true;
                 return Ok(());
+            };
+
+            // Return self at the first page.
+            let path = build_rel_path(&self.core.root, &self.path);
+            ctx.entries
+                .push_back(oio::Entry::new(&path, Metadata::new(EntryMode::DIR)));
+
+            let mut state = self.state.lock().unwrap();
+            state.started = true;
+            state.visited.insert(file_id.clone());
+            state.pending.push_back((file_id, self.path.clone()));
+        }
+
+        // Start a new query with the next batch of pending folders.
+        let parent_ids = {
+            let mut state = self.state.lock().unwrap();
+            if ctx.token.is_empty() {
+                let size = state.pending.len().min(MAX_PARENTS_PER_QUERY);
+                state.parents = state.pending.drain(..size).collect();
             }
+            state.parents.keys().cloned().collect::<Vec<_>>()
         };
+        if parent_ids.is_empty() {
+            ctx.done = true;
+            return Ok(());
+        }
 
         let resp = self
             .core
-            .gdrive_list(file_id.as_str(), 100, &ctx.token)
+            .gdrive_list(
+                &parent_ids,
+                self.query.as_deref(),
+                self.recursive,
+                self.page_size,
+                &ctx.token,
+            )
             .await?;
 
         let bytes = match resp.status() {
@@ -64,43 +136,139 @@ impl oio::PageList for GdriveLister {
             return Ok(());
         }
 
-        // Return self at the first page.
-        if ctx.token.is_empty() && !ctx.done {
-            let path = build_rel_path(&self.core.root, &self.path);
-            let e = oio::Entry::new(&path, Metadata::new(EntryMode::DIR));
-            ctx.entries.push_back(e);
-        }
-
         let decoded_response =
             serde_json::from_slice::<GdriveFileList>(&bytes).map_err(new_json_deserialize_error)?;
 
-        if let Some(next_page_token) = decoded_response.next_page_token {
-            ctx.token = next_page_token;
-        } else {
-            ctx.done = true;
-        }
-
+        let mut folders = Vec::new();
         for mut file in decoded_response.files {
-            let file_type = if file.mime_type.as_str() == "application/vnd.google-apps.folder" {
-                if !file.name.ends_with('/') {
-                    file.name += "/";
+            let parent = {
+                let state = self.state.lock().unwrap();
+                file.parents
+                    .iter()
+                    .find_map(|id| state.parents.get(id).cloned())
+                    .unwrap_or_else(|| self.path.clone())
+            };
+
+            // Metadata is returned by list fields, no need to stat every entry.
+            //
+            // The metadata of shortcut targets is not returned, stat them
+            // for the size and modified time.
+            let mut meta = if self.core.shortcuts.is_some() && file.is_shortcut() {
+                self.core.record_shortcut(&file);
+                if file.is_shortcut_to_folder() {
+                    Metadata::new(EntryMode::DIR)
+                } else {
+                    Metadata::new(EntryMode::FILE)
                 }
-                EntryMode::DIR
             } else {
-                EntryMode::FILE
+                file.to_metadata()?
             };
+            if meta.is_dir() && !file.name.ends_with('/') {
+                file.name += "/";
+            }
 
             let root = &self.core.root;
-            let path = format!("{}{}", &self.path, file.name);
+            let path = format!("{}{}", parent, file.name);
             let normalized_path = build_rel_path(root, &path);
 
             // Update path cache with list result.
             self.core.path_cache.insert(&path, &file.id).await;
 
-            let entry = oio::Entry::new(&normalized_path, Metadata::new(file_type));
+            let file_id = self.core.target_id(&file.id);
+            meta.set_file_id(&file_id);
+            if self.recursive && meta.is_dir() {
+                folders.push((file_id, path));
+            }
+
+            let entry = oio::Entry::new(&normalized_path, meta);
             ctx.entries.push_back(entry);
         }
 
+        let mut state = self.state.lock().unwrap();
+        for (id, path) in folders {
+            if self.cycle_policy == ListCyclePolicy::Follow || state.visited.insert(id.clone()) {
+                state.pending.push_back((id, path));
+            } else if self.cycle_policy == ListCyclePolicy::Error {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "folder has been walked, cycle detected during recursive list",
+                )
+                .with_context("path", path)
+                .with_context("file_id", id));
+            }
+        }
+        match decoded_response.next_page_token {
+            Some(next_page_token) => ctx.token = next_page_token,
+            None => {
+                ctx.token.clear();
+                ctx.done = state.pending.is_empty();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// List the revisions of a file, returned as the versions of the file.
+pub struct GdriveRevisionLister {
+    path: String,
+    core: Arc<GdriveCore>,
+    page_size: usize,
+}
+
+impl GdriveRevisionLister {
+    pub fn new(path: String, core: Arc<GdriveCore>, args: &OpList) -> Self {
+        let page_size = args
+            .limit()
+            .unwrap_or(core.list_page_size)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        Self {
+            path,
+            core,
+            page_size,
+        }
+    }
+}
+
+impl oio::PageList for GdriveRevisionLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        if self.path.ends_with('/') {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "gdrive can only list the revisions of a file",
+            )
+            .with_operation(Operation::List)
+            .with_context("path", &self.path));
+        }
+
+        let Some(file_id) = self.core.resolve_target(&self.path).await? else {
+            ctx.done = true;
+            return Ok(());
+        };
+
+        let resp = self
+            .core
+            .gdrive_list_revisions(&file_id, self.page_size, &ctx.token)
+            .await?;
+        let bytes = match resp.status() {
+            StatusCode::OK => resp.into_body().to_bytes(),
+            _ => return Err(parse_error(resp)),
+        };
+        let decoded_response = serde_json::from_slice::<GdriveRevisionList>(&bytes)
+            .map_err(new_json_deserialize_error)?;
+
+        let path = build_rel_path(&self.core.root, &self.path);
+        for revision in decoded_response.revisions {
+            let mut meta = revision.to_metadata()?;
+            meta.set_file_id(&file_id);
+            ctx.entries.push_back(oio::Entry::new(&path, meta));
+        }
+        match decoded_response.next_page_token {
+            Some(next_page_token) => ctx.token = next_page_token,
+            None => ctx.done = true,
+        }
+
         Ok(())
     }
 }
@@ -18,6 +18,8 @@
 use std::sync::Arc;
 
 use bytes::Buf;
+use http::header;
+use http::Response;
 use http::StatusCode;
 
 use super::core::GdriveCore;
@@ -26,12 +28,26 @@ use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// The max times to resume an upload session after retriable errors.
+const MAX_RESUME_ATTEMPTS: usize = 3;
+
+/// GdriveWriter uploads small files in a single request and large files via
+/// resumable upload sessions.
+///
+/// A session will be started once more than `upload_chunk_size` bytes have
+/// been written. Every chunk is uploaded to the session, and the upload will
+/// continue from the bytes persisted by gdrive after retriable errors.
 pub struct GdriveWriter {
     core: Arc<GdriveCore>,
 
     path: String,
 
     file_id: Option<String>,
+
+    buf: oio::QueueBuf,
+    session_uri: Option<String>,
+    /// The bytes that have been persisted by the upload session.
+    offset: u64,
 }
 
 impl GdriveWriter {
@@ -41,12 +57,14 @@ impl GdriveWriter {
             path,
 
             file_id,
+
+            buf: oio::QueueBuf::new(),
+            session_uri: None,
+            offset: 0,
         }
     }
-}
 
-impl oio::OneShotWrite for GdriveWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn write_simple(&self, bs: Buffer) -> Result<()> {
         let size = bs.len();
 
         let resp = if let Some(file_id) = &self.file_id {
@@ -59,19 +77,202 @@ impl oio::OneShotWrite for GdriveWriter {
                 .await
         }?;
 
-        let status = resp.status();
-        match status {
-            StatusCode::OK | StatusCode::CREATED => {
-                // If we don't have the file id before, let's update the cache to avoid re-fetching.
-                if self.file_id.is_none() {
-                    let bs = resp.into_body();
-                    let file: GdriveFile =
-                        serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
-                    self.core.path_cache.insert(&self.path, &file.id).await;
+        match resp.status() {
+            StatusCode::OK | StatusCode::CREATED => self.finish(resp).await,
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Upload the first chunk in the buffer to the upload session.
+    async fn write_next_chunk(&mut self) -> Result<()> {
+        let chunk_size = self.core.upload_chunk_size;
+        let chunk = self.buf.clone().collect().slice(..chunk_size);
+        self.write_chunk(chunk, None).await?;
+        self.buf.advance(chunk_size);
+        Ok(())
+    }
+
+    /// Upload the chunk that starts at `offset` to the upload session.
+    ///
+    /// `total` is the size of the whole file, which is only known while
+    /// uploading the last chunk.
+    async fn write_chunk(&mut self, chunk: Buffer, total: Option<u64>) -> Result<()> {
+        let session_uri = match &self.session_uri {
+            Some(uri) => uri.clone(),
+            None => {
+                let uri = self
+                    .core
+                    .gdrive_create_resumable_upload(&self.path, self.file_id.as_deref())
+                    .await?;
+                self.session_uri = Some(uri.clone());
+                uri
+            }
+        };
+
+        let start = self.offset;
+        let end = start + chunk.len() as u64;
+        let is_last = total.is_some();
+        let total = total.map_or_else(|| "*".to_string(), |v| v.to_string());
+
+        let mut attempts = 0;
+        loop {
+            // Bytes before `offset` have been persisted by gdrive.
+            let body = chunk.slice((self.offset - start) as usize..);
+            let content_range = if body.is_empty() {
+                format!("bytes */{total}")
+            } else {
+                format!("bytes {}-{}/{total}", self.offset, end - 1)
+            };
+
+            let err = match self
+                .core
+                .gdrive_resumable_upload(&session_uri, &content_range, body)
+                .await
+            {
+                Ok(resp) => match resp.status() {
+                    StatusCode::OK | StatusCode::CREATED => return self.finish(resp).await,
+                    // `308 Resume Incomplete` means the upload is not completed yet.
+                    StatusCode::PERMANENT_REDIRECT => {
+                        let offset = parse_persisted_offset(&resp)?;
+                        if !is_last && offset >= end {
+                            self.offset = offset;
+                            return Ok(());
+                        }
+
+                        // Only part of the chunk has been persisted, send the rest.
+                        if offset > self.offset {
+                            self.offset = offset;
+                            continue;
+                        }
+                        Error::new(ErrorKind::Unexpected, "resumable upload made no progress")
+                            .with_context("offset", offset.to_string())
+                            .set_temporary()
+                    }
+                    _ => parse_error(resp),
+                },
+                Err(err) => err,
+            };
+
+            if !err.is_temporary() || attempts >= MAX_RESUME_ATTEMPTS {
+                return Err(err.with_context("session_uri", &session_uri));
+            }
+            attempts += 1;
+
+            // Query the persisted bytes of the session to continue the upload.
+            let resp = self
+                .core
+                .gdrive_resumable_upload(&session_uri, &format!("bytes */{total}"), Buffer::new())
+                .await?;
+            match resp.status() {
+                StatusCode::OK | StatusCode::CREATED => return self.finish(resp).await,
+                StatusCode::PERMANENT_REDIRECT => {
+                    self.offset = parse_persisted_offset(&resp)?.max(start);
+                    if !is_last && self.offset >= end {
+                        return Ok(());
+                    }
                 }
-                Ok(())
+                _ => return Err(parse_error(resp)),
             }
-            _ => Err(parse_error(resp)),
         }
     }
+
+    /// Finish the upload with the file returned by gdrive.
+    async fn finish(&self, resp: Response<Buffer>) -> Result<()> {
+        // If we don't have the file id before, let's update the cache to avoid re-fetching.
+        if self.file_id.is_none() {
+            let bs = resp.into_body();
+            let file: GdriveFile =
+                serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+            self.core.path_cache.insert(&self.path, &file.id).await;
+        }
+        Ok(())
+    }
+}
+
+impl oio::Write for GdriveWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        // Upload the buffered chunks before accepting new bytes, so that
+        // `bs` is not written if error happens.
+        while self.buf.len() >= self.core.upload_chunk_size {
+            self.write_next_chunk().await?;
+        }
+
+        self.buf.push(bs);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        if self.session_uri.is_none() && self.buf.len() <= self.core.upload_chunk_size {
+            self.write_simple(self.buf.clone().collect()).await?;
+            self.buf.clear();
+            return Ok(Metadata::new(EntryMode::FILE));
+        }
+
+        while self.buf.len() > self.core.upload_chunk_size {
+            self.write_next_chunk().await?;
+        }
+
+        let chunk = self.buf.clone().collect();
+        let total = self.offset + chunk.len() as u64;
+        self.write_chunk(chunk, Some(total)).await?;
+        self.buf.clear();
+        Ok(Metadata::new(EntryMode::FILE))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        // Unfinished sessions will be expired by gdrive.
+        self.buf.clear();
+        self.session_uri = None;
+        self.offset = 0;
+        Ok(())
+    }
+}
+
+/// Parse the persisted bytes from the `Range` header of a `308` response.
+///
+/// The header is absent if no bytes have been persisted.
+fn parse_persisted_offset(resp: &Response<Buffer>) -> Result<u64> {
+    let Some(range) = parse_header_to_str(resp.headers(), header::RANGE)? else {
+        return Ok(0);
+    };
+
+    range
+        .strip_prefix("bytes=0-")
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|v| v + 1)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "resumable upload returned invalid range",
+            )
+            .with_context("range", range)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_persisted_offset() {
+        let resp = Response::builder()
+            .status(StatusCode::PERMANENT_REDIRECT)
+            .body(Buffer::new())
+            .unwrap();
+        assert_eq!(parse_persisted_offset(&resp).unwrap(), 0);
+
+        let resp = Response::builder()
+            .status(StatusCode::PERMANENT_REDIRECT)
+            .header(header::RANGE, "bytes=0-262143")
+            .body(Buffer::new())
+            .unwrap();
+        assert_eq!(parse_persisted_offset(&resp).unwrap(), 262144);
+
+        let resp = Response::builder()
+            .status(StatusCode::PERMANENT_REDIRECT)
+            .header(header::RANGE, "bytes=10-20")
+            .body(Buffer::new())
+            .unwrap();
+        assert!(parse_persisted_offset(&resp).is_err());
+    }
 }
@@ -289,6 +289,16 @@ impl Access for HdfsBackend {
     }
 
     async fn write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // libhdfs doesn't expose xattrs, refuse user metadata instead of dropping it.
+        if op.user_metadata().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "hdfs doesn't support user metadata, please use webhdfs with xattr enabled",
+            )
+            .with_operation(Operation::Write)
+            .with_context("path", path));
+        }
+
         let target_path = build_rooted_abs_path(&self.root, path);
         let target_exists = match self.client.metadata(&target_path) {
             Ok(_) => true,
@@ -484,6 +494,16 @@ impl Access for HdfsBackend {
     }
 
     fn blocking_write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        // libhdfs doesn't expose xattrs, refuse user metadata instead of dropping it.
+        if op.user_metadata().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "hdfs doesn't support user metadata, please use webhdfs with xattr enabled",
+            )
+            .with_operation(Operation::BlockingWrite)
+            .with_context("path", path));
+        }
+
         let target_path = build_rooted_abs_path(&self.root, path);
         let target_exists = match self.client.metadata(&target_path) {
             Ok(_) => true,
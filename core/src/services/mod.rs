@@ -40,6 +40,9 @@ pub use azfile::*;
 mod b2;
 pub use b2::*;
 
+mod r#box;
+pub use r#box::*;
+
 mod cacache;
 pub use self::cacache::*;
 
@@ -163,6 +166,9 @@ pub use pcloud::*;
 mod persy;
 pub use self::persy::*;
 
+mod proton_drive;
+pub use proton_drive::*;
+
 mod postgresql;
 pub use self::postgresql::*;
 
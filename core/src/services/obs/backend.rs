@@ -19,6 +19,7 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use bytes::Buf;
 use http::Response;
 use http::StatusCode;
 use http::Uri;
@@ -27,6 +28,7 @@ use reqsign::HuaweicloudObsConfig;
 use reqsign::HuaweicloudObsCredentialLoader;
 use reqsign::HuaweicloudObsSigner;
 
+use super::core::ListMultipartUploadsResult;
 use super::core::ObsCore;
 use super::error::parse_error;
 use super::lister::ObsLister;
@@ -291,6 +293,9 @@ impl Access for ObsBackend {
                 presign_read: true,
                 presign_write: true,
 
+                list_multipart_uploads: true,
+                abort_multipart_upload: true,
+
                 ..Default::default()
             });
 
@@ -397,4 +402,62 @@ impl Access for ObsBackend {
             parts.headers,
         )))
     }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        _: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .obs_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut v = MultipartUpload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    v = v.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(v);
+            }
+
+            key_marker = output.next_key_marker.unwrap_or_default();
+            upload_id_marker = output.next_upload_id_marker.unwrap_or_default();
+            if !output.is_truncated.unwrap_or_default() || key_marker.is_empty() {
+                break;
+            }
+        }
+
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let resp = self
+            .core
+            .obs_abort_multipart_upload(path, args.upload_id())
+            .await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT => Ok(RpAbortMultipartUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
 }
@@ -391,6 +391,39 @@ impl ObsCore {
         self.send(req).await
     }
 
+    /// List on-going multipart uploads under the given path.
+    pub async fn obs_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut queries = vec!["uploads".to_string()];
+        if !p.is_empty() {
+            queries.push(format!("prefix={}", percent_encode_path(&p)));
+        }
+        if !key_marker.is_empty() {
+            queries.push(format!("key-marker={}", percent_encode_path(key_marker)));
+        }
+        if !upload_id_marker.is_empty() {
+            queries.push(format!(
+                "upload-id-marker={}",
+                percent_encode_path(upload_id_marker)
+            ));
+        }
+
+        let url = format!("{}?{}", self.endpoint, queries.join("&"));
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     /// Abort an on-going multipart upload.
     pub async fn obs_abort_multipart_upload(
         &self,
@@ -415,6 +448,24 @@ impl ObsCore {
     }
 }
 
+/// Result of ListMultipartUploads
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 /// Result of CreateMultipartUpload
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -37,17 +37,24 @@ use crate::*;
 
 #[derive(Clone)]
 pub struct OnedriveBackend {
-    root: String,
+    pub(crate) root: String,
     access_token: String,
     client: HttpClient,
+    pub(crate) upload_chunk_size: usize,
 }
 
 impl OnedriveBackend {
-    pub(crate) fn new(root: String, access_token: String, http_client: HttpClient) -> Self {
+    pub(crate) fn new(
+        root: String,
+        access_token: String,
+        http_client: HttpClient,
+        upload_chunk_size: usize,
+    ) -> Self {
         Self {
             root,
             access_token,
             client: http_client,
+            upload_chunk_size,
         }
     }
 }
@@ -82,6 +89,8 @@ impl Access for OnedriveBackend {
                 list: true,
                 ..Default::default()
             });
+        #[cfg(feature = "extensions")]
+        ma.set_extension(super::ext::OnedriveExtension::new(self.clone()));
 
         ma.into()
     }
@@ -279,31 +288,33 @@ impl OnedriveBackend {
         self.client.send(req).await
     }
 
+    /// Upload the bytes starting at `offset` to the upload session.
+    ///
+    /// The upload url is pre-authenticated, sending the `Authorization`
+    /// header may result in `401 Unauthorized`.
     pub(crate) async fn onedrive_chunked_upload(
         &self,
         url: &str,
-        args: &OpWrite,
         offset: usize,
-        chunk_end: usize,
         total_len: usize,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
         let mut req = Request::put(url);
 
-        let auth_header_content = format!("Bearer {}", self.access_token);
-        req = req.header(header::AUTHORIZATION, auth_header_content);
-
-        let range = format!("bytes {}-{}/{}", offset, chunk_end, total_len);
-        req = req.header("Content-Range".to_string(), range);
+        let range = format!("bytes {}-{}/{}", offset, offset + body.len() - 1, total_len);
+        req = req.header(header::CONTENT_RANGE, range);
+        req = req.header(header::CONTENT_LENGTH, body.len().to_string());
 
-        let size = chunk_end - offset + 1;
-        req = req.header(header::CONTENT_LENGTH, size.to_string());
+        let req = req.body(body).map_err(new_request_build_error)?;
 
-        if let Some(mime) = args.content_type() {
-            req = req.header(header::CONTENT_TYPE, mime)
-        }
+        self.client.send(req).await
+    }
 
-        let req = req.body(body).map_err(new_request_build_error)?;
+    /// Get the status of the upload session to resume the upload.
+    pub(crate) async fn onedrive_get_upload_session(&self, url: &str) -> Result<Response<Buffer>> {
+        let req = Request::get(url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
 
         self.client.send(req).await
     }
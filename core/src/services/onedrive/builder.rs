@@ -28,6 +28,12 @@ use crate::services::OnedriveConfig;
 use crate::Scheme;
 use crate::*;
 
+/// The chunk size of upload sessions must be a multiple of 320 KiB.
+const UPLOAD_CHUNK_SIZE_UNIT: usize = 327_680;
+/// The max bytes of a single request to upload sessions is 60 MiB.
+const MAX_UPLOAD_CHUNK_SIZE: usize = 60 * 1024 * 1024;
+const DEFAULT_UPLOAD_CHUNK_SIZE: usize = 32 * UPLOAD_CHUNK_SIZE_UNIT;
+
 impl Configurator for OnedriveConfig {
     type Builder = OnedriveBuilder;
     fn into_builder(self) -> Self::Builder {
@@ -74,6 +80,14 @@ impl OnedriveBuilder {
         self
     }
 
+    /// Set the size of chunks to upload large files with upload sessions.
+    ///
+    /// Must be a multiple of 320 KiB and no larger than 60 MiB. Default to 10 MiB.
+    pub fn upload_chunk_size(mut self, size: usize) -> Self {
+        self.config.upload_chunk_size = Some(size);
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -103,8 +117,29 @@ impl Builder for OnedriveBuilder {
             })?
         };
 
+        let upload_chunk_size = self
+            .config
+            .upload_chunk_size
+            .unwrap_or(DEFAULT_UPLOAD_CHUNK_SIZE);
+        if upload_chunk_size == 0
+            || upload_chunk_size % UPLOAD_CHUNK_SIZE_UNIT != 0
+            || upload_chunk_size > MAX_UPLOAD_CHUNK_SIZE
+        {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "upload_chunk_size must be a multiple of 320 KiB and no larger than 60 MiB",
+            )
+            .with_context("service", Scheme::Onedrive)
+            .with_context("upload_chunk_size", upload_chunk_size.to_string()));
+        }
+
         match self.config.access_token.clone() {
-            Some(access_token) => Ok(OnedriveBackend::new(root, access_token, client)),
+            Some(access_token) => Ok(OnedriveBackend::new(
+                root,
+                access_token,
+                client,
+                upload_chunk_size,
+            )),
             None => Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")),
         }
     }
@@ -30,6 +30,10 @@ pub struct OnedriveConfig {
     pub access_token: Option<String>,
     /// root path of OneDrive folder.
     pub root: Option<String>,
+    /// The size of chunks to upload large files with upload sessions.
+    ///
+    /// Must be a multiple of 320 KiB and no larger than 60 MiB. Default to 10 MiB.
+    pub upload_chunk_size: Option<usize>,
 }
 
 impl Debug for OnedriveConfig {
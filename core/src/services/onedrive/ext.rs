@@ -0,0 +1,263 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use bytes::Buf;
+
+use super::backend::OnedriveBackend;
+use super::builder::OnedriveBuilder;
+use super::error::parse_error;
+use super::graph_model::GraphApiOnedriveDeltaResponse;
+use super::graph_model::OneDriveDeltaItem;
+use crate::raw::*;
+use crate::*;
+
+const DRIVE_ROOT_PREFIX: &str = "/drive/root:";
+
+/// A change of an item returned by [`OnedriveExtension::delta`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct OnedriveChange {
+    /// The id of the item, which is stable across renames.
+    pub id: String,
+    /// The path of the item relative to root, dirs end with `/`.
+    ///
+    /// Delta doesn't return the paths of items, they are resolved from the
+    /// parents returned in the same delta. The path is `None` if its parent
+    /// is not returned, for example, a file changed in a folder that is not
+    /// changed, or the item is outside of root. Track items by `id` instead.
+    pub path: Option<String>,
+    /// The item has been deleted.
+    pub deleted: bool,
+    /// The metadata of the item, only the mode is available for deleted items.
+    pub metadata: Metadata,
+}
+
+/// The changes returned by [`OnedriveExtension::delta`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct OnedriveDelta {
+    /// The changed items, an item may appear more than once.
+    pub changes: Vec<OnedriveChange>,
+    /// The token to get the changes since this delta.
+    pub token: String,
+}
+
+/// OnedriveExtension provides onedrive-specific operations that are not
+/// covered by [`Operator`].
+///
+/// Get it via `op.as_service::<services::Onedrive>()`.
+#[derive(Debug, Clone)]
+pub struct OnedriveExtension {
+    backend: OnedriveBackend,
+}
+
+impl OnedriveExtension {
+    pub(super) fn new(backend: OnedriveBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Get the changes of items under dir `path` via
+    /// [delta](https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_delta?view=odsp-graph-online).
+    ///
+    /// All items under `path` will be returned if `token` is `None`, pass the
+    /// `token` of the returned delta to get the changes since then.
+    pub async fn delta(&self, path: &str, token: Option<&str>) -> Result<OnedriveDelta> {
+        let mut url = match token {
+            Some(token) => token.to_string(),
+            None => self.delta_url(path),
+        };
+
+        let mut parents = HashMap::new();
+        let mut changes = Vec::new();
+        loop {
+            let resp = self.backend.onedrive_get_next_list_page(&url).await?;
+            if !resp.status().is_success() {
+                return Err(parse_error(resp)
+                    .with_operation("OnedriveExtension::delta")
+                    .with_context("path", path));
+            }
+
+            let page: GraphApiOnedriveDeltaResponse =
+                serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+            for item in page.value {
+                changes.push(self.parse_change(item, &mut parents)?);
+            }
+
+            match (page.next_link, page.delta_link) {
+                (Some(next_link), _) => url = next_link,
+                (None, Some(delta_link)) => {
+                    return Ok(OnedriveDelta {
+                        changes,
+                        token: delta_link,
+                    })
+                }
+                (None, None) => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "delta response doesn't contain next link or delta link",
+                    )
+                    .with_operation("OnedriveExtension::delta")
+                    .with_context("path", path))
+                }
+            }
+        }
+    }
+
+    fn delta_url(&self, path: &str) -> String {
+        let path = build_rooted_abs_path(&self.backend.root, path);
+        // According to OneDrive API examples, the path should not end with a slash.
+        match path.strip_suffix('/').unwrap_or(&path) {
+            "" => format!("{}/drive/root/delta", OnedriveBackend::BASE_URL),
+            p => format!(
+                "{}/drive/root:{}:/delta",
+                OnedriveBackend::BASE_URL,
+                percent_encode_path(p)
+            ),
+        }
+    }
+
+    /// Parse the item into change, `parents` records the absolute paths of
+    /// dirs that have been seen, keyed by their ids.
+    fn parse_change(
+        &self,
+        item: OneDriveDeltaItem,
+        parents: &mut HashMap<String, String>,
+    ) -> Result<OnedriveChange> {
+        let is_dir = item.folder.is_some();
+        let mut metadata = Metadata::new(if is_dir {
+            EntryMode::DIR
+        } else {
+            EntryMode::FILE
+        });
+        if let Some(v) = item.size.filter(|_| !is_dir) {
+            metadata.set_content_length(v);
+        }
+        if let Some(v) = &item.e_tag {
+            metadata.set_etag(v);
+        }
+        if let Some(v) = &item.last_modified_date_time {
+            metadata.set_last_modified(parse_datetime_from_rfc3339(v)?);
+        }
+
+        let parent = item
+            .parent_reference
+            .as_ref()
+            .and_then(|parent| match &parent.path {
+                Some(path) => Some(path.strip_prefix(DRIVE_ROOT_PREFIX)?.to_string()),
+                None => parents.get(parent.id.as_ref()?).cloned(),
+            });
+        let abs_path = match (parent, &item.name) {
+            (Some(parent), Some(name)) => {
+                let path = format!("{}/{}", parent.trim_end_matches('/'), name);
+                if is_dir {
+                    parents.insert(item.id.clone(), path.clone());
+                }
+                Some(path)
+            }
+            _ => None,
+        };
+
+        let root = &self.backend.root;
+        let path = abs_path.and_then(|path| {
+            let path = if is_dir { format!("{path}/") } else { path };
+            match path.strip_prefix(root.as_str()) {
+                Some(p) => Some(if p.is_empty() {
+                    "/".to_string()
+                } else {
+                    p.to_string()
+                }),
+                None => None,
+            }
+        });
+
+        Ok(OnedriveChange {
+            id: item.id,
+            path,
+            deleted: item.deleted.is_some(),
+            metadata,
+        })
+    }
+}
+
+impl ServiceExtension for OnedriveBuilder {
+    type Extension = OnedriveExtension;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, name: &str, parent: (&str, Option<&str>), is_dir: bool) -> OneDriveDeltaItem {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "parentReference": { "id": parent.0, "path": parent.1 },
+            "folder": if is_dir { Some(serde_json::json!({})) } else { None },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_change() {
+        let ext = OnedriveExtension::new(OnedriveBackend::new(
+            "/data/".to_string(),
+            "token".to_string(),
+            HttpClient::new().unwrap(),
+            10 * 1024 * 1024,
+        ));
+        let mut parents = HashMap::new();
+
+        let change = ext
+            .parse_change(
+                item("1", "data", ("0", Some("/drive/root:")), true),
+                &mut parents,
+            )
+            .unwrap();
+        assert_eq!(change.path.as_deref(), Some("/"));
+
+        let change = ext
+            .parse_change(item("2", "logs", ("1", None), true), &mut parents)
+            .unwrap();
+        assert_eq!(change.path.as_deref(), Some("logs/"));
+        assert!(change.metadata.is_dir());
+
+        let change = ext
+            .parse_change(item("3", "a.txt", ("2", None), false), &mut parents)
+            .unwrap();
+        assert_eq!(change.path.as_deref(), Some("logs/a.txt"));
+        assert!(change.metadata.is_file());
+
+        // Parent is not returned in this delta.
+        let change = ext
+            .parse_change(item("4", "b.txt", ("9", None), false), &mut parents)
+            .unwrap();
+        assert_eq!(change.path, None);
+        assert_eq!(change.id, "4");
+
+        // Outside of root.
+        let change = ext
+            .parse_change(
+                item("5", "c.txt", ("0", Some("/drive/root:")), false),
+                &mut parents,
+            )
+            .unwrap();
+        assert_eq!(change.path, None);
+    }
+}
@@ -125,6 +125,68 @@ pub struct OneDriveUploadSessionCreationResponseBody {
     pub expiration_date_time: String,
 }
 
+/// The status of an upload session.
+///
+/// Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#resuming-an-in-progress-upload
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OneDriveUploadSessionStatus {
+    /// The ranges that have not been received yet, like `["12345-55232"]`.
+    #[serde(rename = "nextExpectedRanges")]
+    pub next_expected_ranges: Vec<String>,
+}
+
+impl OneDriveUploadSessionStatus {
+    /// The offset of the first byte that has not been received yet.
+    ///
+    /// Returns `None` if all bytes have been received.
+    pub fn next_offset(&self) -> Option<usize> {
+        self.next_expected_ranges
+            .iter()
+            .filter_map(|range| range.split('-').next()?.parse::<usize>().ok())
+            .min()
+    }
+}
+
+/// A page of the changes returned by delta.
+///
+/// Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_delta?view=odsp-graph-online
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct GraphApiOnedriveDeltaResponse {
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+    #[serde(rename = "@odata.deltaLink")]
+    pub delta_link: Option<String>,
+    pub value: Vec<OneDriveDeltaItem>,
+}
+
+/// DriveItem returned by delta, most fields are omitted for deleted items.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OneDriveDeltaItem {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(rename = "parentReference")]
+    pub parent_reference: Option<DeltaParentReference>,
+    pub folder: Option<serde_json::Value>,
+    pub deleted: Option<serde_json::Value>,
+    pub size: Option<u64>,
+    #[serde(rename = "eTag")]
+    pub e_tag: Option<String>,
+    #[serde(rename = "lastModifiedDateTime")]
+    pub last_modified_date_time: Option<String>,
+}
+
+/// Delta doesn't return the path of parent in most cases, items should be
+/// tracked by id.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct DeltaParentReference {
+    pub id: Option<String>,
+    pub path: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OneDriveUploadSessionCreationRequestBody {
     item: FileUploadItem,
@@ -291,3 +353,53 @@ fn test_parse_folder_single() {
         panic!("item_type is not folder");
     }
 }
+
+#[test]
+fn test_parse_delta_response() {
+    let data = r#"{
+        "@odata.deltaLink": "https://graph.microsoft.com/v1.0/me/drive/root/delta?token=abc",
+        "value": [
+            {
+                "id": "1",
+                "name": "docs",
+                "parentReference": { "id": "0", "path": "/drive/root:" },
+                "folder": { "childCount": 1 }
+            },
+            {
+                "id": "2",
+                "name": "a.txt",
+                "parentReference": { "id": "1" },
+                "file": { "mimeType": "text/plain" },
+                "size": 5,
+                "eTag": "\"etag\"",
+                "lastModifiedDateTime": "2024-01-01T00:00:00Z"
+            },
+            {
+                "id": "3",
+                "parentReference": { "id": "1" },
+                "deleted": { "state": "deleted" }
+            }
+        ]
+    }"#;
+
+    let resp: GraphApiOnedriveDeltaResponse = serde_json::from_str(data).unwrap();
+    assert!(resp.next_link.is_none());
+    assert_eq!(
+        resp.delta_link.as_deref(),
+        Some("https://graph.microsoft.com/v1.0/me/drive/root/delta?token=abc")
+    );
+    assert_eq!(resp.value.len(), 3);
+    assert!(resp.value[0].folder.is_some());
+    assert_eq!(resp.value[1].size, Some(5));
+    assert_eq!(
+        resp.value[1]
+            .parent_reference
+            .as_ref()
+            .unwrap()
+            .id
+            .as_deref(),
+        Some("1")
+    );
+    assert!(resp.value[2].deleted.is_some());
+    assert!(resp.value[2].name.is_none());
+}
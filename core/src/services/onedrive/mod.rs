@@ -31,5 +31,14 @@ mod builder;
 #[cfg(feature = "services-onedrive")]
 pub use builder::OnedriveBuilder as Onedrive;
 
+#[cfg(all(feature = "services-onedrive", feature = "extensions"))]
+mod ext;
+#[cfg(all(feature = "services-onedrive", feature = "extensions"))]
+pub use ext::OnedriveChange;
+#[cfg(all(feature = "services-onedrive", feature = "extensions"))]
+pub use ext::OnedriveDelta;
+#[cfg(all(feature = "services-onedrive", feature = "extensions"))]
+pub use ext::OnedriveExtension;
+
 mod config;
 pub use config::OnedriveConfig;
@@ -16,16 +16,25 @@
 // under the License.
 
 use bytes::Buf;
-use bytes::Bytes;
 use http::StatusCode;
 
 use super::backend::OnedriveBackend;
 use super::error::parse_error;
 use super::graph_model::OneDriveUploadSessionCreationRequestBody;
 use super::graph_model::OneDriveUploadSessionCreationResponseBody;
+use super::graph_model::OneDriveUploadSessionStatus;
 use crate::raw::*;
 use crate::*;
 
+/// The max times to resume an upload session after retriable errors.
+const MAX_RESUME_ATTEMPTS: usize = 3;
+
+/// OneDriveWriter uploads small files in a single request and large files via
+/// upload sessions.
+///
+/// Upload sessions require the fragments to be uploaded in order, so the
+/// chunks are uploaded one by one. After retriable errors, the upload will
+/// continue from the next expected range reported by the session.
 pub struct OneDriveWriter {
     backend: OnedriveBackend,
 
@@ -35,9 +44,7 @@ pub struct OneDriveWriter {
 
 impl OneDriveWriter {
     const MAX_SIMPLE_SIZE: usize = 4 * 1024 * 1024;
-    // If your app splits a file into multiple byte ranges, the size of each byte range MUST be a multiple of 320 KiB (327,680 bytes). Using a fragment size that does not divide evenly by 320 KiB will result in errors committing some files.
-    // https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#upload-bytes-to-the-upload-session
-    const CHUNK_SIZE_FACTOR: usize = 327_680;
+
     pub fn new(backend: OnedriveBackend, op: OpWrite, path: String) -> Self {
         OneDriveWriter { backend, op, path }
     }
@@ -50,7 +57,7 @@ impl oio::OneShotWrite for OneDriveWriter {
         if size <= Self::MAX_SIMPLE_SIZE {
             self.write_simple(bs).await?;
         } else {
-            self.write_chunked(bs.to_bytes()).await?;
+            self.write_chunked(bs).await?;
         }
 
         Ok(())
@@ -74,60 +81,64 @@ impl OneDriveWriter {
         }
     }
 
-    pub(crate) async fn write_chunked(&self, total_bytes: Bytes) -> Result<()> {
+    pub(crate) async fn write_chunked(&self, bs: Buffer) -> Result<()> {
         // Upload large files via sessions: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#upload-bytes-to-the-upload-session
         // 1. Create an upload session
-        // 2. Upload the bytes of each chunk
-        // 3. Commit the session
-
-        let session_response = self.create_upload_session().await?;
+        // 2. Upload the bytes of each chunk, the session is committed after the last one.
+        let session = self.create_upload_session().await?;
+        let upload_url = session.upload_url.as_str();
 
+        let total_len = bs.len();
+        let chunk_size = self.backend.upload_chunk_size;
         let mut offset = 0;
+        let mut attempts = 0;
+        while offset < total_len {
+            let end = (offset + chunk_size).min(total_len);
+            let chunk = bs.slice(offset..end);
 
-        let iter = total_bytes.chunks(OneDriveWriter::CHUNK_SIZE_FACTOR);
-
-        for chunk in iter {
-            let mut end = offset + OneDriveWriter::CHUNK_SIZE_FACTOR;
-            if end > total_bytes.len() {
-                end = total_bytes.len();
-            }
-            let total_len = total_bytes.len();
-            let chunk_end = end - 1;
-
-            let resp = self
+            let err = match self
                 .backend
-                .onedrive_chunked_upload(
-                    &session_response.upload_url,
-                    &OpWrite::default(),
-                    offset,
-                    chunk_end,
-                    total_len,
-                    Buffer::from(Bytes::copy_from_slice(chunk)),
-                )
-                .await?;
-
-            let status = resp.status();
-
-            match status {
-                // Typical response code: 202 Accepted
-                // Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_put_content?view=odsp-graph-online#response
-                StatusCode::ACCEPTED | StatusCode::CREATED | StatusCode::OK => {}
-                _ => return Err(parse_error(resp)),
+                .onedrive_chunked_upload(upload_url, offset, total_len, chunk)
+                .await
+            {
+                Ok(resp) => match resp.status() {
+                    // The last chunk returns the created item.
+                    StatusCode::CREATED | StatusCode::OK => return Ok(()),
+                    // Typical response code: 202 Accepted
+                    StatusCode::ACCEPTED => {
+                        offset = end;
+                        continue;
+                    }
+                    _ => parse_error(resp),
+                },
+                Err(err) => err,
+            };
+
+            if !err.is_temporary() || attempts >= MAX_RESUME_ATTEMPTS {
+                return Err(err.with_context("upload_url", upload_url));
             }
+            attempts += 1;
 
-            offset += OneDriveWriter::CHUNK_SIZE_FACTOR;
+            // Query the session to continue from the bytes that have not been received.
+            let resp = self.backend.onedrive_get_upload_session(upload_url).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+            let status: OneDriveUploadSessionStatus =
+                serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+            match status.next_offset() {
+                Some(next) => offset = next,
+                // All bytes have been received, the item has been created.
+                None => return Ok(()),
+            }
         }
 
         Ok(())
     }
 
     async fn create_upload_session(&self) -> Result<OneDriveUploadSessionCreationResponseBody> {
-        let file_name_from_path = self.path.split('/').last().ok_or_else(|| {
-            Error::new(
-                ErrorKind::Unexpected,
-                "connection string must have AccountName",
-            )
-        })?;
+        let file_name_from_path = get_basename(&self.path);
         let url = format!(
             "{}/drive/root:{}:/createUploadSession",
             OnedriveBackend::BASE_URL,
@@ -154,3 +165,23 @@ impl OneDriveWriter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_session_next_offset() {
+        let status: OneDriveUploadSessionStatus =
+            serde_json::from_str(r#"{"expirationDateTime":"2015-01-29T09:21:55.523Z","nextExpectedRanges":["12345-55232","77829-99375"]}"#)
+                .unwrap();
+        assert_eq!(status.next_offset(), Some(12345));
+
+        let status: OneDriveUploadSessionStatus =
+            serde_json::from_str(r#"{"nextExpectedRanges":["26-"]}"#).unwrap();
+        assert_eq!(status.next_offset(), Some(26));
+
+        let status: OneDriveUploadSessionStatus = serde_json::from_str("{}").unwrap();
+        assert_eq!(status.next_offset(), None);
+    }
+}
@@ -475,6 +475,9 @@ impl Access for OssBackend {
                 presign_read: true,
                 presign_write: true,
 
+                list_multipart_uploads: true,
+                abort_multipart_upload: true,
+
                 batch: true,
                 batch_max_operations: Some(self.core.batch_max_operations),
 
@@ -554,7 +557,8 @@ impl Access for OssBackend {
             args.limit(),
             args.start_after(),
         );
-        Ok((RpList::default(), oio::PageLister::new(l)))
+        let l = oio::PageLister::new(l);
+        Ok((RpList::default().with_stats(l.stats()), l))
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
@@ -645,4 +649,62 @@ impl Access for OssBackend {
             Err(parse_error(resp))
         }
     }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        _: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .oss_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut v = MultipartUpload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    v = v.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(v);
+            }
+
+            key_marker = output.next_key_marker.unwrap_or_default();
+            upload_id_marker = output.next_upload_id_marker.unwrap_or_default();
+            if !output.is_truncated.unwrap_or_default() || key_marker.is_empty() {
+                break;
+            }
+        }
+
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let resp = self
+            .core
+            .oss_abort_multipart_upload(path, args.upload_id())
+            .await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT => Ok(RpAbortMultipartUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
 }
@@ -84,6 +84,12 @@ impl oio::PageList for OssLister {
         ctx.done = !output.is_truncated;
         ctx.token = output.next_continuation_token.unwrap_or_default();
 
+        let bytes = output.contents.iter().map(|v| v.size).sum::<u64>();
+        ctx.stats.update(|stats| {
+            *stats.entries.get_or_insert(0) += output.key_count;
+            *stats.bytes.get_or_insert(0) += bytes;
+        });
+
         for prefix in output.common_prefixes {
             let de = oio::Entry::new(
                 &build_rel_path(&self.core.root, &prefix.prefix),
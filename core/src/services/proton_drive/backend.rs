@@ -0,0 +1,101 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use super::core::ProtonDriveCore;
+use super::lister::ProtonDriveLister;
+use super::reader::ProtonDriveReader;
+use crate::raw::*;
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct ProtonDriveBackend {
+    pub core: Arc<ProtonDriveCore>,
+}
+
+impl Access for ProtonDriveBackend {
+    type Reader = ProtonDriveReader;
+    type Writer = ();
+    type Lister = oio::PageLister<ProtonDriveLister>;
+    type BlockingReader = ();
+    type BlockingWriter = ();
+    type BlockingLister = ();
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        let mut ma = AccessorInfo::default();
+        ma.set_scheme(Scheme::ProtonDrive)
+            .set_root(&self.core.root)
+            .set_native_capability(Capability {
+                stat: true,
+                stat_has_content_length: true,
+                stat_has_content_type: true,
+                stat_has_last_modified: true,
+
+                read: true,
+
+                list: true,
+                list_has_content_length: true,
+                list_has_content_type: true,
+                list_has_last_modified: true,
+                ..Default::default()
+            });
+
+        ma.into()
+    }
+
+    async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
+        let path = build_abs_path(&self.core.root, path);
+        let id = self.core.resolve_existing_path(&path).await?;
+
+        let link = self.core.api.get_link(&id).await?;
+        if link.is_folder() {
+            return Ok(RpStat::new(link.to_metadata(None)?));
+        }
+        let key = self.core.api.node_key(&id).await?;
+        let xattr = link.decrypt_xattr(&key)?;
+        Ok(RpStat::new(link.to_metadata(xattr.as_ref())?))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let path = build_abs_path(&self.core.root, path);
+        let id = self.core.resolve_existing_path(&path).await?;
+
+        let link = self.core.api.get_link(&id).await?;
+        let (revision, content_key_packet) = link.active_revision()?;
+        let key = self.core.api.node_key(&id).await?;
+        let xattr = link.decrypt_xattr(&key)?;
+        let blocks = self.core.api.list_blocks(&id, &revision.id).await?;
+
+        let r = ProtonDriveReader::new(
+            self.core.api.clone(),
+            key,
+            content_key_packet,
+            blocks,
+            xattr.as_ref().and_then(|v| v.common.block_sizes.as_deref()),
+            args.range(),
+        );
+        Ok((RpRead::new(), r))
+    }
+
+    async fn list(&self, path: &str, _args: OpList) -> Result<(RpList, Self::Lister)> {
+        let path = build_abs_path(&self.core.root, path);
+        let l = ProtonDriveLister::new(self.core.clone(), path);
+        Ok((RpList::default(), oio::PageLister::new(l)))
+    }
+}
@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use log::debug;
+
+use super::backend::ProtonDriveBackend;
+use super::core::*;
+use crate::raw::*;
+use crate::services::ProtonDriveConfig;
+use crate::*;
+
+impl Configurator for ProtonDriveConfig {
+    type Builder = ProtonDriveBuilder;
+    fn into_builder(self) -> Self::Builder {
+        ProtonDriveBuilder {
+            config: self,
+            http_client: None,
+        }
+    }
+}
+
+/// [Proton Drive](https://proton.me/drive) backend support.
+#[derive(Default)]
+#[doc = include_str!("docs.md")]
+pub struct ProtonDriveBuilder {
+    config: ProtonDriveConfig,
+
+    http_client: Option<HttpClient>,
+}
+
+impl Debug for ProtonDriveBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProtonDriveBuilder")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl ProtonDriveBuilder {
+    /// Set root path of proton drive folder.
+    pub fn root(mut self, root: &str) -> Self {
+        self.config.root = if root.is_empty() {
+            None
+        } else {
+            Some(root.to_string())
+        };
+
+        self
+    }
+
+    /// Set the endpoint of proton api.
+    ///
+    /// Default to `https://mail.proton.me/api`.
+    pub fn endpoint(mut self, endpoint: &str) -> Self {
+        self.config.endpoint = if endpoint.is_empty() {
+            None
+        } else {
+            Some(endpoint.trim_end_matches('/').to_string())
+        };
+
+        self
+    }
+
+    /// Set the username (email) of the proton account.
+    pub fn username(mut self, username: &str) -> Self {
+        self.config.username = Some(username.to_string());
+        self
+    }
+
+    /// Set the password of the proton account.
+    pub fn password(mut self, password: &str) -> Self {
+        self.config.password = Some(password.to_string());
+        self
+    }
+
+    /// Set the mailbox password of accounts in two-password mode.
+    ///
+    /// The password will be used to unlock keys if not set.
+    pub fn mailbox_password(mut self, mailbox_password: &str) -> Self {
+        self.config.mailbox_password = if mailbox_password.is_empty() {
+            None
+        } else {
+            Some(mailbox_password.to_string())
+        };
+
+        self
+    }
+
+    /// Set the app version sent in `x-pm-appversion`.
+    ///
+    /// Proton rejects requests from unknown clients, change it if the
+    /// default one is not accepted anymore.
+    pub fn app_version(mut self, app_version: &str) -> Self {
+        self.config.app_version = if app_version.is_empty() {
+            None
+        } else {
+            Some(app_version.to_string())
+        };
+
+        self
+    }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+}
+
+impl Builder for ProtonDriveBuilder {
+    const SCHEME: Scheme = Scheme::ProtonDrive;
+    type Config = ProtonDriveConfig;
+
+    fn build(self) -> Result<impl Access> {
+        let root = normalize_root(&self.config.root.unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let username = self.config.username.ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "username is missing")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::ProtonDrive)
+        })?;
+        let password = self.config.password.ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "password is missing")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::ProtonDrive)
+        })?;
+        let mailbox_password = self
+            .config
+            .mailbox_password
+            .unwrap_or_else(|| password.clone());
+
+        let client = if let Some(client) = self.http_client {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::ProtonDrive)
+            })?
+        };
+
+        let api = Arc::new(ProtonDriveApi::new(
+            self.config
+                .endpoint
+                .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string()),
+            self.config
+                .app_version
+                .unwrap_or_else(|| DEFAULT_APP_VERSION.to_string()),
+            client,
+            username,
+            password,
+            mailbox_password,
+        ));
+        Ok(ProtonDriveBackend {
+            core: Arc::new(ProtonDriveCore {
+                root,
+                api: api.clone(),
+                path_cache: PathCacher::new(ProtonDrivePathQuery { api }).with_lock(),
+            }),
+        })
+    }
+}
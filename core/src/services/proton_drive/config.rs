@@ -0,0 +1,56 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// [Proton Drive](https://proton.me/drive) configuration.
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct ProtonDriveConfig {
+    /// The root for proton drive.
+    pub root: Option<String>,
+    /// The endpoint of proton api.
+    ///
+    /// Default to `https://mail.proton.me/api`.
+    pub endpoint: Option<String>,
+    /// The username (email) of the proton account.
+    pub username: Option<String>,
+    /// The password of the proton account.
+    pub password: Option<String>,
+    /// The mailbox password of accounts in two-password mode.
+    ///
+    /// The password will be used to unlock keys if not set.
+    pub mailbox_password: Option<String>,
+    /// The app version sent in `x-pm-appversion`.
+    pub app_version: Option<String>,
+}
+
+impl Debug for ProtonDriveConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProtonDriveConfig")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("username", &self.username)
+            .field("app_version", &self.app_version)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,379 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use hmac::Hmac;
+use hmac::Mac;
+use pgp::cleartext::CleartextSignedMessage;
+use pgp::types::SecretKeyTrait;
+use pgp::Deserializable;
+use pgp::Message;
+use pgp::SignedPublicKey;
+use pgp::SignedSecretKey;
+use rand::RngCore;
+use rsa::BigUint;
+use sha2::Digest;
+use sha2::Sha256;
+use sha2::Sha512;
+
+use crate::*;
+
+/// The generator of proton's SRP groups.
+const SRP_GENERATOR: u32 = 2;
+
+/// The public key that proton signs SRP modulus with.
+///
+/// Pinned from proton's official SRP clients.
+const MODULUS_PUBLIC_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xjMEXAHLgxYJKwYBBAHaRw8BAQdAFurWXXwjTemqjD7CXjXVyKf0of7n9Ctm
+L8v9enkzggHNEnByb3RvbkBzcnAubW9kdWx1c8J3BBAWCgApBQJcAcuDBgsJ
+BwgDAgkQNQWFxOlRjyYEFQgKAgMWAgECGQECGwMCHgEAAPGRAP9sauJsW12U
+MnTQUZpsbJb53d0Wv55mZIIiJL2XulpWPQD/V6NglBd96lZKBmInSXX/kXat
+Sv+y0io+LR8i2+jV+AbOOARcAcuDEgorBgEEAZdVAQUBAQdAeJHUz1c9+KfE
+kSIgcBRE3WuXC4oj5a2/U3oASExGDW4DAQgHwmEEGBYIABMFAlwBy4MJEDUF
+hcTpUY8mAhsMAAD/XQD8DxNI6E78meodQI+wLsrKLeHn32iLvUqJbVDhfWSU
+WO4BAMcm1u02t4VKw++ttECPt+HUgPUq5pqQWe5Q2cW4TMsE
+=Y4Mw
+-----END PGP PUBLIC KEY BLOCK-----";
+
+/// ProtonKey is an OpenPGP private key with its passphrase.
+///
+/// Keys are kept locked, the passphrase is used every time decrypting.
+#[derive(Clone)]
+pub struct ProtonKey {
+    key: SignedSecretKey,
+    passphrase: String,
+}
+
+impl Debug for ProtonKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProtonKey").finish_non_exhaustive()
+    }
+}
+
+impl ProtonKey {
+    /// Parse the armored private key and check the passphrase.
+    pub fn unlock(armored: &str, passphrase: String) -> Result<Self> {
+        let (key, _) = SignedSecretKey::from_string(armored)
+            .map_err(|e| new_pgp_error("private key is invalid", e))?;
+        key.unlock(|| passphrase.clone(), |_| Ok(()))
+            .map_err(|e| new_pgp_error("passphrase of private key is invalid", e))?;
+
+        Ok(Self { key, passphrase })
+    }
+
+    /// Decrypt an armored message.
+    pub fn decrypt_armored(&self, armored: &str) -> Result<Vec<u8>> {
+        let (msg, _) =
+            Message::from_string(armored).map_err(|e| new_pgp_error("message is invalid", e))?;
+        self.decrypt(msg)
+    }
+
+    /// Decrypt a binary message.
+    pub fn decrypt_binary(&self, bs: &[u8]) -> Result<Vec<u8>> {
+        let msg = Message::from_bytes(bs).map_err(|e| new_pgp_error("message is invalid", e))?;
+        self.decrypt(msg)
+    }
+
+    fn decrypt(&self, msg: Message) -> Result<Vec<u8>> {
+        let passphrase = self.passphrase.clone();
+        let (msg, _) = msg
+            .decrypt(move || passphrase, &[&self.key])
+            .map_err(|e| new_pgp_error("failed to decrypt message", e))?;
+        msg.decompress()
+            .and_then(|msg| msg.get_content())
+            .map_err(|e| new_pgp_error("failed to decrypt message", e))?
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected, "message doesn't contain data"))
+    }
+}
+
+/// Decrypt an armored message with the first key that works.
+pub fn decrypt_armored_with_any(keys: &[ProtonKey], armored: &str) -> Result<Vec<u8>> {
+    let mut last_err = None;
+    for key in keys {
+        match key.decrypt_armored(armored) {
+            Ok(bs) => return Ok(bs),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| Error::new(ErrorKind::Unexpected, "no key to decrypt message")))
+}
+
+/// Compute the passphrase of private keys from the (mailbox) password and
+/// the key salt.
+pub fn key_passphrase(password: &str, key_salt: &str) -> Result<String> {
+    let salt = decode_salt(key_salt)?;
+    let hashed = bcrypt_hash(password, &salt)?;
+    // Only the hash part after `$2y$10$` and 22 chars of salt is used.
+    Ok(hashed[29..].to_string())
+}
+
+/// The client side values of proton's SRP login.
+pub struct SrpProofs {
+    pub client_ephemeral: Vec<u8>,
+    pub client_proof: Vec<u8>,
+    pub server_proof: Vec<u8>,
+}
+
+/// Generate the proofs of SRP login from the response of `auth/info`.
+///
+/// Only auth version 3 and 4 are supported, which are used by all accounts
+/// that have logged in since 2018.
+pub fn srp_proofs(
+    password: &str,
+    version: u8,
+    salt: &str,
+    signed_modulus: &str,
+    server_ephemeral: &str,
+) -> Result<SrpProofs> {
+    if version < 3 {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "legacy auth version is not supported, please log in on the web first",
+        )
+        .with_context("version", version));
+    }
+
+    let modulus = parse_modulus(signed_modulus)?;
+    let server_ephemeral = BASE64_STANDARD
+        .decode(server_ephemeral)
+        .map_err(|e| new_srp_error("server ephemeral is not valid base64").set_source(e))?;
+    let salt = BASE64_STANDARD
+        .decode(salt)
+        .map_err(|e| new_srp_error("salt is not valid base64").set_source(e))?;
+    let hashed_password = hash_password(password, &salt, &modulus)?;
+
+    let n = BigUint::from_bytes_le(&modulus);
+    let bit_length = modulus.len() as u64 * 8;
+    // The client secret is in `[bit_length * 2, N - 1)`.
+    let lower = BigUint::from(bit_length * 2);
+    let mut bs = vec![0; modulus.len()];
+    rand::thread_rng().fill_bytes(&mut bs);
+    let client_secret = BigUint::from_bytes_le(&bs) % (&n - BigUint::from(1u32) - &lower) + &lower;
+
+    generate_proofs(
+        &hashed_password,
+        &modulus,
+        &server_ephemeral,
+        &client_secret,
+    )
+}
+
+fn generate_proofs(
+    hashed_password: &[u8],
+    modulus: &[u8],
+    server_ephemeral: &[u8],
+    client_secret: &BigUint,
+) -> Result<SrpProofs> {
+    let len = modulus.len();
+    let n = BigUint::from_bytes_le(modulus);
+    let g = BigUint::from(SRP_GENERATOR);
+    let b = BigUint::from_bytes_le(server_ephemeral);
+    let x = BigUint::from_bytes_le(hashed_password);
+    let n_minus_one = &n - BigUint::from(1u32);
+
+    let k = BigUint::from_bytes_le(&expand_hash(&[&to_bytes(&g, len), modulus].concat())) % &n;
+    if k <= BigUint::from(1u32) || k >= n_minus_one {
+        return Err(new_srp_error("multiplier is out of bound"));
+    }
+    if b <= BigUint::from(1u32) || b >= n_minus_one {
+        return Err(new_srp_error("server ephemeral is out of bound"));
+    }
+
+    let a = g.modpow(client_secret, &n);
+    let a_bytes = to_bytes(&a, len);
+    let u = BigUint::from_bytes_le(&expand_hash(&[&a_bytes, server_ephemeral].concat()));
+    if u == BigUint::from(0u32) {
+        return Err(new_srp_error("scrambling parameter is zero"));
+    }
+
+    // S = (B - k * g^x) ^ (a + u * x) mod N
+    let kgx = (&k * g.modpow(&x, &n)) % &n;
+    let base = (&b + &n - kgx) % &n;
+    let exponent = (&u * &x + client_secret) % &n_minus_one;
+    let shared = to_bytes(&base.modpow(&exponent, &n), len);
+
+    let client_proof = expand_hash(&[&a_bytes, server_ephemeral, &shared].concat());
+    let server_proof = expand_hash(&[&a_bytes, &client_proof, &shared].concat());
+    Ok(SrpProofs {
+        client_ephemeral: a_bytes,
+        client_proof,
+        server_proof,
+    })
+}
+
+/// Hash the password with auth version 3 and 4.
+fn hash_password(password: &str, salt: &[u8], modulus: &[u8]) -> Result<Vec<u8>> {
+    let salt = [salt, b"proton"].concat();
+    let salt: [u8; 16] = salt
+        .try_into()
+        .map_err(|_| new_srp_error("salt must be 10 bytes"))?;
+    let hashed = bcrypt::hash_with_salt(password, 10, salt)
+        .map_err(|e| new_srp_error("failed to hash password").set_source(e))?
+        .format_for_version(bcrypt::Version::TwoY);
+
+    Ok(expand_hash(&[hashed.as_bytes(), modulus].concat()))
+}
+
+fn bcrypt_hash(password: &str, salt: &[u8]) -> Result<String> {
+    let salt: [u8; 16] = salt
+        .try_into()
+        .map_err(|_| Error::new(ErrorKind::Unexpected, "key salt must be 16 bytes"))?;
+    Ok(bcrypt::hash_with_salt(password, 10, salt)
+        .map_err(|e| Error::new(ErrorKind::Unexpected, "failed to hash password").set_source(e))?
+        .format_for_version(bcrypt::Version::TwoY))
+}
+
+fn decode_salt(salt: &str) -> Result<Vec<u8>> {
+    BASE64_STANDARD.decode(salt).map_err(|e| {
+        Error::new(ErrorKind::Unexpected, "key salt is not valid base64").set_source(e)
+    })
+}
+
+/// Verify the cleartext signed message against [`MODULUS_PUBLIC_KEY`] and
+/// extract the modulus from it.
+fn parse_modulus(signed: &str) -> Result<Vec<u8>> {
+    let (msg, _) = CleartextSignedMessage::from_string(signed)
+        .map_err(|e| new_pgp_error("modulus is not a signed message", e))?;
+    let (key, _) = SignedPublicKey::from_string(MODULUS_PUBLIC_KEY)
+        .map_err(|e| new_pgp_error("modulus public key is invalid", e))?;
+    msg.verify(&key)
+        .map_err(|e| new_pgp_error("signature of modulus is invalid", e))?;
+
+    BASE64_STANDARD
+        .decode(msg.text().trim())
+        .map_err(|e| new_srp_error("modulus is not valid base64").set_source(e))
+}
+
+/// Expand the data into 256 bytes hash.
+fn expand_hash(data: &[u8]) -> Vec<u8> {
+    (0u8..4)
+        .flat_map(|i| {
+            Sha512::new()
+                .chain_update(data)
+                .chain_update([i])
+                .finalize()
+        })
+        .collect()
+}
+
+/// Encode the number in little endian with the given length.
+fn to_bytes(v: &BigUint, len: usize) -> Vec<u8> {
+    let mut bs = v.to_bytes_le();
+    bs.resize(len, 0);
+    bs
+}
+
+/// Compute the hash of name in a folder, which is the hex encoded
+/// HMAC-SHA256 of the name keyed by the hash key of the folder.
+pub fn name_hash(hash_key: &[u8], name: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(hash_key).expect("hmac must accept keys of any size");
+    mac.update(name.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn new_srp_error(msg: &str) -> Error {
+    Error::new(ErrorKind::Unexpected, format!("srp login failed: {msg}"))
+}
+
+fn new_pgp_error(msg: &str, err: pgp::errors::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, msg).set_source(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modulus() {
+        let signed = "-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\nAQID\n-----BEGIN PGP SIGNATURE-----\nVersion: ProtonMail\n\nwl4E\n-----END PGP SIGNATURE-----\n";
+        assert!(
+            parse_modulus(signed).is_err(),
+            "forged signature must be rejected"
+        );
+        assert!(parse_modulus("AQID").is_err());
+
+        let (key, _) = SignedPublicKey::from_string(MODULUS_PUBLIC_KEY).unwrap();
+        key.verify().expect("modulus public key must be valid");
+    }
+
+    #[test]
+    fn test_generate_proofs() {
+        // 2^127 - 1 is a prime, which is small enough for test.
+        let mut modulus = vec![0xff; 16];
+        modulus[15] = 0x7f;
+        let n = BigUint::from_bytes_le(&modulus);
+        let g = BigUint::from(SRP_GENERATOR);
+        let x_bytes = expand_hash(b"password");
+        let x = BigUint::from_bytes_le(&x_bytes);
+
+        // Server side: B = k * v + g^b, v = g^x
+        let k =
+            BigUint::from_bytes_le(&expand_hash(&[&to_bytes(&g, 16), &modulus[..]].concat())) % &n;
+        let v = g.modpow(&x, &n);
+        let server_secret = BigUint::from(123456789u64);
+        let b = (&k * &v + g.modpow(&server_secret, &n)) % &n;
+        let b_bytes = to_bytes(&b, 16);
+
+        let proofs =
+            generate_proofs(&x_bytes, &modulus, &b_bytes, &BigUint::from(987654321u64)).unwrap();
+
+        // Server side: S = (A * v^u) ^ b
+        let a = BigUint::from_bytes_le(&proofs.client_ephemeral);
+        let u = BigUint::from_bytes_le(&expand_hash(
+            &[&proofs.client_ephemeral[..], &b_bytes[..]].concat(),
+        ));
+        let shared = to_bytes(
+            &((&a * v.modpow(&u, &n)) % &n).modpow(&server_secret, &n),
+            16,
+        );
+        assert_eq!(
+            proofs.client_proof,
+            expand_hash(&[&proofs.client_ephemeral[..], &b_bytes[..], &shared[..]].concat())
+        );
+        assert_eq!(
+            proofs.server_proof,
+            expand_hash(
+                &[
+                    &proofs.client_ephemeral[..],
+                    &proofs.client_proof[..],
+                    &shared[..]
+                ]
+                .concat()
+            )
+        );
+    }
+
+    #[test]
+    fn test_name_hash() {
+        // HMAC-SHA256 test case 2 of RFC 4231.
+        assert_eq!(
+            name_hash(b"Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
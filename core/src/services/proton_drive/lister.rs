@@ -0,0 +1,80 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use super::core::*;
+use crate::raw::*;
+use crate::*;
+
+pub struct ProtonDriveLister {
+    core: Arc<ProtonDriveCore>,
+    path: String,
+}
+
+impl ProtonDriveLister {
+    pub fn new(core: Arc<ProtonDriveCore>, path: String) -> Self {
+        Self { core, path }
+    }
+}
+
+impl oio::PageList for ProtonDriveLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        let Some(link_id) = self.core.path_cache.get(&self.path).await? else {
+            ctx.done = true;
+            return Ok(());
+        };
+
+        let page = if ctx.token.is_empty() {
+            // Return self at the first page.
+            let path = build_rel_path(&self.core.root, &self.path);
+            ctx.entries
+                .push_back(oio::Entry::new(&path, Metadata::new(EntryMode::DIR)));
+            0
+        } else {
+            ctx.token.parse::<usize>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "list token is invalid").set_source(e)
+            })?
+        };
+
+        let links = self.core.api.list_children(&link_id, page).await?;
+        // Names of children are encrypted by the key of the folder.
+        let key = self.core.api.node_key(&link_id).await?;
+        for link in links.iter().filter(|v| v.state == LINK_STATE_ACTIVE) {
+            let name = link.decrypt_name(&key)?;
+            let (path, meta) = if link.is_folder() {
+                (format!("{}{name}/", self.path), link.to_metadata(None)?)
+            } else {
+                let xattr = link.decrypt_xattr(&self.core.api.unlock_node_key(link, &key)?)?;
+                (
+                    format!("{}{name}", self.path),
+                    link.to_metadata(xattr.as_ref())?,
+                )
+            };
+
+            let path = build_rel_path(&self.core.root, &path);
+            ctx.entries.push_back(oio::Entry::new(&path, meta));
+        }
+
+        if links.len() < CHILDREN_PAGE_SIZE {
+            ctx.done = true;
+        } else {
+            ctx.token = (page + 1).to_string();
+        }
+        Ok(())
+    }
+}
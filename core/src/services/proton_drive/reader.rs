@@ -0,0 +1,109 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use super::core::*;
+use super::crypto::ProtonKey;
+use crate::raw::*;
+use crate::*;
+
+/// ProtonDriveReader downloads and decrypts the blocks of a file one by one.
+///
+/// Blocks are encrypted separately, so only the blocks that overlap the
+/// range will be downloaded.
+pub struct ProtonDriveReader {
+    api: Arc<ProtonDriveApi>,
+    key: ProtonKey,
+    content_key_packet: Vec<u8>,
+
+    blocks: VecDeque<ProtonBlock>,
+    /// The bytes to skip in the next block.
+    skip: u64,
+    /// The bytes left to read, `None` means reading to the end.
+    remaining: Option<u64>,
+}
+
+impl ProtonDriveReader {
+    /// Create a reader of `range` from all blocks of the revision.
+    ///
+    /// `block_sizes` is the plaintext sizes of blocks, all blocks except the
+    /// last one are treated as [`DEFAULT_BLOCK_SIZE`] if it's not recorded.
+    pub fn new(
+        api: Arc<ProtonDriveApi>,
+        key: ProtonKey,
+        content_key_packet: Vec<u8>,
+        mut blocks: Vec<ProtonBlock>,
+        block_sizes: Option<&[u64]>,
+        range: BytesRange,
+    ) -> Self {
+        blocks.sort_by_key(|v| v.index);
+
+        let mut skip = range.offset();
+        let mut first = 0;
+        while first + 1 < blocks.len() {
+            let size = block_sizes
+                .and_then(|v| v.get(first).copied())
+                .unwrap_or(DEFAULT_BLOCK_SIZE);
+            if skip < size {
+                break;
+            }
+            skip -= size;
+            first += 1;
+        }
+
+        Self {
+            api,
+            key,
+            content_key_packet,
+            blocks: blocks.into_iter().skip(first).collect(),
+            skip,
+            remaining: range.size(),
+        }
+    }
+}
+
+impl oio::Read for ProtonDriveReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        if self.remaining == Some(0) {
+            return Ok(Buffer::new());
+        }
+        let Some(block) = self.blocks.pop_front() else {
+            return Ok(Buffer::new());
+        };
+
+        let encrypted = self.api.download_block(&block).await?;
+        // Blocks are the data packets of the message whose session key is
+        // in the content key packet.
+        let mut message = self.content_key_packet.clone();
+        message.extend(encrypted.to_bytes());
+        let mut bs = Bytes::from(self.key.decrypt_binary(&message)?);
+
+        let skip = (self.skip as usize).min(bs.len());
+        bs = bs.slice(skip..);
+        self.skip = 0;
+        if let Some(remaining) = self.remaining.as_mut() {
+            let n = (*remaining as usize).min(bs.len());
+            bs.truncate(n);
+            *remaining -= n as u64;
+        }
+        Ok(Buffer::from(bs))
+    }
+}
@@ -27,6 +27,7 @@ use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Buf;
 use constants::X_AMZ_META_PREFIX;
+use http::HeaderMap;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -38,15 +39,20 @@ use reqsign::AwsAssumeRoleLoader;
 use reqsign::AwsConfig;
 use reqsign::AwsCredentialLoad;
 use reqsign::AwsDefaultLoader;
-use reqsign::AwsV4Signer;
 use reqwest::Url;
 
+use super::compat::S3CompatMode;
 use super::core::*;
+use super::error::is_copy_source_too_large;
 use super::error::parse_error;
 use super::error::parse_s3_error_code;
+use super::express::is_directory_bucket;
+use super::express::parse_directory_bucket_zone;
 use super::lister::{S3Lister, S3Listers, S3ObjectVersionsLister};
+use super::select::S3SelectReader;
 use super::writer::S3Writer;
 use super::writer::S3Writers;
+use crate::raw::oio::MultipartWrite;
 use crate::raw::oio::PageLister;
 use crate::raw::*;
 use crate::services::S3Config;
@@ -65,6 +71,11 @@ static ENDPOINT_TEMPLATES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new
 
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
 
+/// The min part size of multipart copy, larger parts are used if the object
+/// needs more than 10000 parts.
+const MULTIPART_COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+const MULTIPART_MAX_PARTS: u64 = 10000;
+
 impl Configurator for S3Config {
     type Builder = S3Builder;
     fn into_builder(self) -> Self::Builder {
@@ -72,6 +83,7 @@ impl Configurator for S3Config {
             config: self,
             customized_credential_load: None,
             http_client: None,
+            clock: None,
         }
     }
 }
@@ -86,6 +98,7 @@ pub struct S3Builder {
 
     customized_credential_load: Option<Box<dyn AwsCredentialLoad>>,
     http_client: Option<HttpClient>,
+    clock: Option<Clock>,
 }
 
 impl Debug for S3Builder {
@@ -431,6 +444,15 @@ impl S3Builder {
         self
     }
 
+    /// Set whether the bucket is a directory bucket of S3 Express One Zone.
+    ///
+    /// It's detected by the `--x-s3` suffix of the bucket name by default,
+    /// like `data--usw2-az1--x-s3`.
+    pub fn express_one_zone(mut self, enabled: bool) -> Self {
+        self.config.express_one_zone = Some(enabled);
+        self
+    }
+
     /// Disable stat with override so that opendal will not send stat request with override queries.
     ///
     /// For example, R2 doesn't support stat with `response_content_type` query.
@@ -459,6 +481,17 @@ impl S3Builder {
         self
     }
 
+    /// Specify the clock that used to sign requests.
+    ///
+    /// Use [`Clock::skew_corrected`] if the local time may drift from the
+    /// server, which fails requests with `RequestTimeTooSkewed`.
+    ///
+    /// Default to [`Clock::system`].
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     /// Set bucket versioning status for this backend
     pub fn enable_versioning(mut self, enabled: bool) -> Self {
         self.config.enable_versioning = enabled;
@@ -548,6 +581,29 @@ impl S3Builder {
         self
     }
 
+    /// Set compatible mode of this backend.
+    ///
+    /// The compatible mode selects a quirk profile of the vendor, for
+    /// example, R2 will disable stat with override and limit batch
+    /// operations to 700 automatically.
+    ///
+    /// Available options:
+    /// - "aws"
+    /// - "minio"
+    /// - "r2"
+    /// - "ceph"
+    /// - "oracle"
+    /// - "wasabi"
+    pub fn compat_mode(mut self, mode: &str) -> Self {
+        self.config.compat_mode = if mode.is_empty() {
+            None
+        } else {
+            Some(mode.to_string())
+        };
+
+        self
+    }
+
     /// Detect region of S3 bucket.
     ///
     /// # Args
@@ -674,6 +730,23 @@ impl Builder for S3Builder {
         let root = normalize_root(&self.config.root.clone().unwrap_or_default());
         debug!("backend use root {}", &root);
 
+        // Apply quirks of the compatible mode, quirks only enable options so
+        // that options enabled by users are kept.
+        let quirks = match self.config.compat_mode.as_deref() {
+            Some(v) => S3CompatMode::from_str(v)?.quirks(),
+            None => Default::default(),
+        };
+        debug!("backend use quirks {:?}", &quirks);
+        if quirks.virtual_host_style && !self.config.bucket.contains('.') {
+            self.config.enable_virtual_host_style = true;
+        }
+        if quirks.disable_stat_with_override {
+            self.config.disable_stat_with_override = true;
+        }
+        if self.config.batch_max_operations.is_none() {
+            self.config.batch_max_operations = quirks.batch_max_operations;
+        }
+
         // Handle bucket name.
         let bucket = if self.is_bucket_valid() {
             Ok(&self.config.bucket)
@@ -732,6 +805,19 @@ impl Builder for S3Builder {
                 })?),
             };
 
+        if quirks.disable_checksum && self.config.checksum_algorithm.is_some() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "checksum_algorithm is not supported by this compat_mode",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::S3)
+            .with_context(
+                "compat_mode",
+                self.config.compat_mode.as_deref().unwrap_or_default(),
+            ));
+        }
+
         let checksum_algorithm = match self.config.checksum_algorithm.as_deref() {
             Some("crc32c") => Some(ChecksumAlgorithm::Crc32c),
             None => None,
@@ -772,6 +858,28 @@ impl Builder for S3Builder {
         // Retain the user's endpoint if it exists; otherwise, try loading it from the environment.
         self.config.endpoint = self.config.endpoint.or_else(|| cfg.endpoint_url.clone());
 
+        // Directory buckets are served by the zonal endpoint in virtual host style.
+        let express = self
+            .config
+            .express_one_zone
+            .unwrap_or_else(|| is_directory_bucket(&self.config.bucket));
+        if express {
+            if self.config.endpoint.is_none() {
+                let zone = parse_directory_bucket_zone(&self.config.bucket).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "zone of the directory bucket is unknown, please set endpoint",
+                    )
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::S3)
+                    .with_context("bucket", &self.config.bucket)
+                })?;
+                self.config.endpoint =
+                    Some(format!("https://s3express-{zone}.{region}.amazonaws.com"));
+            }
+            self.config.enable_virtual_host_style = true;
+        }
+
         // Building endpoint.
         let endpoint = self.build_endpoint(&region);
         debug!("backend use endpoint: {endpoint}");
@@ -851,8 +959,6 @@ impl Builder for S3Builder {
             }
         };
 
-        let signer = AwsV4Signer::new("s3", &region);
-
         let batch_max_operations = self
             .config
             .batch_max_operations
@@ -872,12 +978,16 @@ impl Builder for S3Builder {
                 allow_anonymous: self.config.allow_anonymous,
                 disable_stat_with_override: self.config.disable_stat_with_override,
                 enable_versioning: self.config.enable_versioning,
-                signer,
+                region,
+                clock: self.clock.unwrap_or_default(),
+                express_session: express.then(Default::default),
                 loader,
                 credential_loaded: AtomicBool::new(false),
                 client,
                 batch_max_operations,
                 checksum_algorithm,
+                list_objects_v1: quirks.list_objects_v1,
+                identity_encoding: quirks.identity_encoding,
             }),
         })
     }
@@ -889,8 +999,104 @@ pub struct S3Backend {
     core: Arc<S3Core>,
 }
 
+impl S3Backend {
+    /// Read the records selected by `expression` via SelectObjectContent.
+    async fn read_select(
+        &self,
+        path: &str,
+        args: &OpRead,
+        expression: &str,
+        format: SelectInputFormat,
+    ) -> Result<(RpRead, <Self as Access>::Reader)> {
+        if !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with select doesn't support range",
+            )
+            .with_operation(Operation::Read)
+            .with_context("path", path));
+        }
+
+        let req = SelectObjectContentRequest::new(expression, format);
+        let resp = self
+            .core
+            .s3_select_object_content(path, &req, args.customer_key())
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok((
+                RpRead::default(),
+                TwoWays::Two(S3SelectReader::new(resp.into_body())),
+            )),
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                Err(parse_error(Response::from_parts(part, buf)))
+            }
+        }
+    }
+}
+
+impl S3Backend {
+    /// Copy objects larger than the limit of CopyObject via UploadPartCopy.
+    ///
+    /// Metadata of the source is not copied by S3 in multipart copy, so it's
+    /// set while creating the upload.
+    async fn copy_multipart(&self, from: &str, to: &str, meta: &Metadata) -> Result<()> {
+        let mut op = OpWrite::default();
+        if let Some(v) = meta.content_type() {
+            op = op.with_content_type(v);
+        }
+        if let Some(v) = meta.content_disposition() {
+            op = op.with_content_disposition(v);
+        }
+        if let Some(v) = meta.cache_control() {
+            op = op.with_cache_control(v);
+        }
+        if let Some(v) = meta.user_metadata() {
+            op = op.with_user_metadata(v.clone());
+        }
+
+        let w = S3Writer::new(self.core.clone(), to, op);
+        let upload_id = w.initiate_part().await?;
+
+        let size = meta.content_length();
+        let part_size = multipart_copy_part_size(size);
+        let copy = async {
+            let mut parts = Vec::new();
+            let mut offset = 0;
+            while offset < size {
+                let len = part_size.min(size - offset);
+                let part = w
+                    .copy_part(
+                        from,
+                        &upload_id,
+                        parts.len(),
+                        BytesRange::new(offset, Some(len)),
+                    )
+                    .await?;
+                parts.push(part);
+                offset += len;
+            }
+            w.complete_part(&upload_id, &parts).await
+        };
+
+        match copy.await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                // Abort the upload to not leave copied parts behind, the
+                // error of copying is returned anyway.
+                if let Err(e) = w.abort_part(&upload_id).await {
+                    warn!("abort multipart copy to {to} failed: {e}");
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
 impl Access for S3Backend {
-    type Reader = HttpBody;
+    type Reader = TwoWays<HttpBody, S3SelectReader<HttpBody>>;
     type Writer = S3Writers;
     type Lister = S3Listers;
     type BlockingReader = ();
@@ -904,6 +1110,10 @@ impl Access for S3Backend {
             .set_name(&self.core.bucket)
             .set_native_capability(Capability {
                 stat: true,
+                stat_has_expiration: true,
+                stat_has_server_side_encryption: true,
+                stat_has_object_lock: true,
+                stat_with_customer_key: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
                 stat_with_override_cache_control: !self.core.disable_stat_with_override,
@@ -918,6 +1128,8 @@ impl Access for S3Backend {
                 read_with_override_content_disposition: true,
                 read_with_override_content_type: true,
                 read_with_version: self.core.enable_versioning,
+                read_with_select: true,
+                read_with_customer_key: true,
 
                 write: true,
                 write_can_empty: true,
@@ -925,7 +1137,10 @@ impl Access for S3Backend {
                 write_with_cache_control: true,
                 write_with_content_type: true,
                 write_with_if_not_exists: true,
+                write_with_if_match: true,
                 write_with_user_metadata: true,
+                write_with_object_lock: true,
+                write_with_customer_key: true,
 
                 // The min multipart size of S3 is 5 MiB.
                 //
@@ -947,20 +1162,29 @@ impl Access for S3Backend {
 
                 list: true,
                 list_with_limit: true,
-                list_with_start_after: true,
+                // Directory buckets don't support `start-after`.
+                list_with_start_after: self.core.express_session.is_none(),
                 list_with_recursive: true,
                 list_with_version: self.core.enable_versioning,
+                list_has_delete_marker: self.core.enable_versioning,
 
                 presign: true,
                 presign_stat: true,
                 presign_read: true,
                 presign_write: true,
 
+                sign: true,
+
                 batch: true,
                 batch_max_operations: Some(self.core.batch_max_operations),
 
+                list_multipart_uploads: true,
+                abort_multipart_upload: true,
+
                 ..Default::default()
             });
+        #[cfg(feature = "extensions")]
+        am.set_extension(super::ext::S3Extension::new(self.core.clone()));
 
         am.into()
     }
@@ -971,33 +1195,29 @@ impl Access for S3Backend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => {
-                let headers = resp.headers();
-                let mut meta = parse_into_metadata(path, headers)?;
-
-                let user_meta = parse_prefixed_headers(headers, X_AMZ_META_PREFIX);
-                if !user_meta.is_empty() {
-                    meta.with_user_metadata(user_meta);
-                }
-
-                if let Some(v) = parse_header_to_str(headers, "x-amz-version-id")? {
-                    meta.set_version(v);
-                }
-
-                Ok(RpStat::new(meta))
-            }
+            StatusCode::OK => Ok(RpStat::new(parse_s3_metadata(path, resp.headers())?)),
             _ => Err(parse_error(resp)),
         }
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if let Some((expression, format)) = args.select() {
+            return self.read_select(path, &args, expression, format).await;
+        }
+
         let resp = self.core.s3_get_object(path, args.range(), &args).await?;
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+            // The headers of a full read carry the same metadata as `stat`.
+            StatusCode::OK => {
+                let meta = parse_s3_metadata(path, resp.headers())?;
+                Ok((
+                    RpRead::default().with_metadata(Some(meta)),
+                    TwoWays::One(resp.into_body()),
+                ))
             }
+            StatusCode::PARTIAL_CONTENT => Ok((RpRead::default(), TwoWays::One(resp.into_body()))),
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
@@ -1037,25 +1257,27 @@ impl Access for S3Backend {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let l = if args.version() {
-            TwoWays::Two(PageLister::new(S3ObjectVersionsLister::new(
+        if args.version() {
+            let l = PageLister::new(S3ObjectVersionsLister::new(
                 self.core.clone(),
                 path,
                 args.recursive(),
                 args.limit(),
                 args.start_after(),
-            )))
+            ))
+            .with_error_policy(args.error_policy().clone());
+            Ok((RpList::default(), TwoWays::Two(l)))
         } else {
-            TwoWays::One(PageLister::new(S3Lister::new(
+            let l = PageLister::new(S3Lister::new(
                 self.core.clone(),
                 path,
                 args.recursive(),
                 args.limit(),
                 args.start_after(),
-            )))
-        };
-
-        Ok((RpList::default(), l))
+            ))
+            .with_error_policy(args.error_policy().clone());
+            Ok((RpList::default().with_stats(l.stats()), TwoWays::One(l)))
+        }
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
@@ -1065,6 +1287,17 @@ impl Access for S3Backend {
 
         match status {
             StatusCode::OK => Ok(RpCopy::default()),
+            // CopyObject only supports objects up to 5 GiB, copy larger
+            // objects part by part instead.
+            _ if is_copy_source_too_large(&resp) => {
+                let resp = self.core.s3_head_object(from, OpStat::new()).await?;
+                if resp.status() != StatusCode::OK {
+                    return Err(parse_error(resp));
+                }
+                let meta = parse_s3_metadata(from, resp.headers())?;
+                self.copy_multipart(from, to, &meta).await?;
+                Ok(RpCopy::default())
+            }
             _ => Err(parse_error(resp)),
         }
     }
@@ -1097,6 +1330,13 @@ impl Access for S3Backend {
         )))
     }
 
+    async fn sign(&self, args: OpSign) -> Result<RpSign> {
+        let mut req = args.into_request();
+        self.core.sign(&mut req).await?;
+
+        Ok(RpSign::new(SignedRequest::new(req)))
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let ops = args.into_operation();
         if ops.len() > 1000 {
@@ -1143,6 +1383,121 @@ impl Access for S3Backend {
             Err(parse_error(resp))
         }
     }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        _: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .s3_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut v = MultipartUpload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    v = v.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(v);
+            }
+
+            key_marker = output.next_key_marker.unwrap_or_default();
+            upload_id_marker = output.next_upload_id_marker.unwrap_or_default();
+            if !output.is_truncated.unwrap_or_default() || key_marker.is_empty() {
+                break;
+            }
+        }
+
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        args: OpAbortMultipartUpload,
+    ) -> Result<RpAbortMultipartUpload> {
+        let resp = self
+            .core
+            .s3_abort_multipart_upload(path, args.upload_id())
+            .await?;
+
+        match resp.status() {
+            // s3 returns code 204 if abort succeeds.
+            StatusCode::NO_CONTENT => Ok(RpAbortMultipartUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+/// Decide the part size to copy an object of `size` via UploadPartCopy.
+fn multipart_copy_part_size(size: u64) -> u64 {
+    MULTIPART_COPY_PART_SIZE.max(size.div_ceil(MULTIPART_MAX_PARTS))
+}
+
+/// Parse the metadata of an object from the response headers of
+/// `HeadObject` or `GetObject`.
+fn parse_s3_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_metadata(path, headers)?;
+
+    let user_meta = parse_prefixed_headers(headers, X_AMZ_META_PREFIX);
+    if !user_meta.is_empty() {
+        meta.with_user_metadata(user_meta);
+    }
+
+    if let Some(v) = parse_header_to_str(headers, "x-amz-version-id")? {
+        meta.set_version(v);
+    }
+
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_EXPIRATION)? {
+        if let Some(expiration) = parse_expiration(v)? {
+            meta.set_expiration(expiration);
+        }
+    }
+
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_MODE)? {
+        meta.set_object_lock_mode(v.parse()?);
+    }
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE)? {
+        meta.set_object_lock_retain_until(parse_datetime_from_rfc3339(v)?);
+    }
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD)? {
+        meta.set_legal_hold(v.eq_ignore_ascii_case("ON"));
+    }
+
+    // SSE-C objects return the customer algorithm only.
+    let algorithm = match parse_header_to_str(headers, constants::X_AMZ_SERVER_SIDE_ENCRYPTION)? {
+        Some(v) => Some(v),
+        None => parse_header_to_str(
+            headers,
+            constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+        )?,
+    };
+    if let Some(v) = algorithm {
+        meta.set_server_side_encryption(v);
+    }
+    if let Some(v) = parse_header_to_str(
+        headers,
+        constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+    )? {
+        meta.set_server_side_encryption_key_id(v);
+    }
+
+    Ok(meta)
 }
 
 #[cfg(test)]
@@ -1204,6 +1559,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_express_one_zone() {
+        let op = Operator::new(
+            S3Builder::default()
+                .bucket("data--usw2-az1--x-s3")
+                .region("us-west-2")
+                .disable_config_load(),
+        )
+        .expect("build must succeed")
+        .finish();
+        assert!(!op.info().native_capability().list_with_start_after);
+
+        let err = Operator::new(
+            S3Builder::default()
+                .bucket("data")
+                .region("us-west-2")
+                .disable_config_load()
+                .express_one_zone(true),
+        )
+        .map(|_| ())
+        .expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_build_compat_mode() {
+        let build = |b: S3Builder| {
+            let b = b.bucket("test").region("auto").disable_config_load();
+            Operator::new(b).expect("build must succeed").finish()
+        };
+
+        let cap = build(S3Builder::default()).info().native_capability();
+        assert!(cap.stat_with_override_content_type);
+
+        let cap = build(S3Builder::default().compat_mode("r2"))
+            .info()
+            .native_capability();
+        assert!(!cap.stat_with_override_content_type);
+
+        // Options enabled by users are kept even if the profile doesn't enable them.
+        let cap = build(
+            S3Builder::default()
+                .compat_mode("minio")
+                .disable_stat_with_override(),
+        )
+        .info()
+        .native_capability();
+        assert!(!cap.stat_with_override_content_type);
+    }
+
+    #[test]
+    fn test_multipart_copy_part_size() {
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(multipart_copy_part_size(6 * gib), MULTIPART_COPY_PART_SIZE);
+        // 10 TiB needs larger parts to fit in 10000 parts.
+        let size = 10 * 1024 * gib;
+        let part_size = multipart_copy_part_size(size);
+        assert!(part_size > MULTIPART_COPY_PART_SIZE);
+        assert!(size.div_ceil(part_size) <= MULTIPART_MAX_PARTS);
+    }
+
+    #[test]
+    fn test_parse_s3_metadata_object_lock() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_MODE,
+            "COMPLIANCE".parse().unwrap(),
+        );
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE,
+            "2030-01-01T00:00:00Z".parse().unwrap(),
+        );
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD,
+            "ON".parse().unwrap(),
+        );
+
+        let meta = parse_s3_metadata("a.txt", &headers).expect("parse must succeed");
+        assert_eq!(meta.object_lock_mode(), Some(ObjectLockMode::Compliance));
+        assert_eq!(
+            meta.object_lock_retain_until(),
+            Some(parse_datetime_from_rfc3339("2030-01-01T00:00:00Z").unwrap())
+        );
+        assert_eq!(meta.legal_hold(), Some(true));
+    }
+
     #[tokio::test]
     async fn test_detect_region() {
         let cases = vec![
@@ -0,0 +1,147 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::str::FromStr;
+
+use crate::*;
+
+/// Compatible mode of s3 services.
+///
+/// Every mode carries a set of [`S3Quirks`] that describes how the
+/// vendor differs from AWS S3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum S3CompatMode {
+    Aws,
+    Minio,
+    R2,
+    Ceph,
+    Oracle,
+    Wasabi,
+}
+
+impl FromStr for S3CompatMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "aws" => Ok(Self::Aws),
+            "minio" => Ok(Self::Minio),
+            "r2" | "cloudflare" => Ok(Self::R2),
+            "ceph" | "rgw" => Ok(Self::Ceph),
+            "oracle" | "oci" => Ok(Self::Oracle),
+            "wasabi" => Ok(Self::Wasabi),
+            _ => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "compat_mode is not a supported s3 compatible mode",
+            )
+            .with_context("service", Scheme::S3)
+            .with_context("compat_mode", s)),
+        }
+    }
+}
+
+impl S3CompatMode {
+    /// Get the quirks of this compatible mode.
+    pub fn quirks(self) -> S3Quirks {
+        match self {
+            // AWS S3 is deprecating path style, but buckets with dot still
+            // require it.
+            Self::Aws => S3Quirks {
+                virtual_host_style: true,
+                ..Default::default()
+            },
+            Self::Minio => S3Quirks::default(),
+            // R2 returns `Internal Error` while deleting more than 700 objects
+            // in a batch, and will transparently decompress gzip encoded
+            // objects which breaks the content length we expect.
+            Self::R2 => S3Quirks {
+                disable_stat_with_override: true,
+                disable_checksum: true,
+                batch_max_operations: Some(700),
+                identity_encoding: true,
+                ..Default::default()
+            },
+            Self::Ceph => S3Quirks {
+                disable_checksum: true,
+                ..Default::default()
+            },
+            Self::Oracle => S3Quirks {
+                list_objects_v1: true,
+                disable_stat_with_override: true,
+                disable_checksum: true,
+                ..Default::default()
+            },
+            Self::Wasabi => S3Quirks {
+                disable_checksum: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Quirks of s3 compatible services.
+///
+/// The default value matches the behavior of AWS S3 without any
+/// special handling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct S3Quirks {
+    /// Send requests in virtual host style if the bucket allows.
+    pub virtual_host_style: bool,
+    /// Use `ListObjects` (V1) instead of `ListObjectsV2`.
+    pub list_objects_v1: bool,
+    /// Service doesn't support stat with override queries.
+    pub disable_stat_with_override: bool,
+    /// Service doesn't support `x-amz-checksum-*` headers.
+    pub disable_checksum: bool,
+    /// Service limits the number of operations in a batch request.
+    pub batch_max_operations: Option<usize>,
+    /// Send `Accept-Encoding: identity` while reading objects.
+    pub identity_encoding: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compat_mode_from_str() {
+        let cases = vec![
+            ("aws", Some(S3CompatMode::Aws)),
+            ("MinIO", Some(S3CompatMode::Minio)),
+            ("r2", Some(S3CompatMode::R2)),
+            ("rgw", Some(S3CompatMode::Ceph)),
+            ("oci", Some(S3CompatMode::Oracle)),
+            ("wasabi", Some(S3CompatMode::Wasabi)),
+            ("unknown", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(S3CompatMode::from_str(input).ok(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_compat_mode_quirks() {
+        assert_eq!(S3CompatMode::Minio.quirks(), S3Quirks::default());
+
+        let r2 = S3CompatMode::R2.quirks();
+        assert!(r2.disable_stat_with_override);
+        assert!(r2.disable_checksum);
+        assert_eq!(r2.batch_max_operations, Some(700));
+
+        assert!(S3CompatMode::Oracle.quirks().list_objects_v1);
+    }
+}
@@ -173,6 +173,27 @@ pub struct S3Config {
     /// Available options:
     /// - "crc32c"
     pub checksum_algorithm: Option<String>,
+    /// Compatible mode of the s3 service.
+    ///
+    /// Select a quirk profile of the vendor so that opendal can adjust its
+    /// behaviors like virtual host style, ListObjects version, checksum
+    /// headers and so on. The profile only enables options, options enabled
+    /// explicitly by users will be kept.
+    ///
+    /// Available options:
+    /// - "aws"
+    /// - "minio"
+    /// - "r2"
+    /// - "ceph"
+    /// - "oracle"
+    /// - "wasabi"
+    pub compat_mode: Option<String>,
+    /// Whether the bucket is a directory bucket of S3 Express One Zone.
+    ///
+    /// Directory buckets are accessed with sessions created by
+    /// `CreateSession`. It's detected by the `--x-s3` suffix of the bucket
+    /// name if not set.
+    pub express_one_zone: Option<bool>,
 }
 
 impl Debug for S3Config {
@@ -182,7 +203,8 @@ impl Debug for S3Config {
         d.field("root", &self.root)
             .field("bucket", &self.bucket)
             .field("endpoint", &self.endpoint)
-            .field("region", &self.region);
+            .field("region", &self.region)
+            .field("compat_mode", &self.compat_mode);
 
         d.finish_non_exhaustive()
     }
@@ -27,6 +27,8 @@ use std::time::Duration;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use constants::X_AMZ_META_PREFIX;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
@@ -41,15 +43,18 @@ use http::Request;
 use http::Response;
 use reqsign::AwsCredential;
 use reqsign::AwsCredentialLoad;
-use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::express::S3ExpressSession;
+use super::express::X_AMZ_S3SESSION_TOKEN;
+use super::signer::S3Signer;
 use crate::raw::*;
 use crate::*;
 
 pub mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_RANGE: &str = "x-amz-copy-source-range";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -70,6 +75,10 @@ pub mod constants {
         "x-amz-copy-source-server-side-encryption-customer-key-md5";
 
     pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
+    pub const X_AMZ_EXPIRATION: &str = "x-amz-expiration";
+    pub const X_AMZ_OBJECT_LOCK_MODE: &str = "x-amz-object-lock-mode";
+    pub const X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE: &str = "x-amz-object-lock-retain-until-date";
+    pub const X_AMZ_OBJECT_LOCK_LEGAL_HOLD: &str = "x-amz-object-lock-legal-hold";
 
     pub const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
     pub const RESPONSE_CONTENT_TYPE: &str = "response-content-type";
@@ -92,12 +101,18 @@ pub struct S3Core {
     pub disable_stat_with_override: bool,
     pub enable_versioning: bool,
 
-    pub signer: AwsV4Signer,
+    pub region: String,
+    pub clock: Clock,
+    /// The cached session of S3 Express One Zone directory buckets, `None`
+    /// if the bucket is not a directory bucket.
+    pub express_session: Option<tokio::sync::Mutex<Option<S3ExpressSession>>>,
     pub loader: Box<dyn AwsCredentialLoad>,
     pub credential_loaded: AtomicBool,
     pub client: HttpClient,
     pub batch_max_operations: usize,
     pub checksum_algorithm: Option<ChecksumAlgorithm>,
+    pub list_objects_v1: bool,
+    pub identity_encoding: bool,
 }
 
 impl Debug for S3Core {
@@ -147,6 +162,18 @@ impl S3Core {
         ))
     }
 
+    /// Build the signer with the time of our clock.
+    ///
+    /// Directory buckets of S3 Express One Zone are signed as `s3express`.
+    pub(super) fn signer(&self) -> S3Signer {
+        let service = if self.express_session.is_some() {
+            "s3express"
+        } else {
+            "s3"
+        };
+        S3Signer::new(service, &self.region, self.clock.clone())
+    }
+
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
@@ -154,7 +181,17 @@ impl S3Core {
             return Ok(());
         };
 
-        self.signer
+        // Requests to directory buckets are signed by the session credential.
+        let cred = if self.express_session.is_some() {
+            let (token, cred) = self.express_session(&cred).await?;
+            req.headers_mut()
+                .insert(X_AMZ_S3SESSION_TOKEN, build_header_value(&token)?);
+            cred
+        } else {
+            cred
+        };
+
+        self.signer()
             .sign(req, &cred)
             .map_err(new_request_sign_error)?;
 
@@ -176,7 +213,7 @@ impl S3Core {
             return Ok(());
         };
 
-        self.signer
+        self.signer()
             .sign_query(req, duration, &cred)
             .map_err(new_request_sign_error)?;
 
@@ -193,18 +230,51 @@ impl S3Core {
 
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
-        self.client.send(req).await
+        let resp = self.client.send(req).await?;
+        self.clock.observe_date(resp.headers());
+        Ok(resp)
     }
 
     /// # Note
     ///
     /// header like X_AMZ_SERVER_SIDE_ENCRYPTION doesn't need to set while
     /// get or stat.
+    ///
+    /// The per-operation `customer_key` replaces the encryption configured in
+    /// builder if it's set.
     pub fn insert_sse_headers(
         &self,
         mut req: http::request::Builder,
         is_write: bool,
+        customer_key: Option<&CustomerEncryptionKey>,
     ) -> http::request::Builder {
+        if let Some(key) = customer_key {
+            for (name, v) in [
+                (
+                    constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                    key.algorithm(),
+                ),
+                (
+                    constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+                    key.key(),
+                ),
+                (
+                    constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                    key.key_md5(),
+                ),
+            ] {
+                req = match HeaderValue::from_str(v) {
+                    Ok(mut v) => {
+                        v.set_sensitive(true);
+                        req.header(HeaderName::from_static(name), v)
+                    }
+                    // Let the builder report the invalid value.
+                    Err(_) => req.header(HeaderName::from_static(name), v),
+                };
+            }
+            return req;
+        }
+
         if is_write {
             if let Some(v) = &self.server_side_encryption {
                 let mut v = v.clone();
@@ -256,6 +326,73 @@ impl S3Core {
 
         req
     }
+
+    /// Insert the SSE-C headers of the source object for copy requests.
+    pub fn insert_copy_source_sse_headers(
+        &self,
+        mut req: http::request::Builder,
+    ) -> http::request::Builder {
+        if let Some(v) = &self.server_side_encryption_customer_algorithm {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                ),
+                v,
+            )
+        }
+
+        if let Some(v) = &self.server_side_encryption_customer_key {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+                ),
+                v,
+            )
+        }
+
+        if let Some(v) = &self.server_side_encryption_customer_key_md5 {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                ),
+                v,
+            )
+        }
+
+        req
+    }
+
+    /// Insert the object lock retention and legal hold headers.
+    pub fn insert_object_lock_headers(
+        mut req: http::request::Builder,
+        args: &OpWrite,
+    ) -> http::request::Builder {
+        if let Some((mode, retain_until)) = args.object_lock() {
+            req = req
+                .header(constants::X_AMZ_OBJECT_LOCK_MODE, mode.as_str())
+                .header(
+                    constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE,
+                    retain_until.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                );
+        }
+        if let Some(legal_hold) = args.legal_hold() {
+            req = req.header(
+                constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD,
+                if legal_hold { "ON" } else { "OFF" },
+            );
+        }
+        req
+    }
+
     pub fn calculate_checksum(&self, body: &Buffer) -> Option<String> {
         match self.checksum_algorithm {
             None => None,
@@ -331,7 +468,7 @@ impl S3Core {
 
         let mut req = Request::head(&url);
 
-        req = self.insert_sse_headers(req, false);
+        req = self.insert_sse_headers(req, false, args.customer_key());
 
         if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
@@ -404,9 +541,13 @@ impl S3Core {
         if let Some(if_match) = args.if_match() {
             req = req.header(IF_MATCH, if_match);
         }
+
+        if self.identity_encoding {
+            req = req.header(http::header::ACCEPT_ENCODING, "identity");
+        }
         // Set SSE headers.
         // TODO: how will this work with presign?
-        req = self.insert_sse_headers(req, false);
+        req = self.insert_sse_headers(req, false, args.customer_key());
 
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
@@ -459,6 +600,10 @@ impl S3Core {
             req = req.header(IF_NONE_MATCH, "*");
         }
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
         // Set storage class header
         if let Some(v) = &self.default_storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
@@ -472,12 +617,18 @@ impl S3Core {
         }
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_sse_headers(req, true, args.customer_key());
+
+        // Set object lock headers.
+        req = Self::insert_object_lock_headers(req, args);
 
         // Calculate Checksum.
         if let Some(checksum) = self.calculate_checksum(&body) {
             // Set Checksum header.
             req = self.insert_checksum_header(req, &checksum);
+        } else if args.object_lock().is_some() || args.legal_hold().is_some() {
+            // Object lock requires content-md5 if no other checksum is sent.
+            req = req.header("CONTENT-MD5", format_content_md5(&body.to_bytes()));
         }
 
         // Set body
@@ -532,46 +683,53 @@ impl S3Core {
         let mut req = Request::put(&target);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_sse_headers(req, true, None);
 
-        if let Some(v) = &self.server_side_encryption_customer_algorithm {
-            let mut v = v.clone();
-            v.set_sensitive(true);
+        // Set SSE headers of the source object.
+        req = self.insert_copy_source_sse_headers(req);
 
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
-                ),
-                v,
-            )
-        }
+        let mut req = req
+            .header(constants::X_AMZ_COPY_SOURCE, &source)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
 
-        if let Some(v) = &self.server_side_encryption_customer_key {
-            let mut v = v.clone();
-            v.set_sensitive(true);
+        self.sign(&mut req).await?;
 
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
-                ),
-                v,
-            )
-        }
+        self.send(req).await
+    }
 
-        if let Some(v) = &self.server_side_encryption_customer_key_md5 {
-            let mut v = v.clone();
-            v.set_sensitive(true);
+    /// Copy a range of `from` into a part of the multipart upload of `to`.
+    ///
+    /// Reference: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_UploadPartCopy.html>
+    pub async fn s3_upload_part_copy(
+        &self,
+        from: &str,
+        to: &str,
+        upload_id: &str,
+        part_number: usize,
+        range: BytesRange,
+    ) -> Result<Response<Buffer>> {
+        let from = build_abs_path(&self.root, from);
+        let to = build_abs_path(&self.root, to);
 
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
-                ),
-                v,
-            )
-        }
+        let source = format!("{}/{}", self.bucket, percent_encode_path(&from));
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&to),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true, None);
+        req = self.insert_copy_source_sse_headers(req);
 
         let mut req = req
             .header(constants::X_AMZ_COPY_SOURCE, &source)
+            .header(constants::X_AMZ_COPY_SOURCE_RANGE, range.to_header())
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
 
@@ -590,33 +748,49 @@ impl S3Core {
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
-        let mut url = format!("{}?list-type=2", self.endpoint);
+        let mut query_args = Vec::new();
+        if !self.list_objects_v1 {
+            query_args.push("list-type=2".to_string());
+        }
         if !p.is_empty() {
-            write!(url, "&prefix={}", percent_encode_path(&p))
-                .expect("write into string must succeed");
+            query_args.push(format!("prefix={}", percent_encode_path(&p)));
         }
         if !delimiter.is_empty() {
-            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+            query_args.push(format!("delimiter={delimiter}"));
         }
         if let Some(limit) = limit {
-            write!(url, "&max-keys={limit}").expect("write into string must succeed");
+            query_args.push(format!("max-keys={limit}"));
         }
-        if let Some(start_after) = start_after {
-            let start_after = build_abs_path(&self.root, &start_after);
-            write!(url, "&start-after={}", percent_encode_path(&start_after))
-                .expect("write into string must succeed");
+        if self.list_objects_v1 {
+            // ListObjects (V1) uses `marker` for both start after and pagination.
+            let marker = if !continuation_token.is_empty() {
+                Some(continuation_token.to_string())
+            } else {
+                start_after.map(|v| build_abs_path(&self.root, &v))
+            };
+            if let Some(marker) = marker {
+                query_args.push(format!("marker={}", percent_encode_path(&marker)));
+            }
+        } else {
+            if let Some(start_after) = start_after {
+                let start_after = build_abs_path(&self.root, &start_after);
+                query_args.push(format!("start-after={}", percent_encode_path(&start_after)));
+            }
+            if !continuation_token.is_empty() {
+                // AWS S3 could return continuation-token that contains `=`
+                // which could lead `reqsign` parse query wrongly.
+                // URL encode continuation-token before starting signing so that
+                // our signer will not be confused.
+                query_args.push(format!(
+                    "continuation-token={}",
+                    percent_encode_path(continuation_token)
+                ));
+            }
         }
-        if !continuation_token.is_empty() {
-            // AWS S3 could return continuation-token that contains `=`
-            // which could lead `reqsign` parse query wrongly.
-            // URL encode continuation-token before starting signing so that
-            // our signer will not be confused.
-            write!(
-                url,
-                "&continuation-token={}",
-                percent_encode_path(continuation_token)
-            )
-            .expect("write into string must succeed");
+
+        let mut url = self.endpoint.clone();
+        if !query_args.is_empty() {
+            url.push_str(&format!("?{}", query_args.join("&")));
         }
 
         let mut req = Request::get(&url)
@@ -657,7 +831,10 @@ impl S3Core {
         }
 
         // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        let req = self.insert_sse_headers(req, true, args.customer_key());
+
+        // Set object lock headers.
+        let req = Self::insert_object_lock_headers(req, args);
 
         // Set SSE headers.
         let req = self.insert_checksum_type_header(req);
@@ -677,6 +854,7 @@ impl S3Core {
         size: u64,
         body: Buffer,
         checksum: Option<String>,
+        customer_key: Option<&CustomerEncryptionKey>,
     ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -693,7 +871,7 @@ impl S3Core {
         req = req.header(CONTENT_LENGTH, size);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_sse_headers(req, true, customer_key);
 
         if let Some(checksum) = checksum {
             // Set Checksum header.
@@ -711,6 +889,7 @@ impl S3Core {
         path: &str,
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
+        args: &OpWrite,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -721,10 +900,19 @@ impl S3Core {
             percent_encode_path(upload_id)
         );
 
-        let req = Request::post(&url);
+        let mut req = Request::post(&url);
 
         // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        req = self.insert_sse_headers(req, true, args.customer_key());
+
+        // The conditions are checked while completing the upload, so that
+        // multipart writes are as safe as one shot writes.
+        if args.if_not_exists() {
+            req = req.header(IF_NONE_MATCH, "*");
+        }
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
 
         let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest { part: parts })
             .map_err(new_xml_deserialize_error)?;
@@ -764,6 +952,40 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// List on-going multipart uploads under the given path.
+    pub async fn s3_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!("{}?uploads", self.endpoint);
+        if !p.is_empty() {
+            write!(url, "&prefix={}", percent_encode_path(&p))
+                .expect("write into string must succeed");
+        }
+        if !key_marker.is_empty() {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if !upload_id_marker.is_empty() {
+            write!(
+                url,
+                "&upload-id-marker={}",
+                percent_encode_path(upload_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn s3_delete_objects(&self, paths: Vec<String>) -> Result<Response<Buffer>> {
         let url = format!("{}/?delete", self.endpoint);
 
@@ -795,6 +1017,39 @@ impl S3Core {
         self.send(req).await
     }
 
+    pub async fn s3_select_object_content(
+        &self,
+        path: &str,
+        args: &SelectObjectContentRequest,
+        customer_key: Option<&CustomerEncryptionKey>,
+    ) -> Result<Response<HttpBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?select&select-type=2",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::post(&url);
+
+        // Set SSE headers for objects encrypted with customer keys.
+        let req = self.insert_sse_headers(req, false, customer_key);
+
+        let content = quick_xml::se::to_string(args).map_err(new_xml_deserialize_error)?;
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        let req = req.header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.client.fetch(req).await
+    }
+
     pub async fn s3_list_object_versions(
         &self,
         prefix: &str,
@@ -847,6 +1102,42 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of CompleteMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
+/// Result of UploadPartCopy.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CopyPartResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+    #[serde(rename = "ChecksumCRC32C")]
+    pub checksum_crc32c: Option<String>,
+}
+
+/// Result of ListMultipartUploads
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -906,6 +1197,58 @@ pub struct DeleteObjectsRequestObject {
     pub key: String,
 }
 
+/// Request of SelectObjectContent.
+#[derive(Default, Debug, Serialize)]
+#[serde(rename = "SelectObjectContentRequest", rename_all = "PascalCase")]
+pub struct SelectObjectContentRequest {
+    pub expression: String,
+    pub expression_type: String,
+    pub input_serialization: SelectInputSerialization,
+    pub output_serialization: SelectOutputSerialization,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectInputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<SelectCsvInput>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<SelectJsonInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parquet: Option<SelectParquetInput>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectCsvInput {
+    pub file_header_info: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectJsonInput {
+    #[serde(rename = "Type")]
+    pub type_: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectParquetInput {}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectOutputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<SelectCsvOutput>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<SelectJsonOutput>,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectCsvOutput {}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectJsonOutput {}
+
 /// Result of DeleteObjects.
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename = "DeleteResult", rename_all = "PascalCase")]
@@ -941,7 +1284,11 @@ pub struct DeleteObjectsResultError {
 #[serde(default, rename_all = "PascalCase")]
 pub struct ListObjectsOutput {
     pub is_truncated: Option<bool>,
+    /// Only returned by ListObjectsV2.
+    pub key_count: Option<u64>,
     pub next_continuation_token: Option<String>,
+    /// Only returned by ListObjects (V1) while `delimiter` is set.
+    pub next_marker: Option<String>,
     pub common_prefixes: Vec<OutputCommonPrefix>,
     pub contents: Vec<ListObjectsOutputContent>,
 }
@@ -971,6 +1318,7 @@ pub struct ListObjectVersionsOutput {
     pub next_version_id_marker: Option<String>,
     pub common_prefixes: Vec<OutputCommonPrefix>,
     pub version: Vec<ListObjectVersionsOutputVersion>,
+    pub delete_marker: Vec<ListObjectVersionsOutputDeleteMarker>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
@@ -985,6 +1333,36 @@ pub struct ListObjectVersionsOutputVersion {
     pub etag: Option<String>,
 }
 
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListObjectVersionsOutputDeleteMarker {
+    pub key: String,
+    pub version_id: String,
+    pub is_latest: bool,
+    pub last_modified: String,
+}
+
+/// Parse the expiry date out of the `x-amz-expiration` header.
+///
+/// The header looks like `expiry-date="Fri, 23 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule"`,
+/// returns `None` if there is no `expiry-date` in it.
+///
+/// ref: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_HeadObject.html#API_HeadObject_ResponseSyntax>
+pub fn parse_expiration(v: &str) -> Result<Option<DateTime<Utc>>> {
+    let Some((_, rest)) = v.split_once("expiry-date=\"") else {
+        return Ok(None);
+    };
+    let Some((date, _)) = rest.split_once('"') else {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "x-amz-expiration header is malformed",
+        )
+        .with_context("value", v));
+    };
+
+    parse_datetime_from_rfc2822(date).map(Some)
+}
+
 pub enum ChecksumAlgorithm {
     Crc32c,
 }
@@ -1014,6 +1392,23 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_expiration() {
+        let v = parse_expiration(
+            r#"expiry-date="Fri, 23 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule""#,
+        )
+        .expect("must succeed");
+        assert_eq!(
+            v,
+            Some(parse_datetime_from_rfc3339("2012-12-23T00:00:00Z").unwrap())
+        );
+
+        let v = parse_expiration(r#"rule-id="picture-deletion-rule""#).expect("must succeed");
+        assert_eq!(v, None);
+
+        assert!(parse_expiration(r#"expiry-date="Fri, 23 Dec 2012"#).is_err());
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CreateMultipartUpload.html#API_CreateMultipartUpload_Examples
     #[test]
     fn test_deserialize_initiate_multipart_upload_result() {
@@ -1182,6 +1577,7 @@ mod tests {
         let out: ListObjectsOutput = quick_xml::de::from_reader(bs.reader()).expect("must success");
 
         assert!(!out.is_truncated.unwrap());
+        assert_eq!(out.key_count, Some(3));
         assert!(out.next_continuation_token.is_none());
         assert_eq!(
             out.common_prefixes
@@ -1215,6 +1611,71 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_deserialize_list_multipart_uploads_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>example-bucket</Bucket>
+  <KeyMarker></KeyMarker>
+  <UploadIdMarker></UploadIdMarker>
+  <NextKeyMarker>my-movie.m2ts</NextKeyMarker>
+  <NextUploadIdMarker>YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ</NextUploadIdMarker>
+  <MaxUploads>2</MaxUploads>
+  <IsTruncated>true</IsTruncated>
+  <Upload>
+    <Key>my-divisor</Key>
+    <UploadId>XMgbGlrZSBlbHZpbmcncyBub3QgaGF2aW5nIG11Y2ggbHVjaw</UploadId>
+    <StorageClass>STANDARD</StorageClass>
+    <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+  </Upload>
+  <Upload>
+    <Key>my-movie.m2ts</Key>
+    <UploadId>YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ</UploadId>
+    <StorageClass>STANDARD</StorageClass>
+    <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+  </Upload>
+</ListMultipartUploadsResult>"#,
+        );
+
+        let out: ListMultipartUploadsResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated.unwrap());
+        assert_eq!(out.next_key_marker.as_deref(), Some("my-movie.m2ts"));
+        assert_eq!(out.upload.len(), 2);
+        assert_eq!(out.upload[0].key, "my-divisor");
+        assert_eq!(
+            out.upload[0].initiated.as_deref(),
+            Some("2010-11-10T20:48:33.000Z")
+        );
+    }
+
+    #[test]
+    fn test_parse_list_output_v1() {
+        let bs = bytes::Bytes::from(
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>example-bucket</Name>
+  <Prefix>photos/</Prefix>
+  <Marker></Marker>
+  <NextMarker>photos/2006/</NextMarker>
+  <MaxKeys>1</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>true</IsTruncated>
+  <CommonPrefixes>
+    <Prefix>photos/2006/</Prefix>
+  </CommonPrefixes>
+</ListBucketResult>"#,
+        );
+
+        let out: ListObjectsOutput = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated.unwrap());
+        assert!(out.next_continuation_token.is_none());
+        assert_eq!(out.next_marker.as_deref(), Some("photos/2006/"));
+        assert!(out.contents.is_empty());
+    }
+
     #[test]
     fn test_parse_list_object_versions() {
         let bs = bytes::Bytes::from(
@@ -1240,6 +1701,15 @@ mod tests {
                     </Owner>
                     <StorageClass>STANDARD</StorageClass>
                 </Version>
+                <DeleteMarker>
+                    <Key>key3</Key>
+                    <VersionId>null</VersionId>
+                    <IsLatest>false</IsLatest>
+                    <LastModified>2009-12-09T00:18:15.000Z</LastModified>
+                    <Owner>
+                        <ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID>
+                    </Owner>
+                </DeleteMarker>
                 <Version>
                     <Key>key3</Key>
                     <VersionId>d-d309mfjFri40QYukDozqBt3UmoQ0DBsVqmcMV15OI.</VersionId>
@@ -1303,5 +1773,14 @@ mod tests {
                 }
             ]
         );
+        assert_eq!(
+            output.delete_marker,
+            vec![ListObjectVersionsOutputDeleteMarker {
+                key: "key3".to_owned(),
+                version_id: "null".to_owned(),
+                is_latest: false,
+                last_modified: "2009-12-09T00:18:15.000Z".to_owned(),
+            }]
+        );
     }
 }
@@ -83,6 +83,22 @@ pub(crate) fn from_s3_error(s3_error: S3Error, parts: Parts) -> Error {
     err
 }
 
+/// Check if the copy failed because the source is larger than the max size
+/// that CopyObject supports, which is 5 GiB.
+///
+/// S3 returns `InvalidRequest` with a message like:
+///
+/// > The specified copy source is larger than the maximum allowable size for a copy source: 5368709120
+pub(super) fn is_copy_source_too_large(resp: &Response<Buffer>) -> bool {
+    if resp.status().as_u16() != 400 {
+        return false;
+    }
+
+    de::from_reader::<_, S3Error>(resp.body().clone().reader()).is_ok_and(|s3_err| {
+        s3_err.code == "InvalidRequest" && s3_err.message.contains("copy source is larger than")
+    })
+}
+
 /// Returns the `Error kind` of this code and whether the error is retryable.
 /// All possible error code: <https://docs.aws.amazon.com/AmazonS3/latest/API/ErrorResponses.html#ErrorCodeList>
 pub fn parse_s3_error_code(code: &str) -> Option<(ErrorKind, bool)> {
@@ -161,4 +177,30 @@ mod tests {
         let out: S3Error = de::from_reader(bs.reader()).expect("must success");
         assert_eq!(out, S3Error::default());
     }
+
+    #[test]
+    fn test_is_copy_source_too_large() {
+        let bs = bytes::Bytes::from(
+            r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>InvalidRequest</Code>
+  <Message>The specified copy source is larger than the maximum allowable size for a copy source: 5368709120</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>
+"#,
+        );
+
+        let resp = Response::builder()
+            .status(400)
+            .body(Buffer::from(bs.clone()))
+            .unwrap();
+        assert!(is_copy_source_too_large(&resp));
+
+        let resp = Response::builder()
+            .status(403)
+            .body(Buffer::from(bs))
+            .unwrap();
+        assert!(!is_copy_source_too_large(&resp));
+    }
 }
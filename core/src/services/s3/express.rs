@@ -0,0 +1,188 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Buf;
+use chrono::DateTime;
+use chrono::Utc;
+use http::header::HOST;
+use http::Request;
+use http::StatusCode;
+use quick_xml::de;
+use reqsign::AwsCredential;
+use serde::Deserialize;
+
+use super::core::S3Core;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The header carries the session token of directory buckets.
+pub const X_AMZ_S3SESSION_TOKEN: &str = "x-amz-s3session-token";
+
+/// The suffix of directory bucket names.
+const DIRECTORY_BUCKET_SUFFIX: &str = "--x-s3";
+
+/// Refresh the session if it will expire within this seconds.
+///
+/// Sessions are valid for 5 minutes.
+const SESSION_REFRESH_AHEAD_SECS: i64 = 60;
+
+/// Check whether the bucket is a directory bucket of S3 Express One Zone.
+pub fn is_directory_bucket(bucket: &str) -> bool {
+    bucket.ends_with(DIRECTORY_BUCKET_SUFFIX)
+}
+
+/// Get the zone id from directory bucket name like `base--usw2-az1--x-s3`.
+pub fn parse_directory_bucket_zone(bucket: &str) -> Option<&str> {
+    let name = bucket.strip_suffix(DIRECTORY_BUCKET_SUFFIX)?;
+    let (_, zone) = name.rsplit_once("--")?;
+    (!zone.is_empty()).then_some(zone)
+}
+
+/// The credential returned by `CreateSession`.
+#[derive(Clone)]
+pub struct S3ExpressSession {
+    token: String,
+    credential: AwsCredential,
+    expires_at: DateTime<Utc>,
+}
+
+/// Output of `CreateSession`.
+///
+/// ref: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_CreateSession.html>
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CreateSessionOutput {
+    credentials: CreateSessionCredentials,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CreateSessionCredentials {
+    session_token: String,
+    secret_access_key: String,
+    access_key_id: String,
+    expiration: String,
+}
+
+impl S3Core {
+    /// Get the session of the directory bucket, create a new one if the
+    /// cached one is going to expire.
+    ///
+    /// Returns the session token and the credential to sign requests.
+    pub(super) async fn express_session(
+        &self,
+        cred: &AwsCredential,
+    ) -> Result<(String, AwsCredential)> {
+        let Some(cache) = &self.express_session else {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "express session is not enabled",
+            ));
+        };
+
+        let mut cache = cache.lock().await;
+        if let Some(session) = cache.as_ref() {
+            let refresh_at =
+                session.expires_at - chrono::Duration::seconds(SESSION_REFRESH_AHEAD_SECS);
+            if refresh_at > self.clock.now() {
+                return Ok((session.token.clone(), session.credential.clone()));
+            }
+        }
+
+        let session = self.s3_create_session(cred).await?;
+        let res = (session.token.clone(), session.credential.clone());
+        *cache = Some(session);
+        Ok(res)
+    }
+
+    async fn s3_create_session(&self, cred: &AwsCredential) -> Result<S3ExpressSession> {
+        let url = format!("{}/?session", self.endpoint);
+
+        let mut req = Request::get(&url)
+            .header("x-amz-create-session-mode", "ReadWrite")
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        // CreateSession is signed by the credential of users.
+        self.signer()
+            .sign(&mut req, cred)
+            .map_err(new_request_sign_error)?;
+        req.headers_mut().remove(HOST);
+
+        let resp = self.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).with_operation("S3Core::create_session"));
+        }
+
+        let output: CreateSessionOutput =
+            de::from_reader(resp.into_body().reader()).map_err(new_xml_deserialize_error)?;
+        let creds = output.credentials;
+        let expires_at = parse_datetime_from_rfc3339(&creds.expiration)?;
+        Ok(S3ExpressSession {
+            token: creds.session_token,
+            credential: AwsCredential {
+                access_key_id: creds.access_key_id,
+                secret_access_key: creds.secret_access_key,
+                session_token: None,
+                expires_in: Some(expires_at),
+            },
+            expires_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directory_bucket_zone() {
+        assert!(is_directory_bucket("data--usw2-az1--x-s3"));
+        assert!(!is_directory_bucket("data"));
+
+        assert_eq!(
+            parse_directory_bucket_zone("data--usw2-az1--x-s3"),
+            Some("usw2-az1")
+        );
+        assert_eq!(
+            parse_directory_bucket_zone("my--data--use1-az4--x-s3"),
+            Some("use1-az4")
+        );
+        assert_eq!(parse_directory_bucket_zone("data--x-s3"), None);
+        assert_eq!(parse_directory_bucket_zone("data"), None);
+    }
+
+    #[test]
+    fn test_deserialize_create_session_output() {
+        let bs = r#"<?xml version="1.0" encoding="UTF-8"?>
+<CreateSessionResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Credentials>
+    <SessionToken>token</SessionToken>
+    <SecretAccessKey>secret</SecretAccessKey>
+    <AccessKeyId>akid</AccessKeyId>
+    <Expiration>2024-01-01T00:05:00Z</Expiration>
+  </Credentials>
+</CreateSessionResult>"#;
+
+        let out: CreateSessionOutput = de::from_str(bs).expect("must succeed");
+        assert_eq!(out.credentials.session_token, "token");
+        assert_eq!(out.credentials.secret_access_key, "secret");
+        assert_eq!(out.credentials.access_key_id, "akid");
+        assert_eq!(out.credentials.expiration, "2024-01-01T00:05:00Z");
+    }
+}
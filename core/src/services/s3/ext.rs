@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use http::Response;
+use http::StatusCode;
+
+use super::backend::S3Builder;
+use super::core::*;
+use super::error::parse_error;
+use super::select::S3SelectReader;
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// The format of the object to select from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum S3SelectInput {
+    /// CSV object, the first line is used as column names if `has_header` is true.
+    Csv {
+        /// Whether the first line of the object is the header.
+        has_header: bool,
+    },
+    /// JSON object, set `lines` to true for newline-delimited JSON.
+    Json {
+        /// Whether the object is newline-delimited JSON.
+        lines: bool,
+    },
+    /// Parquet object.
+    Parquet,
+}
+
+/// The format of the selected records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum S3SelectOutput {
+    /// Records in CSV.
+    Csv,
+    /// Records in newline-delimited JSON.
+    Json,
+}
+
+/// S3Extension provides s3-specific operations that are not covered by
+/// [`Operator`].
+///
+/// Get it via `op.as_service::<services::S3>()`.
+#[derive(Clone)]
+pub struct S3Extension {
+    core: Arc<S3Core>,
+}
+
+impl Debug for S3Extension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Extension")
+            .field("core", &self.core)
+            .finish()
+    }
+}
+
+impl S3Extension {
+    pub(super) fn new(core: Arc<S3Core>) -> Self {
+        Self { core }
+    }
+
+    /// Filter the content of an object with SQL `expression` via
+    /// [SelectObjectContent](https://docs.aws.amazon.com/AmazonS3/latest/API/API_SelectObjectContent.html),
+    /// returns the selected records.
+    ///
+    /// Use [`S3Extension::select_object_content_stream`] to read large results
+    /// without buffering them in memory.
+    pub async fn select_object_content(
+        &self,
+        path: &str,
+        expression: &str,
+        input: S3SelectInput,
+        output: S3SelectOutput,
+    ) -> Result<Buffer> {
+        let mut records = self
+            .select_object_content_stream(path, expression, input, output)
+            .await?;
+
+        let mut bufs = Vec::new();
+        while let Some(buf) = records.next().await? {
+            bufs.push(buf);
+        }
+        Ok(bufs.into_iter().flatten().collect())
+    }
+
+    /// Same as [`S3Extension::select_object_content`], but returns the selected
+    /// records chunk by chunk as they arrive.
+    pub async fn select_object_content_stream(
+        &self,
+        path: &str,
+        expression: &str,
+        input: S3SelectInput,
+        output: S3SelectOutput,
+    ) -> Result<S3SelectRecords> {
+        let mut args = SelectObjectContentRequest {
+            expression: expression.to_string(),
+            expression_type: "SQL".to_string(),
+            ..Default::default()
+        };
+        match input {
+            S3SelectInput::Csv { has_header } => {
+                args.input_serialization.csv = Some(SelectCsvInput {
+                    file_header_info: if has_header { "USE" } else { "NONE" }.to_string(),
+                })
+            }
+            S3SelectInput::Json { lines } => {
+                args.input_serialization.json = Some(SelectJsonInput {
+                    type_: if lines { "LINES" } else { "DOCUMENT" }.to_string(),
+                })
+            }
+            S3SelectInput::Parquet => {
+                args.input_serialization.parquet = Some(SelectParquetInput {})
+            }
+        }
+        match output {
+            S3SelectOutput::Csv => args.output_serialization.csv = Some(SelectCsvOutput {}),
+            S3SelectOutput::Json => args.output_serialization.json = Some(SelectJsonOutput {}),
+        }
+
+        let resp = self
+            .core
+            .s3_select_object_content(path, &args, None)
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(S3SelectRecords {
+                inner: S3SelectReader::new(resp.into_body()),
+            }),
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                Err(parse_error(Response::from_parts(part, buf))
+                    .with_operation("S3Extension::select_object_content"))
+            }
+        }
+    }
+}
+
+/// The records returned by [`S3Extension::select_object_content_stream`].
+pub struct S3SelectRecords {
+    inner: S3SelectReader<HttpBody>,
+}
+
+impl Debug for S3SelectRecords {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3SelectRecords").finish_non_exhaustive()
+    }
+}
+
+impl S3SelectRecords {
+    /// Fetch the next chunk of records, returns `None` once all records are read.
+    pub async fn next(&mut self) -> Result<Option<Buffer>> {
+        let buf = self
+            .inner
+            .read()
+            .await
+            .map_err(|err| err.with_operation("S3SelectRecords::next"))?;
+        Ok((!buf.is_empty()).then_some(buf))
+    }
+}
+
+impl ServiceExtension for S3Builder {
+    type Extension = S3Extension;
+}
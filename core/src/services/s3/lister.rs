@@ -106,7 +106,29 @@ impl oio::PageList for S3Lister {
         } else {
             output.common_prefixes.is_empty() && output.contents.is_empty()
         };
-        ctx.token = output.next_continuation_token.clone().unwrap_or_default();
+        ctx.token = if self.core.list_objects_v1 {
+            // ListObjects (V1) only returns `NextMarker` while `delimiter` is set,
+            // use the last key or prefix in this page instead.
+            output
+                .next_marker
+                .clone()
+                .or_else(|| {
+                    let last_key = output.contents.last().map(|v| v.key.as_str());
+                    let last_prefix = output.common_prefixes.last().map(|v| v.prefix.as_str());
+                    last_key.max(last_prefix).map(String::from)
+                })
+                .unwrap_or_default()
+        } else {
+            output.next_continuation_token.clone().unwrap_or_default()
+        };
+
+        let bytes = output.contents.iter().map(|v| v.size).sum::<u64>();
+        ctx.stats.update(|stats| {
+            if let Some(n) = output.key_count {
+                *stats.entries.get_or_insert(0) += n;
+            }
+            *stats.bytes.get_or_insert(0) += bytes;
+        });
 
         for prefix in output.common_prefixes {
             let de = oio::Entry::new(
@@ -133,7 +155,15 @@ impl oio::PageList for S3Lister {
 
             // object.last_modified provides more precise time that contains
             // nanosecond, let's trim them.
-            meta.set_last_modified(parse_datetime_from_rfc3339(object.last_modified.as_str())?);
+            match parse_datetime_from_rfc3339(object.last_modified.as_str()) {
+                Ok(v) => {
+                    meta.set_last_modified(v);
+                }
+                Err(err) => {
+                    ctx.skip_entry(err.with_context("path", &path))?;
+                    continue;
+                }
+            }
 
             let de = oio::Entry::with(path, meta);
             ctx.entries.push_back(de);
@@ -231,6 +261,7 @@ impl oio::PageList for S3ObjectVersionsLister {
             ctx.entries.push_back(de);
         }
 
+        let mut entries = Vec::with_capacity(output.version.len() + output.delete_marker.len());
         for version_object in output.version {
             let mut path = build_rel_path(&self.core.root, &version_object.key);
             if path.is_empty() {
@@ -240,18 +271,53 @@ impl oio::PageList for S3ObjectVersionsLister {
             let mut meta = Metadata::new(EntryMode::from_path(&path));
             meta.set_version(&version_object.version_id);
             meta.set_content_length(version_object.size);
-            meta.set_last_modified(parse_datetime_from_rfc3339(
-                version_object.last_modified.as_str(),
-            )?);
+            match parse_datetime_from_rfc3339(version_object.last_modified.as_str()) {
+                Ok(v) => {
+                    meta.set_last_modified(v);
+                }
+                Err(err) => {
+                    ctx.skip_entry(err.with_context("path", &path))?;
+                    continue;
+                }
+            }
             if let Some(etag) = version_object.etag {
                 meta.set_etag(&etag);
                 meta.set_content_md5(etag.trim_matches('"'));
             }
 
-            let entry = oio::Entry::new(&path, meta);
-            ctx.entries.push_back(entry);
+            entries.push(oio::Entry::new(&path, meta));
         }
 
+        for delete_marker in output.delete_marker {
+            let path = build_rel_path(&self.core.root, &delete_marker.key);
+
+            let mut meta = Metadata::new(EntryMode::from_path(&path));
+            meta.set_version(&delete_marker.version_id);
+            meta.set_is_delete_marker(true);
+            match parse_datetime_from_rfc3339(delete_marker.last_modified.as_str()) {
+                Ok(v) => {
+                    meta.set_last_modified(v);
+                }
+                Err(err) => {
+                    ctx.skip_entry(err.with_context("path", &path))?;
+                    continue;
+                }
+            }
+
+            entries.push(oio::Entry::new(&path, meta));
+        }
+
+        // Versions and delete markers are returned in separate lists, merge
+        // them back into the order of keys and then the newest first.
+        entries.sort_by(|a, b| {
+            a.path().cmp(b.path()).then_with(|| {
+                b.metadata()
+                    .last_modified()
+                    .cmp(&a.metadata().last_modified())
+            })
+        });
+        ctx.entries.extend(entries);
+
         Ok(())
     }
 }
@@ -15,12 +15,22 @@
 // specific language governing permissions and limitations
 // under the License.
 
+#[cfg(feature = "services-s3")]
+mod compat;
 #[cfg(feature = "services-s3")]
 mod core;
 #[cfg(feature = "services-s3")]
 mod error;
 #[cfg(feature = "services-s3")]
+mod express;
+#[cfg(feature = "services-s3")]
 mod lister;
+#[cfg(feature = "services-s3-sdk-config")]
+mod sdk_config;
+#[cfg(feature = "services-s3")]
+mod select;
+#[cfg(feature = "services-s3")]
+mod signer;
 #[cfg(feature = "services-s3")]
 mod writer;
 
@@ -29,5 +39,16 @@ mod backend;
 #[cfg(feature = "services-s3")]
 pub use backend::S3Builder as S3;
 
+#[cfg(all(feature = "services-s3", feature = "extensions"))]
+mod ext;
+#[cfg(all(feature = "services-s3", feature = "extensions"))]
+pub use ext::S3Extension;
+#[cfg(all(feature = "services-s3", feature = "extensions"))]
+pub use ext::S3SelectInput;
+#[cfg(all(feature = "services-s3", feature = "extensions"))]
+pub use ext::S3SelectOutput;
+#[cfg(all(feature = "services-s3", feature = "extensions"))]
+pub use ext::S3SelectRecords;
+
 mod config;
 pub use config::S3Config;
@@ -0,0 +1,98 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use aws_credential_types::provider::ProvideCredentials;
+use aws_credential_types::provider::SharedCredentialsProvider;
+use aws_types::SdkConfig;
+use chrono::DateTime;
+use chrono::Utc;
+use reqsign::AwsCredential;
+use reqsign::AwsCredentialLoad;
+
+use super::backend::S3Builder;
+
+impl S3Builder {
+    /// Create a builder from the [`SdkConfig`] of AWS SDK, like the one
+    /// loaded by `aws_config::load_from_env()`, so applications already
+    /// using AWS SDK don't need to configure credentials twice.
+    ///
+    /// The region, endpoint url and credentials provider will be used if
+    /// present. Credentials are loaded from the provider of SDK every time
+    /// they are about to expire.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use aws_credential_types::provider::SharedCredentialsProvider;
+    /// use aws_credential_types::Credentials;
+    /// use aws_types::region::Region;
+    /// use aws_types::SdkConfig;
+    /// use opendal::services::S3;
+    /// use opendal::Operator;
+    ///
+    /// # fn test() -> Result<()> {
+    /// let sdk_config = SdkConfig::builder()
+    ///     .region(Region::new("us-east-1"))
+    ///     .credentials_provider(SharedCredentialsProvider::new(Credentials::new(
+    ///         "access_key_id",
+    ///         "secret_access_key",
+    ///         None,
+    ///         None,
+    ///         "example",
+    ///     )))
+    ///     .build();
+    /// let builder = S3::from_sdk_config(&sdk_config).bucket("test");
+    /// let op = Operator::new(builder)?.finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_sdk_config(config: &SdkConfig) -> Self {
+        let mut builder = S3Builder::default();
+        if let Some(region) = config.region() {
+            builder = builder.region(region.as_ref());
+        }
+        if let Some(endpoint) = config.endpoint_url() {
+            builder = builder.endpoint(endpoint);
+        }
+        if let Some(provider) = config.credentials_provider() {
+            builder =
+                builder.customized_credential_load(Box::new(SdkCredentialLoader { provider }));
+        }
+        builder
+    }
+}
+
+/// Load credentials from the credentials provider of AWS SDK.
+struct SdkCredentialLoader {
+    provider: SharedCredentialsProvider,
+}
+
+#[async_trait]
+impl AwsCredentialLoad for SdkCredentialLoader {
+    async fn load_credential(&self, _: reqwest::Client) -> anyhow::Result<Option<AwsCredential>> {
+        let cred = self.provider.provide_credentials().await?;
+
+        Ok(Some(AwsCredential {
+            access_key_id: cred.access_key_id().to_string(),
+            secret_access_key: cred.secret_access_key().to_string(),
+            session_token: cred.session_token().map(|v| v.to_string()),
+            expires_in: cred.expiry().map(DateTime::<Utc>::from),
+        }))
+    }
+}
@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use super::core::*;
+use crate::raw::*;
+use crate::*;
+
+impl SelectObjectContentRequest {
+    /// Build the request for [`OpRead::select`].
+    pub fn new(expression: &str, format: SelectInputFormat) -> Self {
+        let mut req = SelectObjectContentRequest {
+            expression: expression.to_string(),
+            expression_type: "SQL".to_string(),
+            ..Default::default()
+        };
+        match format {
+            SelectInputFormat::Csv => {
+                req.input_serialization.csv = Some(SelectCsvInput {
+                    file_header_info: "USE".to_string(),
+                });
+                req.output_serialization.csv = Some(SelectCsvOutput {});
+            }
+            SelectInputFormat::Json => {
+                req.input_serialization.json = Some(SelectJsonInput {
+                    type_: "LINES".to_string(),
+                });
+                req.output_serialization.json = Some(SelectJsonOutput {});
+            }
+            SelectInputFormat::Parquet => {
+                req.input_serialization.parquet = Some(SelectParquetInput {});
+                req.output_serialization.json = Some(SelectJsonOutput {});
+            }
+        }
+        req
+    }
+}
+
+/// S3SelectReader returns the payloads of `Records` events from the
+/// response of SelectObjectContent, which is encoded in the
+/// [event stream](https://docs.aws.amazon.com/AmazonS3/latest/API/RESTSelectObjectAppendix.html)
+/// format.
+///
+/// The checksums of messages are not verified, they are protected by TLS.
+pub struct S3SelectReader<R> {
+    inner: R,
+    buf: BytesMut,
+    done: bool,
+}
+
+impl<R: oio::Read> S3SelectReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: BytesMut::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for S3SelectReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        while !self.done {
+            let Some(msg) = split_message(&mut self.buf)? else {
+                let bs = self.inner.read().await?;
+                if bs.is_empty() {
+                    // The End event is not received, the response may be truncated.
+                    return Err(new_event_stream_error("end event is not received"));
+                }
+                for b in bs {
+                    self.buf.extend_from_slice(&b);
+                }
+                continue;
+            };
+
+            match parse_message(msg)? {
+                Event::Records(payload) if !payload.is_empty() => return Ok(Buffer::from(payload)),
+                Event::End => self.done = true,
+                _ => {}
+            }
+        }
+        Ok(Buffer::new())
+    }
+}
+
+enum Event {
+    Records(Bytes),
+    End,
+    Other,
+}
+
+/// Split the next complete message from buf, returns `None` if more bytes
+/// are needed.
+fn split_message(buf: &mut BytesMut) -> Result<Option<Bytes>> {
+    if buf.len() < 12 {
+        return Ok(None);
+    }
+    let total_len = (&buf[0..4]).get_u32() as usize;
+    let headers_len = (&buf[4..8]).get_u32() as usize;
+    // prelude (12) + headers + payload + message crc (4)
+    if total_len < headers_len + 16 {
+        return Err(new_event_stream_error("message length is invalid"));
+    }
+    if buf.len() < total_len {
+        return Ok(None);
+    }
+    Ok(Some(buf.split_to(total_len).freeze()))
+}
+
+fn parse_message(mut msg: Bytes) -> Result<Event> {
+    let headers_len = (&msg[4..8]).get_u32() as usize;
+    msg.advance(12);
+    let headers = parse_headers(msg.split_to(headers_len))?;
+    let payload = msg.split_to(msg.len() - 4);
+
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    if header(":message-type") == Some("error") {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            header(":error-message").unwrap_or("select object content failed"),
+        )
+        .with_context("error_code", header(":error-code").unwrap_or_default()));
+    }
+    Ok(match header(":event-type") {
+        Some("Records") => Event::Records(payload),
+        Some("End") => Event::End,
+        _ => Event::Other,
+    })
+}
+
+/// Parse the headers of an event stream message.
+///
+/// Only string values are kept, which is the only type used by S3.
+fn parse_headers(mut bs: Bytes) -> Result<Vec<(String, String)>> {
+    let mut headers = Vec::new();
+
+    while !bs.is_empty() {
+        let name_len = bs.get_u8() as usize;
+        if bs.len() < name_len + 1 {
+            return Err(new_event_stream_error("header name is truncated"));
+        }
+        let name = String::from_utf8_lossy(&bs.split_to(name_len)).into_owned();
+
+        let value_len = match bs.get_u8() {
+            // bool true and false
+            0 | 1 => 0,
+            // byte
+            2 => 1,
+            // short
+            3 => 2,
+            // integer
+            4 => 4,
+            // long and timestamp
+            5 | 8 => 8,
+            // uuid
+            9 => 16,
+            // byte array and string
+            6 | 7 => {
+                if bs.len() < 2 {
+                    return Err(new_event_stream_error("header value is truncated"));
+                }
+                bs.get_u16() as usize
+            }
+            v => {
+                return Err(new_event_stream_error("header value type is invalid")
+                    .with_context("type", v.to_string()))
+            }
+        };
+        if bs.len() < value_len {
+            return Err(new_event_stream_error("header value is truncated"));
+        }
+        let value = bs.split_to(value_len);
+        headers.push((name, String::from_utf8_lossy(&value).into_owned()));
+    }
+
+    Ok(headers)
+}
+
+fn new_event_stream_error(msg: &str) -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        format!("invalid event stream: {msg}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use bytes::BufMut;
+
+    use super::*;
+    use crate::raw::oio::Read;
+
+    /// Return the chunks one by one to split messages across reads.
+    struct ChunkedBody(VecDeque<Bytes>);
+
+    impl oio::Read for ChunkedBody {
+        async fn read(&mut self) -> Result<Buffer> {
+            Ok(self.0.pop_front().map(Buffer::from).unwrap_or_default())
+        }
+    }
+
+    fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut hs = BytesMut::new();
+        for (k, v) in headers {
+            hs.put_u8(k.len() as u8);
+            hs.put_slice(k.as_bytes());
+            hs.put_u8(7);
+            hs.put_u16(v.len() as u16);
+            hs.put_slice(v.as_bytes());
+        }
+
+        let mut msg = BytesMut::new();
+        msg.put_u32((hs.len() + payload.len() + 16) as u32);
+        msg.put_u32(hs.len() as u32);
+        // Checksums are not verified.
+        msg.put_u32(0);
+        msg.put_slice(&hs);
+        msg.put_slice(payload);
+        msg.put_u32(0);
+        msg.to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_select_reader() {
+        let mut bs = Vec::new();
+        bs.extend(encode_message(
+            &[(":message-type", "event"), (":event-type", "Records")],
+            b"a,1\n",
+        ));
+        bs.extend(encode_message(
+            &[(":message-type", "event"), (":event-type", "Stats")],
+            b"<Stats></Stats>",
+        ));
+        bs.extend(encode_message(
+            &[(":message-type", "event"), (":event-type", "Records")],
+            b"b,2\n",
+        ));
+        bs.extend(encode_message(
+            &[(":message-type", "event"), (":event-type", "End")],
+            b"",
+        ));
+
+        // Messages are split across reads.
+        let chunks = bs.chunks(7).map(Bytes::copy_from_slice).collect();
+        let mut r = S3SelectReader::new(ChunkedBody(chunks));
+        let records = r.read_all().await.expect("must succeed");
+        assert_eq!(records.to_vec(), b"a,1\nb,2\n");
+    }
+
+    #[tokio::test]
+    async fn test_select_reader_error() {
+        let bs = encode_message(
+            &[
+                (":message-type", "error"),
+                (":error-code", "InvalidQuery"),
+                (":error-message", "syntax error"),
+            ],
+            b"",
+        );
+        let mut r = S3SelectReader::new(Buffer::from(bs));
+        let err = r.read_all().await.unwrap_err();
+        assert!(err.to_string().contains("syntax error"));
+
+        let bs = encode_message(
+            &[(":message-type", "event"), (":event-type", "Records")],
+            b"a,1\n",
+        );
+        let mut r = S3SelectReader::new(Buffer::from(bs));
+        assert!(r.read_all().await.is_err());
+    }
+}
@@ -44,10 +44,52 @@ impl S3Writer {
             op,
         }
     }
+
+    /// Copy `range` of `from` as a part of the upload via UploadPartCopy.
+    ///
+    /// `part_number` starts from 0 like [`oio::MultipartWrite::write_part`].
+    pub async fn copy_part(
+        &self,
+        from: &str,
+        upload_id: &str,
+        part_number: usize,
+        range: BytesRange,
+    ) -> Result<oio::MultipartPart> {
+        // AWS S3 requires part number must between [1..=10000]
+        let part_number = part_number + 1;
+
+        let resp = self
+            .core
+            .s3_upload_part_copy(from, &self.path, upload_id, part_number, range)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                // S3 might return error for status code 200 like CompleteMultipartUpload.
+                let (parts, body) = resp.into_parts();
+                let maybe_error: S3Error = quick_xml::de::from_reader(body.clone().reader())
+                    .map_err(new_xml_deserialize_error)?;
+                if !maybe_error.code.is_empty() {
+                    return Err(from_s3_error(maybe_error, parts));
+                }
+
+                let result: CopyPartResult =
+                    quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+                Ok(oio::MultipartPart {
+                    part_number,
+                    etag: result.etag,
+                    checksum: result.checksum_crc32c,
+                })
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
 }
 
 impl oio::MultipartWrite for S3Writer {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req = self
             .core
             .s3_put_object_request(&self.path, Some(size), &self.op, body)?;
@@ -59,7 +101,13 @@ impl oio::MultipartWrite for S3Writer {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => {
+                let mut meta = Metadata::new(EntryMode::FILE);
+                if let Some(etag) = parse_etag(resp.headers())? {
+                    meta.set_etag(etag);
+                }
+                Ok(meta)
+            }
             _ => Err(parse_error(resp)),
         }
     }
@@ -104,6 +152,7 @@ impl oio::MultipartWrite for S3Writer {
             size,
             body,
             checksum.clone(),
+            self.op.customer_key(),
         )?;
 
         self.core.sign(&mut req).await?;
@@ -133,7 +182,11 @@ impl oio::MultipartWrite for S3Writer {
         }
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| match &self.core.checksum_algorithm {
@@ -154,7 +207,7 @@ impl oio::MultipartWrite for S3Writer {
 
         let resp = self
             .core
-            .s3_complete_multipart_upload(&self.path, upload_id, parts)
+            .s3_complete_multipart_upload(&self.path, upload_id, parts, &self.op)
             .await?;
 
         let status = resp.status();
@@ -164,13 +217,19 @@ impl oio::MultipartWrite for S3Writer {
                 // still check if there is any error because S3 might return error for status code 200
                 // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Example_4
                 let (parts, body) = resp.into_parts();
-                let maybe_error: S3Error =
-                    quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+                let maybe_error: S3Error = quick_xml::de::from_reader(body.clone().reader())
+                    .map_err(new_xml_deserialize_error)?;
                 if !maybe_error.code.is_empty() {
                     return Err(from_s3_error(maybe_error, parts));
                 }
 
-                Ok(())
+                let result: CompleteMultipartUploadResult =
+                    quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+                let mut meta = Metadata::new(EntryMode::FILE);
+                if !result.etag.is_empty() {
+                    meta.set_etag(&result.etag);
+                }
+                Ok(meta)
             }
             _ => Err(parse_error(resp)),
         }
@@ -21,6 +21,7 @@ use std::io::SeekFrom;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bb8::PooledConnection;
 use bb8::RunError;
@@ -43,6 +44,9 @@ use crate::raw::*;
 use crate::services::SftpConfig;
 use crate::*;
 
+const DEFAULT_MAX_SESSIONS: usize = 64;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
 impl Configurator for SftpConfig {
     type Builder = SftpBuilder;
     fn into_builder(self) -> Self::Builder {
@@ -135,6 +139,51 @@ impl SftpBuilder {
         self
     }
 
+    /// set the known_hosts file for sftp backend.
+    ///
+    /// It's used instead of the default `~/.ssh/known_hosts` to verify the host key.
+    pub fn known_hosts_file(mut self, path: &str) -> Self {
+        self.config.known_hosts_file = if path.is_empty() {
+            None
+        } else {
+            Some(path.to_string())
+        };
+
+        self
+    }
+
+    /// pin the host key for sftp backend.
+    ///
+    /// The value is the public key of the server in the format of
+    /// `ssh-keyscan`'s output without the host, like `ssh-ed25519 AAAA...`.
+    /// Connections to servers presenting other keys will be rejected.
+    ///
+    /// It conflicts with `known_hosts_file`, and `known_hosts_strategy`
+    /// must be `Strict` if set.
+    pub fn host_key(mut self, key: &str) -> Self {
+        self.config.host_key = if key.is_empty() {
+            None
+        } else {
+            Some(key.to_string())
+        };
+
+        self
+    }
+
+    /// set the ssh-agent socket for sftp backend.
+    ///
+    /// The identities held by the agent will be used to authenticate. The
+    /// agent pointed by `SSH_AUTH_SOCK` is used if this is not set.
+    pub fn agent_socket(mut self, socket: &str) -> Self {
+        self.config.agent_socket = if socket.is_empty() {
+            None
+        } else {
+            Some(socket.to_string())
+        };
+
+        self
+    }
+
     /// set enable_copy for sftp backend.
     /// It requires the server supports copy-file extension.
     pub fn enable_copy(mut self, enable_copy: bool) -> Self {
@@ -142,6 +191,26 @@ impl SftpBuilder {
 
         self
     }
+
+    /// set the max number of ssh sessions kept in the pool for sftp backend.
+    ///
+    /// Every session serves one operation at a time, concurrent operations
+    /// are dispatched across sessions. Default to 64.
+    pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+        self.config.max_sessions = (max_sessions > 0).then_some(max_sessions);
+
+        self
+    }
+
+    /// set the idle timeout of ssh sessions for sftp backend.
+    ///
+    /// Sessions that have been idle for this duration will be closed.
+    /// Default to 10 minutes.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.config.idle_timeout = (!timeout.is_zero()).then_some(timeout);
+
+        self
+    }
 }
 
 impl Builder for SftpBuilder {
@@ -183,6 +252,25 @@ impl Builder for SftpBuilder {
             None => KnownHosts::Strict,
         };
 
+        let host_key = match &self.config.host_key {
+            Some(key) => {
+                if self.config.known_hosts_file.is_some() {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "host_key and known_hosts_file can't be set at the same time",
+                    ));
+                }
+                if !matches!(known_hosts_strategy, KnownHosts::Strict) {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "known_hosts_strategy must be strict if host_key is set",
+                    ));
+                }
+                Some(parse_host_key(key)?)
+            }
+            None => None,
+        };
+
         debug!("sftp backend finished: {:?}", &self);
 
         Ok(SftpBackend {
@@ -191,7 +279,12 @@ impl Builder for SftpBuilder {
             user,
             key: self.config.key.clone(),
             known_hosts_strategy,
+            known_hosts_file: self.config.known_hosts_file.clone(),
+            host_key,
+            agent_socket: self.config.agent_socket.clone(),
             copyable: self.config.enable_copy,
+            max_sessions: self.config.max_sessions.unwrap_or(DEFAULT_MAX_SESSIONS),
+            idle_timeout: self.config.idle_timeout.unwrap_or(DEFAULT_IDLE_TIMEOUT),
 
             client: OnceCell::new(),
         })
@@ -207,6 +300,11 @@ pub struct SftpBackend {
     user: Option<String>,
     key: Option<String>,
     known_hosts_strategy: KnownHosts,
+    known_hosts_file: Option<String>,
+    host_key: Option<String>,
+    agent_socket: Option<String>,
+    max_sessions: usize,
+    idle_timeout: Duration,
 
     client: OnceCell<bb8::Pool<Manager>>,
 }
@@ -217,6 +315,10 @@ pub struct Manager {
     user: Option<String>,
     key: Option<String>,
     known_hosts_strategy: KnownHosts,
+    known_hosts_file: Option<PathBuf>,
+    /// Pinned host key files, removed while the pool is dropped.
+    pinned: Option<PinnedHostKey>,
+    agent_socket: Option<String>,
 }
 
 #[async_trait::async_trait]
@@ -235,7 +337,17 @@ impl bb8::ManageConnection for Manager {
             session.keyfile(key);
         }
 
+        if let Some(socket) = &self.agent_socket {
+            session.ssh_auth_sock(socket);
+        }
+
         session.known_hosts_check(self.known_hosts_strategy.clone());
+        if let Some(path) = &self.known_hosts_file {
+            session.user_known_hosts_file(path);
+        }
+        if let Some(pinned) = &self.pinned {
+            session.config_file(pinned.config_file());
+        }
 
         let session = session
             .connect(&self.endpoint)
@@ -293,14 +405,23 @@ impl SftpBackend {
         let client = self
             .client
             .get_or_try_init(|| async {
+                let pinned = match &self.host_key {
+                    Some(key) => Some(PinnedHostKey::new(key).await?),
+                    None => None,
+                };
+
                 bb8::Pool::builder()
-                    .max_size(64)
+                    .max_size(self.max_sessions as u32)
+                    .idle_timeout(Some(self.idle_timeout))
                     .build(Manager {
                         endpoint: self.endpoint.clone(),
                         root: self.root.clone(),
                         user: self.user.clone(),
                         key: self.key.clone(),
                         known_hosts_strategy: self.known_hosts_strategy.clone(),
+                        known_hosts_file: self.known_hosts_file.as_ref().map(PathBuf::from),
+                        pinned,
+                        agent_socket: self.agent_socket.clone(),
                     })
                     .await
             })
@@ -315,6 +436,60 @@ impl SftpBackend {
     }
 }
 
+/// Check the pinned host key is a public key like `ssh-ed25519 AAAA...`.
+fn parse_host_key(key: &str) -> Result<String> {
+    let mut parts = key.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some(algo), Some(data)) if algo.starts_with("ssh-") || algo.starts_with("ecdsa-") => {
+            Ok(format!("{algo} {data}"))
+        }
+        _ => Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "host_key is not a valid public key",
+        )
+        .with_context("host_key", key)),
+    }
+}
+
+/// PinnedHostKey holds an ssh config that only trusts the pinned host key.
+///
+/// The files are written into a temp dir which will be removed on drop.
+struct PinnedHostKey {
+    dir: tempfile::TempDir,
+}
+
+impl PinnedHostKey {
+    async fn new(key: &str) -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("opendal-sftp-")
+            .tempdir()
+            .map_err(new_std_io_error)?;
+
+        // The key is valid for any host since the file is only used by this backend.
+        let known_hosts = dir.path().join("known_hosts");
+        tokio::fs::write(&known_hosts, format!("* {key}\n"))
+            .await
+            .map_err(new_std_io_error)?;
+
+        // Ignore the global known_hosts file so that the pinned key is the only
+        // trusted one. User's config is still included for other options, the
+        // first obtained value of an option wins.
+        let config = format!(
+            "GlobalKnownHostsFile /dev/null\nUserKnownHostsFile \"{}\"\nInclude ~/.ssh/config\n",
+            known_hosts.display()
+        );
+        tokio::fs::write(dir.path().join("config"), config)
+            .await
+            .map_err(new_std_io_error)?;
+
+        Ok(Self { dir })
+    }
+
+    fn config_file(&self) -> PathBuf {
+        self.dir.path().join("config")
+    }
+}
+
 impl Access for SftpBackend {
     type Reader = SftpReader;
     type Writer = SftpWriter;
@@ -429,7 +604,7 @@ impl Access for SftpBackend {
 
         let file = option.open(path).await.map_err(parse_sftp_error)?;
 
-        Ok((RpWrite::new(), SftpWriter::new(file)))
+        Ok((RpWrite::new(), SftpWriter::new(client, file)))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
@@ -17,6 +17,7 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -36,8 +37,20 @@ pub struct SftpConfig {
     pub key: Option<String>,
     /// known_hosts_strategy of this backend
     pub known_hosts_strategy: Option<String>,
+    /// known_hosts_file of this backend, used instead of `~/.ssh/known_hosts`
+    pub known_hosts_file: Option<String>,
+    /// host_key of this backend, the only public key accepted from the server
+    pub host_key: Option<String>,
+    /// agent_socket of this backend, the socket of the ssh-agent to authenticate with
+    pub agent_socket: Option<String>,
     /// enable_copy of this backend
     pub enable_copy: bool,
+    /// The max number of ssh sessions kept in the pool, default to 64.
+    ///
+    /// Concurrent operations are dispatched across sessions.
+    pub max_sessions: Option<usize>,
+    /// Close sessions that have been idle for this duration, default to 10 minutes.
+    pub idle_timeout: Option<Duration>,
 }
 
 impl Debug for SftpConfig {
@@ -45,6 +58,8 @@ impl Debug for SftpConfig {
         f.debug_struct("SftpConfig")
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
+            .field("max_sessions", &self.max_sessions)
+            .field("idle_timeout", &self.idle_timeout)
             .finish_non_exhaustive()
     }
 }
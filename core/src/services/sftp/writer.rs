@@ -17,22 +17,28 @@
 
 use std::pin::Pin;
 
+use bb8::PooledConnection;
 use bytes::Buf;
 use openssh_sftp_client::file::File;
 use openssh_sftp_client::file::TokioCompatFile;
 use tokio::io::AsyncWriteExt;
 
+use super::backend::Manager;
 use crate::raw::*;
 use crate::*;
 
 pub struct SftpWriter {
+    /// Keep the session out of the pool while writing, so that other
+    /// operations don't share it with this transfer.
+    _conn: PooledConnection<'static, Manager>,
     /// TODO: maybe we can use `File` directly?
     file: Pin<Box<TokioCompatFile>>,
 }
 
 impl SftpWriter {
-    pub fn new(file: File) -> Self {
+    pub fn new(conn: PooledConnection<'static, Manager>, file: File) -> Self {
         SftpWriter {
+            _conn: conn,
             file: Box::pin(TokioCompatFile::new(file)),
         }
     }
@@ -52,8 +58,9 @@ impl oio::Write for SftpWriter {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
-        self.file.shutdown().await.map_err(new_std_io_error)
+    async fn close(&mut self) -> Result<Metadata> {
+        self.file.shutdown().await.map_err(new_std_io_error)?;
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -87,6 +87,22 @@ impl SwiftBuilder {
         self
     }
 
+    /// Set the container to store segments of large objects.
+    ///
+    /// Objects larger than one request are uploaded as segments and stitched by a
+    /// [static large object](https://docs.openstack.org/swift/latest/overview_large_objects.html)
+    /// manifest. Segments are stored under `.segments/` of this container.
+    ///
+    /// Default to the same container as `container`.
+    pub fn segment_container(mut self, container: &str) -> Self {
+        self.config.segment_container = if container.is_empty() {
+            None
+        } else {
+            Some(container.trim_end_matches('/').to_string())
+        };
+        self
+    }
+
     /// Set root of this backend.
     ///
     /// All operations will happen under this root.
@@ -150,6 +166,12 @@ impl Builder for SwiftBuilder {
         };
         debug!("backend use container: {}", &container);
 
+        let segment_container = self
+            .config
+            .segment_container
+            .unwrap_or_else(|| container.clone());
+        debug!("backend use segment container: {}", &segment_container);
+
         let token = self.config.token.unwrap_or_default();
 
         let client = HttpClient::new()?;
@@ -159,6 +181,7 @@ impl Builder for SwiftBuilder {
                 root,
                 endpoint,
                 container,
+                segment_container,
                 token,
                 client,
             }),
@@ -174,7 +197,7 @@ pub struct SwiftBackend {
 
 impl Access for SwiftBackend {
     type Reader = HttpBody;
-    type Writer = oio::OneShotWriter<SwiftWriter>;
+    type Writer = oio::MultipartWriter<SwiftWriter>;
     type Lister = oio::PageLister<SwiftLister>;
     type BlockingReader = ();
     type BlockingWriter = ();
@@ -191,6 +214,17 @@ impl Access for SwiftBackend {
 
                 write: true,
                 write_can_empty: true,
+                write_can_multi: true,
+                // Segments except the last one must be at least 1 MiB by default.
+                //
+                // Reference: <https://docs.openstack.org/swift/latest/overview_large_objects.html>
+                write_multi_min_size: Some(1024 * 1024),
+                // The max size of an object is 5 GiB by default.
+                write_multi_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(5 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
                 delete: true,
 
                 list: true,
@@ -233,7 +267,7 @@ impl Access for SwiftBackend {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let writer = SwiftWriter::new(self.core.clone(), args.clone(), path.to_string());
 
-        let w = oio::OneShotWriter::new(writer);
+        let w = oio::MultipartWriter::new(writer, args.executor().cloned(), args.concurrent());
 
         Ok((RpWrite::default(), w))
     }
@@ -34,6 +34,8 @@ pub struct SwiftConfig {
     pub root: Option<String>,
     /// The token for Swift.
     pub token: Option<String>,
+    /// The container to store segments of large objects, default to `container`.
+    pub segment_container: Option<String>,
 }
 
 impl Debug for SwiftConfig {
@@ -43,6 +45,7 @@ impl Debug for SwiftConfig {
         ds.field("root", &self.root);
         ds.field("endpoint", &self.endpoint);
         ds.field("container", &self.container);
+        ds.field("segment_container", &self.segment_container);
 
         if self.token.is_some() {
             ds.field("token", &"<redacted>");
@@ -21,6 +21,7 @@ use http::header;
 use http::Request;
 use http::Response;
 use serde::Deserialize;
+use serde::Serialize;
 
 use crate::raw::*;
 use crate::*;
@@ -29,6 +30,7 @@ pub struct SwiftCore {
     pub root: String,
     pub endpoint: String,
     pub container: String,
+    pub segment_container: String,
     pub token: String,
     pub client: HttpClient,
 }
@@ -39,6 +41,7 @@ impl Debug for SwiftCore {
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
             .field("container", &self.container)
+            .field("segment_container", &self.segment_container)
             .finish_non_exhaustive()
     }
 }
@@ -47,8 +50,10 @@ impl SwiftCore {
     pub async fn swift_delete(&self, path: &str) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
+        // Segments of static large objects are deleted together with the manifest,
+        // other objects are deleted normally.
         let url = format!(
-            "{}/{}/{}",
+            "{}/{}/{}?multipart-manifest=delete",
             &self.endpoint,
             &self.container,
             percent_encode_path(&p)
@@ -124,6 +129,125 @@ impl SwiftCore {
         self.client.send(req).await
     }
 
+    /// Build the prefix of segments in the segment container for an upload.
+    fn segment_prefix(&self, path: &str, upload_id: &str) -> String {
+        let p = build_abs_path(&self.root, path);
+        format!(".segments/{}/{}/", p.trim_end_matches('/'), upload_id)
+    }
+
+    pub async fn swift_put_segment(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let name = format!("{}{:08}", self.segment_prefix(path, upload_id), part_number);
+        let url = format!(
+            "{}/{}/{}",
+            &self.endpoint,
+            &self.segment_container,
+            percent_encode_path(&name)
+        );
+
+        let mut req = Request::put(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+        req = req.header(header::CONTENT_LENGTH, size);
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    /// Create a static large object at `path` from uploaded segments.
+    ///
+    /// Reference: <https://docs.openstack.org/swift/latest/overview_large_objects.html#static-large-objects>
+    pub async fn swift_put_manifest(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Response<Buffer>> {
+        let prefix = self.segment_prefix(path, upload_id);
+        let segments: Vec<SloSegment> = parts
+            .iter()
+            .map(|part| SloSegment {
+                path: format!(
+                    "/{}/{}{:08}",
+                    self.segment_container, prefix, part.part_number
+                ),
+                // Swift may return etag with quotes.
+                etag: part.etag.trim_matches('"').to_string(),
+            })
+            .collect();
+        let content = serde_json::to_vec(&segments).map_err(new_json_serialize_error)?;
+
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/{}/{}?multipart-manifest=put",
+            &self.endpoint,
+            &self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+        req = req.header(header::CONTENT_LENGTH, content.len());
+
+        let req = req
+            .body(Buffer::from(content))
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn swift_list_segments(
+        &self,
+        path: &str,
+        upload_id: &str,
+        marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let prefix = self.segment_prefix(path, upload_id);
+
+        let mut url = format!(
+            "{}/{}/?prefix={}&format=json",
+            &self.endpoint,
+            &self.segment_container,
+            percent_encode_path(&prefix)
+        );
+        if !marker.is_empty() {
+            url += &format!("&marker={}", percent_encode_path(marker));
+        }
+
+        let mut req = Request::get(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn swift_delete_segment(&self, name: &str) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/{}/{}",
+            &self.endpoint,
+            &self.segment_container,
+            percent_encode_path(name)
+        );
+
+        let mut req = Request::delete(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
     pub async fn swift_read(
         &self,
         path: &str,
@@ -226,10 +350,29 @@ pub enum ListOpResponse {
     },
 }
 
+/// A segment in the manifest of a static large object.
+#[derive(Debug, Serialize)]
+pub struct SloSegment {
+    pub path: String,
+    pub etag: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_slo_manifest() {
+        let segments = vec![SloSegment {
+            path: "/segments/.segments/a.txt/id/00000000".to_string(),
+            etag: "5e6b5b70b0426b1cc1968003e1afa5ad".to_string(),
+        }];
+        assert_eq!(
+            serde_json::to_string(&segments).unwrap(),
+            r#"[{"path":"/segments/.segments/a.txt/id/00000000","etag":"5e6b5b70b0426b1cc1968003e1afa5ad"}]"#
+        );
+    }
+
     #[test]
     fn parse_list_response_test() -> Result<()> {
         let resp = bytes::Bytes::from(
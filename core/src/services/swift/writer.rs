@@ -17,13 +17,20 @@
 
 use std::sync::Arc;
 
+use bytes::Buf;
 use http::StatusCode;
+use uuid::Uuid;
 
-use super::core::SwiftCore;
+use super::core::*;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// SwiftWriter uploads small objects in one request and large objects as
+/// [static large objects](https://docs.openstack.org/swift/latest/overview_large_objects.html).
+///
+/// Segments are uploaded to `.segments/{path}/{upload_id}/` of the segment
+/// container, the manifest is created at `path` once all segments are uploaded.
 pub struct SwiftWriter {
     core: Arc<SwiftCore>,
     path: String,
@@ -35,18 +42,108 @@ impl SwiftWriter {
     }
 }
 
-impl oio::OneShotWrite for SwiftWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+impl oio::MultipartWrite for SwiftWriter {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let resp = self
             .core
-            .swift_create_object(&self.path, bs.len() as u64, bs)
+            .swift_create_object(&self.path, size, body)
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp)),
         }
     }
+
+    async fn initiate_part(&self) -> Result<String> {
+        // Swift doesn't have upload sessions, segments of different uploads are
+        // kept apart by a random id.
+        Ok(Uuid::new_v4().to_string())
+    }
+
+    async fn write_part(
+        &self,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: Buffer,
+    ) -> Result<oio::MultipartPart> {
+        let resp = self
+            .core
+            .swift_put_segment(&self.path, upload_id, part_number, size, body)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "ETag not present in returning response",
+                        )
+                    })?
+                    .to_string();
+
+                Ok(oio::MultipartPart {
+                    part_number,
+                    etag,
+                    checksum: None,
+                })
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
+        let resp = self
+            .core
+            .swift_put_manifest(&self.path, upload_id, parts)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn abort_part(&self, upload_id: &str) -> Result<()> {
+        let mut marker = String::new();
+        loop {
+            let resp = self
+                .core
+                .swift_list_segments(&self.path, upload_id, &marker)
+                .await?;
+            if !resp.status().is_success() {
+                return Err(parse_error(resp));
+            }
+
+            let segments: Vec<ListOpResponse> = serde_json::from_reader(resp.into_body().reader())
+                .map_err(new_json_deserialize_error)?;
+            if segments.is_empty() {
+                return Ok(());
+            }
+
+            for segment in segments {
+                let ListOpResponse::FileInfo { name, .. } = segment else {
+                    continue;
+                };
+                let resp = self.core.swift_delete_segment(&name).await?;
+                match resp.status() {
+                    StatusCode::NO_CONTENT | StatusCode::OK | StatusCode::NOT_FOUND => {}
+                    _ => return Err(parse_error(resp)),
+                }
+                marker = name;
+            }
+        }
+    }
 }
@@ -18,7 +18,10 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use http::Response;
 use http::StatusCode;
@@ -32,6 +35,9 @@ use crate::raw::*;
 use crate::services::WebdavConfig;
 use crate::*;
 
+/// The timeout in seconds of locks acquired for writes and deletes.
+const DEFAULT_LOCK_TIMEOUT: u64 = 60;
+
 impl Configurator for WebdavConfig {
     type Builder = WebdavBuilder;
     fn into_builder(self) -> Self::Builder {
@@ -104,6 +110,34 @@ impl WebdavBuilder {
         self
     }
 
+    /// Lock resources before writing or deleting them, so concurrent
+    /// writers won't overwrite each other.
+    ///
+    /// Locks can also be held across multiple operations via
+    /// `WebdavExtension` if the `extensions` feature is enabled.
+    pub fn enable_lock(mut self) -> Self {
+        self.config.enable_lock = true;
+        self
+    }
+
+    /// Set the timeout of locks acquired for writes and deletes.
+    ///
+    /// Default to 60 seconds.
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.config.lock_timeout = Some(timeout.as_secs());
+        self
+    }
+
+    /// Set whether the server allows `Depth: infinity` PROPFIND.
+    ///
+    /// Recursive listing fetches the whole subtree in one request if
+    /// allowed, otherwise it walks directory by directory. It's detected
+    /// at the first recursive listing if not set.
+    pub fn depth_infinity(mut self, enabled: bool) -> Self {
+        self.config.depth_infinity = Some(enabled);
+        self
+    }
+
     /// Set root path of http backend.
     pub fn root(mut self, root: &str) -> Self {
         self.config.root = if root.is_empty() {
@@ -180,6 +214,13 @@ impl Builder for WebdavBuilder {
             server_path,
             authorization,
             disable_copy: self.config.disable_copy,
+            enable_lock: self.config.enable_lock,
+            lock_timeout: Duration::from_secs(
+                self.config.lock_timeout.unwrap_or(DEFAULT_LOCK_TIMEOUT),
+            ),
+            locks: Mutex::default(),
+            depth_infinity: self.config.depth_infinity,
+            depth_infinity_rejected: AtomicBool::new(false),
             root,
             client,
         });
@@ -229,11 +270,13 @@ impl Access for WebdavBackend {
                 rename: true,
 
                 list: true,
-                // We already support recursive list but some details still need to polish.
-                // list_with_recursive: true,
+                list_with_recursive: true,
                 ..Default::default()
             });
 
+        #[cfg(feature = "extensions")]
+        ma.set_extension(super::ext::WebdavExtension::new(self.core.clone()));
+
         ma.into()
     }
 
@@ -275,12 +318,22 @@ impl Access for WebdavBackend {
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.webdav_delete(path).await?;
+        let lock = self.core.lock_for_update(path).await?;
+        let resp = self
+            .core
+            .webdav_delete(path, lock.as_ref().map(|v| v.token.as_str()))
+            .await?;
 
         let status = resp.status();
         match status {
+            // Locks are removed along with the resource, no need to release.
             StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpDelete::default()),
-            _ => Err(parse_error(resp)),
+            _ => {
+                let err = parse_error(resp);
+                // The error of the delete is more important.
+                let _ = self.core.release_lock(path, lock).await;
+                Err(err)
+            }
         }
     }
 
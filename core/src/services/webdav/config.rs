@@ -38,6 +38,15 @@ pub struct WebdavConfig {
     pub root: Option<String>,
     /// WebDAV Service doesn't support copy.
     pub disable_copy: bool,
+    /// Lock resources before writing or deleting them.
+    pub enable_lock: bool,
+    /// The timeout in seconds of locks, default to 60.
+    pub lock_timeout: Option<u64>,
+    /// Whether the server allows `Depth: infinity` PROPFIND, which lists
+    /// the whole subtree in one request for recursive listing.
+    ///
+    /// Detected at the first recursive listing if not set.
+    pub depth_infinity: Option<bool>,
 }
 
 impl Debug for WebdavConfig {
@@ -46,7 +55,10 @@ impl Debug for WebdavConfig {
 
         d.field("endpoint", &self.endpoint)
             .field("username", &self.username)
-            .field("root", &self.root);
+            .field("root", &self.root)
+            .field("enable_lock", &self.enable_lock)
+            .field("lock_timeout", &self.lock_timeout)
+            .field("depth_infinity", &self.depth_infinity);
 
         d.finish_non_exhaustive()
     }
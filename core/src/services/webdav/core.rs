@@ -15,10 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use bytes::Bytes;
 use http::header;
@@ -67,6 +72,22 @@ static HEADER_DESTINATION: &str = "Destination";
 ///
 /// reference: [RFC4918: 10.6.  Overwrite Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.6)
 static HEADER_OVERWRITE: &str = "Overwrite";
+/// The request to acquire an exclusive write lock.
+///
+/// reference: [RFC4918: 9.10. LOCK Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.10)
+static LOCK_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8" ?><D:lockinfo xmlns:D="DAV:"><D:lockscope><D:exclusive/></D:lockscope><D:locktype><D:write/></D:locktype><D:owner>opendal</D:owner></D:lockinfo>"#;
+/// The header carrying the token of the lock.
+///
+/// reference: [RFC4918: 10.5. Lock-Token Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.5)
+static HEADER_LOCK_TOKEN: &str = "Lock-Token";
+/// The header to submit lock tokens with requests modifying locked resources.
+///
+/// reference: [RFC4918: 10.4. If Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.4)
+static HEADER_IF: &str = "If";
+/// The header to specify the timeout of locks, like `Second-60`.
+///
+/// reference: [RFC4918: 10.7. Timeout Request Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.7)
+static HEADER_TIMEOUT: &str = "Timeout";
 
 pub struct WebdavCore {
     pub endpoint: String,
@@ -74,6 +95,15 @@ pub struct WebdavCore {
     pub root: String,
     pub disable_copy: bool,
     pub authorization: Option<String>,
+    /// Lock resources before writing or deleting them.
+    pub enable_lock: bool,
+    pub lock_timeout: Duration,
+    /// Locks held via the extension, keyed by rooted absolute paths.
+    pub locks: Mutex<HashMap<String, String>>,
+    /// Whether the server allows `Depth: infinity`, detected if `None`.
+    pub depth_infinity: Option<bool>,
+    /// Set once the server rejected `Depth: infinity`.
+    pub depth_infinity_rejected: AtomicBool,
 
     pub client: HttpClient,
 }
@@ -159,6 +189,7 @@ impl WebdavCore {
         path: &str,
         size: Option<u64>,
         args: &OpWrite,
+        lock_token: Option<&str>,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
         let path = build_rooted_abs_path(&self.root, path);
@@ -170,6 +201,10 @@ impl WebdavCore {
             req = req.header(header::AUTHORIZATION, v)
         }
 
+        if let Some(token) = lock_token {
+            req = req.header(HEADER_IF, format!("(<{token}>)"))
+        }
+
         if let Some(v) = size {
             req = req.header(header::CONTENT_LENGTH, v)
         }
@@ -187,7 +222,11 @@ impl WebdavCore {
         self.client.send(req).await
     }
 
-    pub async fn webdav_delete(&self, path: &str) -> Result<Response<Buffer>> {
+    pub async fn webdav_delete(
+        &self,
+        path: &str,
+        lock_token: Option<&str>,
+    ) -> Result<Response<Buffer>> {
         let path = build_rooted_abs_path(&self.root, path);
         let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
 
@@ -197,6 +236,10 @@ impl WebdavCore {
             req = req.header(header::AUTHORIZATION, auth.clone())
         }
 
+        if let Some(token) = lock_token {
+            req = req.header(HEADER_IF, format!("(<{token}>)"))
+        }
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.client.send(req).await
@@ -254,7 +297,9 @@ impl WebdavCore {
         self.client.send(req).await
     }
 
-    pub async fn webdav_list(&self, path: &str, args: &OpList) -> Result<Response<Buffer>> {
+    /// List the members of the collection, or the whole subtree if
+    /// `infinite` is true.
+    pub async fn webdav_list(&self, path: &str, infinite: bool) -> Result<Response<Buffer>> {
         let path = build_rooted_abs_path(&self.root, path);
         let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
 
@@ -266,7 +311,7 @@ impl WebdavCore {
             req = req.header(header::AUTHORIZATION, auth);
         }
 
-        if args.recursive() {
+        if infinite {
             req = req.header(HEADER_DEPTH, "infinity");
         } else {
             req = req.header(HEADER_DEPTH, "1");
@@ -279,6 +324,37 @@ impl WebdavCore {
         self.client.send(req).await
     }
 
+    /// Check whether recursive listing should use `Depth: infinity`.
+    pub fn use_depth_infinity(&self) -> bool {
+        self.depth_infinity
+            .unwrap_or_else(|| !self.depth_infinity_rejected.load(Ordering::Relaxed))
+    }
+
+    /// Check whether the response means `Depth: infinity` is rejected, and
+    /// remember it if the support is detected.
+    ///
+    /// Servers reject it with `403 Forbidden` and the `propfind-finite-depth`
+    /// precondition, while some old ones return `400` or `501`.
+    ///
+    /// reference: [RFC4918: 9.1. PROPFIND Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.1)
+    pub fn check_depth_infinity_rejected(&self, resp: &Response<Buffer>) -> bool {
+        if self.depth_infinity.is_some() {
+            return false;
+        }
+
+        let rejected = match resp.status() {
+            StatusCode::FORBIDDEN => {
+                String::from_utf8_lossy(&resp.body().to_bytes()).contains("propfind-finite-depth")
+            }
+            StatusCode::BAD_REQUEST | StatusCode::NOT_IMPLEMENTED => true,
+            _ => false,
+        };
+        if rejected {
+            self.depth_infinity_rejected.store(true, Ordering::Relaxed);
+        }
+        rejected
+    }
+
     /// Create dir recursively for given path.
     ///
     /// # Notes
@@ -349,6 +425,161 @@ impl WebdavCore {
             _ => Err(parse_error(resp)),
         }
     }
+
+    /// Acquire an exclusive write lock of the resource, returns the lock
+    /// token.
+    ///
+    /// Locks of collections cover all their members.
+    pub async fn webdav_lock(&self, path: &str, timeout: Duration) -> Result<String> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
+
+        let mut req = Request::builder().method("LOCK").uri(&url);
+
+        req = req.header(header::CONTENT_TYPE, "application/xml");
+        req = req.header(header::CONTENT_LENGTH, LOCK_REQUEST.len());
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+
+        req = req.header(
+            HEADER_DEPTH,
+            if path.ends_with('/') { "infinity" } else { "0" },
+        );
+        req = req.header(HEADER_TIMEOUT, format!("Second-{}", timeout.as_secs()));
+
+        let req = req
+            .body(Buffer::from(Bytes::from(LOCK_REQUEST)))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            // 201 (Created) means an empty resource has been created to
+            // hold the lock, which will be replaced by the writes.
+            StatusCode::OK | StatusCode::CREATED => parse_lock_token(resp.headers()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Refresh the timeout of the lock held by token.
+    ///
+    /// reference: [RFC4918: 9.10.2. Refreshing Locks](https://datatracker.ietf.org/doc/html/rfc4918#section-9.10.2)
+    pub async fn webdav_refresh_lock(
+        &self,
+        path: &str,
+        token: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
+
+        let mut req = Request::builder().method("LOCK").uri(&url);
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+
+        req = req.header(HEADER_IF, format!("(<{token}>)"));
+        req = req.header(HEADER_TIMEOUT, format!("Second-{}", timeout.as_secs()));
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Release the lock held by token.
+    pub async fn webdav_unlock(&self, path: &str, token: &str) -> Result<()> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
+
+        let mut req = Request::builder().method("UNLOCK").uri(&url);
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+
+        req = req.header(HEADER_LOCK_TOKEN, format!("<{token}>"));
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Get the lock to write or delete the resource with.
+    ///
+    /// Locks held via the extension that cover the resource are used as is,
+    /// otherwise a new lock is acquired if locking is enabled.
+    pub async fn lock_for_update(&self, path: &str) -> Result<Option<WebdavLockToken>> {
+        if let Some(token) = self.held_lock(path) {
+            return Ok(Some(WebdavLockToken {
+                token,
+                acquired: false,
+            }));
+        }
+        if !self.enable_lock {
+            return Ok(None);
+        }
+
+        let token = self.webdav_lock(path, self.lock_timeout).await?;
+        Ok(Some(WebdavLockToken {
+            token,
+            acquired: true,
+        }))
+    }
+
+    /// Release the lock returned by [`WebdavCore::lock_for_update`] if it's
+    /// acquired for this update only.
+    pub async fn release_lock(&self, path: &str, lock: Option<WebdavLockToken>) -> Result<()> {
+        match lock {
+            Some(WebdavLockToken {
+                token,
+                acquired: true,
+            }) => self.webdav_unlock(path, &token).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Find the held lock of the resource or the collections containing it.
+    fn held_lock(&self, path: &str) -> Option<String> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let locks = self.locks.lock().expect("lock must succeed");
+        locks
+            .iter()
+            .find(|(k, _)| *k == &path || (k.ends_with('/') && path.starts_with(k.as_str())))
+            .map(|(_, v)| v.clone())
+    }
+}
+
+/// The token of the lock used to update a resource.
+pub struct WebdavLockToken {
+    pub token: String,
+    /// Whether the lock is acquired for this update only and should be
+    /// released after it.
+    pub acquired: bool,
+}
+
+/// Parse the token from `Lock-Token: <opaquelocktoken:...>`.
+fn parse_lock_token(headers: &http::HeaderMap) -> Result<String> {
+    let token = headers
+        .get(HEADER_LOCK_TOKEN)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().trim_start_matches('<').trim_end_matches('>'))
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "lock token is missing in the response of LOCK",
+            )
+        })?;
+    Ok(token.to_string())
 }
 
 pub fn deserialize_multistatus(bs: &[u8]) -> Result<Multistatus> {
@@ -460,6 +691,54 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_lock_token() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            HEADER_LOCK_TOKEN,
+            "<opaquelocktoken:e71d4fae-5dec-22d6-fea5-00a0c91e6be4>"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            parse_lock_token(&headers).unwrap(),
+            "opaquelocktoken:e71d4fae-5dec-22d6-fea5-00a0c91e6be4"
+        );
+
+        assert!(parse_lock_token(&http::HeaderMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_check_depth_infinity_rejected() {
+        let core = WebdavCore {
+            endpoint: "http://127.0.0.1:8080".to_string(),
+            server_path: "".to_string(),
+            root: "/".to_string(),
+            disable_copy: false,
+            authorization: None,
+            enable_lock: false,
+            lock_timeout: Duration::from_secs(60),
+            locks: Mutex::default(),
+            depth_infinity: None,
+            depth_infinity_rejected: AtomicBool::new(false),
+            client: HttpClient::new().unwrap(),
+        };
+        let resp = |status: StatusCode, body: &'static str| {
+            Response::builder()
+                .status(status)
+                .body(Buffer::from(body))
+                .unwrap()
+        };
+
+        // Forbidden for other reasons.
+        assert!(!core.check_depth_infinity_rejected(&resp(StatusCode::FORBIDDEN, "")));
+        assert!(core.use_depth_infinity());
+
+        let body = r#"<D:error xmlns:D="DAV:"><D:propfind-finite-depth/></D:error>"#;
+        assert!(core.check_depth_infinity_rejected(&resp(StatusCode::FORBIDDEN, body)));
+        assert!(!core.use_depth_infinity());
+    }
+
     #[test]
     fn test_propstat() {
         let xml = r#"<D:propstat>
@@ -0,0 +1,126 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::backend::WebdavBuilder;
+use super::core::WebdavCore;
+use crate::raw::*;
+use crate::*;
+
+/// A write lock held on a resource via [`WebdavExtension::lock`].
+///
+/// Writes and deletes of the resource, or its members if it's a collection,
+/// will be sent with the lock until it's released by
+/// [`WebdavExtension::unlock`]. The lock expires after the timeout unless
+/// it's refreshed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebdavLock {
+    path: String,
+    token: String,
+}
+
+impl WebdavLock {
+    /// The path of the locked resource.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The lock token returned by the server.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+/// WebdavExtension provides webdav-specific operations that are not covered
+/// by [`Operator`].
+///
+/// Get it via `op.as_service::<services::Webdav>()`.
+#[derive(Clone)]
+pub struct WebdavExtension {
+    core: Arc<WebdavCore>,
+}
+
+impl Debug for WebdavExtension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebdavExtension")
+            .field("core", &self.core)
+            .finish()
+    }
+}
+
+impl WebdavExtension {
+    pub(super) fn new(core: Arc<WebdavCore>) -> Self {
+        Self { core }
+    }
+
+    /// Acquire an exclusive write lock of `path` and hold it across
+    /// operations of this operator.
+    ///
+    /// Paths ending with `/` lock the whole collection.
+    pub async fn lock(&self, path: &str, timeout: Duration) -> Result<WebdavLock> {
+        let token = self.core.webdav_lock(path, timeout).await.map_err(|err| {
+            err.with_operation("WebdavExtension::lock")
+                .with_context("path", path)
+        })?;
+
+        self.core
+            .locks
+            .lock()
+            .expect("lock must succeed")
+            .insert(build_rooted_abs_path(&self.core.root, path), token.clone());
+        Ok(WebdavLock {
+            path: path.to_string(),
+            token,
+        })
+    }
+
+    /// Extend the timeout of the lock.
+    pub async fn refresh_lock(&self, lock: &WebdavLock, timeout: Duration) -> Result<()> {
+        self.core
+            .webdav_refresh_lock(&lock.path, &lock.token, timeout)
+            .await
+            .map_err(|err| {
+                err.with_operation("WebdavExtension::refresh_lock")
+                    .with_context("path", &lock.path)
+            })
+    }
+
+    /// Release the lock.
+    pub async fn unlock(&self, lock: WebdavLock) -> Result<()> {
+        self.core
+            .locks
+            .lock()
+            .expect("lock must succeed")
+            .remove(&build_rooted_abs_path(&self.core.root, &lock.path));
+
+        self.core
+            .webdav_unlock(&lock.path, &lock.token)
+            .await
+            .map_err(|err| {
+                err.with_operation("WebdavExtension::unlock")
+                    .with_context("path", &lock.path)
+            })
+    }
+}
+
+impl ServiceExtension for WebdavBuilder {
+    type Extension = WebdavExtension;
+}
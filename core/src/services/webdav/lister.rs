@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use http::StatusCode;
 
@@ -29,6 +31,9 @@ pub struct WebdavLister {
 
     path: String,
     args: OpList,
+    /// Dirs to walk if recursive listing falls back to listing directory by
+    /// directory.
+    dirs: Mutex<VecDeque<String>>,
 }
 
 impl WebdavLister {
@@ -37,13 +42,41 @@ impl WebdavLister {
             core,
             path: path.to_string(),
             args,
+            dirs: Mutex::default(),
         }
     }
 }
 
 impl oio::PageList for WebdavLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
-        let resp = self.core.webdav_list(&self.path, &self.args).await?;
+        // `token` is set to the dir being walked after the first page.
+        let first = ctx.token.is_empty();
+        let dir = if first {
+            self.path.clone()
+        } else {
+            let dir = self.dirs.lock().expect("lock must succeed").pop_front();
+            match dir {
+                Some(dir) => dir,
+                None => {
+                    ctx.done = true;
+                    return Ok(());
+                }
+            }
+        };
+
+        let mut walk = self.args.recursive();
+        let mut resp = None;
+        if first && self.args.recursive() && self.core.use_depth_infinity() {
+            let r = self.core.webdav_list(&dir, true).await?;
+            if !self.core.check_depth_infinity_rejected(&r) {
+                walk = false;
+                resp = Some(r);
+            }
+        }
+        let resp = match resp {
+            Some(resp) => resp,
+            None => self.core.webdav_list(&dir, false).await?,
+        };
 
         // jfrog artifactory's webdav services have some strange behavior.
         // We add this flag to check if the server is jfrog artifactory.
@@ -57,8 +90,8 @@ impl oio::PageList for WebdavLister {
 
         let bs = if resp.status().is_success() {
             resp.into_body()
-        } else if resp.status() == StatusCode::NOT_FOUND && self.path.ends_with('/') {
-            ctx.done = true;
+        } else if resp.status() == StatusCode::NOT_FOUND && dir.ends_with('/') {
+            self.finish_page(ctx, &dir);
             return Ok(());
         } else {
             return Err(parse_error(resp));
@@ -98,11 +131,35 @@ impl oio::PageList for WebdavLister {
                 continue;
             }
 
+            // Dirs walked after the first page have been returned by their
+            // parents already.
+            let is_self = normalized_path == dir;
+            if is_self && !first {
+                continue;
+            }
+            if walk && meta.is_dir() && !is_self {
+                self.dirs
+                    .lock()
+                    .expect("lock must succeed")
+                    .push_back(normalized_path.clone());
+            }
+
             ctx.entries
                 .push_back(oio::Entry::new(&normalized_path, meta))
         }
-        ctx.done = true;
+        self.finish_page(ctx, &dir);
 
         Ok(())
     }
 }
+
+impl WebdavLister {
+    /// Finish the listing if there are no more dirs to walk.
+    fn finish_page(&self, ctx: &mut oio::PageContext, dir: &str) {
+        if self.dirs.lock().expect("lock must succeed").is_empty() {
+            ctx.done = true;
+        } else {
+            ctx.token = dir.to_string();
+        }
+    }
+}
@@ -29,5 +29,12 @@ mod backend;
 #[cfg(feature = "services-webdav")]
 pub use backend::WebdavBuilder as Webdav;
 
+#[cfg(all(feature = "services-webdav", feature = "extensions"))]
+mod ext;
+#[cfg(all(feature = "services-webdav", feature = "extensions"))]
+pub use ext::WebdavExtension;
+#[cfg(all(feature = "services-webdav", feature = "extensions"))]
+pub use ext::WebdavLock;
+
 mod config;
 pub use config::WebdavConfig;
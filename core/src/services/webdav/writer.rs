@@ -37,11 +37,11 @@ impl WebdavWriter {
     }
 }
 
-impl oio::OneShotWrite for WebdavWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+impl WebdavWriter {
+    async fn put(&self, bs: Buffer, lock_token: Option<&str>) -> Result<()> {
         let resp = self
             .core
-            .webdav_put(&self.path, Some(bs.len() as u64), &self.op, bs)
+            .webdav_put(&self.path, Some(bs.len() as u64), &self.op, lock_token, bs)
             .await?;
 
         let status = resp.status();
@@ -52,3 +52,14 @@ impl oio::OneShotWrite for WebdavWriter {
         }
     }
 }
+
+impl oio::OneShotWrite for WebdavWriter {
+    async fn write_once(&self, bs: Buffer) -> Result<()> {
+        let lock = self.core.lock_for_update(&self.path).await?;
+        let res = self.put(bs, lock.as_ref().map(|v| v.token.as_str())).await;
+        // Release the lock even if the write failed, the error of the write
+        // is more important.
+        let released = self.core.release_lock(&self.path, lock).await;
+        res.and(released)
+    }
+}
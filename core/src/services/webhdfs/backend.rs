@@ -16,6 +16,7 @@
 // under the License.
 
 use core::fmt::Debug;
+use std::collections::HashMap;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
@@ -34,6 +35,7 @@ use super::lister::WebhdfsLister;
 use super::message::BooleanResp;
 use super::message::FileStatusType;
 use super::message::FileStatusWrapper;
+use super::message::XAttrsWrapper;
 use super::writer::WebhdfsWriter;
 use super::writer::WebhdfsWriters;
 use crate::raw::*;
@@ -41,6 +43,8 @@ use crate::services::WebhdfsConfig;
 use crate::*;
 
 const WEBHDFS_DEFAULT_ENDPOINT: &str = "http://127.0.0.1:9870";
+/// The xattr namespace that user metadata will be stored in.
+const XATTR_USER_PREFIX: &str = "user.";
 
 impl Configurator for WebhdfsConfig {
     type Builder = WebhdfsBuilder;
@@ -137,6 +141,18 @@ impl WebhdfsBuilder {
         };
         self
     }
+
+    /// Enable user metadata support via HDFS extended attributes (xattrs).
+    ///
+    /// # Notes
+    ///
+    /// User metadata will be stored as xattrs in the `user.` namespace while writing,
+    /// and fetched by an extra `GETXATTRS` request while stat. Please make sure
+    /// `dfs.namenode.xattrs.enabled` is enabled on the cluster.
+    pub fn enable_xattr(mut self) -> Self {
+        self.config.enable_xattr = true;
+        self
+    }
 }
 
 impl Builder for WebhdfsBuilder {
@@ -186,6 +202,7 @@ impl Builder for WebhdfsBuilder {
             root_checker: OnceCell::new(),
             atomic_write_dir,
             disable_list_batch: self.config.disable_list_batch,
+            enable_xattr: self.config.enable_xattr,
         };
 
         Ok(backend)
@@ -202,6 +219,7 @@ pub struct WebhdfsBackend {
 
     pub atomic_write_dir: Option<String>,
     pub disable_list_batch: bool,
+    pub enable_xattr: bool,
     pub client: HttpClient,
 }
 
@@ -469,6 +487,102 @@ impl WebhdfsBackend {
         self.client.send(req).await
     }
 
+    pub(super) async fn webhdfs_get_xattrs(&self, path: &str) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let mut url = format!(
+            "{}/webhdfs/v1/{}?op=GETXATTRS&encoding=text",
+            self.endpoint,
+            percent_encode_path(&p),
+        );
+
+        if let Some(auth) = &self.auth {
+            url += format!("&{auth}").as_str();
+        }
+
+        let req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub(super) async fn webhdfs_set_xattr(
+        &self,
+        path: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        // Wrap value in double quotes so that it will be decoded as text
+        // instead of hex (`0x`) or base64 (`0s`).
+        let mut url = format!(
+            "{}/webhdfs/v1/{}?op=SETXATTR&xattr.name={}&xattr.value={}&flag=CREATE,REPLACE",
+            self.endpoint,
+            percent_encode_path(&p),
+            percent_encode_query(&format!("{XATTR_USER_PREFIX}{name}")),
+            percent_encode_query(&format!("\"{value}\"")),
+        );
+
+        if let Some(auth) = &self.auth {
+            url += format!("&{auth}").as_str();
+        }
+
+        let req = Request::put(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    /// Store user metadata of given write op as xattrs.
+    ///
+    /// This function does nothing if xattr is not enabled.
+    pub(super) async fn webhdfs_set_user_metadata(&self, path: &str, args: &OpWrite) -> Result<()> {
+        if !self.enable_xattr {
+            return Ok(());
+        }
+        let Some(user_metadata) = args.user_metadata() else {
+            return Ok(());
+        };
+
+        for (name, value) in user_metadata {
+            let resp = self.webhdfs_set_xattr(path, name, value).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch xattrs in `user.` namespace as user metadata.
+    async fn webhdfs_get_user_metadata(&self, path: &str) -> Result<HashMap<String, String>> {
+        let resp = self.webhdfs_get_xattrs(path).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let bs = resp.into_body();
+        let xattrs = serde_json::from_reader::<_, XAttrsWrapper>(bs.reader())
+            .map_err(new_json_deserialize_error)?
+            .xattrs;
+
+        Ok(xattrs
+            .into_iter()
+            .filter_map(|xattr| {
+                let name = xattr.name.strip_prefix(XATTR_USER_PREFIX)?.to_string();
+                let value = xattr.value.unwrap_or_default();
+                // Text encoded value is wrapped in double quotes.
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value.as_str())
+                    .to_string();
+                Some((name, value))
+            })
+            .collect())
+    }
+
     pub async fn webhdfs_delete(&self, path: &str) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
         let mut url = format!(
@@ -527,12 +641,14 @@ impl Access for WebhdfsBackend {
             .set_root(&self.root)
             .set_native_capability(Capability {
                 stat: true,
+                stat_has_user_metadata: self.enable_xattr,
 
                 read: true,
 
                 write: true,
                 write_can_append: true,
                 write_can_multi: self.atomic_write_dir.is_some(),
+                write_with_user_metadata: self.enable_xattr,
 
                 create_dir: true,
                 delete: true,
@@ -592,7 +708,7 @@ impl Access for WebhdfsBackend {
                     .map_err(new_json_deserialize_error)?
                     .file_status;
 
-                let meta = match file_status.ty {
+                let mut meta = match file_status.ty {
                     FileStatusType::Directory => Metadata::new(EntryMode::DIR),
                     FileStatusType::File => Metadata::new(EntryMode::FILE)
                         .with_content_length(file_status.length)
@@ -601,6 +717,13 @@ impl Access for WebhdfsBackend {
                         )?),
                 };
 
+                if self.enable_xattr {
+                    let user_metadata = self.webhdfs_get_user_metadata(path).await?;
+                    if !user_metadata.is_empty() {
+                        meta.with_user_metadata(user_metadata);
+                    }
+                }
+
                 Ok(RpStat::new(meta))
             }
 
@@ -659,8 +782,8 @@ impl Access for WebhdfsBackend {
         }
 
         let path = path.trim_end_matches('/');
-        let l = WebhdfsLister::new(self.clone(), path);
-        Ok((RpList::default(), oio::PageLister::new(l)))
+        let l = oio::PageLister::new(WebhdfsLister::new(self.clone(), path));
+        Ok((RpList::default().with_stats(l.stats()), l))
     }
 }
 
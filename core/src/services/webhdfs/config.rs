@@ -36,6 +36,8 @@ pub struct WebhdfsConfig {
     pub disable_list_batch: bool,
     /// atomic_write_dir of this backend
     pub atomic_write_dir: Option<String>,
+    /// Enable user metadata support via HDFS extended attributes (xattrs).
+    pub enable_xattr: bool,
 }
 
 impl Debug for WebhdfsConfig {
@@ -44,6 +46,7 @@ impl Debug for WebhdfsConfig {
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
             .field("atomic_write_dir", &self.atomic_write_dir)
+            .field("enable_xattr", &self.enable_xattr)
             .finish_non_exhaustive()
     }
 }
@@ -51,10 +51,14 @@ impl oio::PageList for WebhdfsLister {
                     ));
 
                     let bs = resp.into_body();
-                    serde_json::from_reader::<_, FileStatusesWrapper>(bs.reader())
-                        .map_err(new_json_deserialize_error)?
-                        .file_statuses
-                        .file_status
+                    let file_status =
+                        serde_json::from_reader::<_, FileStatusesWrapper>(bs.reader())
+                            .map_err(new_json_deserialize_error)?
+                            .file_statuses
+                            .file_status;
+                    ctx.stats
+                        .update(|stats| stats.total_entries = Some(file_status.len() as u64));
+                    file_status
                 }
                 StatusCode::NOT_FOUND => {
                     ctx.done = true;
@@ -75,6 +79,13 @@ impl oio::PageList for WebhdfsLister {
                     let directory_listing = res.directory_listing;
                     let file_statuses = directory_listing.partial_listing.file_statuses.file_status;
 
+                    // The total is known since the first page.
+                    if ctx.token.is_empty() {
+                        let total =
+                            file_statuses.len() as u64 + directory_listing.remaining_entries as u64;
+                        ctx.stats.update(|stats| stats.total_entries = Some(total));
+                    }
+
                     if directory_listing.remaining_entries == 0 {
                         ctx.entries.push_back(oio::Entry::new(
                             format!("{}/", self.path).as_str(),
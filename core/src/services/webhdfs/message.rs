@@ -72,6 +72,18 @@ pub struct FileStatus {
     pub ty: FileStatusType,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct XAttrsWrapper {
+    #[serde(rename = "XAttrs")]
+    pub xattrs: Vec<XAttr>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct XAttr {
+    pub name: String,
+    pub value: Option<String>,
+}
+
 #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum FileStatusType {
@@ -246,4 +258,29 @@ mod test {
             "bazfile"
         );
     }
+
+    #[test]
+    fn test_xattrs() {
+        let json = r#"
+{
+  "XAttrs": [
+    {
+      "name" : "user.commit",
+      "value": "\"42\""
+    },
+    {
+      "name" : "user.empty"
+    }
+  ]
+}
+"#;
+        let xattrs = serde_json::from_str::<XAttrsWrapper>(json)
+            .expect("must success")
+            .xattrs;
+        assert_eq!(xattrs.len(), 2);
+        assert_eq!(xattrs[0].name, "user.commit");
+        assert_eq!(xattrs[0].value.as_deref(), Some("\"42\""));
+        assert_eq!(xattrs[1].name, "user.empty");
+        assert_eq!(xattrs[1].value, None);
+    }
 }
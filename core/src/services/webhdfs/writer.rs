@@ -40,7 +40,7 @@ impl WebhdfsWriter {
 }
 
 impl oio::BlockWrite for WebhdfsWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let req = self
             .backend
             .webhdfs_create_object_request(&self.path, Some(size), &self.op, body)
@@ -50,7 +50,12 @@ impl oio::BlockWrite for WebhdfsWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => {
+                self.backend
+                    .webhdfs_set_user_metadata(&self.path, &self.op)
+                    .await?;
+                Ok(Metadata::new(EntryMode::FILE))
+            }
             _ => Err(parse_error(resp)),
         }
     }
@@ -81,7 +86,7 @@ impl oio::BlockWrite for WebhdfsWriter {
         }
     }
 
-    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<()> {
+    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<Metadata> {
         let Some(ref atomic_write_dir) = self.backend.atomic_write_dir else {
             return Err(Error::new(
                 ErrorKind::Unsupported,
@@ -123,7 +128,12 @@ impl oio::BlockWrite for WebhdfsWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => {
+                self.backend
+                    .webhdfs_set_user_metadata(&self.path, &self.op)
+                    .await?;
+                Ok(Metadata::new(EntryMode::FILE))
+            }
             _ => Err(parse_error(resp)),
         }
     }
@@ -168,6 +178,9 @@ impl oio::AppendWrite for WebhdfsWriter {
 
                 match status {
                     StatusCode::CREATED | StatusCode::OK => {
+                        self.backend
+                            .webhdfs_set_user_metadata(&self.path, &self.op)
+                            .await?;
                         location = self.backend.webhdfs_init_append_request(&self.path).await?;
                     }
                     _ => return Err(parse_error(resp)),
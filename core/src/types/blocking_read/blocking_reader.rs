@@ -15,7 +15,6 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::Bound;
 use std::ops::Range;
 use std::ops::RangeBounds;
 use std::sync::Arc;
@@ -54,16 +53,15 @@ impl BlockingReader {
     ///
     /// To avoid duplicated stat call, we will cache the size of the reader.
     fn parse_range(&self, range: impl RangeBounds<u64>) -> Result<Range<u64>> {
-        let start = match range.start_bound() {
-            Bound::Included(v) => *v,
-            Bound::Excluded(v) => v + 1,
-            Bound::Unbounded => 0,
-        };
+        let range = BytesRange::try_from_bounds(range).map_err(|err| {
+            err.with_operation("BlockingReader::read")
+                .with_context("path", self.ctx.path())
+        })?;
 
-        let end = match range.end_bound() {
-            Bound::Included(v) => v + 1,
-            Bound::Excluded(v) => *v,
-            Bound::Unbounded => match self.size.load() {
+        let start = range.offset();
+        let end = match range.size() {
+            Some(size) => start + size,
+            None => match self.size.load() {
                 Some(v) => v,
                 None => {
                     let size = self
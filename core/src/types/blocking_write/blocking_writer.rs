@@ -84,7 +84,8 @@ impl BlockingWriter {
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
     pub fn close(&mut self) -> Result<()> {
-        self.inner.close()
+        self.inner.close()?;
+        Ok(())
     }
 
     /// Convert writer into [`StdWriter`] which implements [`std::io::Write`],
@@ -68,6 +68,16 @@ impl Builder for () {
     }
 }
 
+/// ServiceExtension is implemented by builders of services that provide
+/// provider-specific operations which don't fit into the generic API.
+///
+/// Use [`Operator::as_service`] to get the extension of the service.
+#[cfg(feature = "extensions")]
+pub trait ServiceExtension: Builder {
+    /// Associated extension for this builder.
+    type Extension: Clone + Send + Sync + 'static;
+}
+
 /// Configurator is used to configure the underlying service.
 ///
 /// This trait allows the developer to define a configuration struct that can:
@@ -78,6 +78,8 @@ pub struct Capability {
     pub stat_with_override_content_type: bool,
     /// Indicates if versioned stat operations are supported.
     pub stat_with_version: bool,
+    /// Indicates if customer-provided encryption keys are supported during stat operations.
+    pub stat_with_customer_key: bool,
     /// Indicates whether cache control information is available in stat response
     pub stat_has_cache_control: bool,
     /// Indicates whether content disposition information is available in stat response
@@ -98,6 +100,14 @@ pub struct Capability {
     pub stat_has_version: bool,
     /// Indicates whether user-defined metadata is available in stat response
     pub stat_has_user_metadata: bool,
+    /// Indicates whether custom time is available in stat response
+    pub stat_has_custom_time: bool,
+    /// Indicates whether expiration time is available in stat response
+    pub stat_has_expiration: bool,
+    /// Indicates whether server-side encryption status is available in stat response
+    pub stat_has_server_side_encryption: bool,
+    /// Indicates whether object lock retention and legal hold are available in stat response
+    pub stat_has_object_lock: bool,
 
     /// Indicates if the operator supports read operations.
     pub read: bool,
@@ -113,6 +123,13 @@ pub struct Capability {
     pub read_with_override_content_type: bool,
     /// Indicates if versioned read operations are supported.
     pub read_with_version: bool,
+    /// Indicates if read operations can query the records of objects in place.
+    pub read_with_select: bool,
+    /// Indicates if customer-provided encryption keys are supported during read operations.
+    pub read_with_customer_key: bool,
+    /// Maximum concurrent requests a reader can send for the same file.
+    /// For example, Google Drive throttles concurrent downloads of a file.
+    pub read_max_concurrent: Option<usize>,
 
     /// Indicates if the operator supports write operations.
     pub write: bool,
@@ -128,12 +145,20 @@ pub struct Capability {
     pub write_with_content_disposition: bool,
     /// Indicates if Cache-Control can be specified during write operations.
     pub write_with_cache_control: bool,
+    /// Indicates if conditional write operations using If-Match are supported.
+    pub write_with_if_match: bool,
     /// Indicates if conditional write operations using If-None-Match are supported.
     pub write_with_if_none_match: bool,
     /// Indicates if write operations can be conditional on object non-existence.
     pub write_with_if_not_exists: bool,
     /// Indicates if custom user metadata can be attached during write operations.
     pub write_with_user_metadata: bool,
+    /// Indicates if custom time can be specified during write operations.
+    pub write_with_custom_time: bool,
+    /// Indicates if object lock retention and legal hold can be specified during write operations.
+    pub write_with_object_lock: bool,
+    /// Indicates if customer-provided encryption keys are supported during write operations.
+    pub write_with_customer_key: bool,
     /// Maximum size supported for multipart uploads.
     /// For example, AWS S3 supports up to 5GiB per part in multipart uploads.
     pub write_multi_max_size: Option<usize>,
@@ -168,6 +193,8 @@ pub struct Capability {
     pub list_with_recursive: bool,
     /// Indicates if versioned listing is supported.
     pub list_with_version: bool,
+    /// Indicates if list operations can filter entries with a service specific query.
+    pub list_with_query: bool,
     /// Indicates whether cache control information is available in list response
     pub list_has_cache_control: bool,
     /// Indicates whether content disposition information is available in list response
@@ -186,8 +213,12 @@ pub struct Capability {
     pub list_has_last_modified: bool,
     /// Indicates whether version information is available in list response
     pub list_has_version: bool,
+    /// Indicates whether delete markers are returned in versioned list response
+    pub list_has_delete_marker: bool,
     /// Indicates whether user-defined metadata is available in list response
     pub list_has_user_metadata: bool,
+    /// Indicates whether server-side encryption status is available in list response
+    pub list_has_server_side_encryption: bool,
 
     /// Indicates if presigned URL generation is supported.
     pub presign: bool,
@@ -198,13 +229,23 @@ pub struct Capability {
     /// Indicates if presigned URLs for write operations are supported.
     pub presign_write: bool,
 
+    /// Indicates if signing user-built requests is supported.
+    pub sign: bool,
+
     /// Indicates if batch operations are supported.
     pub batch: bool,
     /// Indicates if batch delete operations are supported.
     pub batch_delete: bool,
+    /// Indicates if batch copy operations are supported.
+    pub batch_copy: bool,
     /// Maximum number of operations supported in a single batch.
     pub batch_max_operations: Option<usize>,
 
+    /// Indicates if listing in-progress multipart uploads is supported.
+    pub list_multipart_uploads: bool,
+    /// Indicates if aborting multipart uploads by upload id is supported.
+    pub abort_multipart_upload: bool,
+
     /// Indicates if blocking operations are supported.
     pub blocking: bool,
 }
@@ -224,6 +265,9 @@ impl Debug for Capability {
         if self.presign {
             f.write_str("| Presign")?;
         }
+        if self.sign {
+            f.write_str("| Sign")?;
+        }
         if self.blocking {
             f.write_str("| Blocking")?;
         }
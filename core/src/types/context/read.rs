@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::ops::{Bound, Range, RangeBounds};
+use std::ops::{Range, RangeBounds};
 use std::sync::Arc;
 
 use crate::raw::*;
@@ -69,21 +69,31 @@ impl ReadContext {
         &self.options
     }
 
+    /// Calculate the concurrent requests for this read process.
+    ///
+    /// The concurrent set in options is capped by
+    /// [`Capability::read_max_concurrent`].
+    pub fn concurrent(&self) -> usize {
+        let cap = self.accessor().info().full_capability();
+
+        match cap.read_max_concurrent {
+            Some(v) => self.options().concurrent().min(v.max(1)),
+            None => self.options().concurrent(),
+        }
+    }
+
     /// Parse the range bounds into a range.
     pub(crate) async fn parse_into_range(
         &self,
         range: impl RangeBounds<u64>,
     ) -> Result<Range<u64>> {
-        let start = match range.start_bound() {
-            Bound::Included(v) => *v,
-            Bound::Excluded(v) => v + 1,
-            Bound::Unbounded => 0,
-        };
+        let range = BytesRange::try_from_bounds(range)
+            .map_err(|err| err.with_operation("read").with_context("path", self.path()))?;
 
-        let end = match range.end_bound() {
-            Bound::Included(v) => v + 1,
-            Bound::Excluded(v) => *v,
-            Bound::Unbounded => {
+        let start = range.offset();
+        let end = match range.size() {
+            Some(size) => start + size,
+            None => {
                 let mut op_stat = OpStat::new();
 
                 if let Some(v) = self.args().version() {
@@ -262,4 +272,29 @@ mod tests {
         pretty_assertions::assert_eq!(readers.len(), 4);
         Ok(())
     }
+
+    #[test]
+    fn test_concurrent() -> Result<()> {
+        let op = Operator::via_iter(Scheme::Memory, [])?.layer(
+            crate::layers::CapabilityOverrideLayer::new(|cap| cap.read_max_concurrent = Some(4)),
+        );
+
+        let acc = op.into_inner();
+        let ctx = ReadContext::new(
+            acc.clone(),
+            "test".to_string(),
+            OpRead::new(),
+            OpReader::new().with_concurrent(16),
+        );
+        assert_eq!(ctx.concurrent(), 4);
+
+        let ctx = ReadContext::new(
+            acc,
+            "test".to_string(),
+            OpRead::new(),
+            OpReader::new().with_concurrent(2),
+        );
+        assert_eq!(ctx.concurrent(), 2);
+        Ok(())
+    }
 }
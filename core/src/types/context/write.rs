@@ -17,6 +17,8 @@
 
 use std::sync::Arc;
 
+use bytes::Buf;
+
 use crate::raw::oio::Write;
 use crate::raw::*;
 use crate::*;
@@ -93,6 +95,57 @@ impl WriteContext {
 
         (chunk_size, exact)
     }
+
+    /// Check if `size` bytes can be sent to the underlying writer in one
+    /// `write` call.
+    ///
+    /// [`WriteGenerator`] only splits data if chunk size is exact and smaller
+    /// than the data.
+    fn fits_one_write(&self, size: usize) -> bool {
+        match self.calculate_chunk_size() {
+            (Some(chunk_size), true) => size <= chunk_size,
+            _ => true,
+        }
+    }
+
+    /// Write `bs` as the whole content of the file.
+    ///
+    /// Small writes are sent to the underlying writer directly, which skips
+    /// the allocation and buffering of [`WriteGenerator`].
+    ///
+    /// Returns the metadata of the written object returned by services.
+    pub async fn write_all(self, mut bs: Buffer) -> Result<Metadata> {
+        if !self.fits_one_write(bs.len()) {
+            let mut w = WriteGenerator::create(Arc::new(self)).await?;
+            while !bs.is_empty() {
+                let n = w.write(bs.clone()).await?;
+                bs.advance(n);
+            }
+            return w.close().await;
+        }
+
+        let (_, mut w) = self.acc.write(&self.path, self.args).await?;
+        if !bs.is_empty() {
+            w.write_dyn(bs).await?;
+        }
+        w.close().await
+    }
+
+    /// Blocking version of [`WriteContext::write_all`].
+    pub fn blocking_write_all(self, bs: Buffer) -> Result<()> {
+        if !self.fits_one_write(bs.len()) {
+            let mut w = BlockingWriter::new(self)?;
+            w.write(bs)?;
+            return w.close();
+        }
+
+        let (_, mut w) = self.acc.blocking_write(&self.path, self.args)?;
+        if !bs.is_empty() {
+            w.write(bs)?;
+        }
+        w.close()?;
+        Ok(())
+    }
 }
 
 pub struct WriteGenerator<W> {
@@ -184,7 +237,7 @@ impl WriteGenerator<oio::Writer> {
     }
 
     /// Finish the write process.
-    pub async fn close(&mut self) -> Result<()> {
+    pub async fn close(&mut self) -> Result<Metadata> {
         loop {
             if self.buffer.is_empty() {
                 break;
@@ -271,7 +324,7 @@ impl WriteGenerator<oio::BlockingWriter> {
     }
 
     /// Finish the write process.
-    pub fn close(&mut self) -> Result<()> {
+    pub fn close(&mut self) -> Result<Metadata> {
         loop {
             if self.buffer.is_empty() {
                 break;
@@ -315,8 +368,8 @@ mod tests {
             Ok(())
         }
 
-        async fn close(&mut self) -> Result<()> {
-            Ok(())
+        async fn close(&mut self) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn abort(&mut self) -> Result<()> {
@@ -324,6 +377,38 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_write_all() -> Result<()> {
+        let acc = Operator::new(crate::services::Memory::default())?
+            .finish()
+            .into_inner();
+
+        let ctx = WriteContext::new(
+            acc.clone(),
+            "a.txt".to_string(),
+            OpWrite::new(),
+            OpWriter::new().with_chunk(4),
+        );
+        assert!(ctx.fits_one_write(4));
+        assert!(!ctx.fits_one_write(5));
+        ctx.write_all(Buffer::from("hello")).await?;
+
+        let ctx = WriteContext::new(
+            acc.clone(),
+            "b.txt".to_string(),
+            OpWrite::new(),
+            OpWriter::new(),
+        );
+        assert!(ctx.fits_one_write(usize::MAX));
+        ctx.write_all(Buffer::new()).await?;
+
+        let (_, mut r) = acc.read("a.txt", OpRead::new()).await?;
+        assert_eq!(oio::Read::read_all(&mut r).await?.to_vec(), b"hello");
+        let rp = acc.stat("b.txt", OpStat::new()).await?;
+        assert_eq!(rp.into_metadata().content_length(), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_exact_buf_writer_short_write() -> Result<()> {
         let _ = tracing_subscriber::fmt()
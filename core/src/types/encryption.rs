@@ -0,0 +1,88 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+
+use crate::raw::*;
+
+/// A customer-provided encryption key, like SSE-C in S3.
+///
+/// Services encrypt objects with this key on write and forget it after
+/// that, the same key must be provided to read or stat the object again.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CustomerEncryptionKey {
+    algorithm: String,
+    key: String,
+    key_md5: String,
+}
+
+impl Debug for CustomerEncryptionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomerEncryptionKey")
+            .field("algorithm", &self.algorithm)
+            .field("key", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl CustomerEncryptionKey {
+    /// Create a new key with given algorithm and raw key bytes.
+    pub fn new(algorithm: &str, key: &[u8]) -> Self {
+        Self {
+            algorithm: algorithm.to_string(),
+            key: BASE64_STANDARD.encode(key),
+            key_md5: format_content_md5(key),
+        }
+    }
+
+    /// Create a new `AES256` key with 32 raw key bytes.
+    pub fn aes256(key: &[u8; 32]) -> Self {
+        Self::new("AES256", key)
+    }
+
+    /// The encryption algorithm, like `AES256`.
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    /// The base64 encoded key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The base64 encoded MD5 of the key.
+    pub fn key_md5(&self) -> &str {
+        &self.key_md5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_customer_encryption_key() {
+        let key = CustomerEncryptionKey::aes256(&[0; 32]);
+        assert_eq!(key.algorithm(), "AES256");
+        assert_eq!(key.key(), "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=");
+        assert_eq!(key.key_md5(), "cLyPS3KoaSFGi/joRB3OUQ==");
+        assert!(!format!("{key:?}").contains(key.key()));
+    }
+}
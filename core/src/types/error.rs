@@ -86,6 +86,26 @@ pub enum ErrorKind {
     ///
     /// OpenDAL returns this error to indicate that the range of the read request is not satisfied.
     RangeNotSatisfied,
+    /// The operation exceeds the configured quota.
+    ///
+    /// For example, [`QuotaLayer`](crate::layers::QuotaLayer) returns this
+    /// error when the object is too large or the prefix runs out of quota.
+    QuotaExceeded,
+    /// The input of this operation is invalid.
+    ///
+    /// For example, reading with a range whose end is before the start.
+    InvalidInput,
+    /// The request is rejected without reaching the service since the
+    /// circuit breaker is open.
+    ///
+    /// For example, [`RetryLayer::with_circuit_breaker`](crate::layers::RetryLayer::with_circuit_breaker)
+    /// returns this error after consecutive failures.
+    CircuitOpen,
+    /// The content doesn't match the checksum returned by the service.
+    ///
+    /// For example, [`IntegrityLayer`](crate::layers::IntegrityLayer) returns
+    /// this error when the data read is corrupted.
+    DataCorrupted,
 }
 
 impl ErrorKind {
@@ -116,6 +136,10 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::IsSameFile => "IsSameFile",
             ErrorKind::ConditionNotMatch => "ConditionNotMatch",
             ErrorKind::RangeNotSatisfied => "RangeNotSatisfied",
+            ErrorKind::QuotaExceeded => "QuotaExceeded",
+            ErrorKind::InvalidInput => "InvalidInput",
+            ErrorKind::CircuitOpen => "CircuitOpen",
+            ErrorKind::DataCorrupted => "DataCorrupted",
         }
     }
 }
@@ -339,6 +363,22 @@ impl Error {
         self
     }
 
+    /// Replace the path context value `from` with `to`.
+    ///
+    /// Only values of `path`, `from` and `to` context that equal to `from`
+    /// are replaced, the message and other context are kept as is.
+    ///
+    /// This is used by layers that rewrite paths to make errors consistent
+    /// with the paths seen by users.
+    pub(crate) fn replace_path(mut self, from: &str, to: &str) -> Self {
+        for (k, v) in self.context.iter_mut() {
+            if matches!(*k, "path" | "from" | "to") && v == from {
+                *v = to.to_string();
+            }
+        }
+        self
+    }
+
     /// Set source for error.
     ///
     /// # Notes
@@ -400,6 +440,17 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Return the value of the first context with given key.
+    ///
+    /// For errors returned by http based services, `status_code` and
+    /// `request_id` could be used to locate the failed request.
+    pub fn context(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 impl From<Error> for io::Error {
@@ -407,6 +458,7 @@ impl From<Error> for io::Error {
         let kind = match err.kind() {
             ErrorKind::NotFound => io::ErrorKind::NotFound,
             ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
             _ => io::ErrorKind::Other,
         };
 
@@ -461,4 +513,22 @@ Source:
 "#
         )
     }
+
+    #[test]
+    fn test_error_context() {
+        let err = Lazy::force(&TEST_ERROR);
+        assert_eq!(err.context("path"), Some("/path/to/file"));
+        assert_eq!(err.context("status_code"), None);
+    }
+
+    #[test]
+    fn test_error_replace_path() {
+        let err = Error::new(ErrorKind::NotFound, "a/b is not found in a/b/c")
+            .with_context("path", "a/b")
+            .with_context("called", "a/b")
+            .replace_path("a/b", "x/y");
+        assert_eq!(err.message, "a/b is not found in a/b/c");
+        assert_eq!(err.context("path"), Some("x/y"));
+        assert_eq!(err.context("called"), Some("a/b"));
+    }
 }
@@ -0,0 +1,205 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// Etag is the parsed form of the `ETag` returned by services.
+///
+/// Services return etags in different forms, like `"0815"`, `W/"0815"` or
+/// `0815` without quotes. Etag strips the quotes and the weak indicator, so
+/// etags of the same entity compare equal no matter how they are quoted.
+///
+/// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
+///
+/// # Examples
+///
+/// ```
+/// use opendal::Etag;
+///
+/// let etag = Etag::parse(r#"W/"0815""#);
+/// assert!(etag.is_weak());
+/// assert_eq!(etag.value(), "0815");
+/// assert_eq!(etag.to_string(), r#"W/"0815""#);
+///
+/// assert!(etag.weak_eq(&Etag::parse("0815")));
+/// assert!(!etag.strong_eq(&Etag::parse("0815")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Etag {
+    value: String,
+    weak: bool,
+}
+
+impl Etag {
+    /// Create a strong etag of given opaque value, which should not
+    /// contain the quotes.
+    pub fn strong(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            weak: false,
+        }
+    }
+
+    /// Create a weak etag of given opaque value, which should not contain
+    /// the quotes.
+    pub fn weak(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            weak: true,
+        }
+    }
+
+    /// Parse the etag returned by services.
+    ///
+    /// Etags without quotes are accepted as is.
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        let (s, weak) = match s.strip_prefix("W/").or_else(|| s.strip_prefix("w/")) {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+        let value = s
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(s);
+
+        Self {
+            value: value.to_string(),
+            weak,
+        }
+    }
+
+    /// The opaque value of the etag without quotes.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns `true` if the etag is weak, which means entities with the
+    /// same etag are semantically equivalent but may differ in bytes.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns `true` if the etag is strong, which means entities with the
+    /// same etag are identical in bytes.
+    pub fn is_strong(&self) -> bool {
+        !self.weak
+    }
+
+    /// Returns the number of parts if the etag is a composite etag of
+    /// multipart uploads like `"<md5 of part md5s>-<parts>"`, which is
+    /// returned by S3 compatible services.
+    ///
+    /// The value of composite etags is not the md5 of the content.
+    pub fn multipart_parts(&self) -> Option<u64> {
+        let (hash, parts) = self.value.rsplit_once('-')?;
+        if hash.len() != 32 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        parts.parse().ok()
+    }
+
+    /// Strong comparison, both etags must be strong and have the same value.
+    ///
+    /// Use it to decide whether the content is identical, for example, to
+    /// serve cached bytes.
+    ///
+    /// Refer to [RFC 7232: 2.3.2. Comparison](https://httpwg.org/specs/rfc7232.html#rfc.section.2.3.2)
+    pub fn strong_eq(&self, other: &Etag) -> bool {
+        self.is_strong() && other.is_strong() && self.value == other.value
+    }
+
+    /// Weak comparison, etags are equal if they have the same value no matter
+    /// whether they are weak.
+    ///
+    /// Refer to [RFC 7232: 2.3.2. Comparison](https://httpwg.org/specs/rfc7232.html#rfc.section.2.3.2)
+    pub fn weak_eq(&self, other: &Etag) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Display for Etag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.value)
+        } else {
+            write!(f, "\"{}\"", self.value)
+        }
+    }
+}
+
+impl From<&str> for Etag {
+    fn from(s: &str) -> Self {
+        Etag::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let cases = vec![
+            ("quoted", r#""0815""#, "0815", false),
+            ("unquoted", "0815", "0815", false),
+            ("weak", r#"W/"0815""#, "0815", true),
+            ("weak lowercase", r#"w/"0815""#, "0815", true),
+            ("weak unquoted", "W/0815", "0815", true),
+            ("whitespace", " \"0815\" ", "0815", false),
+            ("empty", r#""""#, "", false),
+        ];
+
+        for (name, input, value, weak) in cases {
+            let etag = Etag::parse(input);
+            assert_eq!(etag.value(), value, "{name}");
+            assert_eq!(etag.is_weak(), weak, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let strong = Etag::parse(r#""0815""#);
+        let unquoted = Etag::parse("0815");
+        let weak = Etag::parse(r#"W/"0815""#);
+        let other = Etag::parse(r#""0816""#);
+
+        assert!(strong.strong_eq(&unquoted));
+        assert!(!strong.strong_eq(&weak));
+        assert!(!weak.strong_eq(&weak));
+        assert!(strong.weak_eq(&weak));
+        assert!(!strong.weak_eq(&other));
+
+        assert_eq!(unquoted.to_string(), r#""0815""#);
+        assert_eq!(weak.to_string(), r#"W/"0815""#);
+    }
+
+    #[test]
+    fn test_multipart_parts() {
+        assert_eq!(
+            Etag::parse(r#""d41d8cd98f00b204e9800998ecf8427e-12""#).multipart_parts(),
+            Some(12)
+        );
+        assert_eq!(
+            Etag::parse(r#""d41d8cd98f00b204e9800998ecf8427e""#).multipart_parts(),
+            None
+        );
+        assert_eq!(Etag::parse("0x8DBBDC4F8AC4AEF").multipart_parts(), None);
+        assert_eq!(Etag::parse("abc-3").multipart_parts(), None);
+    }
+}
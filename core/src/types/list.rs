@@ -16,6 +16,8 @@
 // under the License.
 
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
@@ -25,6 +27,160 @@ use futures::Stream;
 use crate::raw::*;
 use crate::*;
 
+/// ListCyclePolicy controls how recursive list handles dirs that have been
+/// walked, which happens on services with symlinks or shortcuts.
+///
+/// Dirs are identified by [`Metadata::file_id`], dirs without file id will
+/// always be walked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ListCyclePolicy {
+    /// Return the dir but don't walk into it again.
+    ///
+    /// This is the default policy.
+    #[default]
+    Skip,
+    /// Return an error once a dir is walked again.
+    Error,
+    /// Walk every dir without tracking, which may loop forever.
+    Follow,
+}
+
+/// ListErrorPolicy controls how list handles entries that can't be parsed,
+/// like keys with invalid UTF-8 or unparsable timestamps.
+///
+/// Errors of the listing itself, like failing to fetch a page, are always
+/// returned.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub enum ListErrorPolicy {
+    /// Return the error and stop listing.
+    ///
+    /// This is the default policy.
+    #[default]
+    Fail,
+    /// Skip the entry and push its error into the report, the listing will
+    /// continue.
+    SkipAndReport(ListErrorReport),
+}
+
+impl ListErrorPolicy {
+    /// Handle the error of an entry, returns the error back if it should
+    /// stop the listing.
+    pub(crate) fn handle(&self, err: Error) -> Result<()> {
+        match self {
+            ListErrorPolicy::Fail => Err(err),
+            ListErrorPolicy::SkipAndReport(report) => {
+                report.0.lock().expect("lock must succeed").push(err);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// ListErrorReport collects the errors of entries skipped by
+/// [`ListErrorPolicy::SkipAndReport`].
+///
+/// Clones of a report share the same errors, keep one to check them while or
+/// after listing.
+#[derive(Debug, Clone, Default)]
+pub struct ListErrorReport(Arc<Mutex<Vec<Error>>>);
+
+impl ListErrorReport {
+    /// Create a new empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of errors in the report.
+    pub fn len(&self) -> usize {
+        self.0.lock().expect("lock must succeed").len()
+    }
+
+    /// Check whether the report is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take all errors out of the report.
+    pub fn take(&self) -> Vec<Error> {
+        std::mem::take(&mut *self.0.lock().expect("lock must succeed"))
+    }
+}
+
+/// ListStats are the counts reported by services while listing.
+///
+/// Fields are `None` if services don't report them. Counts of fetched pages
+/// grow while entries are consumed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ListStats {
+    /// Pages that have been fetched.
+    pub pages: u64,
+    /// Entries counted by services in fetched pages, like `KeyCount` of s3.
+    pub entries: Option<u64>,
+    /// Bytes of files in fetched pages.
+    pub bytes: Option<u64>,
+    /// Entries of the whole listing, which is known since the first page,
+    /// like `total_count` of box.
+    pub total_entries: Option<u64>,
+}
+
+/// ListPage is a page of entries returned by [`Operator::list_page`] and
+/// [`BlockingOperator::list_page`].
+#[derive(Debug, Clone)]
+pub struct ListPage {
+    entries: Vec<Entry>,
+    next_token: Option<String>,
+}
+
+impl ListPage {
+    /// Build a page from the entries fetched with given limit.
+    ///
+    /// The path of the last entry will be used as the continuation token if
+    /// the page is full.
+    pub(crate) fn new(entries: Vec<Entry>, limit: usize) -> Self {
+        let next_token = if entries.len() == limit {
+            entries.last().map(|e| e.path().to_string())
+        } else {
+            None
+        };
+        Self {
+            entries,
+            next_token,
+        }
+    }
+
+    /// Check if the list page can be fetched from this service.
+    pub(crate) fn check(info: &OperatorInfo, path: &str) -> Result<()> {
+        if !info.full_capability().list_with_start_after {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "list page requires list_with_start_after support",
+            )
+            .with_operation("list_page")
+            .with_context("service", info.scheme())
+            .with_context("path", path));
+        }
+        Ok(())
+    }
+
+    /// Entries in this page.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Token to fetch the next page, `None` if this is the last page.
+    pub fn next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+
+    /// Consume the page and return the entries and the next token.
+    pub fn into_parts(self) -> (Vec<Entry>, Option<String>) {
+        (self.entries, self.next_token)
+    }
+}
+
 /// Lister is designed to list entries at given path in an asynchronous
 /// manner.
 ///
@@ -35,6 +191,8 @@ pub struct Lister {
 
     fut: Option<BoxedStaticFuture<(oio::Lister, Result<Option<oio::Entry>>)>>,
     errored: bool,
+    path_policy: PathPolicy,
+    stats: Option<oio::ListStatsRecorder>,
 }
 
 /// # Safety
@@ -44,16 +202,32 @@ unsafe impl Sync for Lister {}
 
 impl Lister {
     /// Create a new lister.
-    pub(crate) async fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
-        let (_, lister) = acc.list(path, args).await?;
+    pub(crate) async fn create(
+        acc: Accessor,
+        path: &str,
+        args: OpList,
+        path_policy: PathPolicy,
+    ) -> Result<Self> {
+        let (rp, lister) = acc.list(path, args).await?;
 
         Ok(Self {
             lister: Some(lister),
 
             fut: None,
             errored: false,
+            path_policy,
+            stats: rp.into_stats(),
         })
     }
+
+    /// Get the stats reported by services so far, returns `None` if the
+    /// service doesn't report stats.
+    ///
+    /// Stats are updated once a page is fetched, check it after consuming
+    /// entries for progress.
+    pub fn stats(&self) -> Option<ListStats> {
+        self.stats.as_ref().map(|v| v.get())
+    }
 }
 
 impl Stream for Lister {
@@ -79,7 +253,7 @@ impl Stream for Lister {
             self.fut = None;
 
             return match entry {
-                Ok(Some(oe)) => Poll::Ready(Some(Ok(oe.into_entry()))),
+                Ok(Some(oe)) => Poll::Ready(Some(Ok(self.path_policy.restore(oe).into_entry()))),
                 Ok(None) => {
                     self.lister = None;
                     Poll::Ready(None)
@@ -105,6 +279,8 @@ impl Stream for Lister {
 pub struct BlockingLister {
     lister: oio::BlockingLister,
     errored: bool,
+    path_policy: PathPolicy,
+    stats: Option<oio::ListStatsRecorder>,
 }
 
 /// # Safety
@@ -114,14 +290,27 @@ unsafe impl Sync for BlockingLister {}
 
 impl BlockingLister {
     /// Create a new lister.
-    pub(crate) fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
-        let (_, lister) = acc.blocking_list(path, args)?;
+    pub(crate) fn create(
+        acc: Accessor,
+        path: &str,
+        args: OpList,
+        path_policy: PathPolicy,
+    ) -> Result<Self> {
+        let (rp, lister) = acc.blocking_list(path, args)?;
 
         Ok(Self {
             lister,
             errored: false,
+            path_policy,
+            stats: rp.into_stats(),
         })
     }
+
+    /// Get the stats reported by services so far, returns `None` if the
+    /// service doesn't report stats.
+    pub fn stats(&self) -> Option<ListStats> {
+        self.stats.as_ref().map(|v| v.get())
+    }
 }
 
 impl Iterator for BlockingLister {
@@ -134,7 +323,7 @@ impl Iterator for BlockingLister {
         }
 
         match self.lister.next() {
-            Ok(Some(entry)) => Some(Ok(entry.into_entry())),
+            Ok(Some(entry)) => Some(Ok(self.path_policy.restore(entry).into_entry())),
             Ok(None) => None,
             Err(err) => {
                 self.errored = true;
@@ -37,13 +37,26 @@ pub struct Metadata {
     content_disposition: Option<String>,
     content_length: Option<u64>,
     content_md5: Option<String>,
+    content_crc32c: Option<String>,
     content_range: Option<BytesContentRange>,
     content_type: Option<String>,
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
     version: Option<String>,
+    is_delete_marker: bool,
+    file_id: Option<String>,
+    custom_time: Option<DateTime<Utc>>,
+    expiration: Option<DateTime<Utc>>,
+    server_side_encryption: Option<String>,
+    server_side_encryption_key_id: Option<String>,
+    object_lock_mode: Option<ObjectLockMode>,
+    object_lock_retain_until: Option<DateTime<Utc>>,
+    legal_hold: Option<bool>,
 
     user_metadata: Option<HashMap<String, String>>,
+
+    #[cfg(feature = "raw-headers")]
+    raw_headers: Option<http::HeaderMap>,
 }
 
 impl Metadata {
@@ -55,13 +68,25 @@ impl Metadata {
             cache_control: None,
             content_length: None,
             content_md5: None,
+            content_crc32c: None,
             content_type: None,
             content_range: None,
             last_modified: None,
             etag: None,
             content_disposition: None,
             version: None,
+            is_delete_marker: false,
+            file_id: None,
+            custom_time: None,
+            expiration: None,
+            server_side_encryption: None,
+            server_side_encryption_key_id: None,
+            object_lock_mode: None,
+            object_lock_retain_until: None,
+            legal_hold: None,
             user_metadata: None,
+            #[cfg(feature = "raw-headers")]
+            raw_headers: None,
         }
     }
 
@@ -176,6 +201,32 @@ impl Metadata {
         self
     }
 
+    /// Content CRC32C of this entry.
+    ///
+    /// The value is the base64 encoded big-endian CRC32C (Castagnoli) of the
+    /// content, which is the format returned by services like gcs and s3.
+    ///
+    /// OpenDAL will try its best to set this value, but not guarantee this value is the crc32c of content.
+    pub fn content_crc32c(&self) -> Option<&str> {
+        self.content_crc32c.as_deref()
+    }
+
+    /// Set content CRC32C of this entry.
+    ///
+    /// The value is the base64 encoded big-endian CRC32C of the content.
+    pub fn set_content_crc32c(&mut self, v: &str) -> &mut Self {
+        self.content_crc32c = Some(v.to_string());
+        self
+    }
+
+    /// Set content CRC32C of this entry.
+    ///
+    /// The value is the base64 encoded big-endian CRC32C of the content.
+    pub fn with_content_crc32c(mut self, v: String) -> Self {
+        self.content_crc32c = Some(v);
+        self
+    }
+
     /// Content Type of this entry.
     ///
     /// Content Type is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-type).
@@ -259,6 +310,169 @@ impl Metadata {
         self
     }
 
+    /// Custom time of this entry.
+    ///
+    /// Custom time is a user-specified timestamp attached to the object, like
+    /// `customTime` in GCS. It's commonly used as the key of lifecycle rules.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_custom_time`] before using it.
+    ///
+    /// [`Capability::stat_has_custom_time`]: crate::Capability::stat_has_custom_time
+    pub fn custom_time(&self) -> Option<DateTime<Utc>> {
+        self.custom_time
+    }
+
+    /// Set custom time of this entry.
+    pub fn set_custom_time(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.custom_time = Some(v);
+        self
+    }
+
+    /// Set custom time of this entry.
+    pub fn with_custom_time(mut self, v: DateTime<Utc>) -> Self {
+        self.custom_time = Some(v);
+        self
+    }
+
+    /// Object lock retention mode of this entry.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_object_lock`] before using it.
+    ///
+    /// [`Capability::stat_has_object_lock`]: crate::Capability::stat_has_object_lock
+    pub fn object_lock_mode(&self) -> Option<ObjectLockMode> {
+        self.object_lock_mode
+    }
+
+    /// Set object lock retention mode of this entry.
+    pub fn set_object_lock_mode(&mut self, v: ObjectLockMode) -> &mut Self {
+        self.object_lock_mode = Some(v);
+        self
+    }
+
+    /// Set object lock retention mode of this entry.
+    pub fn with_object_lock_mode(mut self, v: ObjectLockMode) -> Self {
+        self.object_lock_mode = Some(v);
+        self
+    }
+
+    /// The time until which this entry is retained by object lock.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_object_lock`] before using it.
+    ///
+    /// [`Capability::stat_has_object_lock`]: crate::Capability::stat_has_object_lock
+    pub fn object_lock_retain_until(&self) -> Option<DateTime<Utc>> {
+        self.object_lock_retain_until
+    }
+
+    /// Set the time until which this entry is retained by object lock.
+    pub fn set_object_lock_retain_until(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.object_lock_retain_until = Some(v);
+        self
+    }
+
+    /// Set the time until which this entry is retained by object lock.
+    pub fn with_object_lock_retain_until(mut self, v: DateTime<Utc>) -> Self {
+        self.object_lock_retain_until = Some(v);
+        self
+    }
+
+    /// Whether this entry is under legal hold.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_object_lock`] before using it.
+    ///
+    /// [`Capability::stat_has_object_lock`]: crate::Capability::stat_has_object_lock
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
+
+    /// Set whether this entry is under legal hold.
+    pub fn set_legal_hold(&mut self, v: bool) -> &mut Self {
+        self.legal_hold = Some(v);
+        self
+    }
+
+    /// Set whether this entry is under legal hold.
+    pub fn with_legal_hold(mut self, v: bool) -> Self {
+        self.legal_hold = Some(v);
+        self
+    }
+
+    /// Expiration time of this entry.
+    ///
+    /// Expiration is the time at which the object is going to be removed by
+    /// the service's lifecycle rules, like `x-amz-expiration` in S3.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_expiration`] before using it.
+    ///
+    /// [`Capability::stat_has_expiration`]: crate::Capability::stat_has_expiration
+    pub fn expiration(&self) -> Option<DateTime<Utc>> {
+        self.expiration
+    }
+
+    /// Set expiration time of this entry.
+    pub fn set_expiration(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.expiration = Some(v);
+        self
+    }
+
+    /// Set expiration time of this entry.
+    pub fn with_expiration(mut self, v: DateTime<Utc>) -> Self {
+        self.expiration = Some(v);
+        self
+    }
+
+    /// Server-side encryption algorithm of this entry.
+    ///
+    /// The algorithm is returned as is by the service, like `AES256` or
+    /// `aws:kms` in S3. `None` means the entry is not encrypted by the
+    /// service or the service doesn't report it.
+    ///
+    /// This value is only available when the service supports it, check
+    /// [`Capability::stat_has_server_side_encryption`] before using it.
+    ///
+    /// [`Capability::stat_has_server_side_encryption`]: crate::Capability::stat_has_server_side_encryption
+    pub fn server_side_encryption(&self) -> Option<&str> {
+        self.server_side_encryption.as_deref()
+    }
+
+    /// Set server-side encryption algorithm of this entry.
+    pub fn set_server_side_encryption(&mut self, v: &str) -> &mut Self {
+        self.server_side_encryption = Some(v.to_string());
+        self
+    }
+
+    /// Set server-side encryption algorithm of this entry.
+    pub fn with_server_side_encryption(mut self, v: String) -> Self {
+        self.server_side_encryption = Some(v);
+        self
+    }
+
+    /// The id of the key used to encrypt this entry on the server side.
+    ///
+    /// It's the KMS key id in S3, the KMS key name in GCS, or the encryption
+    /// scope in azblob. For customer-provided keys, it's the SHA256 of the key
+    /// if the service returns it.
+    pub fn server_side_encryption_key_id(&self) -> Option<&str> {
+        self.server_side_encryption_key_id.as_deref()
+    }
+
+    /// Set the id of the key used to encrypt this entry on the server side.
+    pub fn set_server_side_encryption_key_id(&mut self, v: &str) -> &mut Self {
+        self.server_side_encryption_key_id = Some(v.to_string());
+        self
+    }
+
+    /// Set the id of the key used to encrypt this entry on the server side.
+    pub fn with_server_side_encryption_key_id(mut self, v: String) -> Self {
+        self.server_side_encryption_key_id = Some(v);
+        self
+    }
+
     /// ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -277,6 +491,16 @@ impl Metadata {
         self.etag.as_deref()
     }
 
+    /// Parsed ETag of this entry.
+    ///
+    /// Unlike [`Metadata::etag`], the quotes and the weak indicator are
+    /// normalized, so etags returned in different forms by services can be
+    /// compared reliably. Keep using [`Metadata::etag`] for conditional
+    /// requests like `if_match` which expect the value AS-IS.
+    pub fn etag_parsed(&self) -> Option<Etag> {
+        self.etag.as_deref().map(Etag::parse)
+    }
+
     /// Set ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -391,6 +615,53 @@ impl Metadata {
         self
     }
 
+    /// Returns `true` if this entry is a delete marker.
+    ///
+    /// Delete markers are placeholders of deleted objects in versioned
+    /// buckets like AWS S3, which are only returned while listing with
+    /// versions. They carry a version but no content.
+    ///
+    /// Check [`Capability::list_has_delete_marker`] before using it.
+    ///
+    /// [`Capability::list_has_delete_marker`]: crate::Capability::list_has_delete_marker
+    pub fn is_delete_marker(&self) -> bool {
+        self.is_delete_marker
+    }
+
+    /// Set whether this entry is a delete marker.
+    pub fn set_is_delete_marker(&mut self, v: bool) -> &mut Self {
+        self.is_delete_marker = v;
+        self
+    }
+
+    /// Set whether this entry is a delete marker.
+    pub fn with_is_delete_marker(mut self, v: bool) -> Self {
+        self.is_delete_marker = v;
+        self
+    }
+
+    /// File id of this entry.
+    ///
+    /// File id is the identity of the underlying file, like the `dev:ino` on
+    /// unix fs or the file id in gdrive. Entries with the same file id refer
+    /// to the same file even if their paths are different, which is used to
+    /// detect cycles during recursive list.
+    pub fn file_id(&self) -> Option<&str> {
+        self.file_id.as_deref()
+    }
+
+    /// Set file id of this entry.
+    pub fn with_file_id(mut self, v: String) -> Self {
+        self.file_id = Some(v);
+        self
+    }
+
+    /// Set file id of this entry.
+    pub fn set_file_id(&mut self, v: &str) -> &mut Self {
+        self.file_id = Some(v.to_string());
+        self
+    }
+
     /// User defined metadata of this entry
     ///
     /// The prefix of the user defined metadata key(for example: in oss, it's x-oss-meta-)
@@ -404,4 +675,31 @@ impl Metadata {
         self.user_metadata = Some(data);
         self
     }
+
+    /// Raw response headers of the request that produced this metadata.
+    ///
+    /// It's an escape hatch to access provider-specific headers that OpenDAL
+    /// hasn't modeled yet, don't depend on it for headers that have accessors.
+    ///
+    /// This value is only available with the `raw-headers` feature enabled,
+    /// for services that parse metadata from HTTP response headers. Entries
+    /// returned by `list` don't carry it.
+    #[cfg(feature = "raw-headers")]
+    pub fn raw_headers(&self) -> Option<&http::HeaderMap> {
+        self.raw_headers.as_ref()
+    }
+
+    /// Set raw response headers of this entry.
+    #[cfg(feature = "raw-headers")]
+    pub fn set_raw_headers(&mut self, v: http::HeaderMap) -> &mut Self {
+        self.raw_headers = Some(v);
+        self
+    }
+
+    /// Set raw response headers of this entry.
+    #[cfg(feature = "raw-headers")]
+    pub fn with_raw_headers(mut self, v: http::HeaderMap) -> Self {
+        self.raw_headers = Some(v);
+        self
+    }
 }
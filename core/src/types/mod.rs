@@ -27,6 +27,18 @@ pub use entry::Entry;
 mod metadata;
 pub use metadata::Metadata;
 
+mod encryption;
+pub use encryption::CustomerEncryptionKey;
+
+mod etag;
+pub use etag::Etag;
+
+mod object_lock;
+pub use object_lock::ObjectLockMode;
+
+mod multipart_upload;
+pub use multipart_upload::MultipartUpload;
+
 mod read;
 pub use read::*;
 
@@ -41,6 +53,11 @@ pub use blocking_write::*;
 
 mod list;
 pub use list::BlockingLister;
+pub use list::ListCyclePolicy;
+pub use list::ListErrorPolicy;
+pub use list::ListErrorReport;
+pub use list::ListPage;
+pub use list::ListStats;
 pub use list::Lister;
 
 mod execute;
@@ -49,14 +66,24 @@ pub use execute::*;
 mod operator;
 pub use operator::operator_functions;
 pub use operator::operator_futures;
+pub use operator::Batch;
+pub use operator::BatchAction;
+pub use operator::BatchItemReport;
+pub use operator::BatchReport;
 pub use operator::BlockingOperator;
 pub use operator::Operator;
 pub use operator::OperatorBuilder;
 pub use operator::OperatorInfo;
+pub use operator::OperatorPool;
+pub use operator::PathPolicy;
+#[cfg(feature = "path-unicode-normalization")]
+pub use operator::UnicodeNormalization;
 
 mod builder;
 pub use builder::Builder;
 pub use builder::Configurator;
+#[cfg(feature = "extensions")]
+pub use builder::ServiceExtension;
 
 mod error;
 pub use error::Error;
@@ -0,0 +1,66 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use chrono::prelude::*;
+
+/// In-progress multipart upload returned by [`Operator::list_multipart_uploads`].
+///
+/// Multipart uploads that are neither completed nor aborted will keep their
+/// uploaded parts in storage, users can reap them by
+/// [`Operator::abort_multipart_uploads_older_than`].
+///
+/// [`Operator::list_multipart_uploads`]: crate::Operator::list_multipart_uploads
+/// [`Operator::abort_multipart_uploads_older_than`]: crate::Operator::abort_multipart_uploads_older_than
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultipartUpload {
+    path: String,
+    upload_id: String,
+    initiated: Option<DateTime<Utc>>,
+}
+
+impl MultipartUpload {
+    /// Create a new multipart upload.
+    pub fn new(path: &str, upload_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            initiated: None,
+        }
+    }
+
+    /// Set the initiated time of this upload.
+    pub fn with_initiated(mut self, initiated: DateTime<Utc>) -> Self {
+        self.initiated = Some(initiated);
+        self
+    }
+
+    /// Path of this upload. Path is relative to operator's root.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Upload id of this upload.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Initiated time of this upload.
+    ///
+    /// `None` means the service doesn't return it.
+    pub fn initiated(&self) -> Option<DateTime<Utc>> {
+        self.initiated
+    }
+}
@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use crate::*;
+
+/// The retention mode of an object under object lock, like
+/// `x-amz-object-lock-mode` in S3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ObjectLockMode {
+    /// Users with special permissions can overwrite or delete the object
+    /// before the retention period expires.
+    Governance,
+    /// No one can overwrite or delete the object before the retention
+    /// period expires.
+    Compliance,
+}
+
+impl ObjectLockMode {
+    /// Get the string representation of this mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectLockMode::Governance => "GOVERNANCE",
+            ObjectLockMode::Compliance => "COMPLIANCE",
+        }
+    }
+}
+
+impl Display for ObjectLockMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ObjectLockMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("GOVERNANCE") {
+            Ok(ObjectLockMode::Governance)
+        } else if s.eq_ignore_ascii_case("COMPLIANCE") {
+            Ok(ObjectLockMode::Compliance)
+        } else {
+            Err(
+                Error::new(ErrorKind::Unexpected, "object lock mode is invalid")
+                    .with_context("mode", s),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_lock_mode() {
+        for mode in [ObjectLockMode::Governance, ObjectLockMode::Compliance] {
+            assert_eq!(mode.to_string().parse::<ObjectLockMode>().unwrap(), mode);
+        }
+        assert_eq!(
+            "compliance".parse::<ObjectLockMode>().unwrap(),
+            ObjectLockMode::Compliance
+        );
+        assert!("none".parse::<ObjectLockMode>().is_err());
+    }
+}
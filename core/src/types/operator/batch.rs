@@ -0,0 +1,376 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use futures::stream;
+use futures::StreamExt;
+
+use crate::*;
+
+/// The action of an item in [`Batch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BatchAction {
+    /// Write the whole content into `path`.
+    Write,
+    /// Delete `path`.
+    Delete,
+    /// Copy `path` to `target`.
+    Copy {
+        /// The target path of the copy.
+        target: String,
+    },
+}
+
+enum BatchItem {
+    Write(String, Buffer),
+    Delete(String),
+    Copy(String, String),
+}
+
+/// Batch collects writes, deletes and copies, and executes all of them with
+/// bounded concurrency.
+///
+/// # Notes
+///
+/// - Batch is not atomic and there is no all-or-nothing mode: items that
+///   succeeded are never rolled back. The result of each item is returned in
+///   a [`BatchReport`].
+/// - Every item is executed even if some of them failed, unless
+///   [`Batch::fail_fast`] is enabled.
+/// - Items are started in the order they are added, but they are not ordered
+///   if [`Batch::concurrent`] is larger than `1`. Don't add items that depend
+///   on each other, like writing a file and then copying it, to a concurrent
+///   batch.
+///
+/// Create it via [`Operator::batch`].
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::Operator;
+/// # use opendal::Result;
+/// # async fn test(op: Operator) -> Result<()> {
+/// let report = op
+///     .batch()
+///     .write("a.txt", "hello")
+///     .write("b.txt", "world")
+///     .copy("c.txt", "d.txt")
+///     .delete("e.txt")
+///     .concurrent(4)
+///     .execute()
+///     .await;
+///
+/// for item in report.items() {
+///     if let Some(err) = item.error() {
+///         println!("{} failed: {err}, retryable: {}", item.path(), item.is_retryable());
+///     }
+/// }
+/// report.into_result()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Batch {
+    op: Operator,
+    items: Vec<BatchItem>,
+    concurrent: usize,
+    fail_fast: bool,
+}
+
+impl Batch {
+    pub(super) fn new(op: Operator) -> Self {
+        Self {
+            op,
+            items: Vec::new(),
+            concurrent: 1,
+            fail_fast: false,
+        }
+    }
+
+    /// Write `bs` as the whole content of `path`.
+    pub fn write(mut self, path: &str, bs: impl Into<Buffer>) -> Self {
+        self.items
+            .push(BatchItem::Write(path.to_string(), bs.into()));
+        self
+    }
+
+    /// Delete `path`.
+    pub fn delete(mut self, path: &str) -> Self {
+        self.items.push(BatchItem::Delete(path.to_string()));
+        self
+    }
+
+    /// Copy `from` to `to`.
+    pub fn copy(mut self, from: &str, to: &str) -> Self {
+        self.items
+            .push(BatchItem::Copy(from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Set the max number of items executed at the same time.
+    ///
+    /// Items are not ordered if `concurrent` is larger than `1`.
+    ///
+    /// Default to `1`.
+    pub fn concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent.max(1);
+        self
+    }
+
+    /// Stop starting new items once an item failed, the items that are not
+    /// started are reported as skipped.
+    ///
+    /// Items that are running while the failure happens still finish.
+    ///
+    /// Default to `false`.
+    pub fn fail_fast(mut self, enabled: bool) -> Self {
+        self.fail_fast = enabled;
+        self
+    }
+
+    /// Execute all items, returns the report with results in the same
+    /// order as items are added.
+    pub async fn execute(self) -> BatchReport {
+        let op = &self.op;
+        let fail_fast = self.fail_fast;
+        let failed = &AtomicBool::new(false);
+        let items = stream::iter(self.items)
+            .map(|item| async move {
+                if fail_fast && failed.load(Ordering::Relaxed) {
+                    return BatchItemReport::skipped(item);
+                }
+
+                let report = match item {
+                    BatchItem::Write(path, bs) => {
+                        let result = op.write(&path, bs).await;
+                        BatchItemReport::new(BatchAction::Write, path, result)
+                    }
+                    BatchItem::Delete(path) => {
+                        let result = op.delete(&path).await;
+                        BatchItemReport::new(BatchAction::Delete, path, result)
+                    }
+                    BatchItem::Copy(from, to) => {
+                        let result = op.copy(&from, &to).await;
+                        BatchItemReport::new(BatchAction::Copy { target: to }, from, result)
+                    }
+                };
+                if !report.is_ok() {
+                    failed.store(true, Ordering::Relaxed);
+                }
+                report
+            })
+            .buffered(self.concurrent)
+            .collect()
+            .await;
+
+        BatchReport { items }
+    }
+}
+
+/// The result of an item executed by [`Batch`].
+#[derive(Debug)]
+pub struct BatchItemReport {
+    action: BatchAction,
+    path: String,
+    error: Option<Error>,
+    skipped: bool,
+}
+
+impl BatchItemReport {
+    fn new(action: BatchAction, path: String, result: Result<()>) -> Self {
+        Self {
+            action,
+            path,
+            error: result.err(),
+            skipped: false,
+        }
+    }
+
+    fn skipped(item: BatchItem) -> Self {
+        let (action, path) = match item {
+            BatchItem::Write(path, _) => (BatchAction::Write, path),
+            BatchItem::Delete(path) => (BatchAction::Delete, path),
+            BatchItem::Copy(from, to) => (BatchAction::Copy { target: to }, from),
+        };
+        Self {
+            action,
+            path,
+            error: None,
+            skipped: true,
+        }
+    }
+
+    /// Get the action of this item.
+    pub fn action(&self) -> &BatchAction {
+        &self.action
+    }
+
+    /// Get the path of this item, it's the source path for copies.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Check whether this item succeeded.
+    pub fn is_ok(&self) -> bool {
+        !self.skipped && self.error.is_none()
+    }
+
+    /// Check whether this item is not executed since a previous item failed
+    /// with [`Batch::fail_fast`] enabled.
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    /// Get the error of this item if failed.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    /// Get the error kind of this item if failed.
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        self.error.as_ref().map(|err| err.kind())
+    }
+
+    /// Check whether this item failed with a temporary error that could be
+    /// retried.
+    pub fn is_retryable(&self) -> bool {
+        self.error.as_ref().is_some_and(|err| err.is_temporary())
+    }
+}
+
+/// BatchReport is returned by [`Batch::execute`] with the result of every
+/// item.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    items: Vec<BatchItemReport>,
+}
+
+impl BatchReport {
+    /// Get the results of items in the same order as they are added.
+    pub fn items(&self) -> &[BatchItemReport] {
+        &self.items
+    }
+
+    /// Convert into the results of items.
+    pub fn into_items(self) -> Vec<BatchItemReport> {
+        self.items
+    }
+
+    /// Get the number of items that succeeded.
+    pub fn succeeded(&self) -> usize {
+        self.items.iter().filter(|v| v.is_ok()).count()
+    }
+
+    /// Get the number of items that failed.
+    pub fn failed(&self) -> usize {
+        self.items.iter().filter(|v| v.error.is_some()).count()
+    }
+
+    /// Get the number of items that are skipped by [`Batch::fail_fast`].
+    pub fn skipped(&self) -> usize {
+        self.items.iter().filter(|v| v.is_skipped()).count()
+    }
+
+    /// Get the number of items that failed with retryable errors.
+    pub fn retryable(&self) -> usize {
+        self.items.iter().filter(|v| v.is_retryable()).count()
+    }
+
+    /// Check whether all items succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.succeeded() == self.items.len()
+    }
+
+    /// Returns the error of the first failed item if any.
+    pub fn into_result(self) -> Result<()> {
+        let failed = self.failed();
+        match self.items.into_iter().find_map(|v| v.error) {
+            Some(err) => Err(err
+                .with_operation("Batch::execute")
+                .with_context("failed", failed.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_batch() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("c.txt", "world").await?;
+
+        let report = op
+            .batch()
+            .write("a.txt", "hello")
+            .copy("a.txt", "b.txt")
+            .copy("not_exist.txt", "d.txt")
+            .delete("c.txt")
+            .execute()
+            .await;
+
+        assert_eq!(report.items().len(), 4);
+        assert_eq!(report.succeeded(), 3);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.retryable(), 0);
+
+        let item = &report.items()[2];
+        assert_eq!(
+            item.action(),
+            &BatchAction::Copy {
+                target: "d.txt".to_string()
+            }
+        );
+        assert_eq!(item.path(), "not_exist.txt");
+        assert_eq!(item.error_kind(), Some(ErrorKind::NotFound));
+
+        assert_eq!(op.read("b.txt").await?.to_vec(), b"hello");
+        assert!(!op.exists("c.txt").await?);
+        assert_eq!(
+            report.into_result().unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_batch_fail_fast() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let report = op
+            .batch()
+            .write("a.txt", "hello")
+            .copy("not_exist.txt", "b.txt")
+            .write("c.txt", "world")
+            .fail_fast(true)
+            .execute()
+            .await;
+
+        assert_eq!(report.succeeded(), 1);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.skipped(), 1);
+        assert!(report.items()[2].is_skipped());
+        assert!(!report.is_ok());
+        assert!(!op.exists("c.txt").await?);
+        Ok(())
+    }
+}
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::ops::Bound;
+
 use super::operator_functions::*;
 use crate::raw::*;
 use crate::*;
@@ -76,6 +78,7 @@ pub struct BlockingOperator {
     accessor: Accessor,
 
     limit: usize,
+    path_policy: PathPolicy,
 }
 
 impl BlockingOperator {
@@ -93,7 +96,11 @@ impl BlockingOperator {
             .full_capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            path_policy: PathPolicy::default(),
+        }
     }
 
     /// Get current operator's limit
@@ -110,6 +117,20 @@ impl BlockingOperator {
         op
     }
 
+    /// Get the path policy.
+    pub fn path_policy(&self) -> &PathPolicy {
+        &self.path_policy
+    }
+
+    /// Specify the path policy used to normalize input paths.
+    ///
+    /// Default: [`PathPolicy::default`]
+    pub fn with_path_policy(&self, policy: PathPolicy) -> Self {
+        let mut op = self.clone();
+        op.path_policy = policy;
+        op
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -239,7 +260,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn stat_with(&self, path: &str) -> FunctionStat {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionStat(OperatorFunction::new(
             self.inner().clone(),
@@ -268,13 +289,62 @@ impl BlockingOperator {
     /// }
     /// ```
     pub fn exists(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path);
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(false),
-                _ => Err(err),
+        self.exists_with(path).call()
+    }
+
+    /// Check if this path exists or not with extra options.
+    ///
+    /// Returns `false` if the path doesn't exist or the given conditions
+    /// (like `if_match` and `version`) are not matched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    /// fn test(op: BlockingOperator, etag: &str) -> Result<()> {
+    ///     let _ = op.exists_with("test").if_match(etag).call()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn exists_with(&self, path: &str) -> FunctionExists {
+        let path = self.path_policy.normalize(path);
+
+        FunctionExists(OperatorFunction::new(
+            self.inner().clone(),
+            path,
+            OpStat::default(),
+            |inner, path, args| match inner.blocking_stat(&path, args) {
+                Ok(_) => Ok(true),
+                Err(err) => match err.kind() {
+                    ErrorKind::NotFound | ErrorKind::ConditionNotMatch => Ok(false),
+                    _ => Err(err),
+                },
             },
+        ))
+    }
+
+    /// Get given path's metadata, return `None` if it doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    /// fn test(op: BlockingOperator) -> Result<()> {
+    ///     if let Some(meta) = op.stat_or_none("test")? {
+    ///         println!("content length: {}", meta.content_length());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stat_or_none(&self, path: &str) -> Result<Option<Metadata>> {
+        match self.stat(path) {
+            Ok(meta) => Ok(Some(meta)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
         }
     }
 
@@ -321,7 +391,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -376,12 +446,12 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn read_with(&self, path: &str) -> FunctionRead {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionRead(OperatorFunction::new(
             self.inner().clone(),
             path,
-            (OpRead::default(), BytesRange::default()),
+            (OpRead::default(), (Bound::Unbounded, Bound::Unbounded)),
             |inner, path, (args, range)| {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
@@ -394,7 +464,7 @@ impl BlockingOperator {
 
                 let context = ReadContext::new(inner, path, args, OpReader::default());
                 let r = BlockingReader::new(context);
-                let buf = r.read(range.to_range())?;
+                let buf = r.read(range)?;
                 Ok(buf)
             },
         ))
@@ -434,7 +504,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn reader_with(&self, path: &str) -> FunctionReader {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionReader(OperatorFunction::new(
             self.inner().clone(),
@@ -501,7 +571,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.path_policy.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -512,7 +582,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.path_policy.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -558,7 +628,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.path_policy.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -569,7 +639,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.path_policy.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -618,7 +688,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn write_with(&self, path: &str, bs: impl Into<Buffer>) -> FunctionWrite {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         let bs = bs.into();
 
@@ -637,10 +707,7 @@ impl BlockingOperator {
                 }
 
                 let context = WriteContext::new(inner, path, args, options);
-                let mut w = BlockingWriter::new(context)?;
-                w.write(bs)?;
-                w.close()?;
-                Ok(())
+                context.blocking_write_all(bs)
             },
         ))
     }
@@ -689,15 +756,13 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn writer_with(&self, path: &str) -> FunctionWriter {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionWriter(OperatorFunction::new(
             self.inner().clone(),
             path,
             (OpWrite::default(), OpWriter::default()),
             |inner, path, (args, options)| {
-                let path = normalize_path(&path);
-
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
                         Error::new(ErrorKind::IsADirectory, "write path is a directory")
@@ -758,7 +823,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FunctionDelete {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionDelete(OperatorFunction::new(
             self.inner().clone(),
@@ -859,7 +924,7 @@ impl BlockingOperator {
             match v {
                 Ok(entry) => {
                     self.inner()
-                        .blocking_delete(entry.path(), OpDelete::new())?;
+                        .blocking_delete(&self.path_policy.encode(entry.path()), OpDelete::new())?;
                 }
                 Err(e) => return Err(e),
             }
@@ -953,14 +1018,14 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn list_with(&self, path: &str) -> FunctionList {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionList(OperatorFunction::new(
             self.inner().clone(),
             path,
-            OpList::default(),
-            |inner, path, args| {
-                let lister = BlockingLister::create(inner, &path, args)?;
+            (OpList::default(), self.path_policy.clone()),
+            |inner, path, (args, policy)| {
+                let lister = BlockingLister::create(inner, &path, args, policy)?;
 
                 lister.collect()
             },
@@ -1073,19 +1138,74 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn lister_with(&self, path: &str) -> FunctionLister {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         FunctionLister(OperatorFunction::new(
             self.inner().clone(),
             path,
-            OpList::default(),
-            |inner, path, args| BlockingLister::create(inner, &path, args),
+            (OpList::default(), self.path_policy.clone()),
+            |inner, path, (args, policy)| BlockingLister::create(inner, &path, args, policy),
         ))
     }
+
+    /// List a page of at most `limit` entries under given `path`.
+    ///
+    /// Pass the [`ListPage::next_token`] of the returned page as `token` to
+    /// fetch the next page, `None` will be returned as the token of the
+    /// last page.
+    ///
+    /// # Notes
+    ///
+    /// The token is the path of the last entry in the page, which is passed
+    /// as `start_after` to fetch the next page instead of the native
+    /// continuation token of services. So this function requires
+    /// [`Capability::list_with_start_after`], [`ErrorKind::Unsupported`]
+    /// will be returned otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let mut token = None;
+    /// loop {
+    ///     let page = op.list_page("path/to/dir/", 100, token.as_deref(), false)?;
+    ///     for entry in page.entries() {
+    ///         println!("{}", entry.path());
+    ///     }
+    ///     match page.next_token() {
+    ///         Some(v) => token = Some(v.to_string()),
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_page(
+        &self,
+        path: &str,
+        limit: usize,
+        token: Option<&str>,
+        recursive: bool,
+    ) -> Result<ListPage> {
+        ListPage::check(&self.info(), path)?;
+
+        let mut l = self.lister_with(path).limit(limit).recursive(recursive);
+        if let Some(token) = token {
+            l = l.start_after(token);
+        }
+        let entries = l.call()?.take(limit).collect::<Result<Vec<_>>>()?;
+
+        Ok(ListPage::new(entries, limit))
+    }
 }
 
 impl From<BlockingOperator> for Operator {
     fn from(v: BlockingOperator) -> Self {
-        Operator::from_inner(v.accessor).with_limit(v.limit)
+        Operator::from_inner(v.accessor)
+            .with_limit(v.limit)
+            .with_path_policy(v.path_policy)
     }
 }
@@ -90,9 +90,10 @@ impl Operator {
     /// }
     /// ```
     pub fn from_config<C: Configurator>(cfg: C) -> Result<OperatorBuilder<impl Access>> {
+        let layer = ConfigInfoLayer::new(&cfg);
         let builder = cfg.into_builder();
         let acc = builder.build()?;
-        Ok(OperatorBuilder::new(acc))
+        Ok(OperatorBuilder::new(layer.layer(acc)))
     }
 
     /// Create a new operator from given iterator in static dispatch.
@@ -127,9 +128,8 @@ impl Operator {
     pub fn from_iter<B: Builder>(
         iter: impl IntoIterator<Item = (String, String)>,
     ) -> Result<OperatorBuilder<impl Access>> {
-        let builder = B::Config::from_iter(iter)?.into_builder();
-        let acc = builder.build()?;
-        Ok(OperatorBuilder::new(acc))
+        let cfg = B::Config::from_iter(iter)?;
+        Self::from_config(cfg)
     }
 
     /// Create a new operator via given scheme and iterator of config value in dynamic dispatch.
@@ -182,6 +182,8 @@ impl Operator {
             Scheme::YandexDisk => Self::from_iter::<services::YandexDisk>(iter)?.finish(),
             #[cfg(feature = "services-pcloud")]
             Scheme::Pcloud => Self::from_iter::<services::Pcloud>(iter)?.finish(),
+            #[cfg(feature = "services-proton-drive")]
+            Scheme::ProtonDrive => Self::from_iter::<services::ProtonDrive>(iter)?.finish(),
             #[cfg(feature = "services-chainsafe")]
             Scheme::Chainsafe => Self::from_iter::<services::Chainsafe>(iter)?.finish(),
             #[cfg(feature = "services-azblob")]
@@ -192,6 +194,8 @@ impl Operator {
             Scheme::Azfile => Self::from_iter::<services::Azfile>(iter)?.finish(),
             #[cfg(feature = "services-b2")]
             Scheme::B2 => Self::from_iter::<services::B2>(iter)?.finish(),
+            #[cfg(feature = "services-box")]
+            Scheme::Box => Self::from_iter::<services::Box>(iter)?.finish(),
             #[cfg(feature = "services-cacache")]
             Scheme::Cacache => Self::from_iter::<services::Cacache>(iter)?.finish(),
             #[cfg(feature = "services-cos")]
@@ -408,11 +412,18 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// The path policy set by [`Operator::with_path_policy`] and the default
+    /// read options set by [`Operator::with_default_read_options`] will be kept.
     #[must_use]
     pub fn layer<L: Layer<Accessor>>(self, layer: L) -> Self {
+        let path_policy = self.path_policy().clone();
+        let default_read_options = self.default_read_options().clone();
         Self::from_inner(Arc::new(
             TypeEraseLayer.layer(layer.layer(self.into_inner())),
         ))
+        .with_path_policy(path_policy)
+        .with_default_read_options(default_read_options)
     }
 }
 
@@ -462,6 +473,7 @@ impl Operator {
 /// ```
 pub struct OperatorBuilder<A: Access> {
     accessor: A,
+    default_read_options: Option<OpReader>,
 }
 
 impl<A: Access> OperatorBuilder<A> {
@@ -469,9 +481,12 @@ impl<A: Access> OperatorBuilder<A> {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(accessor: A) -> OperatorBuilder<impl Access> {
         // Make sure error context layer has been attached.
-        OperatorBuilder { accessor }
-            .layer(ErrorContextLayer)
-            .layer(CompleteLayer)
+        OperatorBuilder {
+            accessor,
+            default_read_options: None,
+        }
+        .layer(ErrorContextLayer)
+        .layer(CompleteLayer)
     }
 
     /// Create a new layer with static dispatch.
@@ -507,12 +522,79 @@ impl<A: Access> OperatorBuilder<A> {
     pub fn layer<L: Layer<A>>(self, layer: L) -> OperatorBuilder<L::LayeredAccess> {
         OperatorBuilder {
             accessor: layer.layer(self.accessor),
+            default_read_options: self.default_read_options,
         }
     }
 
+    /// Specify the default read options of the built operator.
+    ///
+    /// See [`Operator::with_default_read_options`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::raw::OpReader;
+    /// use opendal::services::Memory;
+    /// use opendal::Operator;
+    ///
+    /// # fn test() -> Result<()> {
+    /// let op = Operator::new(Memory::default())?
+    ///     .with_default_read_options(OpReader::new().with_concurrent(4))
+    ///     .finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_default_read_options(mut self, options: OpReader) -> Self {
+        self.default_read_options = Some(options);
+        self
+    }
+
     /// Finish the building to construct an Operator.
     pub fn finish(self) -> Operator {
         let ob = self.layer(TypeEraseLayer);
-        Operator::from_inner(Arc::new(ob.accessor) as Accessor)
+        let op = Operator::from_inner(Arc::new(ob.accessor) as Accessor);
+        match ob.default_read_options {
+            Some(options) => op.with_default_read_options(options),
+            None => op,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::LoggingLayer;
+    use crate::services;
+
+    #[test]
+    fn test_layer_keeps_path_policy() {
+        let policy = PathPolicy::new().with_backslash_as_separator(true);
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .with_path_policy(policy.clone())
+            .layer(LoggingLayer::default());
+
+        assert_eq!(op.path_policy(), &policy);
+    }
+
+    #[tokio::test]
+    async fn test_layer_keeps_default_read_options() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .with_default_read_options(OpReader::new().with_chunk(2).with_concurrent(4))
+            .layer(LoggingLayer::default());
+
+        assert_eq!(op.default_read_options().chunk(), Some(2));
+        assert_eq!(op.default_read_options().concurrent(), 4);
+
+        op.write("file", "abcdefgh")
+            .await
+            .expect("write must succeed");
+        let bs = op.read("file").await.expect("read must succeed");
+        assert_eq!(bs.to_vec(), b"abcdefgh");
     }
 }
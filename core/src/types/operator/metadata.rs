@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::raw::*;
@@ -58,4 +59,31 @@ impl OperatorInfo {
     pub fn native_capability(&self) -> Capability {
         self.0.native_capability()
     }
+
+    /// Get the effective configuration of operator with secrets like
+    /// passwords and keys replaced by `<redacted>`, which is safe to log.
+    ///
+    /// Unset options are not included and `root` is the normalized root.
+    ///
+    /// Returns `None` if the operator is built from a builder via
+    /// [`Operator::new`], use [`Operator::from_config`] or
+    /// [`Operator::via_iter`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::services::MemoryConfig;
+    /// use opendal::Operator;
+    ///
+    /// # fn test() -> Result<()> {
+    /// let op = Operator::from_config(MemoryConfig::default())?.finish();
+    /// let config = op.info().config_redacted().cloned().unwrap_or_default();
+    /// assert_eq!(config["root"], "/");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn config_redacted(&self) -> Option<&BTreeMap<String, String>> {
+        self.0.config()
+    }
 }
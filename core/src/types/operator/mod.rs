@@ -30,5 +30,19 @@ pub use builder::OperatorBuilder;
 mod metadata;
 pub use metadata::OperatorInfo;
 
+mod pool;
+pub use pool::OperatorPool;
+
+mod batch;
+pub use batch::Batch;
+pub use batch::BatchAction;
+pub use batch::BatchItemReport;
+pub use batch::BatchReport;
+
+mod path_policy;
+pub use path_policy::PathPolicy;
+#[cfg(feature = "path-unicode-normalization")]
+pub use path_policy::UnicodeNormalization;
+
 pub mod operator_functions;
 pub mod operator_futures;
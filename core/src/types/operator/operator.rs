@@ -16,6 +16,7 @@
 // under the License.
 
 use std::future::Future;
+use std::ops::Bound;
 use std::time::Duration;
 
 use futures::stream;
@@ -28,6 +29,9 @@ use crate::operator_futures::*;
 use crate::raw::*;
 use crate::*;
 
+/// The user metadata key to store the idempotency key of writes.
+const IDEMPOTENCY_KEY_METADATA: &str = "opendal-idempotency-key";
+
 /// Operator is the entry for all public async APIs.
 ///
 /// Developer should manipulate the data from storage service through Operator only by right.
@@ -65,6 +69,10 @@ pub struct Operator {
     limit: usize,
     /// The default executor that used to run futures in background.
     default_executor: Option<Executor>,
+    /// The policy used to normalize input paths.
+    path_policy: PathPolicy,
+    /// The default options used by `read` and `reader`.
+    default_read_options: OpReader,
 }
 
 /// # Operator basic API.
@@ -85,6 +93,8 @@ impl Operator {
             accessor,
             limit,
             default_executor: None,
+            path_policy: PathPolicy::default(),
+            default_read_options: OpReader::default(),
         }
     }
 
@@ -120,6 +130,49 @@ impl Operator {
         op
     }
 
+    /// Get the path policy.
+    pub fn path_policy(&self) -> &PathPolicy {
+        &self.path_policy
+    }
+
+    /// Specify the path policy used to normalize input paths.
+    ///
+    /// Default: [`PathPolicy::default`]
+    pub fn with_path_policy(&self, policy: PathPolicy) -> Self {
+        let mut op = self.clone();
+        op.path_policy = policy;
+        op
+    }
+
+    /// Get the default read options.
+    pub fn default_read_options(&self) -> &OpReader {
+        &self.default_read_options
+    }
+
+    /// Specify the default options like `chunk`, `concurrent` and `gap` used by
+    /// [`Operator::read_with`] and [`Operator::reader_with`].
+    ///
+    /// Options set at the call site will override the defaults. Blocking reads are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::raw::OpReader;
+    /// # use opendal::Operator;
+    /// # fn test(op: Operator) {
+    /// let op = op.with_default_read_options(
+    ///     OpReader::new()
+    ///         .with_chunk(8 * 1024 * 1024)
+    ///         .with_concurrent(4),
+    /// );
+    /// # }
+    /// ```
+    pub fn with_default_read_options(&self, options: OpReader) -> Self {
+        let mut op = self.clone();
+        op.default_read_options = options;
+        op
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -138,11 +191,50 @@ impl Operator {
         OperatorInfo::new(self.accessor.info())
     }
 
+    /// Get the provider-specific extension of underlying service.
+    ///
+    /// Returns `None` if the operator is not built from service `B`.
+    ///
+    /// # Notes
+    ///
+    /// Extensions talk to the service directly, all layers applied on this
+    /// operator like retry and logging will be bypassed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::services::S3;
+    /// use opendal::services::S3SelectInput;
+    /// use opendal::services::S3SelectOutput;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// if let Some(s3) = op.as_service::<S3>() {
+    ///     let bs = s3
+    ///         .select_object_content(
+    ///             "data.csv",
+    ///             "SELECT * FROM S3Object s WHERE s.age > 18",
+    ///             S3SelectInput::Csv { has_header: true },
+    ///             S3SelectOutput::Json,
+    ///         )
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "extensions")]
+    pub fn as_service<B: ServiceExtension>(&self) -> Option<B::Extension> {
+        self.accessor.info().extension::<B::Extension>().cloned()
+    }
+
     /// Create a new blocking operator.
     ///
     /// This operation is nearly no cost.
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        BlockingOperator::from_inner(self.accessor.clone())
+            .with_limit(self.limit)
+            .with_path_policy(self.path_policy.clone())
     }
 }
 
@@ -303,7 +395,7 @@ impl Operator {
     /// when `test/abc` exists since the service won't have the concept of dir. There is nothing
     /// we can do about this.
     pub fn stat_with(&self, path: &str) -> FutureStat<impl Future<Output = Result<Metadata>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -332,13 +424,66 @@ impl Operator {
     /// }
     /// ```
     pub async fn exists(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path).await;
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(false),
-                _ => Err(err),
+        self.exists_with(path).await
+    }
+
+    /// Check if this path exists or not with extra options.
+    ///
+    /// Returns `false` if the path doesn't exist or the given conditions
+    /// (like `if_match` and `version`) are not matched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator, etag: &str) -> Result<()> {
+    ///     let _ = op.exists_with("test").if_match(etag).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn exists_with(&self, path: &str) -> FutureExists<impl Future<Output = Result<bool>>> {
+        let path = self.path_policy.normalize(path);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            OpStat::default(),
+            |inner, path, args| async move {
+                match inner.stat(&path, args).await {
+                    Ok(_) => Ok(true),
+                    Err(err) => match err.kind() {
+                        ErrorKind::NotFound | ErrorKind::ConditionNotMatch => Ok(false),
+                        _ => Err(err),
+                    },
+                }
             },
+        )
+    }
+
+    /// Get given path's metadata, return `None` if it doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     if let Some(meta) = op.stat_or_none("test").await? {
+    ///         println!("content length: {}", meta.content_length());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stat_or_none(&self, path: &str) -> Result<Option<Metadata>> {
+        match self.stat(path).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
         }
     }
 
@@ -393,7 +538,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -562,16 +707,17 @@ impl Operator {
     /// # }
     /// ```
     pub fn read_with(&self, path: &str) -> FutureRead<impl Future<Output = Result<Buffer>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
             path,
             (
                 OpRead::default().merge_executor(self.default_executor.clone()),
-                OpReader::default(),
+                self.default_read_options.clone(),
+                (Bound::Unbounded, Bound::Unbounded),
             ),
-            |inner, path, (args, options)| async move {
+            |inner, path, (args, options, range)| async move {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
                         Error::new(ErrorKind::IsADirectory, "read path is a directory")
@@ -581,7 +727,9 @@ impl Operator {
                     );
                 }
 
-                let range = args.range();
+                let range = BytesRange::try_from_bounds(range)
+                    .map_err(|err| err.with_operation("read").with_context("path", &path))?;
+                let args = args.with_range(range);
                 let context = ReadContext::new(inner, path, args, options);
                 let r = Reader::new(context);
                 let buf = r.read(range.to_range()).await?;
@@ -590,6 +738,55 @@ impl Operator {
         )
     }
 
+    /// Read the whole path and return its metadata in the same call.
+    ///
+    /// The metadata is parsed from the response of the read request if the
+    /// service supports it, which saves a round trip of `stat` in serving
+    /// paths. Otherwise, a `stat` request will be sent.
+    ///
+    /// The content is streamed from the read response without reading into
+    /// memory first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let (meta, stream) = op.read_with_metadata("path/to/file").await?;
+    /// println!("etag: {:?}", meta.etag());
+    /// let bs: Vec<_> = stream.try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_with_metadata(&self, path: &str) -> Result<(Metadata, BodyStream)> {
+        let path = self.path_policy.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("read_with_metadata")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        let args = OpRead::default().merge_executor(self.default_executor.clone());
+        let (rp, r) = self.inner().read(&path, args).await?;
+        let meta = match rp.into_metadata() {
+            Some(meta) => meta,
+            None => self
+                .inner()
+                .stat(&path, OpStat::new())
+                .await?
+                .into_metadata(),
+        };
+
+        Ok((meta, BodyStream::new(r)))
+    }
+
     /// Create a new reader which can read the whole path.
     ///
     /// # Notes
@@ -678,14 +875,14 @@ impl Operator {
     /// # }
     /// ```
     pub fn reader_with(&self, path: &str) -> FutureReader<impl Future<Output = Result<Reader>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
             path,
             (
                 OpRead::default().merge_executor(self.default_executor.clone()),
-                OpReader::default(),
+                self.default_read_options.clone(),
             ),
             |inner, path, (args, options)| async move {
                 if !validate_path(&path, EntryMode::FILE) {
@@ -763,23 +960,132 @@ impl Operator {
     /// # }
     /// ```
     pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let (from, to) = self.normalize_copy_paths(from, to, "Operator::copy")?;
+
+        self.inner().copy(&from, &to, OpCopy::new()).await?;
+
+        Ok(())
+    }
+
+    /// Copy files by given `(from, to)` pairs.
+    ///
+    /// Returns the result of every pair in the same order as input, so that
+    /// one failed pair won't stop the others.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support copy in batch, we will use batch
+    /// copy instead. Otherwise, pairs will be copied concurrently with the
+    /// operator's limit.
+    ///
+    /// Every pair follows the same rules of [`Operator::copy`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let results = op
+    ///     .copy_many(vec![
+    ///         ("path/to/a".to_string(), "backup/a".to_string()),
+    ///         ("path/to/b".to_string(), "backup/b".to_string()),
+    ///     ])
+    ///     .await?;
+    /// for result in results {
+    ///     result?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_many(&self, pairs: Vec<(String, String)>) -> Result<Vec<Result<()>>> {
+        let pairs = pairs
+            .iter()
+            .map(|(from, to)| self.normalize_copy_paths(from, to, "Operator::copy_many"))
+            .collect::<Vec<_>>();
+
+        let capability = self.info().full_capability();
+        if !(capability.batch && capability.batch_copy) {
+            let results = stream::iter(pairs)
+                .map(|pair| async move {
+                    let (from, to) = pair?;
+                    self.inner().copy(&from, &to, OpCopy::new()).await?;
+                    Ok::<(), Error>(())
+                })
+                .buffered(self.limit())
+                .collect()
+                .await;
+            return Ok(results);
+        }
+
+        let mut results: Vec<Result<()>> = Vec::with_capacity(pairs.len());
+        let mut pairs = pairs.into_iter().peekable();
+        while pairs.peek().is_some() {
+            // Invalid pairs are reported directly without sending.
+            let mut batch = Vec::with_capacity(self.limit());
+            let mut indexes = Vec::with_capacity(self.limit());
+            for pair in pairs.by_ref().take(self.limit()) {
+                match pair {
+                    Ok((from, to)) => {
+                        indexes.push(results.len());
+                        batch.push((from, BatchOperation::Copy(to, OpCopy::new())));
+                        results.push(Ok(()));
+                    }
+                    Err(err) => results.push(Err(err)),
+                }
+            }
+            if batch.is_empty() {
+                continue;
+            }
+
+            let replies = self
+                .inner()
+                .batch(OpBatch::new(batch))
+                .await?
+                .into_results();
+            if replies.len() != indexes.len() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch copy returns mismatched number of results",
+                )
+                .with_operation("Operator::copy_many")
+                .with_context("service", self.info().scheme())
+                .with_context("expect", indexes.len().to_string())
+                .with_context("actual", replies.len().to_string()));
+            }
+            for (idx, (_, reply)) in indexes.into_iter().zip(replies) {
+                results[idx] = reply.map(|_| ());
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Normalize and validate `from` and `to` for copy.
+    fn normalize_copy_paths(
+        &self,
+        from: &str,
+        to: &str,
+        op: &'static str,
+    ) -> Result<(String, String)> {
+        let from = self.path_policy.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
+                    .with_operation(op)
                     .with_context("service", self.info().scheme())
                     .with_context("from", from),
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.path_policy.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
+                    .with_operation(op)
                     .with_context("service", self.info().scheme())
                     .with_context("to", to),
             );
@@ -788,16 +1094,14 @@ impl Operator {
         if from == to {
             return Err(
                 Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
+                    .with_operation(op)
                     .with_context("service", self.info().scheme())
                     .with_context("from", from)
                     .with_context("to", to),
             );
         }
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
-
-        Ok(())
+        Ok((from, to))
     }
 
     /// Rename a file from `from` to `to`.
@@ -820,7 +1124,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.path_policy.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -831,7 +1135,7 @@ impl Operator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.path_policy.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -1178,7 +1482,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn writer_with(&self, path: &str) -> FutureWriter<impl Future<Output = Result<Writer>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -1197,6 +1501,16 @@ impl Operator {
                     );
                 }
 
+                if args.idempotency_key().is_some() {
+                    return Err(Error::new(
+                        ErrorKind::Unsupported,
+                        "writer doesn't support idempotency key, please use write_with instead",
+                    )
+                    .with_operation("Operator::writer")
+                    .with_context("service", inner.info().scheme().into_static())
+                    .with_context("path", &path));
+                }
+
                 let context = WriteContext::new(inner, path, args, options);
                 let w = Writer::new(context).await?;
                 Ok(w)
@@ -1419,12 +1733,79 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// ## `custom_time`
+    ///
+    /// Sets a user-specified timestamp on the target file, like `customTime` in GCS.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_custom_time`] before using this feature.
+    ///
+    /// ### Behavior
+    ///
+    /// - If supported, the value can be read back via [`Metadata::custom_time`]
+    /// - Lifecycle rules keyed on custom time will take this value into account
+    /// - If not supported, the value will be ignored
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use chrono::Utc;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = b"hello, world!".to_vec();
+    /// let _ = op
+    ///     .write_with("path/to/file", bs)
+    ///     .custom_time(Utc::now())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## `object_lock` and `legal_hold`
+    ///
+    /// Sets the retention mode, retain-until-date and legal hold on the target file,
+    /// like Object Lock in S3.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_object_lock`] before using this feature.
+    ///
+    /// ### Behavior
+    ///
+    /// - If supported, the values can be read back via [`Metadata::object_lock_mode`],
+    ///   [`Metadata::object_lock_retain_until`] and [`Metadata::legal_hold`]
+    /// - The bucket must have object lock enabled, otherwise the write will fail
+    /// - If not supported, the values will be ignored
+    ///
+    /// ### Example
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use chrono::Duration;
+    /// use chrono::Utc;
+    /// use opendal::ObjectLockMode;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = b"hello, world!".to_vec();
+    /// let _ = op
+    ///     .write_with("path/to/file", bs)
+    ///     .object_lock(ObjectLockMode::Compliance, Utc::now() + Duration::days(30))
+    ///     .legal_hold(true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn write_with(
         &self,
         path: &str,
         bs: impl Into<Buffer>,
     ) -> FutureWrite<impl Future<Output = Result<()>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
         let bs = bs.into();
 
         OperatorFuture::new(
@@ -1445,15 +1826,152 @@ impl Operator {
                     );
                 }
 
+                let args = match args.idempotency_key() {
+                    Some(key) => {
+                        let cap = inner.info().full_capability();
+                        if !(cap.write_with_user_metadata && cap.stat) {
+                            return Err(Error::new(
+                                ErrorKind::Unsupported,
+                                "write with idempotency key requires user metadata and stat",
+                            )
+                            .with_operation("Operator::write_with")
+                            .with_context("service", inner.info().scheme().into_static())
+                            .with_context("path", &path));
+                        }
+
+                        // The write has been done by a previous attempt if the
+                        // path carries the same key.
+                        match inner.stat(&path, OpStat::new()).await {
+                            Ok(rp) => {
+                                let written = rp
+                                    .into_metadata()
+                                    .user_metadata()
+                                    .and_then(|m| m.get(IDEMPOTENCY_KEY_METADATA))
+                                    .is_some_and(|v| v == key);
+                                if written {
+                                    return Ok(());
+                                }
+                            }
+                            Err(err) if err.kind() == ErrorKind::NotFound => {}
+                            Err(err) => return Err(err),
+                        }
+
+                        let mut metadata = args.user_metadata().cloned().unwrap_or_default();
+                        metadata.insert(IDEMPOTENCY_KEY_METADATA.to_string(), key.to_string());
+                        args.with_user_metadata(metadata)
+                    }
+                    None => args,
+                };
+
                 let context = WriteContext::new(inner, path, args, options);
-                let mut w = Writer::new(context).await?;
-                w.write(bs).await?;
-                w.close().await?;
-                Ok(())
+                context.write_all(bs).await.map(|_| ())
             },
         )
     }
 
+    /// Replace the content of path only if its etag is still `expected_etag`,
+    /// returns the etag of the new content.
+    ///
+    /// This is the primitive of optimistic concurrency control, like the
+    /// metadata pointer files of table formats: read the content and its
+    /// etag, compute the new content and swap it in. The swap fails with
+    /// [`ErrorKind::ConditionNotMatch`] if another writer has changed the
+    /// path in between, callers should read and try again.
+    ///
+    /// # Notes
+    ///
+    /// - Only services with [`Capability::write_with_if_match`] support
+    ///   swap, others will return [`ErrorKind::Unsupported`].
+    /// - The etag of the new content is taken from the response of the
+    ///   write, services that don't return it will return
+    ///   [`ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::ErrorKind;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let etag = op.stat("metadata/pointer").await?.etag().unwrap().to_string();
+    /// match op.swap("metadata/pointer", &etag, "v2.json").await {
+    ///     Ok(new_etag) => println!("committed as {new_etag}"),
+    ///     Err(err) if err.kind() == ErrorKind::ConditionNotMatch => println!("conflict, retry"),
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn swap(
+        &self,
+        path: &str,
+        expected_etag: &str,
+        bs: impl Into<Buffer>,
+    ) -> Result<String> {
+        let path = self.path_policy.normalize(path);
+        let info = self.info();
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::swap")
+                    .with_context("service", info.scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        if !info.full_capability().write_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support conditional write which is required by swap",
+            )
+            .with_operation("Operator::swap")
+            .with_context("service", info.scheme())
+            .with_context("path", &path));
+        }
+
+        let args = OpWrite::default()
+            .merge_executor(self.default_executor.clone())
+            .with_if_match(expected_etag);
+        let context = WriteContext::new(self.inner().clone(), path.clone(), args, OpWriter::new());
+        let meta = context.write_all(bs.into()).await?;
+
+        match meta.etag() {
+            Some(etag) => Ok(etag.to_string()),
+            None => Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't return etag which is required by swap",
+            )
+            .with_operation("Operator::swap")
+            .with_context("service", info.scheme())
+            .with_context("path", &path)),
+        }
+    }
+
+    /// Collect writes, deletes and copies into a [`Batch`] which executes
+    /// them with bounded concurrency and reports the result of every item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let report = op
+    ///     .batch()
+    ///     .write("a.txt", "hello")
+    ///     .delete("b.txt")
+    ///     .execute()
+    ///     .await;
+    /// println!("{} succeeded, {} failed", report.succeeded(), report.failed());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch(&self) -> Batch {
+        Batch::new(self.clone())
+    }
+
     /// Delete the given path.
     ///
     /// # Notes
@@ -1494,7 +2012,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FutureDelete<impl Future<Output = Result<()>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -1556,7 +2074,8 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
-        let input = input.map(|v| normalize_path(&v));
+        let policy = self.path_policy.clone();
+        let input = input.map(move |v| policy.normalize(&v));
 
         if self.info().full_capability().batch {
             let mut input = input
@@ -1635,7 +2154,12 @@ impl Operator {
                 let batches = batches
                     .map_err(|err| err.1)?
                     .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
+                    .map(|v| {
+                        (
+                            self.path_policy.encode(v.path()),
+                            OpDelete::default().into(),
+                        )
+                    })
                     .collect();
 
                 let results = self
@@ -1838,14 +2362,14 @@ impl Operator {
     /// # }
     /// ```
     pub fn list_with(&self, path: &str) -> FutureList<impl Future<Output = Result<Vec<Entry>>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpList::default(),
-            |inner, path, args| async move {
-                let lister = Lister::create(inner, &path, args).await?;
+            (OpList::default(), self.path_policy.clone()),
+            |inner, path, (args, policy)| async move {
+                let lister = Lister::create(inner, &path, args, policy).await?;
 
                 lister.try_collect().await
             },
@@ -1962,15 +2486,166 @@ impl Operator {
     /// # }
     /// ```
     pub fn lister_with(&self, path: &str) -> FutureLister<impl Future<Output = Result<Lister>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpList::default(),
-            |inner, path, args| async move { Lister::create(inner, &path, args).await },
+            (OpList::default(), self.path_policy.clone()),
+            |inner, path, (args, policy)| async move {
+                Lister::create(inner, &path, args, policy).await
+            },
         )
     }
+
+    /// List a page of at most `limit` entries under given `path`.
+    ///
+    /// Pass the [`ListPage::next_token`] of the returned page as `token` to
+    /// fetch the next page, `None` will be returned as the token of the
+    /// last page.
+    ///
+    /// # Notes
+    ///
+    /// The token is the path of the last entry in the page, which is passed
+    /// as `start_after` to fetch the next page instead of the native
+    /// continuation token of services. So this function requires
+    /// [`Capability::list_with_start_after`], [`ErrorKind::Unsupported`]
+    /// will be returned otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut token = None;
+    /// loop {
+    ///     let page = op.list_page("path/to/dir/", 100, token.as_deref(), false).await?;
+    ///     for entry in page.entries() {
+    ///         println!("{}", entry.path());
+    ///     }
+    ///     match page.next_token() {
+    ///         Some(v) => token = Some(v.to_string()),
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_page(
+        &self,
+        path: &str,
+        limit: usize,
+        token: Option<&str>,
+        recursive: bool,
+    ) -> Result<ListPage> {
+        ListPage::check(&self.info(), path)?;
+
+        let mut l = self.lister_with(path).limit(limit).recursive(recursive);
+        if let Some(token) = token {
+            l = l.start_after(token);
+        }
+        let entries = l.await?.take(limit).try_collect().await?;
+
+        Ok(ListPage::new(entries, limit))
+    }
+}
+
+/// Operator multipart upload API.
+impl Operator {
+    /// List in-progress multipart uploads under the given prefix.
+    ///
+    /// Multipart uploads that are neither completed nor aborted (for
+    /// example, the writer process crashed) are invisible to `list` but
+    /// still occupy storage.
+    ///
+    /// # Notes
+    ///
+    /// This API requires [`Capability::list_multipart_uploads`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let uploads = op.list_multipart_uploads("path/to/dir/").await?;
+    /// for upload in uploads {
+    ///     println!("{} {}", upload.path(), upload.upload_id());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_multipart_uploads(&self, prefix: &str) -> Result<Vec<MultipartUpload>> {
+        let prefix = self.path_policy.normalize(prefix);
+
+        let rp = self
+            .inner()
+            .list_multipart_uploads(&prefix, OpListMultipartUploads::new())
+            .await?;
+        Ok(rp.into_uploads())
+    }
+
+    /// Abort all in-progress multipart uploads that were initiated before `age`.
+    ///
+    /// Returns the uploads that have been aborted.
+    ///
+    /// # Notes
+    ///
+    /// This API requires [`Capability::list_multipart_uploads`] and
+    /// [`Capability::abort_multipart_upload`].
+    ///
+    /// Uploads without initiated time will be skipped since we can't
+    /// tell whether they are still in use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use std::time::Duration;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let aborted = op
+    ///     .abort_multipart_uploads_older_than(Duration::from_secs(7 * 24 * 3600))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn abort_multipart_uploads_older_than(
+        &self,
+        age: Duration,
+    ) -> Result<Vec<MultipartUpload>> {
+        let age = chrono::Duration::from_std(age).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "age is out of range")
+                .with_operation(Operation::AbortMultipartUpload)
+                .set_source(err)
+        })?;
+        let deadline = chrono::Utc::now() - age;
+
+        let uploads = self
+            .list_multipart_uploads("/")
+            .await?
+            .into_iter()
+            .filter(|v| v.initiated().map(|t| t < deadline).unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        stream::iter(uploads.iter().map(Ok))
+            .try_for_each_concurrent(self.limit, |upload| async move {
+                self.inner()
+                    .abort_multipart_upload(
+                        upload.path(),
+                        OpAbortMultipartUpload::new(upload.upload_id()),
+                    )
+                    .await?;
+                Ok::<(), Error>(())
+            })
+            .await?;
+
+        Ok(uploads)
+    }
 }
 
 /// Operator presign API.
@@ -1996,7 +2671,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         let op = OpPresign::new(OpStat::new(), expire);
 
@@ -2024,7 +2699,7 @@ impl Operator {
         path: &str,
         expire: Duration,
     ) -> FuturePresignStat<impl Future<Output = Result<PresignedRequest>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -2072,7 +2747,7 @@ impl Operator {
     /// curl "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -O /tmp/test.txt
     /// ```
     pub async fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         let op = OpPresign::new(OpRead::new(), expire);
 
@@ -2146,7 +2821,7 @@ impl Operator {
         path: &str,
         expire: Duration,
     ) -> FuturePresignRead<impl Future<Output = Result<PresignedRequest>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -2278,7 +2953,7 @@ impl Operator {
         path: &str,
         expire: Duration,
     ) -> FuturePresignWrite<impl Future<Output = Result<PresignedRequest>>> {
-        let path = normalize_path(path);
+        let path = self.path_policy.normalize(path);
 
         OperatorFuture::new(
             self.inner().clone(),
@@ -2292,3 +2967,36 @@ impl Operator {
         )
     }
 }
+
+/// Operator sign API.
+impl Operator {
+    /// Sign a user-built request with the credential of this service.
+    ///
+    /// This allows calling service APIs that OpenDAL doesn't wrap while
+    /// reusing the credential loading and refreshing of this operator. The
+    /// request must carry the full uri, OpenDAL won't rewrite it or send it.
+    ///
+    /// # Notes
+    ///
+    /// This API requires [`Capability::sign`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Buffer;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let req = http::Request::get("https://example.s3.amazonaws.com/?tagging")
+    ///         .body(Buffer::new())?;
+    ///     let signed_req = op.sign(req).await?;
+    ///     let req: http::Request<Buffer> = signed_req.into();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sign(&self, req: http::Request<Buffer>) -> Result<SignedRequest> {
+        let rp = self.inner().sign(OpSign::new(req)).await?;
+        Ok(rp.into_signed_request())
+    }
+}
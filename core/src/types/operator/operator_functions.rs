@@ -19,6 +19,7 @@
 //!
 //! By using functions, users can add more options for operation.
 
+use std::ops::Bound;
 use std::ops::RangeBounds;
 
 use crate::raw::*;
@@ -245,7 +246,7 @@ impl FunctionDelete {
 /// Function that generated by [`BlockingOperator::list_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub struct FunctionList(pub(crate) OperatorFunction<OpList, Vec<Entry>>);
+pub struct FunctionList(pub(crate) OperatorFunction<(OpList, PathPolicy), Vec<Entry>>);
 
 impl FunctionList {
     /// The limit passed to underlying service to specify the max results
@@ -253,14 +254,18 @@ impl FunctionList {
     ///
     /// Users could use this to control the memory usage of list operation.
     pub fn limit(mut self, v: usize) -> Self {
-        self.0 = self.0.map_args(|args| args.with_limit(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_limit(v), policy));
         self
     }
 
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_start_after(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_start_after(v), policy));
         self
     }
 
@@ -271,7 +276,34 @@ impl FunctionList {
     ///
     /// Default to `false`.
     pub fn recursive(mut self, v: bool) -> Self {
-        self.0 = self.0.map_args(|args| args.with_recursive(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_recursive(v), policy));
+        self
+    }
+
+    /// The cycle policy is used to control how recursive list handles dirs
+    /// that have been walked, for example via symlinks or shortcuts.
+    ///
+    /// Default to [`ListCyclePolicy::Skip`].
+    pub fn cycle_policy(mut self, v: ListCyclePolicy) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_cycle_policy(v), policy));
+        self
+    }
+
+    /// The error policy is used to control how list handles entries that
+    /// can't be parsed, like keys with invalid UTF-8 or unparsable timestamps.
+    ///
+    /// Use [`ListErrorPolicy::SkipAndReport`] to skip them while keeping
+    /// their errors in a [`ListErrorReport`].
+    ///
+    /// Default to [`ListErrorPolicy::Fail`].
+    pub fn on_error(mut self, v: ListErrorPolicy) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_error_policy(v), policy));
         self
     }
 
@@ -285,7 +317,7 @@ impl FunctionList {
 /// Function that generated by [`BlockingOperator::lister_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub struct FunctionLister(pub(crate) OperatorFunction<OpList, BlockingLister>);
+pub struct FunctionLister(pub(crate) OperatorFunction<(OpList, PathPolicy), BlockingLister>);
 
 impl FunctionLister {
     /// The limit passed to underlying service to specify the max results
@@ -293,14 +325,18 @@ impl FunctionLister {
     ///
     /// Users could use this to control the memory usage of list operation.
     pub fn limit(mut self, v: usize) -> Self {
-        self.0 = self.0.map_args(|args| args.with_limit(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_limit(v), policy));
         self
     }
 
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_start_after(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_start_after(v), policy));
         self
     }
 
@@ -311,7 +347,34 @@ impl FunctionLister {
     ///
     /// Default to `false`.
     pub fn recursive(mut self, v: bool) -> Self {
-        self.0 = self.0.map_args(|args| args.with_recursive(v));
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_recursive(v), policy));
+        self
+    }
+
+    /// The cycle policy is used to control how recursive list handles dirs
+    /// that have been walked, for example via symlinks or shortcuts.
+    ///
+    /// Default to [`ListCyclePolicy::Skip`].
+    pub fn cycle_policy(mut self, v: ListCyclePolicy) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_cycle_policy(v), policy));
+        self
+    }
+
+    /// The error policy is used to control how list handles entries that
+    /// can't be parsed, like keys with invalid UTF-8 or unparsable timestamps.
+    ///
+    /// Use [`ListErrorPolicy::SkipAndReport`] to skip them while keeping
+    /// their errors in a [`ListErrorReport`].
+    ///
+    /// Default to [`ListErrorPolicy::Fail`].
+    pub fn on_error(mut self, v: ListErrorPolicy) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, policy)| (args.with_error_policy(v), policy));
         self
     }
 
@@ -325,12 +388,19 @@ impl FunctionLister {
 /// Function that generated by [`BlockingOperator::read_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub struct FunctionRead(pub(crate) OperatorFunction<(OpRead, BytesRange), Buffer>);
+///
+/// The range bounds set by [`FunctionRead::range`] are kept as is and
+/// validated while reading.
+pub struct FunctionRead(pub(crate) OperatorFunction<(OpRead, (Bound<u64>, Bound<u64>)), Buffer>);
 
 impl FunctionRead {
     /// Set the range for this operation.
+    ///
+    /// The read will fail with [`ErrorKind::InvalidInput`] if the end of the
+    /// range is before the start.
     pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
-        self.0 = self.0.map_args(|(args, _)| (args, range.into()));
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        self.0 = self.0.map_args(|(args, _)| (args, range));
         self
     }
 
@@ -426,3 +496,34 @@ impl FunctionStat {
         self.0.call()
     }
 }
+
+/// Function that generated by [`BlockingOperator::exists_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FunctionExists(pub(crate) OperatorFunction<OpStat, bool>);
+
+impl FunctionExists {
+    /// Set the If-Match for this operation.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_match(v));
+        self
+    }
+
+    /// Set the If-None-Match for this operation.
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_none_match(v));
+        self
+    }
+
+    /// Set the version for this operation.
+    pub fn version(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_version(v));
+        self
+    }
+
+    /// Call the function to consume all the input and generate a
+    /// result.
+    pub fn call(self) -> Result<bool> {
+        self.0.call()
+    }
+}
@@ -21,9 +21,12 @@
 
 use std::collections::HashMap;
 use std::future::IntoFuture;
+use std::ops::Bound;
 use std::ops::RangeBounds;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use futures::Future;
 
 use crate::raw::*;
@@ -108,6 +111,35 @@ impl<F: Future<Output = Result<Metadata>>> FutureStat<F> {
     pub fn version(self, v: &str) -> Self {
         self.map(|args| args.with_version(v))
     }
+
+    /// Set the customer-provided key to access the object.
+    ///
+    /// This requires [`Capability::stat_with_customer_key`].
+    pub fn customer_key(self, key: CustomerEncryptionKey) -> Self {
+        self.map(|args| args.with_customer_key(key))
+    }
+}
+
+/// Future that generated by [`Operator::exists_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureExists<F> = OperatorFuture<OpStat, bool, F>;
+
+impl<F: Future<Output = Result<bool>>> FutureExists<F> {
+    /// Set the If-Match for this operation.
+    pub fn if_match(self, v: &str) -> Self {
+        self.map(|args| args.with_if_match(v))
+    }
+
+    /// Set the If-None-Match for this operation.
+    pub fn if_none_match(self, v: &str) -> Self {
+        self.map(|args| args.with_if_none_match(v))
+    }
+
+    /// Set the version for this operation.
+    pub fn version(self, v: &str) -> Self {
+        self.map(|args| args.with_version(v))
+    }
 }
 
 /// Future that generated by [`Operator::presign_stat_with`].
@@ -199,42 +231,123 @@ impl<F: Future<Output = Result<PresignedRequest>>> FuturePresignWrite<F> {
 /// Future that generated by [`Operator::read_with`] or [`Operator::reader_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub type FutureRead<F> = OperatorFuture<(OpRead, OpReader), Buffer, F>;
+///
+/// The range bounds set by [`FutureRead::range`] are kept as is and validated
+/// while reading.
+pub type FutureRead<F> = OperatorFuture<(OpRead, OpReader, (Bound<u64>, Bound<u64>)), Buffer, F>;
 
 impl<F: Future<Output = Result<Buffer>>> FutureRead<F> {
     /// Set the If-Match for this operation.
     pub fn if_match(self, v: &str) -> Self {
-        self.map(|(args, op_reader)| (args.with_if_match(v), op_reader))
+        self.map(|(args, op_reader, range)| (args.with_if_match(v), op_reader, range))
     }
 
     /// Set the If-None-Match for this operation.
     pub fn if_none_match(self, v: &str) -> Self {
-        self.map(|(args, op_reader)| (args.with_if_none_match(v), op_reader))
+        self.map(|(args, op_reader, range)| (args.with_if_none_match(v), op_reader, range))
     }
 
     /// Set the version for this operation.
     pub fn version(self, v: &str) -> Self {
-        self.map(|(args, op_reader)| (args.with_version(v), op_reader))
+        self.map(|(args, op_reader, range)| (args.with_version(v), op_reader, range))
     }
 
     /// Set the executor for this operation.
     pub fn executor(self, executor: Executor) -> Self {
-        self.map(|(args, op_reader)| (args.with_executor(executor), op_reader))
+        self.map(|(args, op_reader, range)| (args.with_executor(executor), op_reader, range))
     }
 
     /// Set the range header for this operation.
+    ///
+    /// The read will fail with [`ErrorKind::InvalidInput`] if the end of the
+    /// range is before the start.
     pub fn range(self, range: impl RangeBounds<u64>) -> Self {
-        self.map(|(args, op_reader)| (args.with_range(range.into()), op_reader))
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        self.map(|(args, op_reader, _)| (args, op_reader, range))
+    }
+
+    /// Resume an interrupted download from given offset.
+    ///
+    /// This is a shortcut of `range(offset..)`, usually used with
+    /// [`Self::expect_etag`] to make sure the object has not been changed
+    /// since the download started.
+    pub fn resume_from(self, offset: u64) -> Self {
+        self.range(offset..)
+    }
+
+    /// Expect the object to have given etag.
+    ///
+    /// The read will fail with [`ErrorKind::ConditionNotMatch`] if the object
+    /// has been changed, so that the resumed content won't be mixed with
+    /// the content of another version.
+    ///
+    /// # Notes
+    ///
+    /// This is a shortcut of `if_match(etag)`, which requires
+    /// [`Capability::read_with_if_match`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator, downloaded: u64, etag: &str) -> Result<()> {
+    /// let bs = op
+    ///     .read_with("path/to/file")
+    ///     .resume_from(downloaded)
+    ///     .expect_etag(etag)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expect_etag(self, etag: &str) -> Self {
+        self.if_match(etag)
+    }
+
+    /// Query the records of the object with SQL `expression` in place,
+    /// only the selected records will be returned.
+    ///
+    /// # Notes
+    ///
+    /// This requires [`Capability::read_with_select`], and can't be used
+    /// with `range` or `chunk`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::SelectInputFormat;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = op
+    ///     .read_with("data.csv")
+    ///     .select("SELECT s.name FROM S3Object s", SelectInputFormat::Csv)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select(self, expression: &str, format: SelectInputFormat) -> Self {
+        self.map(|(args, op_reader, range)| {
+            (args.with_select(expression, format), op_reader, range)
+        })
+    }
+
+    /// Set the customer-provided key to decrypt the object.
+    ///
+    /// This requires [`Capability::read_with_customer_key`].
+    pub fn customer_key(self, key: CustomerEncryptionKey) -> Self {
+        self.map(|(args, op_reader, range)| (args.with_customer_key(key), op_reader, range))
     }
 
     /// Set the concurrent read task amount.
     pub fn concurrent(self, concurrent: usize) -> Self {
-        self.map(|(args, op_reader)| (args, op_reader.with_concurrent(concurrent)))
+        self.map(|(args, op_reader, range)| (args, op_reader.with_concurrent(concurrent), range))
     }
 
     /// Set the chunk size for this operation.
     pub fn chunk(self, chunk_size: usize) -> Self {
-        self.map(|(args, op_reader)| (args, op_reader.with_chunk(chunk_size)))
+        self.map(|(args, op_reader, range)| (args, op_reader.with_chunk(chunk_size), range))
     }
 }
 
@@ -253,6 +366,21 @@ impl<F: Future<Output = Result<Reader>>> FutureReader<F> {
         self.map(|(op_read, op_reader)| (op_read.with_version(v), op_reader))
     }
 
+    /// Query the records of the object with SQL `expression` in place, the
+    /// reader will only return the selected records.
+    ///
+    /// See [`FutureRead::select`] for more details.
+    pub fn select(self, expression: &str, format: SelectInputFormat) -> Self {
+        self.map(|(op_read, op_reader)| (op_read.with_select(expression, format), op_reader))
+    }
+
+    /// Set the customer-provided key to decrypt the object.
+    ///
+    /// This requires [`Capability::read_with_customer_key`].
+    pub fn customer_key(self, key: CustomerEncryptionKey) -> Self {
+        self.map(|(op_read, op_reader)| (op_read.with_customer_key(key), op_reader))
+    }
+
     /// Set the concurrent read task amount.
     pub fn concurrent(self, concurrent: usize) -> Self {
         self.map(|(op_read, op_reader)| (op_read, op_reader.with_concurrent(concurrent)))
@@ -323,6 +451,14 @@ impl<F: Future<Output = Result<()>>> FutureWrite<F> {
         self.map(|(args, options, bs)| (args.with_executor(executor), options, bs))
     }
 
+    /// Set the If-Match for this operation.
+    ///
+    /// The write will fail with [`ErrorKind::ConditionNotMatch`] if the etag
+    /// of the object doesn't match, this requires [`Capability::write_with_if_match`].
+    pub fn if_match(self, s: &str) -> Self {
+        self.map(|(args, options, bs)| (args.with_if_match(s), options, bs))
+    }
+
     /// Set the If-None-Match for this operation.
     pub fn if_none_match(self, s: &str) -> Self {
         self.map(|(args, options, bs)| (args.with_if_none_match(s), options, bs))
@@ -333,6 +469,24 @@ impl<F: Future<Output = Result<()>>> FutureWrite<F> {
         self.map(|(args, options, bs)| (args.with_if_not_exists(b), options, bs))
     }
 
+    /// Set the idempotency key for this operation.
+    ///
+    /// Writes with the same idempotency key to the same path will only take
+    /// effect once, retried writes will succeed without writing the content
+    /// again. This is useful for at-least-once pipelines on services that
+    /// bill per write.
+    ///
+    /// The key is stored in user metadata of the written file and checked by
+    /// `stat` before writing, so it requires `write_with_user_metadata` and
+    /// `stat`. Writing to a path that exists without the same key will
+    /// overwrite it as usual.
+    ///
+    /// Only [`Operator::write_with`] supports idempotency key,
+    /// [`Operator::writer_with`] will return an `Unsupported` error.
+    pub fn idempotency_key(self, key: &str) -> Self {
+        self.map(|(args, options, bs)| (args.with_idempotency_key(key), options, bs))
+    }
+
     /// Set the user defined metadata of the op
     ///
     /// ## Notes
@@ -348,6 +502,39 @@ impl<F: Future<Output = Result<()>>> FutureWrite<F> {
             )
         })
     }
+
+    /// Set the custom time of the op
+    ///
+    /// Custom time is a user-specified timestamp attached to the object, which
+    /// is commonly used as the key of lifecycle rules.
+    pub fn custom_time(self, v: DateTime<Utc>) -> Self {
+        self.map(|(args, options, bs)| (args.with_custom_time(v), options, bs))
+    }
+
+    /// Set the object lock retention of the op, the object can't be
+    /// overwritten or deleted until `retain_until` according to `mode`.
+    ///
+    /// This requires [`Capability::write_with_object_lock`], and the object
+    /// lock must be enabled on the bucket.
+    pub fn object_lock(self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.map(|(args, options, bs)| (args.with_object_lock(mode, retain_until), options, bs))
+    }
+
+    /// Set whether the object is under legal hold, which prevents the object
+    /// from being overwritten or deleted until the hold is removed.
+    ///
+    /// This requires [`Capability::write_with_object_lock`].
+    pub fn legal_hold(self, v: bool) -> Self {
+        self.map(|(args, options, bs)| (args.with_legal_hold(v), options, bs))
+    }
+
+    /// Set the customer-provided key to encrypt the object.
+    ///
+    /// This requires [`Capability::write_with_customer_key`], the same key
+    /// must be provided to read the object later.
+    pub fn customer_key(self, key: CustomerEncryptionKey) -> Self {
+        self.map(|(args, options, bs)| (args.with_customer_key(key), options, bs))
+    }
 }
 
 /// Future that generated by [`Operator::writer_with`].
@@ -420,6 +607,38 @@ impl<F: Future<Output = Result<Writer>>> FutureWriter<F> {
     pub fn user_metadata(self, data: impl IntoIterator<Item = (String, String)>) -> Self {
         self.map(|(args, options)| (args.with_user_metadata(HashMap::from_iter(data)), options))
     }
+
+    /// Set the custom time of the op
+    ///
+    /// Custom time is a user-specified timestamp attached to the object, which
+    /// is commonly used as the key of lifecycle rules.
+    pub fn custom_time(self, v: DateTime<Utc>) -> Self {
+        self.map(|(args, options)| (args.with_custom_time(v), options))
+    }
+
+    /// Set the object lock retention of the op, the object can't be
+    /// overwritten or deleted until `retain_until` according to `mode`.
+    ///
+    /// This requires [`Capability::write_with_object_lock`], and the object
+    /// lock must be enabled on the bucket.
+    pub fn object_lock(self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.map(|(args, options)| (args.with_object_lock(mode, retain_until), options))
+    }
+
+    /// Set whether the object is under legal hold, which prevents the object
+    /// from being overwritten or deleted until the hold is removed.
+    ///
+    /// This requires [`Capability::write_with_object_lock`].
+    pub fn legal_hold(self, v: bool) -> Self {
+        self.map(|(args, options)| (args.with_legal_hold(v), options))
+    }
+
+    /// Set the customer-provided key to encrypt the object.
+    ///
+    /// See [`FutureWrite::customer_key`] for more details.
+    pub fn customer_key(self, key: CustomerEncryptionKey) -> Self {
+        self.map(|(args, options)| (args.with_customer_key(key), options))
+    }
 }
 
 /// Future that generated by [`Operator::delete_with`].
@@ -437,7 +656,7 @@ impl<F: Future<Output = Result<()>>> FutureDelete<F> {
 /// Future that generated by [`Operator::list_with`] or [`Operator::lister_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub type FutureList<F> = OperatorFuture<OpList, Vec<Entry>, F>;
+pub type FutureList<F> = OperatorFuture<(OpList, PathPolicy), Vec<Entry>, F>;
 
 impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
     /// The limit passed to underlying service to specify the max results
@@ -445,13 +664,13 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
     ///
     /// Users could use this to control the memory usage of list operation.
     pub fn limit(self, v: usize) -> Self {
-        self.map(|args| args.with_limit(v))
+        self.map(|(args, policy)| (args.with_limit(v), policy))
     }
 
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(self, v: &str) -> Self {
-        self.map(|args| args.with_start_after(v))
+        self.map(|(args, policy)| (args.with_start_after(v), policy))
     }
 
     /// The recursive is used to control whether the list operation is recursive.
@@ -461,7 +680,7 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
     ///
     /// Default to `false`.
     pub fn recursive(self, v: bool) -> Self {
-        self.map(|args| args.with_recursive(v))
+        self.map(|(args, policy)| (args.with_recursive(v), policy))
     }
 
     /// The version is used to control whether the object versions should be returned.
@@ -470,16 +689,52 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
     /// - If `true`, list operation will return with object versions if object versioning is supported
     ///   by the underlying service
     ///
+    /// Delete markers are returned as well if [`Capability::list_has_delete_marker`] is supported,
+    /// check them via [`Metadata::is_delete_marker`].
+    ///
     /// Default to `false`
     pub fn version(self, v: bool) -> Self {
-        self.map(|args| args.with_version(v))
+        self.map(|(args, policy)| (args.with_version(v), policy))
+    }
+
+    /// The cycle policy is used to control how recursive list handles dirs
+    /// that have been walked, for example via symlinks or shortcuts.
+    ///
+    /// Default to [`ListCyclePolicy::Skip`].
+    pub fn cycle_policy(self, v: ListCyclePolicy) -> Self {
+        self.map(|(args, policy)| (args.with_cycle_policy(v), policy))
+    }
+
+    /// Filter entries on the service side with a service specific `query`,
+    /// only the matched entries will be returned.
+    ///
+    /// For gdrive, `query` is a [search query](https://developers.google.com/drive/api/guides/search-files)
+    /// like `name contains 'report' and modifiedTime > '2024-01-01T00:00:00'`,
+    /// which is combined with the parent filter of the listed dir.
+    ///
+    /// # Notes
+    ///
+    /// This requires [`Capability::list_with_query`].
+    pub fn query(self, v: &str) -> Self {
+        self.map(|(args, policy)| (args.with_query(v), policy))
+    }
+
+    /// The error policy is used to control how list handles entries that
+    /// can't be parsed, like keys with invalid UTF-8 or unparsable timestamps.
+    ///
+    /// Use [`ListErrorPolicy::SkipAndReport`] to skip them while keeping
+    /// their errors in a [`ListErrorReport`].
+    ///
+    /// Default to [`ListErrorPolicy::Fail`].
+    pub fn on_error(self, v: ListErrorPolicy) -> Self {
+        self.map(|(args, policy)| (args.with_error_policy(v), policy))
     }
 }
 
 /// Future that generated by [`Operator::list_with`] or [`Operator::lister_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub type FutureLister<F> = OperatorFuture<OpList, Lister, F>;
+pub type FutureLister<F> = OperatorFuture<(OpList, PathPolicy), Lister, F>;
 
 impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
     /// The limit passed to underlying service to specify the max results
@@ -487,13 +742,13 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
     ///
     /// Users could use this to control the memory usage of list operation.
     pub fn limit(self, v: usize) -> Self {
-        self.map(|args| args.with_limit(v))
+        self.map(|(args, policy)| (args.with_limit(v), policy))
     }
 
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     pub fn start_after(self, v: &str) -> Self {
-        self.map(|args| args.with_start_after(v))
+        self.map(|(args, policy)| (args.with_start_after(v), policy))
     }
 
     /// The recursive is used to control whether the list operation is recursive.
@@ -503,7 +758,7 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
     ///
     /// Default to `false`.
     pub fn recursive(self, v: bool) -> Self {
-        self.map(|args| args.with_recursive(v))
+        self.map(|(args, policy)| (args.with_recursive(v), policy))
     }
 
     /// The version is used to control whether the object versions should be returned.
@@ -512,8 +767,44 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
     /// - If `true`, list operation will return with object versions if object versioning is supported
     ///   by the underlying service
     ///
+    /// Delete markers are returned as well if [`Capability::list_has_delete_marker`] is supported,
+    /// check them via [`Metadata::is_delete_marker`].
+    ///
     /// Default to `false`
     pub fn version(self, v: bool) -> Self {
-        self.map(|args| args.with_version(v))
+        self.map(|(args, policy)| (args.with_version(v), policy))
+    }
+
+    /// The cycle policy is used to control how recursive list handles dirs
+    /// that have been walked, for example via symlinks or shortcuts.
+    ///
+    /// Default to [`ListCyclePolicy::Skip`].
+    pub fn cycle_policy(self, v: ListCyclePolicy) -> Self {
+        self.map(|(args, policy)| (args.with_cycle_policy(v), policy))
+    }
+
+    /// Filter entries on the service side with a service specific `query`,
+    /// only the matched entries will be returned.
+    ///
+    /// For gdrive, `query` is a [search query](https://developers.google.com/drive/api/guides/search-files)
+    /// like `name contains 'report' and modifiedTime > '2024-01-01T00:00:00'`,
+    /// which is combined with the parent filter of the listed dir.
+    ///
+    /// # Notes
+    ///
+    /// This requires [`Capability::list_with_query`].
+    pub fn query(self, v: &str) -> Self {
+        self.map(|(args, policy)| (args.with_query(v), policy))
+    }
+
+    /// The error policy is used to control how list handles entries that
+    /// can't be parsed, like keys with invalid UTF-8 or unparsable timestamps.
+    ///
+    /// Use [`ListErrorPolicy::SkipAndReport`] to skip them while keeping
+    /// their errors in a [`ListErrorReport`].
+    ///
+    /// Default to [`ListErrorPolicy::Fail`].
+    pub fn on_error(self, v: ListErrorPolicy) -> Self {
+        self.map(|(args, policy)| (args.with_error_policy(v), policy))
     }
 }
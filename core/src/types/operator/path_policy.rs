@@ -0,0 +1,254 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+#[cfg(feature = "path-unicode-normalization")]
+use unicode_normalization::UnicodeNormalization as _;
+
+use crate::raw::*;
+
+/// Unicode normalization form applied to paths.
+///
+/// Requires the `path-unicode-normalization` feature.
+#[cfg(feature = "path-unicode-normalization")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnicodeNormalization {
+    /// Normalization Form C, canonical composition.
+    ///
+    /// Most object storage services and Linux file systems expect this form.
+    Nfc,
+    /// Normalization Form D, canonical decomposition.
+    ///
+    /// HFS+ on macOS stores file names in (a variant of) this form.
+    Nfd,
+}
+
+/// PathPolicy controls how an [`Operator`][crate::Operator] normalizes the
+/// input paths before sending them to services.
+///
+/// Different services handle edge-case names differently, for example, `a\b`
+/// is a file named `a\b` on S3 but a file `b` under dir `a` on Windows. Setting
+/// the same policy on operators of both sides makes round-tripping file names
+/// between them predictable.
+///
+/// The default policy keeps the existing behavior of OpenDAL:
+///
+/// - Leading and trailing whitespace will be trimmed.
+/// - `\` is treated as a normal character.
+/// - No unicode normalization or percent-encoding is applied.
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::PathPolicy;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .finish()
+///     .with_path_policy(
+///         PathPolicy::new()
+///             .with_backslash_as_separator(true)
+///             .with_percent_encode(true),
+///     );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathPolicy {
+    #[cfg(feature = "path-unicode-normalization")]
+    unicode_normalization: Option<UnicodeNormalization>,
+    backslash_as_separator: bool,
+    keep_whitespace: bool,
+    percent_encode: bool,
+}
+
+impl PathPolicy {
+    /// Create a new `PathPolicy` with default behavior.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Normalize all paths into given unicode normalization form.
+    ///
+    /// Requires the `path-unicode-normalization` feature.
+    #[cfg(feature = "path-unicode-normalization")]
+    pub fn with_unicode_normalization(mut self, form: UnicodeNormalization) -> Self {
+        self.unicode_normalization = Some(form);
+        self
+    }
+
+    /// Treat `\` in paths as the separator `/`.
+    pub fn with_backslash_as_separator(mut self, enabled: bool) -> Self {
+        self.backslash_as_separator = enabled;
+        self
+    }
+
+    /// Keep the leading and trailing whitespace in paths instead of trimming.
+    pub fn with_keep_whitespace(mut self, enabled: bool) -> Self {
+        self.keep_whitespace = enabled;
+        self
+    }
+
+    /// Percent-encode characters that are unsafe in object keys.
+    ///
+    /// Control characters and ``\ { } ^ % ` [ ] " < > ~ # |`` will be encoded
+    /// while sending to services, and entries returned by `list` will be
+    /// decoded back.
+    ///
+    /// # Notes
+    ///
+    /// Existing keys that already contain `%` will be decoded while listing.
+    pub fn with_percent_encode(mut self, enabled: bool) -> Self {
+        self.percent_encode = enabled;
+        self
+    }
+
+    /// Normalize the input path into the path sent to services.
+    pub(crate) fn normalize(&self, path: &str) -> String {
+        let mut path = if self.backslash_as_separator {
+            Cow::Owned(path.replace('\\', "/"))
+        } else {
+            Cow::Borrowed(path)
+        };
+
+        #[cfg(feature = "path-unicode-normalization")]
+        match self.unicode_normalization {
+            Some(UnicodeNormalization::Nfc) => path = Cow::Owned(path.nfc().collect()),
+            Some(UnicodeNormalization::Nfd) => path = Cow::Owned(path.nfd().collect()),
+            None => {}
+        }
+
+        let path = if self.keep_whitespace {
+            normalize_path_keep_whitespace(&path)
+        } else {
+            normalize_path(&path)
+        };
+
+        if self.percent_encode {
+            encode_unsafe_chars(&path)
+        } else {
+            path
+        }
+    }
+
+    /// Encode the normalized path into the path sent to services.
+    pub(crate) fn encode(&self, path: &str) -> String {
+        if self.percent_encode {
+            encode_unsafe_chars(path)
+        } else {
+            path.to_string()
+        }
+    }
+
+    /// Restore the entry returned by services into the entry seen by users.
+    pub(crate) fn restore(&self, mut entry: oio::Entry) -> oio::Entry {
+        if self.percent_encode {
+            let path = percent_decode_path(entry.path());
+            entry.set_path(&path);
+        }
+        entry
+    }
+}
+
+fn encode_unsafe_chars(path: &str) -> String {
+    let mut s = String::with_capacity(path.len());
+    for c in path.chars() {
+        let unsafe_char = c.is_control()
+            || matches!(
+                c,
+                '\\' | '{' | '}' | '^' | '%' | '`' | '[' | ']' | '"' | '<' | '>' | '~' | '#' | '|'
+            );
+        if !unsafe_char {
+            s.push(c);
+            continue;
+        }
+        let mut buf = [0; 4];
+        for b in c.encode_utf8(&mut buf).bytes() {
+            write!(s, "%{b:02X}").expect("write into string must succeed");
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_normalize() {
+        #[allow(unused_mut)]
+        let mut cases = vec![
+            ("default", PathPolicy::new(), " a\\b ", "a\\b"),
+            (
+                "backslash",
+                PathPolicy::new().with_backslash_as_separator(true),
+                "a\\\\b\\",
+                "a/b/",
+            ),
+            (
+                "keep whitespace",
+                PathPolicy::new().with_keep_whitespace(true),
+                "/a /b ",
+                "a /b ",
+            ),
+            (
+                "percent encode",
+                PathPolicy::new().with_percent_encode(true),
+                "a/100%#1\u{7}/caf\u{e9}",
+                "a/100%25%231%07/caf\u{e9}",
+            ),
+        ];
+
+        #[cfg(feature = "path-unicode-normalization")]
+        cases.extend([
+            (
+                "nfc",
+                PathPolicy::new().with_unicode_normalization(UnicodeNormalization::Nfc),
+                "cafe\u{301}",
+                "caf\u{e9}",
+            ),
+            (
+                "nfd",
+                PathPolicy::new().with_unicode_normalization(UnicodeNormalization::Nfd),
+                "caf\u{e9}",
+                "cafe\u{301}",
+            ),
+        ]);
+
+        for (name, policy, input, expect) in cases {
+            assert_eq!(policy.normalize(input), expect, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_restore() {
+        let policy = PathPolicy::new().with_percent_encode(true);
+        let path = "a/100%#1/b";
+        let entry = oio::Entry::new(&policy.normalize(path), Metadata::new(EntryMode::FILE));
+        assert_eq!(policy.restore(entry).path(), path);
+
+        let entry = oio::Entry::new("a/100%25", Metadata::new(EntryMode::FILE));
+        assert_eq!(PathPolicy::new().restore(entry).path(), "a/100%25");
+    }
+}
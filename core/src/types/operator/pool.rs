@@ -0,0 +1,313 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use futures::future::join_all;
+use log::warn;
+
+use crate::*;
+
+/// The key of a cached operator: the scheme and the sorted config.
+type PoolKey = (Scheme, Vec<(String, String)>);
+
+struct PoolEntry {
+    op: Operator,
+    checked_at: DateTime<Utc>,
+    /// Held while checking so that only one check runs for an entry.
+    checking: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl PoolEntry {
+    fn new(op: Operator) -> Self {
+        Self {
+            op,
+            checked_at: Utc::now(),
+            checking: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+/// OperatorPool caches operators built from configs and rebuilds the
+/// broken ones.
+///
+/// Operators are keyed by their scheme and config, so the same config
+/// always gets the same operator (and shares its http client, credential
+/// and caches) instead of building a new one for every request.
+///
+/// # Health Check
+///
+/// Operators are checked by [`Operator::check`] when they are fetched from
+/// the pool and haven't been checked for [`OperatorPool::with_check_interval`].
+/// Operators that fail the check will be evicted and rebuilt. Only one check
+/// runs for an operator at the same time, concurrent fetches will wait for
+/// its result instead of checking again.
+///
+/// OpenDAL doesn't spawn background tasks, users can call
+/// [`OperatorPool::check`] periodically in their runtime to evict broken
+/// operators ahead of time.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::OperatorPool;
+/// use opendal::Scheme;
+///
+/// # async fn test() -> Result<()> {
+/// let pool = OperatorPool::new();
+///
+/// let config = [("root".to_string(), "/tmp/tenant-a".to_string())];
+/// let op = pool.get(Scheme::Fs, config.clone()).await?;
+/// // The cached operator will be returned.
+/// let op = pool.get(Scheme::Fs, config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OperatorPool {
+    check_interval: Duration,
+    entries: Mutex<HashMap<PoolKey, PoolEntry>>,
+}
+
+impl Default for OperatorPool {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(60),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl OperatorPool {
+    /// Create a new `OperatorPool`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the interval between health checks of a cached operator.
+    ///
+    /// Default to 60 seconds.
+    pub fn with_check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// Get the operator for given scheme and config, build it if not cached.
+    pub async fn get(
+        &self,
+        scheme: Scheme,
+        iter: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Operator> {
+        let key = Self::key(scheme, iter);
+
+        let checking = {
+            let entries = self.entries.lock().expect("lock must succeed");
+            match entries.get(&key) {
+                Some(entry) if !self.is_stale(entry.checked_at) => return Ok(entry.op.clone()),
+                Some(entry) => Some(entry.checking.clone()),
+                None => None,
+            }
+        };
+
+        if let Some(checking) = checking {
+            let _guard = checking.lock().await;
+
+            // The entry could be checked or evicted by others while waiting.
+            let cached = {
+                let entries = self.entries.lock().expect("lock must succeed");
+                entries
+                    .get(&key)
+                    .map(|entry| (entry.op.clone(), entry.checked_at))
+            };
+            if let Some((op, checked_at)) = cached {
+                if !self.is_stale(checked_at) {
+                    return Ok(op);
+                }
+                if self.check_entry(&key, &op, &checking).await {
+                    return Ok(op);
+                }
+            }
+        }
+
+        let op = Operator::via_iter(scheme, key.1.clone())?;
+
+        let mut entries = self.entries.lock().expect("lock must succeed");
+        // Keep the operator built by others in the meantime.
+        let entry = entries.entry(key).or_insert_with(|| PoolEntry::new(op));
+        Ok(entry.op.clone())
+    }
+
+    /// Check all cached operators and evict the ones that failed.
+    ///
+    /// Returns the number of evicted operators.
+    pub async fn check(&self) -> usize {
+        let cached: Vec<_> = {
+            let entries = self.entries.lock().expect("lock must succeed");
+            entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.op.clone(), entry.checking.clone()))
+                .collect()
+        };
+
+        let results = join_all(cached.into_iter().map(|(key, op, checking)| async move {
+            let _guard = checking.lock().await;
+            self.check_entry(&key, &op, &checking).await
+        }))
+        .await;
+
+        results.into_iter().filter(|ok| !ok).count()
+    }
+
+    /// Check the operator of given entry, update its check time if passed
+    /// or evict it if failed.
+    ///
+    /// The entry is identified by its `checking` lock, so that an entry
+    /// rebuilt by others in the meantime won't be touched.
+    ///
+    /// Returns `true` if the check passed.
+    async fn check_entry(
+        &self,
+        key: &PoolKey,
+        op: &Operator,
+        checking: &Arc<tokio::sync::Mutex<()>>,
+    ) -> bool {
+        let res = op.check().await;
+
+        let mut entries = self.entries.lock().expect("lock must succeed");
+        let entry = entries
+            .get_mut(key)
+            .filter(|entry| Arc::ptr_eq(&entry.checking, checking));
+        match res {
+            Ok(()) => {
+                if let Some(entry) = entry {
+                    entry.checked_at = Utc::now();
+                }
+                true
+            }
+            Err(err) => {
+                warn!(
+                    "operator pool evicts {} operator which failed check: {err}",
+                    key.0
+                );
+                if entry.is_some() {
+                    entries.remove(key);
+                }
+                false
+            }
+        }
+    }
+
+    /// Remove the operator for given scheme and config from the pool.
+    pub fn remove(&self, scheme: Scheme, iter: impl IntoIterator<Item = (String, String)>) {
+        let key = Self::key(scheme, iter);
+        self.entries.lock().expect("lock must succeed").remove(&key);
+    }
+
+    /// Remove all operators from the pool.
+    pub fn clear(&self) {
+        self.entries.lock().expect("lock must succeed").clear();
+    }
+
+    /// Return the number of cached operators.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("lock must succeed").len()
+    }
+
+    /// Check if there is no cached operator.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn key(scheme: Scheme, iter: impl IntoIterator<Item = (String, String)>) -> PoolKey {
+        let mut config: Vec<_> = iter.into_iter().collect();
+        config.sort_unstable();
+        (scheme, config)
+    }
+
+    fn is_stale(&self, checked_at: DateTime<Utc>) -> bool {
+        match (Utc::now() - checked_at).to_std() {
+            Ok(elapsed) => elapsed >= self.check_interval,
+            // Clock went backwards, check it again.
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_operator_pool() -> Result<()> {
+        let pool = OperatorPool::new();
+        let config = || {
+            [
+                ("root".to_string(), "/a".to_string()),
+                ("foo".to_string(), "bar".to_string()),
+            ]
+        };
+
+        let op = pool.get(Scheme::Memory, config()).await?;
+        op.write("test", "hello").await?;
+
+        // Config in different order shares the same operator.
+        let mut reversed = config();
+        reversed.reverse();
+        let op = pool.get(Scheme::Memory, reversed).await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"hello");
+        assert_eq!(pool.len(), 1);
+
+        let op = pool
+            .get(Scheme::Memory, [("root".to_string(), "/b".to_string())])
+            .await?;
+        assert!(!op.exists("test").await?);
+        assert_eq!(pool.len(), 2);
+
+        assert_eq!(pool.check().await, 0);
+        assert_eq!(pool.len(), 2);
+
+        pool.remove(Scheme::Memory, config());
+        assert_eq!(pool.len(), 1);
+        let op = pool.get(Scheme::Memory, config()).await?;
+        assert!(!op.exists("test").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operator_pool_check_stale() -> Result<()> {
+        let pool = OperatorPool::new().with_check_interval(Duration::ZERO);
+        let config = || [("root".to_string(), "/a".to_string())];
+
+        let op = pool.get(Scheme::Memory, config()).await?;
+        op.write("test", "hello").await?;
+
+        // Concurrent fetches of a stale operator keep the same operator.
+        let ops = join_all((0..4).map(|_| pool.get(Scheme::Memory, config()))).await;
+        for op in ops {
+            assert_eq!(op?.read("test").await?.to_vec(), b"hello");
+        }
+        assert_eq!(pool.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::Stream;
+
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// BodyStream is the [`Stream`] of the content returned by
+/// [`Operator::read_with_metadata`].
+///
+/// Unlike [`FuturesBytesStream`], BodyStream streams the body of the single
+/// read request sent by `read_with_metadata` instead of sending new requests.
+///
+/// BodyStream also implements [`Unpin`], [`Send`] and [`Sync`].
+pub struct BodyStream {
+    state: State,
+    buf: Buffer,
+}
+
+enum State {
+    Idle(Option<oio::Reader>),
+    Reading(BoxedStaticFuture<(oio::Reader, Result<Buffer>)>),
+    Done,
+}
+
+/// Safety: BodyStream only exposes `&mut self` to the outside world,
+unsafe impl Sync for BodyStream {}
+
+impl BodyStream {
+    /// NOTE: don't allow users to create BodyStream directly.
+    pub(crate) fn new(r: oio::Reader) -> Self {
+        BodyStream {
+            state: State::Idle(Some(r)),
+            buf: Buffer::new(),
+        }
+    }
+}
+
+impl Stream for BodyStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Consume current buffer
+            if let Some(bs) = Iterator::next(&mut this.buf) {
+                return Poll::Ready(Some(Ok(bs)));
+            }
+
+            match &mut this.state {
+                State::Idle(r) => {
+                    let mut r = r.take().expect("reader must be valid");
+                    let fut = async move {
+                        let ret = r.read().await;
+                        (r, ret)
+                    };
+                    this.state = State::Reading(Box::pin(fut));
+                }
+                State::Reading(fut) => {
+                    let (r, ret) = ready!(fut.as_mut().poll(cx));
+                    match ret {
+                        Ok(buf) if buf.is_empty() => {
+                            this.state = State::Done;
+                            return Poll::Ready(None);
+                        }
+                        Ok(buf) => {
+                            this.state = State::Idle(Some(r));
+                            this.buf = buf;
+                        }
+                        Err(err) => {
+                            this.state = State::Done;
+                            return Poll::Ready(Some(Err(format_std_io_error(err))));
+                        }
+                    }
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_with_metadata() -> Result<()> {
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        op.write(
+            "test",
+            Buffer::from(vec![Bytes::from("Hello"), Bytes::from("World")]),
+        )
+        .await?;
+
+        let (meta, s) = op.read_with_metadata("test").await?;
+        assert_eq!(meta.content_length(), 10);
+
+        let bs: Vec<Bytes> = s.try_collect().await.unwrap();
+        assert_eq!(bs.concat(), b"HelloWorld");
+
+        Ok(())
+    }
+}
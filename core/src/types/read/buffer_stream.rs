@@ -89,7 +89,7 @@ impl ChunkedReader {
     fn new(ctx: Arc<ReadContext>, range: BytesRange) -> Self {
         let tasks = ConcurrentTasks::new(
             ctx.args().executor().cloned().unwrap_or_default(),
-            ctx.options().concurrent(),
+            ctx.concurrent(),
             |mut r: oio::Reader| {
                 Box::pin(async {
                     match r.read_all().await {
@@ -171,7 +171,9 @@ impl BufferStream {
             let range = ctx.parse_into_range(range).await?;
             TwoWays::Two(ChunkedReader::new(ctx, range.into()))
         } else {
-            TwoWays::One(StreamingReader::new(ctx, range.into()))
+            let range = BytesRange::try_from_bounds(range)
+                .map_err(|err| err.with_operation("read").with_context("path", ctx.path()))?;
+            TwoWays::One(StreamingReader::new(ctx, range))
         };
 
         Ok(Self {
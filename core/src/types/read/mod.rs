@@ -27,3 +27,9 @@ pub use futures_async_reader::FuturesAsyncReader;
 
 mod futures_bytes_stream;
 pub use futures_bytes_stream::FuturesBytesStream;
+
+mod body_stream;
+pub use body_stream::BodyStream;
+
+mod select;
+pub use select::SelectInputFormat;
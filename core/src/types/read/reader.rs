@@ -149,7 +149,7 @@ impl Reader {
 
         let merged_bufs: Vec<_> =
             stream::iter(merged_ranges.clone().into_iter().map(|v| self.read(v)))
-                .buffered(self.ctx.options().concurrent())
+                .buffered(self.ctx.concurrent())
                 .try_collect()
                 .await?;
 
@@ -451,6 +451,31 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[allow(clippy::reversed_empty_ranges)]
+    async fn test_read_invalid_range() -> Result<()> {
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        let path = "test_file";
+
+        op.write(path, "abcd").await.expect("write must succeed");
+
+        let err = op
+            .read_with(path)
+            .range(3..1)
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let reader = op.reader_with(path).chunk(2).await.unwrap();
+        let err = reader.read(3..1).await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let reader = op.reader(path).await.unwrap();
+        let err = reader.read(3..1).await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merge_ranges() -> Result<()> {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
@@ -42,6 +42,8 @@ pub enum Scheme {
     Azdls,
     /// [B2][crate::services::B2]: Backblaze B2 Services.
     B2,
+    /// [box][crate::services::Box]: Box services.
+    Box,
     /// [Compfs][crate::services::Compfs]: Compio fs Services.
     Compfs,
     /// [Seafile][crate::services::Seafile]: Seafile Services.
@@ -54,6 +56,8 @@ pub enum Scheme {
     YandexDisk,
     /// [Pcloud][crate::services::Pcloud]: Pcloud Services.
     Pcloud,
+    /// [ProtonDrive][crate::services::ProtonDrive]: Proton Drive Services.
+    ProtonDrive,
     /// [Koofr][crate::services::Koofr]: Koofr Services.
     Koofr,
     /// [Chainsafe][crate::services::Chainsafe]: Chainsafe Services.
@@ -213,6 +217,8 @@ impl Scheme {
             Scheme::Azfile,
             #[cfg(feature = "services-b2")]
             Scheme::B2,
+            #[cfg(feature = "services-box")]
+            Scheme::Box,
             #[cfg(feature = "services-cacache")]
             Scheme::Cacache,
             #[cfg(feature = "services-cos")]
@@ -287,6 +293,8 @@ impl Scheme {
             Scheme::YandexDisk,
             #[cfg(feature = "services-pcloud")]
             Scheme::Pcloud,
+            #[cfg(feature = "services-proton-drive")]
+            Scheme::ProtonDrive,
             #[cfg(feature = "services-sftp")]
             Scheme::Sftp,
             #[cfg(feature = "services-sled")]
@@ -351,6 +359,7 @@ impl FromStr for Scheme {
             // And abfs is widely used in hadoop ecosystem, keep it for easy to use.
             "azdls" | "azdfs" | "abfs" => Ok(Scheme::Azdls),
             "b2" => Ok(Scheme::B2),
+            "box" => Ok(Scheme::Box),
             "chainsafe" => Ok(Scheme::Chainsafe),
             "cacache" => Ok(Scheme::Cacache),
             "compfs" => Ok(Scheme::Compfs),
@@ -395,6 +404,7 @@ impl FromStr for Scheme {
             "upyun" => Ok(Scheme::Upyun),
             "yandex_disk" => Ok(Scheme::YandexDisk),
             "pcloud" => Ok(Scheme::Pcloud),
+            "proton_drive" => Ok(Scheme::ProtonDrive),
             "sftp" => Ok(Scheme::Sftp),
             "sled" => Ok(Scheme::Sled),
             "supabase" => Ok(Scheme::Supabase),
@@ -424,6 +434,7 @@ impl From<Scheme> for &'static str {
             Scheme::Azblob => "azblob",
             Scheme::Azdls => "azdls",
             Scheme::B2 => "b2",
+            Scheme::Box => "box",
             Scheme::Chainsafe => "chainsafe",
             Scheme::Cacache => "cacache",
             Scheme::CloudflareKv => "cloudflare_kv",
@@ -482,6 +493,7 @@ impl From<Scheme> for &'static str {
             Scheme::Upyun => "upyun",
             Scheme::YandexDisk => "yandex_disk",
             Scheme::Pcloud => "pcloud",
+            Scheme::ProtonDrive => "proton_drive",
             Scheme::HdfsNative => "hdfs_native",
             Scheme::Surrealdb => "surrealdb",
             Scheme::Lakefs => "lakefs",
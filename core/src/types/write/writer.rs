@@ -176,7 +176,8 @@ impl Writer {
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
     pub async fn close(&mut self) -> Result<()> {
-        self.inner.close().await
+        self.inner.close().await?;
+        Ok(())
     }
 
     /// Convert writer into [`FuturesAsyncWriter`] which implements [`futures::AsyncWrite`],
@@ -34,7 +34,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_copy_target_dir,
             test_copy_self,
             test_copy_nested,
-            test_copy_overwrite
+            test_copy_overwrite,
+            test_copy_many
         ))
     }
 }
@@ -229,3 +230,44 @@ pub async fn test_copy_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy many files and check per-pair results.
+pub async fn test_copy_many(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let missing_path = uuid::Uuid::new_v4().to_string();
+
+    let results = op
+        .copy_many(vec![
+            (source_path.clone(), target_path.clone()),
+            (missing_path.clone(), uuid::Uuid::new_v4().to_string()),
+            (source_path.clone(), source_path.clone()),
+        ])
+        .await?;
+    assert_eq!(results.len(), 3);
+
+    assert!(results[0].is_ok(), "copy must succeed");
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    assert_eq!(results[1].as_ref().unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(
+        results[2].as_ref().unwrap_err().kind(),
+        ErrorKind::IsSameFile
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -31,14 +31,58 @@ use crate::*;
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
 
-    if cap.read && cap.write && cap.presign {
+    if !(cap.read && cap.write && cap.presign) {
+        return;
+    }
+
+    if cap.presign_write {
+        tests.extend(async_trials!(op, test_presign_write));
+    }
+    if cap.presign_write && cap.write_with_content_type {
+        tests.extend(async_trials!(op, test_presign_write_with_content_type));
+    }
+    if cap.presign_read {
         tests.extend(async_trials!(
             op,
-            test_presign_write,
             test_presign_read,
-            test_presign_stat
-        ))
+            test_presign_read_range
+        ));
+    }
+    if cap.presign_read && cap.read_with_override_content_disposition {
+        tests.extend(async_trials!(
+            op,
+            test_presign_read_with_override_content_disposition
+        ));
+    }
+    if cap.presign_stat {
+        tests.extend(async_trials!(op, test_presign_stat));
+    }
+}
+
+/// Send the presigned request via a plain http client with all signed
+/// headers, `headers` are sent as extra headers.
+async fn send_presigned_request(
+    signed_req: raw::PresignedRequest,
+    headers: &[(header::HeaderName, String)],
+    body: Option<Vec<u8>>,
+) -> reqwest::Response {
+    let client = reqwest::Client::new();
+    let mut req = client.request(
+        signed_req.method().clone(),
+        Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+    );
+    for (k, v) in signed_req.header() {
+        req = req.header(k, v);
+    }
+    for (k, v) in headers {
+        req = req.header(k, v);
     }
+    if let Some(body) = body {
+        req = req.header(header::CONTENT_LENGTH, body.len());
+        req = req.body(reqwest::Body::from(body));
+    }
+
+    req.send().await.expect("send request must succeed")
 }
 
 /// Presign write should succeed.
@@ -62,13 +106,52 @@ pub async fn test_presign_write(op: Operator) -> Result<()> {
     req = req.body(reqwest::Body::from(content));
 
     let resp = req.send().await.expect("send request must succeed");
+    let status = resp.status();
     debug!(
         "write response: {:?}",
         resp.text().await.expect("read response must succeed")
     );
+    assert!(status.is_success(), "presign write status: {status}");
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Presign write with content type should keep the content type, the signed
+/// content type must be sent by clients as is.
+pub async fn test_presign_write_with_content_type(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes(op.info().full_capability());
+    let content_type = "application/opendal-presign";
+
+    let signed_req = op
+        .presign_write_with(&path, Duration::from_secs(3600))
+        .content_type(content_type)
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+    assert_eq!(
+        signed_req
+            .header()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok()),
+        Some(content_type),
+        "content type must be signed"
+    );
+
+    let resp = send_presigned_request(signed_req, &[], Some(content)).await;
+    assert!(
+        resp.status().is_success(),
+        "presign write status: {}",
+        resp.status()
+    );
 
     let meta = op.stat(&path).await.expect("stat must succeed");
     assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(meta.content_type(), Some(content_type));
 
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
@@ -138,3 +221,69 @@ pub async fn test_presign_read(op: Operator) -> Result<()> {
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Presign read should work with range headers added by clients.
+pub async fn test_presign_read_range(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes_with_range(1024..4096);
+    let (offset, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let signed_req = op.presign_read(&path, Duration::from_secs(3600)).await?;
+    debug!("Generated request: {signed_req:?}");
+
+    let range = format!("bytes={}-{}", offset, offset + length - 1);
+    let resp = send_presigned_request(signed_req, &[(header::RANGE, range)], None).await;
+    assert_eq!(resp.status(), http::StatusCode::PARTIAL_CONTENT);
+
+    let bs = resp.bytes().await.expect("read response must succeed");
+    assert_eq!(length as usize, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!(
+            "{:x}",
+            Sha256::digest(&content[offset as usize..(offset + length) as usize])
+        ),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Presign read should return the overridden content disposition.
+pub async fn test_presign_read_with_override_content_disposition(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes(op.info().full_capability());
+    let target_content_disposition = "attachment; filename=\"presigned.txt\"";
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let signed_req = op
+        .presign_read_with(&path, Duration::from_secs(3600))
+        .override_content_disposition(target_content_disposition)
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+
+    let resp = send_presigned_request(signed_req, &[], None).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert_eq!(
+        resp.headers()
+            .get(header::CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok()),
+        Some(target_content_disposition)
+    );
+
+    let bs = resp.bytes().await.expect("read response must succeed");
+    assert_eq!(size, bs.len(), "read size");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -39,6 +39,7 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_reader,
             test_read_not_exist,
             test_read_with_if_match,
+            test_read_with_resume_from_expect_etag,
             test_read_with_if_none_match,
             test_read_with_dir_path,
             test_read_with_special_chars,
@@ -203,6 +204,41 @@ pub async fn test_read_with_if_match(op: Operator) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Resume read with etag should return the rest content, else get a ConditionNotMatch error.
+pub async fn test_read_with_resume_from_expect_etag(op: Operator) -> anyhow::Result<()> {
+    if !op.info().full_capability().read_with_if_match {
+        return Ok(());
+    }
+
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+    let offset = (size / 2) as u64;
+
+    let res = op
+        .read_with(&path)
+        .resume_from(offset)
+        .expect_etag("\"invalid_etag\"")
+        .await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    let bs = op
+        .read_with(&path)
+        .resume_from(offset)
+        .expect_etag(meta.etag().expect("etag must exist"))
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(bs, content[offset as usize..]);
+
+    Ok(())
+}
+
 /// Read with if_none_match should match, else get a ConditionNotMatch error.
 pub async fn test_read_with_if_none_match(op: Operator) -> anyhow::Result<()> {
     if !op.info().full_capability().read_with_if_none_match {
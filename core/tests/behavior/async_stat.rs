@@ -36,7 +36,9 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_stat_with_special_chars,
             test_stat_not_cleaned_path,
             test_stat_not_exist,
+            test_stat_or_none,
             test_stat_with_if_match,
+            test_exists_with_if_match,
             test_stat_with_if_none_match,
             test_stat_with_override_cache_control,
             test_stat_with_override_content_disposition,
@@ -211,6 +213,45 @@ pub async fn test_stat_with_if_match(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat or none should return None for not exist file.
+pub async fn test_stat_or_none(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    assert!(op.stat_or_none(&path).await?.is_none());
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let meta = op.stat_or_none(&path).await?.expect("meta must exist");
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+
+    Ok(())
+}
+
+/// Exists with if_match should return false if etag is not matched.
+pub async fn test_exists_with_if_match(op: Operator) -> Result<()> {
+    if !op.info().full_capability().stat_with_if_match {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+
+    assert!(!op.exists_with(&path).if_match("\"invalid_etag\"").await?);
+    assert!(
+        op.exists_with(&path)
+            .if_match(meta.etag().expect("etag must exist"))
+            .await?
+    );
+
+    Ok(())
+}
+
 /// Stat with if_none_match should succeed, else get a ConditionNotMatch.
 pub async fn test_stat_with_if_none_match(op: Operator) -> Result<()> {
     if !op.info().full_capability().stat_with_if_none_match {
@@ -46,6 +46,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_write_with_content_disposition,
             test_write_with_if_none_match,
             test_write_with_if_not_exists,
+            test_write_swap,
+            test_write_with_idempotency_key,
             test_write_with_user_metadata,
             test_writer_write,
             test_writer_write_with_overwrite,
@@ -651,6 +653,64 @@ pub async fn test_write_with_if_none_match(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Retried writes with the same idempotency key should not write again, while
+/// writes with a different key should overwrite as usual.
+pub async fn test_write_with_idempotency_key(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.write_with_user_metadata {
+        return Ok(());
+    }
+
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write_with(&path, content.clone())
+        .idempotency_key("key-1")
+        .await?;
+    op.write_with(&path, "retried")
+        .idempotency_key("key-1")
+        .await
+        .expect("retried write with the same key must succeed");
+
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.write_with(&path, "overwritten")
+        .idempotency_key("key-2")
+        .await
+        .expect("write with another key must succeed");
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.to_vec(), b"overwritten");
+
+    Ok(())
+}
+
+/// Swap will only replace the content if the etag is not changed.
+pub async fn test_write_swap(op: Operator) -> Result<()> {
+    if !op.info().full_capability().write_with_if_match {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone()).await?;
+    let Some(etag) = op.stat(&path).await?.etag().map(|v| v.to_string()) else {
+        return Ok(());
+    };
+
+    let new_etag = op.swap(&path, &etag, "swapped").await?;
+    assert_eq!(op.read(&path).await?.to_vec(), b"swapped");
+
+    // The old etag is outdated now.
+    let res = op.swap(&path, &etag, "conflict").await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+    assert_eq!(op.read(&path).await?.to_vec(), b"swapped");
+
+    op.swap(&path, &new_etag, "swapped again").await?;
+    assert_eq!(op.read(&path).await?.to_vec(), b"swapped again");
+
+    Ok(())
+}
+
 /// Write an file with if_not_exists will get a ConditionNotMatch error if file exists.
 pub async fn test_write_with_if_not_exists(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_with_if_not_exists {